@@ -1,17 +1,50 @@
+//! The viewer as a whole: [`App`] routes keys and mouse events, loads files and draws every
+//! widget.
+
 use crate::{
-    command_mode::{CommandModeStateAccess, CommandModeWidget},
-    commands, config,
-    constants::{self, WAVEFORM_HEIGHT},
+    autopilot::Autopilot,
+    command_aliases,
+    command_mode::{
+        parser::quote_args, state::previous_word_start, CommandModeStateAccess, CommandModeWidget,
+    },
+    commands,
+    config::{self, WatchMode},
+    constants, file_browser,
+    file_watcher::FileWatcher,
     fuzzy_finder::FuzzyFinderStateAccess,
-    parsers,
+    initial_view::InitialView,
+    parsers::{
+        types::{parse_time_flag, TimeWindow, WaveformData},
+        vcd::VcdParser,
+    },
+    session::Session,
+    signal_groups,
     state::AppState,
-    types::AppMode,
+    types::{AppMode, DragKind, FileInfo},
     ui::{
-        layout::{create_layout, AppLayout},
+        layout::{create_layout, minimum_size, AppLayout, PaneLayout},
         widgets::{
-            bottom_text_box::BottomTextBoxWidget, fuzzy_finder::FuzzyFinderWidget,
-            help_menu::HelpMenuWidget, marker_names::MarkerNamesWidget,
-            signal_list::SignalListWidget, time_ruler::TimeRulerWidget, waveform::WaveformWidget,
+            bottom_text_box::BottomTextBoxWidget,
+            command_list::CommandListWidget,
+            file_browser::FileBrowserWidget,
+            file_info::FileInfoWidget,
+            fuzzy_finder::FuzzyFinderWidget,
+            grep::GrepWidget,
+            help_menu::HelpMenuWidget,
+            marker_list::MarkerListWidget,
+            marker_names::MarkerNamesWidget,
+            messages::MessagesWidget,
+            minimap::MinimapWidget,
+            parse_warnings::ParseWarningsWidget,
+            sample::SampleWidget,
+            signal_list::SignalListWidget,
+            stats::StatsWidget,
+            time_ruler::{calculate_tick_interval, TimeRulerWidget},
+            too_small::TooSmallWidget,
+            trace::TraceWidget,
+            transitions::TransitionsWidget,
+            view_list::ViewListWidget,
+            waveform::WaveformWidget,
         },
     },
 };
@@ -24,49 +57,99 @@ use ratatui::{
     widgets::{Paragraph, Widget},
     DefaultTerminal,
 };
-use std::io;
-use std::{error::Error, path::Path, time::Duration};
+use std::io::{self, BufRead};
+use std::{
+    error::Error,
+    fs, mem,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 pub struct App {
     pub state: AppState,
     pub layout: AppLayout,
-    pub signal_list: SignalListWidget,
-    pub help_menu: HelpMenuWidget,
+    /// The area the app was last rendered into, used to lay out again before the next frame
+    pub area: Rect,
+    pub marker_list: MarkerListWidget,
+    pub view_list: ViewListWidget,
+    pub messages: MessagesWidget,
     pub waveform: WaveformWidget,
     pub marker_names: MarkerNamesWidget,
     pub time_ruler: TimeRulerWidget,
+    pub minimap: MinimapWidget,
+    pub transitions: TransitionsWidget,
+    pub grep: GrepWidget,
+    pub sample: SampleWidget,
+    pub parse_warnings: ParseWarningsWidget,
+    pub file_info: FileInfoWidget,
+    pub stats: StatsWidget,
+    pub trace: TraceWidget,
     pub command_input: BottomTextBoxWidget,
     pub command_mode: CommandModeWidget<AppState>,
     pub fuzzy_finder: FuzzyFinderWidget,
+    pub file_browser: FileBrowserWidget,
+    /// Where the list of recently opened files is saved. Not saved if None.
+    recent_files_path: Option<PathBuf>,
+    /// Part of the next file loaded to keep, from `--from` and `--to`
+    load_window: TimeWindow,
+    /// Part of the loaded file that was kept, kept again when it is reloaded
+    file_window: TimeWindow,
+    /// Notices the loaded file changing on disk. None without a file or with `ui.watch` off.
+    file_watcher: Option<FileWatcher>,
+    /// Exits and runs commands from the command line on its own, for unattended recordings
+    autopilot: Autopilot,
+    /// The first key of a view bookmark chord like `m1`, waiting for the slot's digit
+    pending_chord: Option<ViewChord>,
+}
+
+// What a view bookmark chord does with the slot of the digit pressed after its first key
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum ViewChord {
+    Save,
+    Load,
 }
 
 impl Default for App {
     fn default() -> Self {
-        let config = config::AppConfig::default();
+        Self::with_config(config::AppConfig::default())
+    }
+}
+
+impl App {
+    /// An app with nothing loaded and every built-in command registered.
+    pub fn with_config(config: config::AppConfig) -> Self {
         let mut app = App {
-            state: AppState::new(),
+            state: AppState::with_config(config),
             layout: AppLayout::default(),
-            signal_list: SignalListWidget::default(),
-            help_menu: HelpMenuWidget::default(),
+            area: Rect::default(),
+            marker_list: MarkerListWidget::default(),
+            view_list: ViewListWidget::default(),
+            messages: MessagesWidget::default(),
             waveform: WaveformWidget::default(),
             marker_names: MarkerNamesWidget::default(),
             time_ruler: TimeRulerWidget::default(),
+            minimap: MinimapWidget::default(),
+            transitions: TransitionsWidget::default(),
+            grep: GrepWidget::default(),
+            sample: SampleWidget::default(),
+            parse_warnings: ParseWarningsWidget::default(),
+            file_info: FileInfoWidget::default(),
+            stats: StatsWidget::default(),
+            trace: TraceWidget::default(),
             command_input: BottomTextBoxWidget::default(),
             command_mode: CommandModeWidget::new(),
             fuzzy_finder: FuzzyFinderWidget::default(),
+            file_browser: FileBrowserWidget::default(),
+            recent_files_path: None,
+            load_window: TimeWindow::default(),
+            file_window: TimeWindow::default(),
+            file_watcher: None,
+            autopilot: Autopilot::default(),
+            pending_chord: None,
         };
-        app.state.config = config;
         app.register_commands();
         app
     }
-}
-
-impl App {
-    pub fn with_config(config: config::AppConfig) -> Self {
-        let mut app = Self::default();
-        app.state.config = config;
-        app
-    }
 
     pub fn load_config(&mut self, path_override: Option<String>) -> Result<(), String> {
         match config::load_config(path_override) {
@@ -78,60 +161,451 @@ impl App {
         }
     }
 
+    /// Draws the app and handles events until it's told to exit.
     pub fn run(mut self, mut terminal: DefaultTerminal) -> Result<(), Box<dyn Error>> {
+        self.run_loop(&mut terminal, |app, timeout| {
+            if !event::poll(timeout)? {
+                return Ok(false);
+            }
+            // Handle every event that is already waiting before drawing again, so that keys typed
+            // in quick succession, like a fuzzy finder query, are redrawn once
+            loop {
+                match event::read()? {
+                    Event::Key(key) => app.handle_key(key),
+                    Event::Mouse(mouse) => app.handle_mouse(mouse),
+                    Event::Resize(width, height) => app.handle_resize(width, height),
+                    _ => {}
+                }
+                if app.state.exit || !event::poll(Duration::ZERO)? {
+                    return Ok(true);
+                }
+            }
+        })
+    }
+
+    // The event loop of `run`, drawing to any backend. `handle_events` waits at most the given time
+    // for events and handles them, returning whether there were any.
+    fn run_loop<B: Backend>(
+        &mut self,
+        terminal: &mut Terminal<B>,
+        mut handle_events: impl FnMut(&mut Self, Duration) -> io::Result<bool>,
+    ) -> Result<(), Box<dyn Error>> {
         let tick_rate = Duration::from_millis(250);
+        let mut next_tick = Instant::now() + tick_rate;
+        self.autopilot.start(Instant::now());
 
         while !self.state.exit {
-            terminal.draw(|frame| frame.render_widget(&mut self, frame.area()))?;
+            terminal.draw(|frame| frame.render_widget(&mut *self, frame.area()))?;
+            if self.autopilot.frame_drawn(Instant::now()) {
+                break;
+            }
+            self.run_queued_commands(Instant::now())
+                .map_err(|err| format!("Error running command {}", err))?;
+            if self.state.exit {
+                break;
+            }
 
-            if event::poll(tick_rate)? {
-                match event::read()? {
-                    Event::Key(key) => {
-                        if self.state.show_help {
-                            match key.code {
-                                KeyCode::Esc => {
-                                    self.state.show_help = false;
-                                    self.state.help_menu_scroll = 0;
-                                }
-                                KeyCode::Up => {
-                                    if self.state.help_menu_scroll > 0 {
-                                        self.state.help_menu_scroll -= 1;
-                                    }
-                                }
-                                KeyCode::Down => {
-                                    self.state.help_menu_scroll += 1;
-                                }
-                                _ => {}
-                            }
-                        } else if self.state.mode == AppMode::Command {
-                            self.handle_command_input(key);
-                        } else if self.state.mode == AppMode::FuzzyFinder {
-                            self.handle_fuzzy_finder_input(key);
-                        } else {
-                            self.handle_input(key);
-                        }
-                    }
-                    Event::Mouse(mouse) => self.handle_mouse(mouse),
-                    _ => {}
+            // Ticks come every `tick_rate` even while events keep coming, like mouse moves
+            let now = Instant::now();
+            let timeout = self
+                .autopilot
+                .timeout(now, next_tick.saturating_duration_since(now));
+            handle_events(self, timeout)?;
+            let now = Instant::now();
+            if now >= next_tick {
+                self.expire_result_message();
+                self.follow_file();
+                self.watch_file(now);
+                next_tick = now + tick_rate;
+            }
+        }
+        Ok(())
+    }
+
+    /// Makes the event loop exit on its own after `exit_after`, or once `frames` frames were drawn.
+    pub fn set_exit_limits(&mut self, exit_after: Option<Duration>, frames: Option<u64>) {
+        self.autopilot.set_limits(exit_after, frames);
+    }
+
+    /// Runs commands given on the command line from the event loop instead of before it, with a
+    /// frame drawn after each one, so recordings show every step.
+    pub fn queue_startup_commands(&mut self, commands: Vec<String>) {
+        self.autopilot.queue(commands, true);
+    }
+
+    // Runs the commands queued from the command line that are due: one per frame if each gets a
+    // frame, otherwise all of them up to a `:sleep`
+    fn run_queued_commands(&mut self, now: Instant) -> Result<(), String> {
+        while let Some(command) = self.autopilot.next_command(now) {
+            self.run_command(&command)
+                .map_err(|err| format!("'{}': {}", command, err))?;
+            if self.autopilot.frame_per_command() || self.state.exit {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Routes a key press to whatever currently has focus.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        // Errors stay visible until the next key press
+        self.state.command_state_mut().dismiss_errors();
+
+        if self.state.show_help {
+            match key.code {
+                KeyCode::Esc => {
+                    self.state.show_help = false;
+                    self.state.help_menu_scroll = 0;
                 }
-            } else {
-                // Check if command result should be hidden
-                if let Some(time) = self.state.command_state().command_result_time {
-                    if time.elapsed().as_secs() >= constants::COMMAND_RESULT_HIDE_THRESHOLD_SECONDS
-                    {
-                        self.state.command_state_mut().result_message = None;
-                        self.state.command_state_mut().command_result_time = None;
-                    }
+                KeyCode::Up if self.state.help_menu_scroll > 0 => {
+                    self.state.help_menu_scroll -= 1;
                 }
+                KeyCode::Down => {
+                    self.state.help_menu_scroll += 1;
+                }
+                _ => {}
             }
+        } else if self.state.show_marker_list {
+            self.handle_marker_list_input(key);
+        } else if self.state.show_view_list {
+            self.handle_view_list_input(key);
+        } else if self.state.show_command_list {
+            self.handle_command_list_input(key);
+        } else if self.state.show_stats {
+            self.handle_stats_input(key);
+        } else if self.state.show_trace {
+            self.handle_trace_input(key);
+        } else if self.state.show_messages {
+            self.handle_messages_input(key);
+        } else if self.state.show_parse_warnings {
+            self.handle_parse_warnings_input(key);
+        } else if self.state.show_file_info {
+            self.handle_file_info_input(key);
+        } else if self.state.transitions_popup.is_some() {
+            self.handle_transitions_input(key);
+        } else if self.state.grep_popup.is_some() {
+            self.handle_grep_input(key);
+        } else if self.state.sample_popup.is_some() {
+            self.handle_sample_input(key);
+        } else if self.state.mode == AppMode::Command {
+            self.handle_command_input(key);
+        } else if self.state.mode == AppMode::Filter {
+            self.handle_filter_input(key);
+        } else if self.state.mode == AppMode::FuzzyFinder {
+            self.handle_fuzzy_finder_input(key);
+        } else if self.state.mode == AppMode::FileBrowser {
+            self.handle_file_browser_input(key);
+        } else {
+            self.handle_input(key);
         }
-        Ok(())
+    }
+
+    /// Reads the changes appended to the loaded file while following it. Following stops if the
+    /// file can't be read or its signal definitions change.
+    pub fn follow_file(&mut self) {
+        if !self.state.following {
+            return;
+        }
+        let (Some(path), Some(parser)) = (&self.state.file_path, &mut self.state.vcd_parser) else {
+            return;
+        };
+
+        match parser.parse_appended(path) {
+            Ok(data) => {
+                self.state.parse_report = parser.report().clone();
+                let appended: usize = data.values.values().map(Vec::len).sum();
+                if let Some(info) = &mut self.state.file_info {
+                    info.change_count += appended;
+                    info.size = fs::metadata(path).ok().map(|metadata| metadata.len());
+                }
+                self.state.append_waveform_data(data);
+            }
+            Err(e) => {
+                self.state.following = false;
+                self.show_result(format!("Stopped following: {}", e), true);
+            }
+        }
+    }
+
+    /// Reloads the loaded file or offers to once it has changed on disk, depending on `ui.watch`.
+    /// Changes are ignored while following the file, which reads them already.
+    pub fn watch_file(&mut self, now: Instant) {
+        if self.state.config.ui.watch == WatchMode::Off {
+            self.file_watcher = None;
+            return;
+        }
+        if self.file_watcher.is_none() {
+            self.start_watching();
+        }
+        let Some(watcher) = &mut self.file_watcher else {
+            return;
+        };
+        if !watcher.poll(now) || self.state.following {
+            return;
+        }
+
+        match self.state.config.ui.watch {
+            WatchMode::Auto => self.reload_and_report(),
+            _ => self.show_result(
+                "File changed on disk — :reload to refresh, :watch auto to always reload"
+                    .to_string(),
+                false,
+            ),
+        }
+    }
+
+    // Watches the loaded file, if there is one and watching isn't off. Failing to watch it
+    // shouldn't stop it from being viewed.
+    fn start_watching(&mut self) {
+        self.file_watcher = match &self.state.file_path {
+            Some(path) if self.state.config.ui.watch != WatchMode::Off => {
+                FileWatcher::new(path).ok()
+            }
+            _ => None,
+        };
+    }
+
+    /// Hides each successful command result once it has been shown for a while.
+    pub fn expire_result_message(&mut self) {
+        self.state
+            .command_state_mut()
+            .expire_results(Instant::now());
+    }
+
+    // The lines of the command results shown above the command bar, and whether each is of an
+    // error. The most recent result is wrapped to the width of the screen, and the ones stacked
+    // above it are cut to a line each.
+    fn result_message_lines(&self) -> Vec<(String, bool)> {
+        let results = self.state.command_state().shown_results();
+        let mut lines = Vec::new();
+        for (i, result) in results.iter().enumerate() {
+            let max_lines = if i + 1 == results.len() {
+                constants::MAX_COMMAND_RESULT_LINES
+            } else {
+                1
+            };
+            let wrapped = wrap_message(&result.message, self.area.width as usize, max_lines);
+            lines.extend(wrapped.into_iter().map(|line| (line, result.is_error)));
+        }
+        lines
+    }
+
+    // Recomputes the layout for the current screen area and command result
+    fn relayout(&mut self) {
+        let result_lines = self.result_message_lines().len() as u16;
+        self.layout = create_layout(
+            self.area,
+            &self.state.config,
+            result_lines,
+            self.state.split_view.as_ref(),
+        );
     }
 
     fn register_commands(&mut self) {
         commands::register_all_commands(&mut self.command_mode);
     }
 
+    /// Runs a command as if it had been typed in command mode and shows its result in the command
+    /// result message. The error is also returned for callers that need to act on it.
+    pub fn run_command(&mut self, input: &str) -> Result<(), String> {
+        let result = self.command_mode.parser().execute(input, &mut self.state);
+        match result {
+            Ok(msg) => {
+                self.show_result(msg, false);
+                self.handle_requests();
+                Ok(())
+            }
+            Err(err) => {
+                self.show_result(err.clone(), true);
+                Err(err)
+            }
+        }
+    }
+
+    /// Shows a message in the command result area, as if a command had returned it.
+    pub fn show_result(&mut self, message: String, is_error: bool) {
+        self.state
+            .command_state_mut()
+            .push_result(message, is_error);
+    }
+
+    /// Runs commands given on the command line in order, stopping at the first one that fails.
+    /// Commands that change the mode (e.g. findsignal) leave the app in that mode, so the event
+    /// loop starts there. Commands after a `:sleep` are left for the event loop.
+    pub fn run_startup_commands(&mut self, commands: &[String]) -> Result<(), String> {
+        for (i, command) in commands.iter().enumerate() {
+            self.run_command(command)
+                .map_err(|err| format!("'{}': {}", command, err))?;
+            // The rest run from the event loop once a `:sleep` is over, so the UI shows the wait
+            if self.autopilot.sleeping(Instant::now()) {
+                self.autopilot.queue(commands[i + 1..].to_vec(), false);
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Opens the loaded waveform at `start`..`end` showing only the signals matching the
+    /// comma-separated globs in `signals`, in declaration order. Times are ticks or take a unit
+    /// like "1.2us". A window outside the waveform is clamped, returning a warning for each
+    /// adjustment, and times that can't be parsed are an error.
+    pub fn apply_view_args(
+        &mut self,
+        start: Option<&str>,
+        end: Option<&str>,
+        signals: Option<&str>,
+    ) -> Result<Vec<String>, String> {
+        let mut warnings = Vec::new();
+        let max_time = self.state.waveform_data.max_time;
+        let timescale_fs = self.state.waveform_data.timescale_fs;
+        let start = parse_time_flag("start", start, timescale_fs)?;
+        let end = parse_time_flag("end", end, timescale_fs)?;
+
+        if start.is_some() || end.is_some() {
+            let mut start = start.unwrap_or(0);
+            let mut end = end.unwrap_or(max_time);
+            if start > max_time {
+                warnings.push(format!(
+                    "--start {} is past the end of the waveform at {}, starting at 0",
+                    start, max_time
+                ));
+                start = 0;
+            }
+            if end > max_time {
+                warnings.push(format!(
+                    "--end {} is past the end of the waveform, ending at {}",
+                    end, max_time
+                ));
+                end = max_time;
+            }
+            if end <= start {
+                warnings.push(format!(
+                    "--end {} isn't after --start {}, ending at {}",
+                    end, start, max_time
+                ));
+                end = max_time;
+            }
+            self.state.set_window(start, end - start);
+        }
+
+        if let Some(signals) = signals {
+            let mut patterns = Vec::new();
+            for pattern in signals.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+                patterns.push(
+                    glob::Pattern::new(pattern)
+                        .map_err(|e| format!("--signals: invalid pattern '{}': {}", pattern, e))?,
+                );
+            }
+            let matching: Vec<String> = self
+                .state
+                .waveform_data
+                .signals
+                .iter()
+                .filter(|signal| patterns.iter().any(|pattern| pattern.matches(signal)))
+                .cloned()
+                .collect();
+            if matching.is_empty() {
+                warnings.push(format!("--signals {} matches no signals", signals));
+            }
+            self.state.fuzzy_finder_state_mut().selected_signals =
+                matching.iter().cloned().collect();
+            self.state.displayed_signals = matching;
+            self.state.selected_signal = 0;
+            self.state.signal_scroll_offset = 0;
+        }
+
+        // This is how the file was asked to be opened, so there's nothing to save yet
+        self.state.mark_session_saved();
+        Ok(warnings)
+    }
+
+    /// Keeps only the value changes from `from` to `to` of the next file loaded, for files too big
+    /// to load whole. Times are ticks or take a unit like "1.2us".
+    pub fn set_load_window(&mut self, from: Option<&str>, to: Option<&str>) {
+        self.load_window = TimeWindow {
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+        };
+    }
+
+    /// Sets where recently opened files are remembered, and reads the ones remembered so far.
+    pub fn set_recent_files_path(&mut self, path: Option<PathBuf>) {
+        self.state.file_browser.recent_files = path
+            .as_deref()
+            .map(file_browser::load_recent_files)
+            .unwrap_or_default();
+        self.recent_files_path = path;
+    }
+
+    /// Sets where signal groups are saved, and reads the ones saved so far.
+    pub fn set_signal_groups_path(&mut self, path: Option<PathBuf>) {
+        self.state.signal_groups = path
+            .as_deref()
+            .map(signal_groups::load_signal_groups)
+            .unwrap_or_default();
+        self.state.signal_groups_path = path;
+    }
+
+    /// Sets where command aliases are saved, and reads the ones saved so far.
+    pub fn set_command_aliases_path(&mut self, path: Option<PathBuf>) {
+        self.state.command_aliases = path
+            .as_deref()
+            .map(command_aliases::load_command_aliases)
+            .unwrap_or_default();
+        self.state.command_aliases_path = path;
+    }
+
+    /// Shows the file picker listing the waveform files in `dir` and the recently opened files.
+    pub fn open_file_browser(&mut self, dir: &Path) {
+        self.state.file_browser.refresh(dir);
+        self.state.mode = AppMode::FileBrowser;
+    }
+
+    pub fn handle_file_browser_input(&mut self, key: KeyEvent) {
+        let browser = &mut self.state.file_browser;
+
+        // Typing a path after '/'
+        if let Some(input) = &mut browser.path_input {
+            match key.code {
+                KeyCode::Esc => browser.path_input = None,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(c) => input.push(c),
+                KeyCode::Enter => {
+                    let path = PathBuf::from(input.as_str());
+                    self.load_from_file_browser(&path);
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        match key.code {
+            KeyCode::Esc => self.state.mode = AppMode::Normal,
+            KeyCode::Up => browser.select_previous(),
+            KeyCode::Down => browser.select_next(),
+            KeyCode::Char('/') => browser.path_input = Some(String::new()),
+            KeyCode::Enter => {
+                if let Some(path) = browser.selected_path() {
+                    self.load_from_file_browser(&path);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    // Loads a file picked in the file browser, staying in the browser if it can't be loaded
+    fn load_from_file_browser(&mut self, path: &Path) {
+        match self.load_file(path) {
+            Ok(()) => {
+                self.state.file_browser.path_input = None;
+                self.state.mode = AppMode::Normal;
+            }
+            Err(e) => self.show_result(format!("Error loading {}: {}", path.display(), e), true),
+        }
+    }
+
     fn handle_fuzzy_finder_input(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Esc => {
@@ -139,24 +613,10 @@ impl App {
                 self.state.mode = AppMode::Normal;
             }
             KeyCode::Enter => {
-                // Get selected signals
-                let selected_signals = self.state.fuzzy_finder_state().get_selected_signals();
-
-                // Maintain original signal order from waveform_data
-                let mut displayed_signals = Vec::new();
-                for signal in &self.state.waveform_data.signals {
-                    if selected_signals.contains(signal) {
-                        displayed_signals.push(signal.clone());
-                    }
-                }
-
-                // Set the displayed signals in the original order
-                self.state.displayed_signals = displayed_signals;
-
-                // Ensure selected signal is within bounds
-                if self.state.selected_signal >= self.state.displayed_signals.len() {
-                    self.state.selected_signal = 0;
-                }
+                // Keep the order the user arranged, adding new signals at the end
+                let selected_signals = self.state.fuzzy_finder_state().selected_signals.clone();
+                self.state.apply_signal_selection(&selected_signals);
+                self.adjust_scroll_if_needed();
                 self.state.mode = AppMode::Normal;
             }
             KeyCode::Char(' ') => {
@@ -173,6 +633,13 @@ impl App {
                         // Clear all selections
                         self.state.fuzzy_finder_state_mut().clear_selection();
                     }
+                    's' | 'S' => {
+                        // Switch between sorting by match score and by activity
+                        let change_counts = self.state.change_counts();
+                        self.state
+                            .fuzzy_finder_state_mut()
+                            .toggle_sort_by_activity(change_counts);
+                    }
                     _ => {}
                 }
             }
@@ -184,91 +651,626 @@ impl App {
         }
     }
 
-    pub fn handle_command_input(&mut self, key: KeyEvent) {
-        match key {
-            k if k.code == self.state.config.keybindings.enter_normal_mode => {
-                self.state.mode = AppMode::Normal;
-                self.state.command_state_mut().clear();
+    pub fn handle_marker_list_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.state.show_marker_list = false;
+                self.state.marker_list_scroll = 0;
             }
-            k if k.code == self.state.config.keybindings.execute_command => {
-                let executed = self.command_mode.execute(&mut self.state);
-                if executed {
-                    self.state.command_state_mut().command_result_time =
-                        Some(std::time::Instant::now());
-                    // If the command execution didn't switch modes, the app is still in Command
-                    // mode. So return to normal mode as the command has finished executing.
-                    if self.state.mode == AppMode::Command {
-                        self.state.mode = AppMode::Normal;
-                    }
+            KeyCode::Up => {
+                self.state.marker_list_scroll = self.state.marker_list_scroll.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.state.marker_list_scroll += 1;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_view_list_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => self.state.show_view_list = false,
+            code => {
+                if let Some(slot) = view_slot(code) {
+                    self.state.show_view_list = false;
+                    // A slot without a view says so in the command result message
+                    let _ = self.run_command(&format!("view load {}", slot));
                 }
             }
-            // Let command mode handle all other keys
-            _ => self.command_mode.handle_input(key, &mut self.state),
         }
     }
 
-    pub fn handle_input(&mut self, key: KeyEvent) {
-        if self.state.mode == AppMode::Command {
-            self.handle_command_input(key);
-        } else {
-            if key.code == self.state.config.keybindings.enter_command_mode {
-                self.state.mode = AppMode::Command;
-                self.state.command_state_mut().clear();
-            } else {
-                self.handle_normal_mode(key);
+    pub fn handle_command_list_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.state.show_command_list = false;
+                self.state.command_list_scroll = 0;
+            }
+            KeyCode::Up => {
+                self.state.command_list_scroll = self.state.command_list_scroll.saturating_sub(1);
             }
+            KeyCode::Down => {
+                self.state.command_list_scroll += 1;
+            }
+            _ => {}
         }
     }
 
-    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
-        if self.mouse_within_rect(&mouse, &self.layout.waveform) {
-            // Convert column to coordinates inside waveform area
-            let column_in_waveform = mouse.column - self.layout.waveform.x;
+    pub fn handle_stats_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.state.show_stats = false;
+                self.state.stats_scroll = 0;
+            }
+            KeyCode::Up => {
+                self.state.stats_scroll = self.state.stats_scroll.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.state.stats_scroll += 1;
+            }
+            _ => {}
+        }
+    }
 
-            match mouse.kind {
-                MouseEventKind::Down(MouseButton::Left) => {
-                    if mouse.modifiers.contains(KeyModifiers::SHIFT) {
-                        // Shift is still used for secondary marker
-                        self.state
-                            .set_secondary_marker(column_in_waveform, self.layout.waveform.width);
-                    } else {
-                        // Start potential drag or click - we don't know which yet
-                        let time = self
-                            .state
-                            .screen_pos_to_time(column_in_waveform, self.layout.waveform.width);
-                        self.state.drag_start = Some((column_in_waveform, time));
-                        self.state.drag_current = Some((column_in_waveform, time));
-                        self.state.is_dragging = false; // Not dragging yet
-                    }
-                }
-                MouseEventKind::Drag(MouseButton::Left) => {
-                    if self.state.drag_start.is_some() {
-                        let time = self
-                            .state
-                            .screen_pos_to_time(column_in_waveform, self.layout.waveform.width);
+    pub fn handle_trace_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.state.show_trace = false;
+                self.state.trace_scroll = 0;
+            }
+            KeyCode::Up => {
+                self.state.trace_scroll = self.state.trace_scroll.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.state.trace_scroll += 1;
+            }
+            _ => {}
+        }
+    }
 
-                        if let Some((start_x, _)) = self.state.drag_start {
-                            // Detect if we've moved enough to consider this a drag
-                            if !self.state.is_dragging
-                                && (start_x as i32 - column_in_waveform as i32).abs()
-                                    >= constants::DRAG_DETECTED_THRESHOLD_PIXELS
-                            {
-                                self.state.is_dragging = true;
-                            }
-                        }
+    pub fn handle_messages_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.state.show_messages = false;
+                self.state.messages_scroll = 0;
+            }
+            KeyCode::Up => {
+                self.state.messages_scroll = self.state.messages_scroll.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.state.messages_scroll += 1;
+            }
+            _ => {}
+        }
+    }
 
-                        // Update current position regardless
-                        self.state.drag_current = Some((column_in_waveform, time));
-                    }
-                }
-                MouseEventKind::Up(MouseButton::Left) => {
-                    if let (Some((start_x, start_time)), Some((end_x, end_time))) =
-                        (self.state.drag_start, self.state.drag_current)
-                    {
-                        if self.state.is_dragging {
-                            // This was a drag operation - zoom to selection
-                            // Only zoom if dragged a minimum distance
-                            if (start_x as i32 - end_x as i32).abs()
+    pub fn handle_parse_warnings_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.state.show_parse_warnings = false;
+                self.state.parse_warnings_scroll = 0;
+            }
+            KeyCode::Up => {
+                self.state.parse_warnings_scroll =
+                    self.state.parse_warnings_scroll.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.state.parse_warnings_scroll += 1;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_file_info_input(&mut self, key: KeyEvent) {
+        match key.code {
+            KeyCode::Esc => {
+                self.state.show_file_info = false;
+                self.state.file_info_scroll = 0;
+            }
+            KeyCode::Up => {
+                self.state.file_info_scroll = self.state.file_info_scroll.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                self.state.file_info_scroll += 1;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_transitions_input(&mut self, key: KeyEvent) {
+        let Some(popup) = &mut self.state.transitions_popup else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.state.transitions_popup = None;
+            }
+            KeyCode::Enter => {
+                // Jump to the selected transition
+                if let Some(time) = popup.transitions.get(popup.selected).map(|t| t.time) {
+                    self.state.move_primary_marker(time);
+                }
+                self.state.transitions_popup = None;
+            }
+            KeyCode::Up => {
+                popup.selected = popup.selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                popup.selected =
+                    (popup.selected + 1).min(popup.transitions.len().saturating_sub(1));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_sample_input(&mut self, key: KeyEvent) {
+        let Some(popup) = &mut self.state.sample_popup else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.state.sample_popup = None;
+            }
+            KeyCode::Up => {
+                popup.scroll = popup.scroll.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                popup.scroll += 1;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_grep_input(&mut self, key: KeyEvent) {
+        let Some(popup) = &mut self.state.grep_popup else {
+            return;
+        };
+
+        match key.code {
+            KeyCode::Esc => {
+                self.state.grep_popup = None;
+            }
+            KeyCode::Enter => {
+                // Jump to the selected match, selecting its signal
+                if let Some(found) = popup.matches.get(popup.selected).cloned() {
+                    self.state.record_jump();
+                    self.state.move_primary_marker(found.time);
+                    self.state.center_on(found.time);
+                    if let Some(index) = self
+                        .state
+                        .displayed_signals
+                        .iter()
+                        .position(|signal| *signal == found.signal)
+                    {
+                        self.state.selected_signal = index;
+                        self.adjust_scroll_if_needed();
+                    }
+                }
+                self.state.grep_popup = None;
+            }
+            KeyCode::Up => {
+                popup.selected = popup.selected.saturating_sub(1);
+            }
+            KeyCode::Down => {
+                popup.selected = (popup.selected + 1).min(popup.matches.len().saturating_sub(1));
+            }
+            _ => {}
+        }
+    }
+
+    pub fn handle_command_input(&mut self, key: KeyEvent) {
+        match key {
+            k if k.code == self.state.config.keybindings.enter_normal_mode => {
+                self.state.mode = AppMode::Normal;
+                self.state.command_state_mut().clear();
+            }
+            k if k.code == self.state.config.keybindings.execute_command => {
+                let executed = self.command_mode.execute(&mut self.state);
+                if executed {
+                    // If the command execution didn't switch modes, the app is still in Command
+                    // mode. So return to normal mode as the command has finished executing.
+                    if self.state.mode == AppMode::Command {
+                        self.state.mode = AppMode::Normal;
+                    }
+                    self.handle_requests();
+                }
+            }
+            // Let command mode handle all other keys
+            _ => self.command_mode.handle_input(key, &mut self.state),
+        }
+    }
+
+    /// Edits the filter narrowing the signal list. The filter is kept when it's confirmed and
+    /// cleared when it's cancelled, which selects the signal that was selected before filtering.
+    pub fn handle_filter_input(&mut self, key: KeyEvent) {
+        let mut query = self
+            .state
+            .signal_filter
+            .as_ref()
+            .map(|filter| filter.query.clone())
+            .unwrap_or_default();
+        match key.code {
+            code if code == self.state.config.keybindings.enter_normal_mode => {
+                self.state.mode = AppMode::Normal;
+                self.state.clear_signal_filter();
+                self.adjust_scroll_if_needed();
+                return;
+            }
+            code if code == self.state.config.keybindings.execute_command => {
+                self.state.mode = AppMode::Normal;
+                // An empty filter lists everything, so there's nothing to keep
+                if query.is_empty() {
+                    self.state.signal_filter = None;
+                }
+                return;
+            }
+            KeyCode::Backspace => {
+                query.pop();
+            }
+            // Like in command mode, Ctrl+W deletes the last word and Ctrl+U everything
+            KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => match c {
+                'w' => query.truncate(previous_word_start(&query, query.len())),
+                'u' => query.clear(),
+                _ => return,
+            },
+            KeyCode::Char(c) => query.push(c),
+            _ => return,
+        }
+        self.state.set_signal_filter(&query);
+        // Show as many of the listed signals as fit, starting from the top
+        self.state.signal_scroll_offset = 0;
+        self.adjust_scroll_if_needed();
+    }
+
+    pub fn handle_input(&mut self, key: KeyEvent) {
+        if self.state.mode == AppMode::Command {
+            self.handle_command_input(key);
+        } else {
+            if key.code == self.state.config.keybindings.enter_command_mode {
+                self.state.mode = AppMode::Command;
+                self.state.command_state_mut().clear();
+                self.pending_chord = None;
+            } else if key.code == self.state.config.keybindings.filter_signals {
+                self.pending_chord = None;
+                // Typing goes on from the filter that's kept, if any
+                self.state.mode = AppMode::Filter;
+                let query = self
+                    .state
+                    .signal_filter
+                    .as_ref()
+                    .map(|filter| filter.query.clone())
+                    .unwrap_or_default();
+                self.state.set_signal_filter(&query);
+            } else {
+                self.handle_normal_mode(key);
+            }
+        }
+    }
+
+    /// Lays out again for a terminal of `width` by `height`, dropping any drag in progress and
+    /// keeping the selected signal and the time window within what still fits.
+    pub fn handle_resize(&mut self, width: u16, height: u16) {
+        // Any drag in progress refers to screen positions in the old layout
+        self.state.drag_start = None;
+        self.state.drag_current = None;
+        self.state.is_dragging = false;
+        self.state.drag_kind = DragKind::Zoom;
+        self.state.is_resizing_signal_list = false;
+        self.state.is_scrubbing_time_ruler = false;
+        self.state.is_panning_minimap = false;
+        self.state.is_dragging_signal_scrollbar = false;
+        self.state.is_dragging_saved_marker = false;
+
+        self.area = Rect::new(0, 0, width, height);
+        self.relayout();
+
+        // Keep the selection on a displayed signal, and don't leave empty rows below the last
+        // signal when the view grew
+        let signal_count = self.state.displayed_signals.len();
+        self.state.selected_signal = self
+            .state
+            .selected_signal
+            .min(signal_count.saturating_sub(1));
+        let max_offset = self
+            .state
+            .max_signal_scroll_offset(self.layout.waveform.height);
+        self.state.signal_scroll_offset = self.state.signal_scroll_offset.min(max_offset);
+        self.adjust_scroll_if_needed();
+
+        self.state
+            .set_window(self.state.time_start, self.state.time_range);
+    }
+
+    // Changes the signal list width and lays out again right away, so that mouse events arriving
+    // before the next frame already use the new waveform width
+    fn resize_signal_list(&mut self, width: u16) {
+        self.state.set_signal_list_width(width);
+        self.relayout();
+    }
+
+    // Handles grabbing and dragging the divider between the signal list and the waveform.
+    // Returns true if the event was consumed.
+    fn handle_divider_mouse(&mut self, mouse: &MouseEvent) -> bool {
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                let divider = self.layout.waveform.x;
+                let on_divider = mouse.column.abs_diff(divider) <= 1
+                    && mouse.row >= self.layout.waveform.y
+                    && mouse.row < self.layout.waveform.bottom();
+                self.state.is_resizing_signal_list = on_divider;
+                on_divider
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.state.is_resizing_signal_list => {
+                let total_width = self.layout.signal_list.width + self.layout.waveform.width;
+                if total_width > 0 {
+                    let column = mouse.column.saturating_sub(self.layout.signal_list.x);
+                    let percentage = (column as u32 * 100 / total_width as u32) as u16;
+                    self.resize_signal_list(percentage);
+                }
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.state.is_resizing_signal_list => {
+                self.state.is_resizing_signal_list = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Handles placing markers by clicking the time ruler and scrubbing the primary marker by
+    // dragging along it. Returns true if the event was consumed.
+    fn handle_time_ruler_mouse(&mut self, mouse: &MouseEvent) -> bool {
+        let ruler = self.layout.time_ruler;
+        // Map the ruler's columns onto the whole window, so that the rightmost column is the end
+        let ruler_time = |state: &AppState| {
+            let column = mouse
+                .column
+                .saturating_sub(ruler.x)
+                .min(ruler.width.saturating_sub(1));
+            state.marker_time_at(column, ruler.width.saturating_sub(1).max(1))
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if self.mouse_within_rect(mouse, &ruler) => {
+                let time = ruler_time(&self.state);
+                if mouse.modifiers.contains(KeyModifiers::SHIFT) {
+                    self.state.secondary_marker = Some(time);
+                } else {
+                    self.state.move_primary_marker(time);
+                    self.state.is_scrubbing_time_ruler = true;
+                }
+                true
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.state.is_scrubbing_time_ruler => {
+                self.state.move_primary_marker(ruler_time(&self.state));
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.state.is_scrubbing_time_ruler => {
+                self.state.is_scrubbing_time_ruler = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Handles jumping to a time by clicking the minimap and panning by dragging along it. Returns
+    // true if the event was consumed.
+    fn handle_minimap_mouse(&mut self, mouse: &MouseEvent) -> bool {
+        let minimap = self.layout.minimap;
+        let minimap_time = |state: &AppState| {
+            let column = mouse.column.saturating_sub(minimap.x);
+            state.minimap_time_at(column, minimap.width)
+        };
+
+        match mouse.kind {
+            // The minimap is right under the time ruler, so only its own row counts
+            MouseEventKind::Down(MouseButton::Left)
+                if minimap.contains(Position::new(mouse.column, mouse.row)) =>
+            {
+                let time = minimap_time(&self.state);
+                self.state.record_jump();
+                self.state.center_on(time);
+                self.state.is_panning_minimap = true;
+                true
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.state.is_panning_minimap => {
+                let time = minimap_time(&self.state);
+                self.state.center_on(time);
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.state.is_panning_minimap => {
+                self.state.is_panning_minimap = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Handles scrolling the signal list by clicking or dragging its scrollbar. Returns true if the
+    // event was consumed.
+    fn handle_signal_scrollbar_mouse(&mut self, mouse: &MouseEvent) -> bool {
+        let list = self.layout.signal_list;
+        let scroll_to = |state: &mut AppState| {
+            if let Some(offset) = SignalListWidget::scroll_offset_at(list, mouse.row, state) {
+                state.signal_scroll_offset = offset;
+            }
+        };
+
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left)
+                if SignalListWidget::scrollbar_area(list, &self.state)
+                    .is_some_and(|area| area.contains(Position::new(mouse.column, mouse.row))) =>
+            {
+                scroll_to(&mut self.state);
+                self.state.is_dragging_signal_scrollbar = true;
+                true
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.state.is_dragging_signal_scrollbar => {
+                scroll_to(&mut self.state);
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.state.is_dragging_signal_scrollbar => {
+                self.state.is_dragging_signal_scrollbar = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // Handles selecting a saved marker by clicking its name and moving it by dragging the name.
+    // Returns true if the event was consumed.
+    fn handle_marker_names_mouse(&mut self, mouse: &MouseEvent) -> bool {
+        let names = self.layout.marker_names;
+        match mouse.kind {
+            // Clicking the row away from the saved markers' names clears the selection
+            MouseEventKind::Down(MouseButton::Left)
+                if names.contains(Position::new(mouse.column, mouse.row)) =>
+            {
+                let marker = MarkerNamesWidget::saved_marker_at(names, mouse.column, &self.state);
+                self.state.is_dragging_saved_marker = marker.is_some();
+                self.state.selected_saved_marker = marker;
+                self.state.is_dragging_saved_marker
+            }
+            MouseEventKind::Drag(MouseButton::Left) if self.state.is_dragging_saved_marker => {
+                // Past either end of the row the marker stops at the edge of the view
+                let column = mouse.column.saturating_sub(names.x).min(names.width);
+                let time = self
+                    .state
+                    .marker_time_at(column, names.width)
+                    .min(self.state.waveform_data.max_time);
+                let selected = self.state.selected_saved_marker.as_deref();
+                if let Some(marker) = self
+                    .state
+                    .saved_markers
+                    .iter_mut()
+                    .find(|marker| Some(marker.name.as_str()) == selected)
+                {
+                    marker.time = time;
+                }
+                true
+            }
+            MouseEventKind::Up(MouseButton::Left) if self.state.is_dragging_saved_marker => {
+                self.state.is_dragging_saved_marker = false;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn handle_mouse(&mut self, mouse: MouseEvent) {
+        // Nothing to click on while the terminal is too small for the UI
+        if !self.layout.is_usable() {
+            return;
+        }
+
+        // Clicking the other pane of a split waveform moves the focus there first
+        if let (MouseEventKind::Down(_), Some(other_pane)) = (mouse.kind, self.layout.other_pane) {
+            let position = Position::new(mouse.column, mouse.row);
+            if [
+                other_pane.signal_list,
+                other_pane.time_ruler,
+                other_pane.waveform,
+            ]
+            .iter()
+            .any(|area| area.contains(position))
+            {
+                self.state.switch_pane_focus();
+                self.relayout();
+            }
+        }
+
+        self.state.hover_position = self
+            .layout
+            .waveform
+            .contains(Position::new(mouse.column, mouse.row))
+            .then_some((mouse.column, mouse.row));
+
+        if self.handle_divider_mouse(&mouse)
+            || self.handle_signal_scrollbar_mouse(&mouse)
+            || self.handle_minimap_mouse(&mouse)
+            || self.handle_marker_names_mouse(&mouse)
+            || self.handle_time_ruler_mouse(&mouse)
+        {
+            return;
+        }
+
+        if self.mouse_within_rect(&mouse, &self.layout.waveform) {
+            // Convert column to coordinates inside waveform area
+            let column_in_waveform = mouse.column - self.layout.waveform.x;
+
+            match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    if mouse.modifiers.contains(KeyModifiers::SHIFT) {
+                        // Shift is still used for secondary marker
+                        self.state
+                            .set_secondary_marker(column_in_waveform, self.layout.waveform.width);
+                    } else {
+                        // Start potential drag or click - we don't know which yet
+                        let time = self
+                            .state
+                            .screen_pos_to_time(column_in_waveform, self.layout.waveform.width);
+                        self.state.drag_start = Some((column_in_waveform, time));
+                        self.state.drag_current = Some((column_in_waveform, time));
+                        self.state.drag_start_row = Some(mouse.row);
+                        self.state.is_dragging = false; // Not dragging yet
+                        self.state.drag_kind = if mouse.modifiers.contains(KeyModifiers::ALT) {
+                            DragKind::Region
+                        } else if self.state.measure_mode
+                            || mouse.modifiers.contains(KeyModifiers::CONTROL)
+                        {
+                            DragKind::Measure
+                        } else {
+                            DragKind::Zoom
+                        };
+                    }
+                }
+                MouseEventKind::Drag(MouseButton::Left) if self.state.drag_start.is_some() => {
+                    let time = self
+                        .state
+                        .screen_pos_to_time(column_in_waveform, self.layout.waveform.width);
+
+                    if let Some((start_x, _)) = self.state.drag_start {
+                        // Detect if we've moved enough to consider this a drag
+                        if !self.state.is_dragging
+                            && (start_x as i32 - column_in_waveform as i32).abs()
+                                >= constants::DRAG_DETECTED_THRESHOLD_PIXELS
+                        {
+                            self.state.is_dragging = true;
+                        }
+                    }
+
+                    // Update current position regardless
+                    self.state.drag_current = Some((column_in_waveform, time));
+                }
+                MouseEventKind::Up(MouseButton::Left) => {
+                    if let (Some((start_x, start_time)), Some((end_x, end_time))) =
+                        (self.state.drag_start, self.state.drag_current)
+                    {
+                        if self.state.is_dragging && self.state.drag_kind == DragKind::Measure {
+                            // This was a measurement - mark both ends without changing the view
+                            self.state.move_primary_marker(start_time);
+                            self.state.secondary_marker = Some(end_time);
+                        } else if self.state.is_dragging && self.state.drag_kind == DragKind::Region
+                        {
+                            // Prompt for the region name with the dragged times already filled in,
+                            // typing it between quotes so it can have spaces
+                            let prompt = "region add \"";
+                            self.state.mode = AppMode::Command;
+                            let command_state = self.state.command_state_mut();
+                            command_state.clear();
+                            command_state.input_buffer = format!(
+                                "{}\" {} {}",
+                                prompt,
+                                start_time.min(end_time),
+                                start_time.max(end_time)
+                            );
+                            command_state.cursor_position = prompt.len();
+                        } else if self.state.is_dragging {
+                            // This was a drag operation - zoom to selection
+                            // Only zoom if dragged a minimum distance
+                            if (start_x as i32 - end_x as i32).abs()
                                 > constants::DRAG_STARTED_THRESHOLD_PIXELS
                             {
                                 // Order the times correctly
@@ -279,8 +1281,17 @@ impl App {
                                 };
 
                                 // Set the new zoom area
-                                self.state.time_start = min_time;
-                                self.state.time_range = max_time.saturating_sub(min_time).max(1);
+                                self.state.record_jump();
+                                self.state.zoom_to(min_time, max_time - min_time);
+
+                                // Select the topmost signal under the selection
+                                let start_row = self.state.drag_start_row.unwrap_or(mouse.row);
+                                if let Some(index) =
+                                    self.waveform_signal_at_row(start_row.min(mouse.row))
+                                {
+                                    self.state.selected_signal = index;
+                                    self.adjust_scroll_if_needed();
+                                }
                             }
                         } else {
                             // This was a click (not a drag) - set marker
@@ -292,22 +1303,17 @@ impl App {
                     // Reset drag state
                     self.state.drag_start = None;
                     self.state.drag_current = None;
+                    self.state.drag_start_row = None;
                     self.state.is_dragging = false;
+                    self.state.drag_kind = DragKind::Zoom;
                 }
                 _ => {}
             }
         } else if self.mouse_within_rect(&mouse, &self.layout.signal_list) {
             // Handle clicking on signal rows in the signal list area
             if mouse.kind == MouseEventKind::Down(MouseButton::Left) {
-                // Calculate which signal was clicked based on mouse position
-                // Each row in signal list has WAVEFORM_HEIGHT height
-                let row_in_signal_list = mouse.row as usize - self.layout.signal_list.y as usize;
-                let clicked_signal_index =
-                    row_in_signal_list / WAVEFORM_HEIGHT + self.state.signal_scroll_offset;
-
-                // Check if it's a valid signal index
-                if clicked_signal_index < self.state.displayed_signals.len() {
-                    self.state.selected_signal = clicked_signal_index;
+                if let Some(index) = self.signal_list_index_at(mouse.column, mouse.row) {
+                    self.state.selected_signal = index;
                     self.adjust_scroll_if_needed();
                 }
             }
@@ -320,119 +1326,129 @@ impl App {
     }
 
     fn handle_normal_mode(&mut self, key: KeyEvent) {
+        // The second key of a view bookmark chord picks the slot. Any other key cancels the chord
+        // and does what it always does.
+        if let Some(chord) = self.pending_chord.take() {
+            if let Some(slot) = view_slot(key.code) {
+                let command = match chord {
+                    ViewChord::Save => "save",
+                    ViewChord::Load => "load",
+                };
+                // Errors are shown in the command result message
+                let _ = self.run_command(&format!("view {} {}", command, slot));
+                return;
+            }
+        }
+
         match key {
+            // Signals hidden by the filter are skipped, also when moving a signal past them
             k if k.code == self.state.config.keybindings.up => {
-                if k.modifiers.contains(KeyModifiers::SHIFT) {
-                    // Move the selected signal up in the list
-                    if !self.state.displayed_signals.is_empty() && self.state.selected_signal > 0 {
+                if let Some(above) = self
+                    .state
+                    .previous_listed_signal(self.state.selected_signal)
+                {
+                    // Keep the selection on the moved signal
+                    self.state.selected_signal = if k.modifiers.contains(KeyModifiers::SHIFT) {
                         // Swap the signal with the one above it
-                        self.state
-                            .displayed_signals
-                            .swap(self.state.selected_signal, self.state.selected_signal - 1);
-                        // Keep the selection on the moved signal
-                        self.state.selected_signal -= 1;
-                        self.adjust_scroll_if_needed();
-                    }
-                } else {
-                    if !self.state.displayed_signals.is_empty() {
-                        if self.state.selected_signal > 0 {
-                            self.state.selected_signal -= 1;
-                            self.adjust_scroll_if_needed();
+                        match self.move_signal(self.state.selected_signal, above) {
+                            Some(index) => index,
+                            None => return,
                         }
-                    }
+                    } else {
+                        above
+                    };
+                    self.adjust_scroll_if_needed();
                 }
             }
             k if k.code == self.state.config.keybindings.down => {
-                if k.modifiers.contains(KeyModifiers::SHIFT) {
-                    // Move the selected signal down in the list
-                    if !self.state.displayed_signals.is_empty()
-                        && self.state.selected_signal < self.state.displayed_signals.len() - 1
-                    {
+                if let Some(below) = self.state.next_listed_signal(self.state.selected_signal) {
+                    // Keep the selection on the moved signal
+                    self.state.selected_signal = if k.modifiers.contains(KeyModifiers::SHIFT) {
                         // Swap the signal with the one below it
-                        self.state
-                            .displayed_signals
-                            .swap(self.state.selected_signal, self.state.selected_signal + 1);
-                        // Keep the selection on the moved signal
-                        self.state.selected_signal += 1;
-                        self.adjust_scroll_if_needed();
-                    }
-                } else {
-                    if !self.state.displayed_signals.is_empty() {
-                        if self.state.selected_signal < self.state.displayed_signals.len() - 1 {
-                            self.state.selected_signal += 1;
-                            self.adjust_scroll_if_needed();
+                        match self.move_signal(self.state.selected_signal, below) {
+                            Some(index) => index,
+                            None => return,
                         }
-                    }
+                    } else {
+                        below
+                    };
+                    self.adjust_scroll_if_needed();
                 }
             }
             k if k.code == self.state.config.keybindings.down => {}
+            k if k.code == self.state.config.keybindings.shrink_signal_list
+                && k.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                let width = self.state.config.ui.signal_list_width;
+                self.resize_signal_list(width.saturating_sub(constants::SIGNAL_LIST_WIDTH_STEP));
+            }
+            k if k.code == self.state.config.keybindings.grow_signal_list
+                && k.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                let width = self.state.config.ui.signal_list_width;
+                self.resize_signal_list(width + constants::SIGNAL_LIST_WIDTH_STEP);
+            }
+            k if k.code == self.state.config.keybindings.jump_back
+                && k.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.state.jump_back();
+                self.adjust_scroll_if_needed();
+            }
+            k if k.code == self.state.config.keybindings.jump_forward
+                && k.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.state.jump_forward();
+                self.adjust_scroll_if_needed();
+            }
             k if k.code == self.state.config.keybindings.left => {
-                if self.state.time_start > 0 {
-                    self.state.time_start = self
-                        .state
-                        .time_start
-                        .saturating_sub(self.state.time_range / 4);
-                }
+                let step = self.pan_step(k.modifiers);
+                self.state.pan_by(-(step as i64));
             }
             k if k.code == self.state.config.keybindings.right => {
-                if self.state.time_start < self.state.waveform_data.max_time {
-                    // Ensure the waveform view doesn't go beyond max_time
-                    let max_start = self
-                        .state
-                        .waveform_data
-                        .max_time
-                        .saturating_sub(self.state.time_range);
-                    self.state.time_start =
-                        (self.state.time_start + self.state.time_range / 4).min(max_start);
-                }
+                let step = self.pan_step(k.modifiers);
+                self.state.pan_by(step as i64);
             }
             k if k.code == self.state.config.keybindings.zoom_out => {
-                // Calculate the new time range, doubling but capped at max_time
-                let new_time_range =
-                    (self.state.time_range * 2).min(self.state.waveform_data.max_time);
-
-                // Calculate center point of current view
-                let center = self.state.time_start + (self.state.time_range / 2);
-
-                // Calculate new start time, keeping the center point if possible
-                let half_new_range = new_time_range / 2;
-                let new_start = if center > half_new_range {
-                    center.saturating_sub(half_new_range)
-                } else {
-                    0
-                };
-
-                // Make sure the end time (start + range) doesn't exceed max_time
-                let adjusted_start =
-                    if new_start + new_time_range > self.state.waveform_data.max_time {
-                        self.state
-                            .waveform_data
-                            .max_time
-                            .saturating_sub(new_time_range)
-                    } else {
-                        new_start
-                    };
-
-                self.state.time_start = adjusted_start;
-                self.state.time_range = new_time_range;
+                self.state.zoom_out();
             }
             k if k.code == self.state.config.keybindings.zoom_in => {
-                // Calculate center point of current view
-                let center = self.state.time_start + (self.state.time_range / 2);
-
-                // Calculate new time range, halving but with a minimum
-                let new_time_range = (self.state.time_range / 2).max(10);
-
-                // Calculate new start time, trying to keep the center point
-                let half_new_range = new_time_range / 2;
-                let new_start = center.saturating_sub(half_new_range);
-
-                self.state.time_start = new_start;
-                self.state.time_range = new_time_range;
+                self.state.zoom_in();
             }
             k if k.code == self.state.config.keybindings.zoom_full => {
-                self.state.time_start = 0;
-                self.state.time_range = self.state.waveform_data.max_time;
+                self.state.zoom_to(0, self.state.waveform_data.max_time);
+            }
+            k if k.code == self.state.config.keybindings.zoom_back => {
+                self.state.zoom_back();
+            }
+            k if k.code == self.state.config.keybindings.zoom_forward => {
+                self.state.zoom_forward();
+            }
+            k if k.code == self.state.config.keybindings.fit_signal => {
+                // The result message says whether the signal changes enough to fit to
+                let _ = self.run_command("fit");
+            }
+            k if k.code == self.state.config.keybindings.rename_marker => {
+                let Some(name) = self.state.selected_saved_marker.clone() else {
+                    self.show_result("Click a saved marker's name to rename it".to_string(), true);
+                    return;
+                };
+                // Prompt for the new name with the current one filled in to edit
+                let prompt = format!("marker rename {} ", quote_args(&[&name]));
+                self.state.mode = AppMode::Command;
+                let command_state = self.state.command_state_mut();
+                command_state.clear();
+                command_state.input_buffer = format!("{}{}", prompt, quote_args(&[&name]));
+                command_state.cursor_position = command_state.input_buffer.len();
+            }
+            k if k.code == self.state.config.keybindings.zoom_fit => {
+                let _ = self.run_command("zoomfit");
+            }
+            k if k.code == self.state.config.keybindings.center_on_marker => {
+                // Errors are shown in the command result message
+                let _ = self.run_command("center");
+            }
+            k if k.code == self.state.config.keybindings.marker_to_center => {
+                let _ = self.run_command("center marker");
             }
 
             k if k.code == self.state.config.keybindings.delete_primary_marker => {
@@ -441,12 +1457,129 @@ impl App {
             k if k.code == self.state.config.keybindings.delete_secondary_marker => {
                 self.state.secondary_marker = None;
             }
+            k if k.code == self.state.config.keybindings.yank => {
+                // Errors are shown in the command result message
+                let _ = self.run_command("yank");
+            }
+            k if k.code == self.state.config.keybindings.cycle_signal_names => {
+                let ui = &mut self.state.config.ui;
+                ui.signal_name_style = ui.signal_name_style.next();
+            }
+            k if k.code == self.state.config.keybindings.toggle_cursor => {
+                // The result message says where the cursor starts
+                let _ = self.run_command("cursor");
+            }
+            k if self.state.cursor.is_some()
+                && is_key_or_shifted(&k, self.state.config.keybindings.cursor_left) =>
+            {
+                let step = self.cursor_step(is_shifted(&k));
+                self.state.move_cursor(-(step as i64));
+            }
+            k if self.state.cursor.is_some()
+                && is_key_or_shifted(&k, self.state.config.keybindings.cursor_right) =>
+            {
+                let step = self.cursor_step(is_shifted(&k));
+                self.state.move_cursor(step as i64);
+            }
+            k if self.state.cursor.is_some()
+                && k.code == self.state.config.keybindings.cursor_to_marker =>
+            {
+                if let Some(cursor) = self.state.cursor {
+                    self.state.move_primary_marker(cursor);
+                }
+            }
+            // Checked after the cursor keys, which may share these keys while the cursor is shown
+            k if k.code == self.state.config.keybindings.save_view => {
+                self.pending_chord = Some(ViewChord::Save);
+            }
+            k if k.code == self.state.config.keybindings.load_view => {
+                self.pending_chord = Some(ViewChord::Load);
+            }
+            k if k.code == self.state.config.keybindings.delete_signal => {
+                self.state.hide_signal(self.state.selected_signal);
+                self.adjust_scroll_if_needed();
+            }
+            k if k.code == self.state.config.keybindings.switch_pane => {
+                self.state.switch_pane_focus();
+                self.relayout();
+            }
 
             _ => {}
         }
     }
 
-    fn mouse_within_rect(&self, mouse: &MouseEvent, rect: &Rect) -> bool {
+    // How far the left and right keys pan: a whole page with Shift, one ruler tick with Alt, and
+    // `ui.pan_fraction` of the page otherwise
+    fn pan_step(&self, modifiers: KeyModifiers) -> u64 {
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            self.state.time_range
+        } else if modifiers.contains(KeyModifiers::ALT) {
+            self.tick_step()
+        } else {
+            self.fraction_step()
+        }
+    }
+
+    // How far the cursor keys move the cursor: `ui.pan_fraction` of the page with Shift, and one
+    // ruler tick otherwise
+    fn cursor_step(&self, shifted: bool) -> u64 {
+        if shifted {
+            self.fraction_step()
+        } else {
+            self.tick_step()
+        }
+    }
+
+    // The time between two ticks of the time ruler
+    fn tick_step(&self) -> u64 {
+        calculate_tick_interval(self.state.time_range, self.layout.time_ruler.width as u64)
+    }
+
+    // `ui.pan_fraction` of the visible time range, at least one time unit
+    fn fraction_step(&self) -> u64 {
+        let range = self.state.time_range as f64;
+        ((range * self.state.config.ui.pan_fraction).round() as u64).max(1)
+    }
+
+    // Swaps the displayed signal at `from` with the one at `to` where they are listed, returning
+    // the moved signal's new index. Pinned signals swap places in the pinned signals, and neither
+    // moves past the separator below them.
+    fn move_signal(&mut self, from: usize, to: usize) -> Option<usize> {
+        match (self.state.is_pinned(from), self.state.is_pinned(to)) {
+            (true, true) => {
+                let position_of = |index: usize| {
+                    let signal = &self.state.displayed_signals[index];
+                    self.state.pinned_signals.iter().position(|s| s == signal)
+                };
+                let (a, b) = (position_of(from)?, position_of(to)?);
+                self.state.pinned_signals.swap(a, b);
+                Some(from)
+            }
+            (false, false) => {
+                self.state.displayed_signals.swap(from, to);
+                Some(to)
+            }
+            _ => None,
+        }
+    }
+
+    // Index of the displayed signal drawn at a screen row of the waveform, if any
+    fn waveform_signal_at_row(&self, row: u16) -> Option<usize> {
+        self.state
+            .signal_at_row(row.checked_sub(self.layout.waveform.y)?)
+    }
+
+    // Index of the displayed signal whose rows are at a position in the signal list. The rows are
+    // drawn inside the list's border, so clicks on the border or below the last signal are None.
+    fn signal_list_index_at(&self, column: u16, row: u16) -> Option<usize> {
+        let inner = SignalListWidget::block().inner(self.layout.signal_list);
+        if !inner.contains(Position::new(column, row)) {
+            return None;
+        }
+        self.state.signal_at_row(row - inner.y)
+    }
+
+    fn mouse_within_rect(&self, mouse: &MouseEvent, rect: &Rect) -> bool {
         mouse.column >= rect.x
             && mouse.column <= rect.right()
             && mouse.row >= rect.y
@@ -454,11 +1587,13 @@ impl App {
     }
 
     fn adjust_scroll_if_needed(&mut self) {
-        // Calculate how many signals can be displayed at once
-        let visible_signals = self.layout.waveform.height as usize / WAVEFORM_HEIGHT;
-
-        if visible_signals == 0 {
-            return; // Avoid division by zero
+        // Nothing is visible before the first layout
+        let height = self.layout.waveform.height;
+        if height == 0
+            || self.state.displayed_signals.is_empty()
+            || self.state.is_pinned(self.state.selected_signal)
+        {
+            return;
         }
 
         // If the selected signal is before the current scroll offset, scroll up
@@ -467,49 +1602,281 @@ impl App {
         }
 
         // If the selected signal is beyond the visible area, scroll down
-        let last_visible = self.state.signal_scroll_offset + visible_signals - 1;
-        if self.state.selected_signal > last_visible {
+        let last_visible = self
+            .state
+            .visible_signal_rows(height)
+            .into_iter()
+            .filter(|(index, _, _)| !self.state.is_pinned(*index))
+            .last()
+            .map(|(index, _, _)| index);
+        if last_visible.is_none_or(|last| self.state.selected_signal > last) {
             // Set scroll to position the selected signal at the bottom of the view
             self.state.signal_scroll_offset = self
                 .state
-                .selected_signal
-                .saturating_sub(visible_signals - 1);
+                .scroll_offset_ending_at(self.state.selected_signal, height);
+        }
+    }
+
+    /// Loads a waveform file of any supported format.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        if path.as_ref().extension().and_then(|ext| ext.to_str()) == Some("vcd") {
+            self.load_vcd_file(path)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Unsupported file format. Only .vcd files are supported.",
+            ))
         }
     }
 
+    /// Loads a VCD file, replacing the loaded waveform. The file is watched for changes and added
+    /// to the recent files.
     pub fn load_vcd_file<P: AsRef<Path>>(&mut self, path: P) -> io::Result<()> {
+        // Keep the parser to read changes appended to the file later
+        let parser = self.load_vcd(|parser| parser.parse_file(path.as_ref()))?;
+        self.state.file_path = Some(path.as_ref().to_path_buf());
+        self.state.vcd_parser = Some(parser);
+        if let Some(info) = &mut self.state.file_info {
+            info.size = fs::metadata(path.as_ref())
+                .ok()
+                .map(|metadata| metadata.len());
+        }
+
+        self.remember_recent_file(path.as_ref());
+        self.start_watching();
+        Ok(())
+    }
+
+    /// Reads the loaded file again from the start, keeping the view, the markers, the displayed
+    /// signals and their settings, and the selection. Signals the file no longer has are dropped,
+    /// as are derived rows like split bits.
+    pub fn reload_file(&mut self) -> io::Result<()> {
+        let Some(path) = self.state.file_path.clone() else {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "No file loaded"));
+        };
+        let session = Session::capture(&self.state);
+        let session_dirty = self.state.is_session_dirty();
+        let selected = self
+            .state
+            .displayed_signals
+            .get(self.state.selected_signal)
+            .cloned();
+        let signal_scroll_offset = self.state.signal_scroll_offset;
+        let window = (self.state.time_start, self.state.time_range);
+        let zoom_history = mem::take(&mut self.state.zoom_history);
+        let zoom_forward_history = mem::take(&mut self.state.zoom_forward_history);
+        let jump_list = mem::take(&mut self.state.jump_list);
+        let jump_index = self.state.jump_index;
+        let highlights = mem::take(&mut self.state.highlights);
+        let tracked_signal = self.state.tracked_signal.take();
+        let trace = self.state.trace.take();
+        let signal_filter = self.state.signal_filter.take();
+        let time_zero = self.state.time_zero;
+        let cursor = self.state.cursor;
+        let following = self.state.following;
+
+        self.load_window = self.file_window.clone();
+        self.load_vcd_file(&path)?;
+
+        session.restore(&mut self.state);
+        self.state.set_window(window.0, window.1);
+        self.state.zoom_history = zoom_history;
+        self.state.zoom_forward_history = zoom_forward_history;
+        self.state.jump_list = jump_list;
+        self.state.jump_index = jump_index;
+        let has_signal = |signal: &str| self.state.waveform_data.values.contains_key(signal);
+        let highlights = highlights
+            .into_iter()
+            .filter(|rule| has_signal(&rule.signal))
+            .collect();
+        let tracked_signal = tracked_signal.filter(|signal| has_signal(signal));
+        let trace = trace.filter(|trace| has_signal(&trace.signal));
+        self.state.highlights = highlights;
+        self.state.tracked_signal = tracked_signal;
+        self.state.trace = trace;
+        self.state.time_zero = time_zero;
+        self.state.cursor = cursor;
+        self.state.following = following;
+        self.state.signal_scroll_offset = signal_scroll_offset;
+        self.state.signal_filter = signal_filter;
+        self.state.select_signal_named(selected.as_deref());
+        if !session_dirty {
+            self.state.mark_session_saved();
+        }
+        Ok(())
+    }
+
+    // Reloads the file, saying so in the command result unless loading it already showed its
+    // problems there
+    fn reload_and_report(&mut self) {
+        match self.reload_file() {
+            Ok(()) if self.state.parse_report.is_empty() => {
+                let path = self.state.file_path.clone().unwrap_or_default();
+                self.show_result(format!("Reloaded {}", path.display()), false);
+            }
+            Ok(()) => {}
+            Err(e) => self.show_result(format!("Error reloading: {}", e), true),
+        }
+    }
+
+    // Carries out what the command that just ran asked of the app, like `:reload` or `:sleep`
+    fn handle_requests(&mut self) {
+        if mem::take(&mut self.state.reload_requested) {
+            self.reload_and_report();
+        }
+        if let Some(duration) = self.state.sleep_requested.take() {
+            self.autopilot.sleep(duration, Instant::now());
+        }
+    }
+
+    /// Loads a whole VCD read from `reader`, like one piped to stdin. There is no file to follow
+    /// or to remember afterwards.
+    pub fn load_vcd_reader<R: BufRead>(&mut self, reader: R) -> io::Result<()> {
+        self.load_vcd(|parser| parser.parse_reader(reader))?;
+        Ok(())
+    }
+
+    // Helper function to replace the loaded waveform with the one `parse` reads, resetting the view
+    // to show all of it. Returns the parser that read it.
+    fn load_vcd(
+        &mut self,
+        parse: impl FnOnce(&mut VcdParser) -> io::Result<WaveformData>,
+    ) -> io::Result<VcdParser> {
         // Clear existing data
         self.state.waveform_data.signals.clear();
         self.state.waveform_data.values.clear();
         self.state.displayed_signals.clear();
-
-        // Parse the VCD file
-        let waveform_data = parsers::vcd::parse_vcd_file(path)?;
+        self.state.file_path = None;
+        self.state.vcd_parser = None;
+        self.state.following = false;
+        self.file_watcher = None;
+        self.state.file_info = None;
+        self.state.show_file_info = false;
+
+        let window = mem::take(&mut self.load_window);
+        let mut parser = VcdParser::with_window(window.clone());
+        let parse_start = Instant::now();
+        let waveform_data = parse(&mut parser)?;
+        self.file_window = window;
+        self.state.file_info = Some(FileInfo {
+            size: None,
+            parse_duration: parse_start.elapsed(),
+            signal_count: waveform_data.signals.len(),
+            change_count: waveform_data.values.values().map(Vec::len).sum(),
+            header: parser.header().clone(),
+        });
+        self.state.parse_report = parser.report().clone();
+        self.state.show_parse_warnings = false;
+        if !self.state.parse_report.is_empty() {
+            self.show_result(
+                format!(
+                    "Loaded with problems: {}. See :parsewarnings",
+                    self.state.parse_report.summary()
+                ),
+                true,
+            );
+        }
 
         // Update the state with the parsed data
-        let signals_clone = waveform_data.signals.clone();
-        self.state.waveform_data.signals = signals_clone.clone();
         self.state
             .fuzzy_finder_state_mut()
-            .set_signals(signals_clone, &[]);
-        self.state.waveform_data.values = waveform_data.values;
-        self.state.waveform_data.max_time = waveform_data.max_time;
-
-        // Reset the view to show the full waveform
-        self.state.time_start = 0;
-        self.state.time_range = waveform_data.max_time;
+            .set_signals(waveform_data.signals.clone(), &[]);
+        self.state.waveform_data = waveform_data;
+        self.state.invalidate_visible_values();
+        self.state.invalidate_waveform_stats();
+
+        // Start with the part of the waveform `ui.initial_view` asks for, or all that was loaded
+        let view = InitialView::parse(
+            &self.state.config.ui.initial_view,
+            self.state.waveform_data.timescale_fs,
+        )
+        .unwrap_or_else(|e| {
+            self.show_result(format!("{}. Showing the full waveform", e), true);
+            InitialView::Full
+        });
+        let (start, range) = view.window(&self.state.waveform_data);
+        self.state.set_window(start, range);
+        self.state.clear_zoom_history();
+
+        // A dump cut short after its header still has signal names worth browsing, but nothing to
+        // draw, so say why the waveform is empty until the message is dismissed
+        let waveform_data = &self.state.waveform_data;
+        if waveform_data.max_time == 0 || waveform_data.values.values().all(Vec::is_empty) {
+            self.show_result("Waveform contains no value changes".to_string(), true);
+        }
+        self.state.clear_jump_list();
+        self.state.cursor = None;
+        self.state.comparison = None;
+        self.state.diff = None;
+        self.state.tracked_signal = None;
+        self.state.time_zero = None;
+        self.state.split_signals.clear();
+        self.state.decoded.clear();
+        self.state.highlights.clear();
+        self.state.trace = None;
+        self.state.show_trace = false;
+        self.state.split_view = None;
+        self.state.signal_filter = None;
         self.state.selected_signal = 0;
+        self.state.mark_session_saved();
+        Ok(parser)
+    }
 
-        Ok(())
+    // Puts a successfully loaded file at the top of the recent files list and saves the list
+    fn remember_recent_file(&mut self, path: &Path) {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        file_browser::record_recent_file(
+            &mut self.state.file_browser.recent_files,
+            &path.to_string_lossy(),
+            file_browser::now_unix_secs(),
+        );
+        if let Some(recent_files_path) = &self.recent_files_path {
+            // Failing to remember the file shouldn't stop it from being viewed
+            let _ = file_browser::save_recent_files(
+                recent_files_path,
+                &self.state.file_browser.recent_files,
+            );
+        }
+    }
+}
+
+impl App {
+    // Draws the signal list, time ruler and waveform of one pane in the current window
+    fn render_pane(&mut self, pane: PaneLayout, focused: bool, buf: &mut Buffer) {
+        let signal_list = SignalListWidget {
+            focused_pane: focused,
+        };
+        signal_list.render(pane.signal_list, buf, &mut self.state);
+        self.waveform.render(pane.waveform, buf, &mut self.state);
+        self.time_ruler
+            .render(pane.time_ruler, buf, &mut self.state);
     }
 }
 
 impl Widget for &mut App {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        self.layout = create_layout(area, &self.state.config);
+        self.area = area;
+        self.relayout();
+
+        if !self.layout.is_usable() {
+            let result_lines = self.result_message_lines().len() as u16;
+            let (min_width, min_height) = minimum_size(
+                &self.state.config,
+                result_lines,
+                self.state.split_view.as_ref(),
+            );
+            TooSmallWidget {
+                min_width,
+                min_height,
+            }
+            .render(area, buf);
+            self.state.theme().strip_colors(area, buf);
+            return; // Nothing else fits
+        }
 
         if self.state.show_help {
-            self.help_menu.render(area, buf, &mut self.state);
+            let commands = self.command_mode.parser().registry().commands();
+            HelpMenuWidget { commands }.render(area, buf, &mut self.state);
             return; // Don't render the rest of the UI when help is shown
         }
 
@@ -518,59 +1885,194 @@ impl Widget for &mut App {
             return; // Don't render the rest of the UI when fuzzy finder is shown
         }
 
-        self.signal_list
-            .render(self.layout.signal_list, buf, &mut self.state);
-        self.waveform
-            .render(self.layout.waveform, buf, &mut self.state);
-        self.marker_names
-            .render(self.layout.marker_names, buf, &mut self.state);
-        self.time_ruler
-            .render(self.layout.time_ruler, buf, &mut self.state);
+        // The focused pane shows the current window. The other pane of a split waveform is drawn
+        // with its own window swapped in for a moment.
+        let focused_pane = PaneLayout {
+            signal_list: self.layout.signal_list,
+            time_ruler: self.layout.time_ruler,
+            waveform: self.layout.waveform,
+        };
+        let bottom_focused = self
+            .state
+            .split_view
+            .as_ref()
+            .is_some_and(|split_view| split_view.bottom_focused);
+        self.render_pane(focused_pane, self.layout.other_pane.is_some(), buf);
+        if !bottom_focused {
+            self.marker_names
+                .render(self.layout.marker_names, buf, &mut self.state);
+        }
+        if let Some(other_pane) = self.layout.other_pane {
+            self.state.swap_pane_windows();
+            self.render_pane(other_pane, false, buf);
+            if bottom_focused {
+                self.marker_names
+                    .render(self.layout.marker_names, buf, &mut self.state);
+            }
+            self.state.swap_pane_windows();
+        }
+        self.minimap
+            .render(self.layout.minimap, buf, &mut self.state);
         self.command_input
             .render(self.layout.command_bar, buf, &mut self.state);
 
-        // Command result message if there is one
-        if let Some(ref result_message) = self.state.command_state().result_message {
-            let status_style = if self.state.command_state().result_is_error {
-                Style::default().fg(Color::Red)
-            } else {
-                Style::default().fg(Color::Green)
-            };
+        // Recent command results, in the rows left for them above the command bar
+        let lines: Vec<Line> = self
+            .result_message_lines()
+            .into_iter()
+            .map(|(line, is_error)| {
+                let color = if is_error { Color::Red } else { Color::Green };
+                Line::styled(line, Style::default().fg(color))
+            })
+            .collect();
+        Paragraph::new(lines).render(self.layout.command_result, buf);
+
+        if self.state.transitions_popup.is_some() {
+            self.transitions.render(area, buf, &mut self.state);
+        }
+
+        if self.state.grep_popup.is_some() {
+            self.grep.render(area, buf, &mut self.state);
+        }
+
+        if self.state.sample_popup.is_some() {
+            self.sample.render(area, buf, &mut self.state);
+        }
+
+        if self.state.show_marker_list {
+            self.marker_list.render(area, buf, &mut self.state);
+        }
+
+        if self.state.show_view_list {
+            self.view_list.render(area, buf, &mut self.state);
+        }
+
+        if self.state.show_stats {
+            self.stats.render(area, buf, &mut self.state);
+        }
+
+        if self.state.show_trace {
+            self.trace.render(area, buf, &mut self.state);
+        }
+
+        if self.state.show_messages {
+            self.messages.render(area, buf, &mut self.state);
+        }
+
+        if self.state.show_parse_warnings {
+            self.parse_warnings.render(area, buf, &mut self.state);
+        }
+
+        if self.state.show_file_info {
+            self.file_info.render(area, buf, &mut self.state);
+        }
+
+        if self.state.mode == AppMode::FileBrowser {
+            self.file_browser.render(area, buf, &mut self.state);
+        }
+
+        if self.state.show_command_list {
+            let commands = self.command_mode.parser().registry().commands();
+            CommandListWidget { commands }.render(area, buf, &mut self.state);
+        }
+
+        // Widgets keep their colors, which monochrome terminals don't get to see
+        self.state.theme().strip_colors(area, buf);
+    }
+}
 
-            // Create a temporary area just above the command bar for the result message
-            let msg_area = Rect::new(
-                self.layout.command_bar.x,
-                self.layout.command_bar.y.saturating_sub(1),
-                self.layout
-                    .command_bar
-                    .width
-                    .min(result_message.len() as u16),
-                1,
+// Splits a message into lines no wider than `width`, breaking at newlines and, where possible,
+// between words. Lines past `max_lines` are dropped and the last line kept ends with '…'.
+pub fn wrap_message(message: &str, width: usize, max_lines: usize) -> Vec<String> {
+    if width == 0 {
+        return Vec::new();
+    }
+
+    let mut lines = Vec::new();
+    for paragraph in message.lines() {
+        let mut rest: Vec<char> = paragraph.chars().collect();
+        loop {
+            if rest.len() <= width {
+                lines.push(rest.iter().collect::<String>());
+                break;
+            }
+            // Break after the last space that fits, or mid-word if there is none
+            let split = rest[..=width]
+                .iter()
+                .rposition(|c| *c == ' ')
+                .filter(|i| *i > 0)
+                .unwrap_or(width);
+            lines.push(
+                rest[..split]
+                    .iter()
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string(),
             );
+            rest = rest[split..]
+                .iter()
+                .copied()
+                .skip_while(|c| *c == ' ')
+                .collect();
+        }
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        if let Some(last) = lines.last_mut() {
+            let mut kept: String = last.chars().take(width - 1).collect();
+            kept.push('…');
+            *last = kept;
+        }
+    }
+    lines
+}
 
-            Paragraph::new(result_message.clone())
-                .style(status_style)
-                .render(msg_area, buf);
+// Whether a key is `binding`, with or without Shift. Letters come in capitalized with Shift, so
+// `h` with Shift arrives as `H`.
+fn is_key_or_shifted(key: &KeyEvent, binding: KeyCode) -> bool {
+    match (key.code, binding) {
+        (KeyCode::Char(pressed), KeyCode::Char(bound)) => {
+            pressed == bound
+                || (bound.is_ascii_lowercase() && pressed == bound.to_ascii_uppercase())
         }
+        (pressed, bound) => pressed == bound,
     }
 }
 
+// Whether a key was pressed with Shift, which capitalizes letters
+fn is_shifted(key: &KeyEvent) -> bool {
+    key.modifiers.contains(KeyModifiers::SHIFT)
+        || matches!(key.code, KeyCode::Char(c) if c.is_ascii_uppercase())
+}
+
+// The view slot of a digit key, if it is one
+fn view_slot(code: KeyCode) -> Option<u8> {
+    let KeyCode::Char(c) = code else {
+        return None;
+    };
+    let slot = c.to_digit(10)? as u8;
+    constants::VIEW_SLOTS.contains(&slot).then_some(slot)
+}
+
 #[cfg(test)]
 mod tests {
     use super::App;
     use crate::{
-        command_mode::CommandModeStateAccess,
+        command_mode::{builder::CommandBuilder, CommandModeStateAccess},
         config,
-        constants::WAVEFORM_HEIGHT,
+        constants::{self, WAVEFORM_HEIGHT},
         fuzzy_finder::FuzzyFinderStateAccess,
         parsers::types::{Value, WaveValue},
+        state::AppState,
         types::AppMode,
+        ui::{layout::minimum_size, widgets::time_ruler::calculate_tick_interval},
     };
     use crossterm::event::{
         KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
     };
     use insta::assert_snapshot;
-    use ratatui::{backend::TestBackend, Terminal};
+    use ratatui::{backend::TestBackend, style::Color, Terminal};
 
     fn setup_arrow_key_test_app(time_start: u64, time_range: u64) -> App {
         use crate::parsers::types::{Value, WaveValue};
@@ -684,6 +2186,38 @@ mod tests {
         assert_snapshot!(terminal.backend());
     }
 
+    #[test]
+    fn test_help_menu_lists_registered_commands_and_bindings() {
+        let mut app = App::with_config(config::AppConfig::default());
+        app.state.config.keybindings.zoom_in = KeyCode::Char('i');
+        app.state.config.keybindings.yank = KeyCode::Null;
+        app.command_mode.parser_mut().registry_mut().register(
+            CommandBuilder::new(
+                "zzdummy",
+                "A command registered after startup, with a description too long for one line",
+                |_args, _state: &mut AppState| Ok(String::new()),
+            )
+            .alias("zzd")
+            .build(),
+        );
+        app.state.show_help = true;
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        let text = format!("{:?}", terminal.backend());
+        assert!(text.contains("'i' - Zoom in"));
+        assert!(!text.contains("Copy value at primary marker"));
+
+        // The registered command is listed last, wrapped to the popup
+        app.state.help_menu_scroll = usize::MAX;
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+    }
+
     #[test]
     fn test_render_app_with_test_data() {
         use crate::parsers::types::{Value, WaveValue};
@@ -736,515 +2270,2876 @@ mod tests {
     }
 
     #[test]
-    fn test_render_app_with_bus_values() {
-        use crate::parsers::types::{Value, WaveValue};
-        let mut app = App::with_config(config::AppConfig::default());
+    fn test_render_app_with_bus_values() {
+        use crate::parsers::types::{Value, WaveValue};
+        let mut app = App::with_config(config::AppConfig::default());
+
+        // Add test data with binary and bus signals
+        app.state.waveform_data.signals = vec![
+            "binary_signal".to_string(),
+            "narrow_bus".to_string(),
+            "wide_bus".to_string(),
+            "mixed_bus".to_string(),
+        ];
+        app.state.displayed_signals = app.state.waveform_data.signals.clone();
+        app.state.waveform_data.max_time = 200;
+
+        // Add a binary signal
+        app.state.waveform_data.values.insert(
+            "binary_signal".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (100, WaveValue::Binary(Value::V1)),
+            ],
+        );
+
+        // Add a narrow bus (4-bit)
+        app.state.waveform_data.values.insert(
+            "narrow_bus".to_string(),
+            vec![
+                (0, WaveValue::Bus("0A".into())),
+                (80, WaveValue::Bus("0F".into())),
+            ],
+        );
+
+        // Add a wide bus (16-bit)
+        app.state.waveform_data.values.insert(
+            "wide_bus".to_string(),
+            vec![
+                (0, WaveValue::Bus("DEAD".into())),
+                (50, WaveValue::Bus("BEEF".into())),
+                (150, WaveValue::Bus("CAFE".into())),
+            ],
+        );
+
+        // Add a bus with x/z values
+        app.state.waveform_data.values.insert(
+            "mixed_bus".to_string(),
+            vec![
+                (0, WaveValue::Bus("00".into())),
+                (60, WaveValue::Bus("xZ".into())),
+                (120, WaveValue::Bus("FF".into())),
+            ],
+        );
+
+        // Set up the view parameters
+        app.state.time_start = 0;
+        app.state.time_range = 200;
+
+        // Render the app
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_tiny_terminal_shows_placeholder() {
+        for (width, height) in [(10, 5), (25, 8)] {
+            let mut app = setup_arrow_key_test_app(0, 1000);
+            app.state.primary_marker = Some(150);
+
+            let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+            terminal
+                .draw(|frame| frame.render_widget(&mut app, frame.area()))
+                .unwrap();
+            assert!(!app.layout.is_usable());
+            assert_snapshot!(
+                format!("render_tiny_terminal_{}x{}", width, height),
+                terminal.backend()
+            );
+
+            // Clicks don't place markers in the hidden waveform
+            app.handle_mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: width - 1,
+                row: height / 2,
+                modifiers: KeyModifiers::empty(),
+            });
+            assert_eq!(app.state.primary_marker, Some(150));
+        }
+    }
+
+    #[test]
+    fn test_render_at_minimum_size_shows_ui() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let (width, height) = minimum_size(&app.state.config, 0, None);
+
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert!(app.layout.is_usable());
+
+        // One column or row less is too small
+        for (width, height) in [(width - 1, height), (width, height - 1)] {
+            terminal.backend_mut().resize(width, height);
+            terminal
+                .draw(|frame| frame.render_widget(&mut app, frame.area()))
+                .unwrap();
+            assert!(!app.layout.is_usable());
+        }
+    }
+
+    #[test]
+    fn test_render_app_with_markers() {
+        use crate::parsers::types::{Value, WaveValue};
+        let mut app = App::with_config(config::AppConfig::default());
+
+        // Add some test data
+        app.state.waveform_data.signals.push("signal".to_string());
+        app.state.displayed_signals.push("signal".to_string());
+        app.state.waveform_data.max_time = 100;
+        app.state.waveform_data.values.insert(
+            "signal".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (50, WaveValue::Binary(Value::V1)),
+            ],
+        );
+
+        // Set markers
+        app.state.primary_marker = Some(25);
+        app.state.secondary_marker = Some(75);
+
+        // Render the app
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_app_in_command_mode() {
+        let mut app = App::with_config(config::AppConfig::default());
+
+        // Enter command mode
+        app.state.mode = AppMode::Command;
+        app.state.command_state_mut().input_buffer = "goto 50".to_string();
+        app.state.command_state_mut().cursor_position = 7; // Cursor after "goto 50"
+
+        // Render the app
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_app_with_command_result() {
+        let mut app = App::with_config(config::AppConfig::default());
+
+        // Set a command result message
+        app.show_result("Command executed successfully".to_string(), false);
+
+        // Render the app
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_stacked_command_results() {
+        let mut app = App::with_config(config::AppConfig::default());
+        app.show_result("Unknown command: zoon".to_string(), true);
+        app.show_result("Zoomed in".to_string(), false);
+        app.show_result("Moved to time 500".to_string(), false);
+        app.show_result("Signal not found: top.foo".to_string(), true);
+
+        // The three most recent results are stacked, each in the color of its kind
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_eq!(app.layout.command_result.height, 3);
+        assert_eq!(
+            terminal.backend().buffer()[(0, 15)].fg,
+            ratatui::style::Color::Green
+        );
+        assert_eq!(
+            terminal.backend().buffer()[(0, 16)].fg,
+            ratatui::style::Color::Red
+        );
+        assert_snapshot!(terminal.backend());
+
+        // Once the successes fade, both errors are left until a key is pressed
+        let long_ago = std::time::Instant::now()
+            - std::time::Duration::from_secs(constants::COMMAND_RESULT_HIDE_THRESHOLD_SECONDS);
+        for result in &mut app.state.command_state_mut().results {
+            result.time = long_ago;
+        }
+        app.expire_result_message();
+        let shown: Vec<&str> = app
+            .state
+            .command_state()
+            .shown_results()
+            .iter()
+            .map(|result| result.message.as_str())
+            .collect();
+        assert_eq!(
+            shown,
+            vec!["Unknown command: zoon", "Signal not found: top.foo"]
+        );
+        press(&mut app, KeyCode::Char('x'), KeyModifiers::empty());
+        assert!(app.state.command_state().shown_results().is_empty());
+    }
+
+    #[test]
+    fn test_render_messages() {
+        let mut app = App::with_config(config::AppConfig::default());
+        app.run_command("zoom 2").unwrap();
+        let _ = app.run_command("zoon 2");
+        app.state.command_state_mut().dismiss_results();
+
+        // Results that are no longer shown are still listed, the most recent at the bottom
+        app.run_command("messages").unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        press(&mut app, KeyCode::Esc, KeyModifiers::empty());
+        assert!(!app.state.show_messages);
+    }
+
+    #[test]
+    fn test_render_app_in_fuzzy_finder_mode() {
+        let mut app = App::with_config(config::AppConfig::default());
+
+        // Setup signals
+        let signals = vec![
+            "signal_1".to_string(),
+            "signal_2".to_string(),
+            "test_signal".to_string(),
+            "data_bus".to_string(),
+        ];
+
+        // Enter fuzzy finder mode
+        app.state.mode = AppMode::FuzzyFinder;
+        app.state.fuzzy_finder_state_mut().set_signals(signals, &[]);
+
+        // Add a query and handle input character by character
+        for c in "sig".chars() {
+            app.state.fuzzy_finder_state_mut().handle_input(c);
+        }
+
+        // Select the first signal
+        app.state.fuzzy_finder_state_mut().toggle_selected_signal();
+
+        // Render the app
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_fuzzy_finder_renders_only_visible_rows() {
+        let mut app = App::with_config(config::AppConfig::default());
+        let signals: Vec<String> = (0..20_000).map(|i| format!("sig_{:05}", i)).collect();
+        app.state.mode = AppMode::FuzzyFinder;
+        app.state.fuzzy_finder_state_mut().set_signals(signals, &[]);
+        app.state
+            .fuzzy_finder_state_mut()
+            .list_state
+            .select(Some(15_000));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        // The selected row is the last visible one
+        let rows: Vec<String> = (0..20)
+            .map(|y| {
+                (0..80)
+                    .map(|x| terminal.backend().buffer()[(x, y)].symbol())
+                    .collect()
+            })
+            .collect();
+        let selected_row = rows.iter().position(|row| row.contains("sig_15000"));
+        assert!(selected_row.is_some());
+        assert!(!rows[selected_row.unwrap() + 1].contains("sig_15001"));
+        assert!(rows[selected_row.unwrap() - 1].contains("sig_14999"));
+
+        // Moving down scrolls by one row
+        app.state.fuzzy_finder_state_mut().select_next();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        let row: String = (0..80)
+            .map(|x| {
+                terminal.backend().buffer()[(x, selected_row.unwrap() as u16)]
+                    .symbol()
+                    .to_string()
+            })
+            .collect();
+        assert!(row.contains("sig_15001"));
+    }
+
+    #[test]
+    fn test_render_fuzzy_finder_activity() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.state.waveform_data.signals.push("reset".to_string());
+        app.state
+            .waveform_data
+            .values
+            .insert("reset".to_string(), vec![(0, WaveValue::Binary(Value::V1))]);
+        let signals = app.state.waveform_data.signals.clone();
+        app.state.fuzzy_finder_state_mut().set_signals(signals, &[]);
+        app.run_command("findsignal").unwrap();
+
+        let ctrl_s = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+        app.handle_key(ctrl_s);
+        assert!(app.state.fuzzy_finder_state().sort_by_activity);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 14)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+
+        // The annotation is dimmed
+        let buffer = terminal.backend().buffer();
+        let rows: Vec<String> = (0..14)
+            .map(|y| (0..80).map(|x| buffer[(x, y)].symbol()).collect())
+            .collect();
+        let row = rows
+            .iter()
+            .position(|text| text.contains("reset (0 changes)"))
+            .unwrap();
+        let byte = rows[row].find("(0 changes)").unwrap();
+        let column = rows[row][..byte].chars().count();
+        assert_eq!(buffer[(column as u16, row as u16)].fg, Color::DarkGray);
+        assert_ne!(buffer[(column as u16 - 2, row as u16)].fg, Color::DarkGray);
+
+        app.handle_key(ctrl_s);
+        assert!(!app.state.fuzzy_finder_state().sort_by_activity);
+    }
+
+    #[test]
+    fn test_constant_signals_are_dimmed() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.state.waveform_data.signals.push("reset".to_string());
+        app.state
+            .waveform_data
+            .values
+            .insert("reset".to_string(), vec![(0, WaveValue::Binary(Value::V1))]);
+        app.state.displayed_signals.push("reset".to_string());
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        let list = app.layout.signal_list;
+        let name_fg = |index: usize| {
+            let y = list.y + 1 + (index * WAVEFORM_HEIGHT) as u16;
+            buffer[(list.x + 1, y)].fg
+        };
+        assert_eq!(name_fg(3), Color::DarkGray);
+        assert_ne!(name_fg(1), Color::DarkGray);
+    }
+
+    #[test]
+    fn test_fuzzy_finder_maintains_signal_order() {
+        let config = config::AppConfig::default();
+        let mut app = App::with_config(config);
+
+        // Setup test data with signals in specific order
+        app.state.waveform_data.signals = vec![
+            "first_signal".to_string(),
+            "middle_signal".to_string(),
+            "last_signal".to_string(),
+        ];
+
+        // Add some test values
+        app.state.waveform_data.values.insert(
+            "first_signal".to_string(),
+            vec![(0, WaveValue::Binary(Value::V0))],
+        );
+        app.state.waveform_data.values.insert(
+            "middle_signal".to_string(),
+            vec![(0, WaveValue::Binary(Value::V1))],
+        );
+        app.state.waveform_data.values.insert(
+            "last_signal".to_string(),
+            vec![(0, WaveValue::Binary(Value::V0))],
+        );
+
+        // Enter fuzzy finder mode
+        app.state.mode = AppMode::FuzzyFinder;
+        let signals = app.state.waveform_data.signals.clone();
+        app.state.fuzzy_finder_state_mut().set_signals(signals, &[]);
+
+        // Select signals in reverse order
+        app.state
+            .fuzzy_finder_state_mut()
+            .list_state
+            .select(Some(2)); // last_signal
+        app.state.fuzzy_finder_state_mut().toggle_selected_signal();
+
+        app.state
+            .fuzzy_finder_state_mut()
+            .list_state
+            .select(Some(0)); // first_signal
+        app.state.fuzzy_finder_state_mut().toggle_selected_signal();
+
+        // Accept selection
+        app.handle_fuzzy_finder_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+
+        // Verify signals are displayed in the original order
+        assert_eq!(app.state.displayed_signals.len(), 2);
+        assert_eq!(app.state.displayed_signals[0], "first_signal");
+        assert_eq!(app.state.displayed_signals[1], "last_signal");
+
+        // Verify app is now in normal mode
+        assert_eq!(app.state.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_fuzzy_finder_keeps_manual_signal_order() {
+        let mut app = setup_arrow_key_test_app(0, 100);
+        app.state.waveform_data.signals.push("reset".to_string());
+        let signals = app.state.waveform_data.signals.clone();
+        app.state.fuzzy_finder_state_mut().set_signals(signals, &[]);
+
+        // Move "enable" to the top
+        app.state.selected_signal = 2;
+        let shift_up = KeyEvent::new(KeyCode::Up, KeyModifiers::SHIFT);
+        app.handle_key(shift_up);
+        app.handle_key(shift_up);
+        assert_eq!(app.state.displayed_signals, vec!["enable", "clock", "data"]);
+
+        // Add "reset", the only signal not displayed yet
+        app.run_command("findsignal").unwrap();
+        assert_eq!(app.state.mode, AppMode::FuzzyFinder);
+        app.state
+            .fuzzy_finder_state_mut()
+            .list_state
+            .select(Some(3));
+        app.handle_key(KeyEvent::new(KeyCode::Char(' '), KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+
+        assert_eq!(
+            app.state.displayed_signals,
+            vec!["enable", "clock", "data", "reset"]
+        );
+        // The selection stays on the moved signal
+        assert_eq!(app.state.selected_signal, 0);
+    }
+
+    #[test]
+    fn test_arrow_keys_with_empty_signal_list_do_not_panic() {
+        let mut app = App::with_config(config::AppConfig::default());
+
+        app.state.waveform_data.max_time = 1000;
+        app.state.displayed_signals = vec![];
+        app.state.selected_signal = 0;
+
+        app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
+        app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
+        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+    }
+
+    #[test]
+    fn test_arrow_keys_up_down_signal_selection() {
+        let mut app = App::with_config(config::AppConfig::default());
+
+        app.state.displayed_signals = vec![
+            "signal_1".to_string(),
+            "signal_2".to_string(),
+            "signal_3".to_string(),
+        ];
+
+        // Start at the first signal
+        app.state.selected_signal = 0;
+
+        app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        assert_eq!(app.state.selected_signal, 1);
+
+        app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        assert_eq!(app.state.selected_signal, 2);
+
+        // Signal should not wrap
+        app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        assert_eq!(app.state.selected_signal, 2);
+
+        app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
+        assert_eq!(app.state.selected_signal, 1);
+
+        app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
+        assert_eq!(app.state.selected_signal, 0);
+
+        // Signal should not wrap
+        app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
+        assert_eq!(app.state.selected_signal, 0);
+    }
+
+    #[test]
+    fn test_delete_signal_key_moves_selection_to_next_signal() {
+        let mut app = App::with_config(config::AppConfig::default());
+        app.state.waveform_data.signals = vec![
+            "signal_1".to_string(),
+            "signal_2".to_string(),
+            "signal_3".to_string(),
+        ];
+        app.state.displayed_signals = app.state.waveform_data.signals.clone();
+        app.state.selected_signal = 1;
+
+        let delete_key = app.state.config.keybindings.delete_signal;
+        app.handle_input(KeyEvent::new(delete_key, KeyModifiers::empty()));
+
+        assert_eq!(app.state.displayed_signals, vec!["signal_1", "signal_3"]);
+        assert_eq!(app.state.selected_signal, 1);
+        assert_eq!(app.state.waveform_data.signals.len(), 3);
+    }
+
+    #[test]
+    fn test_delete_last_signal_in_list() {
+        let mut app = App::with_config(config::AppConfig::default());
+        app.state.displayed_signals = vec!["signal_1".to_string(), "signal_2".to_string()];
+        app.state.selected_signal = 1;
+        app.state.signal_scroll_offset = 1;
+
+        let delete_key = app.state.config.keybindings.delete_signal;
+        app.handle_input(KeyEvent::new(delete_key, KeyModifiers::empty()));
+        assert_eq!(app.state.displayed_signals, vec!["signal_1"]);
+        assert_eq!(app.state.selected_signal, 0);
+        assert_eq!(app.state.signal_scroll_offset, 0);
+
+        // Deleting the only remaining signal leaves an empty list without panicking
+        app.handle_input(KeyEvent::new(delete_key, KeyModifiers::empty()));
+        assert!(app.state.displayed_signals.is_empty());
+        assert_eq!(app.state.selected_signal, 0);
+
+        app.handle_input(KeyEvent::new(delete_key, KeyModifiers::empty()));
+        assert!(app.state.displayed_signals.is_empty());
+    }
+
+    #[test]
+    fn test_arrow_keys_left_once() {
+        let mut app = setup_arrow_key_test_app(400, 200);
+        app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_arrow_keys_left_twice() {
+        let mut app = setup_arrow_key_test_app(400, 200);
+
+        for _ in 0..2 {
+            app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_arrow_keys_right_once() {
+        let mut app = setup_arrow_key_test_app(400, 200);
+        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_arrow_keys_right_twice() {
+        let mut app = setup_arrow_key_test_app(400, 200);
+
+        for _ in 0..2 {
+            app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+        }
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_arrow_keys_right_twice_then_left_once() {
+        let mut app = setup_arrow_key_test_app(400, 200);
+
+        for _ in 0..2 {
+            app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+        }
+        app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_arrow_keys_left_at_time_start_does_not_move() {
+        let mut app = setup_arrow_key_test_app(0, 200);
+        app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_pan_step_modifiers() {
+        let mut app = setup_arrow_key_test_app(400, 200);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        // A whole page with Shift, stopping at both ends
+        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::SHIFT));
+        assert_eq!(app.state.time_start, 600);
+        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::SHIFT));
+        assert_eq!(app.state.time_start, 800);
+        for _ in 0..5 {
+            app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT));
+        }
+        assert_eq!(app.state.time_start, 0);
+
+        // One ruler tick with Alt
+        let tick = calculate_tick_interval(200, app.layout.time_ruler.width as u64);
+        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::ALT));
+        assert_eq!(app.state.time_start, tick);
+
+        // The configured fraction of the page otherwise
+        app.state.config.ui.pan_fraction = 0.01;
+        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+        assert_eq!(app.state.time_start, tick + 2);
+        assert_eq!(app.state.time_range, 200);
+    }
+
+    #[test]
+    fn test_arrow_keys_right_at_time_end_does_not_move() {
+        let mut app = setup_arrow_key_test_app(800, 200);
+        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_config_changes_are_seen_when_rendering() {
+        let mut config = config::AppConfig::default();
+        config.ui.signal_list_width = 40;
+        let mut app = App::with_config(config);
+        assert_eq!(app.state.config.ui.signal_list_width, 40);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_eq!(app.layout.signal_list.width, 40);
+
+        // The config changed by a command is the one the next frame is laid out with
+        app.run_command("set ui.signal_list_width 25").unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_eq!(app.layout.signal_list.width, 25);
+    }
+
+    #[test]
+    fn test_quoted_command_arguments() {
+        let mut app = setup_arrow_key_test_app(400, 200);
+
+        app.run_command("marker add \"reset done\" 100").unwrap();
+        assert_eq!(app.state.saved_markers[0].name, "reset done");
+
+        assert_eq!(
+            app.run_command("marker add \"\" 100"),
+            Err("Marker name cannot be empty".to_string())
+        );
+        assert_eq!(
+            app.run_command("marker remove \"reset done"),
+            Err("Missing closing quote".to_string())
+        );
+        app.run_command("marker remove \"reset done\"").unwrap();
+        assert!(app.state.saved_markers.is_empty());
+    }
+
+    #[test]
+    fn test_compound_alias() {
+        let mut app = setup_arrow_key_test_app(400, 200);
+
+        app.run_command("alias mark goto 300; marker add \"fail point\"")
+            .unwrap();
+        app.state.primary_marker = Some(120);
+        app.run_command("mark").unwrap();
+        assert_eq!(app.state.saved_markers[0].name, "fail point");
+        assert_eq!(app.state.saved_markers[0].time, 120);
+
+        // The alias stops at the marker that now already exists
+        assert_eq!(
+            app.run_command("mark"),
+            Err("'marker add \"fail point\"': Marker 'fail point' already exists".to_string())
+        );
+        assert_eq!(
+            app.run_command("alias zf zoomfull"),
+            Err("'zf' is already a command".to_string())
+        );
+    }
+
+    #[test]
+    fn test_center_keys_clamp_near_edges() {
+        let mut app = setup_arrow_key_test_app(400, 200);
+        let z = KeyEvent::new(KeyCode::Char('z'), KeyModifiers::empty());
+
+        // Without a primary marker the view stays put
+        app.handle_input(z);
+        assert_eq!(app.state.time_start, 400);
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some("Primary marker not set")
+        );
+
+        app.state.primary_marker = Some(30);
+        app.handle_input(z);
+        assert_eq!(app.state.time_start, 0);
+
+        app.state.primary_marker = Some(980);
+        app.handle_input(z);
+        assert_eq!((app.state.time_start, app.state.time_range), (800, 200));
+
+        app.handle_input(KeyEvent::new(KeyCode::Char('Z'), KeyModifiers::SHIFT));
+        assert_eq!(app.state.primary_marker, Some(900));
+    }
+
+    // Create an app with a large number of signals so that scrolling is required to view all of
+    // them.
+    fn setup_up_down_scroll_test_app() -> App {
+        use crate::parsers::types::{Value, WaveValue};
+        let mut app = App::with_config(config::AppConfig::default());
+
+        // Set up waveform data
+        app.state.waveform_data.max_time = 1000;
+        app.state.time_start = 0;
+        app.state.time_range = 400;
+        app.state.selected_signal = 0;
+        app.state.signal_scroll_offset = 0;
+
+        // Add more signals than can fit in the viewport
+        for c in 'a'..='z' {
+            let signal_name = format!("signal_{}", c);
+            app.state.waveform_data.signals.push(signal_name.clone());
+
+            // Create values for each signal with the same pattern
+            let mut signal_values = Vec::new();
+            signal_values.extend(vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (50, WaveValue::Binary(Value::V1)),
+                (100, WaveValue::Binary(Value::V0)),
+                (150, WaveValue::Binary(Value::V1)),
+                (200, WaveValue::Binary(Value::V0)),
+            ]);
+
+            app.state
+                .waveform_data
+                .values
+                .insert(signal_name, signal_values);
+        }
+
+        // Initialize displayed_signals with the same signals
+        app.state.displayed_signals = app.state.waveform_data.signals.clone();
+
+        app
+    }
+
+    #[test]
+    fn test_scroll_down() {
+        let mut app = setup_up_down_scroll_test_app();
+
+        // We need to render the app once to initialize the layout
+        // so that adjust_scroll_if_needed has the correct dimensions
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        // Now move down far enough to trigger scrolling
+        for _ in 0..10 {
+            app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        }
+
+        // Render again to capture the scrolled state
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        // The snapshot assert should capture signals further in the alphabet than a-f
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_scroll_up() {
+        let mut app = setup_up_down_scroll_test_app();
+
+        // We need to render the app once to initialize the layout
+        // so that adjust_scroll_if_needed has the correct dimensions
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        // Now move down far enough to trigger scrolling
+        for _ in 0..10 {
+            app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        }
+
+        // Scroll back up
+        for _ in 0..10 {
+            app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
+        }
+
+        // Render again to capture the scrolled state
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        // The snapshot assert should capture signals a-f
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_mouse_signal_selection() {
+        let mut app = App::with_config(config::AppConfig::default());
+        for i in 0..5 {
+            let signal_name = format!("signal_{}", i);
+            app.state.waveform_data.signals.push(signal_name.clone());
+            let mut signal_values = Vec::new();
+            signal_values.extend(vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (50, WaveValue::Binary(Value::V1)),
+                (100, WaveValue::Binary(Value::V0)),
+            ]);
+
+            app.state
+                .waveform_data
+                .values
+                .insert(signal_name, signal_values);
+        }
+        app.state.displayed_signals = app.state.waveform_data.signals.clone();
+
+        // We need to render the app once to initialize the layout
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        // Create a mouse event for clicking on multiple different signals.
+        // Calculate position (each signal takes WAVEFORM_HEIGHT rows)
+        for signal_index in [2, 1, 3, 0] {
+            // +1 to click in middle of signal
+            let y_position = app.layout.signal_list.y + (signal_index * WAVEFORM_HEIGHT) as u16 + 1;
+
+            let mouse_event = MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column: app.layout.signal_list.x + 2, // "Near" the beginning of the signal name
+                row: y_position,
+                modifiers: KeyModifiers::empty(),
+            };
+
+            // Handle the mouse event
+            app.handle_mouse(mouse_event);
+
+            // Verify the selection changed
+            assert_eq!(app.state.selected_signal, signal_index);
+        }
+    }
+
+    #[test]
+    fn test_mouse_signal_list_clicks_outside_rows_are_ignored() {
+        let mut app = setup_arrow_key_test_app(0, 100);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        app.state.selected_signal = 1;
+        let click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::empty(),
+        };
+
+        // The title row of the border, the left border, and one row past the last signal
+        let list = app.layout.signal_list;
+        let last_row = list.y + 1 + (app.state.displayed_signals.len() * WAVEFORM_HEIGHT) as u16;
+        for (column, row) in [
+            (list.x + 2, list.y),
+            (list.x, list.y + 1),
+            (list.x + 2, last_row),
+        ] {
+            app.handle_mouse(click(column, row));
+            assert_eq!(app.state.selected_signal, 1, "click at {},{}", column, row);
+        }
+
+        // The first row inside the border is the first signal
+        app.handle_mouse(click(list.x + 2, list.y + 1));
+        assert_eq!(app.state.selected_signal, 0);
+
+        // After shrinking below the layout's minimum height, clicks above the list don't panic
+        app.state.selected_signal = 1;
+        app.handle_resize(80, 3);
+        for row in 0..20 {
+            app.handle_mouse(click(app.layout.signal_list.x + 2, row));
+        }
+        assert_eq!(app.state.selected_signal, 1);
+    }
+
+    #[test]
+    fn test_mouse_selection_with_signal_heights() {
+        let mut app = setup_up_down_scroll_test_app();
+        for (signal, height) in [("signal_a", 1), ("signal_b", 4), ("signal_c", 1)] {
+            app.state.signal_heights.insert(signal.to_string(), height);
+        }
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        let click = |column, row| MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::empty(),
+        };
+
+        // Rows of the signal list: a, b four times, c, then d twice
+        let list = app.layout.signal_list;
+        for (row, signal_index) in [(0, 0), (1, 1), (4, 1), (5, 2), (6, 3), (7, 3), (8, 4)] {
+            app.handle_mouse(click(list.x + 2, list.y + 1 + row));
+            assert_eq!(app.state.selected_signal, signal_index, "row {}", row);
+        }
+
+        // The same rows of the waveform
+        drag_in_waveform_rows(&mut app, (10, 3), (30, 3), KeyModifiers::NONE);
+        assert_eq!(app.state.selected_signal, 1);
+        app.state.zoom_to(0, 1000);
+        drag_in_waveform_rows(&mut app, (10, 5), (30, 7), KeyModifiers::NONE);
+        assert_eq!(app.state.selected_signal, 2);
+
+        // Moving down scrolls just far enough to show the whole selected signal
+        for _ in 0..10 {
+            app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        }
+        let height = app.layout.waveform.height;
+        let visible = app.state.visible_signal_rows(height);
+        assert_eq!(visible.last().map(|(index, _, _)| *index), Some(12));
+        assert_eq!(
+            app.state.signal_scroll_offset,
+            app.state.scroll_offset_ending_at(12, height)
+        );
+        app.handle_mouse(click(list.x + 2, list.y + 1));
+        assert_eq!(app.state.selected_signal, app.state.signal_scroll_offset);
+    }
+
+    #[test]
+    fn test_hover_position_follows_mouse_over_waveform() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        let waveform = app.layout.waveform;
+        let mouse = |kind, column, row| MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::empty(),
+        };
+
+        app.handle_mouse(mouse(MouseEventKind::Moved, waveform.x + 5, waveform.y + 1));
+        assert_eq!(
+            app.state.hover_position,
+            Some((waveform.x + 5, waveform.y + 1))
+        );
+
+        // Leaving the waveform hides the readout
+        app.handle_mouse(mouse(MouseEventKind::Moved, 0, waveform.y + 1));
+        assert_eq!(app.state.hover_position, None);
+
+        // Without move events, the last click is shown instead
+        app.handle_mouse(mouse(
+            MouseEventKind::Down(MouseButton::Left),
+            waveform.x + 9,
+            waveform.y + 3,
+        ));
+        app.handle_mouse(mouse(
+            MouseEventKind::Up(MouseButton::Left),
+            waveform.x + 9,
+            waveform.y + 3,
+        ));
+        assert_eq!(
+            app.state.hover_position,
+            Some((waveform.x + 9, waveform.y + 3))
+        );
+    }
+
+    // Drag the left mouse button across the waveform from one column to another, in waveform
+    // coordinates
+    fn drag_in_waveform(app: &mut App, from: u16, to: u16, modifiers: KeyModifiers) {
+        drag_in_waveform_rows(app, (from, 1), (to, 1), modifiers);
+    }
+
+    // Drag the left mouse button across the waveform from one (column, row) to another, in
+    // waveform coordinates
+    fn drag_in_waveform_rows(
+        app: &mut App,
+        from: (u16, u16),
+        to: (u16, u16),
+        modifiers: KeyModifiers,
+    ) {
+        let (x, y) = (app.layout.waveform.x, app.layout.waveform.y);
+        for (kind, (column, row)) in [
+            (MouseEventKind::Down(MouseButton::Left), from),
+            (
+                MouseEventKind::Drag(MouseButton::Left),
+                ((from.0 + to.0) / 2, (from.1 + to.1) / 2),
+            ),
+            (MouseEventKind::Drag(MouseButton::Left), to),
+            (MouseEventKind::Up(MouseButton::Left), to),
+        ] {
+            app.handle_mouse(MouseEvent {
+                kind,
+                column: x + column,
+                row: y + row,
+                modifiers,
+            });
+        }
+    }
+
+    #[test]
+    fn test_drag_zoom_selects_topmost_signal() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        // Dragging up from the third signal to the second
+        drag_in_waveform_rows(&mut app, (30, 4), (10, 3), KeyModifiers::NONE);
+        assert_eq!(app.state.selected_signal, 1);
+
+        // Within the first signal's rows
+        app.state.zoom_to(0, 1000);
+        drag_in_waveform_rows(&mut app, (10, 1), (30, 1), KeyModifiers::NONE);
+        assert_eq!(app.state.selected_signal, 0);
+
+        // Starting on the last signal and ending below it
+        app.state.zoom_to(0, 1000);
+        drag_in_waveform_rows(&mut app, (10, 5), (30, 9), KeyModifiers::NONE);
+        assert_eq!(app.state.selected_signal, 2);
+
+        // Entirely below the last signal, only the time zooms
+        app.state.zoom_to(0, 1000);
+        drag_in_waveform_rows(&mut app, (10, 7), (30, 9), KeyModifiers::NONE);
+        assert_eq!(app.state.selected_signal, 2);
+        assert!(app.state.time_range < 1000);
+    }
+
+    #[test]
+    fn test_jump_keys_return_to_the_exact_window() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        app.state.set_window(130, 370);
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+
+        // A goto and a drag zoom are jumps, the pan in between isn't
+        app.run_command("goto 800").unwrap();
+        let after_goto = (app.state.time_start, app.state.time_range);
+        app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
+        let after_pan = (app.state.time_start, app.state.time_range);
+        drag_in_waveform_rows(&mut app, (10, 3), (30, 3), KeyModifiers::NONE);
+        assert_eq!(app.state.selected_signal, 1);
+        let after_drag = (app.state.time_start, app.state.time_range);
+        assert_eq!(app.state.jump_list.len(), 2);
+        assert_ne!(after_goto, after_pan);
+
+        app.handle_input(ctrl('o'));
+        assert_eq!((app.state.time_start, app.state.time_range), after_pan);
+        assert_eq!(app.state.selected_signal, 0);
+        app.handle_input(ctrl('o'));
+        assert_eq!((app.state.time_start, app.state.time_range), (130, 370));
+        app.handle_input(ctrl('o'));
+        assert_eq!((app.state.time_start, app.state.time_range), (130, 370));
+
+        app.handle_input(ctrl('i'));
+        app.handle_input(ctrl('i'));
+        assert_eq!((app.state.time_start, app.state.time_range), after_drag);
+        assert_eq!(app.state.selected_signal, 1);
+
+        // Plain o and i are not jumps
+        app.handle_input(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::empty()));
+        assert_eq!((app.state.time_start, app.state.time_range), after_drag);
+    }
+
+    #[test]
+    fn test_drag_zoom_selects_signal_with_scroll_offset() {
+        let mut app = setup_up_down_scroll_test_app();
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        app.state.signal_scroll_offset = 5;
+        app.state.selected_signal = 5;
+
+        drag_in_waveform_rows(&mut app, (10, 5), (30, 2), KeyModifiers::NONE);
+        assert_eq!(app.state.selected_signal, 6);
+        assert_eq!(app.state.signal_scroll_offset, 5);
+
+        // Measuring doesn't change the selection
+        drag_in_waveform_rows(&mut app, (10, 0), (30, 0), KeyModifiers::CONTROL);
+        assert_eq!(app.state.selected_signal, 6);
+    }
+
+    #[test]
+    fn test_drag_zoom_at_minimum_range_keeps_view_valid() {
+        let mut app = setup_arrow_key_test_app(500, 1);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        // Both ends of the selection land on the same time, which would be a zero-width window
+        drag_in_waveform(&mut app, 1, 8, KeyModifiers::NONE);
+        assert_eq!(app.state.time_range, 1);
+        assert!(app.state.time_start + app.state.time_range <= 1000);
+
+        // Single pixel drags are too small to zoom at all
+        drag_in_waveform(&mut app, 10, 11, KeyModifiers::NONE);
+        assert_eq!(app.state.time_range, 1);
+
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_ctrl_drag_places_markers_without_zooming() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        let width = app.layout.waveform.width;
+        drag_in_waveform(&mut app, 10, 40, KeyModifiers::CONTROL);
+
+        assert_eq!(
+            app.state.primary_marker,
+            Some(app.state.screen_pos_to_time(10, width))
+        );
+        assert_eq!(
+            app.state.secondary_marker,
+            Some(app.state.screen_pos_to_time(40, width))
+        );
+        assert_eq!(app.state.time_start, 0);
+        assert_eq!(app.state.time_range, 1000);
+        assert!(!app.state.is_dragging);
+    }
+
+    fn mouse_on_time_ruler(
+        app: &mut App,
+        kind: MouseEventKind,
+        column: u16,
+        modifiers: KeyModifiers,
+    ) {
+        app.handle_mouse(MouseEvent {
+            kind,
+            column: app.layout.time_ruler.x + column,
+            row: app.layout.time_ruler.y,
+            modifiers,
+        });
+    }
+
+    #[test]
+    fn test_time_ruler_click_places_markers() {
+        let mut app = setup_arrow_key_test_app(100, 500);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        let last_column = app.layout.time_ruler.width - 1;
+
+        let click = MouseEventKind::Down(MouseButton::Left);
+        let release = MouseEventKind::Up(MouseButton::Left);
+        mouse_on_time_ruler(&mut app, click, 0, KeyModifiers::NONE);
+        mouse_on_time_ruler(&mut app, release, 0, KeyModifiers::NONE);
+        assert_eq!(app.state.primary_marker, Some(100));
+
+        mouse_on_time_ruler(&mut app, click, last_column, KeyModifiers::NONE);
+        mouse_on_time_ruler(&mut app, release, last_column, KeyModifiers::NONE);
+        assert_eq!(app.state.primary_marker, Some(600));
+
+        mouse_on_time_ruler(&mut app, click, last_column, KeyModifiers::SHIFT);
+        assert_eq!(app.state.secondary_marker, Some(600));
+        assert_eq!(app.state.primary_marker, Some(600));
+        assert!(!app.state.is_scrubbing_time_ruler);
+
+        // The view doesn't change
+        assert_eq!((app.state.time_start, app.state.time_range), (100, 500));
+    }
+
+    #[test]
+    fn test_time_ruler_drag_scrubs_primary_marker() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        let width = app.layout.time_ruler.width;
+        let time_at = |column: u16| (column as f64 * 1000.0 / (width - 1) as f64).round() as u64;
+
+        mouse_on_time_ruler(
+            &mut app,
+            MouseEventKind::Down(MouseButton::Left),
+            10,
+            KeyModifiers::NONE,
+        );
+        assert_eq!(app.state.primary_marker, Some(time_at(10)));
+
+        let drag = MouseEventKind::Drag(MouseButton::Left);
+        mouse_on_time_ruler(&mut app, drag, 20, KeyModifiers::NONE);
+        assert_eq!(app.state.primary_marker, Some(time_at(20)));
+
+        // Dragging past the end of the ruler stops at the end of the window
+        mouse_on_time_ruler(&mut app, drag, width + 10, KeyModifiers::NONE);
+        assert_eq!(app.state.primary_marker, Some(1000));
+
+        mouse_on_time_ruler(
+            &mut app,
+            MouseEventKind::Up(MouseButton::Left),
+            30,
+            KeyModifiers::NONE,
+        );
+        assert!(!app.state.is_scrubbing_time_ruler);
+        assert_eq!(app.state.primary_marker, Some(1000));
+        assert!(!app.state.is_dragging);
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 1000));
+    }
+
+    fn app_with_minimap(time_start: u64, time_range: u64) -> App {
+        let mut app = setup_arrow_key_test_app(time_start, time_range);
+        app.state.config.ui.show_minimap = true;
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        app
+    }
+
+    fn mouse_on_minimap(app: &mut App, kind: MouseEventKind, column: u16) {
+        app.handle_mouse(MouseEvent {
+            kind,
+            column: app.layout.minimap.x + column,
+            row: app.layout.minimap.y,
+            modifiers: KeyModifiers::NONE,
+        });
+    }
+
+    #[test]
+    fn test_minimap_click_centers_view() {
+        let mut app = app_with_minimap(0, 100);
+        let minimap = app.layout.minimap;
+        assert_eq!(minimap.y, app.layout.time_ruler.y + 1);
+        assert_eq!(minimap.x, app.layout.time_ruler.x);
+
+        let column = minimap.width * 3 / 4;
+        mouse_on_minimap(&mut app, MouseEventKind::Down(MouseButton::Left), column);
+        let center = app.state.minimap_viewport(minimap.width).time_of(column);
+        assert_eq!(app.state.time_start, center - 50);
+        assert_eq!(app.state.time_range, 100);
+        // Panning doesn't fill up the zoom history
+        assert!(app.state.zoom_history.is_empty());
+
+        // Near the end the view stops at the end of the waveform
+        mouse_on_minimap(
+            &mut app,
+            MouseEventKind::Drag(MouseButton::Left),
+            minimap.width - 1,
+        );
+        assert_eq!(app.state.time_start, 900);
+
+        // Dragging keeps panning even off the strip, until the button is released
+        app.handle_mouse(MouseEvent {
+            kind: MouseEventKind::Drag(MouseButton::Left),
+            column: minimap.x,
+            row: app.layout.waveform.y + 2,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_eq!(app.state.time_start, 0);
+        assert!(!app.state.is_dragging);
+
+        mouse_on_minimap(&mut app, MouseEventKind::Up(MouseButton::Left), 0);
+        assert!(!app.state.is_panning_minimap);
+        assert_eq!(app.state.primary_marker, None);
+    }
+
+    #[test]
+    fn test_minimap_hidden_by_default() {
+        let mut app = setup_arrow_key_test_app(0, 100);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_eq!(app.layout.minimap.height, 0);
+        assert_eq!(app.layout.marker_names.y, app.layout.time_ruler.y + 1);
+    }
+
+    #[test]
+    fn test_resize_during_drag() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        app.state.selected_signal = 2;
+
+        // Start dragging near the right edge of the waveform, then shrink the terminal
+        let row = app.layout.waveform.y + 1;
+        let x = app.layout.waveform.x;
+        for (kind, column) in [
+            (MouseEventKind::Down(MouseButton::Left), 50),
+            (MouseEventKind::Drag(MouseButton::Left), 60),
+        ] {
+            app.handle_mouse(MouseEvent {
+                kind,
+                column: x + column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            });
+        }
+        assert!(app.state.is_dragging);
+
+        app.handle_resize(30, 7);
+        assert!(!app.state.is_dragging);
+        assert_eq!(app.state.drag_start, None);
+        assert_eq!(app.state.drag_current, None);
+
+        // The selected signal is still in view
+        let visible_signals = app.layout.waveform.height as usize / WAVEFORM_HEIGHT;
+        assert!(visible_signals > 0);
+        assert!(app.state.selected_signal >= app.state.signal_scroll_offset);
+        assert!(app.state.selected_signal < app.state.signal_scroll_offset + visible_signals);
+
+        // Releasing the mouse after the resize doesn't zoom or place markers
+        app.handle_mouse(MouseEvent {
+            kind: MouseEventKind::Up(MouseButton::Left),
+            column: 5,
+            row,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 1000));
+        assert_eq!(app.state.primary_marker, None);
+
+        let mut terminal = Terminal::new(TestBackend::new(30, 7)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_resize_reclamps_view_state() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.state.selected_signal = 7;
+        app.state.signal_scroll_offset = 5;
+        app.state.time_start = 950;
+        app.state.time_range = 100;
+
+        app.handle_resize(80, 20);
+        assert_eq!(app.state.selected_signal, 2);
+        assert_eq!(app.state.signal_scroll_offset, 0);
+        assert_eq!((app.state.time_start, app.state.time_range), (900, 100));
+    }
+
+    #[test]
+    fn test_alt_drag_prompts_for_region_name() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        let width = app.layout.waveform.width;
+        let start = app.state.screen_pos_to_time(10, width);
+        let end = app.state.screen_pos_to_time(40, width);
+        drag_in_waveform(&mut app, 40, 10, KeyModifiers::ALT);
+
+        // The view is unchanged and the command line asks for the name
+        assert_eq!(app.state.time_start, 0);
+        assert_eq!(app.state.time_range, 1000);
+        assert_eq!(app.state.mode, AppMode::Command);
+        assert_eq!(
+            app.state.command_state().input_buffer,
+            format!("region add \"\" {} {}", start, end)
+        );
+
+        for c in "rx burst".chars() {
+            app.handle_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        app.handle_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+
+        assert_eq!(app.state.mode, AppMode::Normal);
+        assert_eq!(
+            app.state.regions,
+            vec![crate::types::Region::new(
+                start,
+                end,
+                "rx burst".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_measure_mode_drag_places_markers_without_zooming() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.state.measure_mode = true;
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        let width = app.layout.waveform.width;
+
+        // Dragging right to left still puts the primary marker where the drag started
+        drag_in_waveform(&mut app, 40, 10, KeyModifiers::empty());
+
+        assert_eq!(
+            app.state.primary_marker,
+            Some(app.state.screen_pos_to_time(40, width))
+        );
+        assert_eq!(
+            app.state.secondary_marker,
+            Some(app.state.screen_pos_to_time(10, width))
+        );
+        assert_eq!(app.state.time_start, 0);
+        assert_eq!(app.state.time_range, 1000);
+    }
+
+    #[test]
+    fn test_plain_drag_zooms_to_selection() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        let width = app.layout.waveform.width;
+        let start = app.state.screen_pos_to_time(10, width);
+        let end = app.state.screen_pos_to_time(40, width);
+        drag_in_waveform(&mut app, 10, 40, KeyModifiers::empty());
+
+        assert_eq!(app.state.time_start, start);
+        assert_eq!(app.state.time_range, end - start);
+        assert_eq!(app.state.primary_marker, None);
+        assert_eq!(app.state.secondary_marker, None);
+    }
+
+    #[test]
+    fn test_zoom_back_after_drag_zoom_and_zoom_full() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        drag_in_waveform(&mut app, 10, 40, KeyModifiers::empty());
+        let zoomed = (app.state.time_start, app.state.time_range);
+
+        // Panning doesn't add to the zoom history
+        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+        let panned = (app.state.time_start, app.state.time_range);
+        assert_ne!(panned, zoomed);
+
+        app.handle_input(KeyEvent::new(KeyCode::Char('0'), KeyModifiers::empty()));
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 1000));
+
+        app.handle_input(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::empty()));
+        assert_eq!((app.state.time_start, app.state.time_range), panned);
+        app.handle_input(KeyEvent::new(KeyCode::Char('b'), KeyModifiers::empty()));
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 1000));
+
+        app.handle_input(KeyEvent::new(KeyCode::Char('B'), KeyModifiers::SHIFT));
+        assert_eq!((app.state.time_start, app.state.time_range), panned);
+    }
+
+    #[test]
+    fn test_resize_signal_list_keeps_markers_at_same_time() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        app.state.primary_marker = Some(500);
+        let old_waveform_width = app.layout.waveform.width;
+
+        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL));
+        assert_eq!(app.state.config.ui.signal_list_width, 22);
+        assert!(app.layout.waveform.width < old_waveform_width);
+
+        // Ctrl+Right resizes instead of panning
+        assert_eq!(app.state.time_start, 0);
+
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_eq!(app.state.primary_marker, Some(500));
+
+        // Clicks are converted to times using the new waveform width
+        let width = app.layout.waveform.width;
+        let column = width / 4;
+        app.handle_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: app.layout.waveform.x + column,
+            row: app.layout.waveform.y + 1,
+            modifiers: KeyModifiers::empty(),
+        });
+        app.handle_mouse(MouseEvent {
+            kind: MouseEventKind::Up(MouseButton::Left),
+            column: app.layout.waveform.x + column,
+            row: app.layout.waveform.y + 1,
+            modifiers: KeyModifiers::empty(),
+        });
+        assert_eq!(
+            app.state.primary_marker,
+            Some(app.state.screen_pos_to_time(column, width))
+        );
+    }
+
+    #[test]
+    fn test_resize_signal_list_keys_are_clamped() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        for _ in 0..50 {
+            app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL));
+        }
+        assert_eq!(app.state.config.ui.signal_list_width, 5);
+        for _ in 0..50 {
+            app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL));
+        }
+        assert_eq!(app.state.config.ui.signal_list_width, 80);
+    }
+
+    #[test]
+    fn test_drag_divider_resizes_signal_list() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let mut terminal = Terminal::new(TestBackend::new(100, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        let row = app.layout.waveform.y + 1;
+        let divider = app.layout.waveform.x;
+        for (kind, column) in [
+            (MouseEventKind::Down(MouseButton::Left), divider - 1),
+            (MouseEventKind::Drag(MouseButton::Left), 40),
+            (MouseEventKind::Drag(MouseButton::Left), 50),
+            (MouseEventKind::Up(MouseButton::Left), 50),
+        ] {
+            app.handle_mouse(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::empty(),
+            });
+        }
+
+        assert_eq!(app.state.config.ui.signal_list_width, 50);
+        assert_eq!(app.layout.waveform.x, 50);
+        assert!(!app.state.is_resizing_signal_list);
+
+        // Grabbing the divider doesn't place a marker or zoom
+        assert_eq!(app.state.primary_marker, None);
+        assert_eq!(app.state.time_range, 1000);
+    }
+
+    fn setup_transitions_test_app() -> App {
+        use crate::parsers::types::WaveValue;
+        let mut app = App::with_config(config::AppConfig::default());
+        app.state.waveform_data.signals = vec!["counter".to_string()];
+        app.state.displayed_signals = app.state.waveform_data.signals.clone();
+        app.state.waveform_data.values.insert(
+            "counter".to_string(),
+            (0..40)
+                .map(|i| (i * 10, WaveValue::Bus(format!("{:x}", i).into())))
+                .collect(),
+        );
+        app.state.waveform_data.max_time = 400;
+        app.state.time_start = 0;
+        app.state.time_range = 400;
+        app
+    }
+
+    #[test]
+    fn test_transitions_popup_enter_jumps_to_time() {
+        let mut app = setup_transitions_test_app();
+        app.run_command("transitions").unwrap();
+
+        for _ in 0..3 {
+            app.handle_transitions_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        }
+        app.handle_transitions_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
+        app.handle_transitions_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+
+        assert_eq!(app.state.primary_marker, Some(20));
+        assert!(app.state.transitions_popup.is_none());
+    }
+
+    // Asserts that each of the marker moves so far was added to the trace
+    fn assert_traced_moves(app: &App, moves: usize) {
+        let trace = app.state.trace.as_ref().unwrap();
+        assert_eq!(
+            trace.entries.iter().map(|entry| entry.count).sum::<usize>(),
+            moves
+        );
+        let marker = app.state.primary_marker.unwrap();
+        let value = app.state.get_value_at_marker("counter", marker).unwrap();
+        assert_eq!(trace.entries.last().unwrap().value, value.to_string());
+    }
+
+    #[test]
+    fn test_marker_moves_feed_the_trace() {
+        let mut app = setup_transitions_test_app();
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        app.run_command("trace counter").unwrap();
+        let key = |code| KeyEvent::new(code, KeyModifiers::empty());
+
+        // Clicking in the waveform
+        drag_in_waveform(&mut app, 10, 10, KeyModifiers::empty());
+        assert_traced_moves(&app, 1);
+
+        // Clicking on the time ruler
+        for kind in [
+            MouseEventKind::Down(MouseButton::Left),
+            MouseEventKind::Up(MouseButton::Left),
+        ] {
+            mouse_on_time_ruler(&mut app, kind, 30, KeyModifiers::empty());
+        }
+        assert_traced_moves(&app, 2);
+
+        // Jumping to a transition
+        app.run_command("transitions").unwrap();
+        for code in [KeyCode::Down, KeyCode::Down, KeyCode::Enter] {
+            app.handle_key(key(code));
+        }
+        assert_eq!(app.state.primary_marker, Some(20));
+        assert_traced_moves(&app, 3);
+
+        // Nudging it with the cursor
+        app.run_command("cursor").unwrap();
+        for code in [KeyCode::Char('L'), KeyCode::Char('m')] {
+            app.handle_key(key(code));
+        }
+        assert_traced_moves(&app, 4);
+
+        // Commands
+        app.run_command("center marker").unwrap();
+        assert_traced_moves(&app, 5);
+        app.run_command("marker add end 390").unwrap();
+        app.run_command("marker goto end").unwrap();
+        assert_traced_moves(&app, 6);
+        // Finding the same value again is counted in the same entry
+        app.run_command("marker goto end").unwrap();
+        assert_traced_moves(&app, 7);
+        assert_eq!(app.state.trace.as_ref().unwrap().entries.len(), 6);
+
+        // Measuring
+        drag_in_waveform(&mut app, 5, 20, KeyModifiers::CONTROL);
+        assert_traced_moves(&app, 8);
+
+        app.run_command("trace show").unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+        app.handle_key(key(KeyCode::Esc));
+        assert!(!app.state.show_trace);
+    }
+
+    #[test]
+    fn test_transitions_popup_esc_cancels() {
+        let mut app = setup_transitions_test_app();
+        app.run_command("tr").unwrap();
+        app.handle_transitions_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        app.handle_transitions_input(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+
+        assert_eq!(app.state.primary_marker, None);
+        assert!(app.state.transitions_popup.is_none());
+    }
 
-        // Add test data with binary and bus signals
-        app.state.waveform_data.signals = vec![
-            "binary_signal".to_string(),
-            "narrow_bus".to_string(),
-            "wide_bus".to_string(),
-            "mixed_bus".to_string(),
-        ];
-        app.state.displayed_signals = app.state.waveform_data.signals.clone();
-        app.state.waveform_data.max_time = 200;
+    #[test]
+    fn test_render_transitions_popup_scrolled() {
+        let mut app = setup_transitions_test_app();
+        app.run_command("transitions").unwrap();
+        app.state.command_state_mut().dismiss_results();
+
+        // Select a row past the bottom of the popup so it has to scroll
+        for _ in 0..25 {
+            app.handle_transitions_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        }
 
-        // Add a binary signal
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+
+        let popup = app.state.transitions_popup.as_ref().unwrap();
+        assert_eq!(popup.selected, 25);
+        assert_eq!(popup.scroll, 9);
+        assert_snapshot!(terminal.backend());
+    }
+
+    fn setup_grep_test_app() -> App {
+        let mut app = setup_transitions_test_app();
+        app.state.waveform_data.signals.push("flag".to_string());
+        app.state.displayed_signals.push("flag".to_string());
         app.state.waveform_data.values.insert(
-            "binary_signal".to_string(),
+            "flag".to_string(),
             vec![
                 (0, WaveValue::Binary(Value::V0)),
-                (100, WaveValue::Binary(Value::V1)),
+                (35, WaveValue::Binary(Value::V1)),
+                (300, WaveValue::Binary(Value::V0)),
             ],
         );
+        app.state.time_range = 40;
+        app
+    }
 
-        // Add a narrow bus (4-bit)
-        app.state.waveform_data.values.insert(
-            "narrow_bus".to_string(),
-            vec![
-                (0, WaveValue::Bus("0A".to_string())),
-                (80, WaveValue::Bus("0F".to_string())),
-            ],
+    #[test]
+    fn test_grep_popup_enter_jumps_to_match() {
+        let mut app = setup_grep_test_app();
+        app.run_command("grep 0x1").unwrap();
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some("2 match(es) of 0x1")
         );
 
-        // Add a wide bus (16-bit)
-        app.state.waveform_data.values.insert(
-            "wide_bus".to_string(),
-            vec![
-                (0, WaveValue::Bus("DEAD".to_string())),
-                (50, WaveValue::Bus("BEEF".to_string())),
-                (150, WaveValue::Bus("CAFE".to_string())),
-            ],
-        );
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
 
-        // Add a bus with x/z values
-        app.state.waveform_data.values.insert(
-            "mixed_bus".to_string(),
-            vec![
-                (0, WaveValue::Bus("00".to_string())),
-                (60, WaveValue::Bus("xZ".to_string())),
-                (120, WaveValue::Bus("FF".to_string())),
-            ],
-        );
+        assert!(app.state.grep_popup.is_none());
+        assert_eq!(app.state.primary_marker, Some(35));
+        assert_eq!(app.state.selected_signal, 1);
+        assert_eq!((app.state.time_start, app.state.time_range), (15, 40));
+    }
 
-        // Set up the view parameters
-        app.state.time_start = 0;
-        app.state.time_range = 200;
+    #[test]
+    fn test_grep_popup_esc_cancels() {
+        let mut app = setup_grep_test_app();
+        app.run_command("grep 7").unwrap();
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+
+        assert!(app.state.grep_popup.is_none());
+        assert_eq!(app.state.primary_marker, None);
+        assert_eq!(app.state.time_start, 0);
+    }
+
+    #[test]
+    fn test_render_grep_popup() {
+        let mut app = setup_grep_test_app();
+        app.state.config.ui.grep_match_limit = 2;
+        app.run_command("grep 0").unwrap();
+        app.state.command_state_mut().dismiss_results();
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
 
-        // Render the app
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
-
         assert_snapshot!(terminal.backend());
     }
 
     #[test]
-    fn test_render_app_with_markers() {
-        use crate::parsers::types::{Value, WaveValue};
-        let mut app = App::with_config(config::AppConfig::default());
-
-        // Add some test data
-        app.state.waveform_data.signals.push("signal".to_string());
-        app.state.displayed_signals.push("signal".to_string());
-        app.state.waveform_data.max_time = 100;
-        app.state.waveform_data.values.insert(
-            "signal".to_string(),
-            vec![
-                (0, WaveValue::Binary(Value::V0)),
-                (50, WaveValue::Binary(Value::V1)),
-            ],
-        );
+    fn test_render_sample_popup() {
+        let mut app = setup_grep_test_app();
+        app.state.primary_marker = Some(35);
+        app.run_command("sample").unwrap();
+        app.state.command_state_mut().dismiss_results();
 
-        // Set markers
-        app.state.primary_marker = Some(25);
-        app.state.secondary_marker = Some(75);
-
-        // Render the app
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
-
         assert_snapshot!(terminal.backend());
+
+        // Scrolling stops at the last line, and Esc closes the popup
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_eq!(app.state.sample_popup.as_ref().unwrap().scroll, 0);
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert!(app.state.sample_popup.is_none());
     }
 
     #[test]
-    fn test_render_app_in_command_mode() {
-        let mut app = App::with_config(config::AppConfig::default());
+    fn test_wrap_message() {
+        assert_eq!(
+            super::wrap_message("one two three four", 9, 3),
+            vec!["one two", "three", "four"]
+        );
+        assert_eq!(
+            super::wrap_message("abcdefghij\nxy", 4, 3),
+            vec!["abcd", "efgh", "ij…"]
+        );
+        assert_eq!(
+            super::wrap_message("first\nsecond", 10, 3),
+            vec!["first", "second"]
+        );
+    }
 
-        // Enter command mode
-        app.state.mode = AppMode::Command;
-        app.state.command_state_mut().input_buffer = "goto 50".to_string();
-        app.state.command_state_mut().cursor_position = 7; // Cursor after "goto 50"
+    #[test]
+    fn test_render_long_error_on_narrow_terminal() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let error =
+            "Usage: marker add <name> [time], marker remove <name>, marker goto <name> or list";
+        app.show_result(error.to_string(), true);
 
-        // Render the app
-        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        let mut terminal = Terminal::new(TestBackend::new(40, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
 
+        // The message is wrapped between words across the rows above the command bar, and the
+        // waveform gives them up
+        assert_eq!(app.layout.command_result.height, 3);
+        assert_eq!(app.layout.waveform.bottom(), app.layout.command_result.y);
         assert_snapshot!(terminal.backend());
     }
 
     #[test]
-    fn test_render_app_with_command_result() {
-        let mut app = App::with_config(config::AppConfig::default());
+    fn test_error_result_stays_until_key_press() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        let long_ago = std::time::Instant::now()
+            - std::time::Duration::from_secs(constants::COMMAND_RESULT_HIDE_THRESHOLD_SECONDS + 1);
+
+        app.show_result("Oops".to_string(), true);
+        app.state.command_state_mut().results[0].time = long_ago;
+        app.expire_result_message();
+        assert_eq!(app.state.command_state().result_message(), Some("Oops"));
+
+        app.handle_key(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+        assert_eq!(app.state.command_state().result_message(), None);
+
+        // Successful results are hidden after a while
+        app.show_result("Done".to_string(), false);
+        app.state.command_state_mut().results[1].time = long_ago;
+        app.expire_result_message();
+        assert_eq!(app.state.command_state().result_message(), None);
+    }
 
-        // Set a command result message
-        app.state.command_state_mut().result_message =
-            Some("Command executed successfully".to_string());
-        app.state.command_state_mut().result_is_error = false;
-        app.state.command_state_mut().command_result_time = Some(std::time::Instant::now());
+    #[test]
+    fn test_render_stats() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.run_command("stats").unwrap();
+        app.state.command_state_mut().dismiss_results();
 
-        // Render the app
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
-
         assert_snapshot!(terminal.backend());
+
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert!(!app.state.show_stats);
     }
 
     #[test]
-    fn test_render_app_in_fuzzy_finder_mode() {
-        let mut app = App::with_config(config::AppConfig::default());
-
-        // Setup signals
-        let signals = vec![
-            "signal_1".to_string(),
-            "signal_2".to_string(),
-            "test_signal".to_string(),
-            "data_bus".to_string(),
-        ];
+    fn test_render_command_list_scrolled() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.run_command("commands").unwrap();
+        app.state.command_state_mut().dismiss_results();
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
 
-        // Enter fuzzy finder mode
-        app.state.mode = AppMode::FuzzyFinder;
-        app.state.fuzzy_finder_state_mut().set_signals(signals, &[]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_eq!(app.state.command_list_scroll, 1);
+        assert_snapshot!(terminal.backend());
 
-        // Add a query and handle input character by character
-        for c in "sig".chars() {
-            app.state.fuzzy_finder_state_mut().handle_input(c);
-        }
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert!(!app.state.show_command_list);
+    }
 
-        // Select the first signal
-        app.state.fuzzy_finder_state_mut().toggle_selected_signal();
+    #[test]
+    fn test_render_custom_signal_colors() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.run_command("color clock green").unwrap();
+        app.run_command("color data blue").unwrap();
+        app.state.command_state_mut().dismiss_results();
+        app.state.selected_signal = 1;
 
-        // Render the app
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
 
+        // The selection highlight wins over the data signal's color
+        let buffer = terminal.backend().buffer();
+        let name_row =
+            |rel_idx: u16| app.layout.signal_list.y + 1 + rel_idx * WAVEFORM_HEIGHT as u16;
+        let name_x = app.layout.signal_list.x + 1;
+        assert_eq!(buffer[(name_x, name_row(0))].fg, Color::Green);
+        assert_eq!(buffer[(name_x, name_row(1))].fg, Color::Yellow);
+        assert_eq!(buffer[(name_x, name_row(2))].fg, Color::Reset);
+
+        let signal_fg = |rel_idx: u16| {
+            let top = app.layout.waveform.y + rel_idx * WAVEFORM_HEIGHT as u16;
+            (top..top + WAVEFORM_HEIGHT as u16)
+                .flat_map(|y| {
+                    (app.layout.waveform.x..app.layout.waveform.right()).map(move |x| (x, y))
+                })
+                .map(|pos| buffer[pos].fg)
+                .find(|fg| *fg != Color::Reset)
+        };
+        assert_eq!(signal_fg(0), Some(Color::Green));
+        assert_eq!(signal_fg(1), Some(Color::Yellow));
+        assert_eq!(signal_fg(2), Some(Color::White));
         assert_snapshot!(terminal.backend());
     }
 
-    #[test]
-    fn test_fuzzy_finder_maintains_signal_order() {
-        let config = config::AppConfig::default();
-        let mut app = App::with_config(config);
-
-        // Setup test data with signals in specific order
+    // An app showing signals from two instances of the same module
+    fn hierarchical_names_app() -> App {
+        use crate::parsers::types::{Value, WaveValue};
+        let mut app = App::with_config(config::AppConfig::default());
         app.state.waveform_data.signals = vec![
-            "first_signal".to_string(),
-            "middle_signal".to_string(),
-            "last_signal".to_string(),
+            "top.clk".to_string(),
+            "top.core0.decode.valid".to_string(),
+            "top.core1.decode.valid".to_string(),
+            "top.core0.fetch.ready".to_string(),
         ];
-
-        // Add some test values
-        app.state.waveform_data.values.insert(
-            "first_signal".to_string(),
-            vec![(0, WaveValue::Binary(Value::V0))],
-        );
-        app.state.waveform_data.values.insert(
-            "middle_signal".to_string(),
-            vec![(0, WaveValue::Binary(Value::V1))],
-        );
-        app.state.waveform_data.values.insert(
-            "last_signal".to_string(),
-            vec![(0, WaveValue::Binary(Value::V0))],
-        );
-
-        // Enter fuzzy finder mode
-        app.state.mode = AppMode::FuzzyFinder;
-        let signals = app.state.waveform_data.signals.clone();
-        app.state.fuzzy_finder_state_mut().set_signals(signals, &[]);
-
-        // Select signals in reverse order
-        app.state
-            .fuzzy_finder_state_mut()
-            .list_state
-            .select(Some(2)); // last_signal
-        app.state.fuzzy_finder_state_mut().toggle_selected_signal();
-
-        app.state
-            .fuzzy_finder_state_mut()
-            .list_state
-            .select(Some(0)); // first_signal
-        app.state.fuzzy_finder_state_mut().toggle_selected_signal();
-
-        // Accept selection
-        app.handle_fuzzy_finder_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
-
-        // Verify signals are displayed in the original order
-        assert_eq!(app.state.displayed_signals.len(), 2);
-        assert_eq!(app.state.displayed_signals[0], "first_signal");
-        assert_eq!(app.state.displayed_signals[1], "last_signal");
-
-        // Verify app is now in normal mode
-        assert_eq!(app.state.mode, AppMode::Normal);
+        for signal in &app.state.waveform_data.signals {
+            app.state.waveform_data.values.insert(
+                signal.clone(),
+                vec![
+                    (0, WaveValue::Binary(Value::V0)),
+                    (500, WaveValue::Binary(Value::V1)),
+                ],
+            );
+        }
+        app.state.displayed_signals = app.state.waveform_data.signals.clone();
+        app.state.waveform_data.max_time = 1000;
+        app.state.time_range = 1000;
+        app
     }
 
     #[test]
-    fn test_arrow_keys_with_empty_signal_list_do_not_panic() {
-        let mut app = App::with_config(config::AppConfig::default());
-
-        app.state.waveform_data.max_time = 1000;
-        app.state.displayed_signals = vec![];
-        app.state.selected_signal = 0;
+    fn test_render_leaf_names_disambiguated() {
+        let mut app = hierarchical_names_app();
+        app.state.config.ui.signal_list_width = 35;
+        app.run_command("names leaf").unwrap();
+        app.state.command_state_mut().dismiss_results();
 
-        app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
-        app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
-        app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
-        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
     }
 
     #[test]
-    fn test_arrow_keys_up_down_signal_selection() {
-        let mut app = App::with_config(config::AppConfig::default());
-
-        app.state.displayed_signals = vec![
-            "signal_1".to_string(),
-            "signal_2".to_string(),
-            "signal_3".to_string(),
-        ];
-
-        // Start at the first signal
-        app.state.selected_signal = 0;
-
-        app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
-        assert_eq!(app.state.selected_signal, 1);
-
-        app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
-        assert_eq!(app.state.selected_signal, 2);
-
-        // Signal should not wrap
-        app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
-        assert_eq!(app.state.selected_signal, 2);
+    fn test_render_shortened_names_in_narrow_list() {
+        let mut app = hierarchical_names_app();
+        app.state.displayed_signals.remove(2);
+        app.state.config.ui.signal_list_width = 25;
+        app.run_command("names short").unwrap();
+        app.state.command_state_mut().dismiss_results();
 
-        app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
-        assert_eq!(app.state.selected_signal, 1);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_snapshot!(terminal.backend());
+    }
 
-        app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
-        assert_eq!(app.state.selected_signal, 0);
+    #[test]
+    fn test_cycle_signal_names_key() {
+        let mut app = hierarchical_names_app();
+        let press = |app: &mut App| {
+            app.handle_key(KeyEvent::new(KeyCode::Char('n'), KeyModifiers::empty()))
+        };
 
-        // Signal should not wrap
-        app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
-        assert_eq!(app.state.selected_signal, 0);
+        press(&mut app);
+        assert_eq!(
+            app.state.config.ui.signal_name_style,
+            config::SignalNameStyle::Leaf
+        );
+        press(&mut app);
+        press(&mut app);
+        assert_eq!(
+            app.state.config.ui.signal_name_style,
+            config::SignalNameStyle::Full
+        );
     }
 
-    #[test]
-    fn test_arrow_keys_left_once() {
-        let mut app = setup_arrow_key_test_app(400, 200);
-        app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
+    #[test]
+    fn test_render_diff_stacks_rows() {
+        use crate::parsers::types::{Value, WaveValue};
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("fail.vcd");
+        std::fs::write(
+            &path,
+            "$timescale 1ns $end\n\
+             $scope module top $end\n\
+             $var wire 1 ! clk $end\n\
+             $var wire 1 \" data $end\n\
+             $var wire 1 # extra $end\n\
+             $upscope $end\n\
+             $enddefinitions $end\n\
+             #0\n0!\n0\"\n0#\n\
+             #500\n1!\n\
+             #600\n1\"\n\
+             #1000\n",
+        )
+        .unwrap();
+
+        let mut app = App::with_config(config::AppConfig::default());
+        app.state.file_path = Some(std::path::PathBuf::from("pass.vcd"));
+        let clk = vec![
+            (0, WaveValue::Binary(Value::V0)),
+            (500, WaveValue::Binary(Value::V1)),
+        ];
+        let data = vec![
+            (0, WaveValue::Binary(Value::V0)),
+            (300, WaveValue::Binary(Value::V1)),
+        ];
+        let valid = vec![(0, WaveValue::Binary(Value::V1))];
+        for (signal, values) in [("top.clk", clk), ("top.data", data), ("top.valid", valid)] {
+            app.state.waveform_data.signals.push(signal.to_string());
+            app.state
+                .waveform_data
+                .values
+                .insert(signal.to_string(), values);
+        }
+        app.state.displayed_signals = app.state.waveform_data.signals.clone();
+        app.state.waveform_data.max_time = 1000;
+        app.state.time_range = 1000;
+        app.state.config.ui.signal_list_width = 40;
+        app.run_command(&format!("diff {}", path.display()))
+            .unwrap();
+        app.state.command_state_mut().dismiss_results();
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
 
+        // data differs between 300 and 600 in both of its rows, clk matches
+        let buffer = terminal.backend().buffer();
+        let waveform = app.layout.waveform;
+        let mismatch = app.state.theme().mismatch().bg.unwrap();
+        let column = |time: u64| waveform.x + (time * waveform.width as u64 / 1000) as u16;
+        let data_top = waveform.y + 2 * WAVEFORM_HEIGHT as u16;
+        for y in data_top..data_top + 2 * WAVEFORM_HEIGHT as u16 {
+            assert_eq!(buffer[(column(400), y)].bg, mismatch);
+            assert_ne!(buffer[(column(800), y)].bg, mismatch);
+        }
+        assert_ne!(buffer[(column(400), waveform.y)].bg, mismatch);
         assert_snapshot!(terminal.backend());
     }
 
     #[test]
-    fn test_arrow_keys_left_twice() {
-        let mut app = setup_arrow_key_test_app(400, 200);
-
-        for _ in 0..2 {
-            app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
-        }
+    fn test_render_tracked_signal_with_both_markers() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.run_command("track data").unwrap();
+        app.state.command_state_mut().dismiss_results();
+        app.state.primary_marker = Some(150);
+        app.state.secondary_marker = Some(500);
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
-
         assert_snapshot!(terminal.backend());
     }
 
     #[test]
-    fn test_arrow_keys_right_once() {
-        let mut app = setup_arrow_key_test_app(400, 200);
-        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+    fn test_render_marker_readouts_in_marker_colors() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.state.config.ui.marker_color_primary = "green".to_string();
+        app.state.primary_marker = Some(150);
+        app.state.secondary_marker = Some(500);
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
+        let buffer = terminal.backend().buffer();
+
+        let title_row = (0..20)
+            .find(|&y| buffer[(0, y)].symbol() == "N" && buffer[(1, y)].symbol() == "o")
+            .unwrap();
+        let title: String = (0..80).map(|x| buffer[(x, title_row)].symbol()).collect();
+        let column_of = |text: &str| title.find(text).unwrap() as u16;
+        assert_eq!(buffer[(column_of("M1=150"), title_row)].fg, Color::Green);
+        assert_eq!(buffer[(column_of("M2=500"), title_row)].fg, Color::White);
+        assert_ne!(buffer[(column_of("Δ=350"), title_row)].fg, Color::Green);
+
+        // The marker line has the same color as its readout
+        let waveform = app.layout.waveform;
+        let marker_cells: Vec<u16> = (waveform.left()..waveform.right())
+            .filter(|&x| buffer[(x, waveform.y + 1)].fg == Color::Green)
+            .collect();
+        let marker_x = app.state.viewport(waveform.width).column_of(150);
+        assert_eq!(marker_cells, vec![waveform.x + marker_x]);
 
         assert_snapshot!(terminal.backend());
     }
 
     #[test]
-    fn test_arrow_keys_right_twice() {
-        let mut app = setup_arrow_key_test_app(400, 200);
+    fn test_render_tracked_signal_on_narrow_terminal() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.run_command("track data").unwrap();
+        app.run_command("hide data").unwrap();
+        app.state.command_state_mut().dismiss_results();
+        app.state.primary_marker = Some(150);
+        app.state.secondary_marker = Some(500);
+
+        // The tracked value doesn't fit, so only the marker times are shown
+        let mut terminal = Terminal::new(TestBackend::new(36, 12)).unwrap();
+        terminal
+            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+            .unwrap();
+        assert_eq!(app.state.tracked_signal.as_deref(), Some("data"));
+        assert_snapshot!(terminal.backend());
+    }
 
-        for _ in 0..2 {
-            app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+    // An app displaying more signals than fit in the signal list, scrolled to `offset`
+    fn many_signals_app(offset: usize) -> App {
+        use crate::parsers::types::{Value, WaveValue};
+        let mut app = App::with_config(config::AppConfig::default());
+        for i in 0..30 {
+            let signal = format!("sig{:02}", i);
+            app.state.waveform_data.signals.push(signal.clone());
+            app.state
+                .waveform_data
+                .values
+                .insert(signal, vec![(0, WaveValue::Binary(Value::V0))]);
         }
+        app.state.displayed_signals = app.state.waveform_data.signals.clone();
+        app.state.waveform_data.max_time = 1000;
+        app.state.time_range = 1000;
+        app.state.signal_scroll_offset = offset;
+        app
+    }
 
+    fn render_many_signals(offset: usize) -> Terminal<TestBackend> {
+        let mut app = many_signals_app(offset);
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
+        terminal
+    }
 
-        assert_snapshot!(terminal.backend());
+    #[test]
+    fn test_render_signal_scrollbar_at_top() {
+        assert_snapshot!(render_many_signals(0).backend());
     }
 
     #[test]
-    fn test_arrow_keys_right_twice_then_left_once() {
-        let mut app = setup_arrow_key_test_app(400, 200);
+    fn test_render_signal_scrollbar_in_middle() {
+        assert_snapshot!(render_many_signals(11).backend());
+    }
 
-        for _ in 0..2 {
-            app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+    #[test]
+    fn test_render_signal_scrollbar_at_bottom() {
+        assert_snapshot!(render_many_signals(30).backend());
+    }
+
+    #[test]
+    fn test_mouse_signal_scrollbar_click_and_drag() {
+        let mut app = many_signals_app(0);
+        app.handle_resize(80, 20);
+        let scrollbar = super::SignalListWidget::scrollbar_area(app.layout.signal_list, &app.state)
+            .expect("the signals don't fit, so there's a scrollbar");
+        let mouse = |kind, row| MouseEvent {
+            kind,
+            column: scrollbar.x,
+            row,
+            modifiers: KeyModifiers::empty(),
+        };
+
+        // Clicking the bottom of the track scrolls to the end, without selecting a signal
+        app.handle_mouse(mouse(
+            MouseEventKind::Down(MouseButton::Left),
+            scrollbar.bottom() - 1,
+        ));
+        let visible = app.layout.waveform.height as usize / WAVEFORM_HEIGHT;
+        assert_eq!(app.state.signal_scroll_offset, 30 - visible);
+        assert_eq!(app.state.selected_signal, 0);
+
+        // Dragging back past the top scrolls to the start
+        app.handle_mouse(mouse(
+            MouseEventKind::Drag(MouseButton::Left),
+            scrollbar.y + 3,
+        ));
+        let middle = app.state.signal_scroll_offset;
+        assert!(middle > 0 && middle < 30 - visible);
+        app.handle_mouse(mouse(MouseEventKind::Drag(MouseButton::Left), 0));
+        assert_eq!(app.state.signal_scroll_offset, 0);
+        app.handle_mouse(mouse(MouseEventKind::Up(MouseButton::Left), 0));
+        assert!(!app.state.is_dragging_signal_scrollbar);
+    }
+
+    #[test]
+    fn test_drag_saved_marker_name() {
+        let mut app = many_signals_app(0);
+        app.handle_resize(80, 20);
+        app.state.saved_markers = vec![
+            crate::types::Marker::new(200, "irq".to_string()),
+            crate::types::Marker::new(600, "done".to_string()),
+        ];
+        let names = app.layout.marker_names;
+        let mouse = |kind, column| MouseEvent {
+            kind,
+            column,
+            row: names.y,
+            modifiers: KeyModifiers::empty(),
+        };
+        let irq_column = names.x + app.state.viewport(names.width).column_of(200);
+
+        // Clicking a name selects its marker, anywhere along the name
+        app.handle_mouse(mouse(
+            MouseEventKind::Down(MouseButton::Left),
+            irq_column + 2,
+        ));
+        assert_eq!(app.state.selected_saved_marker.as_deref(), Some("irq"));
+
+        // Dragging moves it live, stopping at the ends of the waveform
+        app.handle_mouse(mouse(
+            MouseEventKind::Drag(MouseButton::Left),
+            irq_column + 10,
+        ));
+        let moved = app.state.saved_markers[0].time;
+        assert!(moved > 200 && moved < 600);
+        app.handle_mouse(mouse(MouseEventKind::Drag(MouseButton::Left), 200));
+        assert_eq!(app.state.saved_markers[0].time, 1000);
+        app.handle_mouse(mouse(MouseEventKind::Drag(MouseButton::Left), 0));
+        assert_eq!(app.state.saved_markers[0].time, 0);
+        app.handle_mouse(mouse(MouseEventKind::Up(MouseButton::Left), 0));
+        assert!(!app.state.is_dragging_saved_marker);
+        assert_eq!(app.state.saved_markers[1].time, 600);
+        assert_eq!(app.state.primary_marker, None);
+
+        // The rename key prompts with the name filled in, and a taken name is rejected
+        press(&mut app, KeyCode::Char('R'), KeyModifiers::SHIFT);
+        assert_eq!(app.state.mode, AppMode::Command);
+        assert_eq!(
+            app.state.command_state().input_buffer,
+            "marker rename irq irq"
+        );
+        press(&mut app, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        for c in "done".chars() {
+            press(&mut app, KeyCode::Char(c), KeyModifiers::empty());
         }
-        app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
+        press(&mut app, KeyCode::Enter, KeyModifiers::empty());
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some("Marker 'done' already exists")
+        );
+        assert_eq!(app.state.saved_markers[0].name, "irq");
 
-        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
-        terminal
-            .draw(|frame| frame.render_widget(&mut app, frame.area()))
-            .unwrap();
+        // Clicking away from the names clears the selection
+        app.handle_mouse(mouse(MouseEventKind::Down(MouseButton::Left), 79));
+        assert_eq!(app.state.selected_saved_marker, None);
+        press(&mut app, KeyCode::Char('R'), KeyModifiers::SHIFT);
+        assert_eq!(app.state.mode, AppMode::Normal);
+    }
 
-        assert_snapshot!(terminal.backend());
+    #[test]
+    fn test_no_signal_scrollbar_when_signals_fit() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.handle_resize(80, 20);
+        assert!(
+            super::SignalListWidget::scrollbar_area(app.layout.signal_list, &app.state).is_none()
+        );
     }
 
     #[test]
-    fn test_arrow_keys_left_at_time_start_does_not_move() {
-        let mut app = setup_arrow_key_test_app(0, 200);
-        app.handle_input(KeyEvent::new(KeyCode::Left, KeyModifiers::empty()));
+    fn test_filter_signal_list_as_you_type() {
+        let mut app = many_signals_app(0);
+        app.handle_resize(80, 20);
+        app.state.selected_signal = 5;
+
+        // Typing narrows the list, moving the selection off the hidden signal
+        press(&mut app, KeyCode::Char('/'), KeyModifiers::empty());
+        assert_eq!(app.state.mode, AppMode::Filter);
+        for c in "1X".chars() {
+            press(&mut app, KeyCode::Char(c), KeyModifiers::empty());
+        }
+        press(&mut app, KeyCode::Backspace, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, 10);
+
+        // Navigation skips the signals that aren't listed
+        press(&mut app, KeyCode::Enter, KeyModifiers::empty());
+        assert_eq!(app.state.mode, AppMode::Normal);
+        press(&mut app, KeyCode::Up, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, 1);
+        press(&mut app, KeyCode::Up, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, 1);
+        for _ in 0..11 {
+            press(&mut app, KeyCode::Down, KeyModifiers::empty());
+        }
+        assert_eq!(app.state.selected_signal, 21);
+        press(&mut app, KeyCode::Down, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, 21);
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
-
         assert_snapshot!(terminal.backend());
+
+        // Clearing the kept filter selects the signal selected before filtering
+        press(&mut app, KeyCode::Char('/'), KeyModifiers::empty());
+        assert_eq!(app.state.signal_filter.as_ref().unwrap().query, "1");
+        press(&mut app, KeyCode::Esc, KeyModifiers::empty());
+        assert_eq!(app.state.mode, AppMode::Normal);
+        assert_eq!(app.state.signal_filter, None);
+        assert_eq!(app.state.selected_signal, 5);
     }
 
     #[test]
-    fn test_arrow_keys_right_at_time_end_does_not_move() {
-        let mut app = setup_arrow_key_test_app(800, 200);
-        app.handle_input(KeyEvent::new(KeyCode::Right, KeyModifiers::empty()));
+    fn test_render_pinned_signals_above_scrolled_list() {
+        let mut app = many_signals_app(0);
+        app.handle_resize(80, 20);
+        app.run_command("pin sig20").unwrap();
+        app.run_command("pin sig03").unwrap();
+        app.state.signal_scroll_offset = 10;
+        app.state.selected_signal = 12;
 
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
-
         assert_snapshot!(terminal.backend());
+
+        // Clicks below the separator land on the scrolled signals
+        let first_scrolled = app.layout.waveform.y + app.state.pinned_height();
+        assert_eq!(app.waveform_signal_at_row(app.layout.waveform.y), Some(20));
+        assert_eq!(app.waveform_signal_at_row(first_scrolled - 1), None);
+        assert_eq!(app.waveform_signal_at_row(first_scrolled), Some(10));
     }
 
-    // Create an app with a large number of signals so that scrolling is required to view all of
-    // them.
-    fn setup_up_down_scroll_test_app() -> App {
-        use crate::parsers::types::{Value, WaveValue};
-        let mut app = App::with_config(config::AppConfig::default());
+    #[test]
+    fn test_navigation_crosses_pinned_boundary() {
+        let mut app = many_signals_app(0);
+        app.handle_resize(80, 20);
+        app.run_command("pin sig20").unwrap();
+        app.run_command("pin sig03").unwrap();
+        app.state.selected_signal = 20;
+
+        // The pinned signals come first, in pin order
+        press(&mut app, KeyCode::Up, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, 20);
+        press(&mut app, KeyCode::Down, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, 3);
+        press(&mut app, KeyCode::Down, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, 0);
+        press(&mut app, KeyCode::Down, KeyModifiers::empty());
+        press(&mut app, KeyCode::Down, KeyModifiers::empty());
+        press(&mut app, KeyCode::Down, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, 4);
+
+        // Scrolling to the bottom keeps the pinned signals, and going back up crosses into them
+        for _ in 0..30 {
+            press(&mut app, KeyCode::Down, KeyModifiers::empty());
+        }
+        assert_eq!(app.state.selected_signal, 29);
+        let offset = app.state.signal_scroll_offset;
+        assert_eq!(
+            offset,
+            app.state
+                .max_signal_scroll_offset(app.layout.waveform.height)
+        );
+        app.state.selected_signal = offset;
+        press(&mut app, KeyCode::Up, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, offset - 1);
+        assert_eq!(app.state.signal_scroll_offset, offset - 1);
 
-        // Set up waveform data
-        app.state.waveform_data.max_time = 1000;
-        app.state.time_start = 0;
-        app.state.time_range = 400;
         app.state.selected_signal = 0;
         app.state.signal_scroll_offset = 0;
+        press(&mut app, KeyCode::Up, KeyModifiers::empty());
+        assert_eq!(app.state.selected_signal, 3);
+        assert_eq!(app.state.signal_scroll_offset, 0);
+
+        // Moving a pinned signal reorders the pins, but not past the separator
+        press(&mut app, KeyCode::Up, KeyModifiers::SHIFT);
+        assert_eq!(app.state.pinned_signals, vec!["sig03", "sig20"]);
+        assert_eq!(app.state.selected_signal, 3);
+        press(&mut app, KeyCode::Down, KeyModifiers::SHIFT);
+        press(&mut app, KeyCode::Down, KeyModifiers::SHIFT);
+        assert_eq!(app.state.pinned_signals, vec!["sig20", "sig03"]);
+        assert_eq!(app.state.selected_signal, 3);
+        assert_eq!(app.state.displayed_signals[3], "sig03");
+    }
 
-        // Add more signals than can fit in the viewport
-        for c in 'a'..='z' {
-            let signal_name = format!("signal_{}", c);
-            app.state.waveform_data.signals.push(signal_name.clone());
-
-            // Create values for each signal with the same pattern
-            let mut signal_values = Vec::new();
-            signal_values.extend(vec![
-                (0, WaveValue::Binary(Value::V0)),
-                (50, WaveValue::Binary(Value::V1)),
-                (100, WaveValue::Binary(Value::V0)),
-                (150, WaveValue::Binary(Value::V1)),
-                (200, WaveValue::Binary(Value::V0)),
-            ]);
+    #[test]
+    fn test_readline_keys_edit_typed_lines() {
+        let mut app = many_signals_app(0);
+        app.handle_resize(80, 20);
 
-            app.state
-                .waveform_data
-                .values
-                .insert(signal_name, signal_values);
+        press(&mut app, KeyCode::Char(':'), KeyModifiers::empty());
+        for c in "add top.sig".chars() {
+            press(&mut app, KeyCode::Char(c), KeyModifiers::empty());
         }
+        // Control characters aren't typed
+        press(&mut app, KeyCode::Char('x'), KeyModifiers::CONTROL);
+        press(&mut app, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(app.state.command_state().input_buffer, "add top.");
+        press(&mut app, KeyCode::Char('a'), KeyModifiers::CONTROL);
+        press(&mut app, KeyCode::Char('f'), KeyModifiers::ALT);
+        press(&mut app, KeyCode::Char('k'), KeyModifiers::CONTROL);
+        assert_eq!(app.state.command_state().input_buffer, "add");
+        press(&mut app, KeyCode::Esc, KeyModifiers::empty());
+
+        press(&mut app, KeyCode::Char('/'), KeyModifiers::empty());
+        for c in "sig_1".chars() {
+            press(&mut app, KeyCode::Char(c), KeyModifiers::empty());
+        }
+        press(&mut app, KeyCode::Char('w'), KeyModifiers::CONTROL);
+        assert_eq!(app.state.signal_filter.as_ref().unwrap().query, "sig_");
+        press(&mut app, KeyCode::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(app.state.signal_filter.as_ref().unwrap().query, "");
+    }
 
-        // Initialize displayed_signals with the same signals
-        app.state.displayed_signals = app.state.waveform_data.signals.clone();
-
-        app
+    // Where the view is, as saved in a view bookmark
+    fn view_of(app: &App) -> (u64, u64, usize, usize) {
+        (
+            app.state.time_start,
+            app.state.time_range,
+            app.state.selected_signal,
+            app.state.signal_scroll_offset,
+        )
     }
 
     #[test]
-    fn test_scroll_down() {
-        let mut app = setup_up_down_scroll_test_app();
+    fn test_view_bookmark_chords() {
+        let mut app = many_signals_app(0);
+        app.handle_resize(80, 20);
+        app.state.set_window(100, 250);
+        app.state.selected_signal = 12;
+        app.state.signal_scroll_offset = 8;
+        press(&mut app, KeyCode::Char('m'), KeyModifiers::empty());
+        press(&mut app, KeyCode::Char('1'), KeyModifiers::empty());
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some("Saved view 1")
+        );
 
-        // We need to render the app once to initialize the layout
-        // so that adjust_scroll_if_needed has the correct dimensions
+        // Recalling it restores the whole view exactly
+        app.state.set_window(0, 1000);
+        app.state.selected_signal = 0;
+        app.state.signal_scroll_offset = 0;
+        press(&mut app, KeyCode::Char('\''), KeyModifiers::empty());
+        press(&mut app, KeyCode::Char('1'), KeyModifiers::empty());
+        assert_eq!(view_of(&app), (100, 250, 12, 8));
+
+        // A key other than a slot's digit cancels the chord and does what it always does
+        press(&mut app, KeyCode::Char('m'), KeyModifiers::empty());
+        press(&mut app, KeyCode::Char('+'), KeyModifiers::empty());
+        assert!(app.state.time_range < 250);
+        press(&mut app, KeyCode::Char('2'), KeyModifiers::empty());
+        assert!(!app.state.view_bookmarks.contains_key(&2));
+        press(&mut app, KeyCode::Char('\''), KeyModifiers::empty());
+        press(&mut app, KeyCode::Char('0'), KeyModifiers::empty());
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 1000));
+
+        // While the cursor is shown, `m` puts the marker at it instead
+        press(&mut app, KeyCode::Char('c'), KeyModifiers::empty());
+        press(&mut app, KeyCode::Char('m'), KeyModifiers::empty());
+        press(&mut app, KeyCode::Char('3'), KeyModifiers::empty());
+        assert_eq!(app.state.primary_marker, app.state.cursor);
+        assert!(!app.state.view_bookmarks.contains_key(&3));
+    }
+
+    #[test]
+    fn test_view_list_loads_a_slot() {
+        let mut app = many_signals_app(0);
+        app.handle_resize(80, 20);
+        app.state.set_window(200, 100);
+        app.run_command("view save 5").unwrap();
+        app.state.set_window(0, 1000);
+
+        app.run_command("view list").unwrap();
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
+        assert_snapshot!(terminal.backend());
 
-        // Now move down far enough to trigger scrolling
-        for _ in 0..10 {
-            app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
-        }
+        press(&mut app, KeyCode::Char('5'), KeyModifiers::empty());
+        assert!(!app.state.show_view_list);
+        assert_eq!((app.state.time_start, app.state.time_range), (200, 100));
+    }
 
-        // Render again to capture the scrolled state
+    fn press(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+        app.handle_key(KeyEvent::new(code, modifiers));
+    }
+
+    #[test]
+    fn test_cursor_step_sizes() {
+        let mut app = setup_arrow_key_test_app(0, 400);
+        app.handle_resize(80, 20);
+        press(&mut app, KeyCode::Char('c'), KeyModifiers::empty());
+        assert_eq!(app.state.cursor, Some(200));
+
+        // One ruler tick, or `ui.pan_fraction` of the view with Shift
+        let tick = calculate_tick_interval(400, app.layout.time_ruler.width as u64);
+        press(&mut app, KeyCode::Char('l'), KeyModifiers::empty());
+        assert_eq!(app.state.cursor, Some(200 + tick));
+        press(&mut app, KeyCode::Char('h'), KeyModifiers::empty());
+        assert_eq!(app.state.cursor, Some(200));
+        press(&mut app, KeyCode::Char('L'), KeyModifiers::SHIFT);
+        assert_eq!(app.state.cursor, Some(300));
+        press(&mut app, KeyCode::Char('H'), KeyModifiers::SHIFT);
+        assert_eq!(app.state.cursor, Some(200));
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 400));
+
+        // The cursor keys do nothing outside cursor mode
+        press(&mut app, KeyCode::Char('c'), KeyModifiers::empty());
+        assert_eq!(app.state.cursor, None);
+        press(&mut app, KeyCode::Char('l'), KeyModifiers::empty());
+        assert_eq!(app.state.cursor, None);
+    }
+
+    #[test]
+    fn test_cursor_pans_at_view_edges() {
+        let mut app = setup_arrow_key_test_app(200, 400);
+        app.handle_resize(80, 20);
+        app.state.cursor = Some(580);
+
+        // Pushing past the right edge pans the view so the cursor is at its end
+        press(&mut app, KeyCode::Char('L'), KeyModifiers::SHIFT);
+        assert_eq!(app.state.cursor, Some(680));
+        assert_eq!((app.state.time_start, app.state.time_range), (280, 400));
+
+        // Likewise on the left edge
+        app.state.cursor = Some(300);
+        press(&mut app, KeyCode::Char('H'), KeyModifiers::SHIFT);
+        assert_eq!(app.state.cursor, Some(200));
+        assert_eq!(app.state.time_start, 200);
+
+        // The cursor stays within the waveform
+        app.state.cursor = Some(20);
+        press(&mut app, KeyCode::Char('H'), KeyModifiers::SHIFT);
+        assert_eq!(app.state.cursor, Some(0));
+        assert_eq!(app.state.time_start, 0);
+        app.state.cursor = Some(990);
+        press(&mut app, KeyCode::Char('L'), KeyModifiers::SHIFT);
+        assert_eq!(app.state.cursor, Some(1000));
+        assert_eq!(app.state.time_start, 600);
+    }
+
+    #[test]
+    fn test_cursor_to_primary_marker() {
+        let mut app = setup_arrow_key_test_app(0, 400);
+        app.handle_resize(80, 20);
+
+        // Without the cursor, the key does nothing
+        press(&mut app, KeyCode::Char('m'), KeyModifiers::empty());
+        assert_eq!(app.state.primary_marker, None);
+
+        press(&mut app, KeyCode::Char('c'), KeyModifiers::empty());
+        press(&mut app, KeyCode::Char('L'), KeyModifiers::SHIFT);
+        press(&mut app, KeyCode::Char('m'), KeyModifiers::empty());
+        assert_eq!(app.state.primary_marker, Some(300));
+        assert_eq!(app.state.cursor, Some(300));
+    }
+
+    #[test]
+    fn test_render_cursor_without_primary_marker() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.state.cursor = Some(150);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
 
-        // The snapshot assert should capture signals further in the alphabet than a-f
+        // The signal list shows the values at the cursor
         assert_snapshot!(terminal.backend());
     }
 
     #[test]
-    fn test_scroll_up() {
-        let mut app = setup_up_down_scroll_test_app();
+    fn test_render_file_browser() {
+        use crate::file_browser::RecentFile;
+        let mut app = App::default();
+        app.state.mode = AppMode::FileBrowser;
+        app.state.file_browser.dir = std::path::PathBuf::from("/work/sim");
+        app.state.file_browser.local_files = vec!["dump.vcd".to_string(), "long.fst".to_string()];
+        app.state.file_browser.recent_files = vec![RecentFile {
+            path: "/work/old/top.vcd".to_string(),
+            opened_at: 1_700_000_000,
+        }];
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
 
-        // We need to render the app once to initialize the layout
-        // so that adjust_scroll_if_needed has the correct dimensions
         let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
+        assert_eq!(app.state.file_browser.selected, 2);
+        assert_snapshot!(terminal.backend());
+    }
 
-        // Now move down far enough to trigger scrolling
-        for _ in 0..10 {
-            app.handle_input(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
-        }
-
-        // Scroll back up
-        for _ in 0..10 {
-            app.handle_input(KeyEvent::new(KeyCode::Up, KeyModifiers::empty()));
-        }
-
-        // Render again to capture the scrolled state
+    #[test]
+    fn test_render_split_view() {
+        let mut app = setup_arrow_key_test_app(0, 1000);
+        app.state.primary_marker = Some(150);
+        app.run_command("split-view").unwrap();
+        app.state.set_window(0, 1000);
+        app.state.switch_pane_focus();
+        app.state.set_window(100, 100);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 24)).unwrap();
         terminal
             .draw(|frame| frame.render_widget(&mut app, frame.area()))
             .unwrap();
 
-        // The snapshot assert should capture signals a-f
+        // The bottom pane has focus, and the panes have rulers of their own
+        let other_pane = app.layout.other_pane.unwrap();
+        assert!(other_pane.waveform.y < app.layout.waveform.y);
+        assert_eq!(other_pane.waveform.height, app.layout.waveform.height);
+        assert_eq!(app.layout.time_ruler.y + 1, app.layout.waveform.y);
         assert_snapshot!(terminal.backend());
     }
 
     #[test]
-    fn test_mouse_signal_selection() {
+    fn test_split_view_panes_move_independently() {
+        let mut app = setup_arrow_key_test_app(0, 400);
+        app.handle_resize(80, 24);
+        app.run_command("split-view").unwrap();
+        app.handle_resize(80, 24);
+
+        // Zooming the focused top pane leaves the bottom one alone
+        press(&mut app, KeyCode::Char('+'), KeyModifiers::empty());
+        assert_eq!((app.state.time_start, app.state.time_range), (100, 200));
+        let split_view = app.state.split_view.clone().unwrap();
+        assert_eq!((split_view.time_start, split_view.time_range), (0, 400));
+
+        // Tab moves the focus to the bottom pane, whose window and zoom history move with it
+        press(&mut app, KeyCode::Tab, KeyModifiers::empty());
+        assert!(app.state.split_view.as_ref().unwrap().bottom_focused);
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 400));
+        assert!(!app.state.zoom_back());
+        press(&mut app, KeyCode::Right, KeyModifiers::empty());
+        assert_eq!((app.state.time_start, app.state.time_range), (100, 400));
+
+        // Clicking the top pane focuses it again, with its own window
+        let top = app.layout.other_pane.unwrap().waveform;
+        app.handle_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column: top.x + 1,
+            row: top.y + 1,
+            modifiers: KeyModifiers::empty(),
+        });
+        assert!(!app.state.split_view.as_ref().unwrap().bottom_focused);
+        assert_eq!(app.layout.waveform, top);
+        assert_eq!((app.state.time_start, app.state.time_range), (100, 200));
+        assert!(app.state.zoom_back());
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 400));
+        let split_view = app.state.split_view.clone().unwrap();
+        assert_eq!((split_view.time_start, split_view.time_range), (100, 400));
+    }
+
+    // Runs the event loop on a TestBackend with no input, waiting out each timeout. Returns what
+    // `inspect` saw at each wait for events, which comes after every frame but the last.
+    fn run_unattended<T>(app: &mut App, mut inspect: impl FnMut(&App) -> T) -> Vec<T> {
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        let mut seen = Vec::new();
+        app.run_loop(&mut terminal, |app, timeout| {
+            seen.push(inspect(app));
+            std::thread::sleep(timeout);
+            Ok(false)
+        })
+        .unwrap();
+        seen
+    }
+
+    fn unattended_app() -> App {
         let mut app = App::with_config(config::AppConfig::default());
-        for i in 0..5 {
-            let signal_name = format!("signal_{}", i);
-            app.state.waveform_data.signals.push(signal_name.clone());
-            let mut signal_values = Vec::new();
-            signal_values.extend(vec![
-                (0, WaveValue::Binary(Value::V0)),
-                (50, WaveValue::Binary(Value::V1)),
-                (100, WaveValue::Binary(Value::V0)),
-            ]);
+        app.state.waveform_data.max_time = 100;
+        app
+    }
 
-            app.state
-                .waveform_data
-                .values
-                .insert(signal_name, signal_values);
-        }
-        app.state.displayed_signals = app.state.waveform_data.signals.clone();
+    #[test]
+    fn test_run_exits_after_frames() {
+        let mut app = unattended_app();
+        app.set_exit_limits(None, Some(3));
+        assert_eq!(run_unattended(&mut app, |_| ()).len(), 2);
+
+        let mut app = unattended_app();
+        app.set_exit_limits(None, Some(1));
+        assert!(run_unattended(&mut app, |_| ()).is_empty());
+    }
 
-        // We need to render the app once to initialize the layout
-        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
-        terminal
-            .draw(|frame| frame.render_widget(&mut app, frame.area()))
+    #[test]
+    fn test_run_exits_after_time() {
+        let mut app = unattended_app();
+        app.set_exit_limits(Some(std::time::Duration::from_millis(60)), None);
+        let start = std::time::Instant::now();
+        run_unattended(&mut app, |_| ());
+        assert!(start.elapsed() >= std::time::Duration::from_millis(60));
+    }
+
+    #[test]
+    fn test_run_draws_a_frame_per_queued_command() {
+        let mut app = unattended_app();
+        app.queue_startup_commands(
+            [
+                "marker add a 5",
+                "marker add b 6",
+                "sleep 20",
+                "marker add c 7",
+                "q!",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+        );
+        let markers = run_unattended(&mut app, |app| app.state.saved_markers.len());
+
+        // Every command gets a frame, but the one running :quit
+        assert_eq!(markers, [1, 2, 2, 3]);
+        assert!(app.state.exit);
+    }
+
+    #[test]
+    fn test_startup_commands_after_sleep_run_in_the_loop() {
+        let mut app = unattended_app();
+        let commands = [
+            "marker add a 5",
+            "sleep 20",
+            "marker add b 6",
+            "marker add c 7",
+        ];
+        app.run_startup_commands(&commands.map(str::to_string))
             .unwrap();
+        assert_eq!(app.state.saved_markers.len(), 1);
+
+        // Without a frame per command, the rest run together once the sleep is over
+        app.set_exit_limits(Some(std::time::Duration::from_millis(100)), None);
+        let markers = run_unattended(&mut app, |app| app.state.saved_markers.len());
+        assert_eq!(markers.first(), Some(&1));
+        assert_eq!(markers.last(), Some(&3));
+        assert!(!markers.contains(&2));
+    }
 
-        // Create a mouse event for clicking on multiple different signals.
-        // Calculate position (each signal takes WAVEFORM_HEIGHT rows)
-        for signal_index in vec![2, 1, 3, 0] {
-            // +1 to click in middle of signal
-            let y_position = app.layout.signal_list.y + (signal_index * WAVEFORM_HEIGHT) as u16 + 1;
+    #[test]
+    fn test_run_follows_file_while_events_keep_coming() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("live.vcd");
+        let mut contents = "$scope module top $end\n\
+                            $var wire 1 ! clk $end\n\
+                            $upscope $end\n\
+                            $enddefinitions $end\n\
+                            #0\n0!\n"
+            .to_string();
+        std::fs::write(&path, &contents).unwrap();
+        let mut app = App::with_config(config::AppConfig::default());
+        app.load_vcd_file(&path).unwrap();
+        app.run_command("follow").unwrap();
 
-            let mouse_event = MouseEvent {
-                kind: MouseEventKind::Down(MouseButton::Left),
-                column: app.layout.signal_list.x + 2, // "Near" the beginning of the signal name
-                row: y_position,
-                modifiers: KeyModifiers::empty(),
-            };
+        contents.push_str("#10\n1!\n");
+        std::fs::write(&path, &contents).unwrap();
+        app.set_exit_limits(Some(std::time::Duration::from_millis(400)), None);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        app.run_loop(&mut terminal, |_, timeout| {
+            // An event arrives before every timeout, like the mouse moving over the waveform
+            std::thread::sleep(timeout.min(std::time::Duration::from_millis(10)));
+            Ok(true)
+        })
+        .unwrap();
+        assert_eq!(app.state.waveform_data.max_time, 10);
+    }
 
-            // Handle the mouse event
-            app.handle_mouse(mouse_event);
+    #[test]
+    fn test_run_watches_file_while_events_keep_coming() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("watched.vcd");
+        let contents = "$scope module top $end\n\
+                        $var wire 1 ! clk $end\n\
+                        $upscope $end\n\
+                        $enddefinitions $end\n\
+                        #0\n0!\n";
+        std::fs::write(&path, contents).unwrap();
+        let mut app = App::with_config(config::AppConfig::default());
+        app.load_vcd_file(&path).unwrap();
+        app.run_command("watch auto").unwrap();
+        app.watch_file(std::time::Instant::now());
 
-            // Verify the selection changed
-            assert_eq!(app.state.selected_signal, signal_index);
-        }
+        std::fs::write(&path, format!("{}#10\n1!\n", contents)).unwrap();
+        app.set_exit_limits(Some(std::time::Duration::from_secs(5)), None);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        app.run_loop(&mut terminal, |app, timeout| {
+            // An event arrives before every timeout, until the file is reloaded
+            std::thread::sleep(timeout.min(std::time::Duration::from_millis(10)));
+            app.state.exit = app.state.waveform_data.max_time == 10;
+            Ok(true)
+        })
+        .unwrap();
+        assert_eq!(app.state.waveform_data.max_time, 10);
+    }
+
+    #[test]
+    fn test_run_stops_at_a_failing_queued_command() {
+        let mut app = unattended_app();
+        app.queue_startup_commands(vec!["marker add a 500".to_string(), "quit".to_string()]);
+        let mut terminal = Terminal::new(TestBackend::new(80, 20)).unwrap();
+        let result = app.run_loop(&mut terminal, |_, _| Ok(false));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Error running command 'marker add a 500': Time out of range (0-100)"
+        );
     }
 }
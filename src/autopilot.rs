@@ -0,0 +1,142 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Drives the app without anyone at the keyboard, e.g. while recording it: exits after a number of
+/// frames or some time, and runs commands from the command line one frame apart, waiting for
+/// `:sleep` between them.
+#[derive(Debug, Default)]
+pub struct Autopilot {
+    /// Exit this long after the event loop starts
+    exit_after: Option<Duration>,
+    /// Exit once this many frames were drawn
+    frame_limit: Option<u64>,
+    /// Whether a frame is drawn after each queued command, rather than running them all at once
+    frame_per_command: bool,
+    commands: VecDeque<String>,
+    exit_at: Option<Instant>,
+    resume_at: Option<Instant>,
+    frames: u64,
+}
+
+impl Autopilot {
+    /// Exits after `exit_after` from the start of the event loop, or `frame_limit` frames.
+    pub fn set_limits(&mut self, exit_after: Option<Duration>, frame_limit: Option<u64>) {
+        self.exit_after = exit_after;
+        self.frame_limit = frame_limit;
+    }
+
+    /// Arms the exit timer. Called when the event loop starts.
+    pub fn start(&mut self, now: Instant) {
+        self.exit_at = self.exit_after.map(|exit_after| now + exit_after);
+    }
+
+    /// Queues commands to run from the event loop, drawing a frame after each if
+    /// `frame_per_command` is set.
+    pub fn queue(&mut self, commands: impl IntoIterator<Item = String>, frame_per_command: bool) {
+        self.commands.extend(commands);
+        self.frame_per_command |= frame_per_command;
+    }
+
+    /// Holds the queued commands until `duration` has passed.
+    pub fn sleep(&mut self, duration: Duration, now: Instant) {
+        self.resume_at = Some(now + duration);
+    }
+
+    /// Counts a drawn frame. Returns true once the app should exit.
+    pub fn frame_drawn(&mut self, now: Instant) -> bool {
+        self.frames += 1;
+        self.frame_limit.is_some_and(|limit| self.frames >= limit) || self.timed_out(now)
+    }
+
+    pub fn timed_out(&self, now: Instant) -> bool {
+        self.exit_at.is_some_and(|exit_at| now >= exit_at)
+    }
+
+    /// Whether a `:sleep` is holding the queued commands.
+    pub fn sleeping(&self, now: Instant) -> bool {
+        self.resume_at.is_some_and(|resume_at| now < resume_at)
+    }
+
+    /// The next queued command, unless a sleep holds them.
+    pub fn next_command(&mut self, now: Instant) -> Option<String> {
+        if self.sleeping(now) {
+            return None;
+        }
+        self.resume_at = None;
+        self.commands.pop_front()
+    }
+
+    /// Whether a frame is drawn before the next queued command runs.
+    pub fn frame_per_command(&self) -> bool {
+        self.frame_per_command
+    }
+
+    /// Longest the event loop may wait for input before the autopilot has something to do.
+    pub fn timeout(&self, now: Instant, tick_rate: Duration) -> Duration {
+        let resume_at = if self.commands.is_empty() {
+            None
+        } else {
+            Some(self.resume_at.unwrap_or(now))
+        };
+        [self.exit_at, resume_at]
+            .into_iter()
+            .flatten()
+            .map(|at| at.saturating_duration_since(now))
+            .fold(tick_rate, Duration::min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_after_frames_or_time() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+
+        let mut autopilot = Autopilot::default();
+        autopilot.set_limits(None, Some(2));
+        autopilot.start(start);
+        assert!(!autopilot.frame_drawn(at(0)));
+        assert!(autopilot.frame_drawn(at(0)));
+
+        // The timer starts with the event loop
+        let mut autopilot = Autopilot::default();
+        autopilot.set_limits(Some(Duration::from_millis(100)), None);
+        assert!(!autopilot.frame_drawn(at(500)));
+        autopilot.start(at(500));
+        assert_eq!(
+            autopilot.timeout(at(520), Duration::from_millis(250)),
+            Duration::from_millis(80)
+        );
+        assert!(!autopilot.timed_out(at(599)));
+        assert!(autopilot.timed_out(at(600)));
+        assert!(autopilot.frame_drawn(at(600)));
+    }
+
+    #[test]
+    fn test_sleep_holds_queued_commands() {
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let tick_rate = Duration::from_millis(250);
+        let mut autopilot = Autopilot::default();
+        assert_eq!(autopilot.timeout(at(0), tick_rate), tick_rate);
+
+        autopilot.queue(["zoom in".to_string(), "quit".to_string()], true);
+        assert_eq!(autopilot.timeout(at(0), tick_rate), Duration::ZERO);
+        assert_eq!(autopilot.next_command(at(0)).as_deref(), Some("zoom in"));
+
+        autopilot.sleep(Duration::from_millis(40), at(0));
+        assert_eq!(
+            autopilot.timeout(at(10), tick_rate),
+            Duration::from_millis(30)
+        );
+        assert_eq!(autopilot.next_command(at(39)), None);
+        assert_eq!(autopilot.next_command(at(40)).as_deref(), Some("quit"));
+        assert_eq!(autopilot.next_command(at(40)), None);
+        assert_eq!(autopilot.timeout(at(40), tick_rate), tick_rate);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::parsers::types::{Value, WaveValue};
+
+/// Name of the row showing bit `bit` of `signal`, e.g. `top.status[3]`.
+pub fn bit_signal_name(signal: &str, bit: usize) -> String {
+    format!("{}[{}]", signal, bit)
+}
+
+/// Bits of a value, most significant first. Bus values are hex, except for those with x or z bits,
+/// which the parser keeps as binary. Values that aren't bits at all, like real values, have none.
+pub fn value_bits(value: &WaveValue) -> Vec<Value> {
+    match value {
+        WaveValue::Bus(bits) if bits.contains(['x', 'X', 'z', 'Z']) => {
+            value.values(2).unwrap_or_default()
+        }
+        value => value.values(16).unwrap_or_default(),
+    }
+}
+
+/// Change lists of each bit of a signal `width` bits wide, most significant bit first. A bit only
+/// changes where its value differs from the one before. Values with fewer bits are extended with
+/// zeros, and values without any bits make every bit x.
+pub fn split_bits(values: &[(u64, WaveValue)], width: usize) -> Vec<Vec<(u64, WaveValue)>> {
+    let mut bit_values: Vec<Vec<(u64, WaveValue)>> = vec![Vec::new(); width];
+
+    for (time, value) in values {
+        let bits = value_bits(value);
+        let bits: Vec<Value> = if bits.is_empty() {
+            vec![Value::VX; width]
+        } else if bits.len() >= width {
+            bits[bits.len() - width..].to_vec()
+        } else {
+            let mut extended = vec![Value::V0; width - bits.len()];
+            extended.extend(bits);
+            extended
+        };
+
+        for (changes, bit) in bit_values.iter_mut().zip(bits) {
+            let bit = WaveValue::Binary(bit);
+            if changes.last().map(|(_, last)| last) != Some(&bit) {
+                changes.push((*time, bit));
+            }
+        }
+    }
+    bit_values
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bus(value: &str) -> WaveValue {
+        WaveValue::Bus(value.into())
+    }
+
+    fn binary(value: Value) -> WaveValue {
+        WaveValue::Binary(value)
+    }
+
+    #[test]
+    fn test_split_bits_with_unknown_bits() {
+        let values = vec![(0, bus("xxxx")), (10, bus("1z0x")), (20, bus("A"))];
+        let bits = split_bits(&values, 4);
+
+        assert_eq!(
+            bits[0],
+            vec![(0, binary(Value::VX)), (10, binary(Value::V1))]
+        );
+        assert_eq!(
+            bits[1],
+            vec![
+                (0, binary(Value::VX)),
+                (10, binary(Value::VZ)),
+                (20, binary(Value::V0)),
+            ]
+        );
+        assert_eq!(
+            bits[2],
+            vec![
+                (0, binary(Value::VX)),
+                (10, binary(Value::V0)),
+                (20, binary(Value::V1)),
+            ]
+        );
+        assert_eq!(
+            bits[3],
+            vec![(0, binary(Value::VX)), (20, binary(Value::V0))]
+        );
+    }
+
+    #[test]
+    fn test_split_bits_only_changes_when_the_bit_changes() {
+        // 0x05 -> 0x07 -> 0x87 -> 0x87 only changes bit 1 and then bit 7
+        let values = vec![
+            (0, bus("05")),
+            (10, bus("07")),
+            (20, bus("87")),
+            (30, bus("87")),
+        ];
+        let bits = split_bits(&values, 8);
+
+        assert_eq!(
+            bits[0],
+            vec![(0, binary(Value::V0)), (20, binary(Value::V1))]
+        );
+        assert_eq!(
+            bits[6],
+            vec![(0, binary(Value::V0)), (10, binary(Value::V1))]
+        );
+        assert_eq!(bits[7], vec![(0, binary(Value::V1))]);
+        assert!(bits.iter().all(|changes| changes[0].0 == 0));
+    }
+
+    #[test]
+    fn test_split_bits_of_narrower_values() {
+        // A 6 bit signal in hex has 8 bits, and a short value is extended with zeros
+        let bits = split_bits(&[(0, bus("3F")), (5, bus("1"))], 6);
+        assert_eq!(bits.len(), 6);
+        assert_eq!(
+            bits[0],
+            vec![(0, binary(Value::V1)), (5, binary(Value::V0))]
+        );
+        assert_eq!(bits[5], vec![(0, binary(Value::V1))]);
+
+        // Real values have no bits to show
+        let bits = split_bits(&[(0, bus("r1.5"))], 2);
+        assert_eq!(bits[1], vec![(0, binary(Value::VX))]);
+    }
+
+    #[test]
+    fn test_bit_signal_name() {
+        assert_eq!(bit_signal_name("top.status", 7), "top.status[7]");
+    }
+}
@@ -0,0 +1,185 @@
+use crate::{
+    cli,
+    parsers::{
+        types::{ParseReport, WaveformData},
+        vcd::VcdParser,
+    },
+};
+use serde::Serialize;
+use std::io::BufRead;
+
+/// Outcome of checking a waveform file, which is also the exit status of `--check`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    /// The file parsed without problems
+    Ok,
+    /// The file parsed, but had problems the viewer works around
+    Warnings,
+    /// The file couldn't be parsed
+    Error,
+}
+
+impl CheckStatus {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            CheckStatus::Ok => 0,
+            CheckStatus::Warnings => 1,
+            CheckStatus::Error => 2,
+        }
+    }
+}
+
+/// Summary of a waveform file checked with `--check`, printed as text or as JSON.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckReport {
+    pub file: String,
+    pub status: CheckStatus,
+    pub signals: usize,
+    pub changes: usize,
+    pub max_time: u64,
+    /// The `$timescale` as written in the file, like `10 ns`
+    pub timescale: Option<String>,
+    pub problems: ParseReport,
+    /// Why the file couldn't be parsed
+    pub error: Option<String>,
+}
+
+impl CheckReport {
+    /// A summary line, each problem with its example lines, and the error if there is one.
+    pub fn text(&self) -> String {
+        let mut lines = vec![format!(
+            "{}: {} signal(s), {} change(s), max time {}, timescale {}",
+            self.file,
+            self.signals,
+            self.changes,
+            self.max_time,
+            self.timescale.as_deref().unwrap_or("none")
+        )];
+        lines.extend(self.problems.details());
+        if let Some(error) = &self.error {
+            lines.push(format!("Error: {}", error));
+        }
+        lines.join("\n")
+    }
+
+    pub fn json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("check reports only hold plain data")
+    }
+}
+
+/// Parses the waveform named on the command line, reading it from `stdin` if it is named `-`, and
+/// reports what it holds and what is wrong with it. A file cut off before `$enddefinitions` is an
+/// error, since none of its values could be read.
+pub fn check(file_name: &str, stdin: impl BufRead) -> CheckReport {
+    let mut parser = VcdParser::new();
+    let result = if file_name == cli::STDIN_FILE_NAME {
+        parser.parse_reader(stdin)
+    } else {
+        parser.parse_file(file_name)
+    };
+    let (data, error) = match result {
+        Ok(_) if !parser.definitions_finished() => (
+            WaveformData::default(),
+            Some("The file ended before $enddefinitions".to_string()),
+        ),
+        Ok(data) => (data, None),
+        Err(e) => (WaveformData::default(), Some(e.to_string())),
+    };
+
+    let problems = parser.report().clone();
+    let status = if error.is_some() {
+        CheckStatus::Error
+    } else if !problems.is_empty() {
+        CheckStatus::Warnings
+    } else {
+        CheckStatus::Ok
+    };
+    CheckReport {
+        file: file_name.to_string(),
+        status,
+        signals: data.signals.len(),
+        changes: data.values.values().map(Vec::len).sum(),
+        max_time: data.max_time,
+        timescale: parser.header().timescale.clone(),
+        problems,
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::io::Cursor;
+
+    fn check_vcd(vcd: &str) -> CheckReport {
+        check(cli::STDIN_FILE_NAME, Cursor::new(vcd))
+    }
+
+    #[test]
+    fn test_check_clean_file() {
+        let report = check("resources/test_data.vcd", Cursor::new(""));
+        assert_eq!(report.status, CheckStatus::Ok);
+        assert_eq!(report.status.exit_code(), 0);
+        assert!(report.signals > 0 && report.changes > 0);
+        assert_eq!(report.error, None);
+        assert_eq!(report.text().lines().count(), 1);
+    }
+
+    #[test]
+    fn test_check_file_with_problems() {
+        let report = check_vcd(include_str!("../resources/test_data_malformed.vcd"));
+        assert_eq!(report.status.exit_code(), 1);
+        assert_eq!(
+            report.text(),
+            "-: 2 signal(s), 9 change(s), max time 40, timescale 1ns\n\
+             2 change(s) of undeclared identifiers, dropped:\n  \
+             line 13: 1?\n  \
+             line 21: b0111 ?\n\
+             1 timestamp(s) going backwards, sorted into place:\n  \
+             line 18: #20\n\
+             1 invalid line(s), skipped:\n  \
+             line 22: not a value change"
+        );
+
+        let json: Value = serde_json::from_str(&report.json()).unwrap();
+        assert_eq!(
+            json,
+            json!({
+                "file": "-",
+                "status": "warnings",
+                "signals": 2,
+                "changes": 9,
+                "max_time": 40,
+                "timescale": "1ns",
+                "problems": {
+                    "unknown_ids": {"count": 2, "examples": [[13, "1?"], [21, "b0111 ?"]]},
+                    "backwards_timestamps": {"count": 1, "examples": [[18, "#20"]]},
+                    "invalid_lines": {"count": 1, "examples": [[22, "not a value change"]]},
+                },
+                "error": null,
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_fatal_errors() {
+        let report = check_vcd("$timescale 1ns $end\n$scope module top $end\n$var wire 1 ! clk");
+        assert_eq!(report.status, CheckStatus::Error);
+        assert_eq!(report.status.exit_code(), 2);
+        assert_eq!(
+            report.error.as_deref(),
+            Some("The file ended before $enddefinitions")
+        );
+        assert!(report
+            .text()
+            .ends_with("\nError: The file ended before $enddefinitions"));
+
+        let report = check("resources/missing.vcd", Cursor::new(""));
+        assert_eq!(report.status, CheckStatus::Error);
+        let json: Value = serde_json::from_str(&report.json()).unwrap();
+        assert_eq!(json["status"], "error");
+        assert!(json["error"].as_str().unwrap().contains("No such file"));
+    }
+}
@@ -1,13 +1,210 @@
-use clap::Parser;
+use clap::{ArgGroup, Parser};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
+#[command(group(ArgGroup::new("report").args(["check", "dump_signals"])))]
 pub struct CliArgs {
-    /// Path to a file to open
+    /// Path to a file to open, or - to read a VCD from stdin
     #[arg(short = 'f', long = "file")]
     pub file_name: Option<String>,
 
+    /// Path to a file to open, or - to read a VCD from stdin. Same as --file.
+    #[arg(value_name = "FILE", conflicts_with = "file_name")]
+    pub file_arg: Option<String>,
+
     /// Override the default config file path
     #[arg(short = 'c', long = "config")]
     pub config_file: Option<String>,
+
+    /// Command to run after the file is loaded. Can be given multiple times.
+    #[arg(short = 'e', long = "command")]
+    pub commands: Vec<String>,
+
+    /// File with commands to run after the file is loaded, one per line. Runs before any commands
+    /// given with --command.
+    #[arg(long = "script")]
+    pub script: Option<String>,
+
+    /// Print the value of a signal at a time (<signal>@<time>) or every change in a time range
+    /// (<signal>@<start>-<end>) as `signal,time,value` lines and exit without starting the UI.
+    /// Can be given multiple times.
+    #[arg(short = 'q', long = "query")]
+    pub queries: Vec<String>,
+
+    /// Time the view starts at, in ticks or with a unit like 1.2us
+    #[arg(long = "start")]
+    pub start: Option<String>,
+
+    /// Time the view ends at, in ticks or with a unit like 1.5us
+    #[arg(long = "end")]
+    pub end: Option<String>,
+
+    /// Only load the value changes from this time on, in ticks or with a unit like 1.2us. Each
+    /// signal starts with the value it had at this time.
+    #[arg(long = "from")]
+    pub from: Option<String>,
+
+    /// Only load the value changes up to this time, in ticks or with a unit like 1.5us
+    #[arg(long = "to")]
+    pub to: Option<String>,
+
+    /// Comma-separated glob patterns of the signals to display, like "top.cpu.*,top.bus.req"
+    #[arg(long = "signals")]
+    pub signals: Option<String>,
+
+    /// Parse the file and print a summary of it and any problems in it without starting the UI.
+    /// Exits with 0 if the file is clean, 1 if it has problems, and 2 if it can't be parsed.
+    #[arg(long = "check")]
+    pub check: bool,
+
+    /// Print every signal declared in the file as `name<TAB>width<TAB>type` lines and exit
+    /// without starting the UI. Only the header of the file is read.
+    #[arg(long = "dump-signals")]
+    pub dump_signals: bool,
+
+    /// Print the signals of --dump-signals as a tree of their scopes
+    #[arg(long = "tree", requires = "dump_signals")]
+    pub tree: bool,
+
+    /// Print the summary of --check or the signals of --dump-signals as JSON
+    #[arg(long = "json", requires = "report")]
+    pub json: bool,
+
+    /// Exit on its own this many milliseconds after the UI starts, e.g. when recording it
+    #[arg(long = "exit-after", value_name = "MS")]
+    pub exit_after: Option<u64>,
+
+    /// Exit on its own after drawing this many frames
+    #[arg(long = "frames", value_name = "N", value_parser = clap::value_parser!(u64).range(1..))]
+    pub frames: Option<u64>,
+
+    /// Draw a frame after each command given with --command or --script, so that recordings show
+    /// every step. Use `sleep <ms>` commands to hold a step for longer.
+    #[arg(long = "frame-per-command")]
+    pub frame_per_command: bool,
+
+    /// Start even if the config has problems, disabling conflicting keybindings and resetting out
+    /// of range settings
+    #[arg(long = "force-config")]
+    pub force_config: bool,
+}
+
+/// File name that reads the waveform from stdin, as in `fst2vcd dump.fst | digisurf -`.
+pub const STDIN_FILE_NAME: &str = "-";
+
+impl CliArgs {
+    /// The file to open, given either with --file or on its own.
+    pub fn file(&self) -> Option<&str> {
+        self.file_name.as_deref().or(self.file_arg.as_deref())
+    }
+}
+
+/// Extracts the commands from the contents of a script file, skipping blank lines and lines
+/// starting with '#'.
+pub fn script_commands(contents: &str) -> Vec<String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_script_commands_skips_blank_lines_and_comments() {
+        let script = "# Jump to the failure\n\ngoto 1200\n   \n  marker add fail 1200  \n#zoom 4\n";
+        assert_eq!(
+            script_commands(script),
+            vec!["goto 1200".to_string(), "marker add fail 1200".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_command_flags_are_repeatable() {
+        let args = CliArgs::parse_from([
+            "digisurf",
+            "-f",
+            "trace.vcd",
+            "-e",
+            "findsignal",
+            "--command",
+            "goto 1200",
+        ]);
+        assert_eq!(args.commands, vec!["findsignal", "goto 1200"]);
+        assert_eq!(args.script, None);
+    }
+
+    #[test]
+    fn test_file_argument() {
+        let args = CliArgs::parse_from(["digisurf", "-"]);
+        assert_eq!(args.file(), Some(STDIN_FILE_NAME));
+        let args = CliArgs::parse_from(["digisurf", "--file", "dump.vcd"]);
+        assert_eq!(args.file(), Some("dump.vcd"));
+        assert_eq!(CliArgs::parse_from(["digisurf"]).file(), None);
+        assert!(CliArgs::try_parse_from(["digisurf", "-f", "a.vcd", "b.vcd"]).is_err());
+    }
+
+    #[test]
+    fn test_view_flags() {
+        let args = CliArgs::parse_from([
+            "digisurf",
+            "-f",
+            "dump.vcd",
+            "--start",
+            "1.2us",
+            "--end",
+            "1.5us",
+            "--signals",
+            "top.cpu.*,top.bus.req",
+        ]);
+        assert_eq!(args.start.as_deref(), Some("1.2us"));
+        assert_eq!(args.end.as_deref(), Some("1.5us"));
+        assert_eq!(args.signals.as_deref(), Some("top.cpu.*,top.bus.req"));
+    }
+
+    #[test]
+    fn test_load_window_flags() {
+        let args = CliArgs::parse_from(["digisurf", "dump.vcd", "--from", "1ms"]);
+        assert_eq!(args.from.as_deref(), Some("1ms"));
+        assert_eq!(args.to, None);
+    }
+
+    #[test]
+    fn test_check_flags() {
+        let args = CliArgs::parse_from(["digisurf", "--check", "--json", "dump.vcd"]);
+        assert!(args.check && args.json);
+        assert_eq!(args.file(), Some("dump.vcd"));
+        // JSON is only for the check summary
+        assert!(CliArgs::try_parse_from(["digisurf", "--json", "dump.vcd"]).is_err());
+    }
+
+    #[test]
+    fn test_dump_signals_flags() {
+        let args = CliArgs::parse_from(["digisurf", "dump.vcd", "--dump-signals", "--tree"]);
+        assert!(args.dump_signals && args.tree && !args.json);
+        assert!(CliArgs::parse_from(["digisurf", "-", "--dump-signals", "--json"]).json);
+        assert!(CliArgs::try_parse_from(["digisurf", "dump.vcd", "--tree"]).is_err());
+        assert!(CliArgs::try_parse_from(["digisurf", "--check", "--dump-signals", "x"]).is_err());
+    }
+
+    #[test]
+    fn test_unattended_flags() {
+        let args = CliArgs::parse_from([
+            "digisurf",
+            "dump.vcd",
+            "--exit-after",
+            "1500",
+            "--frames",
+            "3",
+            "--frame-per-command",
+        ]);
+        assert_eq!(args.exit_after, Some(1500));
+        assert_eq!(args.frames, Some(3));
+        assert!(args.frame_per_command);
+        assert!(CliArgs::try_parse_from(["digisurf", "--frames", "0"]).is_err());
+    }
 }
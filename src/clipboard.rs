@@ -0,0 +1,25 @@
+/// Destination for text copied with the yank command. This is abstracted behind a trait so that
+/// the command can be tested without a system clipboard.
+pub trait ClipboardBackend {
+    fn set_text(&mut self, text: &str) -> Result<(), String>;
+}
+
+/// Clipboard backend that writes to the system clipboard. The handle is created on first use and
+/// then kept alive, because on some platforms (e.g. X11) the copied text is served by the process
+/// that owns the clipboard handle and disappears when it is dropped.
+#[derive(Default)]
+pub struct SystemClipboard {
+    clipboard: Option<arboard::Clipboard>,
+}
+
+impl ClipboardBackend for SystemClipboard {
+    fn set_text(&mut self, text: &str) -> Result<(), String> {
+        let clipboard = match &mut self.clipboard {
+            Some(clipboard) => clipboard,
+            None => self
+                .clipboard
+                .insert(arboard::Clipboard::new().map_err(|e| e.to_string())?),
+        };
+        clipboard.set_text(text).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// Command lines run by the user's own command names, by name. A line may be several commands
+/// separated by `;`.
+pub type CommandAliases = BTreeMap<String, String>;
+
+#[derive(Default, Serialize, Deserialize)]
+struct CommandAliasesFile {
+    #[serde(default)]
+    aliases: CommandAliases,
+}
+
+/// Reads the saved command aliases. A missing or unreadable file has no aliases.
+pub fn load_command_aliases(path: &Path) -> CommandAliases {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| toml::from_str::<CommandAliasesFile>(&content).ok())
+        .map(|file| file.aliases)
+        .unwrap_or_default()
+}
+
+pub fn save_command_aliases(path: &Path, aliases: &CommandAliases) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string(&CommandAliasesFile {
+        aliases: aliases.clone(),
+    })
+    .map_err(io::Error::other)?;
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_command_aliases_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("aliases.toml");
+
+        let aliases = CommandAliases::from([
+            ("g".to_string(), "goto".to_string()),
+            (
+                "failwin".to_string(),
+                "goto 123456; marker add \"fail point\"".to_string(),
+            ),
+        ]);
+        save_command_aliases(&path, &aliases).unwrap();
+        assert_eq!(load_command_aliases(&path), aliases);
+    }
+
+    #[test]
+    fn test_missing_command_aliases_is_empty() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_command_aliases(&dir.path().join("aliases.toml")).is_empty());
+    }
+}
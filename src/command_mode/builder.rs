@@ -1,12 +1,15 @@
 use super::registry::Command;
 use std::rc::Rc;
 
+/// The closure type that commands use to process their arguments and mutate state
+type Handler<S> = Box<dyn Fn(&[&str], &mut S) -> Result<String, String>>;
+
 /// A builder pattern implementation for easily creating commands
 pub struct CommandBuilder<S> {
     name: String,
     aliases: Vec<String>,
     description: String,
-    handler: Box<dyn Fn(&[&str], &mut S) -> Result<String, String>>,
+    handler: Handler<S>,
 }
 
 impl<S> CommandBuilder<S> {
@@ -48,7 +51,7 @@ struct BuiltCommand<S> {
     name: String,
     aliases: Vec<String>,
     description: String,
-    handler: Box<dyn Fn(&[&str], &mut S) -> Result<String, String>>,
+    handler: Handler<S>,
 }
 
 impl<S> Command<S> for BuiltCommand<S> {
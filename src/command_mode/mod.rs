@@ -1,29 +1,39 @@
+//! A vim-like command line for ratatui apps, generic over the state its commands act on.
+//! Commands are made with [`builder::CommandBuilder`] and run with [`parser::CommandParser`].
+
 pub mod builder;
-mod parser;
+pub mod parser;
 pub mod registry;
 pub mod state;
-use crossterm::event::{KeyCode, KeyEvent};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use parser::CommandParser;
-use ratatui::{
-    layout::Rect,
-    style::{Color, Style},
-    text::Span,
-    widgets::{Block, Borders, Paragraph, Widget},
-};
 use registry::Command;
 use state::CommandModeState;
 use std::rc::Rc;
 
+/// State that keeps the command line being typed and the last command's result.
 pub trait CommandModeStateAccess {
     fn command_state(&self) -> &CommandModeState;
     fn command_state_mut(&mut self) -> &mut CommandModeState;
 }
 
+/// State that keeps the user's own command aliases, each running a stored command line.
+pub trait CommandAliasAccess {
+    fn command_alias(&self, name: &str) -> Option<&str>;
+}
+
+/// Edits a command line with the keyboard and runs it with its parser.
 pub struct CommandModeWidget<S> {
     is_active: bool,
     command_parser: CommandParser<S>,
 }
 
+impl<S> Default for CommandModeWidget<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<S> CommandModeWidget<S> {
     pub fn new() -> Self {
         Self {
@@ -43,6 +53,7 @@ impl<S> CommandModeWidget<S> {
         self.is_active
     }
 
+    /// Edits the command line, or moves through the history, for a key press.
     pub fn handle_input(&mut self, key: KeyEvent, app_state: &mut S)
     where
         S: CommandModeStateAccess,
@@ -57,6 +68,20 @@ impl<S> CommandModeWidget<S> {
             KeyCode::End => cmd_state.move_cursor_end(),
             KeyCode::Backspace => cmd_state.backspace(),
             KeyCode::Delete => cmd_state.delete(),
+            // Readline keys, which aren't typed as characters
+            KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::CONTROL) => match c {
+                'a' => cmd_state.move_cursor_start(),
+                'e' => cmd_state.move_cursor_end(),
+                'w' => cmd_state.delete_word_before(),
+                'u' => cmd_state.delete_to_start(),
+                'k' => cmd_state.delete_to_end(),
+                _ => {}
+            },
+            KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::ALT) => match c {
+                'b' => cmd_state.move_word_left(),
+                'f' => cmd_state.move_word_right(),
+                _ => {}
+            },
             KeyCode::Char(c) => cmd_state.insert(c),
             // Up/down for history navigation
             KeyCode::Up => cmd_state.previous_history(),
@@ -65,9 +90,11 @@ impl<S> CommandModeWidget<S> {
         }
     }
 
+    /// Runs the command line typed so far, keeping its result in the command state. Returns false
+    /// if nothing was typed.
     pub fn execute(&mut self, app_state: &mut S) -> bool
     where
-        S: CommandModeStateAccess + 'static,
+        S: CommandModeStateAccess + CommandAliasAccess + 'static,
     {
         let command = app_state.command_state().input_buffer.clone();
 
@@ -84,14 +111,8 @@ impl<S> CommandModeWidget<S> {
         // Store result
         let cmd_state = app_state.command_state_mut();
         match result {
-            Ok(msg) => {
-                cmd_state.result_message = Some(msg);
-                cmd_state.result_is_error = false;
-            }
-            Err(err) => {
-                cmd_state.result_message = Some(err);
-                cmd_state.result_is_error = true;
-            }
+            Ok(msg) => cmd_state.push_result(msg, false),
+            Err(err) => cmd_state.push_result(err, true),
         }
 
         // Clear input buffer but keep result visible
@@ -114,61 +135,31 @@ impl<S> CommandModeWidget<S> {
     }
 }
 
-pub struct CommandModeRender<'a> {
-    is_active: bool,
-    input_buffer: &'a str,
-    result_message: Option<&'a str>,
-    result_is_error: bool,
-}
-
-impl<'a> Widget for CommandModeRender<'a> {
-    fn render(self, area: Rect, buf: &mut ratatui::buffer::Buffer) {
-        let block = Block::default()
-            .borders(Borders::TOP)
-            .style(Style::default().fg(Color::White));
-
-        if self.is_active {
-            // Render command input
-            let prompt = Span::styled(":", Style::default().fg(Color::Yellow));
-            let input = Span::raw(self.input_buffer);
-            let text = ratatui::text::Line::from(vec![prompt, input]);
-
-            Paragraph::new(text)
-                .block(block)
-                .style(Style::default().fg(Color::White))
-                .render(area, buf);
-        } else if let Some(message) = self.result_message {
-            // Render command result
-            let style = if self.result_is_error {
-                Style::default().fg(Color::Red)
-            } else {
-                Style::default().fg(Color::Green)
-            };
-
-            let text = ratatui::text::Line::from(Span::styled(message, style));
-
-            Paragraph::new(text)
-                .block(block)
-                .style(Style::default().fg(Color::White))
-                .render(area, buf);
-        } else {
-            // Render empty command box
-            Paragraph::new("")
-                .block(block)
-                .style(Style::default().fg(Color::White))
-                .render(area, buf);
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use builder::CommandBuilder;
+    use std::collections::HashMap;
 
     // The state that wraps command mode state and is used in the top-level ratatui application
     struct TestAppState {
         command_state: CommandModeState,
+        aliases: HashMap<String, String>,
+    }
+
+    impl TestAppState {
+        fn new() -> Self {
+            Self {
+                command_state: CommandModeState::new(),
+                aliases: HashMap::new(),
+            }
+        }
+    }
+
+    impl CommandAliasAccess for TestAppState {
+        fn command_alias(&self, name: &str) -> Option<&str> {
+            self.aliases.get(name).map(String::as_str)
+        }
     }
 
     impl CommandModeStateAccess for TestAppState {
@@ -184,9 +175,7 @@ mod tests {
     #[test]
     fn test_command_builder() {
         // Create a test app state
-        let mut app_state = TestAppState {
-            command_state: CommandModeState::new(),
-        };
+        let mut app_state = TestAppState::new();
 
         // Create a command mode widget that implements the CommandRegistry trait
         let mut command_widget = CommandModeWidget::new();
@@ -373,9 +362,7 @@ mod tests {
 
     #[test]
     fn test_command_execution() {
-        let mut state = TestAppState {
-            command_state: CommandModeState::new(),
-        };
+        let mut state = TestAppState::new();
 
         let mut command_widget = CommandModeWidget::new();
 
@@ -397,7 +384,7 @@ mod tests {
                 "count",
                 "Count command",
                 |args, state: &mut TestAppState| {
-                    if let Some(arg) = args.get(0) {
+                    if let Some(arg) = args.first() {
                         if let Ok(count) = arg.parse::<usize>() {
                             for _ in 0..count {
                                 state.command_state_mut().input_buffer.push('x');
@@ -432,4 +419,123 @@ mod tests {
         let result = command_widget.parser().execute("nonexistent", &mut state);
         assert!(result.is_err());
     }
+
+    fn prefix_test_widget() -> CommandModeWidget<TestAppState> {
+        let mut command_widget = CommandModeWidget::new();
+        for (name, alias) in [("zoom", None), ("zoomfull", Some("zf")), ("zfw", None)] {
+            let mut builder = CommandBuilder::new(name, "Test command", move |_args, _state| {
+                Ok(name.to_string())
+            });
+            if let Some(alias) = alias {
+                builder = builder.alias(alias);
+            }
+            command_widget.register_command(builder.build());
+        }
+        command_widget
+    }
+
+    #[test]
+    fn test_command_unique_prefix() {
+        let mut state = TestAppState::new();
+        let command_widget = prefix_test_widget();
+
+        let result = command_widget.parser().execute("zoomf", &mut state);
+        assert_eq!(result, Ok("zoomfull".to_string()));
+
+        // An exact name wins even though it also prefixes another command
+        let result = command_widget.parser().execute("zoom", &mut state);
+        assert_eq!(result, Ok("zoom".to_string()));
+
+        let result = command_widget.parser().execute("x", &mut state);
+        assert_eq!(result, Err("Unknown command: x".to_string()));
+    }
+
+    #[test]
+    fn test_command_ambiguous_prefix() {
+        let mut state = TestAppState::new();
+        let command_widget = prefix_test_widget();
+
+        let result = command_widget.parser().execute("z", &mut state);
+        assert_eq!(
+            result,
+            Err("ambiguous command: zfw, zoom, zoomfull".to_string())
+        );
+
+        let result = command_widget.parser().execute("zo", &mut state);
+        assert_eq!(result, Err("ambiguous command: zoom, zoomfull".to_string()));
+    }
+
+    #[test]
+    fn test_command_exact_alias_beats_prefix() {
+        let mut state = TestAppState::new();
+        let command_widget = prefix_test_widget();
+
+        // "zf" is an alias of zoomfull and also a prefix of zfw
+        let result = command_widget.parser().execute("zf", &mut state);
+        assert_eq!(result, Ok("zoomfull".to_string()));
+    }
+
+    #[test]
+    fn test_alias_runs_commands_in_order() {
+        let mut state = TestAppState::new();
+        let command_widget = alias_test_widget();
+        state
+            .aliases
+            .insert("twice".to_string(), "push a; push \"b c\";push".to_string());
+
+        // Arguments given to the alias go to its last command
+        let result = command_widget.parser().execute("twice d", &mut state);
+        assert_eq!(result, Ok("pushed d".to_string()));
+        assert_eq!(state.command_state.input_buffer, "a|b c|d|");
+    }
+
+    #[test]
+    fn test_alias_stops_at_first_error() {
+        let mut state = TestAppState::new();
+        let command_widget = alias_test_widget();
+        state
+            .aliases
+            .insert("broken".to_string(), "push a; fail; push b".to_string());
+
+        let result = command_widget.parser().execute("broken", &mut state);
+        assert_eq!(result, Err("'fail': failed".to_string()));
+        assert_eq!(state.command_state.input_buffer, "a|");
+    }
+
+    #[test]
+    fn test_alias_calling_itself_is_refused() {
+        let mut state = TestAppState::new();
+        let command_widget = alias_test_widget();
+        state
+            .aliases
+            .insert("ping".to_string(), "push a; pong".to_string());
+        state.aliases.insert("pong".to_string(), "ping".to_string());
+
+        let result = command_widget.parser().execute("ping", &mut state);
+        assert_eq!(
+            result,
+            Err("'pong': 'ping': Alias 'ping' calls itself".to_string())
+        );
+        assert_eq!(state.command_state.input_buffer, "a|");
+    }
+
+    fn alias_test_widget() -> CommandModeWidget<TestAppState> {
+        let mut command_widget = CommandModeWidget::new();
+        command_widget.register_command(
+            CommandBuilder::new("push", "Test command", |args, state: &mut TestAppState| {
+                let arg = args.join(" ");
+                state.command_state.input_buffer.push_str(&arg);
+                state.command_state.input_buffer.push('|');
+                Ok(format!("pushed {}", arg))
+            })
+            .build(),
+        );
+        command_widget.register_command(
+            CommandBuilder::new("fail", "Test command", |_args, _state| {
+                Err("failed".to_string())
+            })
+            .build(),
+        );
+        command_widget
+    }
 }
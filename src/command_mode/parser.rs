@@ -1,10 +1,18 @@
-use super::registry::CommandRegistry;
+use super::{registry::CommandRegistry, CommandAliasAccess};
 
+/// Runs command lines like `marker add start 100` with the commands of its registry.
 pub struct CommandParser<S> {
     registry: CommandRegistry<S>,
 }
 
+impl<S> Default for CommandParser<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<S> CommandParser<S> {
+    /// A parser with no commands yet.
     pub fn new() -> Self {
         let registry = CommandRegistry::new();
         Self { registry }
@@ -14,8 +22,29 @@ impl<S> CommandParser<S> {
         Self { registry }
     }
 
-    pub fn execute(&self, input: &str, state: &mut S) -> Result<String, String> {
-        let parts: Vec<&str> = input.trim().split_whitespace().collect();
+    /// Runs a command line on `state`, returning the command's message. The command is found by
+    /// its name or an alias, then as a user alias of `state`, then by a prefix of its name that no
+    /// other command starts with.
+    pub fn execute(&self, input: &str, state: &mut S) -> Result<String, String>
+    where
+        S: CommandAliasAccess,
+    {
+        self.execute_calling(input, state, &mut Vec::new())
+    }
+
+    // Runs a command line, which may use a user alias. `running_aliases` are the aliases whose
+    // commands are being run, so an alias calling itself is refused instead of running forever.
+    fn execute_calling(
+        &self,
+        input: &str,
+        state: &mut S,
+        running_aliases: &mut Vec<String>,
+    ) -> Result<String, String>
+    where
+        S: CommandAliasAccess,
+    {
+        let parts = tokenize(input)?;
+        let parts: Vec<&str> = parts.iter().map(String::as_str).collect();
 
         if parts.is_empty() {
             return Err("No command provided".to_string());
@@ -24,17 +53,228 @@ impl<S> CommandParser<S> {
         let command_name = parts[0];
         let args = &parts[1..];
 
-        match self.registry.get(command_name) {
-            Some(command) => command.execute(args, state),
-            None => Err(format!("Unknown command: {}", command_name)),
+        // An exact name or alias always wins, then a user alias, otherwise fall back to a unique
+        // prefix
+        if let Some(command) = self.registry.get(command_name) {
+            return command.execute(args, state);
+        }
+
+        if let Some(line) = state.command_alias(command_name).map(str::to_string) {
+            return self.execute_alias(command_name, &line, args, state, running_aliases);
+        }
+
+        let candidates = self.registry.find_by_prefix(command_name);
+        match candidates.as_slice() {
+            [] => Err(format!("Unknown command: {}", command_name)),
+            [command] => command.execute(args, state),
+            _ => Err(format!(
+                "ambiguous command: {}",
+                candidates
+                    .iter()
+                    .map(|cmd| cmd.name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    // Runs the commands of a user alias in order, stopping at the first one that fails. Arguments
+    // given to the alias are passed on to its last command.
+    fn execute_alias(
+        &self,
+        name: &str,
+        line: &str,
+        args: &[&str],
+        state: &mut S,
+        running_aliases: &mut Vec<String>,
+    ) -> Result<String, String>
+    where
+        S: CommandAliasAccess,
+    {
+        if running_aliases.iter().any(|running| running == name) {
+            return Err(format!("Alias '{}' calls itself", name));
         }
+        running_aliases.push(name.to_string());
+
+        let commands = split_commands(line);
+        let mut result = Ok(String::new());
+        for (i, command) in commands.iter().enumerate() {
+            let command = if i == commands.len() - 1 && !args.is_empty() {
+                format!("{} {}", command, quote_args(args))
+            } else {
+                command.to_string()
+            };
+            result = self
+                .execute_calling(&command, state, running_aliases)
+                .map_err(|err| format!("'{}': {}", command.trim(), err));
+            if result.is_err() {
+                break;
+            }
+        }
+
+        running_aliases.pop();
+        result
     }
 
+    /// The name and description of each registered command.
     pub fn list_commands(&self) -> Vec<(&str, &str)> {
         self.registry.list_commands()
     }
 
+    pub fn registry(&self) -> &CommandRegistry<S> {
+        &self.registry
+    }
+
     pub fn registry_mut(&mut self) -> &mut CommandRegistry<S> {
         &mut self.registry
     }
 }
+
+/// Splits a command line into arguments at whitespace. Double quotes keep whitespace within an
+/// argument, e.g. `marker add "reset done" 500`, and `\"` is a literal quote. Inside quotes `\\`
+/// is a literal backslash, while other backslashes are kept as they are, so escaped VCD identifiers
+/// like `\bus[0]` don't need quoting.
+pub fn tokenize(input: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    // The argument being read, if any. Quotes start an argument even if nothing is inside them.
+    let mut current: Option<String> = None;
+    let mut in_quotes = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'"') || (in_quotes && chars.peek() == Some(&'\\')) => {
+                current.get_or_insert_with(String::new).extend(chars.next());
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                current.get_or_insert_with(String::new);
+            }
+            c if c.is_whitespace() && !in_quotes => args.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("Missing closing quote".to_string());
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// Splits a compound command line into its commands at the semicolons outside of quotes.
+pub fn split_commands(line: &str) -> Vec<&str> {
+    let mut commands = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => {
+                commands.push(&line[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    commands.push(&line[start..]);
+    commands
+}
+
+/// Joins arguments into a command line that splits back into the same arguments, quoting those
+/// that are empty or have whitespace, quotes or a `;`, which would end the command, in them.
+pub fn quote_args(args: &[&str]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"' || c == ';') {
+                format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+            } else {
+                arg.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(input: &str) -> Vec<String> {
+        tokenize(input).unwrap()
+    }
+
+    #[test]
+    fn test_tokenize_unquoted_splits_on_whitespace() {
+        assert_eq!(
+            args("  marker add\tfail   1200 "),
+            ["marker", "add", "fail", "1200"]
+        );
+        assert_eq!(
+            args("show \\top.bus[0] a\\b"),
+            ["show", "\\top.bus[0]", "a\\b"]
+        );
+        assert!(args("   ").is_empty());
+    }
+
+    #[test]
+    fn test_tokenize_quoted_arguments() {
+        assert_eq!(
+            args("marker add \"reset done\" 500"),
+            ["marker", "add", "reset done", "500"]
+        );
+        // Quotes can be part of a larger argument, and empty quotes are an empty argument
+        assert_eq!(args("a\"b c\"d \"\""), ["ab cd", ""]);
+    }
+
+    #[test]
+    fn test_tokenize_escapes() {
+        assert_eq!(args("\"say \\\"hi\\\"\" \\\"x"), ["say \"hi\"", "\"x"]);
+        assert_eq!(args("\"a\\\\b\\c\""), ["a\\b\\c"]);
+    }
+
+    #[test]
+    fn test_split_commands() {
+        assert_eq!(
+            split_commands("goto 123456; zoom in 4"),
+            ["goto 123456", " zoom in 4"]
+        );
+        assert_eq!(
+            split_commands("marker add \"a;b\" 5;goto 5"),
+            ["marker add \"a;b\" 5", "goto 5"]
+        );
+        assert_eq!(split_commands("goto 5"), ["goto 5"]);
+    }
+
+    #[test]
+    fn test_quote_args_round_trip() {
+        let arguments = ["goto", "reset done", "", "say \"hi\"", "a;b", "\\top.x"];
+        let line = quote_args(&arguments);
+        assert_eq!(args(&line), arguments);
+
+        // Stays one command when split at semicolons, as alias and history lines are
+        let line = quote_args(&["marker", "add", "a;b"]);
+        assert_eq!(split_commands(&line), [line.as_str()]);
+        assert_eq!(args(&line), ["marker", "add", "a;b"]);
+    }
+
+    #[test]
+    fn test_tokenize_unterminated_quote_is_err() {
+        assert_eq!(
+            tokenize("marker add \"reset done 500"),
+            Err("Missing closing quote".to_string())
+        );
+        assert_eq!(
+            tokenize("marker add \\\""),
+            Ok(vec![
+                "marker".to_string(),
+                "add".to_string(),
+                "\"".to_string()
+            ])
+        );
+    }
+}
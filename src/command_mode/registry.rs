@@ -1,18 +1,31 @@
 use std::{collections::HashMap, rc::Rc};
 
+/// A command run from command mode, acting on the state `S`. Most are made with
+/// [`CommandBuilder`](super::builder::CommandBuilder).
 pub trait Command<S> {
     fn name(&self) -> &str;
+    /// Other names the command can be run by, like `q` for `quit`
     fn aliases(&self) -> Vec<&str> {
         vec![]
     }
+    /// One line shown in the help and the command list
     fn description(&self) -> &str;
+    /// Runs the command with the words after its name. The message is shown as the result, and an
+    /// error is shown as one.
     fn execute(&self, args: &[&str], state: &mut S) -> Result<String, String>;
 }
 
+/// Commands by name and alias.
 pub struct CommandRegistry<S> {
     commands: HashMap<String, Rc<Box<dyn Command<S>>>>,
 }
 
+impl<S> Default for CommandRegistry<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<S> CommandRegistry<S> {
     pub fn new() -> Self {
         Self {
@@ -20,6 +33,7 @@ impl<S> CommandRegistry<S> {
         }
     }
 
+    /// Adds a command under its name and its aliases, replacing any command already there.
     pub fn register(&mut self, command: Rc<Box<dyn Command<S>>>) {
         let name = command.name().to_string();
         self.commands.insert(name.clone(), Rc::clone(&command));
@@ -31,10 +45,30 @@ impl<S> CommandRegistry<S> {
         }
     }
 
+    /// The command with a name or alias, which must be given in full.
     pub fn get(&self, name: &str) -> Option<&Rc<Box<dyn Command<S>>>> {
         self.commands.get(name)
     }
 
+    /// Finds the commands with a name or alias starting with `prefix`, listing each one once, sorted
+    /// by name.
+    pub fn find_by_prefix(&self, prefix: &str) -> Vec<&Rc<Box<dyn Command<S>>>> {
+        let mut matches: Vec<&Rc<Box<dyn Command<S>>>> = Vec::new();
+        for (key, cmd) in &self.commands {
+            if key.starts_with(prefix) && !matches.iter().any(|m| m.name() == cmd.name()) {
+                matches.push(cmd);
+            }
+        }
+        matches.sort_by_key(|cmd| cmd.name());
+        matches
+    }
+
+    /// Every registered command once, sorted by name.
+    pub fn commands(&self) -> Vec<&Rc<Box<dyn Command<S>>>> {
+        self.find_by_prefix("")
+    }
+
+    /// The name and description of each command, in no particular order.
     pub fn list_commands(&self) -> Vec<(&str, &str)> {
         // Only include primary commands (not aliases)
         let mut unique_commands = Vec::new();
@@ -1,12 +1,33 @@
+use crate::constants::{
+    COMMAND_RESULT_HIDE_THRESHOLD_SECONDS, MAX_COMMAND_RESULTS, MAX_SHOWN_COMMAND_RESULTS,
+};
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// The result of a command, or a message shown as if a command had returned it.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CommandResult {
+    pub message: String,
+    pub is_error: bool,
+    /// When it was shown, to hide it after a while if it isn't an error
+    pub time: Instant,
+    /// Whether it is still shown above the command bar
+    pub shown: bool,
+}
+
+/// The command line being typed, the commands run before it and their recent results.
 #[derive(Default)]
 pub struct CommandModeState {
     pub input_buffer: String,
+    /// Byte position of the cursor in `input_buffer`
     pub cursor_position: usize,
     pub history: Vec<String>,
+    /// Entry of `history` shown in `input_buffer` while going through it
     pub history_index: Option<usize>,
-    pub result_message: Option<String>,
-    pub command_result_time: Option<std::time::Instant>,
-    pub result_is_error: bool,
+    /// The last `MAX_COMMAND_RESULTS` results, oldest first, listed by `:messages`
+    pub results: VecDeque<CommandResult>,
 }
 
 impl CommandModeState {
@@ -52,12 +73,103 @@ impl CommandModeState {
         self.cursor_position = 0;
     }
 
+    /// Deletes the word before the cursor, and the separators between it and the cursor.
+    pub fn delete_word_before(&mut self) {
+        let start = previous_word_start(&self.input_buffer, self.cursor_position);
+        self.input_buffer
+            .replace_range(start..self.cursor_position, "");
+        self.cursor_position = start;
+    }
+
+    pub fn delete_to_start(&mut self) {
+        self.input_buffer.replace_range(..self.cursor_position, "");
+        self.cursor_position = 0;
+    }
+
+    pub fn delete_to_end(&mut self) {
+        self.input_buffer.truncate(self.cursor_position);
+    }
+
+    pub fn move_word_left(&mut self) {
+        self.cursor_position = previous_word_start(&self.input_buffer, self.cursor_position);
+    }
+
+    pub fn move_word_right(&mut self) {
+        self.cursor_position = next_word_end(&self.input_buffer, self.cursor_position);
+    }
+
     pub fn clear(&mut self) {
         self.input_buffer.clear();
         self.cursor_position = 0;
         self.history_index = None;
-        self.result_message = None;
-        self.result_is_error = false;
+        self.dismiss_results();
+    }
+
+    /// Shows a result above the command bar and keeps it in the results, forgetting the oldest
+    /// one if there are too many.
+    pub fn push_result(&mut self, message: String, is_error: bool) {
+        if self.results.len() == MAX_COMMAND_RESULTS {
+            self.results.pop_front();
+        }
+        self.results.push_back(CommandResult {
+            message,
+            is_error,
+            time: Instant::now(),
+            shown: true,
+        });
+    }
+
+    /// The results shown above the command bar, at most `MAX_SHOWN_COMMAND_RESULTS` of the most
+    /// recent ones, oldest first.
+    pub fn shown_results(&self) -> Vec<&CommandResult> {
+        let mut shown: Vec<&CommandResult> = self
+            .results
+            .iter()
+            .rev()
+            .filter(|result| result.shown)
+            .take(MAX_SHOWN_COMMAND_RESULTS)
+            .collect();
+        shown.reverse();
+        shown
+    }
+
+    /// The most recent result shown above the command bar.
+    pub fn result_message(&self) -> Option<&str> {
+        self.latest_shown().map(|result| result.message.as_str())
+    }
+
+    /// Whether the most recent result shown above the command bar is an error.
+    pub fn result_is_error(&self) -> bool {
+        self.latest_shown().is_some_and(|result| result.is_error)
+    }
+
+    fn latest_shown(&self) -> Option<&CommandResult> {
+        self.results.iter().rev().find(|result| result.shown)
+    }
+
+    /// Hides each successful result once it has been shown for a while at `now`. Errors stay until
+    /// they are dismissed.
+    pub fn expire_results(&mut self, now: Instant) {
+        let threshold = Duration::from_secs(COMMAND_RESULT_HIDE_THRESHOLD_SECONDS);
+        for result in self.results.iter_mut().filter(|result| !result.is_error) {
+            if now.saturating_duration_since(result.time) >= threshold {
+                result.shown = false;
+            }
+        }
+    }
+
+    /// Hides the errors shown above the command bar.
+    pub fn dismiss_errors(&mut self) {
+        for result in self.results.iter_mut().filter(|result| result.is_error) {
+            result.shown = false;
+        }
+    }
+
+    /// Hides every result shown above the command bar. They are still listed by `:messages`.
+    pub fn dismiss_results(&mut self) {
+        for result in &mut self.results {
+            result.shown = false;
+        }
     }
 
     pub fn add_to_history(&mut self) {
@@ -96,3 +208,180 @@ impl CommandModeState {
         self.cursor_position = self.input_buffer.len();
     }
 }
+
+/// Whether `c` separates words when editing by word. Dots and underscores do, so the parts of a
+/// hierarchical signal name like `top.cpu.alu_out` are words of their own.
+fn is_word_separator(c: char) -> bool {
+    c.is_whitespace() || c == '.' || c == '_'
+}
+
+/// Where the word before byte position `pos` of `text` starts, skipping the separators right
+/// before `pos`.
+pub fn previous_word_start(text: &str, pos: usize) -> usize {
+    let mut chars = text[..pos].char_indices().rev().peekable();
+    while chars.next_if(|(_, c)| is_word_separator(*c)).is_some() {}
+    while chars.next_if(|(_, c)| !is_word_separator(*c)).is_some() {}
+    chars.peek().map_or(0, |(i, c)| i + c.len_utf8())
+}
+
+/// Where the word after byte position `pos` of `text` ends, skipping the separators right after
+/// `pos`.
+fn next_word_end(text: &str, pos: usize) -> usize {
+    let mut chars = text[pos..].char_indices().peekable();
+    while chars.next_if(|(_, c)| is_word_separator(*c)).is_some() {}
+    while chars.next_if(|(_, c)| !is_word_separator(*c)).is_some() {}
+    chars.peek().map_or(text.len(), |(i, _)| pos + i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(results: &[&CommandResult]) -> Vec<String> {
+        results
+            .iter()
+            .map(|result| result.message.clone())
+            .collect()
+    }
+
+    fn typed(text: &str, cursor_position: usize) -> CommandModeState {
+        let mut state = CommandModeState::new();
+        state.input_buffer = text.to_string();
+        state.cursor_position = cursor_position;
+        state
+    }
+
+    #[test]
+    fn test_delete_word_before() {
+        // The parts of a signal path are deleted one at a time
+        let mut state = typed("add top.cpu.alu_out", 19);
+        state.delete_word_before();
+        assert_eq!(
+            (state.input_buffer.as_str(), state.cursor_position),
+            ("add top.cpu.alu_", 16)
+        );
+        state.delete_word_before();
+        assert_eq!(
+            (state.input_buffer.as_str(), state.cursor_position),
+            ("add top.cpu.", 12)
+        );
+        state.delete_word_before();
+        assert_eq!(
+            (state.input_buffer.as_str(), state.cursor_position),
+            ("add top.", 8)
+        );
+        state.delete_word_before();
+        state.delete_word_before();
+        assert_eq!(
+            (state.input_buffer.as_str(), state.cursor_position),
+            ("", 0)
+        );
+
+        // Nothing before the cursor, nothing to delete
+        state.delete_word_before();
+        assert_eq!(
+            (state.input_buffer.as_str(), state.cursor_position),
+            ("", 0)
+        );
+
+        // Only what's before the cursor is deleted
+        let mut state = typed("goto 100 ns", 8);
+        state.delete_word_before();
+        assert_eq!(
+            (state.input_buffer.as_str(), state.cursor_position),
+            ("goto  ns", 5)
+        );
+
+        let mut state = typed("..x", 2);
+        state.delete_word_before();
+        assert_eq!(
+            (state.input_buffer.as_str(), state.cursor_position),
+            ("x", 0)
+        );
+    }
+
+    #[test]
+    fn test_delete_to_start_and_end() {
+        let mut state = typed("goto 100", 4);
+        state.delete_to_start();
+        assert_eq!(
+            (state.input_buffer.as_str(), state.cursor_position),
+            (" 100", 0)
+        );
+
+        let mut state = typed("goto 100", 4);
+        state.delete_to_end();
+        assert_eq!(
+            (state.input_buffer.as_str(), state.cursor_position),
+            ("goto", 4)
+        );
+        state.delete_to_end();
+        assert_eq!(state.input_buffer, "goto");
+    }
+
+    #[test]
+    fn test_move_by_word() {
+        let mut state = typed("add top.cpu.alu", 15);
+        state.move_word_left();
+        assert_eq!(state.cursor_position, 12);
+        state.move_word_left();
+        assert_eq!(state.cursor_position, 8);
+        state.move_word_left();
+        state.move_word_left();
+        assert_eq!(state.cursor_position, 0);
+        state.move_word_left();
+        assert_eq!(state.cursor_position, 0);
+
+        state.move_word_right();
+        assert_eq!(state.cursor_position, 3);
+        state.move_word_right();
+        assert_eq!(state.cursor_position, 7);
+        state.move_word_right();
+        state.move_word_right();
+        assert_eq!(state.cursor_position, 15);
+        state.move_word_right();
+        assert_eq!(state.cursor_position, 15);
+    }
+
+    #[test]
+    fn test_results_are_bounded() {
+        let mut state = CommandModeState::new();
+        for i in 0..MAX_COMMAND_RESULTS + 5 {
+            state.push_result(format!("result {}", i), false);
+        }
+        assert_eq!(state.results.len(), MAX_COMMAND_RESULTS);
+        assert_eq!(state.results[0].message, "result 5");
+
+        // Only the most recent are shown
+        assert_eq!(
+            messages(&state.shown_results()),
+            vec!["result 102", "result 103", "result 104"]
+        );
+        assert_eq!(state.result_message(), Some("result 104"));
+    }
+
+    #[test]
+    fn test_results_expire_one_by_one() {
+        let mut state = CommandModeState::new();
+        state.push_result("first".to_string(), false);
+        state.push_result("oops".to_string(), true);
+        state.push_result("second".to_string(), false);
+        let threshold = Duration::from_secs(COMMAND_RESULT_HIDE_THRESHOLD_SECONDS);
+        let start = state.results[0].time;
+        state.results[2].time = start + threshold / 2;
+
+        // The first success was shown long enough, the second not yet, and the error stays
+        let now = start + threshold;
+        state.expire_results(now);
+        assert_eq!(messages(&state.shown_results()), vec!["oops", "second"]);
+
+        state.expire_results(now + threshold);
+        assert_eq!(messages(&state.shown_results()), vec!["oops"]);
+        assert!(state.result_is_error());
+
+        state.dismiss_errors();
+        assert!(state.shown_results().is_empty());
+        assert_eq!(state.result_message(), None);
+        assert_eq!(state.results.len(), 3);
+    }
+}
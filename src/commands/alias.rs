@@ -0,0 +1,200 @@
+use crate::{
+    command_aliases,
+    command_mode::{
+        builder::CommandBuilder,
+        parser::{quote_args, split_commands, tokenize},
+        registry::Command,
+    },
+    state::AppState,
+};
+use std::{collections::HashSet, rc::Rc};
+
+/// `builtin_names` are the names and aliases of the built-in commands, which user aliases can't
+/// take over.
+pub fn create(builtin_names: HashSet<String>) -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "alias",
+        "Name a command line, with commands separated by ;, or list the aliases",
+        move |args, state: &mut AppState| match args {
+            ["list"] => {
+                if state.command_aliases.is_empty() {
+                    return Ok("No command aliases".to_string());
+                }
+                let aliases: Vec<String> = state
+                    .command_aliases
+                    .iter()
+                    .map(|(name, line)| format!("{} = {}", name, line))
+                    .collect();
+                Ok(aliases.join(", "))
+            }
+            [name, command @ ..] if !command.is_empty() => {
+                if name.is_empty() || *name == "list" {
+                    return Err(format!("Can't use '{}' as an alias name", name));
+                }
+                if builtin_names.contains(*name) {
+                    return Err(format!("'{}' is already a command", name));
+                }
+
+                // A single argument is the whole command line, as in alias w "goto 5; zoom in"
+                let line = match command {
+                    [line] => line.to_string(),
+                    _ => join_commands(command),
+                };
+                if calls_alias(name, &line, state, &mut HashSet::new()) {
+                    return Err(format!("Alias '{}' would call itself", name));
+                }
+
+                state.command_aliases.insert(name.to_string(), line.clone());
+                persist(state)?;
+                Ok(format!("Alias '{}' runs: {}", name, line))
+            }
+            _ => Err("Usage: alias <name> <command>[; <command>...] or alias list".to_string()),
+        },
+    )
+    .build()
+}
+
+/// Saves the command aliases, if there is somewhere to save them.
+pub fn persist(state: &AppState) -> Result<(), String> {
+    match &state.command_aliases_path {
+        Some(path) => command_aliases::save_command_aliases(path, &state.command_aliases)
+            .map_err(|e| format!("Couldn't save command aliases: {}", e)),
+        None => Ok(()),
+    }
+}
+
+// Helper function to join the arguments of an alias into its command line. Semicolons in them
+// separate its commands, so only the text between them is quoted.
+fn join_commands(args: &[&str]) -> String {
+    args.iter()
+        .map(|arg| {
+            arg.split(';')
+                .map(|part| match part {
+                    "" => String::new(),
+                    part => quote_args(&[part]),
+                })
+                .collect::<Vec<_>>()
+                .join(";")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Helper function to check whether running `line` would run the alias `target`, directly or through
+// other aliases. `visited` are the aliases already checked.
+fn calls_alias(target: &str, line: &str, state: &AppState, visited: &mut HashSet<String>) -> bool {
+    split_commands(line).into_iter().any(|command| {
+        let Some(name) = tokenize(command)
+            .ok()
+            .and_then(|parts| parts.into_iter().next())
+        else {
+            return false;
+        };
+        if name == target {
+            return true;
+        }
+        match state.command_aliases.get(&name) {
+            Some(called) if visited.insert(name.clone()) => {
+                calls_alias(target, called, state, visited)
+            }
+            _ => false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_alias() -> Rc<Box<dyn Command<AppState>>> {
+        create(HashSet::from(["goto".to_string(), "zf".to_string()]))
+    }
+
+    #[test]
+    fn test_alias_define_and_list() {
+        let command = create_alias();
+        let mut state = AppState::default();
+
+        assert_eq!(
+            command.execute(&["list"], &mut state),
+            Ok("No command aliases".to_string())
+        );
+        assert_eq!(
+            command.execute(
+                &["failwin", "goto", "123456;", "zoom", "in", "4"],
+                &mut state
+            ),
+            Ok("Alias 'failwin' runs: goto 123456; zoom in 4".to_string())
+        );
+        // Arguments with spaces stay quoted, and a single argument is the whole line
+        command
+            .execute(&["fail", "marker", "add", "fail point"], &mut state)
+            .unwrap();
+        command
+            .execute(&["w", "goto 5; zoom in"], &mut state)
+            .unwrap();
+
+        assert_eq!(
+            command.execute(&["list"], &mut state),
+            Ok(
+                "fail = marker add \"fail point\", failwin = goto 123456; zoom in 4, \
+                 w = goto 5; zoom in"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_alias_cannot_shadow_commands() {
+        let command = create_alias();
+        let mut state = AppState::default();
+
+        for name in ["goto", "zf"] {
+            assert_eq!(
+                command.execute(&[name, "zoomfull"], &mut state),
+                Err(format!("'{}' is already a command", name))
+            );
+        }
+        assert_eq!(
+            command.execute(&["list", "goto"], &mut state),
+            Err("Can't use 'list' as an alias name".to_string())
+        );
+        assert!(state.command_aliases.is_empty());
+    }
+
+    #[test]
+    fn test_alias_recursion_is_refused() {
+        let command = create_alias();
+        let mut state = AppState::default();
+
+        assert_eq!(
+            command.execute(&["loop", "goto", "5;", "loop"], &mut state),
+            Err("Alias 'loop' would call itself".to_string())
+        );
+
+        // Indirectly, through another alias
+        command.execute(&["a", "goto 5; b"], &mut state).unwrap();
+        command.execute(&["b", "c"], &mut state).unwrap();
+        assert_eq!(
+            command.execute(&["c", "zf; a"], &mut state),
+            Err("Alias 'c' would call itself".to_string())
+        );
+        assert!(!state.command_aliases.contains_key("c"));
+    }
+
+    #[test]
+    fn test_alias_is_saved() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("aliases.toml");
+        let command = create_alias();
+        let mut state = AppState::default();
+        state.command_aliases_path = Some(path.clone());
+
+        command.execute(&["g", "goto"], &mut state).unwrap();
+        assert_eq!(
+            command_aliases::load_command_aliases(&path),
+            state.command_aliases
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "center",
+        "Center the view on the primary marker, or move the marker to the center of the view",
+        |args, state: &mut AppState| match args {
+            [] | ["view"] => {
+                let marker = state
+                    .primary_marker
+                    .ok_or_else(|| "Primary marker not set".to_string())?;
+                state.record_jump();
+                state.center_on(marker);
+                Ok(format!("Centered on time {}", marker))
+            }
+            ["marker"] => {
+                let center = state.view_center();
+                state.move_primary_marker(center);
+                Ok(format!("Moved primary marker to time {}", center))
+            }
+            _ => Err("Usage: center [view|marker]".to_string()),
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.set_window(400, 100);
+        state
+    }
+
+    #[test]
+    fn test_center_on_marker() {
+        let command = create();
+        let mut state = get_state();
+
+        state.primary_marker = Some(700);
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Ok("Centered on time 700".to_string())
+        );
+        assert_eq!((state.time_start, state.time_range), (650, 100));
+    }
+
+    #[test]
+    fn test_center_on_marker_near_edges_is_clamped() {
+        let command = create();
+        let mut state = get_state();
+
+        state.primary_marker = Some(10);
+        command.execute(&[], &mut state).unwrap();
+        assert_eq!(state.time_start, 0);
+
+        state.primary_marker = Some(990);
+        command.execute(&["view"], &mut state).unwrap();
+        assert_eq!(state.time_start, 900);
+        assert_eq!(state.time_range, 100);
+    }
+
+    #[test]
+    fn test_center_without_marker_is_err() {
+        let command = create();
+        let mut state = get_state();
+
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Err("Primary marker not set".to_string())
+        );
+        assert_eq!(state.time_start, 400);
+    }
+
+    #[test]
+    fn test_marker_to_center() {
+        let command = create();
+        let mut state = get_state();
+
+        assert_eq!(
+            command.execute(&["marker"], &mut state),
+            Ok("Moved primary marker to time 450".to_string())
+        );
+        assert_eq!(state.primary_marker, Some(450));
+        assert_eq!(
+            command.execute(&["middle"], &mut state),
+            Err("Usage: center [view|marker]".to_string())
+        );
+    }
+}
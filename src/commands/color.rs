@@ -0,0 +1,128 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use glob::Pattern;
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "color",
+        "Draw signals matching a glob pattern in a color",
+        |args, state: &mut AppState| {
+            if args.len() < 2 {
+                return Err("Usage: color <pattern> <color>, or color <pattern> clear".to_string());
+            }
+
+            let pattern = Pattern::new(args[0])
+                .map_err(|e| format!("Invalid pattern '{}': {}", args[0], e))?;
+
+            if args[1] == "clear" {
+                let count_before = state.signal_colors.len();
+                state
+                    .signal_colors
+                    .retain(|signal, _| !pattern.matches(signal));
+                let cleared = count_before - state.signal_colors.len();
+
+                if cleared == 0 {
+                    return Err(format!("No colored signals match '{}'", args[0]));
+                }
+                return Ok(format!("Cleared the color of {} signal(s)", cleared));
+            }
+
+            let color = super::parse_color(args[1])?;
+            let matching: Vec<String> = state
+                .waveform_data
+                .signals
+                .iter()
+                .filter(|signal| pattern.matches(signal))
+                .cloned()
+                .collect();
+
+            if matching.is_empty() {
+                return Err(format!("No signals match '{}'", args[0]));
+            }
+
+            let count = matching.len();
+            for signal in matching {
+                state.signal_colors.insert(signal, color);
+            }
+            Ok(format!(
+                "Set the color of {} signal(s) to '{}'",
+                count, args[1]
+            ))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Color;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec![
+            "top.clk_a".to_string(),
+            "top.a.data".to_string(),
+            "top.clk_b".to_string(),
+        ];
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state
+    }
+
+    #[test]
+    fn test_color_glob() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["top.clk_*", "green"], &mut state);
+        assert_eq!(
+            result,
+            Ok("Set the color of 2 signal(s) to 'green'".to_string())
+        );
+        assert_eq!(state.signal_colors.get("top.clk_a"), Some(&Color::Green));
+        assert_eq!(state.signal_colors.get("top.clk_b"), Some(&Color::Green));
+        assert_eq!(state.signal_colors.get("top.a.data"), None);
+
+        // A later color replaces the earlier one
+        let result = command.execute(&["top.clk_b", "Blue"], &mut state);
+        assert!(result.is_ok());
+        assert_eq!(state.signal_colors.get("top.clk_b"), Some(&Color::Blue));
+    }
+
+    #[test]
+    fn test_color_clear() {
+        let command = create();
+        let mut state = get_state();
+        command.execute(&["*", "green"], &mut state).unwrap();
+
+        let result = command.execute(&["top.clk_a", "clear"], &mut state);
+        assert_eq!(result, Ok("Cleared the color of 1 signal(s)".to_string()));
+        assert_eq!(state.signal_colors.len(), 2);
+
+        let result = command.execute(&["top.clk_a", "clear"], &mut state);
+        assert_eq!(
+            result,
+            Err("No colored signals match 'top.clk_a'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_color_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        assert!(command.execute(&["top.clk_a"], &mut state).is_err());
+        assert_eq!(
+            command.execute(&["top.clk_a", "not_a_color"], &mut state),
+            Err("Unknown color: not_a_color. Only ANSI colors are supported.".to_string())
+        );
+        assert_eq!(
+            command.execute(&["nothing*", "red"], &mut state),
+            Err("No signals match 'nothing*'".to_string())
+        );
+        assert!(state.signal_colors.is_empty());
+    }
+}
@@ -0,0 +1,257 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    parsers::types::{Value, WaveValue},
+    state::AppState,
+    types::Comparison,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "compare",
+        "Highlight where two signals differ",
+        |args, state: &mut AppState| match args {
+            ["clear"] => {
+                state.comparison = None;
+                Ok("Comparison cleared".to_string())
+            }
+            [signal_a, signal_b] => {
+                let values_a = state
+                    .waveform_data
+                    .values
+                    .get(*signal_a)
+                    .ok_or_else(|| format!("Signal not found: {}", signal_a))?;
+                let values_b = state
+                    .waveform_data
+                    .values
+                    .get(*signal_b)
+                    .ok_or_else(|| format!("Signal not found: {}", signal_b))?;
+
+                let mismatches =
+                    mismatch_intervals(values_a, values_b, state.waveform_data.max_time);
+                let message = match mismatches.first() {
+                    Some((first, _)) => format!(
+                        "{} mismatch interval(s), first at {} (:goto {})",
+                        mismatches.len(),
+                        first,
+                        first
+                    ),
+                    None => format!("{} and {} match", signal_a, signal_b),
+                };
+
+                state.comparison = Some(Comparison {
+                    signal_a: signal_a.to_string(),
+                    signal_b: signal_b.to_string(),
+                    mismatches,
+                });
+                Ok(message)
+            }
+            _ => Err("Usage: compare <signalA> <signalB> | compare clear".to_string()),
+        },
+    )
+    .build()
+}
+
+/// Computes the half-open `[start, end)` intervals in which two signals have different values.
+/// Each signal holds its last value until it changes, so changes at different times to the same
+/// value don't count as a difference. The final value of each signal lasts until `max_time`.
+pub(super) fn mismatch_intervals(
+    values_a: &[(u64, WaveValue)],
+    values_b: &[(u64, WaveValue)],
+    max_time: u64,
+) -> Vec<(u64, u64)> {
+    let mut change_times: Vec<u64> = values_a
+        .iter()
+        .chain(values_b)
+        .map(|(time, _)| *time)
+        .collect();
+    change_times.sort_unstable();
+    change_times.dedup();
+
+    let mut intervals: Vec<(u64, u64)> = Vec::new();
+    let (mut index_a, mut index_b) = (0, 0);
+    let (mut current_a, mut current_b) = (None, None);
+
+    for (i, &time) in change_times.iter().enumerate() {
+        while index_a < values_a.len() && values_a[index_a].0 <= time {
+            current_a = Some(&values_a[index_a].1);
+            index_a += 1;
+        }
+        while index_b < values_b.len() && values_b[index_b].0 <= time {
+            current_b = Some(&values_b[index_b].1);
+            index_b += 1;
+        }
+
+        if values_equal(current_a, current_b) {
+            continue;
+        }
+
+        // A difference right at the end of the trace still lasts for one time unit
+        let end = change_times
+            .get(i + 1)
+            .copied()
+            .unwrap_or(max_time)
+            .max(time + 1);
+        match intervals.last_mut() {
+            Some((_, last_end)) if *last_end == time => *last_end = end,
+            _ => intervals.push((time, end)),
+        }
+    }
+
+    intervals
+}
+
+// Compares two values bit by bit so that binary signals and buses of different widths can be
+// compared. The narrower value is zero-extended to the width of the wider one.
+fn values_equal(a: Option<&WaveValue>, b: Option<&WaveValue>) -> bool {
+    let (a, b) = match (a, b) {
+        (Some(a), Some(b)) => (a, b),
+        (a, b) => return a.is_none() && b.is_none(),
+    };
+
+    match (a.values(16), b.values(16)) {
+        (Some(mut bits_a), Some(mut bits_b)) => {
+            let width = bits_a.len().max(bits_b.len());
+            for bits in [&mut bits_a, &mut bits_b] {
+                let padding = width - bits.len();
+                bits.splice(0..0, std::iter::repeat_n(Value::V0, padding));
+            }
+            bits_a == bits_b
+        }
+        // Values that can't be expanded into bits (e.g. reals) are compared as they are
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bin(value: Value) -> WaveValue {
+        WaveValue::Binary(value)
+    }
+
+    fn bus(value: &str) -> WaveValue {
+        WaveValue::Bus(value.into())
+    }
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec!["dut.out".to_string(), "model.out".to_string()];
+        state.waveform_data.values.insert(
+            "dut.out".to_string(),
+            vec![(0, bus("00")), (10, bus("05")), (30, bus("07"))],
+        );
+        state.waveform_data.values.insert(
+            "model.out".to_string(),
+            vec![(0, bus("00")), (10, bus("05")), (20, bus("07"))],
+        );
+        state.waveform_data.max_time = 50;
+        state
+    }
+
+    #[test]
+    fn test_mismatch_intervals_equal_values_at_different_times() {
+        // b re-asserts the same value at different times than a changes
+        let a = vec![(0, bin(Value::V0)), (10, bin(Value::V1))];
+        let b = vec![
+            (0, bin(Value::V0)),
+            (5, bin(Value::V0)),
+            (10, bin(Value::V1)),
+            (15, bin(Value::V1)),
+        ];
+        assert!(mismatch_intervals(&a, &b, 20).is_empty());
+    }
+
+    #[test]
+    fn test_mismatch_intervals_overlapping_changes() {
+        let a = vec![
+            (0, bin(Value::V0)),
+            (10, bin(Value::V1)),
+            (30, bin(Value::V0)),
+        ];
+        let b = vec![
+            (0, bin(Value::V0)),
+            (15, bin(Value::V1)),
+            (25, bin(Value::V0)),
+        ];
+        assert_eq!(mismatch_intervals(&a, &b, 40), vec![(10, 15), (25, 30)]);
+    }
+
+    #[test]
+    fn test_mismatch_intervals_merges_adjacent_differences() {
+        let a = vec![(0, bus("01")), (10, bus("02")), (20, bus("03"))];
+        let b = vec![(0, bus("00"))];
+        assert_eq!(mismatch_intervals(&a, &b, 30), vec![(0, 30)]);
+    }
+
+    #[test]
+    fn test_mismatch_intervals_missing_values_and_end_of_trace() {
+        // b has no value until time 5, and differs only at the very last time step
+        let a = vec![(0, bin(Value::V1)), (20, bin(Value::V0))];
+        let b = vec![(5, bin(Value::V1)), (20, bin(Value::V1))];
+        assert_eq!(mismatch_intervals(&a, &b, 20), vec![(0, 5), (20, 21)]);
+    }
+
+    #[test]
+    fn test_values_equal_binary_against_bus() {
+        assert!(values_equal(Some(&bin(Value::V1)), Some(&bus("01"))));
+        assert!(values_equal(Some(&bin(Value::V0)), Some(&bus("0"))));
+        assert!(!values_equal(Some(&bin(Value::V1)), Some(&bus("11"))));
+        assert!(!values_equal(Some(&bin(Value::VX)), Some(&bus("0"))));
+        assert!(!values_equal(None, Some(&bus("0"))));
+        assert!(values_equal(None, None));
+    }
+
+    #[test]
+    fn test_compare_usage_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["dut.out"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Usage: compare <signalA> <signalB> | compare clear".to_string()
+        );
+    }
+
+    #[test]
+    fn test_compare_unknown_signal_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["dut.out", "nope"], &mut state);
+        assert_eq!(result.unwrap_err(), "Signal not found: nope".to_string());
+        assert!(state.comparison.is_none());
+    }
+
+    #[test]
+    fn test_compare_summarizes_and_clears() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["dut.out", "model.out"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "1 mismatch interval(s), first at 20 (:goto 20)".to_string()
+        );
+        assert_eq!(
+            state.comparison,
+            Some(Comparison {
+                signal_a: "dut.out".to_string(),
+                signal_b: "model.out".to_string(),
+                mismatches: vec![(20, 30)],
+            })
+        );
+
+        let result = command.execute(&["clear"], &mut state);
+        assert_eq!(result.unwrap(), "Comparison cleared".to_string());
+        assert!(state.comparison.is_none());
+    }
+
+    #[test]
+    fn test_compare_identical_signals() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["dut.out", "dut.out"], &mut state);
+        assert_eq!(result.unwrap(), "dut.out and dut.out match".to_string());
+        assert_eq!(state.comparison.unwrap().mismatches, vec![]);
+    }
+}
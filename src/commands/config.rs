@@ -0,0 +1,73 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "config",
+        "Show config values or save the current config",
+        |args, state: &mut AppState| match args {
+            ["save"] => {
+                let path = state.config.save()?;
+                Ok(format!("Saved config to {}", path.display()))
+            }
+            ["show", key] => {
+                let (key, value) = state.config.get(key)?;
+                Ok(format!("{} = {}", key, value))
+            }
+            _ => Err("Usage: config save | config show <setting>".to_string()),
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+
+    #[test]
+    fn test_config_usage_is_err() {
+        let command = create();
+        let mut state = AppState::default();
+        let result = command.execute(&["show"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Usage: config save | config show <setting>".to_string()
+        );
+    }
+
+    #[test]
+    fn test_config_show() {
+        let command = create();
+        let mut state = AppState::default();
+        let result = command.execute(&["show", "zoom_out"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "keybindings.zoom_out = { Char = \"-\" }".to_string()
+        );
+
+        let result = command.execute(&["show", "ui.nope"], &mut state);
+        assert_eq!(result.unwrap_err(), "Unknown setting: ui.nope".to_string());
+    }
+
+    #[test]
+    fn test_config_save_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("digisurf").join("config.toml");
+        let mut state = AppState::default();
+        state.config.config_path = Some(path.clone());
+
+        state.config.set("ui.signal_list_width", "35").unwrap();
+        let result = create().execute(&["save"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            format!("Saved config to {}", path.display())
+        );
+
+        let loaded = config::load_config(Some(path.to_str().unwrap().to_string())).unwrap();
+        assert_eq!(loaded.ui.signal_list_width, 35);
+    }
+}
@@ -0,0 +1,59 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "cursor",
+        "Toggle a cursor moved through time with the keyboard",
+        |_args, state: &mut AppState| {
+            if state.cursor.take().is_some() {
+                return Ok("Cursor off".to_string());
+            }
+
+            // Start at the primary marker if it's in view, otherwise in the middle of the view
+            let time = state
+                .primary_marker
+                .filter(|time| {
+                    (state.time_start..=state.time_start + state.time_range).contains(time)
+                })
+                .unwrap_or_else(|| state.view_center());
+            state.cursor = Some(time);
+            Ok(format!("Cursor at {}", time))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_toggle() {
+        let command = create();
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.set_window(200, 100);
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Ok("Cursor at 250".to_string()));
+        assert_eq!(state.cursor, Some(250));
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Ok("Cursor off".to_string()));
+        assert_eq!(state.cursor, None);
+
+        // A visible primary marker is where the cursor starts
+        state.primary_marker = Some(220);
+        command.execute(&[], &mut state).unwrap();
+        assert_eq!(state.cursor, Some(220));
+
+        state.cursor = None;
+        state.primary_marker = Some(900);
+        command.execute(&[], &mut state).unwrap();
+        assert_eq!(state.cursor, Some(250));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    decoders::{
+        spi::SpiDecoder,
+        uart::{UartDecoder, FRAMING_ERROR},
+        Decoder,
+    },
+    state::AppState,
+};
+use std::rc::Rc;
+
+const USAGE: &str = "Usage: decode uart <rx> <baud> | decode spi <clk> <mosi> <cs>";
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "decode",
+        "Show the bytes of a UART (uart <rx> <baud>) or SPI mode 0 (spi <clk> <mosi> <cs>) bus as a row",
+        |args, state: &mut AppState| {
+            // The row's name, the decoder's inputs, and the data signal the row goes below
+            let (row, inputs, data) = match args {
+                ["uart", rx, baud] => (format!("uart({},{})", rx, baud), vec![*rx], *rx),
+                ["spi", clk, mosi, cs] => (
+                    format!("spi({},{},{})", clk, mosi, cs),
+                    vec![*clk, *mosi, *cs],
+                    *mosi,
+                ),
+                _ => return Err(USAGE.to_string()),
+            };
+            for input in &inputs {
+                if !state.waveform_data.values.contains_key(*input) {
+                    return Err(format!("Signal not found: {}", input));
+                }
+            }
+
+            // Decoding looks at every change, so a row shown again reuses its annotations
+            if !state.decoded.contains_key(&row) {
+                let decoder: Box<dyn Decoder> = match args {
+                    ["uart", _, baud] => {
+                        let baud = baud
+                            .parse::<u64>()
+                            .map_err(|_| format!("Invalid baud rate: {}", baud))?;
+                        let timescale_fs = state.waveform_data.timescale_fs.ok_or_else(|| {
+                            "The waveform has no timescale, so the baud rate can't be used"
+                                .to_string()
+                        })?;
+                        Box::new(UartDecoder::with_baud(baud, timescale_fs)?)
+                    }
+                    _ => Box::new(SpiDecoder),
+                };
+                let values: Vec<&[_]> = inputs
+                    .iter()
+                    .map(|input| state.waveform_data.values[*input].as_slice())
+                    .collect();
+                let annotations = decoder.decode(&values);
+                state.decoded.insert(row.clone(), annotations);
+            }
+
+            // The row goes below the data signal if it is displayed, else at the end
+            if !state.displayed_signals.contains(&row) {
+                let index = state
+                    .displayed_signals
+                    .iter()
+                    .position(|signal| signal == data)
+                    .map_or(state.displayed_signals.len(), |index| index + 1);
+                state.displayed_signals.insert(index, row.clone());
+            }
+            state.select_signal_named(Some(&row));
+
+            let annotations = &state.decoded[&row];
+            let errors = annotations
+                .iter()
+                .filter(|annotation| annotation.label == FRAMING_ERROR)
+                .count();
+            let mut message = format!("Decoded {} byte(s) into {}", annotations.len(), row);
+            if errors > 0 {
+                message.push_str(&format!(", {} with framing errors", errors));
+            }
+            Ok(message)
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+
+    fn bit(high: bool) -> WaveValue {
+        WaveValue::Binary(if high { Value::V1 } else { Value::V0 })
+    }
+
+    // Adds the changes of a UART frame of `byte` with 10 tick bits from `start` to `rx`, idling
+    // high after it
+    fn frame(rx: &mut Vec<(u64, WaveValue)>, start: u64, byte: u8, stop: bool) {
+        let mut levels = vec![false];
+        levels.extend((0..8).map(|i| byte >> i & 1 == 1));
+        levels.push(stop);
+        for (i, high) in levels.into_iter().enumerate() {
+            rx.push((start + i as u64 * 10, bit(high)));
+        }
+        rx.push((start + 100, bit(true)));
+    }
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        // 1 ns ticks, so 100 Mbaud has 10 tick bits
+        state.waveform_data.timescale_fs = Some(1_000_000);
+        state.waveform_data.max_time = 500;
+
+        // 0x41, then 0x00 with a low stop bit
+        let mut rx = vec![(0, bit(true))];
+        frame(&mut rx, 100, 0x41, true);
+        frame(&mut rx, 300, 0x00, false);
+        state.waveform_data.values.insert("top.rx".to_string(), rx);
+
+        // One 0xFF byte over SPI, clocked from 10 to 85
+        let mut clk = vec![(0, bit(false))];
+        for time in (10..90).step_by(10) {
+            clk.push((time, bit(true)));
+            clk.push((time + 5, bit(false)));
+        }
+        let spi = [
+            ("top.clk", clk),
+            ("top.mosi", vec![(0, bit(true))]),
+            (
+                "top.cs",
+                vec![(0, bit(true)), (5, bit(false)), (95, bit(true))],
+            ),
+            ("top.other", vec![(0, bit(false))]),
+        ];
+        for (name, values) in spi {
+            state.waveform_data.values.insert(name.to_string(), values);
+        }
+        state.displayed_signals = vec!["top.rx".to_string(), "top.other".to_string()];
+        state
+    }
+
+    #[test]
+    fn test_decode_uart() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["uart", "top.rx", "100000000"], &mut state);
+        assert_eq!(
+            result,
+            Ok("Decoded 2 byte(s) into uart(top.rx,100000000), 1 with framing errors".to_string())
+        );
+        // The row goes below the receive line and is selected
+        assert_eq!(
+            state.displayed_signals,
+            vec!["top.rx", "uart(top.rx,100000000)", "top.other"]
+        );
+        assert_eq!(state.selected_signal, 1);
+        assert_eq!(
+            state
+                .annotation_at("uart(top.rx,100000000)", 150)
+                .map(|annotation| annotation.label.as_str()),
+            Some("41")
+        );
+        assert!(state.is_derived_signal("uart(top.rx,100000000)"));
+    }
+
+    #[test]
+    fn test_decode_spi_goes_at_the_end_without_the_data_signal() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["spi", "top.clk", "top.mosi", "top.cs"], &mut state);
+        assert_eq!(
+            result,
+            Ok("Decoded 1 byte(s) into spi(top.clk,top.mosi,top.cs)".to_string())
+        );
+        assert_eq!(
+            state.displayed_signals,
+            vec!["top.rx", "top.other", "spi(top.clk,top.mosi,top.cs)"]
+        );
+        assert_eq!(state.decoded["spi(top.clk,top.mosi,top.cs)"][0].label, "FF");
+    }
+
+    #[test]
+    fn test_decode_again_reuses_annotations() {
+        let command = create();
+        let mut state = get_state();
+        command
+            .execute(&["uart", "top.rx", "100000000"], &mut state)
+            .unwrap();
+
+        // Hiding the row and decoding again shows it without decoding the signal again
+        state.displayed_signals.remove(1);
+        state
+            .decoded
+            .get_mut("uart(top.rx,100000000)")
+            .unwrap()
+            .truncate(1);
+        let result = command.execute(&["uart", "top.rx", "100000000"], &mut state);
+        assert_eq!(
+            result,
+            Ok("Decoded 1 byte(s) into uart(top.rx,100000000)".to_string())
+        );
+        assert_eq!(state.displayed_signals.len(), 3);
+
+        // Decoding a displayed row doesn't add it twice
+        command
+            .execute(&["uart", "top.rx", "100000000"], &mut state)
+            .unwrap();
+        assert_eq!(state.displayed_signals.len(), 3);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        assert_eq!(
+            command.execute(&["i2c", "top.rx"], &mut state),
+            Err(USAGE.to_string())
+        );
+        assert_eq!(
+            command.execute(&["spi", "top.clk", "top.miso", "top.cs"], &mut state),
+            Err("Signal not found: top.miso".to_string())
+        );
+        assert_eq!(
+            command.execute(&["uart", "top.rx", "fast"], &mut state),
+            Err("Invalid baud rate: fast".to_string())
+        );
+        state.waveform_data.timescale_fs = None;
+        assert_eq!(
+            command.execute(&["uart", "top.rx", "9600"], &mut state),
+            Err("The waveform has no timescale, so the baud rate can't be used".to_string())
+        );
+        assert!(state.decoded.is_empty());
+        assert_eq!(state.displayed_signals.len(), 2);
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "delta",
+        "Show the time between two markers",
+        |args, state: &mut AppState| {
+            let (name_a, name_b) = match args {
+                [a] => (*a, "m1"),
+                [a, b] => (*a, *b),
+                _ => return Err("Usage: delta <markerA> [markerB]".to_string()),
+            };
+
+            let time_a = state.marker_time(name_a)?;
+            let time_b = state.marker_time(name_b)?;
+            Ok(format!(
+                "Δ({}, {}) = {}",
+                name_a,
+                name_b,
+                time_a.abs_diff(time_b)
+            ))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Marker;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.saved_markers = vec![
+            Marker::new(100, "reset_release".to_string()),
+            Marker::new(650, "irq".to_string()),
+        ];
+        state
+    }
+
+    #[test]
+    fn test_delta_defaults_to_primary_marker() {
+        let command = create();
+        let mut state = get_state();
+        state.primary_marker = Some(400);
+
+        let result = command.execute(&["reset_release"], &mut state);
+        assert_eq!(result.unwrap(), "Δ(reset_release, m1) = 300".to_string());
+    }
+
+    #[test]
+    fn test_delta_between_named_markers() {
+        let command = create();
+        let mut state = get_state();
+        state.secondary_marker = Some(700);
+
+        let result = command.execute(&["reset_release", "irq"], &mut state);
+        assert_eq!(result.unwrap(), "Δ(reset_release, irq) = 550".to_string());
+
+        let result = command.execute(&["m2", "irq"], &mut state);
+        assert_eq!(result.unwrap(), "Δ(m2, irq) = 50".to_string());
+    }
+
+    #[test]
+    fn test_delta_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Usage: delta <markerA> [markerB]".to_string()
+        );
+
+        let result = command.execute(&["irq"], &mut state);
+        assert_eq!(result.unwrap_err(), "Primary marker not set".to_string());
+
+        let result = command.execute(&["irq", "m2"], &mut state);
+        assert_eq!(result.unwrap_err(), "Secondary marker not set".to_string());
+
+        state.primary_marker = Some(400);
+        let result = command.execute(&["nope"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "No marker found with name 'nope'".to_string()
+        );
+    }
+}
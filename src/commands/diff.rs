@@ -0,0 +1,180 @@
+use super::compare::mismatch_intervals;
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    parsers::{types::WaveformData, vcd::VcdParser},
+    state::AppState,
+    types::Diff,
+};
+use std::{collections::HashMap, path::PathBuf, rc::Rc};
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "diff",
+        "Load a second waveform file and show where its signals differ from the loaded ones",
+        |args, state: &mut AppState| match args {
+            ["off"] => {
+                if state.diff.take().is_none() {
+                    return Err("No file loaded to diff against".to_string());
+                }
+                Ok("Diff closed".to_string())
+            }
+            [path] => {
+                let path = PathBuf::from(path);
+                let data = VcdParser::new()
+                    .parse_file(&path)
+                    .map_err(|e| format!("Error loading {}: {}", path.display(), e))?;
+                let diff = diff_against(&state.waveform_data, path, data);
+                let message = describe(&state.waveform_data, &diff);
+                state.diff = Some(diff);
+                Ok(message)
+            }
+            _ => Err("Usage: diff <path> | diff off".to_string()),
+        },
+    )
+    .build()
+}
+
+// Matches the signals of `data` to the loaded ones by name and finds where each matched pair
+// differs. Both files are compared up to the end of the longer one.
+fn diff_against(loaded: &WaveformData, path: PathBuf, data: WaveformData) -> Diff {
+    let max_time = loaded.max_time.max(data.max_time);
+    let mismatches: HashMap<String, Vec<(u64, u64)>> = loaded
+        .values
+        .iter()
+        .filter_map(|(signal, values_a)| {
+            let values_b = data.values.get(signal)?;
+            let mismatches = mismatch_intervals(values_a, values_b, max_time);
+            (!mismatches.is_empty()).then(|| (signal.clone(), mismatches))
+        })
+        .collect();
+    Diff {
+        path,
+        data,
+        mismatches,
+    }
+}
+
+// Summary of a diff, counting the differing signals and those only found in one of the files
+fn describe(loaded: &WaveformData, diff: &Diff) -> String {
+    let matched = loaded
+        .signals
+        .iter()
+        .filter(|signal| diff.data.values.contains_key(*signal))
+        .count();
+    let only_b = diff
+        .data
+        .signals
+        .iter()
+        .filter(|signal| !loaded.values.contains_key(*signal))
+        .count();
+    format!(
+        "{} of {} matching signal(s) differ, {} only in the loaded file, {} only in {}",
+        diff.mismatches.len(),
+        matched,
+        loaded.signals.len() - matched,
+        only_b,
+        diff.path.display()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    fn bin(value: Value) -> WaveValue {
+        WaveValue::Binary(value)
+    }
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec!["top.clk".to_string(), "top.done".to_string()];
+        state.waveform_data.values.insert(
+            "top.clk".to_string(),
+            vec![(0, bin(Value::V0)), (10, bin(Value::V1))],
+        );
+        state
+            .waveform_data
+            .values
+            .insert("top.done".to_string(), vec![(0, bin(Value::V0))]);
+        state.waveform_data.max_time = 20;
+        state
+    }
+
+    // The second file re-asserts clk's values at different times and has an extra signal
+    fn second_file() -> NamedTempFile {
+        let mut file = NamedTempFile::with_suffix(".vcd").unwrap();
+        write!(
+            file,
+            "$timescale 1ns $end\n\
+             $scope module top $end\n\
+             $var wire 1 ! clk $end\n\
+             $var wire 1 \" extra $end\n\
+             $upscope $end\n\
+             $enddefinitions $end\n\
+             #0\n0!\n0\"\n\
+             #5\n0!\n\
+             #10\n1!\n\
+             #15\n1!\n\
+             #20\n"
+        )
+        .unwrap();
+        file
+    }
+
+    #[test]
+    fn test_diff_equal_values_changed_at_different_times() {
+        let file = second_file();
+        let command = create();
+        let mut state = get_state();
+
+        let path = file.path().to_string_lossy().to_string();
+        let result = command.execute(&[&path], &mut state).unwrap();
+        assert_eq!(
+            result,
+            format!(
+                "0 of 1 matching signal(s) differ, 1 only in the loaded file, 1 only in {}",
+                path
+            )
+        );
+        let diff = state.diff.as_ref().unwrap();
+        assert!(diff.mismatches.is_empty());
+
+        let result = command.execute(&["off"], &mut state);
+        assert_eq!(result, Ok("Diff closed".to_string()));
+        assert!(state.diff.is_none());
+    }
+
+    #[test]
+    fn test_diff_finds_mismatches() {
+        let loaded = get_state().waveform_data;
+        let mut data = loaded.clone();
+        data.values.insert(
+            "top.clk".to_string(),
+            vec![(0, bin(Value::V0)), (14, bin(Value::V1))],
+        );
+        data.max_time = 30;
+
+        let diff = diff_against(&loaded, PathBuf::from("fail.vcd"), data);
+        assert_eq!(diff.mismatches.len(), 1);
+        assert_eq!(diff.mismatches["top.clk"], vec![(10, 14)]);
+    }
+
+    #[test]
+    fn test_diff_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["off"], &mut state);
+        assert_eq!(result, Err("No file loaded to diff against".to_string()));
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Err("Usage: diff <path> | diff off".to_string()));
+        let result = command.execute(&["/nonexistent/fail.vcd"], &mut state);
+        assert!(result
+            .unwrap_err()
+            .starts_with("Error loading /nonexistent/fail.vcd"));
+        assert!(state.diff.is_none());
+    }
+}
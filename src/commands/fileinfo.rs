@@ -0,0 +1,43 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "fileinfo",
+        "Show the loaded file's path, size, header and how long it took to parse",
+        |_args, state: &mut AppState| {
+            if state.file_info.is_none() {
+                return Err("No file loaded".to_string());
+            }
+            state.show_file_info = true;
+            state.file_info_scroll = 0;
+            Ok("fileinfo".to_string())
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FileInfo;
+
+    #[test]
+    fn test_fileinfo() {
+        let command = create();
+        let mut state = AppState::default();
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Err("No file loaded".to_string()));
+        assert!(!state.show_file_info);
+
+        state.file_info = Some(FileInfo::default());
+        state.file_info_scroll = 3;
+        command.execute(&[], &mut state).unwrap();
+        assert!(state.show_file_info);
+        assert_eq!(state.file_info_scroll, 0);
+    }
+}
@@ -1,5 +1,6 @@
 use crate::{
     command_mode::{builder::CommandBuilder, registry::Command},
+    fuzzy_finder::FuzzyFinderStateAccess,
     state::AppState,
     types::AppMode,
 };
@@ -10,6 +11,9 @@ pub fn create() -> Rc<Box<dyn Command<AppState>>> {
         "findsignal",
         "Open signal finder to select signals to display",
         |_args, state: &mut AppState| {
+            // Start from what is displayed now, which commands like hide may have changed
+            let displayed = state.displayed_signals.iter().cloned().collect();
+            state.fuzzy_finder_state_mut().selected_signals = displayed;
             state.mode = AppMode::FuzzyFinder;
             Ok(String::new()) // Finder window will pop up, so don't have a confirmation message
         },
@@ -0,0 +1,172 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    parsers::types::WaveValue,
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "fit",
+        "Zoom to the changes of the selected signal, or of the given signals together",
+        |args, state: &mut AppState| {
+            let signals: Vec<String> = if args.is_empty() {
+                vec![state
+                    .displayed_signals
+                    .get(state.selected_signal)
+                    .cloned()
+                    .ok_or_else(|| "No signal selected".to_string())?]
+            } else {
+                args.iter().map(|signal| signal.to_string()).collect()
+            };
+
+            let mut span: Option<(u64, u64)> = None;
+            for signal in &signals {
+                let values = state
+                    .waveform_data
+                    .values
+                    .get(signal)
+                    .ok_or_else(|| format!("Signal not found: {}", signal))?;
+                if let Some((first, last)) = activity(values) {
+                    span = Some(span.map_or((first, last), |(start, end)| {
+                        (start.min(first), end.max(last))
+                    }));
+                }
+            }
+
+            let max_time = state.waveform_data.max_time;
+            state.record_jump();
+            let Some((first, last)) = span else {
+                state.zoom_to(0, max_time);
+                return Ok(format!(
+                    "{} changes less than twice, zoomed to full view",
+                    signals.join(", ")
+                ));
+            };
+
+            let (start, range) = padded_window(first, last, max_time);
+            state.zoom_to(start, range);
+            Ok(format!("Zoomed to changes from {} to {}", first, last))
+        },
+    )
+    .build()
+}
+
+// Helper function to get the times of the first and last change from a signal's initial value, if
+// it changes more than once. Values dumped again without changing don't count.
+fn activity(values: &[(u64, WaveValue)]) -> Option<(u64, u64)> {
+    let mut changes = values
+        .windows(2)
+        .filter(|pair| pair[0].1 != pair[1].1)
+        .map(|pair| pair[1].0);
+    let first = changes.next()?;
+    let last = changes.next_back()?;
+    Some((first, last))
+}
+
+// Helper function to pad the time from `first` to `last` by 5% on each side, returning the start
+// and range of a window within the waveform
+pub fn padded_window(first: u64, last: u64, max_time: u64) -> (u64, u64) {
+    let padding = (last - first) / 20;
+    let start = first.saturating_sub(padding);
+    let end = last.saturating_add(padding).min(max_time);
+    (start, end.saturating_sub(start).max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::Value;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.waveform_data.values.insert(
+            "top.irq".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (300, WaveValue::Binary(Value::V1)),
+                (500, WaveValue::Binary(Value::V0)),
+            ],
+        );
+        state.waveform_data.values.insert(
+            "top.ack".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (700, WaveValue::Binary(Value::V1)),
+                (750, WaveValue::Binary(Value::V1)),
+                (800, WaveValue::Binary(Value::V0)),
+            ],
+        );
+        state.waveform_data.values.insert(
+            "top.rst".to_string(),
+            vec![(0, WaveValue::Binary(Value::V0))],
+        );
+        state.displayed_signals = vec!["top.irq".to_string(), "top.rst".to_string()];
+        state.set_window(0, 1000);
+        state
+    }
+
+    #[test]
+    fn test_padded_window() {
+        // 5% of the 200 ticks between the changes on each side
+        assert_eq!(padded_window(300, 500, 1000), (290, 220));
+        // Clamped to the waveform
+        assert_eq!(padded_window(0, 1000, 1000), (0, 1000));
+        assert_eq!(padded_window(10, 990, 1000), (0, 1000));
+        assert_eq!(padded_window(400, 400, 1000), (400, 1));
+    }
+
+    #[test]
+    fn test_fit_selected_signal() {
+        let command = create();
+        let mut state = get_state();
+        state.set_window(900, 50);
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Zoomed to changes from 300 to 500".to_string()
+        );
+        assert_eq!((state.time_start, state.time_range), (290, 220));
+    }
+
+    #[test]
+    fn test_fit_signal_with_a_single_change_zooms_to_full_view() {
+        let command = create();
+        let mut state = get_state();
+        state.waveform_data.values.get_mut("top.irq").unwrap().pop();
+        state.set_window(100, 50);
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "top.irq changes less than twice, zoomed to full view".to_string()
+        );
+        assert_eq!((state.time_start, state.time_range), (0, 1000));
+
+        // Never changing at all is no different
+        state.set_window(100, 50);
+        command.execute(&["top.rst"], &mut state).unwrap();
+        assert_eq!((state.time_start, state.time_range), (0, 1000));
+    }
+
+    #[test]
+    fn test_fit_union_of_signals() {
+        let command = create();
+        let mut state = get_state();
+
+        // Signals that don't change enough add nothing to the span
+        let result = command.execute(&["top.irq", "top.ack", "top.rst"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Zoomed to changes from 300 to 800".to_string()
+        );
+        assert_eq!((state.time_start, state.time_range), (275, 550));
+
+        assert_eq!(
+            command.execute(&["top.missing"], &mut state),
+            Err("Signal not found: top.missing".to_string())
+        );
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "follow",
+        "Toggle reading changes appended to the loaded file",
+        |_args, state: &mut AppState| {
+            if state.following {
+                state.following = false;
+                return Ok("Stopped following".to_string());
+            }
+
+            let Some(path) = &state.file_path else {
+                return Err("No file loaded".to_string());
+            };
+            let message = format!("Following {}", path.display());
+            state.following = true;
+            Ok(message)
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_follow_toggle() {
+        let command = create();
+        let mut state = AppState::default();
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Err("No file loaded".to_string()));
+        assert!(!state.following);
+
+        state.file_path = Some(PathBuf::from("sim.vcd"));
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Ok("Following sim.vcd".to_string()));
+        assert!(state.following);
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Ok("Stopped following".to_string()));
+        assert!(!state.following);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "quit!",
+        "Quit digisurf, even with unsaved session changes",
+        |_args, state: &mut AppState| {
+            state.exit = true;
+            Ok("Exiting digisurf...".to_string())
+        },
+    )
+    .alias("q!")
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_force_quit_always_exits() {
+        let command = create();
+
+        let mut state = AppState::default();
+        assert!(command.execute(&[], &mut state).is_ok());
+        assert!(state.exit);
+
+        let mut state = AppState::default();
+        state.displayed_signals.push("top.clk".to_string());
+        assert!(state.is_session_dirty());
+        assert!(command.execute(&[], &mut state).is_ok());
+        assert!(state.exit);
+    }
+}
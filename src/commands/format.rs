@@ -0,0 +1,126 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "format",
+        "Show the values of a displayed bus signal as unsigned or signed (two's complement) decimal",
+        |args, state: &mut AppState| {
+            let [signal, format] = args else {
+                return Err("Usage: format <signal> <dec|sdec>".to_string());
+            };
+            if !state.displayed_signals.iter().any(|s| s == signal) {
+                return Err(format!("Signal '{}' is not displayed", signal));
+            }
+
+            match *format {
+                "dec" => {
+                    state.signed_signals.remove(*signal);
+                    Ok(format!("Showing {} as unsigned decimal", signal))
+                }
+                "sdec" => {
+                    state.signed_signals.insert(signal.to_string());
+                    Ok(format!(
+                        "Showing {} as signed decimal, {} bits wide",
+                        signal,
+                        state.signal_width(signal)
+                    ))
+                }
+                _ => Err("The format must be dec or sdec".to_string()),
+            }
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commands, parsers::types::WaveValue, query};
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.displayed_signals = vec!["top.clk".to_string(), "top.data".to_string()];
+        state.waveform_data.widths.insert("top.data".to_string(), 8);
+        state
+    }
+
+    #[test]
+    fn test_format() {
+        let command = create();
+        let mut state = get_state();
+        let minus_one = WaveValue::Bus("ff".into());
+
+        assert_eq!(
+            command.execute(&["top.data", "sdec"], &mut state),
+            Ok("Showing top.data as signed decimal, 8 bits wide".to_string())
+        );
+        assert_eq!(state.format_value("top.data", &minus_one), "-1");
+
+        command.execute(&["top.data", "dec"], &mut state).unwrap();
+        assert_eq!(state.format_value("top.data", &minus_one), "255");
+    }
+
+    #[test]
+    fn test_signed_values_shown_everywhere() {
+        let mut state = get_state();
+        state.waveform_data.signals = state.displayed_signals.clone();
+        state.waveform_data.values.insert(
+            "top.data".to_string(),
+            vec![
+                (0, WaveValue::Bus("01".into())),
+                (10, WaveValue::Bus("ff".into())),
+            ],
+        );
+        state.time_range = 20;
+        create().execute(&["top.data", "sdec"], &mut state).unwrap();
+
+        assert_eq!(
+            commands::sample::sample_lines(&state, 10)[1],
+            "top.data : -1"
+        );
+        let query = query::parse_query("top.data@0-10").unwrap();
+        assert_eq!(
+            query::run_query(&query, &state).unwrap(),
+            ["top.data,0,1", "top.data,10,-1"]
+        );
+        commands::transitions::create()
+            .execute(&["top.data"], &mut state)
+            .unwrap();
+        let transition = &state.transitions_popup.as_ref().unwrap().transitions[1];
+        assert_eq!(
+            (
+                transition.old_value.as_deref(),
+                transition.new_value.as_str()
+            ),
+            (Some("1"), "-1")
+        );
+        assert_eq!(
+            state.get_transition_at_marker("top.data", 10).as_deref(),
+            Some("1->-1")
+        );
+    }
+
+    #[test]
+    fn test_format_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        assert_eq!(
+            command.execute(&["top.data"], &mut state),
+            Err("Usage: format <signal> <dec|sdec>".to_string())
+        );
+        assert_eq!(
+            command.execute(&["top.rst", "sdec"], &mut state),
+            Err("Signal 'top.rst' is not displayed".to_string())
+        );
+        assert_eq!(
+            command.execute(&["top.data", "hex"], &mut state),
+            Err("The format must be dec or sdec".to_string())
+        );
+        assert!(state.signed_signals.is_empty());
+    }
+}
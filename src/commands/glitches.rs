@@ -0,0 +1,118 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+    time_format::format_time,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "glitches",
+        "List the times at which a signal changed more than once, with the values it went through",
+        |args, state: &mut AppState| {
+            let [signal] = args else {
+                return Err("Usage: glitches <signal>".to_string());
+            };
+            if !state.waveform_data.values.contains_key(*signal) {
+                return Err(format!("Signal not found: {}", signal));
+            }
+
+            let glitches = state
+                .waveform_data
+                .glitches
+                .get(*signal)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            if glitches.is_empty() {
+                return Ok(format!("No glitches in {}", signal));
+            }
+            let list: Vec<String> = glitches
+                .iter()
+                .map(|glitch| {
+                    let via: Vec<String> = glitch
+                        .via
+                        .iter()
+                        .map(|value| format!("{:X}", value))
+                        .collect();
+                    format!(
+                        "{} (via {})",
+                        format_time(glitch.time, state.time_zero),
+                        via.join(", ")
+                    )
+                })
+                .collect();
+            Ok(format!(
+                "{}: {} glitch(es) at {}",
+                signal,
+                glitches.len(),
+                list.join(", ")
+            ))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Glitch, Value, WaveValue};
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        for signal in ["top.clk", "top.data"] {
+            state
+                .waveform_data
+                .values
+                .insert(signal.to_string(), vec![(0, WaveValue::Binary(Value::V0))]);
+        }
+        state.waveform_data.glitches.insert(
+            "top.data".to_string(),
+            vec![
+                Glitch {
+                    time: 10,
+                    via: vec![WaveValue::Binary(Value::VX)],
+                },
+                Glitch {
+                    time: 30,
+                    via: vec![WaveValue::Binary(Value::V1), WaveValue::Binary(Value::VZ)],
+                },
+            ],
+        );
+        state
+    }
+
+    #[test]
+    fn test_glitches() {
+        let command = create();
+        let mut state = get_state();
+        assert_eq!(
+            command.execute(&["top.data"], &mut state),
+            Ok("top.data: 2 glitch(es) at 10 (via X), 30 (via 1, Z)".to_string())
+        );
+        assert_eq!(
+            command.execute(&["top.clk"], &mut state),
+            Ok("No glitches in top.clk".to_string())
+        );
+
+        // Times are relative to the reference time
+        state.time_zero = Some(20);
+        assert_eq!(
+            command.execute(&["top.data"], &mut state),
+            Ok("top.data: 2 glitch(es) at -10 (via X), 10 (via 1, Z)".to_string())
+        );
+    }
+
+    #[test]
+    fn test_glitches_errors() {
+        let command = create();
+        let mut state = get_state();
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Err("Usage: glitches <signal>".to_string())
+        );
+        assert_eq!(
+            command.execute(&["top.rst"], &mut state),
+            Err("Signal not found: top.rst".to_string())
+        );
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
     command_mode::{builder::CommandBuilder, registry::Command},
     state::AppState,
+    time_format::{format_time, parse_relative_time},
 };
 use std::rc::Rc;
 
@@ -13,23 +14,22 @@ pub fn create() -> Rc<Box<dyn Command<AppState>>> {
                 return Err("Usage: goto <time>".to_string());
             }
 
-            if let Ok(time) = args[0].parse::<u64>() {
-                if time <= state.waveform_data.max_time {
-                    // Center the view around the time point
-                    let half_range = state.time_range / 2;
-                    state.time_start = if time > half_range {
-                        time - half_range
-                    } else {
-                        0
-                    };
-                    return Ok(format!("Moved to time {}", time));
-                }
-                return Err(format!(
-                    "Time out of range (0-{})",
-                    state.waveform_data.max_time
-                ));
-            }
-            Err("Invalid time format".to_string())
+            // Times are typed relative to the reference time set with :timezero
+            let time = parse_relative_time(
+                args[0],
+                state.time_zero,
+                state.waveform_data.timescale_fs,
+                state.waveform_data.max_time,
+            )?;
+
+            // Center the view around the time point
+            state.record_jump();
+            let half_range = state.time_range / 2;
+            state.zoom_to(time.saturating_sub(half_range), state.time_range);
+            Ok(format!(
+                "Moved to time {}",
+                format_time(time, state.time_zero)
+            ))
         },
     )
     .build()
@@ -62,14 +62,17 @@ mod tests {
         let mut state = get_state();
         let result = command.execute(&["not_a_number"], &mut state);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Invalid time format".to_string());
+        assert_eq!(
+            result.unwrap_err(),
+            "Invalid time: not_a_number".to_string()
+        );
     }
 
     #[test]
     fn test_goto_time_out_of_range() {
         let command = create();
         let mut state = get_state();
-        let result = command.execute(&[&"2000"], &mut state);
+        let result = command.execute(&["2000"], &mut state);
         assert!(result.is_err());
         assert_eq!(
             result.unwrap_err(),
@@ -95,4 +98,32 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(state.time_start, 0); // Should clamp to 0 when time < half_range
     }
+
+    #[test]
+    fn test_goto_negative_relative_time() {
+        let command = create();
+        let mut state = get_state();
+        state.time_zero = Some(600);
+
+        let result = command.execute(&["-100"], &mut state);
+        assert_eq!(result.unwrap(), "Moved to time -100".to_string());
+        assert_eq!(state.time_start, 450); // Centered on absolute time 500
+
+        let result = command.execute(&["-700"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Time out of range (-600-400)".to_string()
+        );
+    }
+
+    #[test]
+    fn test_goto_time_with_unit() {
+        let command = create();
+        let mut state = get_state();
+        state.waveform_data.timescale_fs = Some(1_000_000);
+
+        let result = command.execute(&["0.5us"], &mut state);
+        assert_eq!(result.unwrap(), "Moved to time 500".to_string());
+        assert_eq!(state.time_start, 450);
+    }
 }
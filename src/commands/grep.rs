@@ -0,0 +1,246 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    parsers::types::{Value, WaveValue},
+    state::AppState,
+    types::{GrepPopup, ValueMatch},
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "grep",
+        "List where displayed signals change to a value",
+        |args, state: &mut AppState| {
+            let [query] = args else {
+                return Err("Usage: grep <value>".to_string());
+            };
+            let target = parse_query(query).ok_or_else(|| {
+                format!(
+                    "Invalid value '{}': expected a number like 255, 0xff, 0o377 or 0b11111111",
+                    query
+                )
+            })?;
+
+            let mut matches = find_value(state, significant_bits(&target));
+            if matches.is_empty() {
+                return Err(format!("No displayed signal holds {}", query));
+            }
+
+            let count = matches.len();
+            let limit = state.config.ui.grep_match_limit;
+            let truncated = count > limit;
+            matches.truncate(limit);
+
+            state.grep_popup = Some(GrepPopup {
+                query: query.to_string(),
+                matches,
+                truncated,
+                ..Default::default()
+            });
+            if truncated {
+                Ok(format!(
+                    "{} match(es) of {}, showing the first {}",
+                    count, query, limit
+                ))
+            } else {
+                Ok(format!("{} match(es) of {}", count, query))
+            }
+        },
+    )
+    .build()
+}
+
+// Parses a searched value into its bits. Values are decimal, like the values drawn in the
+// waveform, unless they have a 0x, 0o or 0b prefix.
+pub(super) fn parse_query(query: &str) -> Option<Vec<Value>> {
+    let radix = match query.get(..2).map(|prefix| prefix.to_ascii_lowercase()) {
+        Some(prefix) if prefix == "0x" => 16,
+        Some(prefix) if prefix == "0o" => 8,
+        Some(prefix) if prefix == "0b" => 2,
+        _ => {
+            // Bus values are kept in hex
+            let number: u128 = query.parse().ok()?;
+            return WaveValue::Bus(format!("{:x}", number).into()).values(16);
+        }
+    };
+
+    let digits = &query[2..];
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return None;
+    }
+    WaveValue::Bus(query.into()).values(radix)
+}
+
+// The bits of a value without leading zeros, so values of different widths compare equal. Zero
+// keeps a single bit.
+fn significant_bits(bits: &[Value]) -> &[Value] {
+    let first = bits
+        .iter()
+        .position(|bit| *bit != Value::V0)
+        .unwrap_or(bits.len().saturating_sub(1));
+    &bits[first..]
+}
+
+// Times at which a displayed signal changes to `target`, in time order. A signal that keeps the
+// value over several changes, like a bus repeating it, only matches at the first of them.
+fn find_value(state: &AppState, target: &[Value]) -> Vec<ValueMatch> {
+    let mut matches = Vec::new();
+    for signal in &state.displayed_signals {
+        let Some(values) = state.waveform_data.values.get(signal) else {
+            continue;
+        };
+
+        let mut holds_target = false;
+        for (time, value) in values {
+            let matched = value
+                .values(16)
+                .is_some_and(|bits| significant_bits(&bits) == target);
+            if matched && !holds_target {
+                matches.push(ValueMatch {
+                    signal: signal.clone(),
+                    time: *time,
+                });
+            }
+            holds_target = matched;
+        }
+    }
+
+    // Stable, so matches at the same time stay in display order
+    matches.sort_by_key(|found| found.time);
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec![
+            "top.addr".to_string(),
+            "top.data".to_string(),
+            "top.valid".to_string(),
+            "top.hidden".to_string(),
+        ];
+        let bus = |values: &[(u64, &str)]| -> Vec<(u64, WaveValue)> {
+            values
+                .iter()
+                .map(|(time, value)| (*time, WaveValue::Bus((*value).into())))
+                .collect()
+        };
+        state.waveform_data.values.insert(
+            "top.addr".to_string(),
+            bus(&[(0, "0"), (10, "DEADBEEF"), (20, "10"), (40, "deadbeef")]),
+        );
+        state.waveform_data.values.insert(
+            "top.data".to_string(),
+            // Repeating the value over several changes matches once
+            bus(&[(0, "00DEADBEEF"), (5, "00DEADBEEF"), (30, "FF")]),
+        );
+        state.waveform_data.values.insert(
+            "top.valid".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (10, WaveValue::Binary(Value::V1)),
+                (15, WaveValue::Binary(Value::V1)),
+                (20, WaveValue::Binary(Value::V0)),
+                (30, WaveValue::Binary(Value::V1)),
+            ],
+        );
+        state
+            .waveform_data
+            .values
+            .insert("top.hidden".to_string(), bus(&[(0, "DEADBEEF")]));
+        state.displayed_signals = state.waveform_data.signals[..3].to_vec();
+        state
+    }
+
+    fn matches(state: &AppState) -> Vec<(String, u64)> {
+        state
+            .grep_popup
+            .as_ref()
+            .unwrap()
+            .matches
+            .iter()
+            .map(|found| (found.signal.clone(), found.time))
+            .collect()
+    }
+
+    #[test]
+    fn test_grep_matches_on_several_signals() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["0xDEADBEEF"], &mut state);
+        assert_eq!(result.unwrap(), "3 match(es) of 0xDEADBEEF");
+        assert_eq!(
+            matches(&state),
+            vec![
+                ("top.data".to_string(), 0),
+                ("top.addr".to_string(), 10),
+                ("top.addr".to_string(), 40),
+            ]
+        );
+
+        // The same value in decimal and binary
+        command.execute(&["16"], &mut state).unwrap();
+        assert_eq!(matches(&state), vec![("top.addr".to_string(), 20)]);
+        command.execute(&["0b11111111"], &mut state).unwrap();
+        assert_eq!(matches(&state), vec![("top.data".to_string(), 30)]);
+    }
+
+    #[test]
+    fn test_grep_binary_signals() {
+        let command = create();
+        let mut state = get_state();
+
+        command.execute(&["1"], &mut state).unwrap();
+        assert_eq!(
+            matches(&state),
+            vec![("top.valid".to_string(), 10), ("top.valid".to_string(), 30)]
+        );
+
+        command.execute(&["0"], &mut state).unwrap();
+        assert_eq!(
+            matches(&state),
+            vec![
+                ("top.addr".to_string(), 0),
+                ("top.valid".to_string(), 0),
+                ("top.valid".to_string(), 20),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_grep_truncates_to_limit() {
+        let command = create();
+        let mut state = get_state();
+        state.config.ui.grep_match_limit = 2;
+
+        let result = command.execute(&["0xdeadbeef"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "3 match(es) of 0xdeadbeef, showing the first 2"
+        );
+        let popup = state.grep_popup.as_ref().unwrap();
+        assert_eq!(popup.matches.len(), 2);
+        assert!(popup.truncated);
+    }
+
+    #[test]
+    fn test_grep_no_matches_and_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["0x1234"], &mut state);
+        assert_eq!(result.unwrap_err(), "No displayed signal holds 0x1234");
+        assert!(state.grep_popup.is_none());
+
+        for query in ["0xZZ", "0x", "12ab", "0b102"] {
+            let result = command.execute(&[query], &mut state);
+            assert!(result.unwrap_err().starts_with("Invalid value"));
+        }
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap_err(), "Usage: grep <value>");
+    }
+}
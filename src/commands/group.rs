@@ -0,0 +1,263 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    fuzzy_finder::FuzzyFinderStateAccess,
+    signal_groups,
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "group",
+        "Save, load, list or delete named groups of displayed signals",
+        move |args, state: &mut AppState| {
+            if args.is_empty() {
+                return Err(
+                    "Usage: group save <name>, group load <name>, group delete <name> or group list"
+                        .to_string(),
+                );
+            }
+
+            match args[0] {
+                "save" => save_subcommand().execute(&args[1..], state),
+                "load" => load_subcommand().execute(&args[1..], state),
+                "delete" | "rm" => delete_subcommand().execute(&args[1..], state),
+                "list" | "ls" => list_subcommand().execute(&args[1..], state),
+                _ => Err("Unknown subcommand.".to_string()),
+            }
+        },
+    )
+    .build()
+}
+
+// Writes the groups to the config directory so they're kept across restarts
+fn persist(state: &AppState) -> Result<(), String> {
+    match &state.signal_groups_path {
+        Some(path) => signal_groups::save_signal_groups(path, &state.signal_groups)
+            .map_err(|e| format!("Couldn't save signal groups: {}", e)),
+        None => Ok(()),
+    }
+}
+
+fn save_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "save",
+        "Save the displayed signals as a named group",
+        move |args, state: &mut AppState| {
+            let [name] = args else {
+                return Err("Usage: group save <name>".to_string());
+            };
+            if name.is_empty() {
+                return Err("Group name cannot be empty".to_string());
+            }
+            // Bit rows of split signals are derived from the loaded file, so they aren't saved
+            let signals: Vec<String> = state
+                .displayed_signals
+                .iter()
+                .filter(|signal| !state.is_derived_signal(signal))
+                .cloned()
+                .collect();
+            if signals.is_empty() {
+                return Err("No signals displayed".to_string());
+            }
+
+            let count = signals.len();
+            state.signal_groups.insert(name.to_string(), signals);
+            persist(state)?;
+            Ok(format!("Saved {} signal(s) as group '{}'", count, name))
+        },
+    )
+    .build()
+}
+
+fn load_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "load",
+        "Display the signals of a named group",
+        move |args, state: &mut AppState| {
+            let [name] = args else {
+                return Err("Usage: group load <name>".to_string());
+            };
+            let group = state
+                .signal_groups
+                .get(*name)
+                .ok_or_else(|| format!("No group found with name '{}'", name))?;
+
+            // Groups may be saved from another dump of the design with different signals
+            let (present, missing): (Vec<String>, Vec<String>) = group
+                .iter()
+                .cloned()
+                .partition(|signal| state.waveform_data.signals.contains(signal));
+            if present.is_empty() {
+                return Err(format!(
+                    "None of the signals in group '{}' are in this waveform",
+                    name
+                ));
+            }
+
+            let count = present.len();
+            state.fuzzy_finder_state_mut().selected_signals = present.iter().cloned().collect();
+            state.displayed_signals = present;
+            state.selected_signal = 0;
+            state.signal_scroll_offset = 0;
+
+            if missing.is_empty() {
+                Ok(format!("Loaded {} signal(s) from group '{}'", count, name))
+            } else {
+                Ok(format!(
+                    "Loaded {} signal(s) from group '{}'. Not in this waveform: {}",
+                    count,
+                    name,
+                    missing.join(", ")
+                ))
+            }
+        },
+    )
+    .build()
+}
+
+fn delete_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "delete",
+        "Delete a named group",
+        move |args, state: &mut AppState| {
+            let [name] = args else {
+                return Err("Usage: group delete <name>".to_string());
+            };
+            if state.signal_groups.remove(*name).is_none() {
+                return Err(format!("No group found with name '{}'", name));
+            }
+            persist(state)?;
+            Ok(format!("Deleted group '{}'", name))
+        },
+    )
+    .alias("rm")
+    .build()
+}
+
+fn list_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "list",
+        "List the saved groups",
+        move |_args, state: &mut AppState| {
+            if state.signal_groups.is_empty() {
+                return Ok("No signal groups".to_string());
+            }
+
+            let groups: Vec<String> = state
+                .signal_groups
+                .iter()
+                .map(|(name, signals)| format!("{} ({})", name, signals.len()))
+                .collect();
+            Ok(groups.join(", "))
+        },
+    )
+    .alias("ls")
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec![
+            "top.pc".to_string(),
+            "top.instr".to_string(),
+            "top.mem.addr".to_string(),
+        ];
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state
+    }
+
+    #[test]
+    fn test_group_save_and_load_keeps_order() {
+        let command = create();
+        let mut state = get_state();
+
+        state.displayed_signals = vec!["top.instr".to_string(), "top.pc".to_string()];
+        assert_eq!(
+            command.execute(&["save", "fetch"], &mut state),
+            Ok("Saved 2 signal(s) as group 'fetch'".to_string())
+        );
+
+        state.displayed_signals = vec!["top.mem.addr".to_string()];
+        state.selected_signal = 0;
+        assert_eq!(
+            command.execute(&["load", "fetch"], &mut state),
+            Ok("Loaded 2 signal(s) from group 'fetch'".to_string())
+        );
+        assert_eq!(state.displayed_signals, vec!["top.instr", "top.pc"]);
+        assert_eq!(state.fuzzy_finder_state().selected_signals.len(), 2);
+    }
+
+    #[test]
+    fn test_group_load_skips_missing_signals() {
+        let command = create();
+        let mut state = get_state();
+        state.signal_groups.insert(
+            "memory".to_string(),
+            vec![
+                "top.mem.addr".to_string(),
+                "top.mem.data".to_string(),
+                "top.pc".to_string(),
+            ],
+        );
+
+        assert_eq!(
+            command.execute(&["load", "memory"], &mut state),
+            Ok(
+                "Loaded 2 signal(s) from group 'memory'. Not in this waveform: top.mem.data"
+                    .to_string()
+            )
+        );
+        assert_eq!(state.displayed_signals, vec!["top.mem.addr", "top.pc"]);
+
+        state
+            .signal_groups
+            .insert("other".to_string(), vec!["other.sig".to_string()]);
+        assert_eq!(
+            command.execute(&["load", "other"], &mut state),
+            Err("None of the signals in group 'other' are in this waveform".to_string())
+        );
+        assert_eq!(state.displayed_signals, vec!["top.mem.addr", "top.pc"]);
+    }
+
+    #[test]
+    fn test_group_list_and_delete_persist() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("signal_groups.toml");
+        let command = create();
+        let mut state = get_state();
+        state.signal_groups_path = Some(path.clone());
+
+        assert_eq!(
+            command.execute(&["list"], &mut state),
+            Ok("No signal groups".to_string())
+        );
+        command.execute(&["save", "debug"], &mut state).unwrap();
+        command.execute(&["save", "all"], &mut state).unwrap();
+        assert_eq!(
+            command.execute(&["ls"], &mut state),
+            Ok("all (3), debug (3)".to_string())
+        );
+        assert_eq!(signal_groups::load_signal_groups(&path).len(), 2);
+
+        assert_eq!(
+            command.execute(&["delete", "debug"], &mut state),
+            Ok("Deleted group 'debug'".to_string())
+        );
+        assert_eq!(
+            command.execute(&["delete", "debug"], &mut state),
+            Err("No group found with name 'debug'".to_string())
+        );
+        assert_eq!(
+            signal_groups::load_signal_groups(&path)
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["all"]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+/// Rows a signal can be drawn in with `:height`.
+const HEIGHTS: [usize; 3] = [1, 2, 4];
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "height",
+        "Draw a displayed signal in 1, 2 or 4 rows, e.g. to make an important signal stand out",
+        |args, state: &mut AppState| {
+            let [signal, height] = args else {
+                return Err("Usage: height <signal> <1|2|4>".to_string());
+            };
+            if !state.displayed_signals.iter().any(|s| s == signal) {
+                return Err(format!("Signal '{}' is not displayed", signal));
+            }
+            let height = height
+                .parse::<usize>()
+                .ok()
+                .filter(|height| HEIGHTS.contains(height))
+                .ok_or_else(|| "The height must be 1, 2 or 4".to_string())?;
+
+            state.signal_heights.insert(signal.to_string(), height);
+            Ok(format!("Drawing {} in {} row(s)", signal, height))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.displayed_signals = vec!["top.clk".to_string(), "top.data".to_string()];
+        state
+    }
+
+    #[test]
+    fn test_height() {
+        let command = create();
+        let mut state = get_state();
+
+        assert_eq!(
+            command.execute(&["top.data", "4"], &mut state),
+            Ok("Drawing top.data in 4 row(s)".to_string())
+        );
+        assert_eq!(state.signal_height("top.data"), 4);
+        assert_eq!(state.signal_height("top.clk"), 2);
+
+        command.execute(&["top.data", "1"], &mut state).unwrap();
+        assert_eq!(state.signal_height("top.data"), 1);
+    }
+
+    #[test]
+    fn test_height_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        assert_eq!(
+            command.execute(&["top.data"], &mut state),
+            Err("Usage: height <signal> <1|2|4>".to_string())
+        );
+        assert_eq!(
+            command.execute(&["top.rst", "2"], &mut state),
+            Err("Signal 'top.rst' is not displayed".to_string())
+        );
+        assert_eq!(
+            command.execute(&["top.data", "3"], &mut state),
+            Err("The height must be 1, 2 or 4".to_string())
+        );
+        assert!(state.signal_heights.is_empty());
+    }
+}
@@ -31,12 +31,12 @@ mod tests {
         let result = command.execute(&[], &mut state);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "help".to_string());
-        assert_eq!(state.show_help, true);
+        assert!(state.show_help);
 
         // true -> false
         let result = command.execute(&[], &mut state);
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "help".to_string());
-        assert_eq!(state.show_help, false);
+        assert!(!state.show_help);
     }
 }
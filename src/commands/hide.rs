@@ -0,0 +1,118 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use glob::Pattern;
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "hide",
+        "Hide displayed signals matching a glob pattern",
+        |args, state: &mut AppState| {
+            if args.is_empty() {
+                return Err("Usage: hide <pattern>".to_string());
+            }
+
+            let pattern = Pattern::new(args[0])
+                .map_err(|e| format!("Invalid pattern '{}': {}", args[0], e))?;
+
+            // Remember the selected signal so the selection can follow it after removal
+            let selected_name = state.displayed_signals.get(state.selected_signal).cloned();
+
+            let count_before = state.displayed_signals.len();
+            state
+                .displayed_signals
+                .retain(|signal| !pattern.matches(signal));
+            let hidden = count_before - state.displayed_signals.len();
+
+            if hidden == 0 {
+                return Err(format!("No displayed signals match '{}'", args[0]));
+            }
+
+            if let Some(index) = selected_name
+                .and_then(|name| state.displayed_signals.iter().position(|s| *s == name))
+            {
+                state.selected_signal = index;
+            }
+            state.clamp_signal_selection();
+
+            Ok(format!("Hid {} signal(s)", hidden))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec![
+            "top.clk".to_string(),
+            "top.reset".to_string(),
+            "top.cpu.data".to_string(),
+            "top.cpu.addr".to_string(),
+        ];
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state
+    }
+
+    #[test]
+    fn test_hide_empty_args_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap_err(), "Usage: hide <pattern>".to_string());
+    }
+
+    #[test]
+    fn test_hide_pattern_matching_nothing_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["*.nothing"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "No displayed signals match '*.nothing'".to_string()
+        );
+        assert_eq!(state.displayed_signals.len(), 4);
+    }
+
+    #[test]
+    fn test_hide_glob_pattern() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["top.cpu.*"], &mut state);
+        assert_eq!(result.unwrap(), "Hid 2 signal(s)".to_string());
+        assert_eq!(state.displayed_signals, vec!["top.clk", "top.reset"]);
+
+        // The underlying waveform data is untouched
+        assert_eq!(state.waveform_data.signals.len(), 4);
+    }
+
+    #[test]
+    fn test_hide_keeps_selection_on_same_signal() {
+        let command = create();
+        let mut state = get_state();
+        state.selected_signal = 3; // top.cpu.addr
+        let result = command.execute(&["top.c?k"], &mut state);
+        assert!(result.is_ok());
+        assert_eq!(
+            state.displayed_signals[state.selected_signal],
+            "top.cpu.addr"
+        );
+    }
+
+    #[test]
+    fn test_hide_selected_signal_clamps_selection() {
+        let command = create();
+        let mut state = get_state();
+        state.selected_signal = 3;
+        state.signal_scroll_offset = 3;
+        let result = command.execute(&["top.cpu.*"], &mut state);
+        assert!(result.is_ok());
+        assert_eq!(state.selected_signal, 1);
+        assert_eq!(state.signal_scroll_offset, 1);
+    }
+}
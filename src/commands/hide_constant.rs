@@ -0,0 +1,109 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "hide-constant",
+        "Hide displayed signals that never change from their initial value",
+        |_args, state: &mut AppState| {
+            // Remember the selected signal so the selection can follow it after removal
+            let selected_name = state.displayed_signals.get(state.selected_signal).cloned();
+
+            let count_before = state.displayed_signals.len();
+            let mut constant = state.constant_displayed_signals().into_iter();
+            state
+                .displayed_signals
+                .retain(|_| !constant.next().unwrap_or(false));
+            let hidden = count_before - state.displayed_signals.len();
+
+            if hidden == 0 {
+                return Err("No displayed signals are constant".to_string());
+            }
+
+            if let Some(index) = selected_name
+                .and_then(|name| state.displayed_signals.iter().position(|s| *s == name))
+            {
+                state.selected_signal = index;
+            }
+            state.clamp_signal_selection();
+
+            Ok(format!("Hid {} constant signal(s)", hidden))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec![
+            "top.clk".to_string(),
+            "top.rst".to_string(),
+            "top.data".to_string(),
+            "top.mode".to_string(),
+        ];
+        state.waveform_data.values.insert(
+            "top.clk".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (5, WaveValue::Binary(Value::V1)),
+            ],
+        );
+        // Only given a value in $dumpvars
+        state.waveform_data.values.insert(
+            "top.rst".to_string(),
+            vec![(0, WaveValue::Binary(Value::V0))],
+        );
+        state.waveform_data.values.insert(
+            "top.data".to_string(),
+            vec![
+                (0, WaveValue::Bus("00".into())),
+                (10, WaveValue::Bus("A5".into())),
+            ],
+        );
+        // Dumped twice with the same value
+        state.waveform_data.values.insert(
+            "top.mode".to_string(),
+            vec![
+                (0, WaveValue::Bus("3".into())),
+                (10, WaveValue::Bus("3".into())),
+            ],
+        );
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state
+    }
+
+    #[test]
+    fn test_hide_constant() {
+        let command = create();
+        let mut state = get_state();
+        state.selected_signal = 2;
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap(), "Hid 2 constant signal(s)".to_string());
+        assert_eq!(state.displayed_signals, vec!["top.clk", "top.data"]);
+        // The selection follows the selected signal
+        assert_eq!(state.selected_signal, 1);
+    }
+
+    #[test]
+    fn test_hide_constant_without_constant_signals_is_err() {
+        let command = create();
+        let mut state = get_state();
+        state.displayed_signals = vec!["top.clk".to_string()];
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "No displayed signals are constant".to_string()
+        );
+        assert_eq!(state.displayed_signals, vec!["top.clk"]);
+    }
+}
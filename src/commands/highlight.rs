@@ -0,0 +1,195 @@
+use super::grep::parse_query;
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    constants::HIGHLIGHT_COLORS,
+    parsers::types::hex_of_bits,
+    state::AppState,
+    types::Highlight,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "highlight",
+        "Tint every run of a signal holding a value, or list or clear the highlighted values",
+        |args, state: &mut AppState| match args {
+            ["list"] => Ok(list(state)),
+            ["clear"] => clear(state, None),
+            ["clear", signal] => clear(state, Some(signal)),
+            [signal, value] => add(state, signal, value, None),
+            [signal, value, color] => add(state, signal, value, Some(color)),
+            _ => Err(
+                "Usage: highlight <signal> <value> [color], highlight list or highlight clear [signal]"
+                    .to_string(),
+            ),
+        },
+    )
+    .build()
+}
+
+// Adds a rule for `value` of `signal`, or changes the color of the one already there if a color is
+// given. New rules without a color get the first of the highlight colors no other rule has.
+fn add(
+    state: &mut AppState,
+    signal: &str,
+    value: &str,
+    color: Option<&str>,
+) -> Result<String, String> {
+    if !state.waveform_data.values.contains_key(signal) {
+        return Err(format!("Signal not found: {}", signal));
+    }
+    let normalized = parse_query(value)
+        .and_then(|bits| hex_of_bits(&bits))
+        .ok_or_else(|| {
+            format!(
+                "Invalid value '{}': expected a number like 3, 0x3, 0o3 or 0b11",
+                value
+            )
+        })?;
+    let color = color.map(super::parse_color).transpose()?;
+
+    let existing = state
+        .highlights
+        .iter_mut()
+        .find(|rule| rule.signal == signal && rule.value == normalized);
+    match (existing, color) {
+        (Some(rule), Some(color)) => rule.color = color,
+        (Some(_), None) => {}
+        (None, color) => {
+            let color = color.unwrap_or_else(|| {
+                HIGHLIGHT_COLORS
+                    .into_iter()
+                    .find(|color| state.highlights.iter().all(|rule| rule.color != *color))
+                    .unwrap_or(HIGHLIGHT_COLORS[state.highlights.len() % HIGHLIGHT_COLORS.len()])
+            });
+            state.highlights.push(Highlight {
+                signal: signal.to_string(),
+                value: normalized.clone(),
+                color,
+            });
+        }
+    }
+    Ok(format!("Highlighting {} = 0x{}", signal, normalized))
+}
+
+fn list(state: &AppState) -> String {
+    if state.highlights.is_empty() {
+        return "No highlights".to_string();
+    }
+    let list: Vec<String> = state
+        .highlights
+        .iter()
+        .map(|rule| format!("{} = 0x{} ({})", rule.signal, rule.value, rule.color))
+        .collect();
+    list.join(", ")
+}
+
+fn clear(state: &mut AppState, signal: Option<&str>) -> Result<String, String> {
+    let count_before = state.highlights.len();
+    state
+        .highlights
+        .retain(|rule| signal.is_some_and(|signal| rule.signal != signal));
+    let cleared = count_before - state.highlights.len();
+
+    match signal {
+        Some(signal) if cleared == 0 => Err(format!("No highlights on {}", signal)),
+        _ => Ok(format!("Cleared {} highlight(s)", cleared)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+    use ratatui::style::Color;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.values.insert(
+            "top.state".to_string(),
+            vec![(0, WaveValue::Bus("0".into()))],
+        );
+        state.waveform_data.values.insert(
+            "top.busy".to_string(),
+            vec![(0, WaveValue::Binary(Value::V0))],
+        );
+        state
+    }
+
+    #[test]
+    fn test_highlight_normalizes_values() {
+        let command = create();
+        let mut state = get_state();
+
+        for value in ["0x3", "3", "0b11", "0x03"] {
+            assert_eq!(
+                command.execute(&["top.state", value], &mut state),
+                Ok("Highlighting top.state = 0x3".to_string())
+            );
+        }
+        // The same value again keeps the rule
+        assert_eq!(state.highlights.len(), 1);
+        assert_eq!(state.highlights[0].value, "3");
+        assert_eq!(state.highlights[0].color, HIGHLIGHT_COLORS[0]);
+
+        command
+            .execute(&["top.state", "0xA", "green"], &mut state)
+            .unwrap();
+        command.execute(&["top.busy", "1"], &mut state).unwrap();
+        assert_eq!(
+            command.execute(&["list"], &mut state),
+            Ok(format!(
+                "top.state = 0x3 ({}), top.state = 0xa (Green), top.busy = 0x1 ({})",
+                HIGHLIGHT_COLORS[0], HIGHLIGHT_COLORS[1]
+            ))
+        );
+        assert_eq!(state.highlights[1].color, Color::Green);
+    }
+
+    #[test]
+    fn test_highlight_clear() {
+        let command = create();
+        let mut state = get_state();
+        assert_eq!(
+            command.execute(&["list"], &mut state),
+            Ok("No highlights".to_string())
+        );
+
+        command.execute(&["top.state", "1"], &mut state).unwrap();
+        command.execute(&["top.state", "2"], &mut state).unwrap();
+        command.execute(&["top.busy", "0"], &mut state).unwrap();
+        assert_eq!(
+            command.execute(&["clear", "top.state"], &mut state),
+            Ok("Cleared 2 highlight(s)".to_string())
+        );
+        assert_eq!(
+            command.execute(&["clear", "top.state"], &mut state),
+            Err("No highlights on top.state".to_string())
+        );
+        assert_eq!(
+            command.execute(&["clear"], &mut state),
+            Ok("Cleared 1 highlight(s)".to_string())
+        );
+        assert!(state.highlights.is_empty());
+    }
+
+    #[test]
+    fn test_highlight_errors() {
+        let command = create();
+        let mut state = get_state();
+        assert_eq!(
+            command.execute(&["top.missing", "1"], &mut state),
+            Err("Signal not found: top.missing".to_string())
+        );
+        assert_eq!(
+            command.execute(&["top.state", "0xg"], &mut state),
+            Err("Invalid value '0xg': expected a number like 3, 0x3, 0o3 or 0b11".to_string())
+        );
+        assert_eq!(
+            command.execute(&["top.state", "1", "chartreuse"], &mut state),
+            Err("Unknown color: chartreuse. Only ANSI colors are supported.".to_string())
+        );
+        assert!(command.execute(&["top.state"], &mut state).is_err());
+        assert!(state.highlights.is_empty());
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+    time_format::format_time,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "jumps",
+        "List the jump list, oldest first, with > before the place gone back to",
+        |_args, state: &mut AppState| {
+            if state.jump_list.is_empty() {
+                return Ok("No jumps".to_string());
+            }
+
+            let list: Vec<String> = state
+                .jump_list
+                .iter()
+                .enumerate()
+                .map(|(index, jump)| {
+                    format!(
+                        "{}{}: {}-{} {}",
+                        if index == state.jump_index { ">" } else { "" },
+                        index + 1,
+                        format_time(jump.time_start, state.time_zero),
+                        format_time(jump.time_start + jump.time_range, state.time_zero),
+                        jump.selected_signal.as_deref().unwrap_or("-")
+                    )
+                })
+                .collect();
+            Ok(list.join(", "))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jumps_lists_places_and_position() {
+        let command = create();
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.displayed_signals = vec!["top.clk".to_string(), "top.irq".to_string()];
+        assert_eq!(command.execute(&[], &mut state), Ok("No jumps".to_string()));
+
+        state.set_window(0, 1000);
+        state.record_jump();
+        state.set_window(300, 200);
+        state.selected_signal = 1;
+        state.record_jump();
+        state.set_window(600, 100);
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Ok("1: 0-1000 top.clk, 2: 300-500 top.irq".to_string())
+        );
+
+        state.jump_back();
+        state.time_zero = Some(300);
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Ok("1: -300-700 top.clk, >2: 0-200 top.irq, 3: 300-400 top.irq".to_string())
+        );
+    }
+}
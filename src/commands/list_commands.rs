@@ -0,0 +1,35 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "commands",
+        "List every command with its aliases",
+        |_args, state: &mut AppState| {
+            state.show_command_list = true;
+            state.command_list_scroll = 0;
+            Ok("commands".to_string())
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commands_opens_list() {
+        let command = create();
+        let mut state = AppState::default();
+        state.command_list_scroll = 4;
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Ok("commands".to_string()));
+        assert!(state.show_command_list);
+        assert_eq!(state.command_list_scroll, 0);
+    }
+}
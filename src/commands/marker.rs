@@ -1,5 +1,6 @@
 use crate::{
     command_mode::{builder::CommandBuilder, registry::Command},
+    parsers::types::parse_time,
     state::AppState,
     types::Marker,
 };
@@ -18,7 +19,11 @@ pub fn create() -> Rc<Box<dyn Command<AppState>>> {
             match &**subcommand {
                 "add" | "a" => add_subcommand().execute(&args[1..], state),
                 "remove" | "rm" => remove_subcommand().execute(&args[1..], state),
+                "rename" | "mv" => rename_subcommand().execute(&args[1..], state),
                 "color" | "c" => color_subcommand().execute(&args[1..], state),
+                "list" | "ls" => list_subcommand().execute(&args[1..], state),
+                "goto" | "g" => goto_subcommand().execute(&args[1..], state),
+                "swap" | "s" => swap_subcommand().execute(&args[1..], state),
                 _ => Err("Unknown subcommand.".to_string()),
             }
         },
@@ -30,31 +35,24 @@ pub fn create() -> Rc<Box<dyn Command<AppState>>> {
 fn add_subcommand() -> Rc<Box<dyn Command<AppState>>> {
     CommandBuilder::new(
         "add",
-        "Add a saved marker with a name",
+        "Add a saved marker with a name, in quotes if it has spaces, at a time, relative to a marker or at a signal's next transition",
         move |args, state: &mut AppState| {
             if args.is_empty() {
                 return Err("Usage: marker add <name> [time]".to_string());
             }
 
+            // Names with whitespace are given in quotes, e.g. marker add "reset done"
             let name = args[0];
+            if name.is_empty() {
+                return Err("Marker name cannot be empty".to_string());
+            }
 
-            // If time was provided, use it. Otherwise, use the primary marker.
-            let time = if args.len() >= 2 {
-                match args[1].parse::<u64>() {
-                    Ok(t) => {
-                        if t > state.waveform_data.max_time {
-                            return Err(format!(
-                                "Time out of range (0-{})",
-                                state.waveform_data.max_time
-                            ));
-                        }
-                        t
-                    }
-                    Err(_) => return Err("Invalid time format".to_string()),
-                }
+            // If a place was given, use it. Otherwise, use the primary marker.
+            let (time, note) = if args.len() >= 2 {
+                place(args[1], state)?
             } else {
                 match state.primary_marker {
-                    Some(t) => t,
+                    Some(t) => (t, None),
                     None => return Err("No time specified and primary marker not set".to_string()),
                 }
             };
@@ -71,17 +69,86 @@ fn add_subcommand() -> Rc<Box<dyn Command<AppState>>> {
                 color: crate::constants::DEFAULT_SAVED_MARKER_COLOR,
             };
             state.saved_markers.push(marker);
-            Ok(format!("Added marker '{}' at time {}", name, time))
+            let message = format!("Added marker '{}' at time {}", name, time);
+            Ok(match note {
+                Some(note) => format!("{} ({})", message, note),
+                None => message,
+            })
         },
     )
     .alias("a")
     .build()
 }
 
+// Time of a place like `500`, `2ns`, `m1+200`, `<marker>-50ns` or `next:<signal>`, with a note when
+// the offset took it outside of the waveform and it was clamped
+fn place(place: &str, state: &AppState) -> Result<(u64, Option<String>), String> {
+    let max_time = state.waveform_data.max_time;
+    if let Some(signal) = place.strip_prefix("next:") {
+        return next_transition(signal, state).map(|time| (time, None));
+    }
+
+    let Some((reference, sign, offset)) = split_offset(place, state) else {
+        let time = reference_time(place, state)?;
+        if time > max_time {
+            return Err(format!("Time out of range (0-{})", max_time));
+        }
+        return Ok((time, None));
+    };
+    let base = reference_time(reference, state)?;
+    if offset.is_empty() {
+        return Err(format!("Missing offset after '{}' in {}", sign, place));
+    }
+    let offset = parse_time(offset, state.waveform_data.timescale_fs)
+        .map_err(|e| format!("Offset of {}: {}", place, e))?;
+
+    let time = if sign == '+' {
+        base as i128 + offset as i128
+    } else {
+        base as i128 - offset as i128
+    };
+    let clamped = time.clamp(0, max_time as i128) as u64;
+    let note = (clamped as i128 != time).then(|| format!("{} is {}, clamped", place, time));
+    Ok((clamped, note))
+}
+
+// Splits `m1+200` into the reference, the sign and the offset. Saved marker names may contain + or
+// -, so a place that is a marker name has no offset.
+fn split_offset<'a>(place: &'a str, state: &AppState) -> Option<(&'a str, char, &'a str)> {
+    if state.saved_markers.iter().any(|m| m.name == place) {
+        return None;
+    }
+    let index = place.rfind(['+', '-']).filter(|index| *index > 0)?;
+    let sign = place[index..].chars().next()?;
+    Some((&place[..index], sign, &place[index + 1..]))
+}
+
+// A bare time, with a unit if the waveform has a timescale, or m1, m2 or a saved marker
+fn reference_time(reference: &str, state: &AppState) -> Result<u64, String> {
+    if reference.starts_with(|c: char| c.is_ascii_digit()) {
+        parse_time(reference, state.waveform_data.timescale_fs)
+    } else {
+        state.marker_time(reference)
+    }
+}
+
+// The first transition of a signal after the primary marker
+fn next_transition(signal: &str, state: &AppState) -> Result<u64, String> {
+    if !state.waveform_data.values.contains_key(signal) {
+        return Err(format!("Signal not found: {}", signal));
+    }
+    let marker = state
+        .primary_marker
+        .ok_or_else(|| format!("next:{} needs the primary marker", signal))?;
+    state
+        .next_transition(signal, marker)
+        .ok_or_else(|| format!("{} has no transitions after {}", signal, marker))
+}
+
 fn remove_subcommand() -> Rc<Box<dyn Command<AppState>>> {
     CommandBuilder::new(
         "remove",
-        "Remove a saved marker by name",
+        "Remove a saved marker by name, in quotes if it has spaces",
         move |args, state: &mut AppState| {
             if args.is_empty() {
                 return Err("Usage: marker remove <name>".to_string());
@@ -103,6 +170,38 @@ fn remove_subcommand() -> Rc<Box<dyn Command<AppState>>> {
     .build()
 }
 
+fn rename_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "rename",
+        "Rename a saved marker, in quotes if the names have spaces",
+        move |args, state: &mut AppState| {
+            if args.len() < 2 {
+                return Err("Usage: marker rename <name> <new name>".to_string());
+            }
+
+            let (name, new_name) = (args[0], args[1]);
+            if new_name.is_empty() {
+                return Err("Marker name cannot be empty".to_string());
+            }
+            if name != new_name && state.saved_markers.iter().any(|m| m.name == new_name) {
+                return Err(format!("Marker '{}' already exists", new_name));
+            }
+
+            let Some(marker) = state.saved_markers.iter_mut().find(|m| m.name == name) else {
+                return Err(format!("No marker found with name '{}'", name));
+            };
+            marker.name = new_name.to_string();
+            // The selection follows the marker
+            if state.selected_saved_marker.as_deref() == Some(name) {
+                state.selected_saved_marker = Some(new_name.to_string());
+            }
+            Ok(format!("Renamed marker '{}' to '{}'", name, new_name))
+        },
+    )
+    .alias("mv")
+    .build()
+}
+
 fn color_subcommand() -> Rc<Box<dyn Command<AppState>>> {
     CommandBuilder::new(
         "color",
@@ -115,15 +214,7 @@ fn color_subcommand() -> Rc<Box<dyn Command<AppState>>> {
             let name = &args[0];
             let color_str = &args[1];
 
-            let color = match color_str.to_lowercase().parse::<ratatui::style::Color>() {
-                Ok(c) => c,
-                Err(_) => {
-                    return Err(format!(
-                        "Unknown color: {}. Only ANSI colors are supported.",
-                        color_str
-                    ))
-                }
-            };
+            let color = super::parse_color(color_str)?;
 
             // Find the marker with the given name
             if let Some(marker) = state.saved_markers.iter_mut().find(|m| &m.name == name) {
@@ -139,10 +230,88 @@ fn color_subcommand() -> Rc<Box<dyn Command<AppState>>> {
     .build()
 }
 
+fn list_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "list",
+        "List saved markers sorted by time",
+        move |_args, state: &mut AppState| {
+            let markers = state.saved_markers_by_time();
+            if markers.is_empty() {
+                return Ok("No saved markers".to_string());
+            }
+
+            // A few markers fit in the command result, more get a popup
+            if markers.len() <= 3 {
+                let list: Vec<String> = markers
+                    .iter()
+                    .map(|m| format!("{}@{} ({})", m.name, m.time, m.color))
+                    .collect();
+                return Ok(list.join(", "));
+            }
+
+            let count = markers.len();
+            state.show_marker_list = true;
+            state.marker_list_scroll = 0;
+            Ok(format!("{} saved markers", count))
+        },
+    )
+    .alias("ls")
+    .build()
+}
+
+fn goto_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "goto",
+        "Center the view on a saved marker and move the primary marker to it",
+        move |args, state: &mut AppState| {
+            if args.is_empty() {
+                return Err("Usage: marker goto <name>".to_string());
+            }
+
+            let name = &args[0];
+            let Some(time) = state
+                .saved_markers
+                .iter()
+                .find(|m| &m.name == name)
+                .map(|m| m.time)
+            else {
+                return Err(format!("No marker found with name '{}'", name));
+            };
+
+            // Center the view around the marker
+            state.record_jump();
+            let half_range = state.time_range / 2;
+            state.zoom_to(time.saturating_sub(half_range), state.time_range);
+            state.move_primary_marker(time);
+            Ok(format!("Moved to marker '{}' at time {}", name, time))
+        },
+    )
+    .alias("g")
+    .build()
+}
+
+fn swap_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "swap",
+        "Exchange the primary and secondary markers",
+        move |_args, state: &mut AppState| {
+            let (Some(m1), Some(m2)) = (state.primary_marker, state.secondary_marker) else {
+                return Err("Both markers must be set to swap them".to_string());
+            };
+
+            state.move_primary_marker(m2);
+            state.secondary_marker = Some(m1);
+            Ok(format!("Swapped markers: M1={} M2={}", m2, m1))
+        },
+    )
+    .alias("s")
+    .build()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::Marker;
+    use crate::parsers::types::{Value, WaveValue};
 
     fn get_state() -> AppState {
         let mut state = AppState::default();
@@ -201,7 +370,10 @@ mod tests {
         let mut state = get_state();
         let result = command.execute(&["add", "mymarker", "not_a_number"], &mut state);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Invalid time format".to_string());
+        assert_eq!(
+            result.unwrap_err(),
+            "No marker found with name 'not_a_number'".to_string()
+        );
     }
 
     #[test]
@@ -299,6 +471,42 @@ mod tests {
         assert!(state.saved_markers.is_empty());
     }
 
+    #[test]
+    fn test_marker_rename_rejects_collisions() {
+        let command = create();
+        let mut state = get_state();
+        state.saved_markers = vec![
+            Marker::new(100, "start".to_string()),
+            Marker::new(500, "irq".to_string()),
+        ];
+        state.selected_saved_marker = Some("irq".to_string());
+
+        assert_eq!(
+            command.execute(&["rename", "irq", "start"], &mut state),
+            Err("Marker 'start' already exists".to_string())
+        );
+        assert_eq!(
+            command.execute(&["mv", "irq", ""], &mut state),
+            Err("Marker name cannot be empty".to_string())
+        );
+        assert_eq!(
+            command.execute(&["rename", "nmi", "x"], &mut state),
+            Err("No marker found with name 'nmi'".to_string())
+        );
+        assert_eq!(state.saved_markers[1].name, "irq");
+
+        // Keeping the name is allowed, and the selection follows a rename
+        assert!(command
+            .execute(&["rename", "irq", "irq"], &mut state)
+            .is_ok());
+        assert_eq!(
+            command.execute(&["rename", "irq", "irq done"], &mut state),
+            Ok("Renamed marker 'irq' to 'irq done'".to_string())
+        );
+        assert_eq!(state.saved_markers[1].name, "irq done");
+        assert_eq!(state.selected_saved_marker.as_deref(), Some("irq done"));
+    }
+
     #[test]
     fn test_marker_add_duplicate_is_err() {
         let command = create();
@@ -353,4 +561,292 @@ mod tests {
             "Unknown color: not_a_color. Only ANSI colors are supported.".to_string()
         );
     }
+
+    #[test]
+    fn test_marker_add_empty_name_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["add", "", "500"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Marker name cannot be empty".to_string()
+        );
+        assert!(state.saved_markers.is_empty());
+    }
+
+    #[test]
+    fn test_marker_add_and_remove_name_with_whitespace() {
+        let command = create();
+        let mut state = get_state();
+        command
+            .execute(&["add", "reset done", "500"], &mut state)
+            .unwrap();
+        assert_eq!(state.saved_markers[0].name, "reset done");
+
+        let result = command.execute(&["remove", "reset done"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Removed marker 'reset done' at time 500".to_string()
+        );
+    }
+
+    #[test]
+    fn test_marker_list_sorted_by_time() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["list"], &mut state);
+        assert_eq!(result.unwrap(), "No saved markers".to_string());
+
+        state.saved_markers = vec![
+            Marker::new(700, "late".to_string()),
+            Marker::new(100, "early".to_string()),
+            Marker::new(400, "middle".to_string()),
+        ];
+        let result = command.execute(&["list"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "early@100 (Cyan), middle@400 (Cyan), late@700 (Cyan)".to_string()
+        );
+        assert!(!state.show_marker_list);
+
+        // More markers than fit in the command result open the popup
+        state
+            .saved_markers
+            .push(Marker::new(400, "also_middle".to_string()));
+        let result = command.execute(&["ls"], &mut state);
+        assert_eq!(result.unwrap(), "4 saved markers".to_string());
+        assert!(state.show_marker_list);
+
+        let names: Vec<&str> = state
+            .saved_markers_by_time()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["early", "also_middle", "middle", "late"]);
+    }
+
+    #[test]
+    fn test_marker_goto_centers_view() {
+        let command = create();
+        let mut state = get_state();
+        state.time_range = 100;
+        state
+            .saved_markers
+            .push(Marker::new(500, "mymarker".to_string()));
+
+        let result = command.execute(&["goto", "mymarker"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Moved to marker 'mymarker' at time 500".to_string()
+        );
+        assert_eq!((state.time_start, state.time_range), (450, 100));
+        assert_eq!(state.primary_marker, Some(500));
+    }
+
+    #[test]
+    fn test_marker_goto_near_edges() {
+        let command = create();
+        let mut state = get_state();
+        state.time_start = 400;
+        state.time_range = 100;
+        state.saved_markers = vec![
+            Marker::new(10, "start".to_string()),
+            Marker::new(990, "end".to_string()),
+        ];
+
+        // The view can't be centered past either end of the waveform
+        command.execute(&["g", "start"], &mut state).unwrap();
+        assert_eq!((state.time_start, state.time_range), (0, 100));
+        assert_eq!(state.primary_marker, Some(10));
+
+        command.execute(&["g", "end"], &mut state).unwrap();
+        assert_eq!((state.time_start, state.time_range), (900, 100));
+        assert_eq!(state.primary_marker, Some(990));
+    }
+
+    #[test]
+    fn test_marker_goto_nonexistent_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["goto", "mymarker"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "No marker found with name 'mymarker'".to_string()
+        );
+        assert_eq!(state.primary_marker, None);
+    }
+
+    #[test]
+    fn test_marker_swap() {
+        let command = create();
+        let mut state = get_state();
+        state.primary_marker = Some(100);
+        state.secondary_marker = Some(400);
+
+        let result = command.execute(&["swap"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Swapped markers: M1=400 M2=100".to_string()
+        );
+        assert_eq!(state.primary_marker, Some(400));
+        assert_eq!(state.secondary_marker, Some(100));
+    }
+
+    #[test]
+    fn test_marker_swap_needs_both_markers() {
+        let command = create();
+        let mut state = get_state();
+        state.primary_marker = Some(100);
+
+        let result = command.execute(&["s"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Both markers must be set to swap them".to_string()
+        );
+        assert_eq!(state.primary_marker, Some(100));
+        assert_eq!(state.secondary_marker, None);
+
+        state.primary_marker = None;
+        state.secondary_marker = Some(100);
+        assert!(command.execute(&["swap"], &mut state).is_err());
+        assert_eq!(state.secondary_marker, Some(100));
+    }
+
+    fn placed(state: &mut AppState, place: &str) -> Result<String, String> {
+        let result = create().execute(&["add", "placed", place], state);
+        state.saved_markers.retain(|m| m.name != "placed");
+        result
+    }
+
+    #[test]
+    fn test_marker_add_relative_to_markers() {
+        let mut state = get_state();
+        state.primary_marker = Some(300);
+        state.secondary_marker = Some(700);
+        state.saved_markers = vec![Marker::new(100, "reset-done".to_string())];
+
+        assert_eq!(
+            placed(&mut state, "m1+200").unwrap(),
+            "Added marker 'placed' at time 500"
+        );
+        assert_eq!(
+            placed(&mut state, "m2-50").unwrap(),
+            "Added marker 'placed' at time 650"
+        );
+        // Saved marker names may contain a minus
+        assert_eq!(
+            placed(&mut state, "reset-done").unwrap(),
+            "Added marker 'placed' at time 100"
+        );
+        assert_eq!(
+            placed(&mut state, "reset-done+25").unwrap(),
+            "Added marker 'placed' at time 125"
+        );
+        assert_eq!(
+            placed(&mut state, "40+2").unwrap(),
+            "Added marker 'placed' at time 42"
+        );
+    }
+
+    #[test]
+    fn test_marker_add_offsets_with_units() {
+        let mut state = get_state();
+        state.waveform_data.timescale_fs = Some(1_000); // 1ps
+        state.primary_marker = Some(600);
+
+        assert_eq!(
+            placed(&mut state, "m1-0.1ns").unwrap(),
+            "Added marker 'placed' at time 500"
+        );
+        assert_eq!(
+            placed(&mut state, "0.2ns").unwrap(),
+            "Added marker 'placed' at time 200"
+        );
+
+        state.waveform_data.timescale_fs = None;
+        assert_eq!(
+            placed(&mut state, "m1-50ns").unwrap_err(),
+            "Offset of m1-50ns: The waveform has no timescale, so give the time without a unit"
+        );
+    }
+
+    #[test]
+    fn test_marker_add_clamps_offsets() {
+        let mut state = get_state();
+        state.primary_marker = Some(300);
+
+        let result = placed(&mut state, "m1-500");
+        assert_eq!(
+            result.unwrap(),
+            "Added marker 'placed' at time 0 (m1-500 is -200, clamped)"
+        );
+        let result = placed(&mut state, "m1+800");
+        assert_eq!(
+            result.unwrap(),
+            "Added marker 'placed' at time 1000 (m1+800 is 1100, clamped)"
+        );
+    }
+
+    #[test]
+    fn test_marker_add_expression_errors() {
+        let mut state = get_state();
+        state.primary_marker = Some(300);
+
+        assert_eq!(
+            placed(&mut state, "m2+10").unwrap_err(),
+            "Secondary marker not set"
+        );
+        assert_eq!(
+            placed(&mut state, "irq+10").unwrap_err(),
+            "No marker found with name 'irq'"
+        );
+        assert_eq!(
+            placed(&mut state, "m1+").unwrap_err(),
+            "Missing offset after '+' in m1+"
+        );
+        assert_eq!(
+            placed(&mut state, "m1+abc").unwrap_err(),
+            "Offset of m1+abc: Invalid time: abc"
+        );
+        assert_eq!(
+            placed(&mut state, "m1+5xs").unwrap_err(),
+            "Offset of m1+5xs: Unknown time unit: xs"
+        );
+    }
+
+    #[test]
+    fn test_marker_add_at_next_transition() {
+        let mut state = get_state();
+        state.waveform_data.values.insert(
+            "clk".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (100, WaveValue::Binary(Value::V1)),
+                (150, WaveValue::Binary(Value::V1)),
+                (200, WaveValue::Binary(Value::V0)),
+            ],
+        );
+
+        assert_eq!(
+            placed(&mut state, "next:clk").unwrap_err(),
+            "next:clk needs the primary marker"
+        );
+
+        state.primary_marker = Some(100);
+        // The repeated value at 150 is not a transition
+        assert_eq!(
+            placed(&mut state, "next:clk").unwrap(),
+            "Added marker 'placed' at time 200"
+        );
+
+        state.primary_marker = Some(200);
+        assert_eq!(
+            placed(&mut state, "next:clk").unwrap_err(),
+            "clk has no transitions after 200"
+        );
+        assert_eq!(
+            placed(&mut state, "next:data").unwrap_err(),
+            "Signal not found: data"
+        );
+    }
 }
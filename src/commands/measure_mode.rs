@@ -0,0 +1,47 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "measuremode",
+        "Toggle whether dragging in the waveform measures time instead of zooming",
+        |_args, state: &mut AppState| {
+            state.measure_mode = !state.measure_mode;
+            if state.measure_mode {
+                Ok("Measure mode on: dragging places markers".to_string())
+            } else {
+                Ok("Measure mode off: dragging zooms".to_string())
+            }
+        },
+    )
+    .alias("mm")
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measuremode_toggle() {
+        let command = create();
+        let mut state = AppState::default();
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Measure mode on: dragging places markers".to_string()
+        );
+        assert!(state.measure_mode);
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Measure mode off: dragging zooms".to_string()
+        );
+        assert!(!state.measure_mode);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command, CommandModeStateAccess},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "messages",
+        "List the results of the last 100 commands, including the ones no longer shown",
+        |_args, state: &mut AppState| {
+            let count = state.command_state().results.len();
+            if count == 0 {
+                return Err("No messages yet".to_string());
+            }
+            // Start at the most recent results, at the bottom
+            state.show_messages = true;
+            state.messages_scroll = usize::MAX;
+            Ok(format!("Showing {} earlier message(s)", count))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        let command = create();
+        let mut state = AppState::default();
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Err("No messages yet".to_string())
+        );
+        assert!(!state.show_messages);
+
+        state
+            .command_state_mut()
+            .push_result("Oops".to_string(), true);
+        state.command_state_mut().dismiss_errors();
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Ok("Showing 1 earlier message(s)".to_string())
+        );
+        assert!(state.show_messages);
+    }
+}
@@ -1,13 +1,71 @@
+//! The built-in `:` commands of the viewer.
+
+mod alias;
+mod center;
+mod color;
+mod compare;
+mod config;
+mod cursor;
+mod decode;
+mod delta;
+mod diff;
+mod fileinfo;
 mod find_signal;
+mod fit;
+mod follow;
+mod force_quit;
+mod format;
+mod glitches;
 mod goto;
+mod grep;
+mod group;
+mod height;
 mod help;
+mod hide;
+mod hide_constant;
+mod highlight;
+mod jumps;
+mod list_commands;
 mod marker;
+mod measure_mode;
+mod messages;
+mod move_signal;
+mod names;
+mod pan;
+mod parsewarnings;
+mod pin;
 mod quit;
+mod region;
+mod reload;
+mod sample;
+mod session;
+mod set;
+mod show;
+mod sleep;
+mod sort;
+mod split;
+mod split_view;
+mod stats;
+mod timezero;
+mod trace;
+mod track;
+mod transitions;
+mod unalias;
+mod unpin;
+mod unsplit;
+mod view;
+mod watch;
+mod write_quit;
+mod yank;
 mod zoom;
+mod zoomback;
+mod zoomfit;
+mod zoomforward;
 mod zoomfull;
 
 use crate::{command_mode::registry::Command, state::AppState};
-use std::rc::Rc;
+use ratatui::style::Color;
+use std::{collections::HashSet, rc::Rc};
 
 pub trait CommandRegistry<S> {
     fn register_command(&mut self, command: Rc<Box<dyn Command<S>>>);
@@ -19,12 +77,89 @@ impl<S> CommandRegistry<S> for crate::command_mode::CommandModeWidget<S> {
     }
 }
 
+/// Registers every built-in command. User aliases can't use their names afterwards.
 pub fn register_all_commands(registry: &mut impl CommandRegistry<AppState>) {
-    registry.register_command(find_signal::create());
-    registry.register_command(goto::create());
-    registry.register_command(help::create());
-    registry.register_command(marker::create());
-    registry.register_command(quit::create());
-    registry.register_command(zoom::create());
-    registry.register_command(zoomfull::create());
+    let commands = [
+        center::create(),
+        color::create(),
+        compare::create(),
+        config::create(),
+        cursor::create(),
+        decode::create(),
+        delta::create(),
+        diff::create(),
+        fileinfo::create(),
+        find_signal::create(),
+        fit::create(),
+        follow::create(),
+        format::create(),
+        force_quit::create(),
+        glitches::create(),
+        goto::create(),
+        grep::create(),
+        group::create(),
+        height::create(),
+        help::create(),
+        hide::create(),
+        hide_constant::create(),
+        highlight::create(),
+        jumps::create(),
+        list_commands::create(),
+        marker::create(),
+        measure_mode::create(),
+        messages::create(),
+        move_signal::create(),
+        names::create(),
+        pan::create(),
+        parsewarnings::create(),
+        pin::create(),
+        quit::create(),
+        region::create(),
+        reload::create(),
+        sample::create(),
+        session::create(),
+        set::create(),
+        show::create(),
+        sleep::create(),
+        sort::create(),
+        split::create(),
+        split_view::create(),
+        stats::create(),
+        timezero::create(),
+        trace::create(),
+        track::create(),
+        transitions::create(),
+        unalias::create(),
+        unpin::create(),
+        unsplit::create(),
+        view::create(),
+        watch::create(),
+        write_quit::create(),
+        yank::create(),
+        zoom::create(),
+        zoomback::create(),
+        zoomfit::create(),
+        zoomforward::create(),
+        zoomfull::create(),
+    ];
+
+    // User aliases can't take the name of a built-in command, or of alias itself
+    let mut builtin_names: HashSet<String> = HashSet::from(["alias".to_string()]);
+    for command in &commands {
+        builtin_names.insert(command.name().to_string());
+        builtin_names.extend(command.aliases().into_iter().map(str::to_string));
+    }
+
+    registry.register_command(alias::create(builtin_names));
+    for command in commands {
+        registry.register_command(command);
+    }
+}
+
+/// Parses a color name given to a command, such as `blue` or `lightgreen`.
+fn parse_color(color: &str) -> Result<Color, String> {
+    color
+        .to_lowercase()
+        .parse::<Color>()
+        .map_err(|_| format!("Unknown color: {}. Only ANSI colors are supported.", color))
 }
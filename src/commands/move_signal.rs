@@ -0,0 +1,102 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "move",
+        "Move a displayed signal to a position in the list, 0 being the top",
+        |args, state: &mut AppState| {
+            let [signal, index] = args else {
+                return Err("Usage: move <signal> <index>".to_string());
+            };
+
+            let from = state
+                .displayed_signals
+                .iter()
+                .position(|s| s == signal)
+                .ok_or_else(|| format!("Signal not displayed: {}", signal))?;
+            let last_index = state.displayed_signals.len() - 1;
+            let to = index
+                .parse::<usize>()
+                .ok()
+                .filter(|to| *to <= last_index)
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid index '{}': expected a number from 0 to {}",
+                        index, last_index
+                    )
+                })?;
+
+            let selected_name = state.displayed_signals.get(state.selected_signal).cloned();
+            let moved = state.displayed_signals.remove(from);
+            state.displayed_signals.insert(to, moved);
+            state.select_signal_named(selected_name.as_deref());
+
+            Ok(format!("Moved {} to position {}", signal, to))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec![
+            "top.clk".to_string(),
+            "top.reset".to_string(),
+            "top.data".to_string(),
+        ];
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state
+    }
+
+    #[test]
+    fn test_move_signal() {
+        let command = create();
+        let mut state = get_state();
+        state.selected_signal = 1;
+
+        let result = command.execute(&["top.data", "0"], &mut state);
+        assert_eq!(result.unwrap(), "Moved top.data to position 0");
+        assert_eq!(
+            state.displayed_signals,
+            vec!["top.data", "top.clk", "top.reset"]
+        );
+        // The selection stays on the signal it was on
+        assert_eq!(state.selected_signal, 2);
+
+        command.execute(&["top.data", "2"], &mut state).unwrap();
+        assert_eq!(
+            state.displayed_signals,
+            vec!["top.clk", "top.reset", "top.data"]
+        );
+        assert_eq!(state.selected_signal, 1);
+    }
+
+    #[test]
+    fn test_move_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["top.clk"], &mut state);
+        assert_eq!(result.unwrap_err(), "Usage: move <signal> <index>");
+
+        let result = command.execute(&["top.nope", "0"], &mut state);
+        assert_eq!(result.unwrap_err(), "Signal not displayed: top.nope");
+
+        let result = command.execute(&["top.clk", "3"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Invalid index '3': expected a number from 0 to 2"
+        );
+        let result = command.execute(&["top.clk", "up"], &mut state);
+        assert!(result.unwrap_err().starts_with("Invalid index 'up'"));
+        assert_eq!(state.displayed_signals, state.waveform_data.signals);
+    }
+}
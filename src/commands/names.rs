@@ -0,0 +1,52 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    config::SignalNameStyle,
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "names",
+        "Show full, leaf or shortened signal names in the signal list",
+        |args, state: &mut AppState| {
+            let style = match args {
+                ["full"] => SignalNameStyle::Full,
+                ["leaf"] => SignalNameStyle::Leaf,
+                ["short" | "shortened"] => SignalNameStyle::Shortened,
+                _ => return Err("Usage: names <full|leaf|short>".to_string()),
+            };
+            state.config.ui.signal_name_style = style;
+            Ok(format!("Showing {} signal names", args[0]))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_command() {
+        let command = create();
+        let mut state = AppState::default();
+
+        let result = command.execute(&["leaf"], &mut state);
+        assert_eq!(result, Ok("Showing leaf signal names".to_string()));
+        assert_eq!(state.config.ui.signal_name_style, SignalNameStyle::Leaf);
+
+        command.execute(&["short"], &mut state).unwrap();
+        assert_eq!(
+            state.config.ui.signal_name_style,
+            SignalNameStyle::Shortened
+        );
+
+        let result = command.execute(&["tiny"], &mut state);
+        assert_eq!(result, Err("Usage: names <full|leaf|short>".to_string()));
+        assert_eq!(
+            state.config.ui.signal_name_style,
+            SignalNameStyle::Shortened
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    parsers::types::{split_time_unit, time_unit_femtoseconds},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "pan",
+        "Pan by a percentage of the view or by a time",
+        |args, state: &mut AppState| {
+            let [amount] = args else {
+                return Err("Usage: pan <±N%|±time>".to_string());
+            };
+
+            let delta = parse_pan(amount, state)?;
+            state.pan_by(delta);
+            Ok(format!("Panned to time {}", state.time_start))
+        },
+    )
+    .build()
+}
+
+// Parses a pan amount into time units. A percentage is of the visible time range, and a time with
+// a unit like "300ns" is converted using the waveform's timescale.
+fn parse_pan(amount: &str, state: &AppState) -> Result<i64, String> {
+    let invalid = || {
+        format!(
+            "Invalid amount '{}': expected a percentage like -25% or a time like +300ns",
+            amount
+        )
+    };
+
+    let (negative, magnitude) = match amount.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, amount.strip_prefix('+').unwrap_or(amount)),
+    };
+
+    let units = if let Some(percentage) = magnitude.strip_suffix('%') {
+        let percentage: f64 = percentage
+            .parse()
+            .ok()
+            .filter(|p: &f64| p.is_finite() && *p >= 0.0)
+            .ok_or_else(invalid)?;
+        (state.time_range as f64 * percentage / 100.0).round() as u64
+    } else {
+        let (number, unit) = split_time_unit(magnitude);
+        let number: u64 = number.parse().map_err(|_| invalid())?;
+        if unit.is_empty() {
+            number
+        } else {
+            let unit_fs = time_unit_femtoseconds(unit)
+                .ok_or_else(|| format!("Unknown time unit: {}", unit))?;
+            let timescale_fs = state.waveform_data.timescale_fs.ok_or_else(|| {
+                "The waveform has no timescale, so give the time without a unit".to_string()
+            })?;
+            (number as u128 * unit_fs as u128 / timescale_fs as u128) as u64
+        }
+    };
+
+    let units = units.min(i64::MAX as u64) as i64;
+    Ok(if negative { -units } else { units })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1_000_000;
+        state.set_window(500_000, 100_000);
+        state
+    }
+
+    #[test]
+    fn test_parse_percentage() {
+        let state = get_state();
+        assert_eq!(parse_pan("-25%", &state), Ok(-25_000));
+        assert_eq!(parse_pan("+1%", &state), Ok(1_000));
+        assert_eq!(parse_pan("100%", &state), Ok(100_000));
+        assert_eq!(parse_pan("0.5%", &state), Ok(500));
+    }
+
+    #[test]
+    fn test_parse_time_with_timescale() {
+        let mut state = get_state();
+        assert_eq!(parse_pan("-1234", &state), Ok(-1234));
+        assert_eq!(
+            parse_pan("+300ns", &state).unwrap_err(),
+            "The waveform has no timescale, so give the time without a unit"
+        );
+
+        // One time unit is 1ps
+        state.waveform_data.timescale_fs = Some(1_000);
+        assert_eq!(parse_pan("+300ns", &state), Ok(300_000));
+        assert_eq!(parse_pan("-2ps", &state), Ok(-2));
+        assert_eq!(
+            parse_pan("+3min", &state).unwrap_err(),
+            "Unknown time unit: min"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_amounts() {
+        let state = get_state();
+        for amount in ["", "+", "--5%", "abc", "5%%", "-x%"] {
+            assert!(parse_pan(amount, &state)
+                .unwrap_err()
+                .starts_with("Invalid amount"));
+        }
+    }
+
+    #[test]
+    fn test_pan_clamps_at_both_ends() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["-25%"], &mut state);
+        assert_eq!(result.unwrap(), "Panned to time 475000");
+
+        command.execute(&["-1000%"], &mut state).unwrap();
+        assert_eq!((state.time_start, state.time_range), (0, 100_000));
+
+        command.execute(&["+2000000"], &mut state).unwrap();
+        assert_eq!((state.time_start, state.time_range), (900_000, 100_000));
+        // Panning doesn't fill up the zoom history
+        assert!(state.zoom_history.is_empty());
+    }
+
+    #[test]
+    fn test_pan_usage() {
+        let command = create();
+        let mut state = get_state();
+        for args in [&[][..], &["1%", "2%"]] {
+            let result = command.execute(args, &mut state);
+            assert_eq!(result.unwrap_err(), "Usage: pan <±N%|±time>");
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "parsewarnings",
+        "Show the problems found while parsing the loaded file",
+        |_args, state: &mut AppState| {
+            if state.parse_report.is_empty() {
+                return Ok("No problems found while parsing the file".to_string());
+            }
+            state.show_parse_warnings = true;
+            state.parse_warnings_scroll = 0;
+            Ok(state.parse_report.summary())
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parsewarnings() {
+        let command = create();
+        let mut state = AppState::default();
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "No problems found while parsing the file".to_string()
+        );
+        assert!(!state.show_parse_warnings);
+
+        state.parse_report.unknown_ids.record(12, "1?");
+        state.parse_report.unknown_ids.record(14, "0?");
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "2 change(s) of undeclared identifiers".to_string()
+        );
+        assert!(state.show_parse_warnings);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+/// The displayed signal named by the first argument, or the selected signal without one.
+pub fn signal_arg(args: &[&str], state: &AppState) -> Result<String, String> {
+    match args.first() {
+        Some(signal) if state.displayed_signals.iter().any(|s| s == signal) => {
+            Ok(signal.to_string())
+        }
+        Some(signal) => Err(format!("Signal not displayed: {}", signal)),
+        None => state
+            .displayed_signals
+            .get(state.selected_signal)
+            .cloned()
+            .ok_or_else(|| "No signal selected".to_string()),
+    }
+}
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "pin",
+        "Pin a signal, or the selected one, above the scrolled signals",
+        |args, state: &mut AppState| {
+            let signal = signal_arg(args, state)?;
+            if state.pinned_signals.contains(&signal) {
+                return Err(format!("{} is already pinned", signal));
+            }
+
+            state.pinned_signals.push(signal.clone());
+            state.clamp_signal_selection();
+            Ok(format!("Pinned {}", signal))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.displayed_signals = ["top.clk", "top.data", "top.valid"]
+            .map(String::from)
+            .to_vec();
+        state
+    }
+
+    #[test]
+    fn test_pin_stacks_in_pin_order() {
+        let command = create();
+        let mut state = get_state();
+        state.selected_signal = 2;
+        assert_eq!(
+            command.execute(&[], &mut state).unwrap(),
+            "Pinned top.valid"
+        );
+        assert_eq!(
+            command.execute(&["top.clk"], &mut state).unwrap(),
+            "Pinned top.clk"
+        );
+        assert_eq!(state.pinned_signals, vec!["top.valid", "top.clk"]);
+
+        // The pinned signals are drawn first, then the separator, then the others
+        assert_eq!(
+            state.visible_signal_rows(8),
+            vec![(2, 0, 2), (0, 2, 2), (1, 5, 2)]
+        );
+        assert_eq!(state.signal_at_row(4), None);
+        assert_eq!(state.signal_at_row(5), Some(1));
+    }
+
+    #[test]
+    fn test_pin_errors() {
+        let command = create();
+        let mut state = get_state();
+        assert_eq!(
+            command.execute(&["top.nope"], &mut state).unwrap_err(),
+            "Signal not displayed: top.nope"
+        );
+        command.execute(&["top.data"], &mut state).unwrap();
+        assert_eq!(
+            command.execute(&["top.data"], &mut state).unwrap_err(),
+            "top.data is already pinned"
+        );
+
+        state.displayed_signals.clear();
+        assert_eq!(
+            command.execute(&[], &mut state).unwrap_err(),
+            "No signal selected"
+        );
+    }
+}
@@ -6,6 +6,13 @@ use std::rc::Rc;
 
 pub fn create() -> Rc<Box<dyn Command<AppState>>> {
     CommandBuilder::new("quit", "Quit digisurf", |_args, state: &mut AppState| {
+        // Placing markers and arranging signals takes a while, so don't throw it away by accident
+        if state.is_session_dirty() {
+            return Err(
+                "Unsaved session changes, :q! to quit anyway or :wq <path> to save and quit"
+                    .to_string(),
+            );
+        }
         state.exit = true;
         Ok("Exiting digisurf...".to_string())
     })
@@ -16,19 +23,20 @@ pub fn create() -> Rc<Box<dyn Command<AppState>>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::Marker;
 
     #[test]
     fn test_quit_command() {
         let command = create();
         let mut state = AppState::default();
 
-        assert_eq!(state.exit, false);
+        assert!(!state.exit);
 
         let result = command.execute(&[], &mut state);
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "Exiting digisurf...".to_string());
-        assert_eq!(state.exit, true);
+        assert!(state.exit);
     }
 
     #[test]
@@ -38,6 +46,31 @@ mod tests {
         let result = command.execute(&["unused_arg"], &mut state);
 
         assert!(result.is_ok());
-        assert_eq!(state.exit, true);
+        assert!(state.exit);
+    }
+
+    #[test]
+    fn test_quit_with_unsaved_session() {
+        let command = create();
+        let mut state = AppState::default();
+        state.saved_markers.push(Marker {
+            name: "irq".to_string(),
+            time: 42,
+            ..Default::default()
+        });
+
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Err(
+                "Unsaved session changes, :q! to quit anyway or :wq <path> to save and quit"
+                    .to_string()
+            )
+        );
+        assert!(!state.exit);
+
+        // Undoing the change makes the session clean again
+        state.saved_markers.clear();
+        assert!(command.execute(&[], &mut state).is_ok());
+        assert!(state.exit);
     }
 }
@@ -0,0 +1,219 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+    types::Region,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "region",
+        "Add, remove, or list named time regions",
+        move |args, state: &mut AppState| {
+            if args.is_empty() {
+                return Err(
+                    "Usage: region add <name> <start> <end>, region remove <name> or region list"
+                        .to_string(),
+                );
+            }
+
+            let subcommand = &args[0];
+            match &**subcommand {
+                "add" | "a" => add_subcommand().execute(&args[1..], state),
+                "remove" | "rm" => remove_subcommand().execute(&args[1..], state),
+                "list" | "ls" => list_subcommand().execute(&args[1..], state),
+                _ => Err("Unknown subcommand.".to_string()),
+            }
+        },
+    )
+    .build()
+}
+
+fn add_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "add",
+        "Add a named region between two times",
+        move |args, state: &mut AppState| {
+            if args.len() < 3 {
+                return Err("Usage: region add <name> <start> <end>".to_string());
+            }
+
+            let name = args[0];
+            if name.is_empty() {
+                return Err("Region name cannot be empty".to_string());
+            }
+
+            let mut times = Vec::new();
+            for arg in &args[1..3] {
+                let time = arg
+                    .parse::<u64>()
+                    .map_err(|_| "Invalid time format".to_string())?;
+                if time > state.waveform_data.max_time {
+                    return Err(format!(
+                        "Time out of range (0-{})",
+                        state.waveform_data.max_time
+                    ));
+                }
+                times.push(time);
+            }
+            let (start, end) = (times[0].min(times[1]), times[0].max(times[1]));
+            if start == end {
+                return Err("Region start and end must differ".to_string());
+            }
+
+            if state.regions.iter().any(|r| r.name == name) {
+                return Err(format!("Region '{}' already exists", name));
+            }
+
+            state
+                .regions
+                .push(Region::new(start, end, name.to_string()));
+            Ok(format!("Added region '{}' from {} to {}", name, start, end))
+        },
+    )
+    .alias("a")
+    .build()
+}
+
+fn remove_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "remove",
+        "Remove a region by name",
+        move |args, state: &mut AppState| {
+            if args.is_empty() {
+                return Err("Usage: region remove <name>".to_string());
+            }
+
+            let name = &args[0];
+            if let Some(index) = state.regions.iter().position(|r| &r.name == name) {
+                let region = state.regions.remove(index);
+                Ok(format!(
+                    "Removed region '{}' from {} to {}",
+                    region.name, region.start, region.end
+                ))
+            } else {
+                Err(format!("No region found with name '{}'", name))
+            }
+        },
+    )
+    .alias("rm")
+    .build()
+}
+
+fn list_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "list",
+        "List regions sorted by start time",
+        move |_args, state: &mut AppState| {
+            if state.regions.is_empty() {
+                return Ok("No regions".to_string());
+            }
+
+            let mut regions: Vec<&Region> = state.regions.iter().collect();
+            regions.sort_by_key(|r| (r.start, r.end));
+            let list: Vec<String> = regions
+                .iter()
+                .map(|r| format!("{}@{}-{}", r.name, r.start, r.end))
+                .collect();
+            Ok(list.join(", "))
+        },
+    )
+    .alias("ls")
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state
+    }
+
+    #[test]
+    fn test_region_add_success() {
+        let command = create();
+        let mut state = get_state();
+
+        // The times may be given in either order
+        let result = command.execute(&["add", "burst", "400", "100"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Added region 'burst' from 100 to 400".to_string()
+        );
+        assert_eq!(
+            state.regions,
+            vec![Region::new(100, 400, "burst".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_region_add_errors() {
+        let command = create();
+        let mut state = get_state();
+        state.regions.push(Region::new(0, 10, "reset".to_string()));
+
+        let cases: [(&[&str], &str); 6] = [
+            (
+                &["add", "burst", "100"],
+                "Usage: region add <name> <start> <end>",
+            ),
+            (&["add", "burst", "x", "100"], "Invalid time format"),
+            (
+                &["add", "burst", "100", "2000"],
+                "Time out of range (0-1000)",
+            ),
+            (
+                &["add", "burst", "100", "100"],
+                "Region start and end must differ",
+            ),
+            (&["add", "", "1", "2"], "Region name cannot be empty"),
+            (
+                &["add", "reset", "20", "30"],
+                "Region 'reset' already exists",
+            ),
+        ];
+        for (args, error) in cases {
+            let result = command.execute(args, &mut state);
+            assert_eq!(result.unwrap_err(), error.to_string());
+        }
+        assert_eq!(state.regions.len(), 1);
+    }
+
+    #[test]
+    fn test_region_remove() {
+        let command = create();
+        let mut state = get_state();
+        state.regions.push(Region::new(0, 10, "reset".to_string()));
+
+        let result = command.execute(&["remove", "burst"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "No region found with name 'burst'".to_string()
+        );
+
+        let result = command.execute(&["rm", "reset"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Removed region 'reset' from 0 to 10".to_string()
+        );
+        assert!(state.regions.is_empty());
+    }
+
+    #[test]
+    fn test_region_list_sorted_by_start() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["list"], &mut state);
+        assert_eq!(result.unwrap(), "No regions".to_string());
+
+        state.regions = vec![
+            Region::new(500, 600, "burst".to_string()),
+            Region::new(0, 50, "reset".to_string()),
+        ];
+        let result = command.execute(&["ls"], &mut state);
+        assert_eq!(result.unwrap(), "reset@0-50, burst@500-600".to_string());
+    }
+}
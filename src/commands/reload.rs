@@ -0,0 +1,43 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "reload",
+        "Read the loaded file again, keeping the view, markers and selection",
+        |_args, state: &mut AppState| {
+            let Some(path) = &state.file_path else {
+                return Err("No file loaded".to_string());
+            };
+            // The app reloads it once the command has run, as it owns loading files
+            let message = format!("Reloading {}", path.display());
+            state.reload_requested = true;
+            Ok(message)
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_reload_requires_a_file() {
+        let command = create();
+        let mut state = AppState::default();
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Err("No file loaded".to_string()));
+        assert!(!state.reload_requested);
+
+        state.file_path = Some(PathBuf::from("sim.vcd"));
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Ok("Reloading sim.vcd".to_string()));
+        assert!(state.reload_requested);
+    }
+}
@@ -0,0 +1,183 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+    types::SamplePopup,
+};
+use std::{fs, rc::Rc};
+
+const USAGE: &str = "Usage: sample [time] [> path]";
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "sample",
+        "List the value of every displayed signal at a time, or write them to a file",
+        |args, state: &mut AppState| {
+            // The output file comes last, after `>` or `--out`
+            let (args, out) = match args {
+                [rest @ .., ">" | "--out", path] => (rest, Some(*path)),
+                _ => (args, None),
+            };
+            let time = match args {
+                [] => state
+                    .primary_marker
+                    .ok_or_else(|| format!("Primary marker not set. {}", USAGE))?,
+                [time] => time
+                    .parse::<u64>()
+                    .map_err(|_| format!("Invalid time: {}", time))?,
+                _ => return Err(USAGE.to_string()),
+            };
+            if state.displayed_signals.is_empty() {
+                return Err("No signals displayed".to_string());
+            }
+
+            let lines = sample_lines(state, time);
+            match out {
+                Some(path) => {
+                    let mut content = lines.join("\n");
+                    content.push('\n');
+                    fs::write(path, content)
+                        .map_err(|e| format!("Couldn't write {}: {}", path, e))?;
+                    Ok(format!(
+                        "Wrote {} value(s) at time {} to {}",
+                        lines.len(),
+                        time,
+                        path
+                    ))
+                }
+                None => {
+                    let count = lines.len();
+                    state.sample_popup = Some(SamplePopup {
+                        time,
+                        lines,
+                        scroll: 0,
+                    });
+                    Ok(format!("Sampled {} signal(s) at time {}", count, time))
+                }
+            }
+        },
+    )
+    .build()
+}
+
+/// `name : value` lines for the displayed signals at `time`, in display order, with the values
+/// lined up. Signals without a value yet show `-`.
+pub fn sample_lines(state: &AppState, time: u64) -> Vec<String> {
+    let width = state
+        .displayed_signals
+        .iter()
+        .map(|signal| signal.chars().count())
+        .max()
+        .unwrap_or(0);
+    state
+        .displayed_signals
+        .iter()
+        .map(|signal| {
+            let value = state.get_value_at_marker(signal, time).map_or_else(
+                || "-".to_string(),
+                |value| state.format_value(signal, &value),
+            );
+            format!("{:<width$} : {}", signal, value, width = width)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+    use tempfile::TempDir;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.values.insert(
+            "top.clk".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (10, WaveValue::Binary(Value::V1)),
+            ],
+        );
+        state.waveform_data.values.insert(
+            "top.cpu.data".to_string(),
+            vec![(20, WaveValue::Bus("a".into()))],
+        );
+        state.waveform_data.signals = vec!["top.clk".to_string(), "top.cpu.data".to_string()];
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state.waveform_data.max_time = 30;
+        state
+    }
+
+    #[test]
+    fn test_sample_lines_are_aligned() {
+        let state = get_state();
+        assert_eq!(
+            sample_lines(&state, 10),
+            vec!["top.clk      : 1", "top.cpu.data : -"]
+        );
+        assert_eq!(
+            sample_lines(&state, 25),
+            vec!["top.clk      : 1", "top.cpu.data : 10"]
+        );
+    }
+
+    #[test]
+    fn test_sample_at_primary_marker() {
+        let command = create();
+        let mut state = get_state();
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Err("Primary marker not set. Usage: sample [time] [> path]".to_string())
+        );
+
+        state.primary_marker = Some(5);
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Ok("Sampled 2 signal(s) at time 5".to_string())
+        );
+        let popup = state.sample_popup.unwrap();
+        assert_eq!(popup.time, 5);
+        assert_eq!(popup.lines[0], "top.clk      : 0");
+    }
+
+    #[test]
+    fn test_sample_to_file() {
+        let dir = TempDir::new().unwrap();
+        let command = create();
+        let mut state = get_state();
+
+        let path = dir.path().join("sample.txt");
+        let path_arg = path.to_str().unwrap();
+        assert_eq!(
+            command.execute(&["25", ">", path_arg], &mut state),
+            Ok(format!("Wrote 2 value(s) at time 25 to {}", path_arg))
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "top.clk      : 1\ntop.cpu.data : 10\n"
+        );
+        assert!(state.sample_popup.is_none());
+
+        // The primary marker works with --out too
+        state.primary_marker = Some(0);
+        command.execute(&["--out", path_arg], &mut state).unwrap();
+        assert!(fs::read_to_string(&path)
+            .unwrap()
+            .starts_with("top.clk      : 0\n"));
+    }
+
+    #[test]
+    fn test_sample_errors() {
+        let command = create();
+        let mut state = get_state();
+        assert_eq!(
+            command.execute(&["soon"], &mut state),
+            Err("Invalid time: soon".to_string())
+        );
+        assert!(command.execute(&["1", "2"], &mut state).is_err());
+
+        state.displayed_signals.clear();
+        assert_eq!(
+            command.execute(&["1"], &mut state),
+            Err("No signals displayed".to_string())
+        );
+    }
+}
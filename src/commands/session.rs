@@ -0,0 +1,155 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    session::{self, Session},
+    state::AppState,
+};
+use std::{path::PathBuf, rc::Rc};
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "session",
+        "Save or load the displayed signals, their colors, saved markers and regions",
+        move |args, state: &mut AppState| match args.first() {
+            Some(&"save") => save_subcommand().execute(&args[1..], state),
+            Some(&"load") => load_subcommand().execute(&args[1..], state),
+            Some(_) => Err("Unknown subcommand.".to_string()),
+            None => Err("Usage: session save [path] or session load [path]".to_string()),
+        },
+    )
+    .build()
+}
+
+// The path given to a subcommand, or the last one used
+fn session_path(args: &[&str], state: &AppState, usage: &str) -> Result<PathBuf, String> {
+    match args {
+        [] => state
+            .session_path
+            .clone()
+            .ok_or_else(|| format!("No session path, so give one: {}", usage)),
+        [path] => Ok(PathBuf::from(path)),
+        _ => Err(format!("Usage: {}", usage)),
+    }
+}
+
+/// Saves the session to the path in `args` or the last one used, which the next save without a
+/// path goes to.
+pub(super) fn save_session(
+    args: &[&str],
+    state: &mut AppState,
+    usage: &str,
+) -> Result<String, String> {
+    let path = session_path(args, state, usage)?;
+    session::save_session(&path, &Session::capture(state))
+        .map_err(|e| format!("Couldn't save session to {}: {}", path.display(), e))?;
+    state.mark_session_saved();
+    let message = format!("Saved session to {}", path.display());
+    state.session_path = Some(path);
+    Ok(message)
+}
+
+fn save_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "save",
+        "Save the session",
+        move |args, state: &mut AppState| save_session(args, state, "session save [path]"),
+    )
+    .build()
+}
+
+fn load_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "load",
+        "Load a saved session",
+        move |args, state: &mut AppState| {
+            let path = session_path(args, state, "session load [path]")?;
+            if state.waveform_data.signals.is_empty() {
+                return Err("No waveform loaded".to_string());
+            }
+            let session = session::load_session(&path)
+                .map_err(|e| format!("Couldn't load session from {}: {}", path.display(), e))?;
+
+            session.restore(state);
+            state.mark_session_saved();
+            let message = format!("Loaded session from {}", path.display());
+            state.session_path = Some(path);
+            Ok(message)
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parsers::types::{Value, WaveValue},
+        types::Marker,
+    };
+    use tempfile::TempDir;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        for signal in ["top.clk", "top.data"] {
+            state.waveform_data.signals.push(signal.to_string());
+            state
+                .waveform_data
+                .values
+                .insert(signal.to_string(), vec![(0, WaveValue::Binary(Value::V0))]);
+        }
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state
+    }
+
+    #[test]
+    fn test_session_save_and_load() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("session.toml");
+        let path_arg = path.to_str().unwrap();
+        let command = create();
+        let mut state = get_state();
+        state.saved_markers.push(Marker {
+            name: "irq".to_string(),
+            time: 42,
+            color: ratatui::style::Color::Yellow,
+        });
+        assert!(state.is_session_dirty());
+
+        assert_eq!(
+            command.execute(&["save", path_arg], &mut state),
+            Ok(format!("Saved session to {}", path.display()))
+        );
+        assert!(!state.is_session_dirty());
+        assert_eq!(state.session_path, Some(path.clone()));
+
+        // Loading without a path uses the one last saved to
+        state.displayed_signals.reverse();
+        state.saved_markers.clear();
+        assert_eq!(
+            command.execute(&["load"], &mut state),
+            Ok(format!("Loaded session from {}", path.display()))
+        );
+        assert_eq!(state.displayed_signals, vec!["top.clk", "top.data"]);
+        assert_eq!(state.saved_markers.len(), 1);
+        assert!(!state.is_session_dirty());
+    }
+
+    #[test]
+    fn test_session_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        assert!(command.execute(&[], &mut state).is_err());
+        assert!(command.execute(&["merge"], &mut state).is_err());
+        assert_eq!(
+            command.execute(&["save"], &mut state),
+            Err("No session path, so give one: session save [path]".to_string())
+        );
+        assert!(command.execute(&["save", "a", "b"], &mut state).is_err());
+
+        let dir = TempDir::new().unwrap();
+        let missing = dir.path().join("missing.toml");
+        assert!(command
+            .execute(&["load", missing.to_str().unwrap()], &mut state)
+            .is_err());
+    }
+}
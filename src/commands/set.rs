@@ -0,0 +1,122 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    config::WAVEFORM_HEIGHT_RANGE,
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "set",
+        "Change a setting while running",
+        |args, state: &mut AppState| {
+            if args.len() < 2 {
+                return Err("Usage: set <setting> <value>".to_string());
+            }
+
+            // Values written in config file syntax may contain spaces, e.g. { F = 5 }
+            let waveform_height = state.config.ui.waveform_height;
+            let key = state.config.set(args[0], &args[1..].join(" "))?;
+            if key == "ui.signal_list_width" {
+                state.set_signal_list_width(state.config.ui.signal_list_width);
+            }
+            if !WAVEFORM_HEIGHT_RANGE.contains(&state.config.ui.waveform_height) {
+                state.config.ui.waveform_height = waveform_height;
+                return Err(format!(
+                    "ui.waveform_height must be {} or {}",
+                    WAVEFORM_HEIGHT_RANGE.start(),
+                    WAVEFORM_HEIGHT_RANGE.end()
+                ));
+            }
+
+            let (key, value) = state.config.get(&key)?;
+            Ok(format!("{} set to {}", key, value))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+
+    #[test]
+    fn test_set_usage_is_err() {
+        let command = create();
+        let mut state = AppState::default();
+        let result = command.execute(&["signal_list_width"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Usage: set <setting> <value>".to_string()
+        );
+    }
+
+    #[test]
+    fn test_set_unknown_setting_is_err() {
+        let command = create();
+        let mut state = AppState::default();
+        let result = command.execute(&["nope", "1"], &mut state);
+        assert_eq!(result.unwrap_err(), "Unknown setting: nope".to_string());
+    }
+
+    #[test]
+    fn test_set_signal_list_width() {
+        let command = create();
+        let mut state = AppState::default();
+        let result = command.execute(&["signal_list_width", "30"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "ui.signal_list_width set to 30".to_string()
+        );
+        assert_eq!(state.config.ui.signal_list_width, 30);
+
+        let result = command.execute(&["ui.signal_list_width", "200"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "ui.signal_list_width set to 80".to_string()
+        );
+
+        let result = command.execute(&["signal_list_width", "wide"], &mut state);
+        assert!(result
+            .unwrap_err()
+            .starts_with("Invalid value for ui.signal_list_width"));
+        assert_eq!(state.config.ui.signal_list_width, 80);
+    }
+
+    #[test]
+    fn test_set_waveform_height() {
+        let command = create();
+        let mut state = AppState::default();
+
+        let result = command.execute(&["waveform_height", "1"], &mut state);
+        assert_eq!(result.unwrap(), "ui.waveform_height set to 1".to_string());
+        assert_eq!(state.signal_height("top.clk"), 1);
+
+        let result = command.execute(&["waveform_height", "4"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "ui.waveform_height must be 1 or 2".to_string()
+        );
+        assert_eq!(state.config.ui.waveform_height, 1);
+    }
+
+    #[test]
+    fn test_set_keybinding() {
+        let command = create();
+        let mut state = AppState::default();
+        let result = command.execute(&["keybindings.zoom_in", "="], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "keybindings.zoom_in set to { Char = \"=\" }".to_string()
+        );
+        assert_eq!(state.config.keybindings.zoom_in, KeyCode::Char('='));
+
+        let result = command.execute(
+            &["keybindings.zoom_full", "{", "F", "=", "5", "}"],
+            &mut state,
+        );
+        assert!(result.is_ok());
+        assert_eq!(state.config.keybindings.zoom_full, KeyCode::F(5));
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use glob::Pattern;
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "show",
+        "Show signals matching a glob pattern",
+        |args, state: &mut AppState| {
+            if args.is_empty() {
+                return Err("Usage: show <pattern>".to_string());
+            }
+
+            let pattern = Pattern::new(args[0])
+                .map_err(|e| format!("Invalid pattern '{}': {}", args[0], e))?;
+
+            let matching: Vec<String> = state
+                .waveform_data
+                .signals
+                .iter()
+                .filter(|signal| pattern.matches(signal))
+                .cloned()
+                .collect();
+
+            if matching.is_empty() {
+                return Err(format!("No signals match '{}'", args[0]));
+            }
+
+            // Append newly shown signals to the end of the list in their original order
+            let mut shown = 0;
+            for signal in matching {
+                if !state.displayed_signals.contains(&signal) {
+                    state.displayed_signals.push(signal);
+                    shown += 1;
+                }
+            }
+
+            if shown == 0 {
+                return Ok(format!(
+                    "All signals matching '{}' are already displayed",
+                    args[0]
+                ));
+            }
+
+            Ok(format!("Showed {} signal(s)", shown))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec![
+            "top.clk".to_string(),
+            "top.reset".to_string(),
+            "top.cpu.data".to_string(),
+            "top.cpu.addr".to_string(),
+        ];
+        state.displayed_signals = vec!["top.reset".to_string()];
+        state
+    }
+
+    #[test]
+    fn test_show_empty_args_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap_err(), "Usage: show <pattern>".to_string());
+    }
+
+    #[test]
+    fn test_show_pattern_matching_nothing_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["bottom.*"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "No signals match 'bottom.*'".to_string()
+        );
+        assert_eq!(state.displayed_signals.len(), 1);
+    }
+
+    #[test]
+    fn test_show_glob_pattern_appends_in_original_order() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["top.cpu.*"], &mut state);
+        assert_eq!(result.unwrap(), "Showed 2 signal(s)".to_string());
+        assert_eq!(
+            state.displayed_signals,
+            vec!["top.reset", "top.cpu.data", "top.cpu.addr"]
+        );
+    }
+
+    #[test]
+    fn test_show_already_displayed_is_noop() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["top.reset"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "All signals matching 'top.reset' are already displayed".to_string()
+        );
+        assert_eq!(state.displayed_signals, vec!["top.reset"]);
+    }
+}
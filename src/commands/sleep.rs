@@ -0,0 +1,50 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::{rc::Rc, time::Duration};
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "sleep",
+        "Wait before running the next command given on the command line",
+        |args, state: &mut AppState| {
+            let [millis] = args else {
+                return Err("Usage: sleep <ms>".to_string());
+            };
+            let millis: u64 = millis
+                .parse()
+                .map_err(|_| format!("Invalid milliseconds: {}", millis))?;
+            // The app holds its queued commands, as it owns the event loop
+            state.sleep_requested = Some(Duration::from_millis(millis));
+            Ok(format!("Sleeping for {} ms", millis))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sleep_requests_a_duration() {
+        let command = create();
+        let mut state = AppState::default();
+
+        assert_eq!(
+            command.execute(&["250"], &mut state),
+            Ok("Sleeping for 250 ms".to_string())
+        );
+        assert_eq!(state.sleep_requested, Some(Duration::from_millis(250)));
+
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Err("Usage: sleep <ms>".to_string())
+        );
+        assert_eq!(
+            command.execute(&["1s"], &mut state),
+            Err("Invalid milliseconds: 1s".to_string())
+        );
+    }
+}
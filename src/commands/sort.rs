@@ -0,0 +1,90 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "sort",
+        "Sort the displayed signals by name or by declaration order",
+        |args, state: &mut AppState| {
+            let selected_name = state.displayed_signals.get(state.selected_signal).cloned();
+
+            match args {
+                ["name"] => state.displayed_signals.sort(),
+                ["declaration"] => {
+                    let declared = &state.waveform_data.signals;
+                    state.displayed_signals.sort_by_key(|signal| {
+                        declared
+                            .iter()
+                            .position(|s| s == signal)
+                            .unwrap_or(usize::MAX)
+                    });
+                }
+                _ => return Err("Usage: sort <name|declaration>".to_string()),
+            }
+            state.select_signal_named(selected_name.as_deref());
+
+            Ok(format!("Sorted signals by {}", args[0]))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec![
+            "top.reset".to_string(),
+            "top.clk".to_string(),
+            "top.data".to_string(),
+        ];
+        state.displayed_signals = vec![
+            "top.data".to_string(),
+            "top.reset".to_string(),
+            "top.clk".to_string(),
+        ];
+        state
+    }
+
+    #[test]
+    fn test_sort_by_name() {
+        let command = create();
+        let mut state = get_state();
+        state.selected_signal = 1;
+
+        let result = command.execute(&["name"], &mut state);
+        assert_eq!(result.unwrap(), "Sorted signals by name");
+        assert_eq!(
+            state.displayed_signals,
+            vec!["top.clk", "top.data", "top.reset"]
+        );
+        assert_eq!(state.selected_signal, 2);
+    }
+
+    #[test]
+    fn test_sort_by_declaration() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["declaration"], &mut state);
+        assert_eq!(result.unwrap(), "Sorted signals by declaration");
+        assert_eq!(state.displayed_signals, state.waveform_data.signals);
+        assert_eq!(state.selected_signal, 2);
+    }
+
+    #[test]
+    fn test_sort_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        for args in [&[][..], &["size"], &["name", "declaration"]] {
+            let result = command.execute(args, &mut state);
+            assert_eq!(result.unwrap_err(), "Usage: sort <name|declaration>");
+        }
+    }
+}
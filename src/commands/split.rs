@@ -0,0 +1,148 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "split",
+        "Show each bit of a displayed bus signal as a row below it",
+        |args, state: &mut AppState| {
+            let [signal] = args else {
+                return Err("Usage: split <signal>".to_string());
+            };
+            let width = state.split_signal(signal)?;
+            Ok(format!("Split {} into {} bit(s)", signal, width))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        parsers::types::{Value, WaveValue},
+        session::Session,
+    };
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec!["top.clk".to_string(), "top.status".to_string()];
+        state.waveform_data.values.insert(
+            "top.clk".to_string(),
+            vec![(0, WaveValue::Binary(Value::V0))],
+        );
+        state.waveform_data.values.insert(
+            "top.status".to_string(),
+            vec![
+                (0, WaveValue::Bus("x".into())),
+                (10, WaveValue::Bus("6".into())),
+            ],
+        );
+        state.waveform_data.widths.insert("top.clk".to_string(), 1);
+        state
+            .waveform_data
+            .widths
+            .insert("top.status".to_string(), 3);
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state
+    }
+
+    #[test]
+    fn test_split() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["top.status"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Split top.status into 3 bit(s)".to_string()
+        );
+        assert_eq!(
+            state.displayed_signals,
+            vec![
+                "top.clk",
+                "top.status",
+                "top.status[2]",
+                "top.status[1]",
+                "top.status[0]"
+            ]
+        );
+        assert_eq!(
+            state.waveform_data.values["top.status[0]"],
+            vec![
+                (0, WaveValue::Binary(Value::VX)),
+                (10, WaveValue::Binary(Value::V0))
+            ]
+        );
+        assert!(state.is_derived_signal("top.status[2]"));
+        assert!(!state.is_derived_signal("top.status"));
+
+        let result = command.execute(&["top.status"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Signal 'top.status' is already split".to_string()
+        );
+    }
+
+    #[test]
+    fn test_split_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        let result = command.execute(&["top.clk"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Signal 'top.clk' is not a bus".to_string()
+        );
+
+        state.displayed_signals.pop();
+        let result = command.execute(&["top.status"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Signal 'top.status' is not displayed".to_string()
+        );
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap_err(), "Usage: split <signal>".to_string());
+    }
+
+    #[test]
+    fn test_split_bits_are_not_saved_in_the_session() {
+        let command = create();
+        let mut state = get_state();
+        command.execute(&["top.status"], &mut state).unwrap();
+        state
+            .signal_colors
+            .insert("top.status[1]".to_string(), ratatui::style::Color::Red);
+
+        let session = Session::capture(&state);
+        assert_eq!(session.displayed_signals, vec!["top.clk", "top.status"]);
+        assert!(session.signal_colors.is_empty());
+    }
+
+    #[test]
+    fn test_split_bits_follow_appended_values() {
+        let command = create();
+        let mut state = get_state();
+        command.execute(&["top.status"], &mut state).unwrap();
+
+        let mut appended = crate::parsers::types::WaveformData::default();
+        appended.values.insert(
+            "top.status".to_string(),
+            vec![(20, WaveValue::Bus("1".into()))],
+        );
+        state.append_waveform_data(appended);
+
+        assert_eq!(
+            state.waveform_data.values["top.status[0]"],
+            vec![
+                (0, WaveValue::Binary(Value::VX)),
+                (10, WaveValue::Binary(Value::V0)),
+                (20, WaveValue::Binary(Value::V1))
+            ]
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "split-view",
+        "Toggle splitting the waveform into two panes over different time windows",
+        |args, state: &mut AppState| match args {
+            [] if state.split_view.is_none() => {
+                state.open_split_view();
+                Ok("Split the waveform into two panes".to_string())
+            }
+            [] | ["off"] => {
+                if state.split_view.take().is_none() {
+                    return Err("The waveform isn't split".to_string());
+                }
+                Ok("Closed the second pane".to_string())
+            }
+            _ => Err("Usage: split-view [off]".to_string()),
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_view_toggle_and_off() {
+        let command = create();
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.set_window(100, 200);
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Split the waveform into two panes".to_string()
+        );
+        let split_view = state.split_view.as_ref().unwrap();
+        assert_eq!((split_view.time_start, split_view.time_range), (100, 200));
+        assert!(!split_view.bottom_focused);
+
+        // Collapsing keeps the window of the focused pane
+        state.switch_pane_focus();
+        state.set_window(600, 50);
+        let result = command.execute(&["off"], &mut state);
+        assert_eq!(result.unwrap(), "Closed the second pane".to_string());
+        assert_eq!(state.split_view, None);
+        assert_eq!((state.time_start, state.time_range), (600, 50));
+
+        let result = command.execute(&["off"], &mut state);
+        assert_eq!(result.unwrap_err(), "The waveform isn't split".to_string());
+        command.execute(&[], &mut state).unwrap();
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap(), "Closed the second pane".to_string());
+
+        let result = command.execute(&["on", "off"], &mut state);
+        assert_eq!(result.unwrap_err(), "Usage: split-view [off]".to_string());
+    }
+}
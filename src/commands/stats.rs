@@ -0,0 +1,80 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "stats",
+        "Show statistics of the waveform or of one signal",
+        |args, state: &mut AppState| match args {
+            [] => {
+                state.show_stats = true;
+                state.stats_scroll = 0;
+                Ok("stats".to_string())
+            }
+            [signal] => {
+                let stats = state.waveform_stats();
+                stats
+                    .signals
+                    .get(*signal)
+                    .map(|signal_stats| signal_stats.describe(signal, stats.max_time))
+                    .ok_or_else(|| format!("Signal not found: {}", signal))
+            }
+            _ => Err("Usage: stats [signal]".to_string()),
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+
+    fn test_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec!["top.clk".to_string()];
+        state.waveform_data.values.insert(
+            "top.clk".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (5, WaveValue::Binary(Value::V1)),
+                (10, WaveValue::Binary(Value::V0)),
+            ],
+        );
+        state.waveform_data.max_time = 20;
+        state
+    }
+
+    #[test]
+    fn test_stats_of_signal() {
+        let command = create();
+        let mut state = test_state();
+
+        assert_eq!(
+            command.execute(&["top.clk"], &mut state),
+            Ok(
+                "top.clk: 2 change(s), first at 5, last at 10, toggle rate 0.1000 per time unit"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            command.execute(&["top.missing"], &mut state),
+            Err("Signal not found: top.missing".to_string())
+        );
+        assert!(!state.show_stats);
+    }
+
+    #[test]
+    fn test_stats_opens_summary() {
+        let command = create();
+        let mut state = test_state();
+        state.stats_scroll = 3;
+
+        assert_eq!(command.execute(&[], &mut state), Ok("stats".to_string()));
+        assert!(state.show_stats);
+        assert_eq!(state.stats_scroll, 0);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "timezero",
+        "Show and type times relative to a time or marker, or back to absolute times with off",
+        |args, state: &mut AppState| {
+            let time = match args {
+                [] => state.marker_time("m1")?,
+                ["off"] => {
+                    state.time_zero = None;
+                    return Ok("Showing absolute times".to_string());
+                }
+                [reference] => match reference.parse::<u64>() {
+                    Ok(time) if time <= state.waveform_data.max_time => time,
+                    Ok(_) => {
+                        return Err(format!(
+                            "Time out of range (0-{})",
+                            state.waveform_data.max_time
+                        ))
+                    }
+                    Err(_) => state.marker_time(reference)?,
+                },
+                _ => return Err("Usage: timezero [time|m1|marker-name|off]".to_string()),
+            };
+
+            state.time_zero = Some(time);
+            Ok(format!("Showing times relative to time {}", time))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Marker;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.saved_markers = vec![Marker::new(250, "irq".to_string())];
+        state
+    }
+
+    #[test]
+    fn test_timezero_at_time_and_markers() {
+        let command = create();
+        let mut state = get_state();
+
+        // Reference times are absolute, whatever the current reference
+        assert_eq!(
+            command.execute(&["400"], &mut state),
+            Ok("Showing times relative to time 400".to_string())
+        );
+        assert_eq!(
+            command.execute(&["irq"], &mut state),
+            Ok("Showing times relative to time 250".to_string())
+        );
+        assert_eq!(state.time_zero, Some(250));
+
+        state.primary_marker = Some(700);
+        command.execute(&[], &mut state).unwrap();
+        assert_eq!(state.time_zero, Some(700));
+
+        assert_eq!(
+            command.execute(&["off"], &mut state),
+            Ok("Showing absolute times".to_string())
+        );
+        assert_eq!(state.time_zero, None);
+    }
+
+    #[test]
+    fn test_timezero_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        assert_eq!(
+            command.execute(&["m1"], &mut state),
+            Err("Primary marker not set".to_string())
+        );
+        assert_eq!(
+            command.execute(&["2000"], &mut state),
+            Err("Time out of range (0-1000)".to_string())
+        );
+        assert_eq!(
+            command.execute(&["nope"], &mut state),
+            Err("No marker found with name 'nope'".to_string())
+        );
+        assert_eq!(state.time_zero, None);
+    }
+}
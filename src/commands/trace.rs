@@ -0,0 +1,134 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+    types::Trace,
+};
+use std::rc::Rc;
+
+const NO_TRACE: &str = "No trace. Start one with trace <signal>";
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "trace",
+        "Record a signal's value wherever the primary marker moves, or show, clear or stop the trace",
+        |args, state: &mut AppState| match args {
+            ["show"] => {
+                let trace = state.trace.as_ref().ok_or(NO_TRACE)?;
+                let message = format!(
+                    "Showing {} value(s) of {}",
+                    trace.entries.len(),
+                    trace.signal
+                );
+                state.show_trace = true;
+                state.trace_scroll = 0;
+                Ok(message)
+            }
+            ["clear"] => {
+                let trace = state.trace.as_mut().ok_or(NO_TRACE)?;
+                trace.entries.clear();
+                Ok(format!("Cleared the trace of {}", trace.signal))
+            }
+            ["off"] => {
+                let trace = state.trace.as_mut().ok_or(NO_TRACE)?;
+                trace.recording = false;
+                Ok(format!("Stopped tracing {}", trace.signal))
+            }
+            [signal] => {
+                if !state.waveform_data.values.contains_key(*signal) {
+                    return Err(format!("Signal not found: {}", signal));
+                }
+                state.trace = Some(Trace {
+                    signal: signal.to_string(),
+                    entries: Vec::new(),
+                    recording: true,
+                });
+                // Start from the value at the marker, if there is one
+                if let Some(marker) = state.primary_marker {
+                    state.move_primary_marker(marker);
+                }
+                Ok(format!("Tracing {}", signal))
+            }
+            _ => Err("Usage: trace <signal>, trace show, trace clear or trace off".to_string()),
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::WaveValue;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.values.insert(
+            "top.state".to_string(),
+            vec![
+                (0, WaveValue::Bus("0".into())),
+                (10, WaveValue::Bus("1".into())),
+                (20, WaveValue::Bus("10".into())),
+            ],
+        );
+        state.waveform_data.max_time = 30;
+        state
+    }
+
+    #[test]
+    fn test_trace_records_marker_moves() {
+        let command = create();
+        let mut state = get_state();
+        state.primary_marker = Some(5);
+        assert_eq!(
+            command.execute(&["top.state"], &mut state),
+            Ok("Tracing top.state".to_string())
+        );
+        for time in [12, 25, 15] {
+            state.move_primary_marker(time);
+        }
+
+        let trace = state.trace.as_ref().unwrap();
+        let entries: Vec<(u64, &str)> = trace
+            .entries
+            .iter()
+            .map(|entry| (entry.time, entry.value.as_str()))
+            .collect();
+        assert_eq!(entries, [(5, "0"), (12, "1"), (25, "16"), (15, "1")]);
+
+        assert_eq!(
+            command.execute(&["show"], &mut state),
+            Ok("Showing 4 value(s) of top.state".to_string())
+        );
+        assert!(state.show_trace);
+
+        assert_eq!(
+            command.execute(&["off"], &mut state),
+            Ok("Stopped tracing top.state".to_string())
+        );
+        state.move_primary_marker(0);
+        assert_eq!(state.trace.as_ref().unwrap().entries.len(), 4);
+
+        assert_eq!(
+            command.execute(&["clear"], &mut state),
+            Ok("Cleared the trace of top.state".to_string())
+        );
+        assert!(state.trace.as_ref().unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn test_trace_errors() {
+        let command = create();
+        let mut state = get_state();
+        for args in [["show"], ["clear"], ["off"]] {
+            assert_eq!(
+                command.execute(&args, &mut state),
+                Err(NO_TRACE.to_string())
+            );
+        }
+        assert_eq!(
+            command.execute(&["top.missing"], &mut state),
+            Err("Signal not found: top.missing".to_string())
+        );
+        assert!(command.execute(&[], &mut state).is_err());
+        assert!(state.trace.is_none());
+    }
+}
@@ -0,0 +1,62 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "track",
+        "Show a signal's values at the markers in the command bar",
+        |args, state: &mut AppState| match args {
+            ["off"] => {
+                if state.tracked_signal.take().is_none() {
+                    return Err("No signal tracked".to_string());
+                }
+                Ok("Stopped tracking".to_string())
+            }
+            [signal] => {
+                if !state.waveform_data.values.contains_key(*signal) {
+                    return Err(format!("Signal not found: {}", signal));
+                }
+                state.tracked_signal = Some(signal.to_string());
+                Ok(format!("Tracking {}", signal))
+            }
+            _ => Err("Usage: track <signal> | track off".to_string()),
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+
+    #[test]
+    fn test_track_and_off() {
+        let command = create();
+        let mut state = AppState::default();
+        state.waveform_data.values.insert(
+            "top.clk".to_string(),
+            vec![(0, WaveValue::Binary(Value::V0))],
+        );
+
+        let result = command.execute(&["top.clk"], &mut state);
+        assert_eq!(result, Ok("Tracking top.clk".to_string()));
+        assert_eq!(state.tracked_signal.as_deref(), Some("top.clk"));
+
+        let result = command.execute(&["top.gone"], &mut state);
+        assert_eq!(result, Err("Signal not found: top.gone".to_string()));
+        assert_eq!(state.tracked_signal.as_deref(), Some("top.clk"));
+
+        let result = command.execute(&["off"], &mut state);
+        assert_eq!(result, Ok("Stopped tracking".to_string()));
+        assert_eq!(state.tracked_signal, None);
+
+        let result = command.execute(&["off"], &mut state);
+        assert_eq!(result, Err("No signal tracked".to_string()));
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Err("Usage: track <signal> | track off".to_string()));
+    }
+}
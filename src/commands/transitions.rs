@@ -0,0 +1,148 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+    types::{Transition, TransitionsPopup},
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "transitions",
+        "List the transitions of a signal in the visible window",
+        |args, state: &mut AppState| {
+            let signal = match args.first() {
+                Some(signal) => signal.to_string(),
+                None => state
+                    .displayed_signals
+                    .get(state.selected_signal)
+                    .cloned()
+                    .ok_or_else(|| "No signal selected".to_string())?,
+            };
+
+            let values = state
+                .waveform_data
+                .values
+                .get(&signal)
+                .ok_or_else(|| format!("Signal not found: {}", signal))?;
+
+            let window_end = state.time_start + state.time_range;
+            let transitions: Vec<Transition> = values
+                .iter()
+                .enumerate()
+                .filter(|(_, (time, _))| *time >= state.time_start && *time <= window_end)
+                .map(|(i, (time, value))| {
+                    // The previous change may be outside of the visible window
+                    let previous = i.checked_sub(1).map(|prev| &values[prev]);
+                    Transition {
+                        time: *time,
+                        old_value: previous.map(|(_, value)| state.format_value(&signal, value)),
+                        new_value: state.format_value(&signal, value),
+                        delta: previous.map(|(prev_time, _)| time - prev_time),
+                    }
+                })
+                .collect();
+
+            if transitions.is_empty() {
+                return Err(format!("No transitions of {} in view", signal));
+            }
+
+            let count = transitions.len();
+            state.transitions_popup = Some(TransitionsPopup {
+                signal,
+                transitions,
+                ..Default::default()
+            });
+            Ok(format!("{} transition(s)", count))
+        },
+    )
+    .alias("tr")
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::WaveValue;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec!["top.data".to_string(), "top.idle".to_string()];
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state.waveform_data.values.insert(
+            "top.data".to_string(),
+            vec![
+                (0, WaveValue::Bus("00".into())),
+                (10, WaveValue::Bus("0A".into())),
+                (25, WaveValue::Bus("FF".into())),
+                (40, WaveValue::Bus("01".into())),
+            ],
+        );
+        state.waveform_data.values.insert(
+            "top.idle".to_string(),
+            vec![(0, WaveValue::Bus("00".into()))],
+        );
+        state.waveform_data.max_time = 50;
+        state.time_start = 5;
+        state.time_range = 30;
+        state
+    }
+
+    #[test]
+    fn test_transitions_of_selected_signal_in_view() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap(), "2 transition(s)".to_string());
+
+        let popup = state.transitions_popup.unwrap();
+        assert_eq!(popup.signal, "top.data");
+        assert_eq!(
+            popup.transitions,
+            vec![
+                Transition {
+                    time: 10,
+                    old_value: Some("0".to_string()),
+                    new_value: "10".to_string(),
+                    delta: Some(10),
+                },
+                Transition {
+                    time: 25,
+                    old_value: Some("10".to_string()),
+                    new_value: "255".to_string(),
+                    delta: Some(15),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_transitions_of_named_signal() {
+        let command = create();
+        let mut state = get_state();
+        state.time_start = 0;
+        let result = command.execute(&["top.idle"], &mut state);
+        assert!(result.is_ok());
+
+        let popup = state.transitions_popup.unwrap();
+        assert_eq!(popup.transitions[0].old_value, None);
+        assert_eq!(popup.transitions[0].delta, None);
+    }
+
+    #[test]
+    fn test_transitions_errors() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&["top.nope"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Signal not found: top.nope".to_string()
+        );
+
+        let result = command.execute(&["top.idle"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "No transitions of top.idle in view".to_string()
+        );
+        assert!(state.transitions_popup.is_none());
+    }
+}
@@ -0,0 +1,52 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "unalias",
+        "Remove a command alias",
+        |args, state: &mut AppState| {
+            let [name] = args else {
+                return Err("Usage: unalias <name>".to_string());
+            };
+            if state.command_aliases.remove(*name).is_none() {
+                return Err(format!("No alias named '{}'", name));
+            }
+            super::alias::persist(state)?;
+            Ok(format!("Removed alias '{}'", name))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::command_aliases;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_unalias() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("aliases.toml");
+        let command = create();
+        let mut state = AppState::default();
+        state.command_aliases_path = Some(path.clone());
+        state
+            .command_aliases
+            .insert("g".to_string(), "goto".to_string());
+
+        assert_eq!(
+            command.execute(&["g"], &mut state),
+            Ok("Removed alias 'g'".to_string())
+        );
+        assert!(command_aliases::load_command_aliases(&path).is_empty());
+        assert_eq!(
+            command.execute(&["g"], &mut state),
+            Err("No alias named 'g'".to_string())
+        );
+    }
+}
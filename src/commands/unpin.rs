@@ -0,0 +1,51 @@
+use super::pin::signal_arg;
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "unpin",
+        "Scroll a pinned signal, or the selected one, with the others again",
+        |args, state: &mut AppState| {
+            let signal = signal_arg(args, state)?;
+            let position = state
+                .pinned_signals
+                .iter()
+                .position(|s| *s == signal)
+                .ok_or_else(|| format!("{} is not pinned", signal))?;
+
+            state.pinned_signals.remove(position);
+            state.clamp_signal_selection();
+            Ok(format!("Unpinned {}", signal))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unpin() {
+        let command = create();
+        let mut state = AppState::default();
+        state.displayed_signals = ["top.clk", "top.data"].map(String::from).to_vec();
+        state.pinned_signals = vec!["top.data".to_string()];
+        state.selected_signal = 1;
+
+        assert_eq!(
+            command.execute(&["top.clk"], &mut state).unwrap_err(),
+            "top.clk is not pinned"
+        );
+        assert_eq!(
+            command.execute(&[], &mut state).unwrap(),
+            "Unpinned top.data"
+        );
+        assert!(state.pinned_signals.is_empty());
+        assert_eq!(state.visible_signal_rows(4), vec![(0, 0, 2), (1, 2, 2)]);
+    }
+}
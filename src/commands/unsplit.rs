@@ -0,0 +1,56 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "unsplit",
+        "Remove the bit rows of a split signal",
+        |args, state: &mut AppState| {
+            let [signal] = args else {
+                return Err("Usage: unsplit <signal>".to_string());
+            };
+            let bits = state.unsplit_signal(signal)?;
+            Ok(format!("Removed {} bit row(s) of {}", bits, signal))
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::WaveValue;
+
+    #[test]
+    fn test_unsplit() {
+        let command = create();
+        let mut state = AppState::default();
+        state.waveform_data.values.insert(
+            "top.data".to_string(),
+            vec![(0, WaveValue::Bus("A".into()))],
+        );
+        state.displayed_signals = vec!["top.data".to_string(), "top.clk".to_string()];
+        state.split_signal("top.data").unwrap();
+        assert_eq!(state.displayed_signals.len(), 6);
+        // Select a bit row, which hands the selection back to the bus when removed
+        state.selected_signal = 3;
+
+        let result = command.execute(&["top.data"], &mut state);
+        assert_eq!(
+            result.unwrap(),
+            "Removed 4 bit row(s) of top.data".to_string()
+        );
+        assert_eq!(state.displayed_signals, vec!["top.data", "top.clk"]);
+        assert!(!state.waveform_data.values.contains_key("top.data[0]"));
+        assert_eq!(state.selected_signal, 0);
+
+        let result = command.execute(&["top.data"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Signal 'top.data' is not split".to_string()
+        );
+    }
+}
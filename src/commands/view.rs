@@ -0,0 +1,186 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    constants::VIEW_SLOTS,
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "view",
+        "Save the view in a numbered slot and go back to it",
+        move |args, state: &mut AppState| {
+            if args.is_empty() {
+                return Err("Usage: view save <n>, view load <n> or view list".to_string());
+            }
+
+            let subcommand = &args[0];
+            match &**subcommand {
+                "save" | "s" => save_subcommand().execute(&args[1..], state),
+                "load" | "l" => load_subcommand().execute(&args[1..], state),
+                "list" | "ls" => list_subcommand().execute(&args[1..], state),
+                _ => Err("Unknown subcommand.".to_string()),
+            }
+        },
+    )
+    .build()
+}
+
+// The slot given as the first argument, one of `VIEW_SLOTS`
+fn slot(args: &[&str], usage: &str) -> Result<u8, String> {
+    let Some(arg) = args.first() else {
+        return Err(usage.to_string());
+    };
+    arg.parse()
+        .ok()
+        .filter(|slot| VIEW_SLOTS.contains(slot))
+        .ok_or_else(|| {
+            format!(
+                "Slot must be {} to {}, not '{}'",
+                VIEW_SLOTS.start(),
+                VIEW_SLOTS.end(),
+                arg
+            )
+        })
+}
+
+fn save_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "save",
+        "Save the time window, selected signal and scroll position in a slot from 1 to 9",
+        move |args, state: &mut AppState| {
+            let slot = slot(args, "Usage: view save <n>")?;
+            let replaced = state.view_bookmarks.contains_key(&slot);
+            state.save_view(slot);
+            Ok(if replaced {
+                format!("Replaced view {}", slot)
+            } else {
+                format!("Saved view {}", slot)
+            })
+        },
+    )
+    .build()
+}
+
+fn load_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "load",
+        "Go back to the view saved in a slot",
+        move |args, state: &mut AppState| {
+            let slot = slot(args, "Usage: view load <n>")?;
+            state.load_view(slot)?;
+            Ok(format!("Loaded view {}", slot))
+        },
+    )
+    .build()
+}
+
+fn list_subcommand() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "list",
+        "List the saved views",
+        move |_args, state: &mut AppState| {
+            if state.view_bookmarks.is_empty() {
+                return Err("No saved views. Save one with view save <n> or m<n>".to_string());
+            }
+            state.show_view_list = true;
+            Ok(format!("{} saved view(s)", state.view_bookmarks.len()))
+        },
+    )
+    .alias("ls")
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.displayed_signals = ["a", "b", "c", "d"].map(String::from).to_vec();
+        state.time_start = 100;
+        state.time_range = 250;
+        state.selected_signal = 3;
+        state.signal_scroll_offset = 2;
+        state
+    }
+
+    #[test]
+    fn test_view_save_and_load() {
+        let mut state = get_state();
+        let view = create();
+        assert_eq!(
+            view.execute(&["save", "4"], &mut state).unwrap(),
+            "Saved view 4"
+        );
+
+        state.time_start = 0;
+        state.time_range = 1000;
+        state.selected_signal = 0;
+        state.signal_scroll_offset = 0;
+        assert_eq!(
+            view.execute(&["load", "4"], &mut state).unwrap(),
+            "Loaded view 4"
+        );
+        assert_eq!(
+            (
+                state.time_start,
+                state.time_range,
+                state.selected_signal,
+                state.signal_scroll_offset
+            ),
+            (100, 250, 3, 2)
+        );
+        // Loading is a jump, so the view before it is one jump back
+        assert!(state.jump_back());
+        assert_eq!((state.time_start, state.time_range), (0, 1000));
+
+        // Saving again replaces the slot
+        assert_eq!(
+            view.execute(&["save", "4"], &mut state).unwrap(),
+            "Replaced view 4"
+        );
+        assert_eq!(state.view_bookmarks[&4].time_range, 1000);
+    }
+
+    #[test]
+    fn test_view_keeps_selected_signal_when_moved() {
+        let mut state = get_state();
+        let view = create();
+        view.execute(&["save", "4"], &mut state).unwrap();
+
+        // The selected signal is found by name wherever it is in the list now
+        state.displayed_signals = ["d", "a", "b", "c"].map(String::from).to_vec();
+        state.selected_signal = 2;
+        view.execute(&["load", "4"], &mut state).unwrap();
+        assert_eq!(state.selected_signal, 0);
+    }
+
+    #[test]
+    fn test_view_errors() {
+        let mut state = get_state();
+        let view = create();
+        assert_eq!(
+            view.execute(&["load", "2"], &mut state).unwrap_err(),
+            "No view saved in slot 2"
+        );
+        assert_eq!(
+            view.execute(&["save", "0"], &mut state).unwrap_err(),
+            "Slot must be 1 to 9, not '0'"
+        );
+        assert_eq!(
+            view.execute(&["save"], &mut state).unwrap_err(),
+            "Usage: view save <n>"
+        );
+        assert!(view.execute(&["list"], &mut state).is_err());
+        assert!(!state.show_view_list);
+
+        view.execute(&["save", "1"], &mut state).unwrap();
+        assert_eq!(
+            view.execute(&["ls"], &mut state).unwrap(),
+            "1 saved view(s)"
+        );
+        assert!(state.show_view_list);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    config::WatchMode,
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "watch",
+        "Choose whether the loaded file is reloaded when it changes on disk: auto, ask or off",
+        |args, state: &mut AppState| {
+            let mode = match args {
+                [] => return Ok(describe(state.config.ui.watch).to_string()),
+                ["auto"] => WatchMode::Auto,
+                ["ask"] => WatchMode::Ask,
+                ["off"] => WatchMode::Off,
+                _ => return Err("Usage: watch <auto|ask|off>".to_string()),
+            };
+            state.config.ui.watch = mode;
+            Ok(describe(mode).to_string())
+        },
+    )
+    .build()
+}
+
+fn describe(mode: WatchMode) -> &'static str {
+    match mode {
+        WatchMode::Auto => "Reloading the file when it changes on disk",
+        WatchMode::Ask => "Asking to reload the file when it changes on disk",
+        WatchMode::Off => "Not watching the file for changes",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_watch_modes() {
+        let command = create();
+        let mut state = AppState::default();
+        assert_eq!(
+            command.execute(&[], &mut state),
+            Ok("Asking to reload the file when it changes on disk".to_string())
+        );
+
+        assert_eq!(
+            command.execute(&["auto"], &mut state),
+            Ok("Reloading the file when it changes on disk".to_string())
+        );
+        assert_eq!(state.config.ui.watch, WatchMode::Auto);
+        assert_eq!(
+            state.config.get("watch"),
+            Ok(("ui.watch".to_string(), "\"auto\"".to_string()))
+        );
+
+        command.execute(&["off"], &mut state).unwrap();
+        assert_eq!(state.config.ui.watch, WatchMode::Off);
+        assert_eq!(
+            command.execute(&["sometimes"], &mut state),
+            Err("Usage: watch <auto|ask|off>".to_string())
+        );
+        assert_eq!(state.config.ui.watch, WatchMode::Off);
+    }
+}
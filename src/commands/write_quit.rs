@@ -0,0 +1,62 @@
+use super::session::save_session;
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "wq",
+        "Save the session and quit digisurf",
+        |args, state: &mut AppState| {
+            // Only quit once the session is safely saved
+            let message = save_session(args, state, "wq [path]")?;
+            state.exit = true;
+            Ok(message)
+        },
+    )
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::load_session;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_quit_saves_then_exits() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("session.toml");
+        let command = create();
+        let mut state = AppState::default();
+        state.displayed_signals.push("top.clk".to_string());
+
+        assert_eq!(
+            command.execute(&[path.to_str().unwrap()], &mut state),
+            Ok(format!("Saved session to {}", path.display()))
+        );
+        assert!(state.exit);
+        assert!(!state.is_session_dirty());
+        assert_eq!(
+            load_session(&path).unwrap().displayed_signals,
+            vec!["top.clk"]
+        );
+    }
+
+    #[test]
+    fn test_write_quit_stays_when_save_fails() {
+        let command = create();
+        let mut state = AppState::default();
+        assert!(command.execute(&[], &mut state).is_err());
+        assert!(!state.exit);
+
+        // A directory can't be written as a file
+        let dir = TempDir::new().unwrap();
+        assert!(command
+            .execute(&[dir.path().to_str().unwrap()], &mut state)
+            .is_err());
+        assert!(!state.exit);
+    }
+}
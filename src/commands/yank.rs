@@ -0,0 +1,175 @@
+use crate::{
+    clipboard::{ClipboardBackend, SystemClipboard},
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::{cell::RefCell, rc::Rc};
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    create_with_clipboard(SystemClipboard::default())
+}
+
+fn create_with_clipboard(
+    clipboard: impl ClipboardBackend + 'static,
+) -> Rc<Box<dyn Command<AppState>>> {
+    let clipboard = RefCell::new(clipboard);
+    CommandBuilder::new(
+        "yank",
+        "Copy the value at the primary marker, a time, or a signal name to the clipboard",
+        move |args, state: &mut AppState| {
+            let text = yank_text(args, state)?;
+            match clipboard.borrow_mut().set_text(&text) {
+                Ok(()) => Ok(format!("Copied '{}' to clipboard", text)),
+                // Without a clipboard (e.g. over SSH) the text can still be copied from the
+                // terminal, so report it instead of failing
+                Err(_) => Ok(format!("Clipboard unavailable: {}", text)),
+            }
+        },
+    )
+    .alias("y")
+    .build()
+}
+
+fn yank_text(args: &[&str], state: &AppState) -> Result<String, String> {
+    match args.first().copied() {
+        None | Some("value") => {
+            let signal = selected_signal_name(state)?;
+            let time = state
+                .primary_marker
+                .ok_or_else(|| "Primary marker not set".to_string())?;
+            state
+                .get_value_at_marker(signal, time)
+                .map(|value| state.format_value(signal, &value))
+                .ok_or_else(|| format!("No value for '{}' at time {}", signal, time))
+        }
+        Some("time") => state
+            .primary_marker
+            .map(|time| time.to_string())
+            .ok_or_else(|| "Primary marker not set".to_string()),
+        Some("delta") => match (state.primary_marker, state.secondary_marker) {
+            (Some(primary), Some(secondary)) => Ok(primary.abs_diff(secondary).to_string()),
+            _ => Err("Both primary and secondary markers must be set".to_string()),
+        },
+        Some("signal") => selected_signal_name(state).map(|signal| signal.to_string()),
+        Some(other) => Err(format!(
+            "Unknown yank target: {}. Usage: yank [value|time|delta|signal]",
+            other
+        )),
+    }
+}
+
+fn selected_signal_name(state: &AppState) -> Result<&str, String> {
+    state
+        .displayed_signals
+        .get(state.selected_signal)
+        .map(|signal| signal.as_str())
+        .ok_or_else(|| "No signal selected".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::WaveValue;
+
+    type Contents = Rc<RefCell<Option<String>>>;
+
+    /// Clipboard backend that records copied text, or fails as if no clipboard were available
+    struct MockClipboard {
+        contents: Contents,
+        available: bool,
+    }
+
+    impl ClipboardBackend for MockClipboard {
+        fn set_text(&mut self, text: &str) -> Result<(), String> {
+            if !self.available {
+                return Err("no clipboard".to_string());
+            }
+            *self.contents.borrow_mut() = Some(text.to_string());
+            Ok(())
+        }
+    }
+
+    fn create_with_mock(available: bool) -> (Rc<Box<dyn Command<AppState>>>, Contents) {
+        let contents = Rc::new(RefCell::new(None));
+        let command = create_with_clipboard(MockClipboard {
+            contents: Rc::clone(&contents),
+            available,
+        });
+        (command, contents)
+    }
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec!["top.data".to_string()];
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state.waveform_data.values.insert(
+            "top.data".to_string(),
+            vec![
+                (0, WaveValue::Bus("00".into())),
+                (10, WaveValue::Bus("FF".into())),
+            ],
+        );
+        state.waveform_data.max_time = 20;
+        state.primary_marker = Some(12);
+        state.secondary_marker = Some(5);
+        state
+    }
+
+    #[test]
+    fn test_yank_value_at_primary_marker() {
+        let (command, contents) = create_with_mock(true);
+        let result = command.execute(&[], &mut get_state());
+        assert_eq!(result.unwrap(), "Copied '255' to clipboard".to_string());
+        assert_eq!(contents.borrow().as_deref(), Some("255"));
+    }
+
+    #[test]
+    fn test_yank_time_delta_and_signal() {
+        let (command, contents) = create_with_mock(true);
+        let mut state = get_state();
+
+        assert!(command.execute(&["time"], &mut state).is_ok());
+        assert_eq!(contents.borrow().as_deref(), Some("12"));
+
+        assert!(command.execute(&["delta"], &mut state).is_ok());
+        assert_eq!(contents.borrow().as_deref(), Some("7"));
+
+        assert!(command.execute(&["signal"], &mut state).is_ok());
+        assert_eq!(contents.borrow().as_deref(), Some("top.data"));
+    }
+
+    #[test]
+    fn test_yank_without_markers_is_err() {
+        let (command, contents) = create_with_mock(true);
+        let mut state = get_state();
+        state.primary_marker = None;
+
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap_err(), "Primary marker not set".to_string());
+
+        let result = command.execute(&["delta"], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "Both primary and secondary markers must be set".to_string()
+        );
+        assert!(contents.borrow().is_none());
+    }
+
+    #[test]
+    fn test_yank_unknown_target_is_err() {
+        let (command, _) = create_with_mock(true);
+        let result = command.execute(&["bogus"], &mut get_state());
+        assert_eq!(
+            result.unwrap_err(),
+            "Unknown yank target: bogus. Usage: yank [value|time|delta|signal]".to_string()
+        );
+    }
+
+    #[test]
+    fn test_yank_falls_back_to_result_message_without_clipboard() {
+        let (command, contents) = create_with_mock(false);
+        let result = command.execute(&["time"], &mut get_state());
+        assert_eq!(result.unwrap(), "Clipboard unavailable: 12".to_string());
+        assert!(contents.borrow().is_none());
+    }
+}
@@ -4,39 +4,63 @@ use crate::{
 };
 use std::rc::Rc;
 
+const USAGE: &str =
+    "Usage: zoom <factor>, zoom in [factor], zoom out [factor] or zoom marker [factor]";
+
 pub fn create() -> Rc<Box<dyn Command<AppState>>> {
     CommandBuilder::new(
         "zoom",
-        "Zoom to a specific level",
+        "Zoom to a specific level, or in and out of the current view",
         |args, state: &mut AppState| {
             if args.is_empty() {
-                return Err("Usage: zoom <factor>".to_string());
+                return Err(USAGE.to_string());
             }
 
-            if let Ok(factor) = args[0].parse::<u64>() {
-                if factor > 0 {
-                    let center = state.time_start + (state.time_range / 2);
-                    let new_range = state.waveform_data.max_time / factor;
-
-                    // Calculate new start time based on center point
-                    let half_new_range = new_range / 2;
-                    let new_start = if center > half_new_range {
-                        center - half_new_range
-                    } else {
-                        0
+            match args[0] {
+                "in" => {
+                    let factor = parse_factor(args.get(1))?;
+                    state.zoom_centered(state.time_range / factor);
+                    Ok(format!("Zoomed in by {}x", factor))
+                }
+                "out" => {
+                    let factor = parse_factor(args.get(1))?;
+                    state.zoom_centered(state.time_range.saturating_mul(factor));
+                    Ok(format!("Zoomed out by {}x", factor))
+                }
+                "marker" => {
+                    let factor = parse_factor(args.get(1))?;
+                    let Some(marker) = state.primary_marker else {
+                        return Err("Primary marker not set".to_string());
                     };
-
-                    state.time_start = new_start;
-                    state.time_range = new_range;
-                    return Ok(format!("Zoomed to 1/{}", factor));
+                    state.zoom_around(marker, state.time_range / factor);
+                    Ok(format!("Zoomed in by {}x around time {}", factor, marker))
+                }
+                factor => {
+                    if let Ok(factor) = factor.parse::<u64>() {
+                        if let Some(new_range) = state.waveform_data.max_time.checked_div(factor) {
+                            state.zoom_centered(new_range);
+                            return Ok(format!("Zoomed to 1/{}", factor));
+                        }
+                    }
+                    Err("Invalid zoom factor".to_string())
                 }
             }
-            Err("Invalid zoom factor".to_string())
         },
     )
     .build()
 }
 
+// Factor for the relative zoom subcommands, 2 if not given
+fn parse_factor(arg: Option<&&str>) -> Result<u64, String> {
+    match arg {
+        None => Ok(2),
+        Some(arg) => match arg.parse::<u64>() {
+            Ok(factor) if factor > 0 => Ok(factor),
+            _ => Err("Invalid zoom factor".to_string()),
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -55,7 +79,7 @@ mod tests {
         let mut state = get_state();
         let result = command.execute(&[], &mut state);
         assert!(result.is_err());
-        assert_eq!(result.unwrap_err(), "Usage: zoom <factor>".to_string());
+        assert_eq!(result.unwrap_err(), USAGE.to_string());
     }
 
     #[test]
@@ -100,4 +124,126 @@ mod tests {
         assert_eq!(state.time_range, 250); // 1000 / 4
         assert_eq!(state.time_start, 0); // Should clamp to 0 when center < half_new_range
     }
+
+    #[test]
+    fn test_zoom_factor_larger_than_trace_keeps_valid_window() {
+        let command = create();
+        let mut state = get_state();
+        state.waveform_data.max_time = 20;
+        state.time_start = 0;
+        state.time_range = 20;
+        let result = command.execute(&["100000"], &mut state);
+        assert!(result.is_ok());
+        assert_eq!(state.time_range, 1);
+        assert_eq!(state.time_start, 10);
+
+        // The view can still be mapped to the screen
+        assert_eq!(state.screen_pos_to_time(40, 80), 11);
+    }
+
+    #[test]
+    fn test_zoom_in_is_relative_to_view() {
+        let command = create();
+        let mut state = get_state();
+        state.time_start = 400;
+        state.time_range = 100;
+
+        let result = command.execute(&["in"], &mut state);
+        assert_eq!(result, Ok("Zoomed in by 2x".to_string()));
+        assert_eq!((state.time_start, state.time_range), (425, 50));
+
+        let result = command.execute(&["in", "5"], &mut state);
+        assert_eq!(result, Ok("Zoomed in by 5x".to_string()));
+        assert_eq!((state.time_start, state.time_range), (445, 10));
+    }
+
+    #[test]
+    fn test_zoom_in_never_reaches_zero_range() {
+        let command = create();
+        let mut state = get_state();
+        state.time_start = 500;
+        state.time_range = 3;
+
+        let result = command.execute(&["in", "10"], &mut state);
+        assert!(result.is_ok());
+        assert_eq!((state.time_start, state.time_range), (501, 1));
+    }
+
+    #[test]
+    fn test_zoom_out_is_relative_to_view() {
+        let command = create();
+        let mut state = get_state();
+        state.time_start = 400;
+        state.time_range = 100;
+
+        let result = command.execute(&["out", "3"], &mut state);
+        assert_eq!(result, Ok("Zoomed out by 3x".to_string()));
+        assert_eq!((state.time_start, state.time_range), (300, 300));
+    }
+
+    #[test]
+    fn test_zoom_out_clamps_to_waveform() {
+        let command = create();
+        let mut state = get_state();
+
+        // Near the end the window is moved back so it doesn't run past max_time
+        state.time_start = 900;
+        state.time_range = 100;
+        assert!(command.execute(&["out"], &mut state).is_ok());
+        assert_eq!((state.time_start, state.time_range), (800, 200));
+
+        // Zooming out past the whole waveform shows all of it
+        assert!(command.execute(&["out", "100"], &mut state).is_ok());
+        assert_eq!((state.time_start, state.time_range), (0, 1000));
+    }
+
+    #[test]
+    fn test_zoom_marker_centers_on_primary_marker() {
+        let command = create();
+        let mut state = get_state();
+        state.time_start = 0;
+        state.time_range = 400;
+        state.primary_marker = Some(300);
+
+        let result = command.execute(&["marker"], &mut state);
+        assert_eq!(result, Ok("Zoomed in by 2x around time 300".to_string()));
+        assert_eq!((state.time_start, state.time_range), (200, 200));
+    }
+
+    #[test]
+    fn test_zoom_marker_near_edges() {
+        let command = create();
+        let mut state = get_state();
+        state.time_start = 0;
+        state.time_range = 400;
+
+        state.primary_marker = Some(10);
+        assert!(command.execute(&["marker", "4"], &mut state).is_ok());
+        assert_eq!((state.time_start, state.time_range), (0, 100));
+
+        state.time_range = 400;
+        state.primary_marker = Some(990);
+        assert!(command.execute(&["marker", "4"], &mut state).is_ok());
+        assert_eq!((state.time_start, state.time_range), (900, 100));
+    }
+
+    #[test]
+    fn test_zoom_subcommand_errors() {
+        let command = create();
+        let mut state = get_state();
+
+        assert_eq!(
+            command.execute(&["marker"], &mut state),
+            Err("Primary marker not set".to_string())
+        );
+        assert_eq!(
+            command.execute(&["in", "0"], &mut state),
+            Err("Invalid zoom factor".to_string())
+        );
+        assert_eq!(
+            command.execute(&["out", "x"], &mut state),
+            Err("Invalid zoom factor".to_string())
+        );
+        assert_eq!((state.time_start, state.time_range), (0, 100));
+    }
 }
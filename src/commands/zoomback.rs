@@ -0,0 +1,56 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "zoomback",
+        "Restore the view from before the last zoom",
+        |_args, state: &mut AppState| {
+            if state.zoom_back() {
+                Ok("Restored previous zoom".to_string())
+            } else {
+                Err("No previous zoom to restore".to_string())
+            }
+        },
+    )
+    .alias("zb")
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.time_start = 0;
+        state.time_range = 1000;
+        state
+    }
+
+    #[test]
+    fn test_zoomback_without_history_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result.unwrap_err(),
+            "No previous zoom to restore".to_string()
+        );
+    }
+
+    #[test]
+    fn test_zoomback_restores_previous_window() {
+        let command = create();
+        let mut state = get_state();
+        state.zoom_to(100, 50);
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap(), "Restored previous zoom".to_string());
+        assert_eq!(state.time_start, 0);
+        assert_eq!(state.time_range, 1000);
+    }
+}
@@ -0,0 +1,125 @@
+use super::fit::padded_window;
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "zoomfit",
+        "Zoom to the changes of all displayed signals",
+        |_args, state: &mut AppState| {
+            let max_time = state.waveform_data.max_time;
+            let span = state.displayed_activity_span();
+            state.record_jump();
+
+            let fallback = match span {
+                _ if state.displayed_signals.is_empty() => "No signals displayed".to_string(),
+                None => "Displayed signals never change".to_string(),
+                Some((first, last)) if first == last => {
+                    format!("Displayed signals only change at {}", first)
+                }
+                Some((first, last)) => {
+                    let (start, range) = padded_window(first, last, max_time);
+                    state.zoom_to(start, range);
+                    return Ok(format!("Zoomed to changes from {} to {}", first, last));
+                }
+            };
+            state.zoom_to(0, max_time);
+            Ok(format!("{}, zoomed to full view", fallback))
+        },
+    )
+    .alias("zfi")
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 10000;
+        state.waveform_data.values.insert(
+            "top.irq".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (300, WaveValue::Binary(Value::V1)),
+                (500, WaveValue::Binary(Value::V0)),
+            ],
+        );
+        state.waveform_data.values.insert(
+            "top.ack".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (700, WaveValue::Binary(Value::V1)),
+            ],
+        );
+        state.waveform_data.values.insert(
+            "top.rst".to_string(),
+            vec![(0, WaveValue::Binary(Value::V0))],
+        );
+        state.displayed_signals = ["top.irq", "top.ack", "top.rst"].map(String::from).to_vec();
+        state.set_window(9000, 1000);
+        state
+    }
+
+    #[test]
+    fn test_zoomfit() {
+        let command = create();
+        let mut state = get_state();
+
+        // A signal that changes once counts, one that never changes doesn't
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result, Ok("Zoomed to changes from 300 to 700".to_string()));
+        assert_eq!((state.time_start, state.time_range), (280, 440));
+
+        // The span follows the displayed signals
+        state.displayed_signals.retain(|signal| signal != "top.ack");
+        command.execute(&[], &mut state).unwrap();
+        assert_eq!((state.time_start, state.time_range), (290, 220));
+    }
+
+    #[test]
+    fn test_zoomfit_falls_back_to_full_view() {
+        let command = create();
+        let mut state = get_state();
+
+        state.displayed_signals = vec!["top.rst".to_string()];
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result,
+            Ok("Displayed signals never change, zoomed to full view".to_string())
+        );
+        assert_eq!((state.time_start, state.time_range), (0, 10000));
+
+        // Signals that all change only at the start span no time
+        state.set_window(9000, 1000);
+        state.waveform_data.values.insert(
+            "top.en".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::VX)),
+                (0, WaveValue::Binary(Value::V0)),
+            ],
+        );
+        state.displayed_signals = vec!["top.en".to_string(), "top.rst".to_string()];
+        state.invalidate_waveform_stats();
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result,
+            Ok("Displayed signals only change at 0, zoomed to full view".to_string())
+        );
+        assert_eq!((state.time_start, state.time_range), (0, 10000));
+
+        state.set_window(9000, 1000);
+        state.displayed_signals.clear();
+        let result = command.execute(&[], &mut state);
+        assert_eq!(
+            result,
+            Ok("No signals displayed, zoomed to full view".to_string())
+        );
+        assert_eq!((state.time_start, state.time_range), (0, 10000));
+    }
+}
@@ -0,0 +1,54 @@
+use crate::{
+    command_mode::{builder::CommandBuilder, registry::Command},
+    state::AppState,
+};
+use std::rc::Rc;
+
+pub fn create() -> Rc<Box<dyn Command<AppState>>> {
+    CommandBuilder::new(
+        "zoomforward",
+        "Re-apply the last zoom undone by zoomback",
+        |_args, state: &mut AppState| {
+            if state.zoom_forward() {
+                Ok("Re-applied zoom".to_string())
+            } else {
+                Err("No zoom to re-apply".to_string())
+            }
+        },
+    )
+    .alias("zfw")
+    .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.max_time = 1000;
+        state.time_start = 0;
+        state.time_range = 1000;
+        state
+    }
+
+    #[test]
+    fn test_zoomforward_without_history_is_err() {
+        let command = create();
+        let mut state = get_state();
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap_err(), "No zoom to re-apply".to_string());
+    }
+
+    #[test]
+    fn test_zoomforward_reapplies_undone_zoom() {
+        let command = create();
+        let mut state = get_state();
+        state.zoom_to(100, 50);
+        state.zoom_back();
+        let result = command.execute(&[], &mut state);
+        assert_eq!(result.unwrap(), "Re-applied zoom".to_string());
+        assert_eq!(state.time_start, 100);
+        assert_eq!(state.time_range, 50);
+    }
+}
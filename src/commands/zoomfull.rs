@@ -9,8 +9,7 @@ pub fn create() -> Rc<Box<dyn Command<AppState>>> {
         "zoomfull",
         "Zoom to show the full waveform",
         |_args, state: &mut AppState| {
-            state.time_start = 0;
-            state.time_range = state.waveform_data.max_time;
+            state.zoom_to(0, state.waveform_data.max_time);
             Ok("Zoomed to full view".to_string())
         },
     )
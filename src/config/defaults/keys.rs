@@ -40,6 +40,42 @@ pub fn zoom_full() -> KeyCode {
     KeyCode::Char('0')
 }
 
+pub fn zoom_back() -> KeyCode {
+    KeyCode::Char('b')
+}
+
+pub fn zoom_forward() -> KeyCode {
+    KeyCode::Char('B')
+}
+
+pub fn fit_signal() -> KeyCode {
+    KeyCode::Char('f')
+}
+
+pub fn zoom_fit() -> KeyCode {
+    KeyCode::Char('F')
+}
+
+pub fn center_on_marker() -> KeyCode {
+    KeyCode::Char('z')
+}
+
+pub fn marker_to_center() -> KeyCode {
+    KeyCode::Char('Z')
+}
+
+pub fn rename_marker() -> KeyCode {
+    KeyCode::Char('R')
+}
+
+pub fn shrink_signal_list() -> KeyCode {
+    KeyCode::Left
+}
+
+pub fn grow_signal_list() -> KeyCode {
+    KeyCode::Right
+}
+
 pub fn delete_primary_marker() -> KeyCode {
     KeyCode::Delete
 }
@@ -48,6 +84,58 @@ pub fn delete_secondary_marker() -> KeyCode {
     KeyCode::Backspace
 }
 
+pub fn delete_signal() -> KeyCode {
+    KeyCode::Char('d')
+}
+
+pub fn filter_signals() -> KeyCode {
+    KeyCode::Char('/')
+}
+
+pub fn yank() -> KeyCode {
+    KeyCode::Char('y')
+}
+
+pub fn cycle_signal_names() -> KeyCode {
+    KeyCode::Char('n')
+}
+
+pub fn toggle_cursor() -> KeyCode {
+    KeyCode::Char('c')
+}
+
+pub fn cursor_left() -> KeyCode {
+    KeyCode::Char('h')
+}
+
+pub fn cursor_right() -> KeyCode {
+    KeyCode::Char('l')
+}
+
+pub fn cursor_to_marker() -> KeyCode {
+    KeyCode::Char('m')
+}
+
+pub fn save_view() -> KeyCode {
+    KeyCode::Char('m')
+}
+
+pub fn load_view() -> KeyCode {
+    KeyCode::Char('\'')
+}
+
+pub fn jump_back() -> KeyCode {
+    KeyCode::Char('o')
+}
+
+pub fn jump_forward() -> KeyCode {
+    KeyCode::Char('i')
+}
+
+pub fn switch_pane() -> KeyCode {
+    KeyCode::Tab
+}
+
 pub fn enter_normal_mode() -> KeyCode {
     KeyCode::Esc
 }
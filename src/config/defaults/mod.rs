@@ -9,3 +9,31 @@ pub fn config_path() -> Option<PathBuf> {
         config_dir.join("config.toml")
     })
 }
+
+pub fn recent_files_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "digisurf").map(|proj_dirs| {
+        let config_dir = proj_dirs.config_dir();
+        config_dir.join("recent_files.toml")
+    })
+}
+
+pub fn signal_groups_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "digisurf").map(|proj_dirs| {
+        let config_dir = proj_dirs.config_dir();
+        config_dir.join("signal_groups.toml")
+    })
+}
+
+pub fn command_aliases_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "digisurf").map(|proj_dirs| {
+        let config_dir = proj_dirs.config_dir();
+        config_dir.join("aliases.toml")
+    })
+}
+
+pub fn session_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "digisurf").map(|proj_dirs| {
+        let config_dir = proj_dirs.config_dir();
+        config_dir.join("session.toml")
+    })
+}
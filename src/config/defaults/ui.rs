@@ -1,3 +1,8 @@
+use crate::{
+    config::{ColorMode, LabelTruncation, RenderStyle, SignalNameStyle, WatchMode},
+    constants::WAVEFORM_HEIGHT,
+};
+
 pub fn signal_list_width() -> u16 {
     20
 }
@@ -13,3 +18,67 @@ pub fn marker_color_secondary() -> String {
 pub fn drag_color() -> String {
     "rgb(100,150,255)".to_string()
 }
+
+pub fn x_color() -> String {
+    "red".to_string()
+}
+
+pub fn z_color() -> String {
+    "magenta".to_string()
+}
+
+pub fn render_style() -> RenderStyle {
+    RenderStyle::Braille
+}
+
+pub fn waveform_height() -> usize {
+    WAVEFORM_HEIGHT
+}
+
+pub fn marker_snap() -> bool {
+    false
+}
+
+pub fn marker_snap_distance() -> u16 {
+    2
+}
+
+pub fn show_saved_marker_lines() -> bool {
+    true
+}
+
+pub fn show_no_data_yet() -> bool {
+    true
+}
+
+pub fn show_minimap() -> bool {
+    false
+}
+
+pub fn pan_fraction() -> f64 {
+    0.25
+}
+
+pub fn grep_match_limit() -> usize {
+    500
+}
+
+pub fn color_mode() -> ColorMode {
+    ColorMode::Full
+}
+
+pub fn signal_name_style() -> SignalNameStyle {
+    SignalNameStyle::Full
+}
+
+pub fn bus_label_truncation() -> LabelTruncation {
+    LabelTruncation::Start
+}
+
+pub fn watch() -> WatchMode {
+    WatchMode::Ask
+}
+
+pub fn initial_view() -> String {
+    "full".to_string()
+}
@@ -1,5 +1,8 @@
+//! Settings and keybindings, read from `config.toml` and changed with `:set`.
+
 mod defaults;
 use crossterm::event::KeyCode;
+pub use defaults::{command_aliases_path, recent_files_path, session_path, signal_groups_path};
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
@@ -10,10 +13,171 @@ pub struct AppConfig {
     pub ui: UiConfig,
     #[serde(default)]
     pub keybindings: KeybindingsConfig,
-    #[serde(default = "defaults::config_path")]
+    /// Where the config was loaded from and is saved to. Not written to the file itself.
+    #[serde(default = "defaults::config_path", skip_serializing)]
     pub config_path: Option<PathBuf>,
 }
 
+/// Allowed range of `ui.signal_list_width`.
+const SIGNAL_LIST_WIDTH_RANGE: std::ops::RangeInclusive<u16> = 1..=90;
+
+/// Allowed range of `ui.waveform_height`. Taller rows are only for single signals, with `:height`.
+pub const WAVEFORM_HEIGHT_RANGE: std::ops::RangeInclusive<usize> = 1..=2;
+
+/// Config sections whose fields can be changed at runtime with `set`.
+const SETTING_SECTIONS: [&str; 2] = ["ui", "keybindings"];
+
+impl AppConfig {
+    /// Checks for settings that parse but can't work: keys bound to several actions at once and
+    /// out of range values. Returns a description of every problem found.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut problems: Vec<String> = self
+            .keybindings
+            .conflicts()
+            .iter()
+            .map(|(earlier, later, key)| {
+                format!(
+                    "keybindings.{} and keybindings.{} are both {:?}",
+                    earlier, later, key
+                )
+            })
+            .collect();
+        if !SIGNAL_LIST_WIDTH_RANGE.contains(&self.ui.signal_list_width) {
+            problems.push(format!(
+                "ui.signal_list_width must be between {} and {}, got {}",
+                SIGNAL_LIST_WIDTH_RANGE.start(),
+                SIGNAL_LIST_WIDTH_RANGE.end(),
+                self.ui.signal_list_width
+            ));
+        }
+        if !WAVEFORM_HEIGHT_RANGE.contains(&self.ui.waveform_height) {
+            problems.push(format!(
+                "ui.waveform_height must be {} or {}, got {}",
+                WAVEFORM_HEIGHT_RANGE.start(),
+                WAVEFORM_HEIGHT_RANGE.end(),
+                self.ui.waveform_height
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Invalid config: {}", problems.join("; ")))
+        }
+    }
+
+    /// Fixes the problems reported by `validate` so the app can start anyway: the later of two
+    /// conflicting keybindings is disabled, and out of range values are reset to their defaults.
+    /// Returns a warning for every change made.
+    pub fn force_valid(&mut self) -> Vec<String> {
+        let mut warnings: Vec<String> = self
+            .keybindings
+            .disable_conflicts()
+            .iter()
+            .map(|(earlier, later, key)| {
+                format!(
+                    "keybindings.{} disabled, {:?} is already bound to keybindings.{}",
+                    later, key, earlier
+                )
+            })
+            .collect();
+        if !SIGNAL_LIST_WIDTH_RANGE.contains(&self.ui.signal_list_width) {
+            self.ui.signal_list_width = defaults::ui::signal_list_width();
+            warnings.push(format!(
+                "ui.signal_list_width out of range, using {}",
+                self.ui.signal_list_width
+            ));
+        }
+        if !WAVEFORM_HEIGHT_RANGE.contains(&self.ui.waveform_height) {
+            self.ui.waveform_height = defaults::ui::waveform_height();
+            warnings.push(format!(
+                "ui.waveform_height out of range, using {}",
+                self.ui.waveform_height
+            ));
+        }
+        warnings
+    }
+
+    /// Changes a setting given its key, e.g. `ui.signal_list_width` or `keybindings.zoom_in`. Keys
+    /// without a section are looked up in each section in turn. The value is written the same way
+    /// as in the config file, except that single characters are accepted for keybindings. Returns
+    /// the full key of the changed setting.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<String, String> {
+        let mut table = toml::Value::try_from(&*self).map_err(|e| e.to_string())?;
+        let (section, field) = resolve_setting_key(&table, key)?;
+        let full_key = format!("{}.{}", section, field);
+
+        table[section][field] = parse_setting_value(section, value);
+        let mut updated: AppConfig = table.try_into().map_err(|e: toml::de::Error| {
+            format!("Invalid value for {}: {}", full_key, e.message())
+        })?;
+
+        updated.config_path = self.config_path.take();
+        *self = updated;
+        Ok(full_key)
+    }
+
+    /// Returns a setting's key and its current value in config file syntax.
+    pub fn get(&self, key: &str) -> Result<(String, String), String> {
+        let table = toml::Value::try_from(self).map_err(|e| e.to_string())?;
+        let (section, field) = resolve_setting_key(&table, key)?;
+        Ok((
+            format!("{}.{}", section, field),
+            table[section][field].to_string(),
+        ))
+    }
+
+    /// Writes the config to `config_path`, creating its parent directories if needed.
+    pub fn save(&self) -> Result<&PathBuf, String> {
+        let path = self
+            .config_path
+            .as_ref()
+            .ok_or_else(|| "No config file path available".to_string())?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Error creating config directory: {}", e))?;
+        }
+
+        let content =
+            toml::to_string_pretty(self).map_err(|e| format!("Error serializing config: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Error writing config file: {}", e))?;
+        Ok(path)
+    }
+}
+
+fn resolve_setting_key<'a>(
+    table: &toml::Value,
+    key: &'a str,
+) -> Result<(&'static str, &'a str), String> {
+    let (section, field) = match key.split_once('.') {
+        Some((section, field)) => (Some(section), field),
+        None => (None, key),
+    };
+
+    SETTING_SECTIONS
+        .into_iter()
+        .filter(|s| section.is_none_or(|section| section == *s))
+        .find(|s| table.get(s).and_then(|t| t.get(field)).is_some())
+        .map(|s| (s, field))
+        .ok_or_else(|| format!("Unknown setting: {}", key))
+}
+
+fn parse_setting_value(section: &str, input: &str) -> toml::Value {
+    let mut chars = input.chars();
+    if let (Some(c), None, "keybindings") = (chars.next(), chars.next(), section) {
+        let mut key = toml::Table::new();
+        key.insert("Char".to_string(), toml::Value::String(c.to_string()));
+        return toml::Value::Table(key);
+    }
+
+    // Anything that isn't valid TOML is taken as a string, so that e.g. colors and key names
+    // don't need to be quoted
+    toml::from_str::<toml::Table>(&format!("value = {}", input))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(input.to_string()))
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         AppConfig {
@@ -34,6 +198,126 @@ pub struct UiConfig {
     pub marker_color_secondary: String,
     #[serde(default = "defaults::ui::drag_color")]
     pub drag_color: String,
+    /// Color of unknown (X) values, drawn as a band on binary signals
+    #[serde(default = "defaults::ui::x_color")]
+    pub x_color: String,
+    /// Color of high impedance (Z) values, drawn as a dashed middle line on binary signals
+    #[serde(default = "defaults::ui::z_color")]
+    pub z_color: String,
+    #[serde(default = "defaults::ui::render_style")]
+    pub render_style: RenderStyle,
+    /// Rows each signal is drawn in: 2, or 1 for a compact view with binary signals drawn with
+    /// half blocks and buses only as their values
+    #[serde(default = "defaults::ui::waveform_height")]
+    pub waveform_height: usize,
+    /// Whether markers placed with the mouse snap to a nearby transition of the selected signal
+    #[serde(default = "defaults::ui::marker_snap")]
+    pub marker_snap: bool,
+    /// How many columns away from a transition a marker snaps to it
+    #[serde(default = "defaults::ui::marker_snap_distance")]
+    pub marker_snap_distance: u16,
+    /// Whether saved markers are drawn as lines across the waveform, and not only named above it
+    #[serde(default = "defaults::ui::show_saved_marker_lines")]
+    pub show_saved_marker_lines: bool,
+    /// Whether signals are drawn as a dim dashed line before their first value
+    #[serde(default = "defaults::ui::show_no_data_yet")]
+    pub show_no_data_yet: bool,
+    /// Whether a strip showing the activity over the whole trace is drawn under the time ruler
+    #[serde(default = "defaults::ui::show_minimap")]
+    pub show_minimap: bool,
+    /// Fraction of the visible time range that the left and right keys pan by
+    #[serde(default = "defaults::ui::pan_fraction")]
+    pub pan_fraction: f64,
+    /// Most matches `:grep` lists
+    #[serde(default = "defaults::ui::grep_match_limit")]
+    pub grep_match_limit: usize,
+    /// How much color is used. The NO_COLOR environment variable turns colors off regardless.
+    #[serde(default = "defaults::ui::color_mode")]
+    pub color_mode: ColorMode,
+    /// How signal names are shortened in the signal list
+    #[serde(default = "defaults::ui::signal_name_style")]
+    pub signal_name_style: SignalNameStyle,
+    /// Which end of a bus value too wide for its segment is cut off
+    #[serde(default = "defaults::ui::bus_label_truncation")]
+    pub bus_label_truncation: LabelTruncation,
+    /// What happens when the loaded file changes on disk
+    #[serde(default = "defaults::ui::watch")]
+    pub watch: WatchMode,
+    /// Part of the waveform shown when a file is loaded: `full`, `last:<duration>`,
+    /// `first_activity` or `window:<start>:<end>`
+    #[serde(default = "defaults::ui::initial_view")]
+    pub initial_view: String,
+}
+
+/// How waveforms are drawn inside terminal cells.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum RenderStyle {
+    /// One full block per cell
+    Block,
+    /// Braille dots, with 2x4 dots per cell
+    Braille,
+    /// Half blocks, with two rows per cell
+    HalfBlock,
+}
+
+/// How much color the terminal can show.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// Every color, including RGB colors
+    Full,
+    /// Only the 16 ANSI colors, with RGB colors replaced by the closest of them
+    Ansi16,
+    /// No colors, with text attributes like reversed cells in their place
+    Mono,
+}
+
+/// How much of each signal's hierarchical name the signal list shows. Shortened names keep more
+/// scopes where they would otherwise be the same for different signals.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SignalNameStyle {
+    /// The whole name, like `top.core.decode.valid`
+    Full,
+    /// Only the last component, like `valid`
+    Leaf,
+    /// The first and the last two components with the scopes between elided, like
+    /// `top.….decode.valid`
+    Shortened,
+}
+
+/// Which end of a bus label is replaced by `…` when the value doesn't fit in its segment.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum LabelTruncation {
+    /// Cut off the most significant digits, like `…3F0A`
+    Start,
+    /// Cut off the least significant digits, like `DEAD…`
+    End,
+}
+
+/// What to do when the loaded file is rewritten, e.g. by a simulator run finishing.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchMode {
+    /// Reload it, keeping the view, markers and selection
+    Auto,
+    /// Offer to reload it with `:reload`
+    Ask,
+    /// Don't watch it
+    Off,
+}
+
+impl SignalNameStyle {
+    /// The style after this one, for the key that cycles through them.
+    pub fn next(self) -> Self {
+        match self {
+            SignalNameStyle::Full => SignalNameStyle::Leaf,
+            SignalNameStyle::Leaf => SignalNameStyle::Shortened,
+            SignalNameStyle::Shortened => SignalNameStyle::Full,
+        }
+    }
 }
 
 impl Default for UiConfig {
@@ -43,6 +327,22 @@ impl Default for UiConfig {
             marker_color_primary: defaults::ui::marker_color_primary(),
             marker_color_secondary: defaults::ui::marker_color_secondary(),
             drag_color: defaults::ui::drag_color(),
+            x_color: defaults::ui::x_color(),
+            z_color: defaults::ui::z_color(),
+            render_style: defaults::ui::render_style(),
+            waveform_height: defaults::ui::waveform_height(),
+            marker_snap: defaults::ui::marker_snap(),
+            marker_snap_distance: defaults::ui::marker_snap_distance(),
+            show_saved_marker_lines: defaults::ui::show_saved_marker_lines(),
+            show_no_data_yet: defaults::ui::show_no_data_yet(),
+            show_minimap: defaults::ui::show_minimap(),
+            pan_fraction: defaults::ui::pan_fraction(),
+            grep_match_limit: defaults::ui::grep_match_limit(),
+            color_mode: defaults::ui::color_mode(),
+            signal_name_style: defaults::ui::signal_name_style(),
+            bus_label_truncation: defaults::ui::bus_label_truncation(),
+            watch: defaults::ui::watch(),
+            initial_view: defaults::ui::initial_view(),
         }
     }
 }
@@ -79,12 +379,92 @@ pub struct KeybindingsConfig {
     #[serde(default = "defaults::keys::zoom_full")]
     pub zoom_full: KeyCode,
 
+    #[serde(default = "defaults::keys::zoom_back")]
+    pub zoom_back: KeyCode,
+
+    #[serde(default = "defaults::keys::zoom_forward")]
+    pub zoom_forward: KeyCode,
+
+    /// Zooms to the changes of the selected signal, like `:fit`
+    #[serde(default = "defaults::keys::fit_signal")]
+    pub fit_signal: KeyCode,
+
+    /// Zooms to the changes of all displayed signals, like `:zoomfit`
+    #[serde(default = "defaults::keys::zoom_fit")]
+    pub zoom_fit: KeyCode,
+
+    #[serde(default = "defaults::keys::center_on_marker")]
+    pub center_on_marker: KeyCode,
+
+    #[serde(default = "defaults::keys::marker_to_center")]
+    pub marker_to_center: KeyCode,
+
+    /// Renames the saved marker selected by clicking its name, like `:marker rename`
+    #[serde(default = "defaults::keys::rename_marker")]
+    pub rename_marker: KeyCode,
+
+    /// Used together with Ctrl
+    #[serde(default = "defaults::keys::shrink_signal_list")]
+    pub shrink_signal_list: KeyCode,
+
+    /// Used together with Ctrl
+    #[serde(default = "defaults::keys::grow_signal_list")]
+    pub grow_signal_list: KeyCode,
+
+    /// Goes back in the jump list, used together with Ctrl
+    #[serde(default = "defaults::keys::jump_back")]
+    pub jump_back: KeyCode,
+
+    /// Goes forward in the jump list, used together with Ctrl. Many terminals send Ctrl+I as Tab
+    #[serde(default = "defaults::keys::jump_forward")]
+    pub jump_forward: KeyCode,
+
     #[serde(default = "defaults::keys::delete_primary_marker")]
     pub delete_primary_marker: KeyCode,
 
     #[serde(default = "defaults::keys::delete_secondary_marker")]
     pub delete_secondary_marker: KeyCode,
 
+    #[serde(default = "defaults::keys::delete_signal")]
+    pub delete_signal: KeyCode,
+
+    /// Narrows the signal list to the signals whose names contain what is typed next
+    #[serde(default = "defaults::keys::filter_signals")]
+    pub filter_signals: KeyCode,
+
+    #[serde(default = "defaults::keys::yank")]
+    pub yank: KeyCode,
+
+    #[serde(default = "defaults::keys::cycle_signal_names")]
+    pub cycle_signal_names: KeyCode,
+
+    #[serde(default = "defaults::keys::toggle_cursor")]
+    pub toggle_cursor: KeyCode,
+
+    /// Moves the cursor by one ruler tick, or further with Shift
+    #[serde(default = "defaults::keys::cursor_left")]
+    pub cursor_left: KeyCode,
+
+    /// Moves the cursor by one ruler tick, or further with Shift
+    #[serde(default = "defaults::keys::cursor_right")]
+    pub cursor_right: KeyCode,
+
+    #[serde(default = "defaults::keys::cursor_to_marker")]
+    pub cursor_to_marker: KeyCode,
+
+    /// Saves the view in the slot of the digit pressed next, from 1 to 9. While the cursor is
+    /// shown, `cursor_to_marker` takes the key if both are bound to it
+    #[serde(default = "defaults::keys::save_view")]
+    pub save_view: KeyCode,
+
+    /// Goes back to the view saved in the slot of the digit pressed next
+    #[serde(default = "defaults::keys::load_view")]
+    pub load_view: KeyCode,
+
+    /// Moves the focus to the other pane while the waveform is split with `:split-view`
+    #[serde(default = "defaults::keys::switch_pane")]
+    pub switch_pane: KeyCode,
+
     #[serde(default = "defaults::keys::enter_normal_mode")]
     pub enter_normal_mode: KeyCode,
 
@@ -92,6 +472,229 @@ pub struct KeybindingsConfig {
     pub execute_command: KeyCode,
 }
 
+impl KeybindingsConfig {
+    // Keybindings grouped by when they are active: plain keys in normal mode, keys used with Shift,
+    // keys used with Ctrl, and keys in command mode. A key must only be bound once per group.
+    // Bindings are in the order they are declared, so conflicts are resolved in favor of the first.
+    fn groups_mut(&mut self) -> [Vec<(&'static str, &mut KeyCode)>; 4] {
+        let KeybindingsConfig {
+            enter_command_mode,
+            up,
+            down,
+            left,
+            right,
+            move_signal_up,
+            move_signal_down,
+            zoom_in,
+            zoom_out,
+            zoom_full,
+            zoom_back,
+            zoom_forward,
+            fit_signal,
+            zoom_fit,
+            center_on_marker,
+            marker_to_center,
+            rename_marker,
+            shrink_signal_list,
+            grow_signal_list,
+            jump_back,
+            jump_forward,
+            delete_primary_marker,
+            delete_secondary_marker,
+            delete_signal,
+            filter_signals,
+            yank,
+            cycle_signal_names,
+            toggle_cursor,
+            cursor_left,
+            cursor_right,
+            cursor_to_marker,
+            save_view,
+            load_view,
+            switch_pane,
+            enter_normal_mode,
+            execute_command,
+        } = self;
+
+        [
+            vec![
+                ("enter_command_mode", enter_command_mode),
+                ("up", up),
+                ("down", down),
+                ("left", left),
+                ("right", right),
+                ("zoom_in", zoom_in),
+                ("zoom_out", zoom_out),
+                ("zoom_full", zoom_full),
+                ("zoom_back", zoom_back),
+                ("zoom_forward", zoom_forward),
+                ("fit_signal", fit_signal),
+                ("zoom_fit", zoom_fit),
+                ("center_on_marker", center_on_marker),
+                ("marker_to_center", marker_to_center),
+                ("rename_marker", rename_marker),
+                ("delete_primary_marker", delete_primary_marker),
+                ("delete_secondary_marker", delete_secondary_marker),
+                ("delete_signal", delete_signal),
+                ("filter_signals", filter_signals),
+                ("yank", yank),
+                ("cycle_signal_names", cycle_signal_names),
+                ("toggle_cursor", toggle_cursor),
+                ("cursor_left", cursor_left),
+                ("cursor_right", cursor_right),
+                ("cursor_to_marker", cursor_to_marker),
+                ("save_view", save_view),
+                ("load_view", load_view),
+                ("switch_pane", switch_pane),
+            ],
+            vec![
+                ("move_signal_up", move_signal_up),
+                ("move_signal_down", move_signal_down),
+            ],
+            vec![
+                ("shrink_signal_list", shrink_signal_list),
+                ("grow_signal_list", grow_signal_list),
+                ("jump_back", jump_back),
+                ("jump_forward", jump_forward),
+            ],
+            vec![
+                ("enter_normal_mode", enter_normal_mode),
+                ("execute_command", execute_command),
+            ],
+        ]
+    }
+
+    /// What each binding does as shown in the help, with the key it is bound to, in the order they
+    /// are declared. Bindings used with a modifier say so.
+    pub fn descriptions(&self) -> Vec<(&'static str, KeyCode)> {
+        let KeybindingsConfig {
+            enter_command_mode,
+            up,
+            down,
+            left,
+            right,
+            move_signal_up,
+            move_signal_down,
+            zoom_in,
+            zoom_out,
+            zoom_full,
+            zoom_back,
+            zoom_forward,
+            fit_signal,
+            zoom_fit,
+            center_on_marker,
+            marker_to_center,
+            rename_marker,
+            shrink_signal_list,
+            grow_signal_list,
+            jump_back,
+            jump_forward,
+            delete_primary_marker,
+            delete_secondary_marker,
+            delete_signal,
+            filter_signals,
+            yank,
+            cycle_signal_names,
+            toggle_cursor,
+            cursor_left,
+            cursor_right,
+            cursor_to_marker,
+            save_view,
+            load_view,
+            switch_pane,
+            enter_normal_mode,
+            execute_command,
+        } = self;
+
+        vec![
+            ("Enter command mode", *enter_command_mode),
+            ("Select signal above", *up),
+            ("Select signal below", *down),
+            ("Pan left, a page with Shift, a ruler tick with Alt", *left),
+            (
+                "Pan right, a page with Shift, a ruler tick with Alt",
+                *right,
+            ),
+            ("Move signal up, with Shift", *move_signal_up),
+            ("Move signal down, with Shift", *move_signal_down),
+            ("Zoom in", *zoom_in),
+            ("Zoom out", *zoom_out),
+            ("Zoom full", *zoom_full),
+            ("Zoom back", *zoom_back),
+            ("Zoom forward", *zoom_forward),
+            ("Zoom to selected signal's changes", *fit_signal),
+            ("Zoom to displayed signals' changes", *zoom_fit),
+            ("Center view on yellow marker", *center_on_marker),
+            ("Move yellow marker to view center", *marker_to_center),
+            ("Rename the clicked saved marker", *rename_marker),
+            ("Shrink signal list, with Ctrl", *shrink_signal_list),
+            ("Grow signal list, with Ctrl", *grow_signal_list),
+            ("Jump back to previous view, with Ctrl", *jump_back),
+            ("Jump forward again, with Ctrl", *jump_forward),
+            ("Remove primary marker", *delete_primary_marker),
+            ("Remove secondary marker", *delete_secondary_marker),
+            ("Hide selected signal", *delete_signal),
+            ("Filter the signal list", *filter_signals),
+            ("Copy value at primary marker", *yank),
+            (
+                "Cycle full/leaf/shortened signal names",
+                *cycle_signal_names,
+            ),
+            ("Toggle keyboard cursor", *toggle_cursor),
+            ("Move cursor left a tick, more with Shift", *cursor_left),
+            ("Move cursor right a tick, more with Shift", *cursor_right),
+            ("Put yellow marker at cursor", *cursor_to_marker),
+            ("Save view in slot 1-9, then the digit", *save_view),
+            ("Load view from slot 1-9, then the digit", *load_view),
+            ("Switch pane of a split waveform", *switch_pane),
+            ("Leave command mode", *enter_normal_mode),
+            ("Run the typed command", *execute_command),
+        ]
+    }
+
+    /// Finds keys bound to more than one action at the same time. Each conflict is given as the
+    /// earlier binding, the later binding, and the key. Disabled keys never conflict.
+    pub fn conflicts(&self) -> Vec<(&'static str, &'static str, KeyCode)> {
+        let mut copy = self.clone();
+        let mut conflicts = Vec::new();
+        for group in copy.groups_mut() {
+            for (i, (later, key)) in group.iter().enumerate() {
+                if **key == KeyCode::Null {
+                    continue;
+                }
+                if let Some((earlier, _)) = group[..i].iter().find(|(earlier, other)| {
+                    **other == **key && !never_active_together(earlier, later)
+                }) {
+                    conflicts.push((*earlier, *later, **key));
+                }
+            }
+        }
+        conflicts
+    }
+
+    // Disables every binding that conflicts with an earlier one. Returns the conflicts that were
+    // resolved.
+    fn disable_conflicts(&mut self) -> Vec<(&'static str, &'static str, KeyCode)> {
+        let conflicts = self.conflicts();
+        for mut group in self.groups_mut() {
+            for (name, key) in group.iter_mut() {
+                if conflicts.iter().any(|(_, later, _)| later == name) {
+                    **key = KeyCode::Null;
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+// Keys used only while the cursor is shown, like `cursor_to_marker`, take precedence over the view
+// keys then, so `m` can both put the marker at the cursor and save views
+fn never_active_together(earlier: &str, later: &str) -> bool {
+    const CURSOR_KEYS: [&str; 3] = ["cursor_left", "cursor_right", "cursor_to_marker"];
+    const VIEW_KEYS: [&str; 2] = ["save_view", "load_view"];
+    CURSOR_KEYS.contains(&earlier) && VIEW_KEYS.contains(&later)
+}
+
 impl Default for KeybindingsConfig {
     fn default() -> Self {
         Self {
@@ -105,8 +708,30 @@ impl Default for KeybindingsConfig {
             zoom_in: defaults::keys::zoom_in(),
             zoom_out: defaults::keys::zoom_out(),
             zoom_full: defaults::keys::zoom_full(),
+            zoom_back: defaults::keys::zoom_back(),
+            zoom_forward: defaults::keys::zoom_forward(),
+            fit_signal: defaults::keys::fit_signal(),
+            zoom_fit: defaults::keys::zoom_fit(),
+            center_on_marker: defaults::keys::center_on_marker(),
+            marker_to_center: defaults::keys::marker_to_center(),
+            rename_marker: defaults::keys::rename_marker(),
+            shrink_signal_list: defaults::keys::shrink_signal_list(),
+            grow_signal_list: defaults::keys::grow_signal_list(),
+            jump_back: defaults::keys::jump_back(),
+            jump_forward: defaults::keys::jump_forward(),
             delete_primary_marker: defaults::keys::delete_primary_marker(),
             delete_secondary_marker: defaults::keys::delete_secondary_marker(),
+            delete_signal: defaults::keys::delete_signal(),
+            filter_signals: defaults::keys::filter_signals(),
+            yank: defaults::keys::yank(),
+            cycle_signal_names: defaults::keys::cycle_signal_names(),
+            toggle_cursor: defaults::keys::toggle_cursor(),
+            cursor_left: defaults::keys::cursor_left(),
+            cursor_right: defaults::keys::cursor_right(),
+            cursor_to_marker: defaults::keys::cursor_to_marker(),
+            save_view: defaults::keys::save_view(),
+            load_view: defaults::keys::load_view(),
+            switch_pane: defaults::keys::switch_pane(),
             enter_normal_mode: defaults::keys::enter_normal_mode(),
             execute_command: defaults::keys::execute_command(),
         }
@@ -222,4 +847,168 @@ mod tests {
         fs::write(&temp_file, "this is not valid TOML").unwrap();
         assert!(load_config(Some(temp_file.path().to_str().unwrap().to_string())).is_err());
     }
+
+    #[test]
+    fn test_set_and_get_settings() {
+        let mut config = AppConfig::default();
+
+        assert_eq!(
+            config.set("ui.signal_list_width", "30"),
+            Ok("ui.signal_list_width".to_string())
+        );
+        assert_eq!(config.ui.signal_list_width, 30);
+
+        // Keys can leave out the section, and keybindings accept a single character
+        assert_eq!(
+            config.set("zoom_in", "="),
+            Ok("keybindings.zoom_in".to_string())
+        );
+        assert_eq!(config.keybindings.zoom_in, KeyCode::Char('='));
+        assert_eq!(
+            config.get("keybindings.zoom_in"),
+            Ok((
+                "keybindings.zoom_in".to_string(),
+                "{ Char = \"=\" }".to_string()
+            ))
+        );
+
+        // Keybindings also accept key names and the config file syntax
+        config.set("keybindings.zoom_out", "PageDown").unwrap();
+        assert_eq!(config.keybindings.zoom_out, KeyCode::PageDown);
+        config.set("keybindings.zoom_full", "{ F = 5 }").unwrap();
+        assert_eq!(config.keybindings.zoom_full, KeyCode::F(5));
+
+        config.set("ui.drag_color", "red").unwrap();
+        assert_eq!(config.ui.drag_color, "red");
+
+        // Enum settings are written without quotes
+        assert_eq!(
+            config.set("render_style", "halfblock"),
+            Ok("ui.render_style".to_string())
+        );
+        assert_eq!(config.ui.render_style, RenderStyle::HalfBlock);
+        config.set("color_mode", "mono").unwrap();
+        assert_eq!(config.ui.color_mode, ColorMode::Mono);
+        assert!(config.set("color_mode", "sepia").is_err());
+
+        config.set("marker_snap", "true").unwrap();
+        assert!(config.ui.marker_snap);
+        config.set("ui.marker_snap_distance", "4").unwrap();
+        assert_eq!(config.ui.marker_snap_distance, 4);
+        config.set("pan_fraction", "0.01").unwrap();
+        assert_eq!(config.ui.pan_fraction, 0.01);
+    }
+
+    #[test]
+    fn test_set_invalid_settings_is_err() {
+        let mut config = AppConfig::default();
+        assert_eq!(
+            config.set("ui.nope", "1"),
+            Err("Unknown setting: ui.nope".to_string())
+        );
+        assert_eq!(
+            config.set("config_path", "/tmp"),
+            Err("Unknown setting: config_path".to_string())
+        );
+        assert!(config
+            .set("ui.signal_list_width", "wide")
+            .unwrap_err()
+            .starts_with("Invalid value for ui.signal_list_width"));
+        assert!(config.set("keybindings.zoom_in", "NotAKey").is_err());
+
+        // Failed updates leave the config unchanged
+        assert_eq!(config.ui.signal_list_width, 20);
+        assert_eq!(config.keybindings.zoom_in, KeyCode::Char('+'));
+    }
+
+    #[test]
+    fn test_save_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("config.toml");
+
+        let mut config = AppConfig {
+            config_path: Some(path.clone()),
+            ..AppConfig::default()
+        };
+        config.set("ui.signal_list_width", "42").unwrap();
+        config.set("keybindings.zoom_in", "=").unwrap();
+        assert_eq!(config.save(), Ok(&path));
+
+        let loaded = load_config(Some(path.to_str().unwrap().to_string())).unwrap();
+        assert_eq!(loaded.ui.signal_list_width, 42);
+        assert_eq!(loaded.keybindings.zoom_in, KeyCode::Char('='));
+        assert_eq!(loaded.keybindings.zoom_out, KeyCode::Char('-'));
+        assert_eq!(loaded.config_path, Some(path));
+    }
+
+    const CONFLICTING_CONFIG: &str = r#"
+        [ui]
+        signal_list_width = 95
+        waveform_height = 3
+
+        [keybindings]
+        enter_command_mode = { Char = ":" }
+        zoom_in = { Char = ":" }
+        zoom_full = { Char = ":" }
+        shrink_signal_list = "Up"
+    "#;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        assert_eq!(AppConfig::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_view_keys_share_cursor_keys() {
+        // `m` puts the marker at the cursor while it's shown, and saves a view otherwise
+        let mut keybindings = KeybindingsConfig::default();
+        assert_eq!(keybindings.save_view, keybindings.cursor_to_marker);
+        assert_eq!(keybindings.conflicts(), vec![]);
+
+        keybindings.load_view = keybindings.zoom_in;
+        assert_eq!(
+            keybindings.conflicts(),
+            vec![("zoom_in", "load_view", KeyCode::Char('+'))]
+        );
+    }
+
+    #[test]
+    fn test_validate_conflicting_config_is_err() {
+        let config: AppConfig = toml::from_str(CONFLICTING_CONFIG).unwrap();
+
+        // Up is only bound once among the keys used with Ctrl, so it doesn't conflict with `up`
+        assert_eq!(
+            config.validate(),
+            Err("Invalid config: \
+                keybindings.enter_command_mode and keybindings.zoom_in are both Char(':'); \
+                keybindings.enter_command_mode and keybindings.zoom_full are both Char(':'); \
+                ui.signal_list_width must be between 1 and 90, got 95; \
+                ui.waveform_height must be 1 or 2, got 3"
+                .to_string())
+        );
+    }
+
+    #[test]
+    fn test_force_valid_disables_later_bindings() {
+        let mut config: AppConfig = toml::from_str(CONFLICTING_CONFIG).unwrap();
+        let warnings = config.force_valid();
+
+        assert_eq!(
+            warnings,
+            vec![
+                "keybindings.zoom_in disabled, Char(':') is already bound to keybindings.enter_command_mode"
+                    .to_string(),
+                "keybindings.zoom_full disabled, Char(':') is already bound to keybindings.enter_command_mode"
+                    .to_string(),
+                "ui.signal_list_width out of range, using 20".to_string(),
+                "ui.waveform_height out of range, using 2".to_string(),
+            ]
+        );
+        assert_eq!(config.keybindings.enter_command_mode, KeyCode::Char(':'));
+        assert_eq!(config.keybindings.zoom_in, KeyCode::Null);
+        assert_eq!(config.keybindings.zoom_full, KeyCode::Null);
+        assert_eq!(config.ui.signal_list_width, 20);
+        assert_eq!(config.ui.waveform_height, 2);
+        assert_eq!(config.validate(), Ok(()));
+    }
 }
@@ -6,13 +6,63 @@ pub const DRAG_DETECTED_THRESHOLD_PIXELS: i32 = 3;
 /// greater than or equal to DRAG_DETECTED_THRESHOLD_PIXELS.
 pub const DRAG_STARTED_THRESHOLD_PIXELS: i32 = 5;
 
+/// The smallest time range that zooming in with the zoom in key will reach. Other ways of zooming
+/// can go down to a range of a single time unit.
+pub const MIN_ZOOM_IN_RANGE: u64 = 10;
+
+/// The maximum number of previous views remembered for zooming back.
+pub const MAX_ZOOM_HISTORY: usize = 64;
+
+/// The maximum number of views remembered in the jump list.
+pub const MAX_JUMPS: usize = 100;
+
+/// The numbered slots views can be saved in with `:view save`, one per digit key.
+pub const VIEW_SLOTS: std::ops::RangeInclusive<u8> = 1..=9;
+
+/// How many times narrower than the whole waveform the view `ui.initial_view = "first_activity"`
+/// starts with is.
+pub const FIRST_ACTIVITY_VIEW_DIVISOR: u64 = 20;
+
+/// The maximum number of entries kept by `:trace`, after which the oldest are dropped.
+pub const MAX_TRACE_ENTRIES: usize = 1000;
+
+/// The narrowest the signal list can be made, as a percentage of the terminal width.
+pub const MIN_SIGNAL_LIST_WIDTH: u16 = 5;
+
+/// The widest the signal list can be made, as a percentage of the terminal width.
+pub const MAX_SIGNAL_LIST_WIDTH: u16 = 80;
+
+/// The fewest columns of the signal list, including its borders, that the UI is drawn with.
+pub const MIN_SIGNAL_LIST_COLUMNS: u16 = 3;
+
+/// The fewest columns of the waveform that the UI is drawn with.
+pub const MIN_WAVEFORM_COLUMNS: u16 = 10;
+
+/// The fewest rows of the waveform that the UI is drawn with, enough for the signal list's borders
+/// and one signal.
+pub const MIN_WAVEFORM_ROWS: u16 = 4;
+
+/// The percentage by which the signal list width changes with each resize key press.
+pub const SIGNAL_LIST_WIDTH_STEP: u16 = 2;
+
 /// The duration in seconds after which the toast of a command result will be hidden.
 pub const COMMAND_RESULT_HIDE_THRESHOLD_SECONDS: u64 = 3;
 
-/// The highlight color when clicking and dragging on the waveform.
-pub const DRAG_COLOR: ratatui::style::Color = ratatui::style::Color::Rgb(100, 150, 255);
+/// How long in milliseconds the loaded file must go unchanged after changing on disk before it is
+/// reloaded or a reload is offered, so a simulator writing it in several steps causes one reload.
+pub const FILE_WATCH_DEBOUNCE_MILLIS: u64 = 300;
+
+/// The maximum number of rows a command result message is wrapped across.
+pub const MAX_COMMAND_RESULT_LINES: usize = 3;
+
+/// The maximum number of recent command results stacked above the command bar.
+pub const MAX_SHOWN_COMMAND_RESULTS: usize = 3;
+
+/// The maximum number of command results remembered for `:messages`.
+pub const MAX_COMMAND_RESULTS: usize = 100;
 
-/// The height of a single wave line in terminal rows.
+/// The height of a single wave line in terminal rows, unless `ui.waveform_height` or `:height`
+/// change it.
 pub const WAVEFORM_HEIGHT: usize = 2;
 
 /// The color of the primary marker.
@@ -21,5 +71,23 @@ pub const PRIMARY_MARKER_COLOR: ratatui::style::Color = ratatui::style::Color::Y
 /// The color of the secondary marker.
 pub const SECONDARY_MARKER_COLOR: ratatui::style::Color = ratatui::style::Color::White;
 
+/// The color of unknown (X) values, unless `ui.x_color` is a color.
+pub const X_COLOR: ratatui::style::Color = ratatui::style::Color::Red;
+
+/// The color of high impedance (Z) values, unless `ui.z_color` is a color.
+pub const Z_COLOR: ratatui::style::Color = ratatui::style::Color::Magenta;
+
 /// The color of the default saved marker.
 pub const DEFAULT_SAVED_MARKER_COLOR: ratatui::style::Color = ratatui::style::Color::Cyan;
+
+/// The background color of a region.
+pub const DEFAULT_REGION_COLOR: ratatui::style::Color = ratatui::style::Color::Rgb(40, 70, 40);
+
+/// The background colors given to `:highlight` rules without a color, in order, so each rule gets
+/// one the others don't have until they run out.
+pub const HIGHLIGHT_COLORS: [ratatui::style::Color; 4] = [
+    ratatui::style::Color::Rgb(110, 90, 0),
+    ratatui::style::Color::Rgb(0, 80, 110),
+    ratatui::style::Color::Rgb(100, 40, 100),
+    ratatui::style::Color::Rgb(120, 60, 20),
+];
@@ -0,0 +1,64 @@
+pub mod spi;
+pub mod uart;
+
+use crate::parsers::types::{Value, WaveValue};
+
+/// A decoded piece of a protocol, like a byte, from `start` to `end`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    pub start: u64,
+    pub end: u64,
+    pub label: String,
+}
+
+impl Annotation {
+    pub fn new(start: u64, end: u64, label: impl Into<String>) -> Self {
+        Self {
+            start,
+            end,
+            label: label.into(),
+        }
+    }
+}
+
+/// Turns the change lists of a protocol's signals into annotations of what they carry.
+pub trait Decoder {
+    /// Annotations found in `inputs`, the change lists of the decoder's signals in the order the
+    /// decoder takes them, sorted by start time.
+    fn decode(&self, inputs: &[&[(u64, WaveValue)]]) -> Vec<Annotation>;
+}
+
+/// Level of a single bit value, or None if it is x or z or more than one bit.
+pub fn level(value: &WaveValue) -> Option<bool> {
+    match value.values(2)?.as_slice() {
+        [Value::V1] => Some(true),
+        [Value::V0] => Some(false),
+        _ => None,
+    }
+}
+
+/// Level of a signal at `time`, from the latest change at or before it.
+pub fn level_at(values: &[(u64, WaveValue)], time: u64) -> Option<bool> {
+    let index = values.partition_point(|(t, _)| *t <= time);
+    level(&values[index.checked_sub(1)?].1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_at() {
+        let values = vec![
+            (0, WaveValue::Binary(Value::V1)),
+            (10, WaveValue::Binary(Value::V0)),
+            (20, WaveValue::Binary(Value::VX)),
+            (30, WaveValue::Bus("1".into())),
+        ];
+        assert_eq!(level_at(&values, 5), Some(true));
+        assert_eq!(level_at(&values, 10), Some(false));
+        assert_eq!(level_at(&values, 25), None);
+        assert_eq!(level_at(&values, 40), Some(true));
+        assert_eq!(level_at(&values[1..], 5), None);
+    }
+}
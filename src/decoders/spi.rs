@@ -0,0 +1,129 @@
+use super::{level, level_at, Annotation, Decoder};
+use crate::parsers::types::WaveValue;
+
+/// Decodes the bytes sent on SPI in mode 0: the clock idles low and data is sampled on its rising
+/// edges, most significant bit first, while the active low chip select is low. Chip select going
+/// high drops the bits of an unfinished byte.
+pub struct SpiDecoder;
+
+impl Decoder for SpiDecoder {
+    /// Takes the clock, the data line and the chip select as its inputs.
+    fn decode(&self, inputs: &[&[(u64, WaveValue)]]) -> Vec<Annotation> {
+        let [clk, data, cs] = inputs else {
+            return Vec::new();
+        };
+
+        let mut annotations = Vec::new();
+        let mut byte = 0u8;
+        let mut bits = 0;
+        let mut byte_start = 0;
+        let mut last_edge = 0;
+        let rising_edges = clk
+            .windows(2)
+            .filter(|pair| level(&pair[0].1) == Some(false) && level(&pair[1].1) == Some(true))
+            .map(|pair| pair[1].0);
+        for edge in rising_edges {
+            if level_at(cs, edge) != Some(false) || deselected_between(cs, last_edge, edge) {
+                bits = 0;
+            }
+            last_edge = edge;
+            if level_at(cs, edge) != Some(false) {
+                continue;
+            }
+
+            if bits == 0 {
+                byte = 0;
+                byte_start = edge;
+            }
+            byte = (byte << 1) | (level_at(data, edge) == Some(true)) as u8;
+            bits += 1;
+            if bits == 8 {
+                // The last bit lasts until the clock falls again
+                let end = clk
+                    .iter()
+                    .find(|(time, value)| *time > edge && level(value) == Some(false))
+                    .map_or(edge, |(time, _)| *time);
+                annotations.push(Annotation::new(byte_start, end, format!("{:02X}", byte)));
+                bits = 0;
+            }
+        }
+        annotations
+    }
+}
+
+// Whether chip select went high after `from` and up to `to`, ending a transfer between two clock
+// edges
+fn deselected_between(cs: &[(u64, WaveValue)], from: u64, to: u64) -> bool {
+    cs.iter()
+        .any(|(time, value)| *time > from && *time <= to && level(value) != Some(false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::Value;
+
+    fn bit(high: bool) -> WaveValue {
+        WaveValue::Binary(if high { Value::V1 } else { Value::V0 })
+    }
+
+    // Clock, data and chip select sending each transfer of bits with a 10 tick clock period.
+    // Chip select is low from 5 ticks before each transfer to 5 ticks after it, with 20 ticks
+    // between transfers.
+    fn bus(transfers: &[&[bool]]) -> [Vec<(u64, WaveValue)>; 3] {
+        let (mut clk, mut data, mut cs) = (vec![(0, bit(false))], vec![(0, bit(false))], vec![]);
+        cs.push((0, bit(true)));
+        let mut time = 10;
+        for bits in transfers {
+            cs.push((time - 5, bit(false)));
+            for high in bits.iter() {
+                // Data changes while the clock is low, and is sampled when it rises
+                data.push((time, bit(*high)));
+                clk.push((time + 5, bit(true)));
+                clk.push((time + 10, bit(false)));
+                time += 10;
+            }
+            cs.push((time + 5, bit(true)));
+            time += 20;
+        }
+        [clk, data, cs]
+    }
+
+    fn byte_bits(byte: u8) -> Vec<bool> {
+        (0..8).rev().map(|i| byte >> i & 1 == 1).collect()
+    }
+
+    #[test]
+    fn test_decode_spi_bytes() {
+        let mut transfer = byte_bits(0xA5);
+        transfer.extend(byte_bits(0x3C));
+        let [clk, data, cs] = bus(&[&transfer]);
+
+        let annotations = SpiDecoder.decode(&[&clk, &data, &cs]);
+        assert_eq!(
+            annotations,
+            vec![
+                Annotation::new(15, 90, "A5"),
+                Annotation::new(95, 170, "3C"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_spi_drops_unfinished_bytes() {
+        // Only 5 bits are sent before chip select goes high
+        let [clk, data, cs] = bus(&[&byte_bits(0xFF)[..5], &byte_bits(0x81)]);
+
+        let labels: Vec<String> = SpiDecoder
+            .decode(&[&clk, &data, &cs])
+            .into_iter()
+            .map(|annotation| annotation.label)
+            .collect();
+        assert_eq!(labels, vec!["81"]);
+
+        // Clock edges while not selected are ignored
+        let mut cs = cs;
+        cs.retain(|(time, _)| *time == 0);
+        assert!(SpiDecoder.decode(&[&clk, &data, &cs]).is_empty());
+    }
+}
@@ -0,0 +1,156 @@
+use super::{level, level_at, Annotation, Decoder};
+use crate::parsers::types::WaveValue;
+
+/// Label of a byte whose stop bit wasn't high, which isn't hex so it can't be mistaken for a byte.
+pub const FRAMING_ERROR: &str = "ERR";
+
+/// Decodes the bytes on a UART receive line with 8 data bits, no parity and one stop bit. The line
+/// idles high, and each byte starts with a falling edge into the start bit. Bits are sampled in
+/// their middle, least significant first.
+pub struct UartDecoder {
+    /// Length of one bit in ticks
+    bit_ticks: f64,
+}
+
+impl UartDecoder {
+    pub fn new(bit_ticks: f64) -> Self {
+        Self { bit_ticks }
+    }
+
+    /// Decoder for `baud` bits per second on a waveform whose ticks are `timescale_fs`
+    /// femtoseconds long. Fails if a bit would be shorter than a tick.
+    pub fn with_baud(baud: u64, timescale_fs: u64) -> Result<Self, String> {
+        if baud == 0 {
+            return Err("The baud rate must be more than 0".to_string());
+        }
+        let bit_ticks = 1e15 / baud as f64 / timescale_fs as f64;
+        if bit_ticks < 1.0 {
+            return Err(format!(
+                "A bit at {} baud is shorter than one tick of the waveform",
+                baud
+            ));
+        }
+        Ok(Self::new(bit_ticks))
+    }
+
+    // Time of the middle of bit `bit` of a byte starting at `start`, counting the start bit as 0
+    fn sample_time(&self, start: u64, bit: u32) -> u64 {
+        start + ((bit as f64 + 0.5) * self.bit_ticks) as u64
+    }
+}
+
+impl Decoder for UartDecoder {
+    /// Takes the receive line as its only input.
+    fn decode(&self, inputs: &[&[(u64, WaveValue)]]) -> Vec<Annotation> {
+        let Some(rx) = inputs.first() else {
+            return Vec::new();
+        };
+
+        let mut annotations = Vec::new();
+        // Bytes can only start once the previous one is over
+        let mut idle_from = 0;
+        for pair in rx.windows(2) {
+            let start = pair[1].0;
+            let falling = level(&pair[0].1) == Some(true) && level(&pair[1].1) == Some(false);
+            if !falling || start < idle_from {
+                continue;
+            }
+            // A glitch that is over before the middle of the start bit isn't a start bit
+            if level_at(rx, self.sample_time(start, 0)) != Some(false) {
+                continue;
+            }
+
+            let byte = (0..8).fold(0u8, |byte, bit| {
+                let high = level_at(rx, self.sample_time(start, bit + 1)) == Some(true);
+                byte | ((high as u8) << bit)
+            });
+            let end = start + (10.0 * self.bit_ticks) as u64;
+            let label = if level_at(rx, self.sample_time(start, 9)) == Some(true) {
+                format!("{:02X}", byte)
+            } else {
+                FRAMING_ERROR.to_string()
+            };
+            annotations.push(Annotation::new(start, end, label));
+            idle_from = self.sample_time(start, 9);
+        }
+        annotations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::Value;
+
+    // Changes of a receive line sending `frames` of (byte, stop bit level) with 10 tick bits,
+    // starting at `start` with a 20 tick gap after each
+    fn rx_line(start: u64, frames: &[(u8, bool)]) -> Vec<(u64, WaveValue)> {
+        let bit = |high: bool| WaveValue::Binary(if high { Value::V1 } else { Value::V0 });
+        let mut values = vec![(0, bit(true))];
+        let mut time = start;
+        for (byte, stop) in frames {
+            let mut levels = vec![false];
+            levels.extend((0..8).map(|i| byte >> i & 1 == 1));
+            levels.push(*stop);
+            for high in levels {
+                values.push((time, bit(high)));
+                time += 10;
+            }
+            values.push((time, bit(true)));
+            time += 20;
+        }
+        values
+    }
+
+    #[test]
+    fn test_decode_uart_bytes() {
+        let rx = rx_line(100, &[(0x41, true), (0xA5, true), (0x00, true)]);
+        let annotations = UartDecoder::new(10.0).decode(&[&rx]);
+        assert_eq!(
+            annotations,
+            vec![
+                Annotation::new(100, 200, "41"),
+                Annotation::new(220, 320, "A5"),
+                Annotation::new(340, 440, "00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_uart_framing_error() {
+        // The second byte's stop bit is low, and the line only goes high again later
+        let mut rx = rx_line(100, &[(0x3C, true), (0xFF, false)]);
+        rx.retain(|(time, _)| *time != 320);
+        rx.push((400, WaveValue::Binary(Value::V1)));
+        rx.extend(rx_line(450, &[(0x7E, true)]).into_iter().skip(1));
+
+        let labels: Vec<String> = UartDecoder::new(10.0)
+            .decode(&[&rx])
+            .into_iter()
+            .map(|annotation| annotation.label)
+            .collect();
+        assert_eq!(labels, vec!["3C", FRAMING_ERROR, "7E"]);
+    }
+
+    #[test]
+    fn test_decode_uart_ignores_glitches() {
+        let mut rx = rx_line(100, &[(0x55, true)]);
+        rx.insert(1, (40, WaveValue::Binary(Value::V0)));
+        rx.insert(2, (42, WaveValue::Binary(Value::V1)));
+
+        let annotations = UartDecoder::new(10.0).decode(&[&rx]);
+        assert_eq!(annotations, vec![Annotation::new(100, 200, "55")]);
+    }
+
+    #[test]
+    fn test_uart_bit_time_from_baud() {
+        // 1 ns ticks at 1 Mbaud
+        let decoder = UartDecoder::with_baud(1_000_000, 1_000_000).unwrap();
+        assert_eq!(decoder.bit_ticks, 1000.0);
+        assert!(UartDecoder::with_baud(0, 1_000_000).is_err());
+        assert_eq!(
+            UartDecoder::with_baud(9600, 1_000_000_000_000_000).err(),
+            Some("A bit at 9600 baud is shorter than one tick of the waveform".to_string())
+        );
+    }
+}
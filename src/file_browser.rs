@@ -0,0 +1,220 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// How many recently opened files are remembered.
+pub const MAX_RECENT_FILES: usize = 10;
+
+/// Extensions of the waveform files listed from the current directory.
+const WAVEFORM_EXTENSIONS: [&str; 2] = ["vcd", "fst"];
+
+/// A file that was opened successfully, and when, in seconds since the Unix epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentFile {
+    pub path: String,
+    pub opened_at: u64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct RecentFiles {
+    #[serde(default)]
+    files: Vec<RecentFile>,
+}
+
+/// Reads the recent files list, most recent first. A missing or unreadable file is an empty list.
+pub fn load_recent_files(path: &Path) -> Vec<RecentFile> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| toml::from_str::<RecentFiles>(&content).ok())
+        .map(|recent| recent.files)
+        .unwrap_or_default()
+}
+
+pub fn save_recent_files(path: &Path, files: &[RecentFile]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string(&RecentFiles {
+        files: files.to_vec(),
+    })
+    .map_err(io::Error::other)?;
+    fs::write(path, content)
+}
+
+/// Moves `path` to the front of `files` with the time it was opened, keeping at most
+/// `MAX_RECENT_FILES` entries.
+pub fn record_recent_file(files: &mut Vec<RecentFile>, path: &str, opened_at: u64) {
+    files.retain(|file| file.path != path);
+    files.insert(
+        0,
+        RecentFile {
+            path: path.to_string(),
+            opened_at,
+        },
+    );
+    files.truncate(MAX_RECENT_FILES);
+}
+
+pub fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// Names of the waveform files in `dir`, sorted.
+pub fn list_waveform_files(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_file())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| WAVEFORM_EXTENSIONS.contains(&ext))
+        })
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    names.sort();
+    names
+}
+
+/// State of the file picker shown when no file is loaded. The listing has the waveform files in
+/// `dir` followed by the recent files.
+#[derive(Default)]
+pub struct FileBrowserState {
+    pub dir: PathBuf,
+    pub local_files: Vec<String>,
+    pub recent_files: Vec<RecentFile>,
+    pub selected: usize,
+    /// The path being typed after pressing '/', if any
+    pub path_input: Option<String>,
+}
+
+impl FileBrowserState {
+    /// Lists the waveform files in `dir` again and selects the first entry.
+    pub fn refresh(&mut self, dir: &Path) {
+        self.dir = dir.to_path_buf();
+        self.local_files = list_waveform_files(dir);
+        self.selected = 0;
+        self.path_input = None;
+    }
+
+    pub fn entry_count(&self) -> usize {
+        self.local_files.len() + self.recent_files.len()
+    }
+
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.entry_count() {
+            self.selected += 1;
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    /// Path of the selected entry. Local files are relative to `dir`.
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        match self.local_files.get(self.selected) {
+            Some(name) => Some(self.dir.join(name)),
+            None => self
+                .recent_files
+                .get(self.selected - self.local_files.len())
+                .map(|file| PathBuf::from(&file.path)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_recent_files_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("recent_files.toml");
+
+        let mut files = Vec::new();
+        record_recent_file(&mut files, "/tmp/a.vcd", 100);
+        record_recent_file(&mut files, "/tmp/b.vcd", 200);
+        save_recent_files(&path, &files).unwrap();
+
+        assert_eq!(load_recent_files(&path), files);
+        assert_eq!(files[0].path, "/tmp/b.vcd");
+    }
+
+    #[test]
+    fn test_missing_recent_files_is_empty() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_recent_files(&dir.path().join("recent_files.toml")).is_empty());
+    }
+
+    #[test]
+    fn test_record_recent_file_moves_to_front_and_caps() {
+        let mut files = Vec::new();
+        for i in 0..12 {
+            record_recent_file(&mut files, &format!("/tmp/{}.vcd", i), i);
+        }
+        assert_eq!(files.len(), MAX_RECENT_FILES);
+        assert_eq!(files[0].path, "/tmp/11.vcd");
+        assert_eq!(files[9].path, "/tmp/2.vcd");
+
+        // Opening a file again moves it to the front with the new time instead of duplicating it
+        record_recent_file(&mut files, "/tmp/5.vcd", 50);
+        assert_eq!(files.len(), MAX_RECENT_FILES);
+        assert_eq!(
+            files[0],
+            RecentFile {
+                path: "/tmp/5.vcd".to_string(),
+                opened_at: 50
+            }
+        );
+        assert_eq!(files.iter().filter(|f| f.path == "/tmp/5.vcd").count(), 1);
+    }
+
+    #[test]
+    fn test_list_waveform_files() {
+        let dir = TempDir::new().unwrap();
+        for name in ["b.vcd", "a.fst", "notes.txt", "c.vcd"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        fs::create_dir(dir.path().join("dir.vcd")).unwrap();
+
+        assert_eq!(
+            list_waveform_files(dir.path()),
+            vec![
+                "a.fst".to_string(),
+                "b.vcd".to_string(),
+                "c.vcd".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_selected_path_spans_local_and_recent() {
+        let mut state = FileBrowserState {
+            dir: PathBuf::from("/work"),
+            local_files: vec!["a.vcd".to_string()],
+            ..Default::default()
+        };
+        record_recent_file(&mut state.recent_files, "/old/b.vcd", 1);
+
+        assert_eq!(state.selected_path(), Some(PathBuf::from("/work/a.vcd")));
+        state.select_next();
+        assert_eq!(state.selected_path(), Some(PathBuf::from("/old/b.vcd")));
+        state.select_next();
+        assert_eq!(state.selected, 1);
+        state.select_previous();
+        state.select_previous();
+        assert_eq!(state.selected, 0);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::constants::FILE_WATCH_DEBOUNCE_MILLIS;
+use notify::{event::ModifyKind, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver},
+    time::{Duration, Instant},
+};
+
+/// Notices when a file is written to, without blocking. Dropping it stops watching.
+pub struct FileWatcher {
+    path: PathBuf,
+    // Sends the events read by `events`, from a thread of its own
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    debounce: Debounce,
+}
+
+impl FileWatcher {
+    pub fn new(path: &Path) -> notify::Result<Self> {
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(sender)?;
+        // Watch the directory, so the file is still seen when it's replaced by a new one with the
+        // same name rather than written in place
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+        Ok(FileWatcher {
+            path: path.to_path_buf(),
+            _watcher: watcher,
+            events,
+            debounce: Debounce::new(Duration::from_millis(FILE_WATCH_DEBOUNCE_MILLIS)),
+        })
+    }
+
+    /// Reads the events that arrived since the last poll. Returns true once the file has changed
+    /// and then gone unchanged for a while.
+    pub fn poll(&mut self, now: Instant) -> bool {
+        while let Ok(event) = self.events.try_recv() {
+            if event.is_ok_and(|event| self.is_change(&event)) {
+                self.debounce.record(now);
+            }
+        }
+        self.debounce.ready(now)
+    }
+
+    // Whether an event in the watched directory changed the contents of the file
+    fn is_change(&self, event: &Event) -> bool {
+        let changes_contents = match event.kind {
+            EventKind::Modify(ModifyKind::Metadata(_)) => false,
+            EventKind::Create(_) | EventKind::Modify(_) => true,
+            _ => false,
+        };
+        changes_contents
+            && event
+                .paths
+                .iter()
+                .any(|path| path.file_name() == self.path.file_name())
+    }
+}
+
+/// Turns a burst of changes into a single one, reported once no more have come for `delay`.
+#[derive(Debug)]
+pub struct Debounce {
+    delay: Duration,
+    last_change: Option<Instant>,
+}
+
+impl Debounce {
+    pub fn new(delay: Duration) -> Self {
+        Debounce {
+            delay,
+            last_change: None,
+        }
+    }
+
+    pub fn record(&mut self, now: Instant) {
+        self.last_change = Some(now);
+    }
+
+    /// Whether the changes recorded so far have settled. True only once for each burst.
+    pub fn ready(&mut self, now: Instant) -> bool {
+        match self.last_change {
+            Some(last_change) if now.saturating_duration_since(last_change) >= self.delay => {
+                self.last_change = None;
+                true
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debounce_reports_a_burst_once() {
+        let delay = Duration::from_millis(100);
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        let mut debounce = Debounce::new(delay);
+        assert!(!debounce.ready(at(0)));
+
+        // Writes closer together than the delay keep putting it off
+        debounce.record(at(0));
+        assert!(!debounce.ready(at(50)));
+        debounce.record(at(60));
+        assert!(!debounce.ready(at(120)));
+        debounce.record(at(130));
+        assert!(!debounce.ready(at(229)));
+        assert!(debounce.ready(at(230)));
+        assert!(!debounce.ready(at(400)));
+
+        // A later write is reported again
+        debounce.record(at(500));
+        assert!(!debounce.ready(at(500)));
+        assert!(debounce.ready(at(700)));
+    }
+}
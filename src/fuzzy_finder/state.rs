@@ -1,7 +1,9 @@
 use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use ratatui::widgets::ListState;
-use std::collections::HashSet;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Default)]
 pub struct FuzzyFinderState {
@@ -11,6 +13,18 @@ pub struct FuzzyFinderState {
     pub all_signals: Vec<String>,
     pub selected_signals: HashSet<String>,
     pub matcher: SkimMatcherV2,
+    /// Query that `filtered_signals` was filtered with
+    filtered_query: String,
+    /// Indices into `all_signals` of the signals matching `filtered_query`, in their original
+    /// order. A query extending `filtered_query` only needs to look at these.
+    matched: Vec<usize>,
+    /// Whether matches are sorted by their number of changes rather than their match score
+    pub sort_by_activity: bool,
+    /// Number of changes of each signal, used while sorting by activity
+    change_counts: HashMap<String, usize>,
+    /// Whether `query` changed since the signals were last filtered. Filtering waits until the
+    /// results are needed, so that keys typed in quick succession are filtered for only once.
+    filter_pending: bool,
 }
 
 impl FuzzyFinderState {
@@ -20,6 +34,9 @@ impl FuzzyFinderState {
         // Mark currently displayed signals as selected
         self.selected_signals = displayed_signals.iter().cloned().collect();
 
+        // The previous matches are indices into the old signals
+        self.filtered_query.clear();
+        self.matched.clear();
         self.update_filtered_signals();
 
         // Set initial selection if there are items
@@ -30,17 +47,32 @@ impl FuzzyFinderState {
 
     pub fn handle_input(&mut self, c: char) {
         self.query.push(c);
-        self.update_filtered_signals();
+        self.filter_pending = true;
     }
 
     pub fn handle_backspace(&mut self) {
         if !self.query.is_empty() {
             self.query.pop();
+            self.filter_pending = true;
+        }
+    }
+
+    /// Switches between sorting matches by match score and by `change_counts`, busiest first.
+    pub fn toggle_sort_by_activity(&mut self, change_counts: HashMap<String, usize>) {
+        self.sort_by_activity = !self.sort_by_activity;
+        self.change_counts = change_counts;
+        self.update_filtered_signals();
+    }
+
+    /// Filters the signals for the query if it changed since they were last filtered.
+    pub fn apply_pending_filter(&mut self) {
+        if self.filter_pending {
             self.update_filtered_signals();
         }
     }
 
     pub fn select_next(&mut self) {
+        self.apply_pending_filter();
         let i = match self.list_state.selected() {
             Some(i) => {
                 if i >= self.filtered_signals.len().saturating_sub(1) {
@@ -55,6 +87,7 @@ impl FuzzyFinderState {
     }
 
     pub fn select_previous(&mut self) {
+        self.apply_pending_filter();
         let i = match self.list_state.selected() {
             Some(i) => {
                 if i == 0 {
@@ -69,6 +102,7 @@ impl FuzzyFinderState {
     }
 
     pub fn toggle_selected_signal(&mut self) {
+        self.apply_pending_filter();
         if let Some(idx) = self.list_state.selected() {
             if let Some(signal) = self.filtered_signals.get(idx) {
                 if self.selected_signals.contains(signal) {
@@ -80,41 +114,56 @@ impl FuzzyFinderState {
         }
     }
 
-    pub fn get_selected_signals(&self) -> Vec<String> {
-        self.selected_signals.iter().cloned().collect()
-    }
-
     pub fn clear_selection(&mut self) {
         self.selected_signals.clear();
     }
 
     pub fn select_all(&mut self) {
+        self.apply_pending_filter();
         for signal in &self.filtered_signals {
             self.selected_signals.insert(signal.clone());
         }
     }
 
     fn update_filtered_signals(&mut self) {
+        self.filter_pending = false;
+
         if self.query.is_empty() {
             // If query is empty, show all signals
             self.filtered_signals = self.all_signals.clone();
+            self.matched = (0..self.all_signals.len()).collect();
         } else {
-            // Otherwise, filter signals based on fuzzy matching
-            let mut matches: Vec<(String, i64)> = self
-                .all_signals
-                .iter()
-                .filter_map(|signal| {
-                    self.matcher
-                        .fuzzy_match(signal, &self.query)
-                        .map(|score| (signal.clone(), score))
-                })
-                .collect();
-
-            // Sort by match score (descending)
-            matches.sort_by(|a, b| b.1.cmp(&a.1));
+            // A signal matching the query also matches every prefix of it, so a longer query only
+            // needs to look at the signals that matched the last one
+            let candidates = if !self.filtered_query.is_empty()
+                && self.query.starts_with(&self.filtered_query)
+            {
+                std::mem::take(&mut self.matched)
+            } else {
+                (0..self.all_signals.len()).collect()
+            };
+
+            // Filter signals based on fuzzy matching
+            let mut matches = self.match_candidates(&candidates);
+            self.matched = matches.iter().map(|(i, _)| *i).collect();
+
+            // Sort by match score (descending). The sort is stable, so equal scores keep the
+            // original signal order.
+            matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
 
             // Extract just the signal names
-            self.filtered_signals = matches.into_iter().map(|(signal, _)| signal).collect();
+            self.filtered_signals = matches
+                .into_iter()
+                .map(|(i, _)| self.all_signals[i].clone())
+                .collect();
+        }
+        self.filtered_query = self.query.clone();
+
+        // The sort is stable, so equally busy signals keep their match order
+        if self.sort_by_activity {
+            let counts = &self.change_counts;
+            self.filtered_signals
+                .sort_by_key(|signal| std::cmp::Reverse(counts.get(signal).copied().unwrap_or(0)));
         }
 
         // Adjust selection if necessary
@@ -130,6 +179,32 @@ impl FuzzyFinderState {
             self.list_state.select(Some(0));
         }
     }
+
+    // Helper function to score the signals at `candidates` against the query, keeping the ones that
+    // match in the order of `candidates`
+    #[cfg(not(feature = "parallel"))]
+    fn match_candidates(&self, candidates: &[usize]) -> Vec<(usize, i64)> {
+        candidates
+            .iter()
+            .filter_map(|&i| {
+                self.matcher
+                    .fuzzy_match(&self.all_signals[i], &self.query)
+                    .map(|score| (i, score))
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "parallel")]
+    fn match_candidates(&self, candidates: &[usize]) -> Vec<(usize, i64)> {
+        candidates
+            .par_iter()
+            .filter_map(|&i| {
+                self.matcher
+                    .fuzzy_match(&self.all_signals[i], &self.query)
+                    .map(|score| (i, score))
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -167,7 +242,7 @@ mod tests {
         // Test fuzzy matching
         state.query = "tsg".to_string(); // should match test_signal
         state.update_filtered_signals();
-        assert!(state.filtered_signals.len() > 0);
+        assert!(!state.filtered_signals.is_empty());
 
         // Test selection operations
         state.query = "".to_string();
@@ -216,4 +291,103 @@ mod tests {
         state.select_next();
         assert_eq!(state.list_state.selected(), Some(0)); // Wrapped to first
     }
+
+    #[test]
+    fn test_incremental_filtering_matches_naive_filtering() {
+        let signals: Vec<String> = (0..50_000)
+            .map(|i| format!("soc.cluster{}.core{}.lsu.data_{}", i % 7, i % 13, i))
+            .collect();
+        let mut state = FuzzyFinderState::default();
+        state.set_signals(signals.clone(), &[]);
+
+        let naive = |query: &str| -> Vec<String> {
+            let matcher = SkimMatcherV2::default();
+            let mut matches: Vec<(&String, i64)> = signals
+                .iter()
+                .filter_map(|signal| matcher.fuzzy_match(signal, query).map(|s| (signal, s)))
+                .collect();
+            matches.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+            matches
+                .into_iter()
+                .map(|(signal, _)| signal.clone())
+                .collect()
+        };
+
+        // Each character filters the matches of the query before it
+        for c in "core12lsu4".chars() {
+            state.handle_input(c);
+            state.apply_pending_filter();
+        }
+        assert_eq!(state.filtered_signals, naive("core12lsu4"));
+
+        // Removing characters filters all signals again
+        for _ in 0..4 {
+            state.handle_backspace();
+        }
+        state.apply_pending_filter();
+        assert_eq!(state.query, "core12");
+        assert_eq!(state.filtered_signals, naive("core12"));
+    }
+
+    #[test]
+    fn test_filtering_waits_until_results_are_needed() {
+        let mut state = FuzzyFinderState::default();
+        let signals = vec!["alpha".to_string(), "beta".to_string()];
+        state.set_signals(signals, &["beta".to_string()]);
+
+        for c in "be".chars() {
+            state.handle_input(c);
+        }
+        assert_eq!(state.filtered_signals.len(), 2);
+
+        // Toggling filters first, so it acts on the signal shown for the query
+        state.toggle_selected_signal();
+        assert_eq!(state.filtered_signals, vec!["beta"]);
+        assert!(state.selected_signals.is_empty());
+    }
+
+    #[test]
+    fn test_sort_by_activity_toggle() {
+        let mut state = FuzzyFinderState::default();
+        let signals = vec![
+            "top.data_valid".to_string(),
+            "top.data".to_string(),
+            "top.data_ready".to_string(),
+            "top.clk".to_string(),
+        ];
+        state.set_signals(signals, &[]);
+        for c in "data".chars() {
+            state.handle_input(c);
+        }
+        state.apply_pending_filter();
+        let by_score = state.filtered_signals.clone();
+        assert_eq!(by_score.len(), 3);
+
+        let change_counts = HashMap::from([
+            ("top.data_valid".to_string(), 4),
+            ("top.data".to_string(), 0),
+            ("top.data_ready".to_string(), 1200),
+            ("top.clk".to_string(), 5000),
+        ]);
+        state.toggle_sort_by_activity(change_counts);
+        assert!(state.sort_by_activity);
+        assert_eq!(
+            state.filtered_signals,
+            vec!["top.data_ready", "top.data_valid", "top.data"]
+        );
+
+        // Typing keeps the activity order
+        state.handle_input('_');
+        state.apply_pending_filter();
+        assert_eq!(
+            state.filtered_signals,
+            vec!["top.data_ready", "top.data_valid"]
+        );
+        state.handle_backspace();
+
+        // Toggling again goes back to the match score order
+        state.toggle_sort_by_activity(HashMap::new());
+        assert!(!state.sort_by_activity);
+        assert_eq!(state.filtered_signals, by_score);
+    }
 }
@@ -0,0 +1,190 @@
+use crate::{
+    constants::FIRST_ACTIVITY_VIEW_DIVISOR,
+    parsers::types::{parse_time, WaveformData},
+};
+
+/// Which part of a waveform is shown when it's loaded, as `ui.initial_view` asks.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum InitialView {
+    /// All of it
+    Full,
+    /// The last this many ticks
+    Last(u64),
+    /// Around the first time a signal changes from the value it started with
+    FirstActivity,
+    /// From a start to an end time
+    Window(u64, u64),
+}
+
+impl InitialView {
+    /// Parses `full`, `last:<duration>`, `first_activity` or `window:<start>:<end>`. Times are
+    /// ticks or take a unit like 1.2us, which needs the waveform's timescale.
+    pub fn parse(spec: &str, timescale_fs: Option<u64>) -> Result<Self, String> {
+        let invalid = |reason: String| format!("Invalid ui.initial_view \"{}\": {}", spec, reason);
+        let time = |text: &str| parse_time(text, timescale_fs).map_err(invalid);
+
+        match spec.split(':').collect::<Vec<_>>()[..] {
+            ["full"] => Ok(InitialView::Full),
+            ["first_activity"] => Ok(InitialView::FirstActivity),
+            ["last", duration] => match time(duration)? {
+                0 => Err(invalid("the duration must be more than 0".to_string())),
+                duration => Ok(InitialView::Last(duration)),
+            },
+            ["window", start, end] => {
+                let (start, end) = (time(start)?, time(end)?);
+                if end <= start {
+                    return Err(invalid("the end must be after the start".to_string()));
+                }
+                Ok(InitialView::Window(start, end))
+            }
+            _ => Err(invalid(
+                "expected full, last:<duration>, first_activity or window:<start>:<end>"
+                    .to_string(),
+            )),
+        }
+    }
+
+    /// Start and range of the view over `data`, within the part of the file that was loaded.
+    pub fn window(self, data: &WaveformData) -> (u64, u64) {
+        let (first, last) = data.window.unwrap_or((0, data.max_time));
+        let full = (first, last - first);
+        match self {
+            InitialView::Full => full,
+            InitialView::Last(duration) => {
+                let start = last.saturating_sub(duration).max(first);
+                (start, last - start)
+            }
+            InitialView::FirstActivity => match first_activity(data) {
+                Some(time) => {
+                    let range = ((last - first) / FIRST_ACTIVITY_VIEW_DIVISOR).max(1);
+                    let start = time.saturating_sub(range / 2).max(first);
+                    (start, range)
+                }
+                None => full,
+            },
+            InitialView::Window(start, end) => (start, end - start),
+        }
+    }
+}
+
+// Earliest time any signal changes from the value it started with, if any does
+fn first_activity(data: &WaveformData) -> Option<u64> {
+    data.values
+        .values()
+        .filter_map(|values| values.get(1).map(|(time, _)| *time))
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+
+    fn waveform(changes: &[(&str, &[u64])], max_time: u64) -> WaveformData {
+        let mut data = WaveformData {
+            max_time,
+            ..Default::default()
+        };
+        for (signal, times) in changes {
+            let values = times
+                .iter()
+                .enumerate()
+                .map(|(i, time)| {
+                    let value = if i % 2 == 0 { Value::V0 } else { Value::V1 };
+                    (*time, WaveValue::Binary(value))
+                })
+                .collect();
+            data.values.insert(signal.to_string(), values);
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_initial_view() {
+        assert_eq!(InitialView::parse("full", None), Ok(InitialView::Full));
+        assert_eq!(
+            InitialView::parse("last:1000", None),
+            Ok(InitialView::Last(1000))
+        );
+        assert_eq!(
+            InitialView::parse("last:2ns", Some(1000)),
+            Ok(InitialView::Last(2000))
+        );
+        assert_eq!(
+            InitialView::parse("first_activity", None),
+            Ok(InitialView::FirstActivity)
+        );
+        assert_eq!(
+            InitialView::parse("window:100:250", None),
+            Ok(InitialView::Window(100, 250))
+        );
+
+        assert_eq!(
+            InitialView::parse("middle", None),
+            Err(
+                "Invalid ui.initial_view \"middle\": expected full, last:<duration>, \
+                 first_activity or window:<start>:<end>"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            InitialView::parse("last:soon", None),
+            Err("Invalid ui.initial_view \"last:soon\": Invalid time: soon".to_string())
+        );
+        assert_eq!(
+            InitialView::parse("last:0", None),
+            Err("Invalid ui.initial_view \"last:0\": the duration must be more than 0".to_string())
+        );
+        assert_eq!(
+            InitialView::parse("window:250:100", None),
+            Err(
+                "Invalid ui.initial_view \"window:250:100\": the end must be after the start"
+                    .to_string()
+            )
+        );
+        assert!(InitialView::parse("window:100", None).is_err());
+    }
+
+    #[test]
+    fn test_full_and_window_views() {
+        let data = waveform(&[("clk", &[0, 10, 20])], 5000);
+        assert_eq!(InitialView::Full.window(&data), (0, 5000));
+        assert_eq!(InitialView::Window(100, 250).window(&data), (100, 150));
+
+        // Only part of the file was loaded
+        let data = WaveformData {
+            window: Some((1000, 3000)),
+            ..data
+        };
+        assert_eq!(InitialView::Full.window(&data), (1000, 2000));
+    }
+
+    #[test]
+    fn test_last_view() {
+        let data = waveform(&[("clk", &[0, 10, 20])], 5000);
+        assert_eq!(InitialView::Last(1000).window(&data), (4000, 1000));
+
+        // Longer than the trace shows all of it
+        assert_eq!(InitialView::Last(8000).window(&data), (0, 5000));
+        let data = WaveformData {
+            window: Some((1000, 3000)),
+            ..data
+        };
+        assert_eq!(InitialView::Last(8000).window(&data), (1000, 2000));
+    }
+
+    #[test]
+    fn test_first_activity_view() {
+        let data = waveform(&[("reset", &[0, 3000]), ("clk", &[0, 2000, 2100])], 10000);
+        // A twentieth of the trace, centered on the first change
+        assert_eq!(InitialView::FirstActivity.window(&data), (1750, 500));
+
+        // A change at 0 can't be centered, so the view starts there
+        let data = waveform(&[("reset", &[0, 0, 3000]), ("clk", &[0, 2000])], 10000);
+        assert_eq!(InitialView::FirstActivity.window(&data), (0, 500));
+
+        // Nothing changes, so there's nothing better than all of it
+        let data = waveform(&[("tied", &[0])], 10000);
+        assert_eq!(InitialView::FirstActivity.window(&data), (0, 10000));
+    }
+}
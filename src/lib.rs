@@ -0,0 +1,37 @@
+//! A TUI digital signal waveform viewer.
+//!
+//! The `digisurf` binary is a thin wrapper around this library. [`app::App`] holds everything the
+//! viewer shows and renders as a ratatui widget, [`parsers`] reads VCD files into
+//! [`parsers::types::WaveformData`], and every `:` command is a
+//! [`command_mode::registry::Command`] registered by [`commands::register_all_commands`]. The
+//! [`command_mode`] module itself is generic over the state commands act on, so it can be used by
+//! other ratatui apps.
+
+pub mod app;
+mod autopilot;
+mod bit_split;
+pub mod check;
+pub mod cli;
+mod clipboard;
+mod command_aliases;
+pub mod command_mode;
+pub mod commands;
+pub mod config;
+pub mod constants;
+mod decoders;
+mod file_browser;
+mod file_watcher;
+mod fuzzy_finder;
+mod initial_view;
+pub mod parsers;
+pub mod query;
+mod session;
+pub mod signal_dump;
+mod signal_groups;
+mod signal_names;
+pub mod state;
+mod stats;
+pub mod terminal;
+mod time_format;
+pub mod types;
+mod ui;
@@ -1,70 +1,223 @@
-mod app;
-mod cli;
-mod command_mode;
-mod commands;
-mod config;
-mod constants;
-mod fuzzy_finder;
-mod parsers;
-mod state;
-mod types;
-mod ui;
-use app::App;
 use clap::Parser;
-use cli::CliArgs;
-use crossterm::{
-    event::{DisableMouseCapture, EnableMouseCapture},
-    execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+use digisurf::{
+    app::App,
+    check,
+    cli::{self, CliArgs},
+    config, query, signal_dump, terminal,
+    types::AppMode,
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::{
+    env,
+    error::Error,
+    fs,
+    io::{self, BufRead, Write},
+    path::Path,
+    process,
+    time::Duration,
 };
-use std::{error::Error, io};
 
 fn main() -> Result<(), Box<dyn Error>> {
     let args = CliArgs::parse();
-    let config = config::load_config(args.config_file)?;
 
-    let mut app = App::with_config(config);
-    if let Some(file_path) = args.file_name {
-        let result = if file_path.ends_with(".vcd") {
-            app.load_vcd_file(file_path)
+    // A check is a lint step for scripts, so it needs neither the config nor the terminal
+    if args.check {
+        let Some(file_name) = args.file() else {
+            eprintln!("Error: --check needs a file");
+            process::exit(2);
+        };
+        let report = check::check(file_name, io::stdin().lock());
+        if args.json {
+            println!("{}", report.json());
         } else {
-            Err(io::Error::new(
-                io::ErrorKind::InvalidInput,
-                "Unsupported file format. Only .vcd files are supported.",
-            ))
+            println!("{}", report.text());
+        }
+        process::exit(report.status.exit_code());
+    }
+
+    // Only the header is read, so the signals of even huge files can be piped to grep at once
+    if args.dump_signals {
+        let Some(file_name) = args.file() else {
+            eprintln!("Error: --dump-signals needs a file");
+            process::exit(2);
         };
+        match signal_dump::dump_signals(file_name, io::stdin().lock()) {
+            Ok(definitions) => {
+                let output = signal_dump::render(&definitions, args.tree, args.json);
+                // Piping into `head` closes the pipe early, which isn't an error
+                match writeln!(io::stdout(), "{}", output) {
+                    Err(e) if e.kind() != io::ErrorKind::BrokenPipe => return Err(e.into()),
+                    _ => return Ok(()),
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(2);
+            }
+        }
+    }
+
+    let file_name = args.file().map(str::to_string);
+    let mut config = config::load_config(args.config_file)?;
+    let config_warnings = if args.force_config {
+        config.force_valid()
+    } else {
+        config.validate()?;
+        Vec::new()
+    };
 
-        match result {
-            Ok(_) => {}
+    let mut app = App::with_config(config);
+    // https://no-color.org: any non-empty value turns colors off
+    app.state.no_color = env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    app.set_recent_files_path(config::recent_files_path());
+    app.set_signal_groups_path(config::signal_groups_path());
+    app.set_command_aliases_path(config::command_aliases_path());
+    app.state.session_path = config::session_path();
+    app.set_load_window(args.from.as_deref(), args.to.as_deref());
+    if let Some(file_name) = &file_name {
+        match load_waveform(&mut app, file_name, io::stdin().lock()) {
+            Ok(_) => match app.apply_view_args(
+                args.start.as_deref(),
+                args.end.as_deref(),
+                args.signals.as_deref(),
+            ) {
+                Ok(warnings) => {
+                    for warning in warnings {
+                        eprintln!("Warning: {}", warning);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    process::exit(1);
+                }
+            },
             Err(e) => {
                 eprintln!("Error loading waveform file: {}", e);
+                if !args.queries.is_empty() {
+                    process::exit(1);
+                }
             }
         }
     }
 
-    // Terminal setup
-    enable_raw_mode()?;
-    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
-    let terminal = ratatui::init();
+    // Queries are answered without ever touching the terminal, so the output can be piped
+    if !args.queries.is_empty() {
+        run_queries(&app, &args.queries);
+        return Ok(());
+    }
 
-    let app_result = app.run(terminal);
-    ratatui::restore();
+    let mut commands = Vec::new();
+    if let Some(script_path) = args.script {
+        commands.extend(cli::script_commands(&fs::read_to_string(script_path)?));
+    }
+    commands.extend(args.commands);
+    app.set_exit_limits(args.exit_after.map(Duration::from_millis), args.frames);
+    if args.frame_per_command {
+        app.queue_startup_commands(commands);
+    } else if let Err(e) = app.run_startup_commands(&commands) {
+        eprintln!("Error running command {}", e);
+        process::exit(1);
+    }
+
+    // Without a file there is nothing to show, so start by picking one
+    if file_name.is_none() && app.state.mode == AppMode::Normal {
+        app.open_file_browser(Path::new("."));
+    }
+
+    if !config_warnings.is_empty() {
+        app.show_result(config_warnings.join("; "), true);
+    }
+
+    if file_name.as_deref() == Some(cli::STDIN_FILE_NAME) {
+        if let Err(e) = check_terminal_input() {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    }
+
+    run_interactive(app)
+}
+
+// Loads the waveform named on the command line, reading it all from `stdin` if it is named `-`.
+// This has to happen before the terminal is put in raw mode.
+fn load_waveform(app: &mut App, file_name: &str, stdin: impl BufRead) -> io::Result<()> {
+    if file_name == cli::STDIN_FILE_NAME {
+        app.load_vcd_reader(stdin)
+    } else {
+        app.load_file(file_name)
+    }
+}
+
+// When the waveform was piped to stdin, keys can't come from there too. crossterm reads them from
+// the controlling terminal instead, so make sure there is one before starting the UI.
+#[cfg(unix)]
+fn check_terminal_input() -> io::Result<()> {
+    fs::File::open("/dev/tty").map(|_| ()).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!(
+                "The waveform was read from stdin, but /dev/tty can't be opened for keys: {}",
+                e
+            ),
+        )
+    })
+}
+
+#[cfg(not(unix))]
+fn check_terminal_input() -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Reading the waveform from stdin is only supported on Unix. Use --query to answer queries \
+         about it without the UI, or pass the path of a file",
+    ))
+}
 
-    // Terminal cleanup
-    disable_raw_mode()?;
-    if let Err(err) = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture) {
-        println!("{:?}", err)
+fn run_queries(app: &App, queries: &[String]) {
+    for input in queries {
+        match query::parse_query(input).and_then(|q| query::run_query(&q, &app.state)) {
+            Ok(lines) => {
+                for line in lines {
+                    println!("{}", line);
+                }
+            }
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                process::exit(2);
+            }
+        }
     }
+}
 
-    app_result
+fn run_interactive(app: App) -> Result<(), Box<dyn Error>> {
+    // Hooked before the terminal changes, so that no panic can leave it in raw mode
+    terminal::install_panic_hook();
+    let mut guard = terminal::TerminalGuard::new(terminal::Crossterm)?;
+    let terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+
+    let app_result = app.run(terminal);
+    let restored = guard.restore();
+    app_result?;
+    Ok(restored?)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{app::App, config, types::AppMode};
-    use crossterm::event::{KeyEvent, KeyModifiers};
-    use std::fs;
-    use tempfile::NamedTempFile;
+    use super::load_waveform;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use digisurf::{
+        app::App,
+        command_mode::CommandModeStateAccess,
+        config, constants,
+        parsers::types::{Value, WaveValue},
+        types::AppMode,
+    };
+    use std::{
+        fs,
+        io::Cursor,
+        thread,
+        time::{Duration, Instant},
+    };
+    use tempfile::{NamedTempFile, TempDir};
 
     // Utility function to create a test VCD file
     fn create_test_vcd_file() -> NamedTempFile {
@@ -103,7 +256,7 @@ mod tests {
         let mut app = App::with_config(config::load_config(None).unwrap());
 
         // Load the VCD file
-        let result = app.load_vcd_file(&create_test_vcd_file());
+        let result = app.load_vcd_file(create_test_vcd_file());
         assert!(result.is_ok());
 
         // Check the loaded data
@@ -115,6 +268,210 @@ mod tests {
         assert_eq!(app.state.time_range, 20);
     }
 
+    #[test]
+    fn test_load_waveform_from_stdin() {
+        let vcd_content = fs::read_to_string(create_test_vcd_file()).unwrap();
+        let mut app = App::with_config(config::load_config(None).unwrap());
+
+        load_waveform(&mut app, "-", Cursor::new(vcd_content)).unwrap();
+        assert_eq!(app.state.waveform_data.signals.len(), 3);
+        assert_eq!(app.state.waveform_data.max_time, 20);
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 20));
+        // There is no file to follow
+        assert_eq!(app.state.file_path, None);
+        assert!(app.run_command("follow").is_err());
+
+        // Other names are still files, leaving stdin alone
+        let vcd_file = create_test_vcd_file();
+        let vcd_path = vcd_file.path().with_extension("vcd");
+        fs::copy(&vcd_file, &vcd_path).unwrap();
+        let result = load_waveform(&mut app, &vcd_path.to_string_lossy(), Cursor::new(""));
+        fs::remove_file(&vcd_path).unwrap();
+        assert!(result.is_ok());
+        assert_eq!(app.state.file_path, Some(vcd_path));
+    }
+
+    #[test]
+    fn test_load_time_window() {
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.set_load_window(Some("0.015ns"), None);
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+
+        assert_eq!(app.state.waveform_data.window, Some((15, 20)));
+        assert_eq!((app.state.time_start, app.state.time_range), (15, 5));
+        assert_eq!(
+            app.state.waveform_data.values["test.clk"],
+            vec![
+                (15, WaveValue::Binary(Value::V1)),
+                (20, WaveValue::Binary(Value::V0))
+            ]
+        );
+
+        // The window is only for the file loaded with it
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+        assert_eq!(app.state.waveform_data.window, None);
+        assert_eq!(app.state.waveform_data.values["test.clk"].len(), 3);
+
+        // Times are checked once the file's timescale is known
+        app.set_load_window(Some("20"), Some("10"));
+        assert_eq!(
+            app.load_vcd_file(create_test_vcd_file())
+                .unwrap_err()
+                .to_string(),
+            "--to 10 is before --from 20"
+        );
+    }
+
+    #[test]
+    fn test_load_applies_initial_view() {
+        let mut config = config::AppConfig::default();
+        config.ui.initial_view = "last:5".to_string();
+        let mut app = App::with_config(config);
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+        assert_eq!((app.state.time_start, app.state.time_range), (15, 5));
+
+        // The command line asks for a view of its own
+        app.apply_view_args(Some("0"), Some("10"), None).unwrap();
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 10));
+
+        app.state.config.ui.initial_view = "last".to_string();
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 20));
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some(
+                "Invalid ui.initial_view \"last\": expected full, last:<duration>, \
+                 first_activity or window:<start>:<end>. Showing the full waveform"
+            )
+        );
+        assert!(app.state.command_state().result_is_error());
+    }
+
+    #[test]
+    fn test_load_warns_about_parse_problems() {
+        let vcd = include_str!("../resources/test_data_malformed.vcd");
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.load_vcd_reader(Cursor::new(vcd)).unwrap();
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some(
+                "Loaded with problems: 2 change(s) of undeclared identifiers, 1 timestamp(s) \
+                 going backwards, 1 invalid line(s). See :parsewarnings"
+            )
+        );
+        assert!(app.state.command_state().result_is_error());
+
+        app.run_command("parsewarnings").unwrap();
+        assert!(app.state.show_parse_warnings);
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert!(!app.state.show_parse_warnings);
+
+        // Loading a good file forgets the problems
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+        assert!(app.state.parse_report.is_empty());
+    }
+
+    #[test]
+    fn test_run_startup_commands_in_order() {
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+
+        let commands = vec![
+            "zoom 2".to_string(),
+            "goto 15".to_string(),
+            "findsignal".to_string(),
+        ];
+        assert!(app.run_startup_commands(&commands).is_ok());
+        assert_eq!(app.state.time_range, 10);
+        assert_eq!(app.state.time_start, 10);
+
+        // The app opens directly in the mode the commands left it in
+        assert_eq!(app.state.mode, AppMode::FuzzyFinder);
+    }
+
+    #[test]
+    fn test_run_startup_commands_stops_at_first_error() {
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+
+        let commands = vec!["goto 100".to_string(), "zoom 2".to_string()];
+        let result = app.run_startup_commands(&commands);
+        assert_eq!(
+            result.unwrap_err(),
+            "'goto 100': Time out of range (0-20)".to_string()
+        );
+        assert_eq!(app.state.time_range, 20);
+    }
+
+    #[test]
+    fn test_view_args_zoom_and_pick_signals() {
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+
+        let warnings = app
+            .apply_view_args(Some("5"), Some("0.015ns"), Some("test.data, *.clk"))
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert_eq!((app.state.time_start, app.state.time_range), (5, 10));
+
+        // Signals are displayed in the order they're declared, not the order of the patterns
+        assert_eq!(app.state.displayed_signals, vec!["test.clk", "test.data"]);
+        assert!(!app.state.is_session_dirty());
+    }
+
+    #[test]
+    fn test_view_args_clamp_invalid_ranges() {
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+
+        let warnings = app.apply_view_args(Some("10"), Some("100"), None).unwrap();
+        assert_eq!(
+            warnings,
+            vec!["--end 100 is past the end of the waveform, ending at 20"]
+        );
+        assert_eq!((app.state.time_start, app.state.time_range), (10, 10));
+
+        let warnings = app
+            .apply_view_args(Some("12ps"), Some("8ps"), None)
+            .unwrap();
+        assert_eq!(
+            warnings,
+            vec!["--end 8 isn't after --start 12, ending at 20"]
+        );
+        assert_eq!((app.state.time_start, app.state.time_range), (12, 8));
+
+        let warnings = app.apply_view_args(Some("30"), None, None).unwrap();
+        assert_eq!(
+            warnings,
+            vec!["--start 30 is past the end of the waveform at 20, starting at 0"]
+        );
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 20));
+
+        // Without flags the view and signals are left alone
+        assert!(app.apply_view_args(None, None, None).unwrap().is_empty());
+        assert_eq!((app.state.time_start, app.state.time_range), (0, 20));
+        assert!(app.state.displayed_signals.is_empty());
+    }
+
+    #[test]
+    fn test_view_args_errors() {
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.load_vcd_file(create_test_vcd_file()).unwrap();
+
+        assert_eq!(
+            app.apply_view_args(Some("soon"), None, None),
+            Err("--start: Invalid time: soon".to_string())
+        );
+        assert_eq!(
+            app.apply_view_args(None, Some("3min"), None),
+            Err("--end: Unknown time unit: min".to_string())
+        );
+        assert_eq!(
+            app.apply_view_args(None, None, Some("nothing.*")),
+            Ok(vec!["--signals nothing.* matches no signals".to_string()])
+        );
+    }
+
     #[test]
     fn test_app_mode_transitions() {
         let mut app = App::with_config(config::load_config(None).unwrap());
@@ -136,4 +493,243 @@ mod tests {
         ));
         assert_eq!(app.state.mode, AppMode::Normal);
     }
+
+    #[test]
+    fn test_load_file_chosen_in_file_browser() {
+        let dir = TempDir::new().unwrap();
+        fs::copy(create_test_vcd_file(), dir.path().join("b.vcd")).unwrap();
+        fs::write(dir.path().join("a.txt"), "").unwrap();
+        fs::write(dir.path().join("a.fst"), "").unwrap();
+        let recent_files_path = dir.path().join("config").join("recent_files.toml");
+
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.set_recent_files_path(Some(recent_files_path.clone()));
+        app.open_file_browser(dir.path());
+        assert_eq!(app.state.mode, AppMode::FileBrowser);
+        assert_eq!(
+            app.state.file_browser.local_files,
+            vec!["a.fst".to_string(), "b.vcd".to_string()]
+        );
+
+        // A file that fails to load leaves the browser open with the error shown
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.state.mode, AppMode::FileBrowser);
+        assert!(app.state.command_state().result_is_error());
+
+        app.handle_key(KeyEvent::new(KeyCode::Down, KeyModifiers::empty()));
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        assert_eq!(app.state.mode, AppMode::Normal);
+        assert_eq!(app.state.waveform_data.signals.len(), 3);
+
+        // The file is remembered for the next start
+        let loaded_path = dir.path().join("b.vcd").canonicalize().unwrap();
+        let mut next_app = App::with_config(config::load_config(None).unwrap());
+        next_app.set_recent_files_path(Some(recent_files_path));
+        let recent_files = &next_app.state.file_browser.recent_files;
+        assert_eq!(recent_files.len(), 1);
+        assert_eq!(recent_files[0].path, loaded_path.to_string_lossy());
+    }
+
+    #[test]
+    fn test_file_browser_typed_path() {
+        let vcd_file = create_test_vcd_file();
+        let vcd_path = vcd_file.path().with_extension("vcd");
+        fs::copy(&vcd_file, &vcd_path).unwrap();
+
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.open_file_browser(&std::env::temp_dir().join("digisurf-missing-dir"));
+        app.handle_key(KeyEvent::new(KeyCode::Char('/'), KeyModifiers::empty()));
+        for c in vcd_path.to_string_lossy().chars() {
+            app.handle_key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::empty()));
+        }
+        app.handle_key(KeyEvent::new(KeyCode::Enter, KeyModifiers::empty()));
+        fs::remove_file(&vcd_path).unwrap();
+
+        assert_eq!(app.state.mode, AppMode::Normal);
+        assert_eq!(app.state.waveform_data.max_time, 20);
+
+        // Loads are remembered in the running app even when the list isn't saved
+        assert_eq!(app.state.file_browser.recent_files.len(), 1);
+
+        // Esc dismisses the browser to the empty UI
+        app.open_file_browser(&std::env::temp_dir().join("digisurf-missing-dir"));
+        app.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::empty()));
+        assert_eq!(app.state.mode, AppMode::Normal);
+    }
+
+    #[test]
+    fn test_stats_are_recomputed_on_load() {
+        let vcd_file = create_test_vcd_file();
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.load_vcd_file(vcd_file.path()).unwrap();
+        app.run_command("stats test.clk").unwrap();
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some(
+                "test.clk: 2 change(s), first at 10, last at 20, toggle rate 0.1000 per time unit"
+            )
+        );
+
+        let mut contents = fs::read_to_string(vcd_file.path()).unwrap();
+        contents.push_str("\n#40\n1#\n");
+        fs::write(vcd_file.path(), &contents).unwrap();
+        app.load_vcd_file(vcd_file.path()).unwrap();
+        app.run_command("stats test.clk").unwrap();
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some(
+                "test.clk: 3 change(s), first at 10, last at 40, toggle rate 0.0750 per time unit"
+            )
+        );
+    }
+
+    #[test]
+    fn test_follow_appended_changes() {
+        let vcd_file = create_test_vcd_file();
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.load_vcd_file(vcd_file.path()).unwrap();
+        app.run_command("follow").unwrap();
+
+        // The test file doesn't end with a newline, so finish its last line first
+        let mut contents = fs::read_to_string(vcd_file.path()).unwrap();
+        contents.push_str("\n#30\n1#\n");
+        fs::write(vcd_file.path(), &contents).unwrap();
+        app.follow_file();
+
+        assert_eq!(app.state.waveform_data.max_time, 30);
+        assert_eq!((app.state.time_start, app.state.time_range), (10, 20));
+        assert_eq!(app.state.waveform_data.values["test.clk"].len(), 4);
+
+        // New signal definitions stop following
+        contents.push_str("$var wire 1 & extra $end\n");
+        fs::write(vcd_file.path(), &contents).unwrap();
+        app.follow_file();
+        assert!(!app.state.following);
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some("Stopped following: Signal definitions changed")
+        );
+    }
+
+    // Rewrites the test file with the clock toggling once more, at 40
+    fn rewrite_test_vcd_file(path: &std::path::Path) {
+        let mut contents = fs::read_to_string(path).unwrap();
+        contents.push_str("\n#40\n1#\n");
+        fs::write(path, contents).unwrap();
+    }
+
+    // An app showing the test file with its view, markers and selection changed
+    fn app_with_changed_view(path: &std::path::Path) -> App {
+        let mut app = App::with_config(config::AppConfig::default());
+        app.load_vcd_file(path).unwrap();
+        app.state.displayed_signals = vec!["test.data".to_string(), "test.clk".to_string()];
+        app.state.selected_signal = 1;
+        app.state.set_window(5, 10);
+        app.state.primary_marker = Some(12);
+        app.run_command("marker add edge 10").unwrap();
+        app
+    }
+
+    fn assert_view_kept(app: &App) {
+        assert_eq!(app.state.waveform_data.max_time, 40);
+        assert_eq!(app.state.waveform_data.values["test.clk"].len(), 4);
+        assert_eq!((app.state.time_start, app.state.time_range), (5, 10));
+        assert_eq!(app.state.displayed_signals, ["test.data", "test.clk"]);
+        assert_eq!(app.state.selected_signal, 1);
+        assert_eq!(app.state.primary_marker, Some(12));
+        assert_eq!(app.state.saved_markers.len(), 1);
+        assert_eq!(app.state.saved_markers[0].name, "edge");
+    }
+
+    #[test]
+    fn test_reload_keeps_view_markers_and_selection() {
+        let vcd_file = create_test_vcd_file();
+        let mut app = app_with_changed_view(vcd_file.path());
+        rewrite_test_vcd_file(vcd_file.path());
+
+        app.run_command("reload").unwrap();
+        assert_view_kept(&app);
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some(format!("Reloaded {}", vcd_file.path().display()).as_str())
+        );
+    }
+
+    #[test]
+    fn test_reload_keeps_load_window() {
+        let vcd_file = create_test_vcd_file();
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.set_load_window(Some("0.015ns"), None);
+        app.load_vcd_file(vcd_file.path()).unwrap();
+
+        app.run_command("reload").unwrap();
+        assert_eq!(app.state.waveform_data.window, Some((15, 20)));
+        assert_eq!(app.state.waveform_data.values["test.clk"].len(), 2);
+
+        // Loading a file anew keeps all of it
+        app.load_vcd_file(vcd_file.path()).unwrap();
+        app.run_command("reload").unwrap();
+        assert_eq!(app.state.waveform_data.window, None);
+    }
+
+    #[test]
+    fn test_load_clears_cursor() {
+        let vcd_file = create_test_vcd_file();
+        let mut app = App::with_config(config::load_config(None).unwrap());
+        app.load_vcd_file(vcd_file.path()).unwrap();
+        app.state.cursor = Some(10);
+
+        app.run_command("reload").unwrap();
+        assert_eq!(app.state.cursor, Some(10));
+        app.load_vcd_file(vcd_file.path()).unwrap();
+        assert_eq!(app.state.cursor, None);
+    }
+
+    #[test]
+    fn test_watch_reloads_rewritten_file() {
+        let vcd_file = create_test_vcd_file();
+        let mut app = app_with_changed_view(vcd_file.path());
+        app.run_command("watch auto").unwrap();
+        app.watch_file(Instant::now());
+
+        // Several writes in a row cause a single reload once they stop
+        rewrite_test_vcd_file(vcd_file.path());
+        fs::write(
+            vcd_file.path(),
+            fs::read_to_string(vcd_file.path()).unwrap(),
+        )
+        .unwrap();
+        let changed_at = Instant::now();
+        // The watcher's events arrive from another thread, so poll until the reload happens
+        while app.state.waveform_data.max_time != 40
+            && changed_at.elapsed() < Duration::from_secs(5)
+        {
+            thread::sleep(Duration::from_millis(20));
+            app.watch_file(Instant::now());
+        }
+        assert!(
+            changed_at.elapsed() >= Duration::from_millis(constants::FILE_WATCH_DEBOUNCE_MILLIS)
+        );
+        assert_view_kept(&app);
+        assert_eq!(
+            app.state.command_state().result_message(),
+            Some(format!("Reloaded {}", vcd_file.path().display()).as_str())
+        );
+
+        // Asking only offers to reload
+        app.run_command("watch ask").unwrap();
+        let mut contents = fs::read_to_string(vcd_file.path()).unwrap();
+        contents.push_str("#50\n0#\n");
+        fs::write(vcd_file.path(), contents).unwrap();
+        let prompt = "File changed on disk — :reload to refresh, :watch auto to always reload";
+        let changed_at = Instant::now();
+        while app.state.command_state().result_message() != Some(prompt)
+            && changed_at.elapsed() < Duration::from_secs(5)
+        {
+            thread::sleep(Duration::from_millis(20));
+            app.watch_file(Instant::now());
+        }
+        assert_eq!(app.state.command_state().result_message(), Some(prompt));
+        assert_eq!(app.state.waveform_data.max_time, 40);
+    }
 }
@@ -0,0 +1,175 @@
+//! Compact storage for the text of bus values.
+
+use std::{
+    fmt::{Debug, Display, Formatter, Result},
+    ops::Deref,
+    str,
+};
+
+/// Bytes of text kept inline, enough for the hex digits of an 88 bit bus or the bits of a 22 bit
+/// bus with x or z in it.
+const INLINE_CAPACITY: usize = 22;
+
+/// The text of a bus value, like `FF`, `10xz` or `r1.5`, read as a `&str`. Text as long as the
+/// hex digits of a 64 bit bus is kept inline instead of in an allocation of its own, so storing
+/// millions of changes of a counter doesn't allocate millions of small strings. Wider buses, and
+/// the bits of buses wider than 22 bits with x or z in them, still get an allocation each.
+#[derive(Clone)]
+pub struct BusValue(Repr);
+
+#[derive(Clone)]
+enum Repr {
+    /// Only ever filled from whole characters, so `bytes[..len]` is always valid UTF-8
+    Inline {
+        len: u8,
+        bytes: [u8; INLINE_CAPACITY],
+    },
+    Heap(Box<str>),
+}
+
+// The start of the inline bytes, as text
+fn inline_str(bytes: &[u8; INLINE_CAPACITY], len: usize) -> &str {
+    str::from_utf8(&bytes[..len])
+        .expect("inline bytes are only copied from a &str or encoded from whole chars")
+}
+
+impl BusValue {
+    pub fn as_str(&self) -> &str {
+        self
+    }
+
+    /// Bytes allocated for the text, 0 when it is kept inline.
+    pub fn heap_bytes(&self) -> usize {
+        match &self.0 {
+            Repr::Inline { .. } => 0,
+            Repr::Heap(text) => text.len(),
+        }
+    }
+}
+
+impl Deref for BusValue {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match &self.0 {
+            Repr::Inline { len, bytes } => inline_str(bytes, *len as usize),
+            Repr::Heap(text) => text,
+        }
+    }
+}
+
+impl From<&str> for BusValue {
+    fn from(text: &str) -> Self {
+        if text.len() > INLINE_CAPACITY {
+            return BusValue(Repr::Heap(text.into()));
+        }
+        let mut bytes = [0; INLINE_CAPACITY];
+        bytes[..text.len()].copy_from_slice(text.as_bytes());
+        BusValue(Repr::Inline {
+            len: text.len() as u8,
+            bytes,
+        })
+    }
+}
+
+impl From<String> for BusValue {
+    fn from(text: String) -> Self {
+        if text.len() > INLINE_CAPACITY {
+            BusValue(Repr::Heap(text.into_boxed_str()))
+        } else {
+            BusValue::from(text.as_str())
+        }
+    }
+}
+
+impl FromIterator<char> for BusValue {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        let mut bytes = [0; INLINE_CAPACITY];
+        let mut len = 0;
+        let mut chars = iter.into_iter();
+        while let Some(c) = chars.next() {
+            if len + c.len_utf8() > INLINE_CAPACITY {
+                // Too long to keep inline, so the rest goes into a string
+                let mut text = String::from(inline_str(&bytes, len));
+                text.push(c);
+                text.extend(chars);
+                return BusValue::from(text);
+            }
+            len += c.encode_utf8(&mut bytes[len..]).len();
+        }
+        BusValue(Repr::Inline {
+            len: len as u8,
+            bytes,
+        })
+    }
+}
+
+impl PartialEq for BusValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for BusValue {}
+
+impl PartialEq<str> for BusValue {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for BusValue {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for BusValue {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+// Shown like the string it holds, as bus values were before they were stored compactly
+impl Debug for BusValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Display for BusValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        Display::fmt(self.as_str(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_values_are_inline() {
+        let counter = BusValue::from("00000000DEADBEEF");
+        assert!(matches!(counter.0, Repr::Inline { len: 16, .. }));
+        assert_eq!(counter, "00000000DEADBEEF");
+        assert_eq!(format!("{:?}", counter), "\"00000000DEADBEEF\"");
+
+        let wide = BusValue::from("F".repeat(32));
+        assert!(matches!(wide.0, Repr::Heap(_)));
+        assert_eq!(wide.len(), 32);
+        assert_eq!(wide, BusValue::from("F".repeat(32).as_str()));
+    }
+
+    #[test]
+    fn test_collect_spills_past_inline_capacity() {
+        for len in [0, INLINE_CAPACITY, INLINE_CAPACITY + 1, 100] {
+            let text = "10xz".chars().cycle().take(len).collect::<String>();
+            let value: BusValue = text.chars().collect();
+            assert_eq!(value, text);
+            assert_eq!(
+                matches!(value.0, Repr::Inline { .. }),
+                len <= INLINE_CAPACITY
+            );
+        }
+    }
+}
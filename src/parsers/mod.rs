@@ -1,3 +1,6 @@
+//! Reading waveform files. Only VCD is supported.
+
+pub mod bus_value;
 mod parse_fns;
 pub mod types;
 pub mod vcd;
@@ -1,8 +1,9 @@
+use super::bus_value::BusValue;
 use nom::IResult;
 
-pub fn parse_binary_to_hex(bin_str: &str) -> IResult<&str, String> {
+pub fn parse_binary_to_hex(bin_str: &str) -> IResult<&str, BusValue> {
     if bin_str.is_empty() {
-        return Ok(("", String::new()));
+        return Ok(("", BusValue::from("")));
     }
 
     // Check if the string contains only valid binary digits
@@ -13,19 +14,21 @@ pub fn parse_binary_to_hex(bin_str: &str) -> IResult<&str, String> {
         )));
     }
 
-    // Pad with leading zeros to make length a multiple of 4
-    let padding = (4 - (bin_str.len() % 4)) % 4;
-    let padded = "0".repeat(padding) + bin_str;
-
-    // Convert chunks of 4 bits to hex characters
-    let hex_result = padded
-        .chars()
-        .collect::<Vec<_>>()
-        .chunks(4)
+    // The leftmost nibble is short when the bits don't make whole nibbles, as if it were padded
+    // with leading zeros
+    let bits = bin_str.as_bytes();
+    let first_nibble = match bits.len() % 4 {
+        0 => 4,
+        len => len,
+    };
+
+    // Convert chunks of 4 bits to hex characters, straight into the stored value
+    let hex_result = std::iter::once(&bits[..first_nibble])
+        .chain(bits[first_nibble..].chunks(4))
         .map(|chunk| {
-            let value = chunk
-                .iter()
-                .fold(0u8, |acc, &bit| (acc << 1) | if bit == '1' { 1 } else { 0 });
+            let value = chunk.iter().fold(0u8, |acc, &bit| {
+                (acc << 1) | if bit == b'1' { 1 } else { 0 }
+            });
             char::from_digit(value as u32, 16)
                 .unwrap()
                 .to_ascii_uppercase()
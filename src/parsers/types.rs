@@ -1,5 +1,6 @@
 // Types that all file parsers must use to extract data from their files.
 
+use super::bus_value::BusValue;
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -8,6 +9,7 @@ use nom::{
     multi::many1,
     IResult, Parser,
 };
+use serde::Serialize;
 use std::{
     collections::HashMap,
     fmt::{Binary, Debug, Display, Formatter, LowerHex, Octal, Result, UpperHex},
@@ -35,11 +37,16 @@ impl Display for Value {
 #[derive(Debug, PartialEq, Clone)]
 pub enum WaveValue {
     Binary(Value),
-    // String is always hexadecimal format internally
-    Bus(String),
+    // Always hexadecimal internally, or the bits when some of them are x or z
+    Bus(BusValue),
 }
 
 impl WaveValue {
+    /// A bus value from its hex digits, or its bits when some of them are x or z.
+    pub fn bus(value: impl Into<BusValue>) -> Self {
+        WaveValue::Bus(value.into())
+    }
+
     // Parse a single 4-state logic value
     fn parse_logic(input: &str) -> IResult<&str, Value> {
         alt((
@@ -156,6 +163,38 @@ impl WaveValue {
         }
     }
 
+    /// The value as lowercase hex without leading zeros, so equal values of different widths
+    /// compare equal. Values with x or z bits, or that aren't bits at all, have none.
+    pub fn normalized_hex(&self) -> Option<String> {
+        hex_of_bits(&self.values(16)?)
+    }
+
+    /// The value as a signed decimal number `width` bits wide in two's complement, so the most
+    /// significant of those bits is the sign, like `-1` for `ff` 8 bits wide. Bits above `width`
+    /// are dropped, and values with fewer bits are positive, since leading zeros aren't stored.
+    /// Values with x or z bits, or wider than 128 bits, are formatted like `{}`.
+    pub fn format_signed_decimal(&self, width: usize) -> String {
+        let bits = match self.values(16) {
+            Some(bits) if !bits.contains(&Value::VX) && !bits.contains(&Value::VZ) => bits,
+            _ => return self.to_string(),
+        };
+        let bits = &bits[bits.len().saturating_sub(width)..];
+        if bits.is_empty() || bits.len() > 128 {
+            return self.to_string();
+        }
+
+        let number = bits.iter().fold(0u128, |number, bit| {
+            number << 1 | u128::from(*bit == Value::V1)
+        });
+        if bits.len() == width && bits[0] == Value::V1 {
+            // The distance below 2^width, worked out without overflowing at 128 bits
+            let mask = u128::MAX >> (128 - width);
+            format!("-{}", (!number & mask) + 1)
+        } else {
+            number.to_string()
+        }
+    }
+
     // Format a bus value with radix
     fn format_bus(&self, radix: u32, uppercase: bool, f: &mut Formatter<'_>) -> Result {
         match self {
@@ -291,7 +330,7 @@ impl WaveValue {
 
         // Try to convert to binary first, then to decimal
         // For simple cases without x/z values
-        if !s.contains(|c| c == 'x' || c == 'X' || c == 'z' || c == 'Z') {
+        if !s.contains(['x', 'X', 'z', 'Z']) {
             // Try to parse as hex and convert to decimal
             if let Ok(num) = u64::from_str_radix(s, 16) {
                 return num.to_string();
@@ -328,7 +367,7 @@ impl WaveValue {
 
     fn format_string_as_octal(s: &str, uppercase: bool) -> String {
         // If the string contains x or z, handle it differently
-        if s.contains(|c| c == 'x' || c == 'X' || c == 'z' || c == 'Z') {
+        if s.contains(['x', 'X', 'z', 'Z']) {
             return s
                 .chars()
                 .map(|c| match c {
@@ -376,7 +415,7 @@ impl WaveValue {
         let mut binary_padded = binary.clone();
 
         // Pad with leading zeros to make the length a multiple of 3
-        while binary_padded.len() % 3 != 0 {
+        while !binary_padded.len().is_multiple_of(3) {
             binary_padded.insert(0, '0');
         }
 
@@ -500,12 +539,321 @@ pub struct WaveformData {
     pub signals: Vec<String>,
     pub values: HashMap<String, Vec<(u64, WaveValue)>>,
     pub max_time: u64,
+    /// Length of one time unit in femtoseconds, if the file gave a timescale
+    pub timescale_fs: Option<u64>,
+    /// Declared width in bits of each signal
+    pub widths: HashMap<String, usize>,
+    /// First and last time of the value changes kept, if only part of the file was loaded. Every
+    /// signal starts with the value it had at the first time.
+    pub window: Option<(u64, u64)>,
+    /// Times at which signals changed more than once, sorted by time, by signal name. Only the
+    /// last change at each time is in `values`.
+    pub glitches: HashMap<String, Vec<Glitch>>,
+}
+
+/// A time at which a signal changed more than once, like in a delta-cycle glitch.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Glitch {
+    pub time: u64,
+    /// The values the signal had for no time at all, in the order they were written
+    pub via: Vec<WaveValue>,
+}
+
+/// Keeps only the last of the changes at each time in `values`, which are sorted by time, and
+/// returns the glitches where earlier ones were dropped. A change repeating the one before it at
+/// the same time isn't a glitch.
+pub fn coalesce_changes(values: &mut Vec<(u64, WaveValue)>) -> Vec<Glitch> {
+    if !values.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+        return Vec::new();
+    }
+
+    let mut glitches: Vec<Glitch> = Vec::new();
+    let mut kept: Vec<(u64, WaveValue)> = Vec::with_capacity(values.len());
+    for (time, value) in values.drain(..) {
+        match kept.last_mut() {
+            Some((last_time, last_value)) if *last_time == time => {
+                if *last_value == value {
+                    continue;
+                }
+                let dropped = std::mem::replace(last_value, value);
+                match glitches.last_mut() {
+                    Some(glitch) if glitch.time == time => glitch.via.push(dropped),
+                    _ => glitches.push(Glitch {
+                        time,
+                        via: vec![dropped],
+                    }),
+                }
+            }
+            _ => kept.push((time, value)),
+        }
+    }
+    *values = kept;
+    glitches
+}
+
+/// Adds `more` glitches to `glitches`, both sorted by time. The values of glitches at the same time
+/// are taken to come after the ones already there.
+pub fn merge_glitches(glitches: &mut Vec<Glitch>, more: Vec<Glitch>) {
+    for glitch in more {
+        match glitches.binary_search_by_key(&glitch.time, |existing| existing.time) {
+            Ok(index) => glitches[index].via.extend(glitch.via),
+            Err(index) => glitches.insert(index, glitch),
+        }
+    }
+}
+
+/// Times limiting the value changes a parser keeps, as given with `--from` and `--to`: ticks or a
+/// time with a unit like 1.2us. Either end may be left open.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct TimeWindow {
+    pub from: Option<String>,
+    pub to: Option<String>,
+}
+
+impl TimeWindow {
+    /// Whether the window keeps the whole file.
+    pub fn is_whole(&self) -> bool {
+        self.from.is_none() && self.to.is_none()
+    }
+
+    /// The first and last time kept, in ticks of a file with the given timescale.
+    pub fn resolve(&self, timescale_fs: Option<u64>) -> std::result::Result<(u64, u64), String> {
+        let from = parse_time_flag("from", self.from.as_deref(), timescale_fs)?.unwrap_or(0);
+        let to = parse_time_flag("to", self.to.as_deref(), timescale_fs)?.unwrap_or(u64::MAX);
+        if to < from {
+            return Err(format!("--to {} is before --from {}", to, from));
+        }
+        Ok((from, to))
+    }
+}
+
+/// A signal as declared in a waveform file's header, listed by `--dump-signals`.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+pub struct SignalDefinition {
+    /// Full hierarchical name, like `top.cpu.pc`
+    pub name: String,
+    /// Declared width in bits
+    pub width: usize,
+    /// Kind of variable, like `wire` or `reg`
+    #[serde(rename = "type")]
+    pub var_type: String,
+}
+
+/// Header directives describing a waveform file, with their text as written in the file.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct FileHeader {
+    /// When the file was written, from `$date`
+    pub date: Option<String>,
+    /// What wrote the file, from `$version`
+    pub version: Option<String>,
+    /// Length of one time unit, from `$timescale`, like `10 ns`
+    pub timescale: Option<String>,
+}
+
+/// Problems found in a waveform file that didn't stop it from loading.
+#[derive(Debug, Default, PartialEq, Clone, Serialize)]
+pub struct ParseReport {
+    /// Value changes of identifiers that no variable declared, which are dropped
+    pub unknown_ids: ProblemLines,
+    /// Timestamps earlier than the one before them, whose changes are sorted into place
+    pub backwards_timestamps: ProblemLines,
+    /// Lines that aren't valid in the file format, which are skipped
+    pub invalid_lines: ProblemLines,
+}
+
+/// How often one kind of problem occurred, with the first few lines it occurred on.
+#[derive(Debug, Default, PartialEq, Clone, Serialize)]
+pub struct ProblemLines {
+    pub count: usize,
+    /// `(line number, line)` of the first occurrences, at most `MAX_EXAMPLES`
+    pub examples: Vec<(usize, String)>,
+}
+
+impl ProblemLines {
+    pub const MAX_EXAMPLES: usize = 3;
+
+    pub fn record(&mut self, line_number: usize, line: &str) {
+        self.count += 1;
+        if self.examples.len() < Self::MAX_EXAMPLES {
+            self.examples.push((line_number, line.to_string()));
+        }
+    }
+}
+
+impl ParseReport {
+    pub fn is_empty(&self) -> bool {
+        self.problems()
+            .iter()
+            .all(|(problems, _)| problems.count == 0)
+    }
+
+    /// One line naming how often each problem occurred, e.g. for a warning after loading.
+    pub fn summary(&self) -> String {
+        let counts: Vec<String> = self
+            .problems()
+            .iter()
+            .filter(|(problems, _)| problems.count > 0)
+            .map(|(problems, description)| format!("{} {}", problems.count, description))
+            .collect();
+        counts.join(", ")
+    }
+
+    /// Each problem with how it was handled and its example lines, for `:parsewarnings`.
+    pub fn details(&self) -> Vec<String> {
+        let handling = ["dropped", "sorted into place", "skipped"];
+        let mut lines = Vec::new();
+        for ((problems, description), handling) in self.problems().iter().zip(handling) {
+            if problems.count == 0 {
+                continue;
+            }
+            lines.push(format!("{} {}, {}:", problems.count, description, handling));
+            for (line_number, line) in &problems.examples {
+                lines.push(format!("  line {}: {}", line_number, line));
+            }
+        }
+        lines
+    }
+
+    // Helper function to pair each kind of problem with a description of it
+    fn problems(&self) -> [(&ProblemLines, &'static str); 3] {
+        [
+            (&self.unknown_ids, "change(s) of undeclared identifiers"),
+            (&self.backwards_timestamps, "timestamp(s) going backwards"),
+            (&self.invalid_lines, "invalid line(s)"),
+        ]
+    }
+}
+
+/// Femtoseconds in one of the time units a VCD timescale can use, like "ns".
+pub fn time_unit_femtoseconds(unit: &str) -> Option<u64> {
+    match unit {
+        "s" => Some(1_000_000_000_000_000),
+        "ms" => Some(1_000_000_000_000),
+        "us" => Some(1_000_000_000),
+        "ns" => Some(1_000_000),
+        "ps" => Some(1_000),
+        "fs" => Some(1),
+        _ => None,
+    }
+}
+
+/// Splits a duration like "300ns" into its number and unit. The unit is empty if there is none.
+pub fn split_time_unit(input: &str) -> (&str, &str) {
+    let unit_start = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    input.split_at(unit_start)
+}
+
+/// Parses a time given in ticks like "1200", or with a unit like "1.2us" that is converted to
+/// ticks with the waveform's timescale, rounding down.
+pub fn parse_time(text: &str, timescale_fs: Option<u64>) -> std::result::Result<u64, String> {
+    let invalid = || format!("Invalid time: {}", text);
+    let unit_start = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(unit_start);
+    if number.is_empty() {
+        return Err(invalid());
+    }
+    if unit.is_empty() {
+        return number.parse().map_err(|_| invalid());
+    }
+
+    let unit_fs =
+        time_unit_femtoseconds(unit).ok_or_else(|| format!("Unknown time unit: {}", unit))?;
+    let timescale_fs = timescale_fs.ok_or_else(|| {
+        "The waveform has no timescale, so give the time without a unit".to_string()
+    })?;
+
+    // Work in whole femtoseconds so that decimal times like 1.2us are exact
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if (whole.is_empty() && fraction.is_empty()) || fraction.contains('.') {
+        return Err(invalid());
+    }
+    let whole: u128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| invalid())?
+    };
+    let mut femtoseconds = whole.checked_mul(unit_fs as u128).ok_or_else(invalid)?;
+    let mut digit_fs = unit_fs as u128;
+    for digit in fraction.chars() {
+        digit_fs /= 10;
+        femtoseconds += digit.to_digit(10).ok_or_else(invalid)? as u128 * digit_fs;
+    }
+    Ok((femtoseconds / timescale_fs as u128).min(u64::MAX as u128) as u64)
+}
+
+/// Parses the time given to a command line flag like `--from`, if it was given, naming the flag
+/// in the error.
+pub fn parse_time_flag(
+    flag: &str,
+    time: Option<&str>,
+    timescale_fs: Option<u64>,
+) -> std::result::Result<Option<u64>, String> {
+    time.map(|time| parse_time(time, timescale_fs).map_err(|e| format!("--{}: {}", flag, e)))
+        .transpose()
+}
+
+/// Bits, most significant first, as lowercase hex without leading zeros. Zero is `0`, and bits
+/// that are x or z have no hex digit.
+pub fn hex_of_bits(bits: &[Value]) -> Option<String> {
+    let padding = (4 - bits.len() % 4) % 4;
+    let mut digits = String::new();
+    let padded: Vec<&Value> = std::iter::repeat_n(&Value::V0, padding)
+        .chain(bits)
+        .collect();
+    for nibble in padded.chunks(4) {
+        let mut digit = 0;
+        for bit in nibble {
+            digit = digit * 2
+                + match bit {
+                    Value::V0 => 0,
+                    Value::V1 => 1,
+                    Value::VX | Value::VZ => return None,
+                };
+        }
+        digits.push(std::char::from_digit(digit, 16)?);
+    }
+    let trimmed = digits.trim_start_matches('0');
+    Some(if trimmed.is_empty() { "0" } else { trimmed }.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(parse_time("1200", None), Ok(1200));
+        assert_eq!(parse_time("1.2us", Some(1_000)), Ok(1_200_000));
+        assert_eq!(parse_time(".5ns", Some(1_000)), Ok(500));
+        // Times between ticks round down
+        assert_eq!(parse_time("1500ps", Some(1_000_000)), Ok(1));
+
+        assert_eq!(
+            parse_time("1.2us", None),
+            Err("The waveform has no timescale, so give the time without a unit".to_string())
+        );
+        assert_eq!(
+            parse_time("3min", Some(1)),
+            Err("Unknown time unit: min".to_string())
+        );
+        for invalid in ["1.5", "1..2ns", ".ns", "", "-3ns"] {
+            assert!(parse_time(invalid, Some(1)).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_split_time_unit() {
+        assert_eq!(split_time_unit("300ns"), ("300", "ns"));
+        assert_eq!(split_time_unit("42"), ("42", ""));
+        assert_eq!(split_time_unit("ps"), ("", "ps"));
+        assert_eq!(time_unit_femtoseconds("us"), Some(1_000_000_000));
+        assert_eq!(time_unit_femtoseconds("min"), None);
+    }
+
     #[test]
     fn test_value_display() {
         assert_eq!(format!("{}", Value::V0), "0");
@@ -529,8 +877,8 @@ mod tests {
         assert_eq!(format!("{:b}", WaveValue::Binary(Value::V1)), "1");
 
         // Test bus values with binary formatting
-        let bus_a = WaveValue::Bus("a".to_string());
-        let bus_f0 = WaveValue::Bus("f0".to_string());
+        let bus_a = WaveValue::bus("a".to_string());
+        let bus_f0 = WaveValue::bus("f0".to_string());
 
         assert_eq!(format!("{:b}", bus_a), "1010");
         assert_eq!(format!("{:b}", bus_f0), "11110000");
@@ -547,8 +895,8 @@ mod tests {
         assert_eq!(format!("{:o}", WaveValue::Binary(Value::V1)), "1");
 
         // Test bus values with octal formatting
-        let bus_a = WaveValue::Bus("a".to_string());
-        let bus_ff = WaveValue::Bus("ff".to_string());
+        let bus_a = WaveValue::bus("a".to_string());
+        let bus_ff = WaveValue::bus("ff".to_string());
 
         assert_eq!(format!("{:o}", bus_a), "12");
         assert_eq!(format!("{:o}", bus_ff), "377");
@@ -565,8 +913,8 @@ mod tests {
         assert_eq!(format!("{:x}", WaveValue::Binary(Value::V1)), "1");
 
         // Test bus values with lowercase hex
-        let bus_abcd = WaveValue::Bus("abcd".to_string());
-        let bus_1234 = WaveValue::Bus("1234".to_string());
+        let bus_abcd = WaveValue::bus("abcd".to_string());
+        let bus_1234 = WaveValue::bus("1234".to_string());
 
         assert_eq!(format!("{:x}", bus_abcd), "abcd");
         assert_eq!(format!("{:x}", bus_1234), "1234");
@@ -590,10 +938,10 @@ mod tests {
 
     #[test]
     fn test_hex_to_oct_conversion() {
-        assert_eq!(format!("{:o}", WaveValue::Bus("7".to_string())), "7");
-        assert_eq!(format!("{:o}", WaveValue::Bus("f0".to_string())), "360");
-        assert_eq!(format!("{:o}", WaveValue::Bus("12".to_string())), "22");
-        assert_eq!(format!("{:o}", WaveValue::Bus("x4".to_string())), "x4");
+        assert_eq!(format!("{:o}", WaveValue::bus("7".to_string())), "7");
+        assert_eq!(format!("{:o}", WaveValue::bus("f0".to_string())), "360");
+        assert_eq!(format!("{:o}", WaveValue::bus("12".to_string())), "22");
+        assert_eq!(format!("{:o}", WaveValue::bus("x4".to_string())), "x4");
     }
 
     #[test]
@@ -601,12 +949,116 @@ mod tests {
         assert_eq!(format!("{}", WaveValue::Binary(Value::V0)), "0");
         assert_eq!(format!("{}", WaveValue::Binary(Value::V1)), "1");
 
-        assert_eq!(format!("{}", WaveValue::Bus("0".to_string())), "0");
-        assert_eq!(format!("{}", WaveValue::Bus("9".to_string())), "9");
-        assert_eq!(format!("{}", WaveValue::Bus("1".to_string())), "1");
-        assert_eq!(format!("{}", WaveValue::Bus("a".to_string())), "10");
-        assert_eq!(format!("{}", WaveValue::Bus("64".to_string())), "100");
-        assert_eq!(format!("{}", WaveValue::Bus("3x".to_string())), "3x");
-        assert_eq!(format!("{}", WaveValue::Bus("z6".to_string())), "z6");
+        assert_eq!(format!("{}", WaveValue::bus("0".to_string())), "0");
+        assert_eq!(format!("{}", WaveValue::bus("9".to_string())), "9");
+        assert_eq!(format!("{}", WaveValue::bus("1".to_string())), "1");
+        assert_eq!(format!("{}", WaveValue::bus("a".to_string())), "10");
+        assert_eq!(format!("{}", WaveValue::bus("64".to_string())), "100");
+        assert_eq!(format!("{}", WaveValue::bus("3x".to_string())), "3x");
+        assert_eq!(format!("{}", WaveValue::bus("z6".to_string())), "z6");
+    }
+
+    #[test]
+    fn test_signed_decimal_formatting() {
+        let signed =
+            |value: &str, width| WaveValue::bus(value.to_string()).format_signed_decimal(width);
+
+        // The sign is the top bit of the declared width
+        assert_eq!(signed("7f", 8), "127");
+        assert_eq!(signed("80", 8), "-128");
+        assert_eq!(signed("ff", 8), "-1");
+        assert_eq!(signed("0", 8), "0");
+        assert_eq!(signed("7fff", 16), "32767");
+        assert_eq!(signed("8000", 16), "-32768");
+        assert_eq!(signed("ffff", 16), "-1");
+        // Values stored without their leading zeros are positive
+        assert_eq!(signed("ff", 16), "255");
+
+        assert_eq!(WaveValue::Binary(Value::V0).format_signed_decimal(1), "0");
+        assert_eq!(WaveValue::Binary(Value::V1).format_signed_decimal(1), "-1");
+        assert_eq!(signed("1", 1), "-1");
+
+        // Values wider than the signal are cut to its width first
+        assert_eq!(signed("1ff", 8), "-1");
+        assert_eq!(signed("17f", 8), "127");
+        assert_eq!(signed("f", 3), "-1");
+
+        // Unknown bits are formatted as before
+        assert_eq!(signed("8x", 8), "8x");
+        assert_eq!(signed("z0", 8), "z0");
+    }
+
+    #[test]
+    fn test_coalesce_changes() {
+        let v = |value: Value| WaveValue::Binary(value);
+        let mut values = vec![
+            (0, v(Value::V0)),
+            (10, v(Value::VX)),
+            (10, v(Value::V1)),
+            (20, v(Value::V0)),
+            (30, v(Value::V1)),
+            (30, v(Value::VZ)),
+            (30, v(Value::V0)),
+            (40, v(Value::V1)),
+            (40, v(Value::V1)),
+        ];
+        let glitches = coalesce_changes(&mut values);
+
+        // The last change at each time is kept, and a repeated change is no glitch
+        assert_eq!(
+            values,
+            vec![
+                (0, v(Value::V0)),
+                (10, v(Value::V1)),
+                (20, v(Value::V0)),
+                (30, v(Value::V0)),
+                (40, v(Value::V1)),
+            ]
+        );
+        assert_eq!(
+            glitches,
+            vec![
+                Glitch {
+                    time: 10,
+                    via: vec![v(Value::VX)],
+                },
+                Glitch {
+                    time: 30,
+                    via: vec![v(Value::V1), v(Value::VZ)],
+                },
+            ]
+        );
+
+        let mut clean = vec![(0, v(Value::V0)), (5, v(Value::V1))];
+        assert!(coalesce_changes(&mut clean).is_empty());
+        assert_eq!(clean.len(), 2);
+    }
+
+    #[test]
+    fn test_normalized_hex() {
+        assert_eq!(
+            WaveValue::bus("03".to_string()).normalized_hex().as_deref(),
+            Some("3")
+        );
+        assert_eq!(
+            WaveValue::bus("A0".to_string()).normalized_hex().as_deref(),
+            Some("a0")
+        );
+        assert_eq!(
+            WaveValue::bus("00".to_string()).normalized_hex().as_deref(),
+            Some("0")
+        );
+        assert_eq!(
+            WaveValue::Binary(Value::V1).normalized_hex().as_deref(),
+            Some("1")
+        );
+        assert_eq!(WaveValue::bus("3x".to_string()).normalized_hex(), None);
+        assert_eq!(WaveValue::Binary(Value::VZ).normalized_hex(), None);
+
+        // Bits that don't fill the top hex digit
+        assert_eq!(
+            hex_of_bits(&[Value::V1, Value::V0, Value::V0, Value::V0, Value::V1]).as_deref(),
+            Some("11")
+        );
     }
 }
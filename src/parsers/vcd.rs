@@ -1,17 +1,19 @@
 use super::parse_fns::*;
-use super::types::{Value, WaveValue, WaveformData};
-use indexmap::IndexMap;
+use super::types::{
+    coalesce_changes, split_time_unit, time_unit_femtoseconds, FileHeader, ParseReport,
+    SignalDefinition, TimeWindow, Value, WaveValue, WaveformData,
+};
 use nom::{
     branch::alt,
     bytes::complete::{is_not, tag, take_till1, take_until, take_while1},
     character::complete::{char, digit1, multispace0, multispace1, one_of},
-    combinator::{map_res, value},
-    sequence::preceded,
+    combinator::{map_res, opt, value},
+    sequence::{delimited, preceded},
     IResult, Parser,
 };
 use std::collections::HashMap;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
 use std::path::Path;
 use std::str;
 
@@ -22,84 +24,550 @@ struct VarDef {
     name: String,
     width: usize,
     var_type: String,
+    /// Bit range following the reference, e.g. `7:0` for `data [7:0]`
+    range: Option<String>,
+}
+
+impl VarDef {
+    /// Name used to identify the signal. The bit range is part of the name so that split vectors
+    /// like `data [7:0]` and `data [15:8]` are kept as separate signals.
+    fn canonical_name(&self) -> String {
+        match &self.range {
+            Some(range) => format!("{}[{}]", self.name, range),
+            None => self.name.clone(),
+        }
+    }
 }
 
-pub fn parse_vcd_file<P: AsRef<Path>>(path: P) -> io::Result<WaveformData> {
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
+/// Parser that keeps its place in a VCD file, so that value changes appended to a file that is
+/// still being written can be read without parsing the whole file again.
+pub struct VcdParser {
+    var_defs: HashMap<String, VarDef>,
+    /// Signal names in the order they were declared
+    signals: Vec<String>,
+    /// Names of the signals of each identifier. An identifier declared under several names, like a
+    /// port and the net connected to it, is one value stream shown as several signals.
+    id_to_names: HashMap<String, Vec<String>>,
+    name_to_id: HashMap<String, String>,
+    current_time: u64,
+    /// Latest timestamp so far, which `current_time` is behind after a timestamp going backwards
+    max_time: u64,
+    in_definitions: bool,
+    in_dumpvars: bool,
+    /// Whether the parser is inside a text directive like `$comment` whose `$end` is on a later line
+    in_text_block: bool,
+    /// Text of the `$date`, `$version` or `$timescale` directive being read, until its `$end`
+    header_text: Option<String>,
+    /// Length of one time unit in femtoseconds, once the `$timescale` was read
+    timescale_fs: Option<u64>,
+    /// Header directives describing the file
+    header: FileHeader,
+    current_scope: Vec<String>,
+    /// Bytes of the file parsed so far. The start of a line, unless `unfinished_line` was parsed.
+    offset: u64,
+    /// Last line of the file parsed before its newline was written, so only what is appended to it
+    /// is read next time
+    unfinished_line: Option<String>,
+    /// Complete lines of the file parsed so far
+    lines_read: usize,
+    /// Problems found in the file so far
+    report: ParseReport,
+    /// Part of the file to keep value changes from, resolved into `window` once the timescale is
+    /// known at `$enddefinitions`
+    time_window: TimeWindow,
+    window: Option<(u64, u64)>,
+    /// Why `time_window` couldn't be resolved, failing the parse
+    window_error: Option<String>,
+    /// Latest value of each identifier before the window starts, which become changes at its
+    /// start once a later time is reached. None once they have.
+    carried: Option<HashMap<String, WaveValue>>,
+}
+
+impl VcdParser {
+    pub fn new() -> Self {
+        Self {
+            var_defs: HashMap::new(),
+            signals: Vec::new(),
+            id_to_names: HashMap::new(),
+            name_to_id: HashMap::new(),
+            current_time: 0,
+            max_time: 0,
+            in_definitions: true,
+            in_dumpvars: false,
+            in_text_block: false,
+            header_text: None,
+            timescale_fs: None,
+            header: FileHeader::default(),
+            current_scope: Vec::new(),
+            offset: 0,
+            unfinished_line: None,
+            lines_read: 0,
+            report: ParseReport::default(),
+            time_window: TimeWindow::default(),
+            window: None,
+            window_error: None,
+            carried: None,
+        }
+    }
+
+    /// Keeps only the value changes within `window`, along with the value each signal had at its
+    /// start. Timestamps after it are still read for the end time of the waveform.
+    pub fn with_window(window: TimeWindow) -> Self {
+        Self {
+            time_window: window,
+            ..Self::new()
+        }
+    }
+
+    /// Problems found in everything parsed so far that didn't stop the file from loading.
+    pub fn report(&self) -> &ParseReport {
+        &self.report
+    }
+
+    /// Whether `$enddefinitions` was read. A file cut off before it has no value changes.
+    pub fn definitions_finished(&self) -> bool {
+        !self.in_definitions
+    }
+
+    /// The `$date`, `$version` and `$timescale` of the file, as far as it was parsed.
+    pub fn header(&self) -> &FileHeader {
+        &self.header
+    }
+
+    /// Parses the rest of the file, including a last line without a newline. If the writer hadn't
+    /// finished that line, `parse_appended` parses it again once it has more text.
+    pub fn parse_file<P: AsRef<Path>>(&mut self, path: P) -> io::Result<WaveformData> {
+        self.parse(path.as_ref(), true)
+    }
+
+    /// Parses the lines appended to the file since the last call, returning only their value
+    /// changes. Fails if the file got shorter or declares signals after `$enddefinitions`.
+    pub fn parse_appended<P: AsRef<Path>>(&mut self, path: P) -> io::Result<WaveformData> {
+        self.parse(path.as_ref(), false)
+    }
+
+    /// Parses a whole VCD from `reader`, like one piped to stdin. `parse_file` reads files the
+    /// same way.
+    pub fn parse_reader<R: BufRead>(&mut self, reader: R) -> io::Result<WaveformData> {
+        self.parse_lines(reader, true)
+    }
+
+    /// Parses only the header of a VCD from `reader`, stopping at `$enddefinitions` without
+    /// reading any of the value changes, and returns the declared signals in declaration order.
+    pub fn parse_definitions<R: BufRead>(
+        &mut self,
+        mut reader: R,
+    ) -> io::Result<Vec<SignalDefinition>> {
+        // Nothing before `$enddefinitions` changes a value
+        let mut values = HashMap::new();
+        let mut line = String::new();
+        while self.in_definitions {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                break;
+            }
+            self.offset += read as u64;
+            self.lines_read += 1;
+            self.parse_line(line.trim(), self.lines_read, &mut values);
+        }
+        Ok(self.signal_definitions())
+    }
+
+    /// Like `parse_definitions`, for the header of a file.
+    pub fn parse_definitions_file<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+    ) -> io::Result<Vec<SignalDefinition>> {
+        self.parse_definitions(BufReader::new(File::open(path)?))
+    }
+
+    // Helper function to describe the signals declared so far, in the order they were declared
+    fn signal_definitions(&self) -> Vec<SignalDefinition> {
+        self.signals
+            .iter()
+            .filter_map(|name| {
+                let var_def = self.var_defs.get(self.name_to_id.get(name)?)?;
+                Some(SignalDefinition {
+                    name: name.clone(),
+                    width: var_def.width,
+                    var_type: var_def.var_type.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn parse(&mut self, path: &Path, finished: bool) -> io::Result<WaveformData> {
+        let mut file = File::open(path)?;
+        if file.metadata()?.len() < self.offset {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "The file got shorter",
+            ));
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        self.parse_lines(BufReader::new(file), finished)
+    }
+
+    // Helper function to parse the lines of `reader`, which starts `self.offset` bytes into the
+    // file. Unless `finished`, a last line without a newline is left for the next call.
+    fn parse_lines<R: BufRead>(
+        &mut self,
+        mut reader: R,
+        finished: bool,
+    ) -> io::Result<WaveformData> {
+        let mut values: HashMap<String, Vec<(u64, WaveValue)>> = HashMap::new();
+        let backwards_timestamps = self.report.backwards_timestamps.count;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                break;
+            }
+            let line_number = self.lines_read + 1;
+            let complete = line.ends_with('\n');
+            if !complete && !finished {
+                break;
+            }
+            self.offset += read as u64;
+
+            // A line parsed before its newline was written is only parsed again if more of it was
+            // written since, so its changes aren't read twice
+            if let Some(start) = self.unfinished_line.take() {
+                if complete && line.trim().is_empty() {
+                    self.lines_read += 1;
+                    continue;
+                }
+                line.insert_str(0, &start);
+            }
+            if complete {
+                self.lines_read += 1;
+            } else {
+                self.unfinished_line = Some(line.clone());
+            }
+
+            let line = line.trim();
+            if !finished && !self.in_definitions && !self.in_text_block && is_definition(line) {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Signal definitions changed",
+                ));
+            }
+            self.parse_line(line, line_number, &mut values);
+            if let Some(error) = self.window_error.take() {
+                return Err(io::Error::new(io::ErrorKind::InvalidInput, error));
+            }
+        }
 
-    let mut var_defs = HashMap::new();
-    let mut id_to_name = IndexMap::new();
-    let mut current_time = 0u64;
-    let mut values: HashMap<String, Vec<(u64, WaveValue)>> = HashMap::new();
-    let mut in_definitions = true;
-    let mut in_dumpvars = false;
-    let mut current_scope = Vec::<String>::new();
+        // A window starting after the last timestamp still starts with the values carried to it
+        if finished {
+            if let Some((from, _)) = self.window {
+                self.record_carried(from.min(self.max_time), &mut values);
+            }
+        }
+
+        // Changes after a timestamp going backwards belong before some that were already read.
+        // Sorting keeps the changes at each time in file order.
+        if self.report.backwards_timestamps.count > backwards_timestamps {
+            for signal_values in values.values_mut() {
+                signal_values.sort_by_key(|(time, _)| *time);
+            }
+        }
+
+        // Signals changing more than once at a time, like in delta-cycle glitches, keep their last
+        // value at that time, which every lookup then agrees on
+        let glitches = values
+            .iter_mut()
+            .filter_map(|(signal, signal_values)| {
+                let glitches = coalesce_changes(signal_values);
+                (!glitches.is_empty()).then(|| (signal.clone(), glitches))
+            })
+            .collect();
+
+        // Appended changes can't declare signals, so only a whole file has widths to give
+        let widths = if finished {
+            self.name_to_id
+                .iter()
+                .filter_map(|(name, id)| Some((name.clone(), self.var_defs.get(id)?.width)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(WaveformData {
+            signals: self.signals.clone(),
+            values,
+            max_time: self.max_time,
+            timescale_fs: self.timescale_fs,
+            widths,
+            window: self
+                .window
+                .map(|(from, to)| (from.min(self.max_time), to.min(self.max_time))),
+            glitches,
+        })
+    }
+
+    fn parse_line(
+        &mut self,
+        line: &str,
+        line_number: usize,
+        values: &mut HashMap<String, Vec<(u64, WaveValue)>>,
+    ) {
+        // Text directives may span several lines, whose contents mean nothing until their `$end`
+        if self.in_text_block || is_text_directive(line) {
+            if !self.in_text_block && !line.starts_with("$comment") {
+                self.header_text = Some(String::new());
+            }
+            if let Some(text) = &mut self.header_text {
+                text.push_str(line);
+                text.push(' ');
+            }
 
-    for line in reader.lines() {
-        let line = line?;
-        let line = line.trim();
+            self.in_text_block = !line.contains("$end");
+            if !self.in_text_block {
+                if let Some(text) = self.header_text.take() {
+                    self.read_header_directive(&text);
+                }
+            }
+            return;
+        }
 
         if line.starts_with("$var") {
             if let Ok((_, var_def)) = parse_var_declaration(line) {
                 // Combine full hierarchical name using the current scope
                 let mut full_name = String::new();
-                for scope in &current_scope {
+                for scope in &self.current_scope {
                     full_name.push_str(scope);
                     full_name.push('.');
                 }
-                full_name.push_str(&var_def.name);
+                full_name.push_str(&var_def.canonical_name());
+
+                // Declaring the same identifier under the same name again adds nothing. Distinct
+                // identifiers must never share a name, otherwise their value changes would be
+                // interleaved in one signal, so later ones get a `$1`, `$2`, ... suffix.
+                match self.name_to_id.get(&full_name) {
+                    Some(id) if *id == var_def.id => return,
+                    Some(_) => {
+                        full_name = (1..)
+                            .map(|n| format!("{}${}", full_name, n))
+                            .find(|name| !self.name_to_id.contains_key(name))
+                            .expect("unbounded range always finds a free name");
+                    }
+                    None => {}
+                }
 
-                var_defs.insert(var_def.id.clone(), var_def.clone());
-                id_to_name.insert(var_def.id.clone(), full_name);
+                self.var_defs.insert(var_def.id.clone(), var_def.clone());
+                self.name_to_id
+                    .insert(full_name.clone(), var_def.id.clone());
+                self.signals.push(full_name.clone());
+                self.id_to_names
+                    .entry(var_def.id)
+                    .or_default()
+                    .push(full_name);
+            } else {
+                self.report.invalid_lines.record(line_number, line);
             }
         } else if line.starts_with("$scope") {
             if let Ok((_, scope_name)) = parse_scope_declaration(line) {
-                current_scope.push(scope_name);
+                self.current_scope.push(scope_name);
             }
         } else if line.starts_with("$upscope") {
-            if !current_scope.is_empty() {
-                current_scope.pop();
+            if !self.current_scope.is_empty() {
+                self.current_scope.pop();
             }
         } else if line.starts_with("$enddefinitions") {
-            in_definitions = false;
+            self.in_definitions = false;
+            if !self.time_window.is_whole() {
+                match self.time_window.resolve(self.timescale_fs) {
+                    Ok(window) => {
+                        self.window = Some(window);
+                        self.carried = Some(HashMap::new());
+                    }
+                    Err(error) => self.window_error = Some(error),
+                }
+            }
         } else if line.starts_with("$dumpvars") {
-            in_dumpvars = true;
-        } else if line.starts_with("$end") && in_dumpvars {
-            in_dumpvars = false;
-        } else if !in_definitions && line.starts_with("#") {
-            if let Ok((_, time)) = parse_time_stamp(line) {
-                current_time = time;
+            self.in_dumpvars = true;
+        } else if line.starts_with("$end") && self.in_dumpvars {
+            self.in_dumpvars = false;
+        } else if !self.in_definitions && line.starts_with("#") {
+            match parse_time_stamp(line) {
+                Ok((_, time)) => {
+                    if time < self.current_time {
+                        self.report.backwards_timestamps.record(line_number, line);
+                    }
+                    self.current_time = time;
+                    self.max_time = self.max_time.max(time);
+                    if let Some((from, _)) = self.window {
+                        if time > from {
+                            self.record_carried(from, values);
+                        }
+                    }
+                }
+                Err(_) => self.report.invalid_lines.record(line_number, line),
+            }
+        } else if !self.in_definitions && !line.is_empty() && !line.starts_with("$") {
+            // Changes after the window aren't even parsed, only their timestamps are
+            if self.window.is_some_and(|(_, to)| self.current_time > to) {
+                return;
+            }
+            let Ok((_, (value, id))) = parse_value_change(line) else {
+                self.report.invalid_lines.record(line_number, line);
+                return;
+            };
+            if !self.id_to_names.contains_key(&id) {
+                self.report.unknown_ids.record(line_number, line);
+                return;
             }
-        } else if !in_definitions && !line.is_empty() && !line.starts_with("$") {
-            if let Ok((_, (value, id))) = parse_value_change(line) {
-                if let Some(signal_name) = id_to_name.get(&id) {
-                    let signal_values = values.entry(signal_name.clone()).or_insert_with(Vec::new);
-                    signal_values.push((current_time, value));
+
+            // Track the latest value of each identifier until the window starts, and only record
+            // changes from then on
+            let width = self.var_defs.get(&id).map_or(1, |var_def| var_def.width);
+            let value = bus_to_hex(fit_to_width(value, width));
+            match &mut self.carried {
+                Some(carried) => {
+                    carried.insert(id, value);
                 }
+                None => self.record_change(&id, self.current_time, value, values),
             }
         }
     }
 
-    // Convert binary values to hex strings for bus signals
-    for (_, signal_values) in values.iter_mut() {
-        for (_, value) in signal_values.iter_mut() {
-            if let WaveValue::Bus(bin_str) = value {
-                // Only convert if the value looks like a binary string (all 0s and 1s)
-                if bin_str.chars().all(|c| c == '0' || c == '1') {
-                    if let Ok((_, hex_str)) = parse_binary_to_hex(bin_str) {
-                        *value = WaveValue::Bus(hex_str);
-                    }
-                }
+    // Helper function to add a change of identifier `id` to the values of each of its signals
+    fn record_change(
+        &self,
+        id: &str,
+        time: u64,
+        value: WaveValue,
+        values: &mut HashMap<String, Vec<(u64, WaveValue)>>,
+    ) {
+        let Some(signal_names) = self.id_to_names.get(id) else {
+            return;
+        };
+        let change = (time, value);
+        for signal_name in signal_names {
+            let signal_values = values.entry(signal_name.clone()).or_default();
+            // A line read again after it was finished doesn't repeat its change
+            if signal_values.last() != Some(&change) {
+                signal_values.push(change.clone());
             }
         }
     }
 
-    Ok(WaveformData {
-        signals: id_to_name.values().cloned().collect(),
-        values,
-        max_time: current_time,
-    })
+    // Helper function to record the values carried to the start of the window as changes at `time`,
+    // once the parser gets past it
+    fn record_carried(&mut self, time: u64, values: &mut HashMap<String, Vec<(u64, WaveValue)>>) {
+        let Some(carried) = self.carried.take() else {
+            return;
+        };
+        for (id, value) in carried {
+            self.record_change(&id, time, value, values);
+        }
+    }
+
+    // Helper function to keep the text of a whole header directive, like "$date May 1 $end"
+    fn read_header_directive(&mut self, text: &str) {
+        let Some((keyword, rest)) = text.split_once(char::is_whitespace) else {
+            return;
+        };
+        let contents = rest.split("$end").next().unwrap_or_default();
+        let contents = contents.split_whitespace().collect::<Vec<_>>().join(" ");
+        let contents = (!contents.is_empty()).then_some(contents);
+
+        match keyword {
+            "$date" => self.header.date = contents,
+            "$version" => self.header.version = contents,
+            "$timescale" => {
+                self.timescale_fs = parse_timescale(text);
+                self.header.timescale = contents;
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Default for VcdParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Whether a line declares signals or scopes, which is only allowed before `$enddefinitions`
+fn is_definition(line: &str) -> bool {
+    ["$var", "$scope", "$upscope"]
+        .iter()
+        .any(|keyword| line.starts_with(keyword))
+}
+
+// Whether a line starts a directive holding free text, which ends at the next `$end`
+fn is_text_directive(line: &str) -> bool {
+    ["$comment", "$date", "$version", "$timescale"]
+        .iter()
+        .any(|keyword| line.starts_with(keyword))
+}
+
+// Length of one time unit in femtoseconds from the text of a `$timescale` directive, like
+// "$timescale 10 ns $end". The number and unit may be split across lines or spaces.
+fn parse_timescale(text: &str) -> Option<u64> {
+    let text: String = text
+        .trim_start_matches("$timescale")
+        .split("$end")
+        .next()?
+        .split_whitespace()
+        .collect();
+    let (number, unit) = split_time_unit(&text);
+    let number: u64 = number.parse().ok()?;
+    number.checked_mul(time_unit_femtoseconds(unit)?)
+}
+
+// Makes a value as wide as the variable it is assigned to. A scalar value assigned to a vector, or a
+// vector value with fewer bits than the vector, is extended to the left with zeros, or with x or z
+// if its leftmost bit is x or z. Single bit vector values of scalar variables become scalar values.
+fn fit_to_width(value: WaveValue, width: usize) -> WaveValue {
+    let bits = match &value {
+        WaveValue::Binary(value) if width > 1 => match value {
+            Value::V0 => "0",
+            Value::V1 => "1",
+            Value::VX => "x",
+            Value::VZ => "z",
+        },
+        WaveValue::Bus(bits) if !bits.starts_with('r') => bits.as_str(),
+        _ => return value,
+    };
+
+    if width == 1 && bits.len() == 1 {
+        return match bits {
+            "0" => WaveValue::Binary(Value::V0),
+            "1" => WaveValue::Binary(Value::V1),
+            "x" | "X" => WaveValue::Binary(Value::VX),
+            _ => WaveValue::Binary(Value::VZ),
+        };
+    }
+    if bits.len() >= width {
+        return value;
+    }
+    let fill = bits
+        .chars()
+        .next()
+        .filter(|c| "xXzZ".contains(*c))
+        .unwrap_or('0');
+    let extension = std::iter::repeat_n(fill, width - bits.len());
+    WaveValue::Bus(extension.chain(bits.chars()).collect())
+}
+
+// Converts the binary string of a bus value to hex
+fn bus_to_hex(value: WaveValue) -> WaveValue {
+    match value {
+        // Only convert if the value looks like a binary string (all 0s and 1s)
+        WaveValue::Bus(bin_str) if bin_str.chars().all(|c| c == '0' || c == '1') => {
+            match parse_binary_to_hex(&bin_str) {
+                Ok((_, hex_str)) => WaveValue::Bus(hex_str),
+                Err(_) => WaveValue::Bus(bin_str),
+            }
+        }
+        value => value,
+    }
 }
 
 fn parse_scope_declaration(input: &str) -> IResult<&str, String> {
@@ -132,7 +600,15 @@ fn parse_var_declaration(input: &str) -> IResult<&str, VarDef> {
     let (input, id) = take_till1(|c: char| c.is_whitespace())(input)?; // identifier
     let (input, _) = multispace1(input)?;
 
-    let (input, name) = take_till1(|c: char| c.is_whitespace() || c == '$')(input)?; // reference
+    let (input, name) = parse_reference(input)?; // reference
+
+    // Optional bit range, e.g. [7:0] or [3]
+    let (input, range) = opt(preceded(
+        multispace0,
+        delimited(char('['), is_not("]"), char(']')),
+    ))
+    .parse(input)?;
+    let range = range.map(|r: &str| r.chars().filter(|c| !c.is_whitespace()).collect());
 
     // Optional whitespace before $end
     let (input, _) = multispace0(input)?;
@@ -143,13 +619,45 @@ fn parse_var_declaration(input: &str) -> IResult<&str, VarDef> {
         input,
         VarDef {
             id: id.to_string(),
-            name: name.to_string(),
+            name,
             width,
             var_type: var_type.to_string(),
+            range,
         },
     ))
 }
 
+fn parse_reference(input: &str) -> IResult<&str, String> {
+    // A backslash escapes the next character, which allows escaped identifiers to contain spaces.
+    // Escaped identifiers (starting with a backslash) also keep any '$' or '[' characters, while
+    // for plain references they mark the start of `$end` or a bit range.
+    let escaped_identifier = input.starts_with('\\');
+    let mut name = String::new();
+    let mut chars = input.char_indices();
+    let mut end = input.len();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, escaped)) => name.push(escaped),
+                None => break,
+            },
+            c if c.is_whitespace() || (!escaped_identifier && (c == '$' || c == '[')) => {
+                end = i;
+                break;
+            }
+            c => name.push(c),
+        }
+    }
+
+    if name.is_empty() {
+        return Err(nom::Err::Error(nom::error::Error::new(
+            input,
+            nom::error::ErrorKind::TakeTill1,
+        )));
+    }
+    Ok((&input[end..], name))
+}
+
 fn parse_time_stamp(input: &str) -> IResult<&str, u64> {
     let (input, _) = char('#')(input)?;
     let (input, time) = map_res(digit1, |s: &str| s.parse::<u64>()).parse(input)?;
@@ -159,7 +667,7 @@ fn parse_time_stamp(input: &str) -> IResult<&str, u64> {
 fn parse_value_change(input: &str) -> IResult<&str, (WaveValue, String)> {
     alt((
         // Binary values (0, 1, x, z)
-        ((
+        (
             alt((
                 value(Value::V0, char::<&str, nom::error::Error<&str>>('0')),
                 value(Value::V1, char::<&str, nom::error::Error<&str>>('1')),
@@ -167,37 +675,38 @@ fn parse_value_change(input: &str) -> IResult<&str, (WaveValue, String)> {
                 value(Value::VZ, one_of::<&str, _, nom::error::Error<&str>>("zZ")),
             )),
             // The identifier follows the value with no whitespace
-            take_while1(|c: char| c.is_ascii()),
-        ))
+            take_till1(|c: char| c.is_whitespace()),
+        )
             .map(|(value, id): (Value, &str)| (WaveValue::Binary(value), id.to_string())),
         // Parse bus values (b followed by bit string)
-        ((
+        (
             preceded(
                 one_of::<&str, _, nom::error::Error<&str>>("bB"), // Support both 'b' and 'B' prefixes
                 take_while1(|c: char| "01xXzZ".contains(c)),
             ),
             preceded(
-                multispace0,
-                // Take the identifier (one or more valid identifier chars)
-                take_while1(|c: char| c.is_ascii()),
+                multispace1,
+                // The identifier may start with characters that are valid in the value, like `b1`
+                // or `1f`, so only whitespace separates the two
+                take_till1(|c: char| c.is_whitespace()),
             ),
-        ))
-            .map(|(value, id): (&str, &str)| (WaveValue::Bus(value.to_string()), id.to_string())),
+        )
+            .map(|(value, id): (&str, &str)| (WaveValue::Bus(value.into()), id.to_string())),
         // Support for real values (r followed by a real number)
-        ((
+        (
             preceded(
                 one_of::<&str, _, nom::error::Error<&str>>("rR"),
                 take_while1(|c: char| "0123456789.eE+-".contains(c)),
             ),
-            preceded(multispace0, take_while1(|c: char| c.is_ascii())),
-        ))
+            preceded(multispace1, take_till1(|c: char| c.is_whitespace())),
+        )
             .map(|(value, id): (&str, &str)| {
                 // FIXME: Real values are simply placed into a bus format right now. There is no
                 // intention for this app to support mixed signal waveforms, and I don't think that
                 // a TUI would be a good UI for viewing non-binary signals. However, the parser
                 // should still be able to handle these values. Need to review this to determine if
                 // a new enum variant should be added for real values.
-                (WaveValue::Bus(format!("r{}", value)), id.to_string())
+                (WaveValue::Bus(format!("r{}", value).into()), id.to_string())
             }),
     ))
     .parse(input)
@@ -206,7 +715,8 @@ fn parse_value_change(input: &str) -> IResult<&str, (WaveValue, String)> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
+    use crate::parsers::types::Glitch;
+    use std::io::{Cursor, Write};
     use tempfile::NamedTempFile;
 
     #[test]
@@ -247,6 +757,51 @@ mod tests {
         assert_eq!(var_def.var_type, "wire");
     }
 
+    #[test]
+    fn test_parse_var_declaration_with_range() {
+        let input = "$var wire 8 % data [7:0] $end";
+        let (remaining, var_def) = parse_var_declaration(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(var_def.name, "data");
+        assert_eq!(var_def.range, Some("7:0".to_string()));
+        assert_eq!(var_def.canonical_name(), "data[7:0]");
+
+        // Range attached to the name, with whitespace inside the brackets
+        let input = "$var wire 8 % data[15: 8] $end";
+        let (remaining, var_def) = parse_var_declaration(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(var_def.canonical_name(), "data[15:8]");
+
+        // Single bit select
+        let input = "$var wire 1 & flags [3] $end";
+        let (_, var_def) = parse_var_declaration(input).unwrap();
+        assert_eq!(var_def.canonical_name(), "flags[3]");
+
+        // No range
+        let input = "$var wire 1 # clk $end";
+        let (_, var_def) = parse_var_declaration(input).unwrap();
+        assert_eq!(var_def.range, None);
+        assert_eq!(var_def.canonical_name(), "clk");
+    }
+
+    #[test]
+    fn test_parse_var_declaration_escaped_identifier() {
+        let input = "$var wire 1 ! \\my\\ signal $end";
+        let (remaining, var_def) = parse_var_declaration(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(var_def.name, "my signal");
+
+        let input = "$var wire 4 \" \\bus\\ a\\$b [3:0] $end";
+        let (remaining, var_def) = parse_var_declaration(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(var_def.canonical_name(), "bus a$b[3:0]");
+
+        let input = "$var wire 1 # \\arr[2] $end";
+        let (_, var_def) = parse_var_declaration(input).unwrap();
+        assert_eq!(var_def.name, "arr[2]");
+        assert_eq!(var_def.range, None);
+    }
+
     #[test]
     fn test_parse_scope_declaration() {
         let input = "$scope module top $end";
@@ -367,6 +922,127 @@ mod tests {
         assert!(matches!(value, WaveValue::Bus(ref s) if s == "10101010"));
     }
 
+    #[test]
+    fn test_parse_value_change_hex_like_identifiers() {
+        // Scalar values are followed by identifiers starting with characters of vector values
+        let input = "0b1";
+        let (remaining, (value, id)) = parse_value_change(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(id, "b1");
+        assert!(matches!(value, WaveValue::Binary(Value::V0)));
+
+        let input = "x1f";
+        let (remaining, (value, id)) = parse_value_change(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(id, "1f");
+        assert!(matches!(value, WaveValue::Binary(Value::VX)));
+
+        let input = "1(#a";
+        let (remaining, (value, id)) = parse_value_change(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(id, "(#a");
+        assert!(matches!(value, WaveValue::Binary(Value::V1)));
+
+        // Identifiers made of characters that are valid bits only start after the whitespace
+        let input = "b1 x";
+        let (remaining, (value, id)) = parse_value_change(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(id, "x");
+        assert!(matches!(value, WaveValue::Bus(ref s) if s == "1"));
+
+        let input = "b10 01z";
+        let (remaining, (value, id)) = parse_value_change(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(id, "01z");
+        assert!(matches!(value, WaveValue::Bus(ref s) if s == "10"));
+
+        // A vector value needs whitespace before its identifier
+        assert!(parse_value_change("b10x").is_err());
+    }
+
+    #[test]
+    fn test_fit_to_width() {
+        // Scalar values of vectors fill the whole vector
+        assert_eq!(
+            fit_to_width(WaveValue::Binary(Value::VX), 8),
+            WaveValue::Bus("xxxxxxxx".into())
+        );
+        assert_eq!(
+            fit_to_width(WaveValue::Binary(Value::V1), 4),
+            WaveValue::Bus("0001".into())
+        );
+        assert_eq!(
+            fit_to_width(WaveValue::Binary(Value::V0), 1),
+            WaveValue::Binary(Value::V0)
+        );
+
+        // Short vector values are extended to the left
+        assert_eq!(
+            fit_to_width(WaveValue::Bus("z1".into()), 4),
+            WaveValue::Bus("zzz1".into())
+        );
+        assert_eq!(
+            fit_to_width(WaveValue::Bus("101".into()), 6),
+            WaveValue::Bus("000101".into())
+        );
+        assert_eq!(
+            fit_to_width(WaveValue::Bus("1010".into()), 4),
+            WaveValue::Bus("1010".into())
+        );
+
+        // Single bit vector values of scalars become scalar values
+        assert_eq!(
+            fit_to_width(WaveValue::Bus("x".into()), 1),
+            WaveValue::Binary(Value::VX)
+        );
+
+        // Real values are left alone
+        assert_eq!(
+            fit_to_width(WaveValue::Bus("r1.5".into()), 8),
+            WaveValue::Bus("r1.5".into())
+        );
+    }
+
+    #[test]
+    fn test_parse_vcd_scalar_values_of_vectors() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        writeln!(temp_file, "$scope module top $end").unwrap();
+        writeln!(temp_file, "$var wire 8 b1 data [7:0] $end").unwrap();
+        writeln!(temp_file, "$var wire 1 1f valid $end").unwrap();
+        writeln!(temp_file, "$upscope $end").unwrap();
+        writeln!(temp_file, "$enddefinitions $end").unwrap();
+        writeln!(temp_file, "#0").unwrap();
+        writeln!(temp_file, "$dumpvars").unwrap();
+        writeln!(temp_file, "xb1").unwrap();
+        writeln!(temp_file, "01f").unwrap();
+        writeln!(temp_file, "$end").unwrap();
+        writeln!(temp_file, "#10").unwrap();
+        writeln!(temp_file, "b101 b1").unwrap();
+        writeln!(temp_file, "b1 1f").unwrap();
+        writeln!(temp_file, "#20").unwrap();
+        writeln!(temp_file, "zb1").unwrap();
+        temp_file.flush().unwrap();
+
+        let waveform_data = VcdParser::new().parse_file(temp_file.path()).unwrap();
+        assert_eq!(
+            waveform_data.values["top.data[7:0]"],
+            vec![
+                (0, WaveValue::Bus("xxxxxxxx".into())),
+                (10, WaveValue::Bus("05".into())),
+                (20, WaveValue::Bus("zzzzzzzz".into())),
+            ]
+        );
+        assert_eq!(
+            waveform_data.values["top.valid"],
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (10, WaveValue::Binary(Value::V1)),
+            ]
+        );
+        assert_eq!(waveform_data.widths["top.data[7:0]"], 8);
+        assert_eq!(waveform_data.widths["top.valid"], 1);
+    }
+
     #[test]
     fn test_parse_value_change_real() {
         let input = "r1.234 %";
@@ -397,6 +1073,303 @@ mod tests {
         assert!(matches!(value, WaveValue::Bus(ref s) if s == "r1.234"));
     }
 
+    #[test]
+    fn test_parse_reader() {
+        // A VCD piped in is read like a file, including a last line without a newline
+        let vcd = "$timescale 1ns $end\n\
+                   $scope module top $end\n\
+                   $var wire 1 ! clk $end\n\
+                   $var wire 4 \" count $end\n\
+                   $upscope $end\n\
+                   $enddefinitions $end\n\
+                   #0\n0!\nb0 \"\n#10\n1!\nb1010 \"\n#20\n0!";
+        let from_reader = VcdParser::new().parse_reader(Cursor::new(vcd)).unwrap();
+
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", vcd).unwrap();
+        let from_file = VcdParser::new().parse_file(temp_file.path()).unwrap();
+
+        assert_eq!(from_reader.signals, vec!["top.clk", "top.count"]);
+        assert_eq!(from_reader.max_time, 20);
+        assert_eq!(from_reader.values["top.clk"].len(), 3);
+        assert_eq!(from_reader.widths["top.count"], 4);
+        assert_eq!(from_reader.values, from_file.values);
+        assert_eq!(from_reader.timescale_fs, from_file.timescale_fs);
+    }
+
+    #[test]
+    fn test_parse_same_time_changes() {
+        // The last change at a time wins, and the ones before it are kept as glitches, also when a
+        // timestamp going backwards puts changes at a time already read
+        let vcd = "$scope module top $end\n\
+                   $var wire 1 ! clk $end\n\
+                   $var wire 4 \" count $end\n\
+                   $upscope $end\n\
+                   $enddefinitions $end\n\
+                   #0\n0!\nb0 \"\n\
+                   #10\nx!\n1!\nb1 \"\nb10 \"\nb11 \"\n\
+                   #20\n0!\n\
+                   #10\n0!\n";
+        let data = VcdParser::new().parse_reader(Cursor::new(vcd)).unwrap();
+
+        let bit = |value| WaveValue::Binary(value);
+        assert_eq!(
+            data.values["top.clk"],
+            vec![
+                (0, bit(Value::V0)),
+                (10, bit(Value::V0)),
+                (20, bit(Value::V0))
+            ]
+        );
+        assert_eq!(
+            data.glitches["top.clk"],
+            vec![Glitch {
+                time: 10,
+                via: vec![bit(Value::VX), bit(Value::V1)],
+            }]
+        );
+        assert_eq!(
+            data.values["top.count"],
+            vec![
+                (0, WaveValue::Bus("0".into())),
+                (10, WaveValue::Bus("3".into())),
+            ]
+        );
+        assert_eq!(
+            data.glitches["top.count"],
+            vec![Glitch {
+                time: 10,
+                via: vec![WaveValue::Bus("1".into()), WaveValue::Bus("2".into()),],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_definitions_stops_at_enddefinitions() {
+        // The body isn't even text, which would fail a full parse
+        let mut vcd = b"$timescale 1ns $end\n\
+                        $scope module top $end\n\
+                        $var wire 1 ! clk $end\n\
+                        $scope module cpu $end\n\
+                        $var reg 32 \" pc $end\n\
+                        $upscope $end\n\
+                        $upscope $end\n\
+                        $enddefinitions $end\n\
+                        #0\n"
+            .to_vec();
+        vcd.extend_from_slice(&[0xff, 0xfe, b'\n']);
+        assert!(VcdParser::new().parse_reader(Cursor::new(&vcd)).is_err());
+
+        let mut parser = VcdParser::new();
+        let definitions = parser.parse_definitions(Cursor::new(&vcd)).unwrap();
+        assert_eq!(
+            definitions,
+            vec![
+                SignalDefinition {
+                    name: "top.clk".to_string(),
+                    width: 1,
+                    var_type: "wire".to_string(),
+                },
+                SignalDefinition {
+                    name: "top.cpu.pc".to_string(),
+                    width: 32,
+                    var_type: "reg".to_string(),
+                },
+            ]
+        );
+        assert!(parser.definitions_finished());
+        assert_eq!(parser.header().timescale.as_deref(), Some("1ns"));
+    }
+
+    #[test]
+    fn test_parse_report_of_malformed_file() {
+        let vcd = include_str!("../../resources/test_data_malformed.vcd");
+        let mut parser = VcdParser::new();
+        let data = parser.parse_reader(Cursor::new(vcd)).unwrap();
+
+        // Changes of the undeclared identifier `?` are dropped
+        let report = parser.report();
+        assert_eq!(report.unknown_ids.count, 2);
+        assert_eq!(
+            report.unknown_ids.examples,
+            vec![(13, "1?".to_string()), (21, "b0111 ?".to_string())]
+        );
+        assert_eq!(report.invalid_lines.count, 1);
+        assert_eq!(report.invalid_lines.examples[0].0, 22);
+
+        // The changes after going back to #20 are sorted in before those at #30
+        assert_eq!(report.backwards_timestamps.count, 1);
+        assert_eq!(
+            report.backwards_timestamps.examples,
+            vec![(18, "#20".to_string())]
+        );
+        let times: Vec<u64> = data.values["top.count"].iter().map(|(t, _)| *t).collect();
+        assert_eq!(times, vec![0, 10, 20, 30]);
+        let times: Vec<u64> = data.values["top.clk"].iter().map(|(t, _)| *t).collect();
+        assert_eq!(times, vec![0, 10, 20, 30, 40]);
+        assert_eq!(data.max_time, 40);
+
+        assert_eq!(
+            report.summary(),
+            "2 change(s) of undeclared identifiers, 1 timestamp(s) going backwards, \
+             1 invalid line(s)"
+        );
+        assert_eq!(
+            report.details()[..3],
+            [
+                "2 change(s) of undeclared identifiers, dropped:",
+                "  line 13: 1?",
+                "  line 21: b0111 ?",
+            ]
+        );
+
+        // A well-formed file has nothing to report
+        let mut parser = VcdParser::new();
+        parser
+            .parse_reader(Cursor::new(include_str!("../../resources/test_data.vcd")))
+            .unwrap();
+        assert!(parser.report().is_empty());
+    }
+
+    #[test]
+    fn test_parse_header_directives() {
+        let vcd = "$date November 11, 2023 $end\n\
+                   $version Test VCD 1.0 $end\n\
+                   $comment $date not this one $end\n\
+                   $timescale 10 ns $end\n\
+                   $enddefinitions $end\n";
+        let mut parser = VcdParser::new();
+        parser.parse_reader(Cursor::new(vcd)).unwrap();
+        assert_eq!(
+            parser.header(),
+            &FileHeader {
+                date: Some("November 11, 2023".to_string()),
+                version: Some("Test VCD 1.0".to_string()),
+                timescale: Some("10 ns".to_string()),
+            }
+        );
+
+        // Directives that are missing or empty have no text
+        let mut parser = VcdParser::new();
+        parser
+            .parse_reader(Cursor::new("$date $end\n$enddefinitions $end\n"))
+            .unwrap();
+        assert_eq!(parser.header(), &FileHeader::default());
+    }
+
+    const WINDOW_VCD: &str = "$timescale 1ns $end\n\
+                              $scope module top $end\n\
+                              $var wire 1 ! clk $end\n\
+                              $var wire 8 % data $end\n\
+                              $var wire 1 & done $end\n\
+                              $upscope $end\n\
+                              $enddefinitions $end\n\
+                              #0\n\
+                              $dumpvars\n\
+                              0!\n\
+                              b0 %\n\
+                              $end\n\
+                              #10\n\
+                              1!\n\
+                              b1010 %\n\
+                              #20\n\
+                              0!\n\
+                              #30\n\
+                              1!\n\
+                              b1111 %\n\
+                              #40\n\
+                              0!\n\
+                              #50\n\
+                              1!\n\
+                              1&\n\
+                              #60\n";
+
+    #[test]
+    fn test_parse_window_matches_full_parse() {
+        let full = VcdParser::new()
+            .parse_reader(Cursor::new(WINDOW_VCD))
+            .unwrap();
+        let window = TimeWindow {
+            from: Some("15".to_string()),
+            to: Some("40ns".to_string()),
+        };
+        let partial = VcdParser::with_window(window)
+            .parse_reader(Cursor::new(WINDOW_VCD))
+            .unwrap();
+
+        assert_eq!(partial.window, Some((15, 40)));
+        assert_eq!(full.window, None);
+        // Timestamps after the window still give the end of the waveform
+        assert_eq!(partial.max_time, 60);
+
+        // Each signal starts with its value at 15, then changes as in the whole file up to 40
+        for signal in ["top.clk", "top.data"] {
+            let values = &full.values[signal];
+            let carried = values.iter().rev().find(|(time, _)| *time <= 15).unwrap();
+            let mut expected = vec![(15, carried.1.clone())];
+            expected.extend(
+                values
+                    .iter()
+                    .filter(|(time, _)| *time > 15 && *time <= 40)
+                    .cloned(),
+            );
+            assert_eq!(partial.values[signal], expected, "{}", signal);
+        }
+        // A signal without a value until after the window has none in it
+        assert!(!partial.values.contains_key("top.done"));
+    }
+
+    #[test]
+    fn test_parse_window_open_ends() {
+        // A window starting exactly at a change carries in the value from that time
+        let window = TimeWindow {
+            from: Some("30".to_string()),
+            to: None,
+        };
+        let data = VcdParser::with_window(window)
+            .parse_reader(Cursor::new(WINDOW_VCD))
+            .unwrap();
+        assert_eq!(data.window, Some((30, 60)));
+        assert_eq!(
+            data.values["top.data"],
+            vec![(30, WaveValue::Bus("0F".into()))]
+        );
+        assert_eq!(
+            data.values["top.done"][0],
+            (50, WaveValue::Binary(Value::V1))
+        );
+
+        // Starting after the last timestamp, signals hold their last values at the end
+        let window = TimeWindow {
+            from: Some("100".to_string()),
+            to: None,
+        };
+        let data = VcdParser::with_window(window)
+            .parse_reader(Cursor::new(WINDOW_VCD))
+            .unwrap();
+        assert_eq!(
+            data.values["top.clk"],
+            vec![(60, WaveValue::Binary(Value::V1))]
+        );
+    }
+
+    #[test]
+    fn test_parse_window_errors() {
+        let parse = |from: &str, to: &str| {
+            let window = TimeWindow {
+                from: Some(from.to_string()),
+                to: Some(to.to_string()),
+            };
+            VcdParser::with_window(window)
+                .parse_reader(Cursor::new(WINDOW_VCD))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(parse("40", "20"), "--to 20 is before --from 40");
+        assert_eq!(parse("soon", "20"), "--from: Invalid time: soon");
+        assert_eq!(parse("10", "1pc"), "--to: Unknown time unit: pc");
+    }
+
     #[test]
     fn test_parse_simple_vcd() {
         let mut temp_file = NamedTempFile::new().unwrap();
@@ -429,7 +1402,7 @@ mod tests {
         writeln!(temp_file, "b10101010 %").unwrap();
 
         // Parse the VCD file
-        let vcd_data = parse_vcd_file(temp_file.path()).unwrap();
+        let vcd_data = VcdParser::new().parse_file(temp_file.path()).unwrap();
 
         // Check the parsed data
         assert_eq!(vcd_data.signals.len(), 3);
@@ -465,4 +1438,300 @@ mod tests {
         assert_eq!(data_values[4].0, 20);
         assert!(matches!(data_values[4].1, WaveValue::Bus(ref s) if s == "AA"));
     }
+
+    #[test]
+    fn test_parse_vcd_split_vectors() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+
+        writeln!(temp_file, "$scope module top $end").unwrap();
+        writeln!(temp_file, "$var wire 8 ! data [7:0] $end").unwrap();
+        writeln!(temp_file, "$var wire 8 \" data [15:8] $end").unwrap();
+        writeln!(temp_file, "$var wire 1 # clk $end").unwrap();
+        writeln!(temp_file, "$var wire 1 $ clk $end").unwrap();
+        writeln!(temp_file, "$upscope $end").unwrap();
+        writeln!(temp_file, "$enddefinitions $end").unwrap();
+        writeln!(temp_file, "#0").unwrap();
+        writeln!(temp_file, "b00000001 !").unwrap();
+        writeln!(temp_file, "b00000010 \"").unwrap();
+        writeln!(temp_file, "#10").unwrap();
+        writeln!(temp_file, "b11111111 !").unwrap();
+        writeln!(temp_file, "1#").unwrap();
+
+        let vcd_data = VcdParser::new().parse_file(temp_file.path()).unwrap();
+
+        assert_eq!(
+            vcd_data.signals,
+            vec!["top.data[7:0]", "top.data[15:8]", "top.clk", "top.clk$1"]
+        );
+
+        let low = vcd_data.values.get("top.data[7:0]").unwrap();
+        assert_eq!(low.len(), 2);
+        assert!(matches!(low[0].1, WaveValue::Bus(ref s) if s == "01"));
+        assert!(matches!(low[1].1, WaveValue::Bus(ref s) if s == "FF"));
+
+        let high = vcd_data.values.get("top.data[15:8]").unwrap();
+        assert_eq!(high.len(), 1);
+        assert!(matches!(high[0].1, WaveValue::Bus(ref s) if s == "02"));
+
+        // Distinct identifiers with the same name don't share a value list
+        assert_eq!(vcd_data.values.get("top.clk").unwrap().len(), 1);
+        assert!(!vcd_data.values.contains_key("top.clk$1"));
+    }
+
+    #[test]
+    fn test_parse_vcd_aliased_and_duplicated_names() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+
+        writeln!(temp_file, "$scope module top $end").unwrap();
+        // A port and its net declared with the same identifier, once with the same name
+        writeln!(temp_file, "$var wire 1 ! valid $end").unwrap();
+        writeln!(temp_file, "$var wire 1 ! valid $end").unwrap();
+        // Distinct identifiers that flatten to the same name
+        writeln!(temp_file, "$var wire 1 \" ready $end").unwrap();
+        writeln!(temp_file, "$var reg 1 # ready $end").unwrap();
+        writeln!(temp_file, "$var reg 1 $ ready $end").unwrap();
+        writeln!(temp_file, "$scope module sub $end").unwrap();
+        // The same identifier under a different name in another scope
+        writeln!(temp_file, "$var wire 1 ! valid_in $end").unwrap();
+        writeln!(temp_file, "$upscope $end").unwrap();
+        writeln!(temp_file, "$upscope $end").unwrap();
+        writeln!(temp_file, "$enddefinitions $end").unwrap();
+        writeln!(temp_file, "#0").unwrap();
+        writeln!(temp_file, "0!").unwrap();
+        writeln!(temp_file, "0\"").unwrap();
+        writeln!(temp_file, "1#").unwrap();
+        writeln!(temp_file, "0$").unwrap();
+        writeln!(temp_file, "#10").unwrap();
+        writeln!(temp_file, "1!").unwrap();
+        writeln!(temp_file, "1\"").unwrap();
+        writeln!(temp_file, "0#").unwrap();
+
+        let vcd_data = VcdParser::new().parse_file(temp_file.path()).unwrap();
+
+        assert_eq!(
+            vcd_data.signals,
+            vec![
+                "top.valid",
+                "top.ready",
+                "top.ready$1",
+                "top.ready$2",
+                "top.sub.valid_in"
+            ]
+        );
+
+        // The aliases are one value stream, not doubled up
+        let valid = vec![
+            (0, WaveValue::Binary(Value::V0)),
+            (10, WaveValue::Binary(Value::V1)),
+        ];
+        assert_eq!(vcd_data.values["top.valid"], valid);
+        assert_eq!(vcd_data.values["top.sub.valid_in"], valid);
+
+        // The duplicated names keep separate value streams instead of a zigzag
+        assert_eq!(vcd_data.values["top.ready"], valid);
+        assert_eq!(
+            vcd_data.values["top.ready$1"],
+            vec![
+                (0, WaveValue::Binary(Value::V1)),
+                (10, WaveValue::Binary(Value::V0)),
+            ]
+        );
+        assert_eq!(
+            vcd_data.values["top.ready$2"],
+            vec![(0, WaveValue::Binary(Value::V0))]
+        );
+    }
+
+    #[test]
+    fn test_parse_vcd_multiline_text_directives() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+
+        writeln!(temp_file, "$date").unwrap();
+        writeln!(temp_file, "    Mon Jan  1 00:00:00 2024").unwrap();
+        writeln!(temp_file, "$end").unwrap();
+        writeln!(temp_file, "$version Simulator").unwrap();
+        writeln!(temp_file, "    #1 build 1!").unwrap();
+        writeln!(temp_file, "$end").unwrap();
+        writeln!(temp_file, "$timescale").unwrap();
+        writeln!(temp_file, "    1ps").unwrap();
+        writeln!(temp_file, "$end").unwrap();
+        writeln!(temp_file, "$scope module top $end").unwrap();
+        writeln!(temp_file, "$var wire 1 ! clk $end").unwrap();
+        writeln!(temp_file, "$upscope $end").unwrap();
+        writeln!(temp_file, "$enddefinitions $end").unwrap();
+        writeln!(temp_file, "#0").unwrap();
+        writeln!(temp_file, "0!").unwrap();
+        // Lines that look like changes, timestamps and definitions inside a comment
+        writeln!(temp_file, "$comment").unwrap();
+        writeln!(temp_file, "#500").unwrap();
+        writeln!(temp_file, "1!").unwrap();
+        writeln!(temp_file, "$var wire 1 \" fake $end").unwrap();
+        writeln!(temp_file, "#10").unwrap();
+        writeln!(temp_file, "1!").unwrap();
+        writeln!(temp_file, "$comment on one line $end").unwrap();
+        writeln!(temp_file, "#20").unwrap();
+        writeln!(temp_file, "0!").unwrap();
+
+        let mut parser = VcdParser::new();
+        let vcd_data = parser.parse_file(temp_file.path()).unwrap();
+
+        // Header text spread over lines is joined by single spaces
+        assert_eq!(
+            parser.header(),
+            &FileHeader {
+                date: Some("Mon Jan 1 00:00:00 2024".to_string()),
+                version: Some("Simulator #1 build 1!".to_string()),
+                timescale: Some("1ps".to_string()),
+            }
+        );
+        assert_eq!(vcd_data.signals, vec!["top.clk"]);
+        assert_eq!(vcd_data.max_time, 20);
+        assert_eq!(vcd_data.timescale_fs, Some(1_000));
+        assert_eq!(
+            vcd_data.values["top.clk"],
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (10, WaveValue::Binary(Value::V1)),
+                (20, WaveValue::Binary(Value::V0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_timescale() {
+        assert_eq!(parse_timescale("$timescale 1ps $end"), Some(1_000));
+        assert_eq!(parse_timescale("$timescale 10 ns $end"), Some(10_000_000));
+        assert_eq!(
+            parse_timescale("$timescale 100us $end"),
+            Some(100_000_000_000)
+        );
+        assert_eq!(parse_timescale("$timescale $end"), None);
+        assert_eq!(parse_timescale("$timescale 1 minute $end"), None);
+    }
+
+    fn write_follow_header(file: &mut NamedTempFile) {
+        writeln!(file, "$scope module top $end").unwrap();
+        writeln!(file, "$var wire 1 ! clk $end").unwrap();
+        writeln!(file, "$var wire 8 \" data $end").unwrap();
+        writeln!(file, "$upscope $end").unwrap();
+        writeln!(file, "$enddefinitions $end").unwrap();
+        writeln!(file, "#0").unwrap();
+        writeln!(file, "0!").unwrap();
+        writeln!(file, "b00000000 \"").unwrap();
+    }
+
+    #[test]
+    fn test_parse_appended_changes() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write_follow_header(&mut temp_file);
+        writeln!(temp_file, "#10").unwrap();
+        writeln!(temp_file, "1!").unwrap();
+
+        let mut parser = VcdParser::new();
+        let first = parser.parse_appended(temp_file.path()).unwrap();
+        assert_eq!(first.signals, vec!["top.clk", "top.data"]);
+        assert_eq!(first.max_time, 10);
+        assert_eq!(first.values.get("top.clk").unwrap().len(), 2);
+
+        // Nothing new yet
+        let unchanged = parser.parse_appended(temp_file.path()).unwrap();
+        assert!(unchanged.values.is_empty());
+        assert_eq!(unchanged.max_time, 10);
+
+        // The id-to-name map and the current time carry over to the appended lines
+        writeln!(temp_file, "b11110000 \"").unwrap();
+        writeln!(temp_file, "#20").unwrap();
+        writeln!(temp_file, "0!").unwrap();
+        let second = parser.parse_appended(temp_file.path()).unwrap();
+        assert_eq!(second.max_time, 20);
+        assert_eq!(
+            second.values.get("top.data").unwrap(),
+            &vec![(10, WaveValue::Bus("F0".into()))]
+        );
+        assert_eq!(
+            second.values.get("top.clk").unwrap(),
+            &vec![(20, WaveValue::Binary(Value::V0))]
+        );
+
+        // Merging both reads gives the same data as parsing the whole file at once
+        let mut merged = first.values;
+        for (signal, values) in second.values {
+            merged.entry(signal).or_default().extend(values);
+        }
+        let whole = VcdParser::new().parse_file(temp_file.path()).unwrap();
+        assert_eq!(merged, whole.values);
+        assert_eq!(whole.max_time, 20);
+    }
+
+    #[test]
+    fn test_parse_appended_waits_for_whole_lines() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write_follow_header(&mut temp_file);
+        write!(temp_file, "#3").unwrap();
+
+        let mut parser = VcdParser::new();
+        assert_eq!(parser.parse_appended(temp_file.path()).unwrap().max_time, 0);
+
+        writeln!(temp_file, "0").unwrap();
+        writeln!(temp_file, "1!").unwrap();
+        let appended = parser.parse_appended(temp_file.path()).unwrap();
+        assert_eq!(appended.max_time, 30);
+        assert_eq!(
+            appended.values.get("top.clk").unwrap(),
+            &vec![(30, WaveValue::Binary(Value::V1))]
+        );
+    }
+
+    #[test]
+    fn test_parse_appended_after_unfinished_last_line() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write_follow_header(&mut temp_file);
+        writeln!(temp_file, "#10").unwrap();
+        write!(temp_file, "1!").unwrap();
+
+        let mut parser = VcdParser::new();
+        let first = parser.parse_file(temp_file.path()).unwrap();
+        assert_eq!(
+            first.values.get("top.clk").unwrap(),
+            &vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (10, WaveValue::Binary(Value::V1))
+            ]
+        );
+
+        // Finishing the line doesn't read its change again
+        writeln!(temp_file).unwrap();
+        writeln!(temp_file, "#20").unwrap();
+        writeln!(temp_file, "0!").unwrap();
+        let appended = parser.parse_appended(temp_file.path()).unwrap();
+        assert_eq!(
+            appended.values.get("top.clk").unwrap(),
+            &vec![(20, WaveValue::Binary(Value::V0))]
+        );
+
+        // A line the writer was still writing is parsed again once it is finished
+        write!(temp_file, "#3").unwrap();
+        parser.parse_file(temp_file.path()).unwrap();
+        writeln!(temp_file, "0").unwrap();
+        writeln!(temp_file, "1!").unwrap();
+        let appended = parser.parse_appended(temp_file.path()).unwrap();
+        assert_eq!(appended.max_time, 30);
+        assert_eq!(
+            appended.values.get("top.clk").unwrap(),
+            &vec![(30, WaveValue::Binary(Value::V1))]
+        );
+    }
+
+    #[test]
+    fn test_parse_appended_rejects_new_definitions() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write_follow_header(&mut temp_file);
+
+        let mut parser = VcdParser::new();
+        parser.parse_appended(temp_file.path()).unwrap();
+
+        writeln!(temp_file, "$var wire 1 # reset $end").unwrap();
+        let error = parser.parse_appended(temp_file.path()).unwrap_err();
+        assert_eq!(error.to_string(), "Signal definitions changed");
+    }
 }
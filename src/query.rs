@@ -0,0 +1,200 @@
+//! Headless value queries, answered without starting the terminal UI.
+
+use crate::state::AppState;
+
+/// A single `--query` argument: either `<signal>@<time>` or `<signal>@<start>-<end>`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Query {
+    pub signal: String,
+    pub start: u64,
+    pub end: Option<u64>,
+}
+
+pub fn parse_query(input: &str) -> Result<Query, String> {
+    let invalid = || {
+        format!(
+            "Invalid query '{}'. Expected <signal>@<time> or <signal>@<start>-<end>",
+            input
+        )
+    };
+
+    let (signal, times) = input.rsplit_once('@').ok_or_else(invalid)?;
+    if signal.is_empty() {
+        return Err(invalid());
+    }
+
+    let parse_time = |time: &str| time.trim().parse::<u64>().map_err(|_| invalid());
+    let (start, end) = match times.split_once('-') {
+        Some((start, end)) => (parse_time(start)?, Some(parse_time(end)?)),
+        None => (parse_time(times)?, None),
+    };
+    if end.is_some_and(|end| end < start) {
+        return Err(invalid());
+    }
+
+    Ok(Query {
+        signal: signal.to_string(),
+        start,
+        end,
+    })
+}
+
+/// Answers a query with `signal,time,value` lines. A time query prints the value at that time,
+/// and a range query prints the value at the start of the range followed by every change up to
+/// and including the end. Times before the signal's first value have an empty value.
+pub fn run_query(query: &Query, state: &AppState) -> Result<Vec<String>, String> {
+    if !state.waveform_data.signals.contains(&query.signal) {
+        return Err(format!("Signal not found: {}", query.signal));
+    }
+
+    let value_at_start = state
+        .get_value_at_marker(&query.signal, query.start)
+        .map(|value| state.format_value(&query.signal, &value))
+        .unwrap_or_default();
+    let mut lines = vec![format_line(&query.signal, query.start, &value_at_start)];
+
+    if let (Some(end), Some(values)) = (query.end, state.waveform_data.values.get(&query.signal)) {
+        lines.extend(
+            values
+                .iter()
+                .filter(|(time, _)| *time > query.start && *time <= end)
+                .map(|(time, value)| {
+                    format_line(
+                        &query.signal,
+                        *time,
+                        &state.format_value(&query.signal, value),
+                    )
+                }),
+        );
+    }
+
+    Ok(lines)
+}
+
+fn format_line(signal: &str, time: u64, value: &str) -> String {
+    format!("{},{},{}", signal, time, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+
+    fn get_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.signals = vec![
+            "top.done".to_string(),
+            "top.data".to_string(),
+            "top.idle".to_string(),
+        ];
+        state.waveform_data.values.insert(
+            "top.done".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (50000, WaveValue::Binary(Value::V1)),
+            ],
+        );
+        state.waveform_data.values.insert(
+            "top.data".to_string(),
+            vec![
+                (10, WaveValue::Bus("0F".into())),
+                (20, WaveValue::Bus("FF".into())),
+                (30, WaveValue::Bus("10".into())),
+            ],
+        );
+        state.waveform_data.max_time = 60000;
+        state
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            parse_query("top.done@50000"),
+            Ok(Query {
+                signal: "top.done".to_string(),
+                start: 50000,
+                end: None,
+            })
+        );
+        assert_eq!(
+            parse_query("top.data[7:0]@10-20"),
+            Ok(Query {
+                signal: "top.data[7:0]".to_string(),
+                start: 10,
+                end: Some(20),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_query_is_err() {
+        for input in [
+            "top.done",
+            "@10",
+            "top.done@",
+            "top.done@abc",
+            "top.done@20-10",
+        ] {
+            assert_eq!(
+                parse_query(input).unwrap_err(),
+                format!(
+                    "Invalid query '{}'. Expected <signal>@<time> or <signal>@<start>-<end>",
+                    input
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_time_query() {
+        let state = get_state();
+        let query = parse_query("top.done@50000").unwrap();
+        assert_eq!(
+            run_query(&query, &state),
+            Ok(vec!["top.done,50000,1".to_string()])
+        );
+
+        // Bus values use the same decimal formatting as the signal list
+        let query = parse_query("top.data@25").unwrap();
+        assert_eq!(
+            run_query(&query, &state),
+            Ok(vec!["top.data,25,255".to_string()])
+        );
+
+        // No value yet
+        let query = parse_query("top.data@5").unwrap();
+        assert_eq!(
+            run_query(&query, &state),
+            Ok(vec!["top.data,5,".to_string()])
+        );
+        let query = parse_query("top.idle@5").unwrap();
+        assert_eq!(
+            run_query(&query, &state),
+            Ok(vec!["top.idle,5,".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_run_range_query() {
+        let state = get_state();
+        let query = parse_query("top.data@15-30").unwrap();
+        assert_eq!(
+            run_query(&query, &state),
+            Ok(vec![
+                "top.data,15,15".to_string(),
+                "top.data,20,255".to_string(),
+                "top.data,30,16".to_string(),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_run_query_unknown_signal_is_err() {
+        let state = get_state();
+        let query = parse_query("top.nope@10").unwrap();
+        assert_eq!(
+            run_query(&query, &state),
+            Err("Signal not found: top.nope".to_string())
+        );
+    }
+}
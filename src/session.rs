@@ -0,0 +1,285 @@
+use crate::{
+    state::AppState,
+    types::{Marker, Region, ViewBookmark},
+};
+use ratatui::style::Color;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::Path,
+    str::FromStr,
+};
+
+/// The arrangement of a waveform worth keeping: the displayed signals in their order, their
+/// colors, heights and which are signed, the saved markers and regions, and the views saved in
+/// numbered slots. Colors are kept by name, like `yellow` or `#6496ff`.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Session {
+    #[serde(default)]
+    pub displayed_signals: Vec<String>,
+    #[serde(default)]
+    pub signal_colors: BTreeMap<String, String>,
+    #[serde(default)]
+    pub signal_heights: BTreeMap<String, usize>,
+    #[serde(default)]
+    pub signed_signals: BTreeSet<String>,
+    #[serde(default)]
+    pub markers: Vec<SessionMarker>,
+    #[serde(default)]
+    pub regions: Vec<SessionRegion>,
+    #[serde(default)]
+    pub views: Vec<SessionView>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SessionMarker {
+    pub name: String,
+    pub time: u64,
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SessionRegion {
+    pub name: String,
+    pub start: u64,
+    pub end: u64,
+    pub color: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SessionView {
+    pub slot: u8,
+    pub time_start: u64,
+    pub time_range: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_signal: Option<String>,
+    pub signal_scroll_offset: usize,
+}
+
+impl Session {
+    pub fn capture(state: &AppState) -> Self {
+        Session {
+            displayed_signals: state
+                .displayed_signals
+                .iter()
+                .filter(|signal| !state.is_derived_signal(signal))
+                .cloned()
+                .collect(),
+            signal_colors: state
+                .signal_colors
+                .iter()
+                .filter(|(signal, _)| !state.is_derived_signal(signal))
+                .map(|(signal, color)| (signal.clone(), color.to_string()))
+                .collect(),
+            signal_heights: state
+                .signal_heights
+                .iter()
+                .filter(|(signal, _)| !state.is_derived_signal(signal))
+                .map(|(signal, height)| (signal.clone(), *height))
+                .collect(),
+            signed_signals: state
+                .signed_signals
+                .iter()
+                .filter(|signal| !state.is_derived_signal(signal))
+                .cloned()
+                .collect(),
+            markers: state
+                .saved_markers
+                .iter()
+                .map(|marker| SessionMarker {
+                    name: marker.name.clone(),
+                    time: marker.time,
+                    color: marker.color.to_string(),
+                })
+                .collect(),
+            regions: state
+                .regions
+                .iter()
+                .map(|region| SessionRegion {
+                    name: region.name.clone(),
+                    start: region.start,
+                    end: region.end,
+                    color: region.color.to_string(),
+                })
+                .collect(),
+            views: state
+                .view_bookmarks
+                .iter()
+                .map(|(slot, view)| SessionView {
+                    slot: *slot,
+                    time_start: view.time_start,
+                    time_range: view.time_range,
+                    selected_signal: view.selected_signal.clone(),
+                    signal_scroll_offset: view.signal_scroll_offset,
+                })
+                .collect(),
+        }
+    }
+
+    /// Puts the session back into `state`. Signals the loaded waveform doesn't have are left out,
+    /// and unknown colors fall back to the default ones.
+    pub fn restore(&self, state: &mut AppState) {
+        let has_signal = |signal: &String| state.waveform_data.values.contains_key(signal);
+        state.displayed_signals = self
+            .displayed_signals
+            .iter()
+            .filter(|signal| has_signal(signal))
+            .cloned()
+            .collect();
+        state.signal_colors = self
+            .signal_colors
+            .iter()
+            .filter(|(signal, _)| has_signal(signal))
+            .filter_map(|(signal, color)| Some((signal.clone(), Color::from_str(color).ok()?)))
+            .collect();
+        state.signal_heights = self
+            .signal_heights
+            .iter()
+            .filter(|(signal, _)| has_signal(signal))
+            .map(|(signal, height)| (signal.clone(), *height))
+            .collect();
+        state.signed_signals = self
+            .signed_signals
+            .iter()
+            .filter(|signal| has_signal(signal))
+            .cloned()
+            .collect();
+        state.saved_markers = self
+            .markers
+            .iter()
+            .map(|marker| Marker {
+                name: marker.name.clone(),
+                time: marker.time,
+                color: Color::from_str(&marker.color).unwrap_or(Color::White),
+            })
+            .collect();
+        state.regions = self
+            .regions
+            .iter()
+            .map(|region| {
+                let mut restored = Region::new(region.start, region.end, region.name.clone());
+                if let Ok(color) = Color::from_str(&region.color) {
+                    restored.color = color;
+                }
+                restored
+            })
+            .collect();
+        state.view_bookmarks = self
+            .views
+            .iter()
+            .map(|view| {
+                let bookmark = ViewBookmark {
+                    time_start: view.time_start,
+                    time_range: view.time_range,
+                    selected_signal: view.selected_signal.clone(),
+                    signal_scroll_offset: view.signal_scroll_offset,
+                };
+                (view.slot, bookmark)
+            })
+            .collect();
+
+        state.selected_signal = 0;
+        state.signal_scroll_offset = 0;
+        state.invalidate_waveform_stats();
+    }
+}
+
+pub fn load_session(path: &Path) -> io::Result<Session> {
+    let content = fs::read_to_string(path)?;
+    toml::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn save_session(path: &Path, session: &Session) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string(session).map_err(io::Error::other)?;
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+    use tempfile::TempDir;
+
+    fn arranged_state() -> AppState {
+        let mut state = AppState::default();
+        for signal in ["top.clk", "top.data"] {
+            state.waveform_data.signals.push(signal.to_string());
+            state
+                .waveform_data
+                .values
+                .insert(signal.to_string(), vec![(0, WaveValue::Binary(Value::V0))]);
+        }
+        state.displayed_signals = vec!["top.data".to_string(), "top.clk".to_string()];
+        state
+            .signal_colors
+            .insert("top.clk".to_string(), Color::LightGreen);
+        state.signal_heights.insert("top.data".to_string(), 4);
+        state.signed_signals.insert("top.data".to_string());
+        state.saved_markers.push(Marker {
+            name: "irq".to_string(),
+            time: 42,
+            color: Color::Rgb(100, 150, 255),
+        });
+        state.regions.push(Region::new(10, 20, "reset".to_string()));
+        state.view_bookmarks.insert(
+            3,
+            ViewBookmark {
+                time_start: 5,
+                time_range: 30,
+                selected_signal: Some("top.clk".to_string()),
+                signal_scroll_offset: 1,
+            },
+        );
+        state
+    }
+
+    #[test]
+    fn test_session_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("session.toml");
+        let state = arranged_state();
+        save_session(&path, &Session::capture(&state)).unwrap();
+
+        let mut restored = arranged_state();
+        restored.displayed_signals.clear();
+        restored.signal_colors.clear();
+        restored.signal_heights.clear();
+        restored.signed_signals.clear();
+        restored.saved_markers.clear();
+        restored.regions.clear();
+        restored.view_bookmarks.clear();
+        load_session(&path).unwrap().restore(&mut restored);
+
+        assert_eq!(restored.displayed_signals, state.displayed_signals);
+        assert_eq!(restored.signal_colors, state.signal_colors);
+        assert_eq!(restored.signal_heights, state.signal_heights);
+        assert_eq!(restored.signed_signals, state.signed_signals);
+        assert_eq!(restored.saved_markers, state.saved_markers);
+        assert_eq!(restored.regions, state.regions);
+        assert_eq!(restored.view_bookmarks, state.view_bookmarks);
+    }
+
+    #[test]
+    fn test_restore_skips_missing_signals() {
+        let mut session = Session::capture(&arranged_state());
+        session.displayed_signals.push("top.gone".to_string());
+        session
+            .signal_colors
+            .insert("top.gone".to_string(), "red".to_string());
+
+        let mut state = arranged_state();
+        session.restore(&mut state);
+        assert_eq!(state.displayed_signals, vec!["top.data", "top.clk"]);
+        assert!(!state.signal_colors.contains_key("top.gone"));
+    }
+
+    #[test]
+    fn test_load_missing_session_is_err() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_session(&dir.path().join("session.toml")).is_err());
+    }
+}
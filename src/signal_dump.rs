@@ -0,0 +1,212 @@
+use crate::{
+    cli,
+    parsers::{types::SignalDefinition, vcd::VcdParser},
+};
+use serde::Serialize;
+use std::io::{self, BufRead};
+
+/// Reads the signals declared in the header of the waveform named on the command line, reading it
+/// from `stdin` if it is named `-`. Parsing stops at `$enddefinitions`, so even huge files are
+/// listed at once.
+pub fn dump_signals(file_name: &str, stdin: impl BufRead) -> io::Result<Vec<SignalDefinition>> {
+    let mut parser = VcdParser::new();
+    let definitions = if file_name == cli::STDIN_FILE_NAME {
+        parser.parse_definitions(stdin)?
+    } else {
+        parser.parse_definitions_file(file_name)?
+    };
+    if !parser.definitions_finished() {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "The file ended before $enddefinitions",
+        ));
+    }
+    Ok(definitions)
+}
+
+/// The signals as printed by `--dump-signals`: one `name<TAB>width<TAB>type` line each, or a tree
+/// of their scopes with `tree`, or either as JSON.
+pub fn render(definitions: &[SignalDefinition], tree: bool, json: bool) -> String {
+    match (tree, json) {
+        (false, false) => definitions
+            .iter()
+            .map(|signal| format!("{}\t{}\t{}", signal.name, signal.width, signal.var_type))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        (true, false) => {
+            let mut lines = Vec::new();
+            for node in &build_tree(definitions) {
+                node.render(&mut lines, "", "", "");
+            }
+            lines.join("\n")
+        }
+        (false, true) => to_json(definitions),
+        (true, true) => to_json(&build_tree(definitions)),
+    }
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> String {
+    serde_json::to_string_pretty(value).expect("signal definitions only hold plain data")
+}
+
+/// A scope with what is declared in it, or a signal named after the last part of its name.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(untagged)]
+enum TreeNode {
+    Scope {
+        name: String,
+        children: Vec<TreeNode>,
+    },
+    Signal {
+        name: String,
+        width: usize,
+        #[serde(rename = "type")]
+        var_type: String,
+    },
+}
+
+impl TreeNode {
+    // Adds the lines of this node and everything in it, drawn like `tree` does. `prefix` comes
+    // before the line of this node and `child_prefix` before the lines of its children.
+    fn render(&self, lines: &mut Vec<String>, prefix: &str, branch: &str, child_prefix: &str) {
+        match self {
+            TreeNode::Scope { name, children } => {
+                lines.push(format!("{}{}{}", prefix, branch, name));
+                for (i, child) in children.iter().enumerate() {
+                    let (branch, indent) = if i + 1 == children.len() {
+                        ("└── ", "    ")
+                    } else {
+                        ("├── ", "│   ")
+                    };
+                    child.render(
+                        lines,
+                        child_prefix,
+                        branch,
+                        &format!("{}{}", child_prefix, indent),
+                    );
+                }
+            }
+            TreeNode::Signal {
+                name,
+                width,
+                var_type,
+            } => lines.push(format!(
+                "{}{}{}\t{}\t{}",
+                prefix, branch, name, width, var_type
+            )),
+        }
+    }
+}
+
+// Nests the signals in their scopes, keeping the order they were declared in
+fn build_tree(definitions: &[SignalDefinition]) -> Vec<TreeNode> {
+    let mut roots = Vec::new();
+    for signal in definitions {
+        let mut parts: Vec<&str> = signal.name.split('.').collect();
+        let leaf = parts.pop().unwrap_or_default();
+        let mut nodes = &mut roots;
+        for part in parts {
+            let index = nodes
+                .iter()
+                .position(|node| matches!(node, TreeNode::Scope { name, .. } if name == part))
+                .unwrap_or_else(|| {
+                    nodes.push(TreeNode::Scope {
+                        name: part.to_string(),
+                        children: Vec::new(),
+                    });
+                    nodes.len() - 1
+                });
+            let TreeNode::Scope { children, .. } = &mut nodes[index] else {
+                unreachable!("only scopes are found or added")
+            };
+            nodes = children;
+        }
+        nodes.push(TreeNode::Signal {
+            name: leaf.to_string(),
+            width: signal.width,
+            var_type: signal.var_type.clone(),
+        });
+    }
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use std::io::Cursor;
+
+    const VCD: &str = "$timescale 1ns $end\n\
+                       $scope module top $end\n\
+                       $var wire 1 ! clk $end\n\
+                       $scope module cpu $end\n\
+                       $var reg 32 \" pc $end\n\
+                       $var wire 1 # valid $end\n\
+                       $upscope $end\n\
+                       $var wire 8 $ data [7:0] $end\n\
+                       $upscope $end\n\
+                       $enddefinitions $end\n\
+                       #0\n";
+
+    fn definitions() -> Vec<SignalDefinition> {
+        dump_signals(cli::STDIN_FILE_NAME, Cursor::new(VCD)).unwrap()
+    }
+
+    #[test]
+    fn test_dump_signals_list() {
+        assert_eq!(
+            render(&definitions(), false, false),
+            "top.clk\t1\twire\n\
+             top.cpu.pc\t32\treg\n\
+             top.cpu.valid\t1\twire\n\
+             top.data[7:0]\t8\twire"
+        );
+
+        // Without the end of the header, the list could be missing signals
+        let error = dump_signals(
+            cli::STDIN_FILE_NAME,
+            Cursor::new("$var wire 1 ! clk $end\n"),
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "The file ended before $enddefinitions");
+    }
+
+    #[test]
+    fn test_dump_signals_tree() {
+        assert_eq!(
+            render(&definitions(), true, false),
+            "top\n\
+             ├── clk\t1\twire\n\
+             ├── cpu\n\
+             │   ├── pc\t32\treg\n\
+             │   └── valid\t1\twire\n\
+             └── data[7:0]\t8\twire"
+        );
+    }
+
+    #[test]
+    fn test_dump_signals_json() {
+        let list: Value = serde_json::from_str(&render(&definitions(), false, true)).unwrap();
+        assert_eq!(
+            list[1],
+            json!({"name": "top.cpu.pc", "width": 32, "type": "reg"})
+        );
+        assert_eq!(list.as_array().unwrap().len(), 4);
+
+        let tree: Value = serde_json::from_str(&render(&definitions(), true, true)).unwrap();
+        assert_eq!(
+            tree,
+            json!([{
+                "name": "top",
+                "children": [
+                    {"name": "clk", "width": 1, "type": "wire"},
+                    {"name": "cpu", "children": [
+                        {"name": "pc", "width": 32, "type": "reg"},
+                        {"name": "valid", "width": 1, "type": "wire"},
+                    ]},
+                    {"name": "data[7:0]", "width": 8, "type": "wire"},
+                ],
+            }])
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// Named lists of signals, in the order they are displayed, by group name.
+pub type SignalGroups = BTreeMap<String, Vec<String>>;
+
+#[derive(Default, Serialize, Deserialize)]
+struct SignalGroupsFile {
+    #[serde(default)]
+    groups: SignalGroups,
+}
+
+/// Reads the saved signal groups. A missing or unreadable file has no groups.
+pub fn load_signal_groups(path: &Path) -> SignalGroups {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| toml::from_str::<SignalGroupsFile>(&content).ok())
+        .map(|file| file.groups)
+        .unwrap_or_default()
+}
+
+pub fn save_signal_groups(path: &Path, groups: &SignalGroups) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let content = toml::to_string(&SignalGroupsFile {
+        groups: groups.clone(),
+    })
+    .map_err(io::Error::other)?;
+    fs::write(path, content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_signal_groups_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("nested").join("signal_groups.toml");
+
+        let groups = SignalGroups::from([
+            (
+                "fetch".to_string(),
+                vec!["top.pc".to_string(), "top.instr".to_string()],
+            ),
+            ("debug".to_string(), vec!["top.dbg.state".to_string()]),
+        ]);
+        save_signal_groups(&path, &groups).unwrap();
+
+        let loaded = load_signal_groups(&path);
+        assert_eq!(loaded, groups);
+        // The order of the signals in a group is kept
+        assert_eq!(loaded["fetch"], vec!["top.pc", "top.instr"]);
+    }
+
+    #[test]
+    fn test_missing_signal_groups_is_empty() {
+        let dir = TempDir::new().unwrap();
+        assert!(load_signal_groups(&dir.path().join("signal_groups.toml")).is_empty());
+    }
+}
@@ -0,0 +1,134 @@
+use crate::config::SignalNameStyle;
+use std::collections::HashMap;
+
+/// Names of `signals` as the signal list shows them in `style`. Where leaf or shortened names of
+/// different signals would be the same, those signals keep more trailing components until they
+/// differ or are shown in full.
+pub fn display_names(signals: &[String], style: SignalNameStyle) -> Vec<String> {
+    let initial_kept = match style {
+        SignalNameStyle::Full => return signals.to_vec(),
+        SignalNameStyle::Leaf => 1,
+        SignalNameStyle::Shortened => 2,
+    };
+
+    let components: Vec<Vec<&str>> = signals
+        .iter()
+        .map(|signal| signal.split('.').collect())
+        .collect();
+    let mut kept: Vec<usize> = components
+        .iter()
+        .map(|parts| initial_kept.min(parts.len()))
+        .collect();
+
+    loop {
+        let names: Vec<String> = components
+            .iter()
+            .zip(&kept)
+            .map(|(parts, kept)| shorten(parts, *kept, style))
+            .collect();
+
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for name in &names {
+            *counts.entry(name.as_str()).or_default() += 1;
+        }
+
+        let mut lengthened = false;
+        for (i, name) in names.iter().enumerate() {
+            if counts[name.as_str()] > 1 && kept[i] < components[i].len() {
+                kept[i] += 1;
+                lengthened = true;
+            }
+        }
+        if !lengthened {
+            return names;
+        }
+    }
+}
+
+// Helper function to show the last `kept` components of a name. Shortened names also keep the
+// first component, unless eliding the scopes between wouldn't make the name any shorter.
+fn shorten(parts: &[&str], kept: usize, style: SignalNameStyle) -> String {
+    let tail = parts[parts.len() - kept..].join(".");
+    match style {
+        SignalNameStyle::Shortened if parts.len() > kept + 1 => {
+            let shortened = format!("{}.….{}", parts[0], tail);
+            let full = parts.join(".");
+            if shortened.chars().count() < full.chars().count() {
+                shortened
+            } else {
+                full
+            }
+        }
+        SignalNameStyle::Shortened => parts.join("."),
+        _ => tail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(signals: &[&str], style: SignalNameStyle) -> Vec<String> {
+        let signals: Vec<String> = signals.iter().map(|s| s.to_string()).collect();
+        display_names(&signals, style)
+    }
+
+    #[test]
+    fn test_leaf_names() {
+        assert_eq!(
+            names(
+                &["top.core.clk", "top.core.decode.valid"],
+                SignalNameStyle::Leaf
+            ),
+            vec!["clk", "valid"]
+        );
+        assert_eq!(names(&["top.clk"], SignalNameStyle::Full), vec!["top.clk"]);
+    }
+
+    #[test]
+    fn test_colliding_names_keep_more_components() {
+        let signals = [
+            "top.core0.decode.valid",
+            "top.core1.decode.valid",
+            "top.core0.fetch.valid",
+            "top.clk",
+        ];
+        assert_eq!(
+            names(&signals, SignalNameStyle::Leaf),
+            vec![
+                "core0.decode.valid",
+                "core1.decode.valid",
+                "fetch.valid",
+                "clk"
+            ]
+        );
+        assert_eq!(
+            names(&["a.valid", "valid"], SignalNameStyle::Leaf),
+            vec!["a.valid", "valid"]
+        );
+    }
+
+    #[test]
+    fn test_shortened_names() {
+        let signals = [
+            "top.core.pipeline.decode.valid",
+            "top.decode.ready",
+            "top.x.decode.ready",
+        ];
+        assert_eq!(
+            names(&signals, SignalNameStyle::Shortened),
+            vec![
+                "top.….decode.valid",
+                "top.decode.ready",
+                // Eliding a single short scope doesn't save anything
+                "top.x.decode.ready"
+            ]
+        );
+
+        let colliding = ["top.core0.decode.valid", "top.core1.decode.valid"];
+        assert_eq!(
+            names(&colliding, SignalNameStyle::Shortened),
+            vec!["top.core0.decode.valid", "top.core1.decode.valid"]
+        );
+    }
+}
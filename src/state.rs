@@ -1,11 +1,67 @@
+//! Everything the viewer shows and remembers, which commands act on.
+
 use crate::{
-    command_mode::{state::CommandModeState, CommandModeStateAccess},
-    config,
+    bit_split,
+    command_aliases::CommandAliases,
+    command_mode::{state::CommandModeState, CommandAliasAccess, CommandModeStateAccess},
+    config::{self, ColorMode},
+    constants,
+    decoders::Annotation,
+    file_browser::FileBrowserState,
     fuzzy_finder::{state::FuzzyFinderState, FuzzyFinderStateAccess},
-    parsers::types::{WaveValue, WaveformData},
-    types::{AppMode, Marker},
+    parsers::{
+        types::{coalesce_changes, merge_glitches, Glitch, ParseReport, WaveValue, WaveformData},
+        vcd::VcdParser,
+    },
+    session::Session,
+    signal_groups::SignalGroups,
+    stats::{activity_density, WaveformStats},
+    types::{
+        AppMode, Comparison, Diff, DragKind, FileInfo, GrepPopup, Highlight, Jump, Marker, Region,
+        SamplePopup, SignalFilter, SplitView, Trace, TraceEntry, TransitionsPopup, ViewBookmark,
+        Viewport,
+    },
+    ui::theme::Theme,
+};
+use ratatui::style::Color;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap, HashSet},
+    ops::Range,
+    path::PathBuf,
+    time::Duration,
 };
-use std::collections::HashSet;
+
+/// Index ranges into each signal's values that are visible in the `(time_start, time_range)`
+/// window they were computed for.
+#[derive(Default)]
+struct VisibleValuesCache {
+    view: (u64, u64),
+    ranges: HashMap<String, Range<usize>>,
+}
+
+/// Minimap density along with the width and displayed signals it was computed for.
+#[derive(Default)]
+struct MinimapDensity {
+    width: u16,
+    signals: Vec<String>,
+    density: Vec<usize>,
+}
+
+/// Index range of the time-sorted `values` that fall in `(time_start, time_end)`, starting with
+/// the value the window is entered with: the last one at or before `time_start`, if there is one.
+/// Earlier values at `time_start` are left out, so a change landing exactly on `time_start` isn't
+/// preceded by the value it replaces. Only touches O(log n) values.
+fn visible_range<T>(
+    values: &[T],
+    time_of: impl Fn(&T) -> u64,
+    time_start: u64,
+    time_end: u64,
+) -> Range<usize> {
+    let entered = values.partition_point(|v| time_of(v) <= time_start);
+    let end = entered + values[entered..].partition_point(|v| time_of(v) < time_end);
+    entered.saturating_sub(1)..end
+}
 
 #[derive(Default)]
 pub struct AppState {
@@ -30,6 +86,20 @@ pub struct AppState {
     /// variable is 10, the waveform view will show time steps 3 through 13.
     pub time_range: u64,
 
+    /// Previous `(time_start, time_range)` windows, most recent last, restored by zooming back.
+    pub zoom_history: Vec<(u64, u64)>,
+
+    /// Windows that were zoomed back from, most recent last, re-applied by zooming forward.
+    pub zoom_forward_history: Vec<(u64, u64)>,
+
+    /// Places jumped from with `:goto`, markers, matches, fitting, drag zooms and the minimap,
+    /// oldest first, gone back and forth through with `jump_back` and `jump_forward`.
+    pub jump_list: Vec<Jump>,
+
+    /// Position in `jump_list` of the place shown, or its length while showing a place after the
+    /// newest jump.
+    pub jump_index: usize,
+
     /// Primary marker position in time step units.
     pub primary_marker: Option<u64>,
 
@@ -39,15 +109,101 @@ pub struct AppState {
     /// Saved marker positions in time step units.
     pub saved_markers: Vec<Marker>,
 
+    /// Named time intervals highlighted across all signals.
+    pub regions: Vec<Region>,
+
+    /// Views saved in numbered slots with `:view save`, by slot.
+    pub view_bookmarks: BTreeMap<u8, ViewBookmark>,
+
+    /// Colors that signals are drawn in instead of the default, by signal name.
+    pub signal_colors: HashMap<String, Color>,
+
+    /// Rows that signals are drawn in instead of `ui.waveform_height`, by signal name.
+    pub signal_heights: HashMap<String, usize>,
+
+    /// Bus signals whose values are shown as signed decimal numbers, set with `:format`.
+    pub signed_signals: HashSet<String>,
+
+    /// Values tinted wherever their signals hold them, added with `:highlight`.
+    pub highlights: Vec<Highlight>,
+
+    /// Whether the divider between the signal list and the waveform is being dragged.
+    pub is_resizing_signal_list: bool,
+
+    /// Whether the primary marker is being dragged along the time ruler.
+    pub is_scrubbing_time_ruler: bool,
+
+    /// Flag indicating that the view is being panned by dragging along the minimap
+    pub is_panning_minimap: bool,
+
+    /// Whether the signal list is being scrolled by dragging its scrollbar.
+    pub is_dragging_signal_scrollbar: bool,
+
+    /// Name of the saved marker selected by clicking its name, which `rename_marker` renames.
+    pub selected_saved_marker: Option<String>,
+
+    /// Whether the selected saved marker is being moved by dragging its name.
+    pub is_dragging_saved_marker: bool,
+
+    /// Popup listing the transitions of a signal, shown while set.
+    pub transitions_popup: Option<TransitionsPopup>,
+
+    /// Popup listing where displayed signals hold a searched value, shown while set.
+    pub grep_popup: Option<GrepPopup>,
+
+    /// Popup listing the value of every displayed signal at one time, shown while set.
+    pub sample_popup: Option<SamplePopup>,
+
+    /// Active signal comparison whose mismatches are highlighted in the waveform.
+    pub comparison: Option<Comparison>,
+
+    /// Second waveform file whose signals are drawn under the matching displayed signals.
+    pub diff: Option<Diff>,
+
+    /// Window of the other pane while the waveform is split into two panes over different times.
+    pub split_view: Option<SplitView>,
+
+    /// Time of the cursor moved with the keyboard, shown while cursor mode is on.
+    pub cursor: Option<u64>,
+
+    /// Signal whose values at the markers are shown in the command bar's title, whether or not
+    /// it is displayed.
+    pub tracked_signal: Option<String>,
+
+    /// Reference time that times are shown and typed relative to, set with `:timezero`. Stored
+    /// times stay absolute.
+    pub time_zero: Option<u64>,
+
+    /// Rows of the bits of split bus signals, most significant first, by the signal they were split
+    /// from. Their values are derived from the bus, so they aren't exported with the session.
+    pub split_signals: HashMap<String, Vec<String>>,
+
+    /// Annotations of the protocols decoded with `:decode`, by the name of the row showing them.
+    /// They are decoded once and kept while the file is loaded, so hidden rows can be shown again.
+    pub decoded: HashMap<String, Vec<Annotation>>,
+
     /// Is Some(Screen X coordinate, Time Step) if starting dragging for zoom selection
     pub drag_start: Option<(u16, u64)>,
 
     /// Is Some(Screen X coordinate, Time Step) if currently dragging for zoom selection
     pub drag_current: Option<(u16, u64)>,
 
+    /// Screen row where the current drag selection started
+    pub drag_start_row: Option<u16>,
+
     /// Flag used to differentiate between a drag operation and a potential click
     pub is_dragging: bool,
 
+    /// What the current drag operation will do when it is released
+    pub drag_kind: DragKind,
+
+    /// Screen position of the mouse over the waveform, where the value under it is shown. Terminals
+    /// that don't report mouse moves only update it on clicks.
+    pub hover_position: Option<(u16, u16)>,
+
+    /// Flag indicating that dragging measures time instead of zooming, even without holding Ctrl
+    pub measure_mode: bool,
+
     /// State of command mode to render. This is accessed via methods in a trait implementation of
     /// CommandModeStateAccess, so it is not public.
     command_mode_state: CommandModeState,
@@ -56,12 +212,115 @@ pub struct AppState {
     /// FuzzyFinderStateAccess, so it is not public.
     fuzzy_finder_state: FuzzyFinderState,
 
+    /// Visible values per signal for the current view, so that rendering doesn't search every
+    /// signal's values again each frame.
+    visible_values_cache: RefCell<VisibleValuesCache>,
+
     /// Flag indicating that the help menu is currently being displayed
     pub show_help: bool,
 
     /// Current scroll position in the help menu
     pub help_menu_scroll: usize,
 
+    /// Flag indicating that the list of saved markers is currently being displayed
+    pub show_marker_list: bool,
+
+    /// Current scroll position in the saved marker list
+    pub marker_list_scroll: usize,
+
+    /// Flag indicating that the views saved in numbered slots are currently being displayed
+    pub show_view_list: bool,
+
+    /// Flag indicating that the list of commands is currently being displayed
+    pub show_command_list: bool,
+
+    /// Current scroll position in the command list
+    pub command_list_scroll: usize,
+
+    /// Problems found while parsing the loaded file, shown with `:parsewarnings`.
+    pub parse_report: ParseReport,
+
+    /// Flag indicating that the parse warnings are currently being displayed
+    pub show_parse_warnings: bool,
+
+    /// Current scroll position in the parse warnings
+    pub parse_warnings_scroll: usize,
+
+    /// Size, header and parse time of the loaded waveform, if one is loaded.
+    pub file_info: Option<FileInfo>,
+
+    /// Flag indicating that the file information is currently being displayed
+    pub show_file_info: bool,
+
+    /// Current scroll position in the file information
+    pub file_info_scroll: usize,
+
+    /// Flag indicating that the waveform statistics are currently being displayed
+    pub show_stats: bool,
+
+    /// Current scroll position in the waveform statistics
+    pub stats_scroll: usize,
+
+    /// Values of a signal at each place the primary marker moved to, from `:trace`.
+    pub trace: Option<Trace>,
+
+    /// Flag indicating that the trace is currently being displayed
+    pub show_trace: bool,
+
+    /// Current scroll position in the trace
+    pub trace_scroll: usize,
+
+    /// Flag indicating that the recent command results are currently being displayed
+    pub show_messages: bool,
+
+    /// Current scroll position in the recent command results
+    pub messages_scroll: usize,
+
+    /// Statistics of `waveform_data`, computed when first needed since they look at every value.
+    waveform_stats: Option<WaveformStats>,
+
+    /// Changes of the displayed signals per minimap column, kept until the data, the displayed
+    /// signals or the minimap width change.
+    minimap_density: Option<MinimapDensity>,
+
+    /// Saved lists of signals to display, by name.
+    pub signal_groups: SignalGroups,
+
+    /// Where signal groups are saved. Not saved if None.
+    pub signal_groups_path: Option<PathBuf>,
+
+    /// The user's own command names, each running a stored command line, by name.
+    pub command_aliases: CommandAliases,
+
+    /// Where command aliases are saved. Not saved if None.
+    pub command_aliases_path: Option<PathBuf>,
+
+    /// Where the session is saved when no path is given, which is the last one saved to or loaded
+    /// from once there is one.
+    pub session_path: Option<PathBuf>,
+
+    /// The session as it was when last saved, loaded or when the file was opened, to tell whether
+    /// quitting would lose changes.
+    saved_session: Session,
+
+    /// Files listed by the file picker shown in `AppMode::FileBrowser`.
+    pub file_browser: FileBrowserState,
+
+    /// Path of the loaded waveform file.
+    pub file_path: Option<PathBuf>,
+
+    /// Parser that read the loaded file, kept to read the changes appended to it.
+    pub vcd_parser: Option<VcdParser>,
+
+    /// Whether changes appended to the loaded file are read as they're written.
+    pub following: bool,
+
+    /// Set by `:reload` for the app to read the loaded file again once the command has run.
+    pub reload_requested: bool,
+
+    /// Set by `:sleep` for the app to hold the commands queued from the command line this long.
+    pub sleep_requested: Option<Duration>,
+
     /// Configuration state. Originally loaded from a file, but saved in app state so that the user
     /// can update configuration values while the application is running.
     pub config: config::AppConfig,
@@ -69,17 +328,19 @@ pub struct AppState {
     /// List of signals that are currently being displayed
     pub displayed_signals: Vec<String>,
 
-    /// Flag indicating if signals have been loaded but not yet filtered/selected
-    pub signals_need_selection: bool,
+    /// Current scroll offset for signals
+    pub signal_scroll_offset: usize,
 
-    /// Set of signals that are currently selected in visual mode
-    pub selected_signals: HashSet<usize>,
+    /// Signals pinned with `:pin`, in pin order. They are drawn above the scrolled signals and
+    /// aren't scrolled themselves.
+    pub pinned_signals: Vec<String>,
 
-    /// Visual mode start position - used for determining selection range
-    pub visual_start: Option<usize>,
+    /// Narrows the signal list while set. Indices like the selection and the scroll offset stay
+    /// indices into `displayed_signals`, and skip the signals it hides.
+    pub signal_filter: Option<SignalFilter>,
 
-    /// Current scroll offset for signals
-    pub signal_scroll_offset: usize,
+    /// Whether the NO_COLOR environment variable asks for no colors, whatever `ui.color_mode` says.
+    pub no_color: bool,
 }
 
 // Access command mode state in the overall app state via a trait implementation
@@ -93,6 +354,12 @@ impl CommandModeStateAccess for AppState {
     }
 }
 
+impl CommandAliasAccess for AppState {
+    fn command_alias(&self, name: &str) -> Option<&str> {
+        self.command_aliases.get(name).map(String::as_str)
+    }
+}
+
 impl FuzzyFinderStateAccess for AppState {
     fn fuzzy_finder_state(&self) -> &FuzzyFinderState {
         &self.fuzzy_finder_state
@@ -104,29 +371,794 @@ impl FuzzyFinderStateAccess for AppState {
 }
 
 impl AppState {
+    /// State using the config file from the default location, or the default config if it can't
+    /// be loaded.
     pub fn new() -> Self {
-        let mut app_state = AppState::default();
-        app_state.time_range = 50;
-        app_state.config =
-            config::load_config(None).unwrap_or_else(|_| config::AppConfig::default());
-        app_state
+        Self::with_config(
+            config::load_config(None).unwrap_or_else(|_| config::AppConfig::default()),
+        )
+    }
+
+    /// State for an empty viewer with the given config.
+    pub fn with_config(config: config::AppConfig) -> Self {
+        AppState {
+            time_range: 50,
+            config,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the displayed signals, their colors and heights, the saved markers or the regions
+    /// changed since the session was last saved, loaded or the file was opened.
+    pub fn is_session_dirty(&self) -> bool {
+        Session::capture(self) != self.saved_session
+    }
+
+    /// Takes the current session as the saved one, so it's no longer dirty.
+    pub fn mark_session_saved(&mut self) {
+        self.saved_session = Session::capture(self);
+    }
+
+    /// Styles for the colors the terminal can show.
+    pub fn theme(&self) -> Theme {
+        if self.no_color {
+            Theme::new(ColorMode::Mono)
+        } else {
+            Theme::new(self.config.ui.color_mode)
+        }
+    }
+
+    /// Color of the primary marker from `ui.marker_color_primary`, used by the marker line and its
+    /// readout alike.
+    pub fn primary_marker_color(&self) -> Color {
+        self.theme().color(
+            self.config
+                .ui
+                .marker_color_primary
+                .to_lowercase()
+                .parse()
+                .unwrap_or(constants::PRIMARY_MARKER_COLOR),
+        )
+    }
+
+    /// Color of the secondary marker from `ui.marker_color_secondary`.
+    pub fn secondary_marker_color(&self) -> Color {
+        self.theme().color(
+            self.config
+                .ui
+                .marker_color_secondary
+                .to_lowercase()
+                .parse()
+                .unwrap_or(constants::SECONDARY_MARKER_COLOR),
+        )
+    }
+
+    /// Color of unknown (X) values from `ui.x_color`.
+    pub fn x_color(&self) -> Color {
+        self.theme().color(
+            self.config
+                .ui
+                .x_color
+                .to_lowercase()
+                .parse()
+                .unwrap_or(constants::X_COLOR),
+        )
+    }
+
+    /// Color of high impedance (Z) values from `ui.z_color`.
+    pub fn z_color(&self) -> Color {
+        self.theme().color(
+            self.config
+                .ui
+                .z_color
+                .to_lowercase()
+                .parse()
+                .unwrap_or(constants::Z_COLOR),
+        )
     }
 
     pub fn set_primary_marker(&mut self, x_pos: u16, window_width: u16) {
-        self.primary_marker = Some(self.screen_pos_to_time(x_pos, window_width));
+        self.move_primary_marker(self.marker_time_at(x_pos, window_width));
+    }
+
+    /// Puts the primary marker at `time`. Everything that moves the marker goes through here, so
+    /// the trace sees each move.
+    pub fn move_primary_marker(&mut self, time: u64) {
+        self.primary_marker = Some(time);
+        self.record_trace(time);
+    }
+
+    // Adds the traced signal's value at `time` to the trace, or counts it again if the last entry
+    // has the same value. The oldest entries are dropped once there are too many.
+    fn record_trace(&mut self, time: u64) {
+        let Some(trace) = self.trace.as_ref().filter(|trace| trace.recording) else {
+            return;
+        };
+        let value = self
+            .get_value_at_marker(&trace.signal, time)
+            .map_or_else(|| "-".to_string(), |value| value.to_string());
+        let Some(trace) = &mut self.trace else {
+            return;
+        };
+
+        match trace.entries.last_mut() {
+            Some(last) if last.value == value => last.count += 1,
+            _ => {
+                if trace.entries.len() == constants::MAX_TRACE_ENTRIES {
+                    trace.entries.remove(0);
+                }
+                trace.entries.push(TraceEntry {
+                    time,
+                    value,
+                    count: 1,
+                });
+            }
+        }
     }
 
     pub fn set_secondary_marker(&mut self, x_pos: u16, window_width: u16) {
-        self.secondary_marker = Some(self.screen_pos_to_time(x_pos, window_width));
+        self.secondary_marker = Some(self.marker_time_at(x_pos, window_width));
+    }
+
+    /// Time for a marker placed with the mouse at `x_pos`. With `ui.marker_snap` on, this is the
+    /// closest transition of the selected signal within `ui.marker_snap_distance` columns, if any.
+    pub fn marker_time_at(&self, x_pos: u16, window_width: u16) -> u64 {
+        let time = self.screen_pos_to_time(x_pos, window_width);
+        if !self.config.ui.marker_snap || window_width == 0 {
+            return time;
+        }
+
+        let max_distance = (self.config.ui.marker_snap_distance as f64 * self.time_range as f64
+            / window_width as f64)
+            .round() as u64;
+        self.nearest_transition(time, max_distance).unwrap_or(time)
+    }
+
+    // The transition of the selected signal closest to `time` and at most `max_distance` away. A
+    // transition is a change to a different value, as reported by `get_transition_at_marker`.
+    fn nearest_transition(&self, time: u64, max_distance: u64) -> Option<u64> {
+        let signal = self.displayed_signals.get(self.selected_signal)?;
+        let values = self.waveform_data.values.get(signal)?;
+        let is_transition = |i: usize| self.is_transition(values, i);
+
+        let split = values.partition_point(|(t, _)| *t < time);
+        let after = (split..values.len())
+            .take_while(|i| values[*i].0 - time <= max_distance)
+            .find(|i| is_transition(*i))
+            .map(|i| values[i].0);
+        let before = (0..split)
+            .rev()
+            .take_while(|i| time - values[*i].0 <= max_distance)
+            .find(|i| is_transition(*i))
+            .map(|i| values[i].0);
+
+        match (before, after) {
+            (Some(before), Some(after)) if after - time < time - before => Some(after),
+            (Some(before), _) => Some(before),
+            (None, after) => after,
+        }
+    }
+
+    /// Time of the first transition of `signal` after `time`, counted like the transitions markers
+    /// snap to.
+    pub fn next_transition(&self, signal: &str, time: u64) -> Option<u64> {
+        let values = self.waveform_data.values.get(signal)?;
+        let start = values.partition_point(|(t, _)| *t <= time);
+        (start..values.len())
+            .find(|i| self.is_transition(values, *i))
+            .map(|i| values[i].0)
+    }
+
+    // Whether the change at `index` is to a different value than the one before it
+    fn is_transition(&self, values: &[(u64, WaveValue)], index: usize) -> bool {
+        index > 0 && !self.values_equal(&values[index - 1].1, &values[index].1)
+    }
+
+    /// Removes the displayed signal at `index` from view without touching the underlying waveform
+    /// data. The selection stays at the same index so that it lands on the next signal, or on the
+    /// new last signal if the removed signal was at the end of the list.
+    pub fn hide_signal(&mut self, index: usize) -> Option<String> {
+        if index >= self.displayed_signals.len() {
+            return None;
+        }
+
+        let removed = self.displayed_signals.remove(index);
+        self.pinned_signals.retain(|signal| *signal != removed);
+        self.clamp_signal_selection();
+        Some(removed)
+    }
+
+    /// Sets the signal list width as a percentage of the terminal width, clamped to
+    /// `MIN_SIGNAL_LIST_WIDTH`..=`MAX_SIGNAL_LIST_WIDTH`. Returns the width that was applied.
+    pub fn set_signal_list_width(&mut self, width: u16) -> u16 {
+        let width = width.clamp(
+            constants::MIN_SIGNAL_LIST_WIDTH,
+            constants::MAX_SIGNAL_LIST_WIDTH,
+        );
+        self.config.ui.signal_list_width = width;
+        width
+    }
+
+    /// Displays the signals in `selected`. Signals that were already displayed keep their order,
+    /// and newly selected ones are added at the end in declaration order.
+    pub fn apply_signal_selection(&mut self, selected: &HashSet<String>) {
+        let selected_name = self.displayed_signals.get(self.selected_signal).cloned();
+
+        let split_signals = &self.split_signals;
+        self.displayed_signals.retain(|signal| {
+            selected.contains(signal)
+                || split_signals
+                    .iter()
+                    .any(|(parent, bits)| selected.contains(parent) && bits.contains(signal))
+        });
+        let added: Vec<String> = self
+            .waveform_data
+            .signals
+            .iter()
+            .filter(|signal| selected.contains(*signal) && !self.displayed_signals.contains(signal))
+            .cloned()
+            .collect();
+        self.displayed_signals.extend(added);
+
+        self.select_signal_named(selected_name.as_deref());
+    }
+
+    /// Moves the selection to the displayed signal called `name`, wherever it is in the list now.
+    /// The selection is only kept within the list if there is no such signal.
+    pub fn select_signal_named(&mut self, name: Option<&str>) {
+        if let Some(index) =
+            name.and_then(|name| self.displayed_signals.iter().position(|s| s == name))
+        {
+            self.selected_signal = index;
+        }
+        self.clamp_signal_selection();
+    }
+
+    /// Keeps the selected signal and the signal scroll offset within the displayed signal list,
+    /// moving the selection off a signal the filter hides to the next listed one.
+    pub fn clamp_signal_selection(&mut self) {
+        let last_index = self.displayed_signals.len().saturating_sub(1);
+        self.selected_signal = self.selected_signal.min(last_index);
+        self.signal_scroll_offset = self.signal_scroll_offset.min(last_index);
+        if !self.is_listed(self.selected_signal) {
+            if let Some(index) = self
+                .next_listed_signal(self.selected_signal)
+                .or_else(|| self.previous_listed_signal(self.selected_signal))
+            {
+                self.selected_signal = index;
+            }
+        }
+    }
+
+    /// Whether the displayed signal at `index` is in the signal list, rather than hidden by the
+    /// filter.
+    pub fn is_listed(&self, index: usize) -> bool {
+        match (&self.signal_filter, self.displayed_signals.get(index)) {
+            (Some(filter), Some(signal)) => filter.matches(signal),
+            (_, signal) => signal.is_some(),
+        }
+    }
+
+    /// Whether the displayed signal at `index` is pinned above the scrolled signals.
+    pub fn is_pinned(&self, index: usize) -> bool {
+        self.displayed_signals
+            .get(index)
+            .is_some_and(|signal| self.pinned_signals.contains(signal))
+    }
+
+    // Whether the displayed signal at `index` is listed below the pinned signals
+    fn is_scrolled(&self, index: usize) -> bool {
+        self.is_listed(index) && !self.is_pinned(index)
+    }
+
+    // Indices of the listed pinned signals, in pin order
+    fn listed_pinned_signals(&self) -> Vec<usize> {
+        self.pinned_signals
+            .iter()
+            .filter_map(|pinned| self.displayed_signals.iter().position(|s| s == pinned))
+            .filter(|index| self.is_listed(*index))
+            .collect()
+    }
+
+    /// Index of the listed signal after `index`, if any. The pinned signals come first in pin
+    /// order, then the others in display order.
+    pub fn next_listed_signal(&self, index: usize) -> Option<usize> {
+        let pinned = self.listed_pinned_signals();
+        let first_scrolled = if self.is_pinned(index) { 0 } else { index + 1 };
+        match pinned.iter().position(|i| *i == index) {
+            Some(position) if position + 1 < pinned.len() => Some(pinned[position + 1]),
+            _ => (first_scrolled..self.displayed_signals.len()).find(|i| self.is_scrolled(*i)),
+        }
+    }
+
+    /// Index of the listed signal before `index`, if any, in the order of `next_listed_signal`.
+    pub fn previous_listed_signal(&self, index: usize) -> Option<usize> {
+        let pinned = self.listed_pinned_signals();
+        if let Some(position) = pinned.iter().position(|i| *i == index) {
+            return position.checked_sub(1).map(|position| pinned[position]);
+        }
+        (0..index.min(self.displayed_signals.len()))
+            .rev()
+            .find(|i| self.is_scrolled(*i))
+            .or(pinned.last().copied())
+    }
+
+    /// How many signals the list scrolls past before `index`, which is where `index` is in the
+    /// filtered list below the pinned signals.
+    pub fn listed_position(&self, index: usize) -> usize {
+        (0..index.min(self.displayed_signals.len()))
+            .filter(|i| self.is_scrolled(*i))
+            .count()
+    }
+
+    /// Narrows the signal list to the signals whose names contain `query`, remembering the
+    /// selected signal to go back to when the filter is cleared.
+    pub fn set_signal_filter(&mut self, query: &str) {
+        let selected = self.displayed_signals.get(self.selected_signal).cloned();
+        let filter = self.signal_filter.get_or_insert_with(|| SignalFilter {
+            query: String::new(),
+            selected_before: selected,
+        });
+        filter.query = query.to_string();
+        self.clamp_signal_selection();
+    }
+
+    /// Lists every displayed signal again, and selects the signal that was selected before
+    /// filtering.
+    pub fn clear_signal_filter(&mut self) {
+        if let Some(filter) = self.signal_filter.take() {
+            self.select_signal_named(filter.selected_before.as_deref());
+        }
+    }
+
+    /// The width of a signal in bits: its declared width where known, else the widest value it
+    /// holds.
+    pub fn signal_width(&self, signal: &str) -> usize {
+        self.waveform_data
+            .widths
+            .get(signal)
+            .copied()
+            .unwrap_or_else(|| {
+                self.waveform_data
+                    .values
+                    .get(signal)
+                    .into_iter()
+                    .flatten()
+                    .map(|(_, value)| bit_split::value_bits(value).len())
+                    .max()
+                    .unwrap_or(0)
+            })
+    }
+
+    /// A value of a signal as shown in the signal list: signed decimal if `:format` made the
+    /// signal signed, else unsigned decimal.
+    pub fn format_value(&self, signal: &str, value: &WaveValue) -> String {
+        self.format_signed(signal, value)
+            .unwrap_or_else(|| value.to_string())
+    }
+
+    /// Like `format_value`, with unsigned values in uppercase hex, as transitions show them.
+    pub fn format_value_hex(&self, signal: &str, value: &WaveValue) -> String {
+        self.format_signed(signal, value)
+            .unwrap_or_else(|| format!("{:X}", value))
+    }
+
+    // Helper function to format a value of `signal` as signed decimal, if `:format` made it signed
+    fn format_signed(&self, signal: &str, value: &WaveValue) -> Option<String> {
+        self.signed_signals
+            .contains(signal)
+            .then(|| value.format_signed_decimal(self.signal_width(signal)))
+    }
+
+    /// Shows each bit of the displayed bus `signal` as a row of its own, directly below it. The
+    /// signal's declared width is used where known, else the widest value it holds.
+    pub fn split_signal(&mut self, signal: &str) -> Result<usize, String> {
+        let Some(index) = self.displayed_signals.iter().position(|s| s == signal) else {
+            return Err(format!("Signal '{}' is not displayed", signal));
+        };
+        if self.split_signals.contains_key(signal) {
+            return Err(format!("Signal '{}' is already split", signal));
+        }
+
+        let values = self
+            .waveform_data
+            .values
+            .get(signal)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let width = self.signal_width(signal);
+        if width < 2
+            || values
+                .iter()
+                .all(|(_, value)| matches!(value, WaveValue::Binary(_)))
+        {
+            return Err(format!("Signal '{}' is not a bus", signal));
+        }
+
+        let bits = self.derive_bits(signal, width);
+        self.displayed_signals
+            .splice(index + 1..index + 1, bits.iter().cloned());
+        self.split_signals.insert(signal.to_string(), bits);
+        Ok(width)
+    }
+
+    /// Removes the bit rows of a signal split with `split_signal`.
+    pub fn unsplit_signal(&mut self, signal: &str) -> Result<usize, String> {
+        let Some(bits) = self.split_signals.remove(signal) else {
+            return Err(format!("Signal '{}' is not split", signal));
+        };
+
+        let selected_name = self.displayed_signals.get(self.selected_signal).cloned();
+        self.displayed_signals.retain(|s| !bits.contains(s));
+        for bit in &bits {
+            self.waveform_data.values.remove(bit);
+        }
+        self.invalidate_visible_values();
+        self.invalidate_waveform_stats();
+
+        // A removed bit row hands the selection back to the signal it was split from
+        let selected_name = match selected_name {
+            Some(name) if bits.contains(&name) => Some(signal.to_string()),
+            name => name,
+        };
+        self.select_signal_named(selected_name.as_deref());
+        Ok(bits.len())
+    }
+
+    /// Whether `signal` is a row derived from other signals: a bit of a split bus signal, or a
+    /// decoded protocol.
+    pub fn is_derived_signal(&self, signal: &str) -> bool {
+        self.decoded.contains_key(signal)
+            || self
+                .split_signals
+                .values()
+                .any(|bits| bits.iter().any(|bit| bit == signal))
+    }
+
+    /// The annotation of the decoded row `row` spanning `time`, if any.
+    pub fn annotation_at(&self, row: &str, time: u64) -> Option<&Annotation> {
+        self.decoded
+            .get(row)?
+            .iter()
+            .find(|annotation| annotation.start <= time && time < annotation.end)
+    }
+
+    // Helper function to (re)compute the values of the bit rows of `signal` from its bus values.
+    // Returns the names of the rows, most significant bit first.
+    fn derive_bits(&mut self, signal: &str, width: usize) -> Vec<String> {
+        let values = self
+            .waveform_data
+            .values
+            .get(signal)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let bit_values = bit_split::split_bits(values, width);
+
+        let mut names = Vec::with_capacity(width);
+        for (i, values) in bit_values.into_iter().enumerate() {
+            let name = bit_split::bit_signal_name(signal, width - 1 - i);
+            self.waveform_data.values.insert(name.clone(), values);
+            names.push(name);
+        }
+        self.invalidate_visible_values();
+        self.invalidate_waveform_stats();
+        names
+    }
+
+    /// Sets the visible time window. The range is kept between one time unit and the full
+    /// waveform, and the start is moved back if needed so the window doesn't extend past the end.
+    pub fn set_window(&mut self, start: u64, range: u64) {
+        let max_time = self.waveform_data.max_time;
+        self.time_range = range.clamp(1, max_time.max(1));
+        self.time_start = start.min(max_time.saturating_sub(self.time_range));
+    }
+
+    /// Adds value changes read from the end of the loaded file. If the view showed the end of the
+    /// waveform, it moves along so the newest changes stay in view.
+    pub fn append_waveform_data(&mut self, mut data: WaveformData) {
+        let showed_end = self.time_start + self.time_range >= self.waveform_data.max_time;
+
+        for (signal, values) in data.values {
+            let signal_values = self.waveform_data.values.entry(signal.clone()).or_default();
+            // A timestamp going backwards can put appended changes before ones already read
+            let sorted = match (signal_values.last(), values.first()) {
+                (Some((last, _)), Some((first, _))) => last <= first,
+                _ => true,
+            };
+
+            // Appended changes at the time of the last change read before are coalesced with it
+            let last_read = signal_values.len().saturating_sub(1);
+            signal_values.extend(values);
+            let glitches = if sorted {
+                let mut appended = signal_values.split_off(last_read);
+                let glitches = coalesce_changes(&mut appended);
+                signal_values.append(&mut appended);
+                glitches
+            } else {
+                signal_values.sort_by_key(|(time, _)| *time);
+                coalesce_changes(signal_values)
+            };
+
+            let appended_glitches = data.glitches.remove(&signal).unwrap_or_default();
+            if !glitches.is_empty() || !appended_glitches.is_empty() {
+                let signal_glitches = self.waveform_data.glitches.entry(signal).or_default();
+                merge_glitches(signal_glitches, glitches);
+                merge_glitches(signal_glitches, appended_glitches);
+            }
+        }
+        let split: Vec<(String, usize)> = self
+            .split_signals
+            .iter()
+            .map(|(signal, bits)| (signal.clone(), bits.len()))
+            .collect();
+        for (signal, width) in split {
+            self.derive_bits(&signal, width);
+        }
+        self.invalidate_visible_values();
+        self.invalidate_waveform_stats();
+
+        if data.max_time > self.waveform_data.max_time {
+            self.waveform_data.max_time = data.max_time;
+            if showed_end {
+                let range = self.time_range;
+                self.set_window(data.max_time.saturating_sub(range), range);
+            }
+        }
+    }
+
+    /// Zooms to a new time window, remembering the current one in the zoom history so it can be
+    /// restored with `zoom_back`. Pans should use `set_window` so they don't fill up the history.
+    pub fn zoom_to(&mut self, start: u64, range: u64) {
+        let previous = (self.time_start, self.time_range);
+        self.set_window(start, range);
+        if (self.time_start, self.time_range) == previous {
+            return;
+        }
+
+        if self.zoom_history.len() == constants::MAX_ZOOM_HISTORY {
+            self.zoom_history.remove(0);
+        }
+        self.zoom_history.push(previous);
+        self.zoom_forward_history.clear();
+    }
+
+    /// Splits the waveform into two panes over the same signals, both showing the current window to
+    /// begin with. The top pane has focus.
+    pub fn open_split_view(&mut self) {
+        self.split_view = Some(SplitView {
+            time_start: self.time_start,
+            time_range: self.time_range,
+            ..Default::default()
+        });
+    }
+
+    /// Moves the focus to the other pane of the split waveform, so that navigation and zooming move
+    /// its window. Returns false if the waveform isn't split.
+    pub fn switch_pane_focus(&mut self) -> bool {
+        self.swap_pane_windows();
+        match &mut self.split_view {
+            Some(split_view) => {
+                split_view.bottom_focused = !split_view.bottom_focused;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Exchanges the window and zoom histories of the focused pane with those of the other pane,
+    /// without moving the focus. Used to draw the other pane, and swapped back afterwards.
+    pub fn swap_pane_windows(&mut self) {
+        if let Some(split_view) = &mut self.split_view {
+            std::mem::swap(&mut self.time_start, &mut split_view.time_start);
+            std::mem::swap(&mut self.time_range, &mut split_view.time_range);
+            std::mem::swap(&mut self.zoom_history, &mut split_view.zoom_history);
+            std::mem::swap(
+                &mut self.zoom_forward_history,
+                &mut split_view.zoom_forward_history,
+            );
+        }
+    }
+
+    /// Zooms to `range`, keeping the current center time in view.
+    pub fn zoom_centered(&mut self, range: u64) {
+        let center = self.time_start + self.time_range / 2;
+        self.zoom_around(center, range);
+    }
+
+    /// Zooms to `range` with `center` in the middle of the window, or as close to the middle as
+    /// the ends of the waveform allow.
+    pub fn zoom_around(&mut self, center: u64, range: u64) {
+        self.zoom_to(center.saturating_sub(range / 2), range);
+    }
+
+    /// Pans by `delta` time units, stopping at the start and end of the waveform.
+    pub fn pan_by(&mut self, delta: i64) {
+        let range = self.time_range;
+        self.set_window(self.time_start.saturating_add_signed(delta), range);
+    }
+
+    /// Pans so `time` is in the middle of the view, or as close to it as the start and end of the
+    /// waveform allow.
+    pub fn center_on(&mut self, time: u64) {
+        let range = self.time_range;
+        self.set_window(time.saturating_sub(range / 2), range);
+    }
+
+    /// Moves the cursor by `delta`, staying within the waveform. Pushing the cursor past the edge
+    /// of the view pans the view along with it.
+    pub fn move_cursor(&mut self, delta: i64) {
+        let Some(cursor) = self.cursor else {
+            return;
+        };
+        let cursor = cursor
+            .saturating_add_signed(delta)
+            .min(self.waveform_data.max_time);
+        self.cursor = Some(cursor);
+
+        let range = self.time_range;
+        if cursor < self.time_start {
+            self.set_window(cursor, range);
+        } else if cursor > self.time_start + range {
+            self.set_window(cursor - range, range);
+        }
+    }
+
+    /// Time in the middle of the view.
+    pub fn view_center(&self) -> u64 {
+        self.time_start + self.time_range / 2
+    }
+
+    /// Restores the window from before the last zoom. Returns false if there is nothing to restore.
+    pub fn zoom_back(&mut self) -> bool {
+        match self.zoom_history.pop() {
+            Some((start, range)) => {
+                self.zoom_forward_history
+                    .push((self.time_start, self.time_range));
+                self.set_window(start, range);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Re-applies the last zoom undone by `zoom_back`. Returns false if there is nothing to re-apply.
+    pub fn zoom_forward(&mut self) -> bool {
+        match self.zoom_forward_history.pop() {
+            Some((start, range)) => {
+                self.zoom_history.push((self.time_start, self.time_range));
+                self.set_window(start, range);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn clear_zoom_history(&mut self) {
+        self.zoom_history.clear();
+        self.zoom_forward_history.clear();
+    }
+
+    /// The view and selected signal, as remembered in the jump list.
+    fn current_jump(&self) -> Jump {
+        Jump {
+            time_start: self.time_start,
+            time_range: self.time_range,
+            selected_signal: self.displayed_signals.get(self.selected_signal).cloned(),
+        }
+    }
+
+    /// Saves the current view in a numbered slot, replacing what was saved there.
+    pub fn save_view(&mut self, slot: u8) {
+        self.view_bookmarks.insert(
+            slot,
+            ViewBookmark {
+                time_start: self.time_start,
+                time_range: self.time_range,
+                selected_signal: self.displayed_signals.get(self.selected_signal).cloned(),
+                signal_scroll_offset: self.signal_scroll_offset,
+            },
+        );
+    }
+
+    /// Goes back to the view saved in a numbered slot, which can be jumped back from.
+    pub fn load_view(&mut self, slot: u8) -> Result<(), String> {
+        let Some(view) = self.view_bookmarks.get(&slot).cloned() else {
+            return Err(format!("No view saved in slot {}", slot));
+        };
+        self.record_jump();
+        self.set_window(view.time_start, view.time_range);
+        self.signal_scroll_offset = view.signal_scroll_offset;
+        self.select_signal_named(view.selected_signal.as_deref());
+        Ok(())
+    }
+
+    /// Remembers the current place in the jump list before jumping away from it. Places after the
+    /// one shown are dropped, so going back and then jumping starts a new path like in vim.
+    pub fn record_jump(&mut self) {
+        self.jump_list.truncate(self.jump_index);
+        self.push_current_jump();
+    }
+
+    /// Goes back to the place before the last jump, remembering the current place to come back to
+    /// with `jump_forward`. Returns false if there is nowhere to go back to.
+    pub fn jump_back(&mut self) -> bool {
+        if self.jump_index == self.jump_list.len() {
+            self.push_current_jump();
+            self.jump_index = self.jump_list.len() - 1;
+        }
+        if self.jump_index == 0 {
+            return false;
+        }
+        self.jump_index -= 1;
+        self.restore_jump(self.jump_list[self.jump_index].clone());
+        true
+    }
+
+    // Adds the current place to the end of the jump list unless it is already the last place, and
+    // moves past it
+    fn push_current_jump(&mut self) {
+        let jump = self.current_jump();
+        if self.jump_list.last() != Some(&jump) {
+            if self.jump_list.len() == constants::MAX_JUMPS {
+                self.jump_list.remove(0);
+            }
+            self.jump_list.push(jump);
+        }
+        self.jump_index = self.jump_list.len();
+    }
+
+    /// Goes forward to the place `jump_back` left. Returns false if there is nowhere to go.
+    pub fn jump_forward(&mut self) -> bool {
+        if self.jump_index + 1 >= self.jump_list.len() {
+            return false;
+        }
+        self.jump_index += 1;
+        self.restore_jump(self.jump_list[self.jump_index].clone());
+        true
+    }
+
+    // Shows the window of a jump, and selects its signal if it is still displayed
+    fn restore_jump(&mut self, jump: Jump) {
+        self.set_window(jump.time_start, jump.time_range);
+        if let Some(index) = jump
+            .selected_signal
+            .and_then(|signal| self.displayed_signals.iter().position(|s| *s == signal))
+        {
+            self.selected_signal = index;
+        }
+    }
+
+    pub fn clear_jump_list(&mut self) {
+        self.jump_list.clear();
+        self.jump_index = 0;
+    }
+
+    /// Halves the visible time range around its center, stopping at `MIN_ZOOM_IN_RANGE`.
+    pub fn zoom_in(&mut self) {
+        let new_range = (self.time_range / 2)
+            .max(constants::MIN_ZOOM_IN_RANGE)
+            .min(self.time_range);
+        self.zoom_centered(new_range);
+    }
+
+    /// Doubles the visible time range around its center, up to the full waveform.
+    pub fn zoom_out(&mut self) {
+        self.zoom_centered(self.time_range.saturating_mul(2));
+    }
+
+    /// The view's time window drawn over `width` columns, mapping times to columns and back.
+    pub fn viewport(&self, width: u16) -> Viewport {
+        Viewport {
+            time_start: self.time_start,
+            time_range: self.time_range,
+            width,
+        }
     }
 
     // Markers are saved with the time at which they're placed -- not the x coordinate at which
     // they're placed. This method converts the x coordinate to a time value.
     pub fn screen_pos_to_time(&self, x_pos: u16, window_width: u16) -> u64 {
-        let time_range = self.time_range as f64;
-        let position_ratio = x_pos as f64 / window_width as f64;
-        let exact_time = self.time_start as f64 + (position_ratio * time_range);
-        exact_time.round() as u64
+        self.viewport(window_width).time_of(x_pos)
     }
 
     pub fn get_value_at_marker(&self, signal: &str, marker_time: u64) -> Option<WaveValue> {
@@ -146,6 +1178,8 @@ impl AppState {
         None
     }
 
+    /// The change of a signal at exactly `marker_time`, like `0->1`, and the values it went
+    /// through if it changed more than once then, like `0->1 (glitch via X)`.
     pub fn get_transition_at_marker(&self, signal: &str, marker_time: u64) -> Option<String> {
         if let Some(values) = self.waveform_data.values.get(signal) {
             for i in 0..values.len() {
@@ -155,10 +1189,20 @@ impl AppState {
                     // We found our transition point
                     let (_, before_val) = &values[i - 1];
                     let (_, after_val) = &values[i];
-
-                    // Only report if values are different (it's a real transition)
-                    if !self.values_equal(before_val, after_val) {
-                        return Some(format!("{:X}->{:X}", before_val, after_val));
+                    let glitch = self.glitch_at(signal, time);
+
+                    // Only report if values are different (it's a real transition), or if they
+                    // differed for no time at all
+                    if !self.values_equal(before_val, after_val) || glitch.is_some() {
+                        let format = |value: &WaveValue| self.format_value_hex(signal, value);
+                        let transition = format!("{}->{}", format(before_val), format(after_val));
+                        return Some(match glitch {
+                            Some(glitch) => {
+                                let via: Vec<String> = glitch.via.iter().map(format).collect();
+                                format!("{} (glitch via {})", transition, via.join(", "))
+                            }
+                            None => transition,
+                        });
                     }
                 }
             }
@@ -166,6 +1210,15 @@ impl AppState {
         None
     }
 
+    /// Where a signal changed more than once at `time`, if it did.
+    pub fn glitch_at(&self, signal: &str, time: u64) -> Option<&Glitch> {
+        let glitches = self.waveform_data.glitches.get(signal)?;
+        let index = glitches
+            .binary_search_by_key(&time, |glitch| glitch.time)
+            .ok()?;
+        Some(&glitches[index])
+    }
+
     // Helper function to check if two WaveValues are equal
     fn values_equal(&self, v1: &WaveValue, v2: &WaveValue) -> bool {
         match (v1, v2) {
@@ -175,40 +1228,293 @@ impl AppState {
         }
     }
 
-    pub fn get_visible_values(&self, signal: &str) -> Vec<(u64, WaveValue)> {
-        if !self.displayed_signals.contains(&signal.to_string()) {
-            return Vec::new();
+    /// Returns the changes of a displayed signal in the visible window, starting with the value the
+    /// window is entered with, which is the latest change at or before `time_start`. That first
+    /// change may lie before `time_start`, so callers should treat its time as `time_start`. Empty
+    /// if the signal isn't displayed.
+    pub fn get_visible_values(&self, signal: &str) -> &[(u64, WaveValue)] {
+        if !self.displayed_signals.iter().any(|s| s == signal) {
+            return &[];
+        }
+        let Some(values) = self.waveform_data.values.get(signal) else {
+            return &[];
+        };
+
+        let mut cache = self.visible_values_cache.borrow_mut();
+        let view = (self.time_start, self.time_range);
+        if cache.view != view {
+            cache.view = view;
+            cache.ranges.clear();
         }
 
-        if let Some(values) = self.waveform_data.values.get(signal) {
-            let mut result = Vec::new();
-
-            // Find the last value before the visible range
-            let mut last_before_view = None;
-            for (t, v) in values {
-                if *t < self.time_start {
-                    last_before_view = Some((*t, v.clone()));
-                } else {
-                    break;
-                }
+        let range = match cache.ranges.get(signal) {
+            // Guard against the values changing without the cache being invalidated
+            Some(range) if range.end <= values.len() => range.clone(),
+            _ => {
+                let range = visible_range(
+                    values,
+                    |(t, _)| *t,
+                    self.time_start,
+                    self.time_start + self.time_range,
+                );
+                cache.ranges.insert(signal.to_string(), range.clone());
+                range
             }
+        };
 
-            // Add the last value before the view with an adjusted timestamp
-            // to ensure it appears at the left edge of the view
-            if let Some((_, v)) = last_before_view {
-                result.push((self.time_start, v));
-            }
+        &values[range]
+    }
+
+    /// Time of a signal's first value, if it has any. Before it the signal has no value at all,
+    /// not even x.
+    pub fn first_value_time(&self, signal: &str) -> Option<u64> {
+        let values = self.waveform_data.values.get(signal)?;
+        values.first().map(|(time, _)| *time)
+    }
+
+    /// Like `get_visible_values`, but for the file loaded with `:diff`. Empty if no file is loaded
+    /// or it doesn't have the signal.
+    pub fn get_visible_diff_values(&self, signal: &str) -> &[(u64, WaveValue)] {
+        let Some(values) = self
+            .diff
+            .as_ref()
+            .and_then(|diff| diff.data.values.get(signal))
+        else {
+            return &[];
+        };
+        let range = visible_range(
+            values,
+            |(t, _)| *t,
+            self.time_start,
+            self.time_start + self.time_range,
+        );
+        &values[range]
+    }
+
+    /// Rows `signal` is drawn in: its height from `:height`, or `ui.waveform_height`.
+    pub fn signal_height(&self, signal: &str) -> usize {
+        self.signal_heights
+            .get(signal)
+            .copied()
+            .unwrap_or(self.config.ui.waveform_height)
+    }
+
+    /// Screen rows `signal` takes up. While a file is loaded with `:diff`, every signal is drawn a
+    /// second time below itself with its values in that file.
+    pub fn row_height(&self, signal: &str) -> usize {
+        if self.diff.is_some() {
+            2 * self.signal_height(signal)
+        } else {
+            self.signal_height(signal)
+        }
+    }
+
+    /// Rows the pinned signals take up at the top, with the separator below them, or 0 when
+    /// nothing is pinned.
+    pub fn pinned_height(&self) -> u16 {
+        let pinned = self.listed_pinned_signals();
+        if pinned.is_empty() {
+            return 0;
+        }
+        pinned
+            .iter()
+            .map(|index| self.row_height(&self.displayed_signals[*index]) as u16)
+            .sum::<u16>()
+            + 1
+    }
 
-            // Add all values within the view range
-            for (t, v) in values {
-                if *t >= self.time_start && *t < self.time_start + self.time_range {
-                    result.push((*t, v.clone()));
+    // The pinned signals then the displayed signals from the scroll offset on, as
+    // `(index, first row, rows)` with rows counted from the top of the signals
+    fn signal_rows(&self) -> impl Iterator<Item = (usize, u16, u16)> + '_ {
+        let pinned = self.listed_pinned_signals();
+        let pinned_count = pinned.len();
+        let scrolled = (self.signal_scroll_offset..self.displayed_signals.len())
+            .filter(|index| self.is_scrolled(*index));
+        pinned
+            .into_iter()
+            .chain(scrolled)
+            .enumerate()
+            .scan(0u16, move |top, (position, index)| {
+                // A separator row is left between the pinned and the scrolled signals
+                if position == pinned_count && pinned_count > 0 {
+                    *top = top.saturating_add(1);
                 }
+                let row_height = self.row_height(&self.displayed_signals[index]) as u16;
+                let rows = (index, *top, row_height);
+                *top = top.saturating_add(row_height);
+                Some(rows)
+            })
+    }
+
+    /// The pinned signals and the displayed signals from the scroll offset on whose rows all fit
+    /// in `height` rows, as `(index, first row, rows)` with rows counted from the top of the
+    /// signals.
+    pub fn visible_signal_rows(&self, height: u16) -> Vec<(usize, u16, u16)> {
+        self.signal_rows()
+            .take_while(|(_, top, row_height)| top + row_height <= height)
+            .collect()
+    }
+
+    /// Index of the displayed signal whose rows include `row`, counted from the top of the signals,
+    /// if any. The separator below the pinned signals has none.
+    pub fn signal_at_row(&self, row: u16) -> Option<usize> {
+        self.signal_rows()
+            .find(|(_, top, row_height)| row < top + row_height)
+            .filter(|(_, top, _)| row >= *top)
+            .map(|(index, _, _)| index)
+    }
+
+    /// Smallest scroll offset that shows the displayed signal at `index` in `height` rows below
+    /// the pinned signals, leaving it at the bottom of them.
+    pub fn scroll_offset_ending_at(&self, index: usize, height: u16) -> usize {
+        let height = height.saturating_sub(self.pinned_height());
+        let mut offset = index + 1;
+        let mut rows = 0;
+        while offset > 0 {
+            if self.is_scrolled(offset - 1) {
+                rows += self.row_height(&self.displayed_signals[offset - 1]);
             }
+            if rows > height as usize {
+                break;
+            }
+            offset -= 1;
+        }
+        offset.min(index)
+    }
 
-            result
-        } else {
-            Vec::new()
+    /// Largest scroll offset that doesn't leave rows empty below the last displayed signal.
+    pub fn max_signal_scroll_offset(&self, height: u16) -> usize {
+        match (0..self.displayed_signals.len())
+            .rev()
+            .find(|i| self.is_scrolled(*i))
+        {
+            Some(last) => self.scroll_offset_ending_at(last, height),
+            None => 0,
+        }
+    }
+
+    /// Drops all cached visible value ranges. Must be called when the waveform data is replaced.
+    pub fn invalidate_visible_values(&mut self) {
+        self.visible_values_cache.get_mut().ranges.clear();
+    }
+
+    /// Statistics of the waveform data, computed on first use and cached until
+    /// `invalidate_waveform_stats` is called.
+    pub fn waveform_stats(&mut self) -> &WaveformStats {
+        self.waveform_stats
+            .get_or_insert_with(|| WaveformStats::new(&self.waveform_data))
+    }
+
+    /// Whether each displayed signal is constant, never changing from its initial value.
+    pub fn constant_displayed_signals(&mut self) -> Vec<bool> {
+        let stats = self
+            .waveform_stats
+            .get_or_insert_with(|| WaveformStats::new(&self.waveform_data));
+        // Decoded rows have no values of their own to be constant
+        let decoded = &self.decoded;
+        self.displayed_signals
+            .iter()
+            .map(|signal| !decoded.contains_key(signal) && stats.is_constant(signal))
+            .collect()
+    }
+
+    /// The times of the first and last change of the displayed signals, if any of them changes.
+    pub fn displayed_activity_span(&mut self) -> Option<(u64, u64)> {
+        let stats = self
+            .waveform_stats
+            .get_or_insert_with(|| WaveformStats::new(&self.waveform_data));
+        stats.activity_span(&self.displayed_signals)
+    }
+
+    /// Change counts of all signals, for sorting the signal finder by activity.
+    pub fn change_counts(&mut self) -> HashMap<String, usize> {
+        self.waveform_stats()
+            .signals
+            .iter()
+            .map(|(signal, stats)| (signal.clone(), stats.changes))
+            .collect()
+    }
+
+    /// Drops the cached statistics and minimap density. Must be called when the waveform data
+    /// changes.
+    pub fn invalidate_waveform_stats(&mut self) {
+        self.waveform_stats = None;
+        self.minimap_density = None;
+    }
+
+    /// Number of changes of the displayed signals at each column of a minimap `width` columns
+    /// wide, placed with `minimap_viewport` like its window highlight.
+    pub fn minimap_density(&mut self, width: u16) -> &[usize] {
+        let stale = self
+            .minimap_density
+            .as_ref()
+            .is_none_or(|cached| cached.width != width || cached.signals != self.displayed_signals);
+        if stale {
+            self.minimap_density = None;
+        }
+
+        let viewport = self.minimap_viewport(width);
+        &self
+            .minimap_density
+            .get_or_insert_with(|| MinimapDensity {
+                width,
+                signals: self.displayed_signals.clone(),
+                density: activity_density(&self.waveform_data, &self.displayed_signals, &viewport),
+            })
+            .density
+    }
+
+    /// Time at `column` of a minimap `width` columns wide spanning the whole waveform.
+    pub fn minimap_time_at(&self, column: u16, width: u16) -> u64 {
+        if width == 0 {
+            return 0;
+        }
+        self.minimap_viewport(width).time_of(column.min(width - 1))
+    }
+
+    /// The whole waveform drawn over a minimap `width` columns wide.
+    pub fn minimap_viewport(&self, width: u16) -> Viewport {
+        Viewport {
+            time_start: 0,
+            time_range: self.waveform_data.max_time.saturating_add(1),
+            width,
+        }
+    }
+
+    /// Names of the signal groups whose signals are all selected in the signal finder.
+    pub fn complete_signal_groups(&self) -> Vec<&str> {
+        let selected = &self.fuzzy_finder_state.selected_signals;
+        self.signal_groups
+            .iter()
+            .filter(|(_, signals)| {
+                !signals.is_empty() && signals.iter().all(|signal| selected.contains(signal))
+            })
+            .map(|(name, _)| name.as_str())
+            .collect()
+    }
+
+    /// Saved markers sorted by time, and by name for markers at the same time.
+    pub fn saved_markers_by_time(&self) -> Vec<&Marker> {
+        let mut markers: Vec<&Marker> = self.saved_markers.iter().collect();
+        markers.sort_by(|a, b| a.time.cmp(&b.time).then_with(|| a.name.cmp(&b.name)));
+        markers
+    }
+
+    /// Time of the primary marker (m1), secondary marker (m2), or a saved marker by name.
+    pub fn marker_time(&self, name: &str) -> Result<u64, String> {
+        match name {
+            "m1" => self
+                .primary_marker
+                .ok_or_else(|| "Primary marker not set".to_string()),
+            "m2" => self
+                .secondary_marker
+                .ok_or_else(|| "Secondary marker not set".to_string()),
+            _ => self
+                .saved_markers
+                .iter()
+                .find(|m| m.name == name)
+                .map(|m| m.time)
+                .ok_or_else(|| format!("No marker found with name '{}'", name)),
         }
     }
 
@@ -221,6 +1527,7 @@ impl AppState {
 mod tests {
     use crate::parsers::types::{Value, WaveValue};
     use crate::state::AppState;
+    use crate::types::Viewport;
     use std::collections::HashMap;
 
     fn create_test_state() -> AppState {
@@ -254,6 +1561,30 @@ mod tests {
         state
     }
 
+    #[test]
+    fn test_viewport_round_trip() {
+        let ranges = (7..10007).step_by(97).chain(20..200);
+        for (width, time_range) in (20..200).flat_map(|w| ranges.clone().map(move |r| (w, r))) {
+            let viewport = Viewport {
+                time_start: 1000,
+                time_range,
+                width,
+            };
+            for column in 0..width {
+                let time = viewport.time_of(column);
+                if time_range >= width as u64 {
+                    assert_eq!(viewport.column_of(time), column, "{:?}", viewport);
+                } else {
+                    // Zoomed in past a time unit per column, neighbouring columns share a time,
+                    // which is drawn in one of them
+                    assert_eq!(viewport.time_of(viewport.column_of(time)), time);
+                }
+            }
+            assert_eq!(viewport.column_of(0), 0);
+            assert_eq!(viewport.column_of(1000 + time_range), width);
+        }
+    }
+
     #[test]
     fn test_screen_pos_to_time_conversion() {
         let mut state = create_test_state();
@@ -271,6 +1602,16 @@ mod tests {
         assert_eq!(state.screen_pos_to_time(50, 100), 100);
     }
 
+    #[test]
+    fn test_screen_pos_to_time_without_width() {
+        let mut state = create_test_state();
+        state.time_start = 30;
+        state.time_range = 100;
+
+        assert_eq!(state.screen_pos_to_time(0, 0), 30);
+        assert_eq!(state.screen_pos_to_time(5, 0), 30);
+    }
+
     #[test]
     fn test_get_value_at_marker() {
         let state = create_test_state();
@@ -296,6 +1637,48 @@ mod tests {
         assert_eq!(state.get_value_at_marker("nonexistent", 0), None);
     }
 
+    #[test]
+    fn test_glitch_appended_at_last_time() {
+        use crate::parsers::types::{Glitch, WaveformData};
+
+        let mut state = create_test_state();
+        let last = state.waveform_data.values["sig1"].last().unwrap().clone();
+        state.append_waveform_data(WaveformData {
+            signals: Vec::new(),
+            values: HashMap::from([(
+                "sig1".to_string(),
+                vec![(last.0, WaveValue::Binary(Value::VX)), last.clone()],
+            )]),
+            max_time: last.0,
+            timescale_fs: None,
+            widths: HashMap::new(),
+            window: None,
+            glitches: HashMap::new(),
+        });
+
+        // The changes appended at the time of the last one read are coalesced with it
+        assert_eq!(state.waveform_data.values["sig1"].last(), Some(&last));
+        assert_eq!(
+            state.glitch_at("sig1", last.0),
+            Some(&Glitch {
+                time: last.0,
+                via: vec![last.1.clone(), WaveValue::Binary(Value::VX)],
+            })
+        );
+
+        state.waveform_data.glitches.insert(
+            "sig1".to_string(),
+            vec![Glitch {
+                time: 10,
+                via: vec![WaveValue::Binary(Value::VX)],
+            }],
+        );
+        assert_eq!(
+            state.get_transition_at_marker("sig1", 10),
+            Some("0->1 (glitch via X)".to_string())
+        );
+    }
+
     #[test]
     fn test_get_visible_values() {
         let mut state = create_test_state();
@@ -306,11 +1689,12 @@ mod tests {
         let visible = state.get_visible_values("sig1");
         assert_eq!(visible.len(), 3);
 
-        // Still all...
+        // A change exactly at the start replaces the value before it
         state.time_start = 10;
         state.time_range = 20;
         let visible = state.get_visible_values("sig1");
-        assert_eq!(visible.len(), 3);
+        assert_eq!(visible.len(), 2);
+        assert_eq!(visible[0].0, 10);
 
         // ...Now fewer
         state.time_start = 11;
@@ -335,4 +1719,537 @@ mod tests {
         let visible = state.get_visible_values("sig1");
         assert_eq!(visible.len(), 0);
     }
+
+    // A linear scan doing what get_visible_values does with binary searches, to check it against
+    fn reference_visible_values(state: &AppState, signal: &str) -> Vec<(u64, WaveValue)> {
+        if !state.displayed_signals.contains(&signal.to_string()) {
+            return Vec::new();
+        }
+        let Some(values) = state.waveform_data.values.get(signal) else {
+            return Vec::new();
+        };
+
+        let mut result = Vec::new();
+        let mut entry_value = None;
+        for (t, v) in values {
+            if *t <= state.time_start {
+                entry_value = Some(v.clone());
+            } else {
+                break;
+            }
+        }
+        if let Some(v) = entry_value {
+            result.push((state.time_start, v));
+        }
+        for (t, v) in values {
+            if *t > state.time_start && *t < state.time_start + state.time_range {
+                result.push((*t, v.clone()));
+            }
+        }
+        result
+    }
+
+    fn assert_matches_reference(state: &AppState, signal: &str) {
+        let visible: Vec<(u64, WaveValue)> = state
+            .get_visible_values(signal)
+            .iter()
+            .map(|(t, v)| ((*t).max(state.time_start), v.clone()))
+            .collect();
+        assert_eq!(
+            visible,
+            reference_visible_values(state, signal),
+            "window {}+{}",
+            state.time_start,
+            state.time_range
+        );
+    }
+
+    #[test]
+    fn test_get_visible_values_matches_reference() {
+        let mut state = create_test_state();
+        // Include repeated timestamps and a gap
+        state.waveform_data.values.insert(
+            "sig2".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V1)),
+                (5, WaveValue::Binary(Value::V0)),
+                (5, WaveValue::Binary(Value::VX)),
+                (6, WaveValue::Binary(Value::V1)),
+                (30, WaveValue::Binary(Value::V0)),
+            ],
+        );
+
+        for time_start in 0..40 {
+            for time_range in 0..40 {
+                state.time_start = time_start;
+                state.time_range = time_range;
+                assert_matches_reference(&state, "sig1");
+                assert_matches_reference(&state, "sig2");
+                assert_matches_reference(&state, "nonexistent");
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_visible_values_is_invalidated() {
+        let mut state = create_test_state();
+        state.time_start = 5;
+        state.time_range = 10;
+        assert_matches_reference(&state, "sig1");
+
+        // Changing the view recomputes the range
+        state.time_start = 15;
+        assert_matches_reference(&state, "sig1");
+
+        // Replacing the data with the same number of values needs an explicit invalidation
+        state.waveform_data.values.insert(
+            "sig1".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (16, WaveValue::Binary(Value::V1)),
+                (17, WaveValue::Binary(Value::V0)),
+            ],
+        );
+        state.invalidate_visible_values();
+        assert_matches_reference(&state, "sig1");
+    }
+
+    #[test]
+    fn test_visible_range_touches_log_n_values() {
+        use std::cell::Cell;
+
+        let n: u64 = 1_000_000;
+        let times: Vec<u64> = (0..n).map(|i| i * 2).collect();
+        let touched = Cell::new(0);
+        let time_of = |t: &u64| {
+            touched.set(touched.get() + 1);
+            *t
+        };
+
+        // A window containing k = 1000 changes in the middle of the signal, the first of them
+        // exactly at the start
+        let range = super::visible_range(&times, time_of, 1_000_000, 1_002_000);
+        assert_eq!(range, 500_000..501_000);
+
+        // Two binary searches, independent of both n and k
+        let log_n = (n as f64).log2().ceil() as usize;
+        assert!(
+            touched.get() <= 2 * (log_n + 1),
+            "touched {}",
+            touched.get()
+        );
+    }
+
+    #[test]
+    fn test_get_visible_values_large_signal() {
+        let mut state = create_test_state();
+        state.waveform_data.values.insert(
+            "sig1".to_string(),
+            (0..1_000_000u64)
+                .map(|i| {
+                    let value = if i % 2 == 0 { Value::V0 } else { Value::V1 };
+                    (i * 2, WaveValue::Binary(value))
+                })
+                .collect(),
+        );
+        state.time_start = 1_000_001;
+        state.time_range = 100;
+
+        let visible = state.get_visible_values("sig1");
+        assert_eq!(visible.len(), 51);
+        assert_eq!(visible[0].0, 1_000_000);
+        assert_eq!(visible[50].0, 1_000_100);
+        assert_matches_reference(&state, "sig1");
+    }
+
+    #[test]
+    fn test_set_window_keeps_view_valid() {
+        let mut state = AppState::new();
+        state.waveform_data.max_time = 100;
+
+        state.set_window(50, 0);
+        assert_eq!((state.time_start, state.time_range), (50, 1));
+
+        state.set_window(90, 50);
+        assert_eq!((state.time_start, state.time_range), (50, 50));
+
+        state.set_window(10, 500);
+        assert_eq!((state.time_start, state.time_range), (0, 100));
+
+        // An empty waveform still gets a non-zero range
+        state.waveform_data.max_time = 0;
+        state.set_window(10, 0);
+        assert_eq!((state.time_start, state.time_range), (0, 1));
+    }
+
+    #[test]
+    fn test_zoom_in_and_out() {
+        let mut state = AppState::new();
+        state.waveform_data.max_time = 100;
+        state.set_window(0, 100);
+
+        state.zoom_in();
+        assert_eq!((state.time_start, state.time_range), (25, 50));
+
+        // Zooming in stops at the minimum range, but never widens an already narrower view
+        for _ in 0..10 {
+            state.zoom_in();
+        }
+        assert_eq!(state.time_range, 10);
+        state.set_window(40, 3);
+        state.zoom_in();
+        assert_eq!(state.time_range, 3);
+
+        // Zooming out near the end keeps the window within the waveform
+        state.set_window(90, 10);
+        state.zoom_out();
+        assert_eq!((state.time_start, state.time_range), (80, 20));
+        for _ in 0..10 {
+            state.zoom_out();
+        }
+        assert_eq!((state.time_start, state.time_range), (0, 100));
+    }
+
+    #[test]
+    fn test_zoom_history_push_and_pop() {
+        let mut state = AppState::new();
+        state.waveform_data.max_time = 1000;
+        state.set_window(0, 1000);
+
+        // Drag-zoom into a region, then zoom to the full view again
+        state.zoom_to(200, 100);
+        state.zoom_to(0, 1000);
+        assert_eq!(state.zoom_history, vec![(0, 1000), (200, 100)]);
+
+        assert!(state.zoom_back());
+        assert_eq!((state.time_start, state.time_range), (200, 100));
+        assert!(state.zoom_back());
+        assert_eq!((state.time_start, state.time_range), (0, 1000));
+        assert!(!state.zoom_back());
+
+        assert!(state.zoom_forward());
+        assert_eq!((state.time_start, state.time_range), (200, 100));
+        assert!(state.zoom_forward());
+        assert_eq!((state.time_start, state.time_range), (0, 1000));
+        assert!(!state.zoom_forward());
+
+        // A new zoom discards the windows that could be zoomed forward to
+        state.zoom_back();
+        state.zoom_to(500, 10);
+        assert!(state.zoom_forward_history.is_empty());
+    }
+
+    #[test]
+    fn test_zoom_history_ignores_pans_and_is_capped() {
+        let mut state = AppState::new();
+        state.waveform_data.max_time = 1000;
+        state.set_window(0, 100);
+
+        state.set_window(50, 100);
+        assert!(state.zoom_history.is_empty());
+
+        // Zooming to the current window is not a change
+        state.zoom_to(50, 100);
+        assert!(state.zoom_history.is_empty());
+
+        for i in 0..100 {
+            state.zoom_to(i, 10);
+        }
+        assert_eq!(state.zoom_history.len(), crate::constants::MAX_ZOOM_HISTORY);
+        assert_eq!(state.zoom_history.last(), Some(&(98, 10)));
+    }
+
+    #[test]
+    fn test_set_signal_list_width_is_clamped() {
+        let mut state = AppState::new();
+        assert_eq!(state.set_signal_list_width(30), 30);
+        assert_eq!(state.config.ui.signal_list_width, 30);
+
+        assert_eq!(state.set_signal_list_width(1), 5);
+        assert_eq!(state.config.ui.signal_list_width, 5);
+
+        assert_eq!(state.set_signal_list_width(95), 80);
+        assert_eq!(state.config.ui.signal_list_width, 80);
+    }
+
+    #[test]
+    fn test_marker_snaps_to_nearest_transition() {
+        let mut state = create_test_state();
+        // A clock toggling every 3 time units, several edges per column at 10 units per column
+        let clock = (0..=1000u64)
+            .step_by(3)
+            .map(|t| {
+                let value = if (t / 3) % 2 == 0 {
+                    Value::V0
+                } else {
+                    Value::V1
+                };
+                (t, WaveValue::Binary(value))
+            })
+            .collect();
+        state.waveform_data.values.insert("clk".to_string(), clock);
+        state.displayed_signals = vec!["clk".to_string()];
+        state.waveform_data.max_time = 1000;
+        state.set_window(0, 1000);
+
+        // Without snapping the marker lands where clicked
+        state.set_primary_marker(49, 100);
+        assert_eq!(state.primary_marker, Some(490));
+
+        // Between the edges at 489 and 492, the closer one wins
+        state.config.ui.marker_snap = true;
+        state.set_primary_marker(49, 100);
+        assert_eq!(state.primary_marker, Some(489));
+        state.set_secondary_marker(50, 100);
+        assert_eq!(state.secondary_marker, Some(501));
+
+        // The first value isn't a transition, so there is nothing to snap to near time 0
+        state.config.ui.marker_snap_distance = 0;
+        state.set_primary_marker(0, 100);
+        assert_eq!(state.primary_marker, Some(0));
+    }
+
+    #[test]
+    fn test_marker_snap_respects_distance() {
+        let mut state = create_test_state();
+        state.config.ui.marker_snap = true;
+        state.waveform_data.max_time = 100;
+        state.set_window(0, 100);
+
+        // sig1 changes at 10 and 20, one time unit per column
+        state.set_primary_marker(13, 100);
+        assert_eq!(state.primary_marker, Some(13));
+        state.set_primary_marker(12, 100);
+        assert_eq!(state.primary_marker, Some(10));
+        state.set_primary_marker(18, 100);
+        assert_eq!(state.primary_marker, Some(20));
+
+        // Only the selected signal's transitions count
+        state.selected_signal = 1;
+        state.set_primary_marker(12, 100);
+        assert_eq!(state.primary_marker, Some(12));
+        state.set_primary_marker(17, 100);
+        assert_eq!(state.primary_marker, Some(15));
+    }
+
+    #[test]
+    fn test_waveform_stats_are_cached_until_invalidated() {
+        let mut state = create_test_state();
+        assert_eq!(state.waveform_stats().total_changes, 3);
+
+        // Changing the data in place keeps the cached statistics
+        state
+            .waveform_data
+            .values
+            .get_mut("sig2")
+            .unwrap()
+            .push((30, WaveValue::Binary(Value::V1)));
+        assert_eq!(state.waveform_stats().total_changes, 3);
+
+        state.invalidate_waveform_stats();
+        assert_eq!(state.waveform_stats().total_changes, 4);
+        assert_eq!(state.waveform_stats().signals["sig2"].last_change, Some(30));
+    }
+
+    #[test]
+    fn test_complete_signal_groups() {
+        let mut state = create_test_state();
+        state.signal_groups.insert(
+            "both".to_string(),
+            vec!["sig1".to_string(), "sig2".to_string()],
+        );
+        state
+            .signal_groups
+            .insert("first".to_string(), vec!["sig1".to_string()]);
+
+        state.fuzzy_finder_state.selected_signals = ["sig1".to_string()].into();
+        assert_eq!(state.complete_signal_groups(), vec!["first"]);
+
+        state
+            .fuzzy_finder_state
+            .selected_signals
+            .insert("sig2".to_string());
+        assert_eq!(state.complete_signal_groups(), vec!["both", "first"]);
+    }
+
+    #[test]
+    fn test_append_waveform_data_follows_end_of_view() {
+        use crate::parsers::types::WaveformData;
+
+        let appended = |time: u64| WaveformData {
+            signals: Vec::new(),
+            values: HashMap::from([(
+                "sig1".to_string(),
+                vec![(time, WaveValue::Binary(Value::V1))],
+            )]),
+            max_time: time,
+            timescale_fs: None,
+            widths: HashMap::new(),
+            window: None,
+            glitches: HashMap::new(),
+        };
+
+        // Showing the end of the waveform, the view moves along with the new data
+        let mut state = create_test_state();
+        state.set_window(20, 30);
+        state.append_waveform_data(appended(80));
+        assert_eq!(state.waveform_data.max_time, 80);
+        assert_eq!((state.time_start, state.time_range), (50, 30));
+
+        // Looking at earlier data, the view stays where it is
+        state.set_window(10, 30);
+        state.append_waveform_data(appended(100));
+        assert_eq!(state.waveform_data.values["sig1"].len(), 5);
+        assert_eq!((state.time_start, state.time_range), (10, 30));
+    }
+
+    #[test]
+    fn test_visible_signal_rows_with_heights() {
+        let mut state = AppState::new();
+        state.displayed_signals = ["a", "b", "c", "d"].map(String::from).to_vec();
+        state.signal_heights.insert("a".to_string(), 1);
+        state.signal_heights.insert("b".to_string(), 4);
+
+        // Only signals whose rows all fit are visible
+        assert_eq!(
+            state.visible_signal_rows(8),
+            vec![(0, 0, 1), (1, 1, 4), (2, 5, 2)]
+        );
+        assert_eq!(state.signal_at_row(4), Some(1));
+        assert_eq!(state.signal_at_row(7), Some(3));
+        assert_eq!(state.signal_at_row(9), None);
+
+        assert_eq!(state.scroll_offset_ending_at(3, 8), 1);
+        assert_eq!(state.scroll_offset_ending_at(3, 3), 3);
+        assert_eq!(state.max_signal_scroll_offset(8), 1);
+
+        state.signal_scroll_offset = 1;
+        assert_eq!(state.signal_at_row(0), Some(1));
+        assert_eq!(state.visible_signal_rows(3), vec![]);
+    }
+
+    #[test]
+    fn test_signal_filter_skips_rows() {
+        let mut state = AppState::new();
+        state.displayed_signals = ["top.clk", "top.data", "top.valid", "top.data_en"]
+            .map(String::from)
+            .to_vec();
+        state.selected_signal = 2;
+
+        // The selection moves to a listed signal, and the others take no rows
+        state.set_signal_filter("DATA");
+        assert_eq!(state.selected_signal, 3);
+        assert_eq!(state.visible_signal_rows(8), vec![(1, 0, 2), (3, 2, 2)]);
+        assert_eq!(state.signal_at_row(2), Some(3));
+        assert_eq!(state.previous_listed_signal(3), Some(1));
+        assert_eq!(state.next_listed_signal(1), Some(3));
+        assert_eq!(state.listed_position(3), 1);
+        assert_eq!(state.max_signal_scroll_offset(2), 2);
+
+        // Clearing it lists everything again and selects what was selected before
+        state.clear_signal_filter();
+        assert_eq!(state.selected_signal, 2);
+        assert_eq!(state.visible_signal_rows(8).len(), 4);
+
+        // Nothing is selected differently when nothing matches
+        state.set_signal_filter("reset");
+        assert_eq!(state.selected_signal, 2);
+        assert_eq!(state.visible_signal_rows(8), vec![]);
+        assert_eq!(state.max_signal_scroll_offset(2), 0);
+    }
+
+    #[test]
+    fn test_jump_list_back_forward_and_truncate() {
+        let mut state = AppState::new();
+        state.waveform_data.max_time = 1000;
+        state.displayed_signals = vec!["a".to_string(), "b".to_string()];
+        assert!(!state.jump_back());
+
+        state.set_window(0, 1000);
+        state.record_jump();
+        state.set_window(100, 50);
+        state.selected_signal = 1;
+        state.record_jump();
+        state.set_window(700, 20);
+
+        // Going back remembers where it came from, and going forward returns there exactly
+        assert!(state.jump_back());
+        assert_eq!((state.time_start, state.time_range), (100, 50));
+        assert!(state.jump_back());
+        assert_eq!((state.time_start, state.time_range), (0, 1000));
+        assert_eq!(state.selected_signal, 0);
+        assert!(!state.jump_back());
+        assert!(state.jump_forward());
+        assert!(state.jump_forward());
+        assert_eq!((state.time_start, state.time_range), (700, 20));
+        assert_eq!(state.selected_signal, 1);
+        assert!(!state.jump_forward());
+
+        // Jumping from the middle drops the places ahead
+        state.jump_back();
+        state.jump_back();
+        state.set_window(400, 10);
+        state.record_jump();
+        assert_eq!(state.jump_list.len(), 1);
+        assert_eq!(state.jump_list[0].time_start, 400);
+        assert!(!state.jump_forward());
+    }
+
+    #[test]
+    fn test_jump_list_skips_repeats_and_is_capped() {
+        let mut state = AppState::new();
+        state.waveform_data.max_time = 1000;
+        state.set_window(0, 1000);
+        state.record_jump();
+        state.record_jump();
+        assert_eq!(state.jump_list.len(), 1);
+
+        for start in 0..crate::constants::MAX_JUMPS as u64 + 10 {
+            state.set_window(start, 10);
+            state.record_jump();
+        }
+        assert_eq!(state.jump_list.len(), crate::constants::MAX_JUMPS);
+        assert_eq!(state.jump_list[0].time_start, 10);
+        assert_eq!(state.jump_index, crate::constants::MAX_JUMPS);
+    }
+
+    #[test]
+    fn test_trace_collapses_repeated_values_and_is_capped() {
+        let mut state = AppState::new();
+        state.waveform_data.values.insert(
+            "fsm".to_string(),
+            vec![
+                (0, WaveValue::Bus("1".into())),
+                (100, WaveValue::Bus("10".into())),
+            ],
+        );
+        // Moves before tracing starts aren't recorded
+        state.move_primary_marker(50);
+        state.trace = Some(crate::types::Trace {
+            signal: "fsm".to_string(),
+            entries: Vec::new(),
+            recording: true,
+        });
+
+        for time in [10, 20, 30, 150, 160, 40] {
+            state.move_primary_marker(time);
+        }
+        let entries: Vec<(u64, &str, usize)> = state
+            .trace
+            .as_ref()
+            .unwrap()
+            .entries
+            .iter()
+            .map(|entry| (entry.time, entry.value.as_str(), entry.count))
+            .collect();
+        assert_eq!(entries, [(10, "1", 3), (150, "16", 2), (40, "1", 1)]);
+
+        for time in 0..crate::constants::MAX_TRACE_ENTRIES as u64 {
+            state.move_primary_marker(if time % 2 == 0 { 0 } else { 100 });
+        }
+        let entries = &state.trace.as_ref().unwrap().entries;
+        assert_eq!(entries.len(), crate::constants::MAX_TRACE_ENTRIES);
+        // The oldest two went, and the first move at 0 counted towards the entry at 40
+        assert_eq!((entries[0].time, entries[0].count), (40, 2));
+    }
 }
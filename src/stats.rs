@@ -0,0 +1,381 @@
+use crate::{
+    parsers::types::{WaveValue, WaveformData},
+    types::Viewport,
+};
+use std::{collections::HashMap, mem};
+
+/// How many of the signals with the most changes the summary lists.
+pub const BUSIEST_SIGNAL_COUNT: usize = 10;
+
+/// Changes of one signal. A change is a value different from the one before it, so the initial
+/// value isn't counted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignalStats {
+    pub changes: usize,
+    pub first_change: Option<u64>,
+    pub last_change: Option<u64>,
+    /// Whether the signal is a single bit, so its changes are toggles
+    pub is_binary: bool,
+}
+
+impl SignalStats {
+    fn new(values: &[(u64, WaveValue)]) -> Self {
+        let change_times: Vec<u64> = values
+            .windows(2)
+            .filter(|pair| pair[0].1 != pair[1].1)
+            .map(|pair| pair[1].0)
+            .collect();
+
+        SignalStats {
+            changes: change_times.len(),
+            first_change: change_times.first().copied(),
+            last_change: change_times.last().copied(),
+            is_binary: matches!(values.first(), Some((_, WaveValue::Binary(_)))),
+        }
+    }
+
+    /// One line summary, with the toggle rate over `[0, max_time]` for binary signals.
+    pub fn describe(&self, signal: &str, max_time: u64) -> String {
+        let mut description = format!("{}: {} change(s)", signal, self.changes);
+        if let (Some(first), Some(last)) = (self.first_change, self.last_change) {
+            description.push_str(&format!(", first at {}, last at {}", first, last));
+        }
+        if self.is_binary && max_time > 0 {
+            description.push_str(&format!(
+                ", toggle rate {:.4} per time unit",
+                self.changes as f64 / max_time as f64
+            ));
+        }
+        description
+    }
+}
+
+/// Summary of the loaded waveform, computed once per load since it looks at every value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveformStats {
+    pub signal_count: usize,
+    pub total_changes: usize,
+    pub max_time: u64,
+    /// Rough size of the values and signal names in memory
+    pub memory_bytes: usize,
+    pub signals: HashMap<String, SignalStats>,
+    /// Changes of rows derived from the declared signals, like the bits of a split bus. They
+    /// aren't counted in the totals, which are of the file.
+    pub derived: HashMap<String, SignalStats>,
+}
+
+impl WaveformStats {
+    pub fn new(data: &WaveformData) -> Self {
+        let (signals, derived): (HashMap<String, SignalStats>, _) = data
+            .values
+            .iter()
+            .map(|(signal, values)| (signal.clone(), SignalStats::new(values)))
+            .partition(|(signal, _)| data.signals.contains(signal));
+
+        let memory_bytes = data
+            .values
+            .iter()
+            .filter(|(signal, _)| signals.contains_key(*signal))
+            .map(|(signal, values)| {
+                let bus_bytes: usize = values
+                    .iter()
+                    .map(|(_, value)| match value {
+                        WaveValue::Bus(bits) => bits.heap_bytes(),
+                        WaveValue::Binary(_) => 0,
+                    })
+                    .sum();
+                signal.capacity()
+                    + values.capacity() * mem::size_of::<(u64, WaveValue)>()
+                    + bus_bytes
+            })
+            .sum();
+
+        WaveformStats {
+            signal_count: data.signals.len(),
+            total_changes: signals.values().map(|stats| stats.changes).sum(),
+            max_time: data.max_time,
+            memory_bytes,
+            signals,
+            derived,
+        }
+    }
+
+    // Helper function to find the stats of a declared signal or a derived row
+    fn get(&self, signal: &str) -> Option<&SignalStats> {
+        self.signals
+            .get(signal)
+            .or_else(|| self.derived.get(signal))
+    }
+
+    /// Changes of `signal`, 0 for signals without any values.
+    pub fn changes(&self, signal: &str) -> usize {
+        self.get(signal).map_or(0, |stats| stats.changes)
+    }
+
+    /// Whether `signal` never changes from its initial value, like a signal only given a value in
+    /// `$dumpvars`.
+    pub fn is_constant(&self, signal: &str) -> bool {
+        self.changes(signal) == 0
+    }
+
+    /// The times of the first and last change of any of `signals`, if any of them changes.
+    pub fn activity_span(&self, signals: &[String]) -> Option<(u64, u64)> {
+        signals
+            .iter()
+            .filter_map(|signal| {
+                let stats = self.get(signal)?;
+                Some((stats.first_change?, stats.last_change?))
+            })
+            .reduce(|(start, end), (first, last)| (start.min(first), end.max(last)))
+    }
+
+    /// The signals with the most changes and their change counts, busiest first and by name for
+    /// equal counts.
+    pub fn busiest_signals(&self) -> Vec<(&str, usize)> {
+        let mut busiest: Vec<(&str, usize)> = self
+            .signals
+            .iter()
+            .map(|(signal, stats)| (signal.as_str(), stats.changes))
+            .collect();
+        busiest.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        busiest.truncate(BUSIEST_SIGNAL_COUNT);
+        busiest
+    }
+}
+
+/// Number of changes of `signals` at each column of `viewport`, placed like everything else drawn
+/// over it. Changes past its last column count towards that column.
+pub fn activity_density(
+    data: &WaveformData,
+    signals: &[String],
+    viewport: &Viewport,
+) -> Vec<usize> {
+    let mut density = vec![0; viewport.width as usize];
+    if viewport.width == 0 {
+        return density;
+    }
+
+    let last_column = viewport.width - 1;
+    for values in signals.iter().filter_map(|signal| data.values.get(signal)) {
+        for pair in values.windows(2).filter(|pair| pair[0].1 != pair[1].1) {
+            density[viewport.column_of(pair[1].0).min(last_column) as usize] += 1;
+        }
+    }
+    density
+}
+
+/// Formats a count briefly with a metric suffix, e.g. "950", "1.2k" or "3.4M".
+pub fn format_count(count: usize) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{:.1}k", count as f64 / 1e3),
+        _ => format!("{:.1}M", count as f64 / 1e6),
+    }
+}
+
+/// Formats a byte count with a binary unit, e.g. "1.5 MiB".
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{bit_split, parsers::types::Value};
+
+    fn test_data() -> WaveformData {
+        let clock = (0..=10)
+            .map(|i| {
+                let value = if i % 2 == 0 { Value::V0 } else { Value::V1 };
+                (i * 10, WaveValue::Binary(value))
+            })
+            .collect();
+        WaveformData {
+            signals: vec!["top.clk".to_string(), "top.data".to_string()],
+            values: HashMap::from([
+                ("top.clk".to_string(), clock),
+                (
+                    "top.data".to_string(),
+                    vec![
+                        (0, WaveValue::Bus("00".into())),
+                        (30, WaveValue::Bus("A5".into())),
+                        // Repeating a value isn't a change
+                        (40, WaveValue::Bus("A5".into())),
+                        (70, WaveValue::Bus("FF".into())),
+                    ],
+                ),
+            ]),
+            max_time: 100,
+            timescale_fs: None,
+            widths: HashMap::new(),
+            window: None,
+            glitches: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_activity_span() {
+        let mut data = test_data();
+        data.values.insert(
+            "top.rst".to_string(),
+            vec![(0, WaveValue::Binary(Value::V1))],
+        );
+        // Dumped again at 0 with a different value, a change at 0
+        data.values.insert(
+            "top.en".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::VX)),
+                (0, WaveValue::Binary(Value::V0)),
+            ],
+        );
+        let stats = WaveformStats::new(&data);
+        let span = |signals: &[&str]| {
+            let signals: Vec<String> = signals.iter().map(|signal| signal.to_string()).collect();
+            stats.activity_span(&signals)
+        };
+
+        // A constant signal adds nothing to the span
+        assert_eq!(span(&["top.data", "top.rst"]), Some((30, 70)));
+        assert_eq!(span(&["top.clk", "top.data", "top.rst"]), Some((10, 100)));
+        assert_eq!(span(&["top.rst", "top.missing"]), None);
+        assert_eq!(span(&[]), None);
+
+        // Signals that all change only at 0 span no time
+        assert_eq!(span(&["top.en", "top.rst"]), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_signal_description() {
+        let stats = WaveformStats::new(&test_data());
+
+        assert_eq!(
+            stats.signals["top.clk"].describe("top.clk", 100),
+            "top.clk: 10 change(s), first at 10, last at 100, toggle rate 0.1000 per time unit"
+        );
+        assert_eq!(
+            stats.signals["top.data"].describe("top.data", 100),
+            "top.data: 2 change(s), first at 30, last at 70"
+        );
+
+        let constant = SignalStats::new(&[(0, WaveValue::Binary(Value::V1))]);
+        assert_eq!(
+            constant.describe("top.rst", 100),
+            "top.rst: 0 change(s), toggle rate 0.0000 per time unit"
+        );
+    }
+
+    #[test]
+    fn test_summary() {
+        let stats = WaveformStats::new(&test_data());
+
+        assert_eq!(stats.signal_count, 2);
+        assert_eq!(stats.total_changes, 12);
+        assert_eq!(stats.max_time, 100);
+        assert!(stats.memory_bytes > 0);
+        assert_eq!(
+            stats.busiest_signals(),
+            vec![("top.clk", 10), ("top.data", 2)]
+        );
+    }
+
+    #[test]
+    fn test_split_bits_are_not_counted_as_signals() {
+        let mut data = test_data();
+        let whole = WaveformStats::new(&data);
+
+        // Split like `:split` does, into bit rows kept alongside the signals' values
+        let bits = bit_split::split_bits(&data.values["top.data"], 8);
+        for (i, values) in bits.into_iter().enumerate() {
+            data.values
+                .insert(bit_split::bit_signal_name("top.data", 7 - i), values);
+        }
+        let stats = WaveformStats::new(&data);
+
+        assert_eq!(stats.signal_count, whole.signal_count);
+        assert_eq!(stats.total_changes, whole.total_changes);
+        assert_eq!(stats.memory_bytes, whole.memory_bytes);
+        assert_eq!(stats.busiest_signals(), whole.busiest_signals());
+
+        // The bit rows still have changes of their own. 00 to A5 sets bits 7, 5, 2 and 0, and A5
+        // to FF the others.
+        for bit in 0..8 {
+            assert_eq!(stats.changes(&format!("top.data[{}]", bit)), 1);
+        }
+    }
+
+    #[test]
+    fn test_activity_density() {
+        let data = test_data();
+        let signals = vec!["top.clk".to_string(), "top.data".to_string()];
+
+        let viewport = |width| Viewport {
+            time_start: 0,
+            time_range: data.max_time + 1,
+            width,
+        };
+
+        // Changes are at their nearest column: 0..=12, 13..=37, 38..=62 and 63..=100, whose last
+        // column also takes the times past it
+        assert_eq!(
+            activity_density(&data, &signals, &viewport(4)),
+            vec![1, 3, 3, 5]
+        );
+        assert_eq!(
+            activity_density(&data, &signals[1..], &viewport(4)),
+            vec![0, 1, 0, 1]
+        );
+        assert!(activity_density(&data, &signals, &viewport(0)).is_empty());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn test_constant_signals() {
+        let mut data = test_data();
+        // Only given a value in $dumpvars
+        data.values.insert(
+            "top.rst".to_string(),
+            vec![(0, WaveValue::Binary(Value::V0))],
+        );
+        // Dumped again with the same value, which isn't a change
+        data.values.insert(
+            "top.mode".to_string(),
+            vec![
+                (0, WaveValue::Bus("3".into())),
+                (50, WaveValue::Bus("3".into())),
+            ],
+        );
+        let stats = WaveformStats::new(&data);
+
+        assert!(stats.is_constant("top.rst"));
+        assert!(stats.is_constant("top.mode"));
+        assert!(stats.is_constant("top.missing"));
+        assert!(!stats.is_constant("top.clk"));
+        assert!(!stats.is_constant("top.data"));
+        assert_eq!(stats.changes("top.data"), 2);
+    }
+
+    #[test]
+    fn test_format_count() {
+        assert_eq!(format_count(0), "0");
+        assert_eq!(format_count(950), "950");
+        assert_eq!(format_count(1234), "1.2k");
+        assert_eq!(format_count(3_400_000), "3.4M");
+    }
+}
@@ -0,0 +1,124 @@
+use crossterm::{
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use std::{backtrace::Backtrace, io, panic};
+
+/// Switches the terminal in and out of the mode the UI draws in.
+pub trait TerminalControl {
+    fn enter(&mut self) -> io::Result<()>;
+    fn leave(&mut self) -> io::Result<()>;
+}
+
+/// Raw mode on the alternate screen, with mouse capture, on stdout.
+pub struct Crossterm;
+
+impl TerminalControl for Crossterm {
+    fn enter(&mut self) -> io::Result<()> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)
+    }
+
+    fn leave(&mut self) -> io::Result<()> {
+        // Leave the screen even if raw mode can't be turned off, so the shell's output shows
+        let raw_mode = disable_raw_mode();
+        execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+        raw_mode
+    }
+}
+
+/// Keeps the terminal set up for the UI while it lives. It's restored when the guard is dropped,
+/// also when unwinding from a panic, unless `restore` already did.
+pub struct TerminalGuard<T: TerminalControl> {
+    control: T,
+    restored: bool,
+}
+
+impl<T: TerminalControl> TerminalGuard<T> {
+    pub fn new(mut control: T) -> io::Result<Self> {
+        control.enter()?;
+        Ok(TerminalGuard {
+            control,
+            restored: false,
+        })
+    }
+
+    /// Restores the terminal now, to see whether that worked. Does nothing the second time.
+    pub fn restore(&mut self) -> io::Result<()> {
+        if self.restored {
+            return Ok(());
+        }
+        self.restored = true;
+        self.control.leave()
+    }
+}
+
+impl<T: TerminalControl> Drop for TerminalGuard<T> {
+    fn drop(&mut self) {
+        // Errors can't be reported from here, and the terminal is as restored as it will get
+        let _ = self.restore();
+    }
+}
+
+/// Restores the terminal as soon as anything panics, before the panic message and a backtrace are
+/// printed, so they show on the normal screen and the shell still works afterwards.
+pub fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        let _ = Crossterm.leave();
+        eprintln!("{}", info);
+        eprintln!("{}", Backtrace::force_capture());
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    // Records what was done to the terminal instead of doing it
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<&'static str>>>);
+
+    impl Recorder {
+        fn calls(&self) -> Vec<&'static str> {
+            self.0.lock().unwrap().clone()
+        }
+    }
+
+    impl TerminalControl for Recorder {
+        fn enter(&mut self) -> io::Result<()> {
+            self.0.lock().unwrap().push("enter");
+            Ok(())
+        }
+
+        fn leave(&mut self) -> io::Result<()> {
+            self.0.lock().unwrap().push("leave");
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_guard_restores_terminal_when_panicking() {
+        let recorder = Recorder::default();
+        let result = panic::catch_unwind(|| {
+            let _guard = TerminalGuard::new(recorder.clone()).unwrap();
+            assert_eq!(recorder.calls(), ["enter"]);
+            panic!("a widget wrote outside its buffer");
+        });
+
+        assert!(result.is_err());
+        assert_eq!(recorder.calls(), ["enter", "leave"]);
+    }
+
+    #[test]
+    fn test_guard_restores_terminal_once() {
+        let recorder = Recorder::default();
+        let mut guard = TerminalGuard::new(recorder.clone()).unwrap();
+        guard.restore().unwrap();
+        guard.restore().unwrap();
+        drop(guard);
+
+        assert_eq!(recorder.calls(), ["enter", "leave"]);
+    }
+}
@@ -0,0 +1,79 @@
+use crate::parsers::types::parse_time;
+
+/// `time` as the user sees it. Times are stored absolute, but are shown relative to `time_zero`
+/// while `:timezero` sets one, with a minus sign before times that come earlier.
+pub fn format_time(time: u64, time_zero: Option<u64>) -> String {
+    match time_zero {
+        Some(zero) if time < zero => format!("-{}", zero - time),
+        Some(zero) => (time - zero).to_string(),
+        None => time.to_string(),
+    }
+}
+
+/// Absolute time of a time the user typed, in ticks like `500` or with a unit like `2ns` as for
+/// `parsers::types::parse_time`. It is relative to `time_zero` if set and may then be negative.
+/// Fails on times outside of 0 to `max_time`.
+pub fn parse_relative_time(
+    text: &str,
+    time_zero: Option<u64>,
+    timescale_fs: Option<u64>,
+    max_time: u64,
+) -> Result<u64, String> {
+    let (negative, magnitude) = match text.strip_prefix('-') {
+        Some(magnitude) => (true, magnitude),
+        None => (false, text),
+    };
+    let ticks = parse_time(magnitude, timescale_fs)?;
+
+    let zero = time_zero.unwrap_or(0);
+    let time = if negative {
+        zero.checked_sub(ticks)
+    } else {
+        zero.checked_add(ticks)
+    };
+    time.filter(|time| *time <= max_time).ok_or_else(|| {
+        format!(
+            "Time out of range ({}-{})",
+            format_time(0, time_zero),
+            format_time(max_time, time_zero)
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_relative_time() {
+        assert_eq!(format_time(120, None), "120");
+        assert_eq!(format_time(120, Some(100)), "20");
+        assert_eq!(format_time(70, Some(100)), "-30");
+        assert_eq!(format_time(100, Some(100)), "0");
+    }
+
+    #[test]
+    fn test_parse_relative_time() {
+        let parse = |text| parse_relative_time(text, Some(100), Some(1_000_000), 1000);
+        assert_eq!(parse_relative_time("20", None, None, 1000), Ok(20));
+        assert_eq!(parse("-30"), Ok(70));
+        assert_eq!(parse("30"), Ok(130));
+        assert_eq!(
+            parse_relative_time("-30", None, None, 1000),
+            Err("Time out of range (0-1000)".to_string())
+        );
+        assert_eq!(
+            parse("-130"),
+            Err("Time out of range (-100-900)".to_string())
+        );
+
+        // Units are converted with the timescale, here 1ns
+        assert_eq!(parse("1ns"), Ok(101));
+        assert_eq!(parse("-0.05us"), Ok(50));
+        assert_eq!(
+            parse_relative_time("1ns", None, None, 1000),
+            Err("The waveform has no timescale, so give the time without a unit".to_string())
+        );
+        assert_eq!(parse("ns"), Err("Invalid time: ns".to_string()));
+    }
+}
@@ -1,10 +1,200 @@
+//! Plain data kept in [`AppState`](crate::state::AppState).
+
+use crate::parsers::types::{FileHeader, WaveformData};
+use std::{collections::HashMap, path::PathBuf, time::Duration};
+
 #[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
 pub enum AppMode {
     #[default]
     Normal,
     Command,
     FuzzyFinder,
+    FileBrowser,
+    /// Typing the signal list filter after `/`
+    Filter,
+}
+
+/// What a click-and-drag in the waveform does when the mouse button is released.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub enum DragKind {
+    /// Zoom the view to the dragged selection
+    #[default]
+    Zoom,
+    /// Place the primary marker at the drag start and the secondary marker at the drag end
+    Measure,
+    /// Prompt for the name of a region spanning the dragged times
+    Region,
+}
+
+/// A time window of `time_range` time units from `time_start` drawn over `width` columns. Times
+/// and columns are mapped to each other by rounding to the nearest, so the column picked for a
+/// time is the one the time is drawn at, and `column_of(time_of(column)) == column` whenever a
+/// column spans at least one time unit. Zoomed in further, neighbouring columns share a time.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Viewport {
+    pub time_start: u64,
+    pub time_range: u64,
+    pub width: u16,
+}
+
+impl Viewport {
+    /// The column `time` is drawn at, from 0 at `time_start` to `width` at the end of the window.
+    /// Earlier times are at column 0, and later ones past `width`.
+    pub fn column_of(&self, time: u64) -> u16 {
+        if self.time_range == 0 {
+            return 0;
+        }
+        let offset = time.saturating_sub(self.time_start) as u128;
+        let (range, width) = (self.time_range as u128, self.width as u128);
+        ((2 * offset * width + range) / (2 * range)).min(u16::MAX as u128) as u16
+    }
+
+    /// The time drawn at `column`, which is where a click at the column places a marker.
+    pub fn time_of(&self, column: u16) -> u64 {
+        if self.width == 0 {
+            return self.time_start;
+        }
+        let (range, width) = (self.time_range as u128, self.width as u128);
+        let offset = (2 * column as u128 * range + width) / (2 * width);
+        self.time_start.saturating_add(offset as u64)
+    }
+}
+
+/// The result of comparing two signals, highlighted in the waveform until cleared.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Comparison {
+    pub signal_a: String,
+    pub signal_b: String,
+    /// Half-open `[start, end)` time intervals in which the two signals differ, in time order
+    pub mismatches: Vec<(u64, u64)>,
+}
+
+/// Second time window over the same signals, drawn in a pane of its own while the waveform is split
+/// with `:split-view`. The focused pane always shows `time_start`/`time_range` of the state, so
+/// navigation and zooming only ever move the focused pane.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct SplitView {
+    /// Window of the pane without focus
+    pub time_start: u64,
+    pub time_range: u64,
+    /// Zoom histories of the pane without focus
+    pub zoom_history: Vec<(u64, u64)>,
+    pub zoom_forward_history: Vec<(u64, u64)>,
+    /// Whether the bottom pane has focus
+    pub bottom_focused: bool,
+}
+
+/// A second waveform file loaded with `:diff`, drawn under the matching signals of the loaded one.
+#[derive(Default, Debug, Clone)]
+pub struct Diff {
+    pub path: PathBuf,
+    pub data: WaveformData,
+    /// Half-open `[start, end)` time intervals in which each signal found in both files differs
+    /// between them, in time order
+    pub mismatches: HashMap<String, Vec<(u64, u64)>>,
+}
+
+/// A single value change listed in the transitions popup, with values already formatted for display.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Transition {
+    pub time: u64,
+    /// The value before this change, if the signal had one
+    pub old_value: Option<String>,
+    pub new_value: String,
+    /// Time since the previous change of the signal, if there was one
+    pub delta: Option<u64>,
+}
+
+/// State of the popup listing the transitions of a signal.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct TransitionsPopup {
+    pub signal: String,
+    pub transitions: Vec<Transition>,
+    /// Index of the highlighted row in `transitions`
+    pub selected: usize,
+    /// Index of the first row shown
+    pub scroll: usize,
 }
+
+/// A time at which a signal changed to a searched value.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct ValueMatch {
+    pub signal: String,
+    pub time: u64,
+}
+
+/// State of the popup listing where displayed signals hold a searched value.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct GrepPopup {
+    /// The value as it was searched for
+    pub query: String,
+    pub matches: Vec<ValueMatch>,
+    /// Whether there were more matches than `ui.grep_match_limit`, which were left out
+    pub truncated: bool,
+    /// Index of the highlighted row in `matches`
+    pub selected: usize,
+    /// Index of the first row shown
+    pub scroll: usize,
+}
+
+/// State of the popup listing the value of every displayed signal at one time.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct SamplePopup {
+    pub time: u64,
+    /// The `name : value` lines, already aligned
+    pub lines: Vec<String>,
+    /// Index of the first line shown
+    pub scroll: usize,
+}
+
+/// Values of a signal at the places the primary marker was moved to, started with `:trace`.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Trace {
+    pub signal: String,
+    /// Oldest first, with a value the marker stayed on for several moves in a single entry
+    pub entries: Vec<TraceEntry>,
+    /// Whether marker moves are still added, until `:trace off`
+    pub recording: bool,
+}
+
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct TraceEntry {
+    /// Where the marker first found the value
+    pub time: u64,
+    pub value: String,
+    /// How many moves in a row found the value
+    pub count: usize,
+}
+
+/// Narrows the signal list to the displayed signals whose names contain `query`, typed after `/`.
+/// The others stay displayed, but are skipped in the list and the waveform until it's cleared.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct SignalFilter {
+    pub query: String,
+    /// The signal selected before filtering, selected again when the filter is cleared
+    pub selected_before: Option<String>,
+}
+
+impl SignalFilter {
+    /// Whether `signal` is listed, ignoring case.
+    pub fn matches(&self, signal: &str) -> bool {
+        signal.to_lowercase().contains(&self.query.to_lowercase())
+    }
+}
+
+/// What is known about the loaded waveform, shown with `:fileinfo`.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct FileInfo {
+    /// Size of the file in bytes, unless it was read from stdin
+    pub size: Option<u64>,
+    /// How long reading and parsing the file took
+    pub parse_duration: Duration,
+    pub signal_count: usize,
+    /// Value changes of every signal, counting their initial values
+    pub change_count: usize,
+    pub header: FileHeader,
+}
+
 #[derive(Default, Debug, Clone, Eq, PartialEq)]
 pub struct Marker {
     pub time: u64,
@@ -12,6 +202,55 @@ pub struct Marker {
     pub color: ratatui::style::Color,
 }
 
+/// A named time interval, highlighted across all signals.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Region {
+    pub start: u64,
+    pub end: u64,
+    pub name: String,
+    pub color: ratatui::style::Color,
+}
+
+/// A place the view jumped from, returned to with the jump list.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Jump {
+    pub time_start: u64,
+    pub time_range: u64,
+    /// The selected signal, by name so it survives signals being moved or hidden
+    pub selected_signal: Option<String>,
+}
+
+/// The whole view saved in a numbered slot with `m1`..`m9` or `:view save`, recalled exactly as it
+/// was with `'1`..`'9` or `:view load`.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct ViewBookmark {
+    pub time_start: u64,
+    pub time_range: u64,
+    /// The selected signal, by name like in a `Jump`
+    pub selected_signal: Option<String>,
+    pub signal_scroll_offset: usize,
+}
+
+/// A value of a signal tinted with `:highlight` wherever the signal holds it.
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct Highlight {
+    pub signal: String,
+    /// The value as returned by `WaveValue::normalized_hex`
+    pub value: String,
+    pub color: ratatui::style::Color,
+}
+
+impl Region {
+    pub fn new(start: u64, end: u64, name: String) -> Self {
+        Self {
+            start,
+            end,
+            name,
+            color: crate::constants::DEFAULT_REGION_COLOR,
+        }
+    }
+}
+
 impl Marker {
     pub fn new(time: u64, name: String) -> Self {
         Self {
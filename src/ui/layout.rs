@@ -1,40 +1,132 @@
-use crate::config;
+use crate::{
+    config,
+    constants::{MIN_SIGNAL_LIST_COLUMNS, MIN_WAVEFORM_COLUMNS, MIN_WAVEFORM_ROWS},
+    types::SplitView,
+};
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
+/// While the waveform is split into two panes, `signal_list`, `time_ruler` and `waveform` are the
+/// areas of the focused pane, and `other_pane` has those of the other one.
 #[derive(Default)]
 pub struct AppLayout {
     pub marker_names: Rect,
     pub signal_list: Rect,
     pub time_ruler: Rect,
+    pub minimap: Rect,
     pub waveform: Rect,
+    pub command_result: Rect,
     pub command_bar: Rect,
+    pub other_pane: Option<PaneLayout>,
 }
 
-pub fn create_layout(area: Rect, config: &config::AppConfig) -> AppLayout {
+/// Areas of one pane of the split waveform.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PaneLayout {
+    pub signal_list: Rect,
+    pub time_ruler: Rect,
+    pub waveform: Rect,
+}
+
+/// Rows of the command bar, including its top border.
+const COMMAND_BAR_HEIGHT: u16 = 3;
+
+impl AppLayout {
+    /// Whether the signal list and the waveform are big enough to draw, without squeezing the rows
+    /// around them. Smaller terminals get a placeholder instead of the UI.
+    pub fn is_usable(&self) -> bool {
+        self.command_bar.height == COMMAND_BAR_HEIGHT
+            && self.time_ruler.height == 1
+            && self.marker_names.height == 1
+            && self.signal_list.width >= MIN_SIGNAL_LIST_COLUMNS
+            && self.waveform.width >= MIN_WAVEFORM_COLUMNS
+            && self.waveform.height >= MIN_WAVEFORM_ROWS
+            && self.other_pane.is_none_or(|pane| {
+                pane.time_ruler.height == 1 && pane.waveform.height >= MIN_WAVEFORM_ROWS
+            })
+    }
+}
+
+/// The smallest terminal size, as (width, height), whose layout is usable.
+pub fn minimum_size(
+    config: &config::AppConfig,
+    result_lines: u16,
+    split_view: Option<&SplitView>,
+) -> (u16, u16) {
+    // Far bigger than any minimum, so that only one dimension limits the layout at a time
+    const LARGE: u16 = 1000;
+    let usable = |width, height| {
+        create_layout(
+            Rect::new(0, 0, width, height),
+            config,
+            result_lines,
+            split_view,
+        )
+        .is_usable()
+    };
+    let width = (1..LARGE).find(|&width| usable(width, LARGE));
+    let height = (1..LARGE).find(|&height| usable(LARGE, height));
+    (width.unwrap_or(LARGE), height.unwrap_or(LARGE))
+}
+
+/// Lays out the UI in `area`, leaving `result_lines` rows above the command bar for the command
+/// result message. A split waveform stacks two equally tall panes, the lower one under a time ruler
+/// of its own.
+pub fn create_layout(
+    area: Rect,
+    config: &config::AppConfig,
+    result_lines: u16,
+    split_view: Option<&SplitView>,
+) -> AppLayout {
     let main_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Min(1),    // Content area
-                Constraint::Length(3), // Command bar
+                Constraint::Min(1),                     // Content area
+                Constraint::Length(result_lines),       // Command result
+                Constraint::Length(COMMAND_BAR_HEIGHT), // Command bar
             ]
             .as_ref(),
         )
         .split(area);
     let remainder = main_chunks[0];
-    let command_bar = main_chunks[1];
+    let command_result = main_chunks[1];
+    let command_bar = main_chunks[2];
 
     let remainder = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Time ruler height
-            Constraint::Length(1), // Marker names area
-            Constraint::Min(5),    // Waveform area
+            Constraint::Length(1),                             // Time ruler height
+            Constraint::Length(config.ui.show_minimap as u16), // Minimap, if enabled
+            Constraint::Length(1),                             // Marker names area
+            Constraint::Min(5),                                // Waveform area
         ])
         .split(remainder);
     let time_ruler = remainder[0];
-    let marker_names = remainder[1];
-    let remainder = remainder[2];
+    let minimap = remainder[1];
+    let marker_names = remainder[2];
+    let mut remainder = remainder[3];
+
+    let mut bottom_pane = None;
+    if split_view.is_some() {
+        let pane_height = remainder.height.saturating_sub(1) / 2;
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(pane_height), // Top pane
+                Constraint::Length(1),           // Time ruler of the bottom pane
+                Constraint::Length(pane_height), // Bottom pane
+                Constraint::Min(0),              // Row left over by an odd height
+            ])
+            .split(remainder);
+        remainder = rows[0];
+
+        let [signal_list, waveform] = split_columns(rows[2], config);
+        bottom_pane = Some(PaneLayout {
+            signal_list,
+            time_ruler: split_columns(rows[1], config)[1],
+            waveform,
+        });
+    }
 
     let remainder = Layout::default()
         .direction(Direction::Horizontal)
@@ -50,34 +142,42 @@ pub fn create_layout(area: Rect, config: &config::AppConfig) -> AppLayout {
     let waveform = remainder[1];
 
     // Leave a gap above signal list
-    let time_ruler = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(
-            [
-                Constraint::Percentage(config.ui.signal_list_width),
-                Constraint::Percentage(100 - config.ui.signal_list_width),
-            ]
-            .as_ref(),
-        )
-        .split(time_ruler)[1];
-
-    // Leave a gap above signal list
-    let marker_names = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints(
-            [
-                Constraint::Percentage(config.ui.signal_list_width),
-                Constraint::Percentage(100 - config.ui.signal_list_width),
-            ]
-            .as_ref(),
-        )
-        .split(marker_names)[1];
+    let time_ruler = split_columns(time_ruler, config)[1];
+    let minimap = split_columns(minimap, config)[1];
+    let marker_names = split_columns(marker_names, config)[1];
 
-    AppLayout {
-        marker_names,
+    let top_pane = PaneLayout {
         signal_list,
         time_ruler,
         waveform,
+    };
+    let (focused_pane, other_pane) = match bottom_pane {
+        Some(bottom_pane) if split_view.is_some_and(|split_view| split_view.bottom_focused) => {
+            (bottom_pane, Some(top_pane))
+        }
+        bottom_pane => (top_pane, bottom_pane),
+    };
+
+    AppLayout {
+        marker_names,
+        signal_list: focused_pane.signal_list,
+        time_ruler: focused_pane.time_ruler,
+        minimap,
+        waveform: focused_pane.waveform,
+        command_result,
         command_bar,
+        other_pane,
     }
 }
+
+// Helper function to divide `area` into the signal list's columns and the waveform's columns
+fn split_columns(area: Rect, config: &config::AppConfig) -> [Rect; 2] {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(config.ui.signal_list_width),
+            Constraint::Percentage(100 - config.ui.signal_list_width),
+        ])
+        .split(area);
+    [columns[0], columns[1]]
+}
@@ -0,0 +1,214 @@
+// digisurf/src/ui/theme.rs
+use crate::config::ColorMode;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Modifier, Style},
+};
+
+/// The highlight color when clicking and dragging on the waveform.
+const DRAG_COLOR: Color = Color::Rgb(100, 150, 255);
+
+/// The background color of time intervals in which two compared signals differ.
+const MISMATCH_COLOR: Color = Color::Rgb(120, 20, 20);
+
+/// The ANSI colors and the RGB values terminals commonly show them as, to pick the closest one to
+/// an RGB color on terminals with only 16 colors.
+const ANSI16_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (128, 0, 0)),
+    (Color::Green, (0, 128, 0)),
+    (Color::Yellow, (128, 128, 0)),
+    (Color::Blue, (0, 0, 128)),
+    (Color::Magenta, (128, 0, 128)),
+    (Color::Cyan, (0, 128, 128)),
+    (Color::Gray, (192, 192, 192)),
+    (Color::DarkGray, (128, 128, 128)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (0, 0, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Styles of the parts of the UI that are told apart by color, for the colors the terminal can
+/// show. Without colors, text attributes like reversed cells tell them apart instead.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Theme {
+    pub mode: ColorMode,
+}
+
+impl Theme {
+    pub fn new(mode: ColorMode) -> Self {
+        Theme { mode }
+    }
+
+    pub fn is_mono(&self) -> bool {
+        self.mode == ColorMode::Mono
+    }
+
+    /// A color as the terminal can show it. RGB colors become the closest ANSI color on 16 color
+    /// terminals.
+    pub fn color(&self, color: Color) -> Color {
+        match (self.mode, color) {
+            (ColorMode::Ansi16, Color::Rgb(r, g, b)) => closest_ansi16(r, g, b),
+            _ => color,
+        }
+    }
+
+    /// The selected signal, in the signal list and the waveform.
+    pub fn selected(&self) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default().add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+            _ => Style::default().fg(Color::Yellow),
+        }
+    }
+
+    /// The highlighted row of a list.
+    pub fn highlight(&self) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD),
+            _ => Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+
+    /// The area selected by dragging on the waveform.
+    pub fn drag(&self) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default().add_modifier(Modifier::REVERSED),
+            ColorMode::Ansi16 => Style::default().bg(Color::Blue),
+            ColorMode::Full => Style::default().bg(DRAG_COLOR),
+        }
+    }
+
+    /// Time intervals in which two compared signals differ.
+    pub fn mismatch(&self) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default().add_modifier(Modifier::UNDERLINED),
+            ColorMode::Ansi16 => Style::default().bg(Color::Red),
+            ColorMode::Full => Style::default().bg(MISMATCH_COLOR),
+        }
+    }
+
+    /// Time intervals of a region tinted with `color`. Without colors regions are only shown by
+    /// their names above the waveform.
+    pub fn region(&self, color: Color) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default(),
+            _ => Style::default().bg(self.color(color)),
+        }
+    }
+
+    /// Runs of a signal holding a value highlighted with `:highlight` in `color`.
+    pub fn value_highlight(&self, color: Color) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default().add_modifier(Modifier::REVERSED),
+            _ => Style::default().bg(self.color(color)),
+        }
+    }
+
+    /// Text drawn over the waveform, like the value under the mouse.
+    pub fn readout(&self) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default().add_modifier(Modifier::REVERSED),
+            _ => Style::default().fg(Color::Black).bg(Color::Gray),
+        }
+    }
+
+    /// Signals that never change, and other things of little interest.
+    pub fn dimmed(&self) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default().add_modifier(Modifier::DIM),
+            _ => Style::default().fg(Color::DarkGray),
+        }
+    }
+
+    /// Background of the time ruler and the minimap.
+    pub fn ruler(&self) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default(),
+            _ => Style::default().bg(Color::DarkGray),
+        }
+    }
+
+    /// The part of the minimap covering the visible window.
+    pub fn minimap_window(&self) -> Style {
+        match self.mode {
+            ColorMode::Mono => Style::default().add_modifier(Modifier::REVERSED),
+            _ => Style::default().bg(Color::Blue),
+        }
+    }
+
+    /// Drops every color from `area` of `buf` if the terminal shouldn't show colors, leaving the
+    /// text attributes that tell things apart without them.
+    pub fn strip_colors(&self, area: Rect, buf: &mut Buffer) {
+        if !self.is_mono() {
+            return;
+        }
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf[(x, y)].set_fg(Color::Reset).set_bg(Color::Reset);
+            }
+        }
+    }
+}
+
+// The ANSI color closest to an RGB color
+fn closest_ansi16(r: u8, g: u8, b: u8) -> Color {
+    let distance = |(ar, ag, ab): (u8, u8, u8)| {
+        let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+        channel(ar, r) + channel(ag, g) + channel(ab, b)
+    };
+    ANSI16_COLORS
+        .iter()
+        .min_by_key(|(_, rgb)| distance(*rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ansi16_replaces_rgb_colors() {
+        let theme = Theme::new(ColorMode::Ansi16);
+        assert_eq!(theme.drag().bg, Some(Color::Blue));
+        assert_eq!(theme.mismatch().bg, Some(Color::Red));
+        assert_eq!(theme.region(Color::Rgb(40, 70, 40)).bg, Some(Color::Green));
+        assert_eq!(theme.color(Color::Rgb(250, 250, 10)), Color::LightYellow);
+        assert_eq!(theme.color(Color::Cyan), Color::Cyan);
+
+        let theme = Theme::new(ColorMode::Full);
+        assert_eq!(theme.drag().bg, Some(DRAG_COLOR));
+    }
+
+    #[test]
+    fn test_mono_uses_text_attributes() {
+        let theme = Theme::new(ColorMode::Mono);
+        for style in [
+            theme.selected(),
+            theme.highlight(),
+            theme.drag(),
+            theme.mismatch(),
+            theme.readout(),
+            theme.minimap_window(),
+        ] {
+            assert_eq!((style.fg, style.bg), (None, None));
+            assert!(!style.add_modifier.is_empty());
+        }
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buf[(0, 0)].set_fg(Color::Red).set_bg(Color::Blue);
+        theme.strip_colors(buf.area, &mut buf);
+        assert_eq!(
+            (buf[(0, 0)].fg, buf[(0, 0)].bg),
+            (Color::Reset, Color::Reset)
+        );
+    }
+}
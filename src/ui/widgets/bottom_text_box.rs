@@ -1,8 +1,11 @@
-use crate::{command_mode::CommandModeStateAccess, state::AppState, types::AppMode};
+use crate::{
+    command_mode::CommandModeStateAccess, state::AppState, time_format::format_time, types::AppMode,
+};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Paragraph, StatefulWidget, Widget},
 };
 
@@ -14,19 +17,32 @@ impl StatefulWidget for BottomTextBoxWidget {
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let block = Block::default()
-            .title(format!("{:?}", state.mode))
+            .title(title(state, area.width as usize))
             .borders(Borders::TOP);
         let inner_area = block.inner(area);
         block.render(area, buf);
 
-        // Command text to display
-        let command_text = if state.mode == AppMode::Command {
-            format!(":{}", state.command_state().input_buffer)
-        } else {
-            " ':' for command mode. :q, then <Enter> to quit. :h then <Enter> for help.".to_string()
+        // Command text to display, and where the cursor is in it while typing
+        let (command_text, cursor) = match state.mode {
+            AppMode::Command => (
+                format!(":{}", state.command_state().input_buffer),
+                Some(state.command_state().cursor_position),
+            ),
+            AppMode::Filter => {
+                let query = state
+                    .signal_filter
+                    .as_ref()
+                    .map_or("", |filter| filter.query.as_str());
+                (format!("/{}", query), Some(query.chars().count()))
+            }
+            _ => (
+                " ':' for command mode. :q, then <Enter> to quit. :h then <Enter> for help."
+                    .to_string(),
+                None,
+            ),
         };
 
-        let style = if state.mode == AppMode::Command {
+        let style = if cursor.is_some() {
             Style::default().fg(Color::Yellow)
         } else {
             Style::default().fg(Color::DarkGray)
@@ -36,17 +52,214 @@ impl StatefulWidget for BottomTextBoxWidget {
             .style(style)
             .render(inner_area, buf);
 
-        // In command mode, render cursor
-        if state.mode == AppMode::Command {
+        // While typing, render cursor
+        if let Some(cursor) = cursor {
             // Position cursor at cursor_position, not just at the end
-            let cursor_x = inner_area.x + 1 + state.command_state().cursor_position as u16;
+            let cursor_x = inner_area.x + 1 + cursor as u16;
             let cursor_y = inner_area.y;
 
             if cursor_x < inner_area.right() && cursor_y < buf.area().height {
                 let cell = &mut buf[(cursor_x, cursor_y)];
-                cell.set_bg(cell.fg);
-                cell.set_fg(Color::Black);
+                if state.theme().is_mono() {
+                    cell.modifier.insert(Modifier::REVERSED);
+                } else {
+                    cell.set_bg(cell.fg);
+                    cell.set_fg(Color::Black);
+                }
             }
         }
     }
 }
+
+// Title of the command bar: the loaded file's name, with the times loaded if not all were, the
+// mode, then the marker times in the markers' colors, then the tracked signal's values at the
+// markers. The values and then the times are dropped when they don't fit in `width`, and then the
+// file name is shortened.
+fn title(state: &AppState, width: usize) -> Line<'static> {
+    let mode = Span::raw(format!("{:?}", state.mode));
+    let file_name = state
+        .file_path
+        .as_ref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string());
+    // Only part of the file was loaded with --from and --to
+    let file_name = match (file_name, state.waveform_data.window) {
+        (Some(name), Some((from, to))) => Some(format!("{} [{}-{}]", name, from, to)),
+        (None, Some((from, to))) => Some(format!("[{}-{}]", from, to)),
+        (name, None) => name,
+    };
+    let parts: Vec<Vec<Span<'static>>> = [
+        Some(vec![mode.clone()]),
+        marker_times(state),
+        tracked_values(state).map(|text| vec![Span::raw(text)]),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    let join = |name: Option<String>, parts: &[Vec<Span<'static>>]| {
+        let mut spans = Vec::new();
+        if let Some(name) = name {
+            spans.push(Span::raw(name));
+            spans.push(Span::raw(" | "));
+        }
+        for (i, part) in parts.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(" | "));
+            }
+            spans.extend(part.iter().cloned());
+        }
+        Line::from(spans)
+    };
+
+    if let Some(title) = (1..=parts.len())
+        .rev()
+        .map(|count| join(file_name.clone(), &parts[..count]))
+        .find(|title| title.width() <= width)
+    {
+        return title;
+    }
+
+    // Keep the start of the file name, with room for the separator and an ellipsis
+    let room = width.saturating_sub(mode.width() + " | ".len());
+    match file_name {
+        Some(name) if room >= 2 => {
+            let shortened: String = name.chars().take(room - 1).collect();
+            join(Some(format!("{}…", shortened)), &parts[..1])
+        }
+        _ => Line::from(mode),
+    }
+}
+
+// Helper function to show the set markers' times, and the time between them if both are set
+fn marker_times(state: &AppState) -> Option<Vec<Span<'static>>> {
+    let primary = |m1: u64| {
+        Span::styled(
+            format!("M1={}", format_time(m1, state.time_zero)),
+            Style::default().fg(state.primary_marker_color()),
+        )
+    };
+    let secondary = |m2: u64| {
+        Span::styled(
+            format!("M2={}", format_time(m2, state.time_zero)),
+            Style::default().fg(state.secondary_marker_color()),
+        )
+    };
+
+    match (state.primary_marker, state.secondary_marker) {
+        (Some(m1), Some(m2)) => Some(vec![
+            primary(m1),
+            Span::raw(" "),
+            secondary(m2),
+            Span::raw(format!(" Δ={}", m1.abs_diff(m2))),
+        ]),
+        (Some(m1), None) => Some(vec![primary(m1)]),
+        (None, Some(m2)) => Some(vec![secondary(m2)]),
+        (None, None) => None,
+    }
+}
+
+// Helper function to show the tracked signal's value at each set marker, `-` before its first
+// change
+fn tracked_values(state: &AppState) -> Option<String> {
+    let signal = state.tracked_signal.as_ref()?;
+    let value_at = |time: u64| {
+        state
+            .get_value_at_marker(signal, time)
+            .map_or("-".to_string(), |value| value.to_string())
+    };
+
+    let mut text = signal.clone();
+    if let Some(m1) = state.primary_marker {
+        text.push_str(&format!("@M1={}", value_at(m1)));
+    }
+    if let Some(m2) = state.secondary_marker {
+        let separator = if state.primary_marker.is_some() {
+            " "
+        } else {
+            ""
+        };
+        text.push_str(&format!("{}@M2={}", separator, value_at(m2)));
+    }
+    Some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::WaveValue;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn tracking_state() -> AppState {
+        let mut state = AppState::default();
+        state.waveform_data.values.insert(
+            "top.data".to_string(),
+            vec![(100, WaveValue::Bus("5".into()))],
+        );
+        state.tracked_signal = Some("top.data".to_string());
+        state.primary_marker = Some(50);
+        state.secondary_marker = Some(150);
+        state
+    }
+
+    #[test]
+    fn test_title_tracked_values() {
+        let state = tracking_state();
+        assert_eq!(
+            title(&state, 80).to_string(),
+            "Normal | M1=50 M2=150 Δ=100 | top.data@M1=- @M2=5"
+        );
+
+        // Tracking doesn't depend on the signal being displayed
+        assert!(state.displayed_signals.is_empty());
+
+        let mut state = tracking_state();
+        state.primary_marker = None;
+        assert_eq!(
+            title(&state, 80).to_string(),
+            "Normal | M2=150 | top.data@M2=5"
+        );
+    }
+
+    #[test]
+    fn test_title_drops_values_before_times() {
+        let state = tracking_state();
+        assert_eq!(title(&state, 30).to_string(), "Normal | M1=50 M2=150 Δ=100");
+        assert_eq!(title(&state, 10).to_string(), "Normal");
+    }
+
+    #[test]
+    fn test_title_starts_with_file_name() {
+        let mut state = tracking_state();
+        state.file_path = Some("/tmp/waves/top.vcd".into());
+        assert_eq!(
+            title(&state, 80).to_string(),
+            "top.vcd | Normal | M1=50 M2=150 Δ=100 | top.data@M1=- @M2=5"
+        );
+        // Times go before the file name does
+        assert_eq!(title(&state, 20).to_string(), "top.vcd | Normal");
+        assert_eq!(title(&state, 15).to_string(), "top.v… | Normal");
+        assert_eq!(title(&state, 10).to_string(), "Normal");
+
+        state.waveform_data.window = Some((100, 400));
+        assert_eq!(title(&state, 30).to_string(), "top.vcd [100-400] | Normal");
+    }
+
+    #[test]
+    fn test_title_shortens_long_file_name() {
+        let mut state = AppState::default();
+        state.file_path = Some("/tmp/a_very_long_waveform_name_from_regression_run_42.vcd".into());
+        state.primary_marker = Some(1200);
+
+        let backend = TestBackend::new(40, 2);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| {
+                BottomTextBoxWidget::default().render(f.area(), f.buffer_mut(), &mut state);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+}
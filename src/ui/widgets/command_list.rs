@@ -0,0 +1,54 @@
+use crate::{command_mode::registry::Command, state::AppState};
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+use std::rc::Rc;
+
+/// Popup listing the registered commands, built from the command registry each time it is drawn.
+pub struct CommandListWidget<'a> {
+    pub commands: Vec<&'a Rc<Box<dyn Command<AppState>>>>,
+}
+
+impl StatefulWidget for CommandListWidget<'_> {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        // Calculate a centered rectangle just big enough for the list
+        let popup_width = area.width.min(80);
+        let popup_height = area.height.min(self.commands.len() as u16 + 3);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title("Commands [Esc to close]")
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Limit scrolling so the last command stays at the bottom
+        let visible_rows = (inner_area.height as usize).saturating_sub(1);
+        let max_scroll = self.commands.len().saturating_sub(visible_rows);
+        let scroll = state.command_list_scroll.min(max_scroll);
+
+        let mut lines = vec![Line::styled(
+            format!("{:<12} {:<10} {}", "Name", "Aliases", "Description"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        for command in self.commands.iter().skip(scroll).take(visible_rows) {
+            lines.push(Line::raw(format!(
+                "{:<12} {:<10} {}",
+                command.name(),
+                command.aliases().join(", "),
+                command.description()
+            )));
+        }
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines).render(inner_area, buf);
+        state.command_list_scroll = scroll;
+    }
+}
@@ -0,0 +1,75 @@
+use crate::state::AppState;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct FileBrowserWidget {}
+
+impl StatefulWidget for FileBrowserWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let browser = &state.file_browser;
+
+        // Calculate a centered rectangle for the popup
+        let popup_width = area.width.min(70);
+        let popup_height = area.height.min(20);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title("Open file [Enter to open, / to type a path, Esc to close]")
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        let header = Style::default().add_modifier(Modifier::BOLD);
+        let mut lines = Vec::new();
+        if let Some(input) = &browser.path_input {
+            lines.push(Line::styled(
+                format!("Path: {}", input),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+
+        // Rows of the listing, with the index of the row of the selected entry
+        let mut rows = vec![Line::styled(
+            format!("Files in {}", browser.dir.display()),
+            header,
+        )];
+        let mut selected_row = 0;
+        if browser.local_files.is_empty() {
+            rows.push(Line::raw("  No .vcd or .fst files"));
+        }
+        let entries = browser
+            .local_files
+            .iter()
+            .chain(browser.recent_files.iter().map(|file| &file.path));
+        for (i, name) in entries.enumerate() {
+            if i == browser.local_files.len() {
+                rows.push(Line::raw(""));
+                rows.push(Line::styled("Recent files", header));
+            }
+            let style = if i == browser.selected && browser.path_input.is_none() {
+                selected_row = rows.len();
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            rows.push(Line::styled(format!("  {}", name), style));
+        }
+
+        // Scroll just enough to keep the selected entry in view
+        let visible_rows = (inner_area.height as usize).saturating_sub(lines.len());
+        let scroll = (selected_row + 1).saturating_sub(visible_rows);
+        lines.extend(rows.into_iter().skip(scroll).take(visible_rows));
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines).render(inner_area, buf);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::state::AppState;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct FileInfoWidget {}
+
+impl StatefulWidget for FileInfoWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let lines: Vec<Line> = file_info_lines(state).into_iter().map(Line::raw).collect();
+
+        // Calculate a centered rectangle just big enough for the information
+        let popup_width = area.width.min(70);
+        let popup_height = area.height.min(lines.len() as u16 + 2);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title("File information [Esc to close]")
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Limit scrolling so the last line stays at the bottom
+        let max_scroll = lines.len().saturating_sub(inner_area.height as usize);
+        let scroll = state.file_info_scroll.min(max_scroll);
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines)
+            .scroll((scroll as u16, 0))
+            .render(inner_area, buf);
+        state.file_info_scroll = scroll;
+    }
+}
+
+// Helper function to describe the loaded file one fact per line, with `-` for what it didn't say
+fn file_info_lines(state: &AppState) -> Vec<String> {
+    let Some(info) = &state.file_info else {
+        return vec!["No file loaded".to_string()];
+    };
+    let path = state
+        .file_path
+        .as_ref()
+        .map_or("standard input".to_string(), |path| {
+            path.display().to_string()
+        });
+    let size = info
+        .size
+        .map_or("-".to_string(), |size| format!("{} bytes", size));
+    let loaded = state
+        .waveform_data
+        .window
+        .map_or("whole file".to_string(), |(from, to)| {
+            format!("{} to {}", from, to)
+        });
+    let header = |text: &Option<String>| text.clone().unwrap_or_else(|| "-".to_string());
+
+    vec![
+        format!("Path:      {}", path),
+        format!("Size:      {}", size),
+        format!("Loaded:    {}", loaded),
+        format!("Parsed in: {:.2?}", info.parse_duration),
+        format!("Signals:   {}", info.signal_count),
+        format!("Changes:   {}", info.change_count),
+        format!("Date:      {}", header(&info.header.date)),
+        format!("Version:   {}", header(&info.header.version)),
+        format!("Timescale: {}", header(&info.header.timescale)),
+    ]
+}
@@ -1,10 +1,10 @@
-use crate::{fuzzy_finder::FuzzyFinderStateAccess, state::AppState};
+use crate::{fuzzy_finder::FuzzyFinderStateAccess, state::AppState, stats::format_count};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Modifier, Style},
-    text::Span,
-    widgets::{Block, Borders, Clear, List, ListItem, StatefulWidget, Widget},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, StatefulWidget, Widget},
 };
 
 #[derive(Default, Copy, Clone)]
@@ -42,11 +42,18 @@ impl StatefulWidget for &mut FuzzyFinderWidget {
 
         // Render info text
         let selected_count = state.fuzzy_finder_state().selected_signals.len();
-        let info_text = format!(
+        let mut info_text = format!(
             "Selected: {}/{}",
             selected_count,
             state.fuzzy_finder_state().all_signals.len()
         );
+        if state.fuzzy_finder_state().sort_by_activity {
+            info_text.push_str(" | Sorted by activity");
+        }
+        let complete_groups = state.complete_signal_groups();
+        if !complete_groups.is_empty() {
+            info_text.push_str(&format!(" | All of group: {}", complete_groups.join(", ")));
+        }
         let info_span = Span::styled(info_text, Style::default().fg(Color::Cyan));
         ratatui::widgets::Paragraph::new(info_span).render(
             Rect::new(inner_area.x, inner_area.y + 1, inner_area.width, 1),
@@ -61,39 +68,64 @@ impl StatefulWidget for &mut FuzzyFinderWidget {
             inner_area.height.saturating_sub(4), // Leave room for query, info and help text
         );
 
-        let items: Vec<ListItem> = state
-            .fuzzy_finder_state()
+        // Only the rows that fit in the list are turned into items, as there may be many
+        // thousands of matches. The offset keeps the selected row in view like `List` would.
+        let finder = state.fuzzy_finder_state_mut();
+        finder.apply_pending_filter();
+        let visible_rows = list_area.height as usize;
+        let selected = finder.list_state.selected();
+        let mut offset = finder.list_state.offset();
+        if let Some(selected) = selected {
+            if selected < offset {
+                offset = selected;
+            } else if selected >= offset + visible_rows {
+                offset = selected + 1 - visible_rows;
+            }
+        }
+        offset = offset.min(finder.filtered_signals.len().saturating_sub(visible_rows));
+        *finder.list_state.offset_mut() = offset;
+
+        let rows: Vec<(&str, String)> = finder
             .filtered_signals
             .iter()
+            .skip(offset)
+            .take(visible_rows)
             .map(|s| {
-                let prefix = if state.fuzzy_finder_state().selected_signals.contains(s) {
+                let prefix = if finder.selected_signals.contains(s) {
                     "[✓] "
                 } else {
                     "[ ] "
                 };
-                ListItem::new(format!("{}{}", prefix, s))
+                (prefix, s.clone())
             })
             .collect();
 
-        let list = List::new(items).highlight_style(
-            Style::default()
-                .fg(Color::Black)
-                .bg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        );
-
-        // Get a mutable reference to the list state
-        let mut list_state = state.fuzzy_finder_state_mut().list_state.clone();
+        // Each signal's number of changes follows its name, dimmed
+        let dimmed = state.theme().dimmed();
+        let stats = state.waveform_stats();
+        let items: Vec<ListItem> = rows
+            .into_iter()
+            .map(|(prefix, signal)| {
+                let mut spans = vec![Span::raw(format!("{}{}", prefix, signal))];
+                if let Some(signal_stats) = stats.signals.get(&signal) {
+                    let changes = signal_stats.changes;
+                    let noun = if changes == 1 { "change" } else { "changes" };
+                    spans.push(Span::styled(
+                        format!(" ({} {})", format_count(changes), noun),
+                        dimmed,
+                    ));
+                }
+                ListItem::new(Line::from(spans))
+            })
+            .collect();
 
-        // Render using the cloned list state
+        let list = List::new(items).highlight_style(state.theme().highlight());
+        let mut list_state = ListState::default().with_selected(selected.map(|s| s - offset));
         StatefulWidget::render(list, list_area, buf, &mut list_state);
 
-        // Update the original list state with our changes
-        state.fuzzy_finder_state_mut().list_state = list_state;
-
         // Render help text at bottom
         let help_text =
-            "↑/↓: Nav | Space: Toggle | ^A: Select All | ^X: Clear All | Enter: Done | Esc: Cancel";
+            "↑/↓: Nav | Space: Toggle | ^A: All | ^X: Clear | ^S: Sort | Enter: Done | Esc: Cancel";
         let help_span = Span::styled(help_text, Style::default().fg(Color::DarkGray));
         let help_area = Rect::new(
             inner_area.x,
@@ -0,0 +1,66 @@
+use crate::state::AppState;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct GrepWidget {}
+
+impl StatefulWidget for GrepWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let Some(popup) = &mut state.grep_popup else {
+            return;
+        };
+
+        // Calculate a centered rectangle for the popup
+        let popup_width = area.width.min(70);
+        let popup_height = area.height.min(20);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let truncated_note = if popup.truncated { " (truncated)" } else { "" };
+        let block = Block::default()
+            .title(format!(
+                "{} match(es) of {}{} [Enter to jump]",
+                popup.matches.len(),
+                popup.query,
+                truncated_note
+            ))
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Keep the selected row in view
+        let visible_rows = inner_area.height as usize;
+        if popup.selected < popup.scroll {
+            popup.scroll = popup.selected;
+        } else if visible_rows > 0 && popup.selected >= popup.scroll + visible_rows {
+            popup.scroll = popup.selected + 1 - visible_rows;
+        }
+
+        let lines: Vec<Line> = popup
+            .matches
+            .iter()
+            .enumerate()
+            .skip(popup.scroll)
+            .take(visible_rows)
+            .map(|(i, found)| {
+                let style = if i == popup.selected {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                Line::styled(format!("{} @ {}", found.signal, found.time), style)
+            })
+            .collect();
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines).render(inner_area, buf);
+    }
+}
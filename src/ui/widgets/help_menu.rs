@@ -1,15 +1,37 @@
-use crate::state::AppState;
+use crate::{
+    app::wrap_message, command_mode::registry::Command, config::KeybindingsConfig, state::AppState,
+};
 use crossterm::event::KeyCode;
 use ratatui::{
     prelude::{Buffer, Rect},
     style::Style,
     widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
 };
+use std::rc::Rc;
+
+/// Mouse usage, which isn't configurable
+const MOUSE_HELP: &str = "Mouse:\n\
+    Left Click - Place yellow marker (primary)\n\
+    Shift+Left Click - Place white marker (secondary)\n\
+    Click and Drag - Zoom to selection\n\
+    Ctrl+Click and Drag - Measure and place both markers\n\
+    Alt+Click and Drag - Add a named region\n\
+    Click/Drag on time ruler - Place/scrub yellow marker\n\
+    Shift+Click on time ruler - Place white marker\n\
+    Drag Divider - Resize signal list\n\
+    Click/Drag signal scrollbar - Scroll signal list";
+
+const NAVIGATION_HELP: &str = "Help Navigation:\n\
+    Up/Down arrows - Scroll help content\n\
+    Esc - Close help";
 
-#[derive(Default, Clone, Copy, Eq, PartialEq)]
-pub struct HelpMenuWidget {}
+/// Help popup. The keys and commands are listed from the keybindings and the command registry
+/// each time it is drawn, so rebound keys and new commands show up without editing the help.
+pub struct HelpMenuWidget<'a> {
+    pub commands: Vec<&'a Rc<Box<dyn Command<AppState>>>>,
+}
 
-impl HelpMenuWidget {
+impl HelpMenuWidget<'_> {
     fn key_to_string(&self, key: &KeyCode) -> String {
         match key {
             KeyCode::Char(c) => format!("'{}'", c),
@@ -17,61 +39,62 @@ impl HelpMenuWidget {
             _ => format!("{:?}", key),
         }
     }
+
+    // Lines of the help for a popup `width` columns wide. Command descriptions are wrapped, with
+    // their continuation lines indented.
+    fn lines(&self, keybindings: &KeybindingsConfig, width: usize) -> Vec<String> {
+        let mut lines = vec![
+            "DigiSurf Keyboard Controls".to_string(),
+            String::new(),
+            "Keys:".to_string(),
+        ];
+        for (description, key) in keybindings.descriptions() {
+            // Disabled bindings have no key to press
+            if key != KeyCode::Null {
+                lines.push(format!("{} - {}", self.key_to_string(&key), description));
+            }
+        }
+
+        lines.push(String::new());
+        lines.extend(MOUSE_HELP.lines().map(str::to_string));
+
+        lines.push(String::new());
+        lines.push("Commands:".to_string());
+        let indent = "    ";
+        for command in &self.commands {
+            let mut name = format!(":{}", command.name());
+            let aliases = command.aliases();
+            if !aliases.is_empty() {
+                name.push_str(&format!(" (:{})", aliases.join(", :")));
+            }
+            let text = format!("{} - {}", name, command.description());
+            for (i, line) in wrap_message(&text, width.saturating_sub(indent.len()), usize::MAX)
+                .into_iter()
+                .enumerate()
+            {
+                lines.push(if i == 0 {
+                    line
+                } else {
+                    format!("{}{}", indent, line)
+                });
+            }
+        }
+        lines.push("Commands can be shortened to any unique prefix".to_string());
+        lines.push("Quote arguments with spaces: \"reset done\"".to_string());
+
+        lines.push(String::new());
+        lines.extend(NAVIGATION_HELP.lines().map(str::to_string));
+        lines
+    }
 }
 
-impl StatefulWidget for HelpMenuWidget {
+impl StatefulWidget for HelpMenuWidget<'_> {
     type State = AppState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         // Clear the entire screen first so only the help menu is visible
         Clear.render(area, buf);
 
-        let help_text = format!(
-            "DigiSurf Keyboard Controls\n\
-            \n\
-            Navigation:\n\
-            {}/{} - Select signal\n\
-            {}+Shift/{}+Shift - Move signal up/down\n\
-            {}/{} - Navigate timeline\n\
-            {} - Zoom in\n\
-            {} - Zoom out\n\
-            {} - Zoom full\n\
-            {} - Enter command mode\n\
-            \n\
-            Markers:\n\
-            Left Click - Place yellow marker (primary)\n\
-            Shift+Left Click - Place white marker (secondary)\n\
-            {} - Remove primary marker\n\
-            {} - Remove secondary marker\n\
-            \n\
-            Selection:\n\
-            Click and Drag - Zoom to selection\n\
-            \n\
-            Commands:\n\
-            :zoom <factor> - Zoom to 1/factor of total\n\
-            :zoomfull (:zf) - Zoom to full view\n\
-            :goto <time> - Go to specific time\n\
-            :marker <1|2> <time> - Set marker\n\
-            :q - Quit digisurf\n\
-            :help (:h) - Show this help\n\
-            \n\
-            Help Navigation:\n\
-            Up/Down arrows - Scroll help content\n\
-            Esc - Close help",
-            self.key_to_string(&state.config.keybindings.up),
-            self.key_to_string(&state.config.keybindings.down),
-            self.key_to_string(&state.config.keybindings.up),
-            self.key_to_string(&state.config.keybindings.down),
-            self.key_to_string(&state.config.keybindings.left),
-            self.key_to_string(&state.config.keybindings.right),
-            self.key_to_string(&state.config.keybindings.zoom_in),
-            self.key_to_string(&state.config.keybindings.zoom_out),
-            self.key_to_string(&state.config.keybindings.zoom_full),
-            self.key_to_string(&state.config.keybindings.enter_command_mode),
-            self.key_to_string(&state.config.keybindings.delete_primary_marker),
-            self.key_to_string(&state.config.keybindings.delete_secondary_marker)
-        );
-
         // Calculate a centered rectangle for the help menu
         let help_width = area.width.min(70);
         let help_height = area.height.min(20);
@@ -88,8 +111,7 @@ impl StatefulWidget for HelpMenuWidget {
         // Calculate inner area for text content
         let inner_area = block.inner(help_area);
 
-        // Split the text into lines
-        let lines: Vec<&str> = help_text.split('\n').collect();
+        let lines = self.lines(&state.config.keybindings, inner_area.width as usize);
 
         // Calculate max scroll value (ensure we can't scroll past content)
         let max_scroll = lines.len().saturating_sub(inner_area.height as usize);
@@ -104,7 +126,7 @@ impl StatefulWidget for HelpMenuWidget {
             .skip(scroll)
             .take(inner_area.height as usize)
             .cloned()
-            .collect::<Vec<&str>>()
+            .collect::<Vec<String>>()
             .join("\n");
 
         // Render the block
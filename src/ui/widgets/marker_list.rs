@@ -0,0 +1,55 @@
+use crate::{state::AppState, time_format::format_time};
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct MarkerListWidget {}
+
+impl StatefulWidget for MarkerListWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let markers = state.saved_markers_by_time();
+
+        // Calculate a centered rectangle just big enough for the list
+        let popup_width = area.width.min(50);
+        let popup_height = area.height.min(markers.len() as u16 + 3);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title("Saved markers [Esc to close]")
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Limit scrolling so the last marker stays at the bottom
+        let visible_rows = (inner_area.height as usize).saturating_sub(1);
+        let max_scroll = markers.len().saturating_sub(visible_rows);
+        let scroll = state.marker_list_scroll.min(max_scroll);
+
+        let mut lines = vec![Line::styled(
+            format!("{:<20} {:>12}  {}", "Name", "Time", "Color"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        for marker in markers.iter().skip(scroll).take(visible_rows) {
+            lines.push(Line::from(vec![
+                Span::raw(format!(
+                    "{:<20} {:>12}  ",
+                    marker.name,
+                    format_time(marker.time, state.time_zero)
+                )),
+                Span::styled(marker.color.to_string(), Style::default().fg(marker.color)),
+            ]));
+        }
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines).render(inner_area, buf);
+        state.marker_list_scroll = scroll;
+    }
+}
@@ -1,12 +1,74 @@
-use ratatui::{buffer::Buffer, layout::Rect, widgets::StatefulWidget};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Modifier, Style},
+    widgets::StatefulWidget,
+};
 
 use crate::state::AppState;
 
 #[derive(Default, Copy, Clone)]
 pub struct MarkerNamesWidget {}
 
+// A name drawn in the row, either in full or shortened to its first character
+struct Label {
+    x: u16,
+    text: String,
+    style: Style,
+    /// Name of the saved marker it labels, or None for a region
+    marker: Option<String>,
+}
+
 impl MarkerNamesWidget {
+    /// Name of the saved marker whose label is drawn at `column` of `area`, if any.
+    pub fn saved_marker_at(area: Rect, column: u16, state: &AppState) -> Option<String> {
+        // Labels drawn later are on top
+        Self::labels(area, state)
+            .into_iter()
+            .rev()
+            .find(|label| column >= label.x && column < label.x + label.text.chars().count() as u16)
+            .and_then(|label| label.marker)
+    }
+
     pub fn draw_saved_markers(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
+        for label in Self::labels(area, state) {
+            let style = if label.marker.is_some() && label.marker == state.selected_saved_marker {
+                label.style.add_modifier(Modifier::REVERSED)
+            } else {
+                label.style
+            };
+            for (j, c) in label.text.chars().enumerate() {
+                buf[(label.x + j as u16, area.y)]
+                    .set_char(c)
+                    .set_style(style);
+            }
+        }
+    }
+
+    // The labels of the regions and saved markers in view, in the order they are drawn
+    fn labels(area: Rect, state: &AppState) -> Vec<Label> {
+        // Create a list of marker and region positions and their display names
+        let mut marker_displays = Vec::new();
+        let viewport = state.viewport(area.width);
+
+        // Regions are labeled at their start, or at the left edge if they start before the view
+        for region in &state.regions {
+            if region.end < state.time_start || region.start > state.time_start + state.time_range {
+                continue;
+            }
+            let x_pos = viewport.column_of(region.start);
+            if x_pos < area.width {
+                let region_style = state.theme().region(region.color);
+                marker_displays.push((
+                    area.x + x_pos,
+                    region.name.clone(),
+                    region.name.clone(),
+                    region_style,
+                    None,
+                ));
+            }
+        }
+
         // First, find which markers are visible in the current time window
         let visible_markers: Vec<_> = state
             .saved_markers
@@ -17,48 +79,57 @@ impl MarkerNamesWidget {
             })
             .collect();
 
-        if visible_markers.is_empty() {
-            return;
-        }
-
-        // Create a list of marker positions and their display names
-        let mut marker_displays = Vec::new();
-
         // Calculate positions for each marker
         for marker in visible_markers {
             // Calculate x position based on time
-            let x_pos = ((marker.time - state.time_start) as f64 / state.time_range as f64
-                * area.width as f64) as u16;
+            let x_pos = viewport.column_of(marker.time);
 
             // Only consider markers that start within the visible area
             if x_pos < area.width {
                 let marker_pos = area.x + x_pos;
-                let marker_style = ratatui::style::Style::default().fg(marker.color);
-                marker_displays.push((marker_pos, marker.name.clone(), marker_style));
+                let marker_style = Style::default().fg(state.theme().color(marker.color));
+
+                // With the primary marker set, the full label also shows the distance to it
+                let label = match state.primary_marker {
+                    Some(primary) => {
+                        format!("Δ{}={}", marker.name, marker.time.abs_diff(primary))
+                    }
+                    None => marker.name.clone(),
+                };
+                marker_displays.push((
+                    marker_pos,
+                    marker.name.clone(),
+                    label,
+                    marker_style,
+                    Some(marker.name.clone()),
+                ));
             }
         }
 
         // Sort markers by position
-        marker_displays.sort_by_key(|(pos, _, _)| *pos);
+        marker_displays.sort_by_key(|(pos, _, _, _, _)| *pos);
 
-        // First, display all marker indicators with minimum representations (just first character)
+        // First, show all marker indicators with minimum representations (just first character)
         // This ensures all markers are at least minimally visible
-        for (pos, name, style) in marker_displays.iter() {
+        let mut labels = Vec::new();
+        for (pos, name, _, style, marker) in marker_displays.iter() {
             if let Some(first_char) = name.chars().next() {
-                // Draw a single character indicator for each marker
                 if *pos < area.right() {
-                    buf[(*pos, area.y)].set_char(first_char).set_style(*style);
+                    labels.push(Label {
+                        x: *pos,
+                        text: first_char.to_string(),
+                        style: *style,
+                        marker: marker.clone(),
+                    });
                 }
             }
         }
 
-        // Now draw the full names where there's space available
+        // Now show the full names where there's space available
         // Using a greedy approach - markers that are further apart get their full names
-        let mut i = 0;
-        while i < marker_displays.len() {
-            let (pos, name, style) = &marker_displays[i];
-            let mut end_pos = *pos + name.len() as u16;
-            let mut display_name = name.clone();
+        for (i, (pos, _, label, style, marker)) in marker_displays.iter().enumerate() {
+            let mut end_pos = *pos + label.chars().count() as u16;
+            let mut display_name = label.clone();
 
             // Check for right edge truncation
             if end_pos > area.right() {
@@ -70,27 +141,19 @@ impl MarkerNamesWidget {
                 end_pos = area.right();
             }
 
-            // Check if this marker would overlap with the next marker
+            // If this marker would overlap with the next marker, it keeps just the first character
             let will_overlap = i + 1 < marker_displays.len() && end_pos > marker_displays[i + 1].0;
-
-            if will_overlap {
-                // If there would be an overlap, just show the first character
-                if *pos < area.right() {
-                    // First character already drawn in the previous loop
-                }
-            } else {
-                // There's enough room, draw the full name
-                for (j, c) in display_name.chars().enumerate().skip(1) {
-                    // Skip first char, already drawn
-                    let x = *pos + j as u16;
-                    if x < area.right() {
-                        buf[(x, area.y)].set_char(c).set_style(*style);
-                    }
-                }
+            if !will_overlap {
+                // There's enough room, draw the full label over the indicator
+                labels.push(Label {
+                    x: *pos,
+                    text: display_name,
+                    style: *style,
+                    marker: marker.clone(),
+                });
             }
-
-            i += 1;
         }
+        labels
     }
 }
 
@@ -98,18 +161,21 @@ impl StatefulWidget for MarkerNamesWidget {
     type State = AppState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        if state.saved_markers.is_empty() {
+        if state.saved_markers.is_empty() && state.regions.is_empty() {
             return;
         }
 
-        self.draw_saved_markers(buf, area, &state);
+        self.draw_saved_markers(buf, area, state);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{state::AppState, types::Marker};
+    use crate::{
+        state::AppState,
+        types::{Marker, Region},
+    };
     use insta::assert_snapshot;
     use ratatui::{backend::TestBackend, Terminal};
 
@@ -143,7 +209,7 @@ mod tests {
         terminal
             .draw(|f| {
                 let size = f.area();
-                widget.render(size, &mut f.buffer_mut(), &mut state);
+                widget.render(size, f.buffer_mut(), &mut state);
             })
             .unwrap();
 
@@ -168,7 +234,7 @@ mod tests {
         terminal
             .draw(|f| {
                 let size = f.area();
-                widget.render(size, &mut f.buffer_mut(), &mut state);
+                widget.render(size, f.buffer_mut(), &mut state);
             })
             .unwrap();
 
@@ -193,7 +259,7 @@ mod tests {
         terminal
             .draw(|f| {
                 let size = f.area();
-                widget.render(size, &mut f.buffer_mut(), &mut state);
+                widget.render(size, f.buffer_mut(), &mut state);
             })
             .unwrap();
 
@@ -220,10 +286,118 @@ mod tests {
         terminal
             .draw(|f| {
                 let size = f.area();
-                widget.render(size, &mut f.buffer_mut(), &mut state);
+                widget.render(size, f.buffer_mut(), &mut state);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_marker_names_delta_to_primary() {
+        let widget = MarkerNamesWidget::default();
+        let mut state = setup_state();
+        state.primary_marker = Some(25);
+
+        let backend = TestBackend::new(80, 1);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        // Render the widget
+        terminal
+            .draw(|f| {
+                let size = f.area();
+                widget.render(size, f.buffer_mut(), &mut state);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_saved_marker_at_follows_drawn_labels() {
+        let mut state = setup_state();
+        state
+            .saved_markers
+            .push(Marker::new(15, "Overlap".to_string()));
+        state.regions = vec![Region::new(60, 70, "burst".to_string())];
+        let area = Rect::new(0, 0, 80, 1);
+
+        // Marker1 is shortened to its first character by the marker after it
+        assert_eq!(
+            MarkerNamesWidget::saved_marker_at(area, 8, &state).as_deref(),
+            Some("Marker1")
+        );
+        assert_eq!(MarkerNamesWidget::saved_marker_at(area, 9, &state), None);
+        assert_eq!(
+            MarkerNamesWidget::saved_marker_at(area, 18, &state).as_deref(),
+            Some("Overlap")
+        );
+        assert_eq!(
+            MarkerNamesWidget::saved_marker_at(area, 38, &state).as_deref(),
+            Some("Marker2")
+        );
+        // Region names aren't saved markers
+        assert_eq!(MarkerNamesWidget::saved_marker_at(area, 48, &state), None);
+    }
+
+    #[test]
+    fn test_region_names_overlapping() {
+        let widget = MarkerNamesWidget::default();
+        let mut state = AppState::default();
+        state.time_start = 0;
+        state.time_range = 100;
+
+        // The first region's name runs into the second one, so it's reduced to 'A'
+        state.regions = vec![
+            Region::new(10, 60, "AXI_burst".to_string()),
+            Region::new(15, 30, "reset_window".to_string()),
+        ];
+        state
+            .saved_markers
+            .push(Marker::new(50, "Marker1".to_string()));
+
+        let backend = TestBackend::new(80, 1);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        // Render the widget
+        terminal
+            .draw(|f| {
+                let size = f.area();
+                widget.render(size, f.buffer_mut(), &mut state);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_region_names_partially_outside_view() {
+        let widget = MarkerNamesWidget::default();
+        let mut state = AppState::default();
+        state.time_start = 100;
+        state.time_range = 100;
+
+        // One region starts before the view and is labeled at the left edge, one runs past the
+        // right edge and has its name truncated, and one is entirely outside the view
+        state.regions = vec![
+            Region::new(50, 120, "early".to_string()),
+            Region::new(190, 300, "late_region".to_string()),
+            Region::new(250, 300, "hidden".to_string()),
+        ];
+
+        let backend = TestBackend::new(80, 1);
+        let mut terminal = Terminal::new(backend).unwrap();
+
+        // Render the widget
+        terminal
+            .draw(|f| {
+                let size = f.area();
+                widget.render(size, f.buffer_mut(), &mut state);
             })
             .unwrap();
 
         assert_snapshot!(terminal.backend());
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(0, 0)].bg, crate::constants::DEFAULT_REGION_COLOR);
     }
 }
@@ -0,0 +1,57 @@
+use crate::{command_mode::CommandModeStateAccess, state::AppState};
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct MessagesWidget {}
+
+impl StatefulWidget for MessagesWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let results = &state.command_state().results;
+
+        // Calculate a centered rectangle for the popup
+        let popup_width = area.width.min(80);
+        let popup_height = area.height.min(results.len() as u16 + 2);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title("Messages [Esc to close]")
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Limit scrolling so the most recent result stays at the bottom
+        let visible_rows = inner_area.height as usize;
+        let scroll = state
+            .messages_scroll
+            .min(results.len().saturating_sub(visible_rows));
+
+        // Each result takes a line, with the first line of results that have several
+        let lines: Vec<Line> = results
+            .iter()
+            .skip(scroll)
+            .take(visible_rows)
+            .map(|result| {
+                let color = if result.is_error {
+                    Color::Red
+                } else {
+                    Color::Green
+                };
+                let first_line = result.message.lines().next().unwrap_or_default();
+                Line::styled(first_line.to_string(), Style::default().fg(color))
+            })
+            .collect();
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines).render(inner_area, buf);
+        state.messages_scroll = scroll;
+    }
+}
@@ -0,0 +1,145 @@
+// digisurf/src/ui/widgets/minimap.rs
+use crate::state::AppState;
+use ratatui::{buffer::Buffer, layout::Rect, style::Color, widgets::StatefulWidget};
+
+/// Characters for increasing activity, from no changes to the busiest column.
+const SHADES: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// Strip showing where the displayed signals change over the whole waveform, with the visible
+/// window highlighted.
+#[derive(Default, Copy, Clone)]
+pub struct MinimapWidget {}
+
+impl StatefulWidget for MinimapWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if state.waveform_data.max_time == 0 || area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let (window_start, window_end) = window_columns(state, area.width);
+        let theme = state.theme();
+        let density = state.minimap_density(area.width);
+        let busiest = density.iter().copied().max().unwrap_or(0);
+
+        for (column, count) in density.iter().enumerate() {
+            let x = area.x + column as u16;
+            let in_window = (window_start..=window_end).contains(&(column as u16));
+            let style = if in_window {
+                theme.minimap_window()
+            } else {
+                theme.ruler()
+            };
+            buf[(x, area.y)]
+                .set_char(shade(*count, busiest))
+                .set_style(style.fg(Color::Cyan));
+        }
+    }
+}
+
+// Helper function to pick the shade for a column with `count` changes, relative to the busiest
+// column. Any change gets at least the lightest shade so single edges aren't lost.
+fn shade(count: usize, busiest: usize) -> char {
+    if count == 0 || busiest == 0 {
+        return SHADES[0];
+    }
+    let levels = SHADES.len() - 1;
+    SHADES[(count * levels).div_ceil(busiest).clamp(1, levels)]
+}
+
+// Helper function to get the first and last minimap columns covered by the visible window. The
+// window always covers at least one column.
+fn window_columns(state: &AppState, width: u16) -> (u16, u16) {
+    let viewport = state.minimap_viewport(width);
+    let column = |time: u64| viewport.column_of(time).min(width - 1);
+    let start = column(state.time_start);
+    let end = column(state.time_start + state.time_range).max(start);
+    (start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::types::{Value, WaveValue};
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    // A clock that only toggles in two bursts, around 100 and 800
+    fn bursty_state() -> AppState {
+        let mut state = AppState::default();
+        let mut clock = vec![(0, WaveValue::Binary(Value::V0))];
+        for (i, time) in (100..150).chain(800..810).enumerate() {
+            let value = if i % 2 == 0 { Value::V1 } else { Value::V0 };
+            clock.push((time, WaveValue::Binary(value)));
+        }
+        state.waveform_data.signals = vec!["top.clk".to_string()];
+        state
+            .waveform_data
+            .values
+            .insert("top.clk".to_string(), clock);
+        state.waveform_data.max_time = 999;
+        state.displayed_signals = vec!["top.clk".to_string()];
+        state
+    }
+
+    fn render_minimap(state: &mut AppState, width: u16) -> Terminal<TestBackend> {
+        let mut terminal = Terminal::new(TestBackend::new(width, 1)).unwrap();
+        terminal
+            .draw(|f| {
+                let size = f.area();
+                MinimapWidget::default().render(size, f.buffer_mut(), state);
+            })
+            .unwrap();
+        terminal
+    }
+
+    #[test]
+    fn test_shade() {
+        assert_eq!(shade(0, 10), ' ');
+        assert_eq!(shade(1, 10), '░');
+        assert_eq!(shade(5, 10), '▒');
+        assert_eq!(shade(10, 10), '█');
+        assert_eq!(shade(0, 0), ' ');
+    }
+
+    #[test]
+    fn test_render_minimap_bursty_trace() {
+        let mut state = bursty_state();
+        state.time_start = 0;
+        state.time_range = 200;
+        let terminal = render_minimap(&mut state, 40);
+        assert_snapshot!(terminal.backend());
+
+        // The first fifth of the strip is the visible window
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(0, 0)].bg, Color::Blue);
+        assert_eq!(buffer[(8, 0)].bg, Color::Blue);
+        assert_eq!(buffer[(9, 0)].bg, Color::DarkGray);
+    }
+
+    #[test]
+    fn test_render_minimap_narrow_window() {
+        let mut state = bursty_state();
+        state.time_start = 810;
+        state.time_range = 5;
+        let terminal = render_minimap(&mut state, 20);
+        assert_snapshot!(terminal.backend());
+
+        // A window narrower than a column still highlights one
+        let buffer = terminal.backend().buffer();
+        let highlighted: Vec<u16> = (0..20)
+            .filter(|x| buffer[(*x, 0)].bg == Color::Blue)
+            .collect();
+        assert_eq!(highlighted, vec![16]);
+    }
+
+    #[test]
+    fn test_density_follows_displayed_signals() {
+        let mut state = bursty_state();
+        assert_eq!(state.minimap_density(10).iter().sum::<usize>(), 60);
+
+        state.displayed_signals.clear();
+        assert_eq!(state.minimap_density(10).iter().sum::<usize>(), 0);
+    }
+}
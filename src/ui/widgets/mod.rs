@@ -1,7 +1,21 @@
 pub mod bottom_text_box;
+pub mod command_list;
+pub mod file_browser;
+pub mod file_info;
 pub mod fuzzy_finder;
+pub mod grep;
 pub mod help_menu;
+pub mod marker_list;
 pub mod marker_names;
+pub mod messages;
+pub mod minimap;
+pub mod parse_warnings;
+pub mod sample;
 pub mod signal_list;
+pub mod stats;
 pub mod time_ruler;
+pub mod too_small;
+pub mod trace;
+pub mod transitions;
+pub mod view_list;
 pub mod waveform;
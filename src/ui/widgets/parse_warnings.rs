@@ -0,0 +1,45 @@
+use crate::state::AppState;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct ParseWarningsWidget {}
+
+impl StatefulWidget for ParseWarningsWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let lines: Vec<Line> = state
+            .parse_report
+            .details()
+            .into_iter()
+            .map(Line::raw)
+            .collect();
+
+        // Calculate a centered rectangle just big enough for the problems
+        let popup_width = area.width.min(70);
+        let popup_height = area.height.min(lines.len() as u16 + 2);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title("Parse warnings [Esc to close]")
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Limit scrolling so the last line stays at the bottom
+        let max_scroll = lines.len().saturating_sub(inner_area.height as usize);
+        let scroll = state.parse_warnings_scroll.min(max_scroll);
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines)
+            .scroll((scroll as u16, 0))
+            .render(inner_area, buf);
+        state.parse_warnings_scroll = scroll;
+    }
+}
@@ -0,0 +1,52 @@
+use crate::state::AppState;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct SampleWidget {}
+
+impl StatefulWidget for SampleWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let Some(popup) = &mut state.sample_popup else {
+            return;
+        };
+
+        // Calculate a centered rectangle just big enough for the values
+        let longest = popup
+            .lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+        let popup_width = area.width.min(longest as u16 + 2).max(area.width.min(40));
+        let popup_height = area.height.min(popup.lines.len() as u16 + 2);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title(format!("Values at {} [Esc to close]", popup.time))
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Limit scrolling so the last line stays at the bottom
+        let max_scroll = popup.lines.len().saturating_sub(inner_area.height as usize);
+        popup.scroll = popup.scroll.min(max_scroll);
+
+        let lines: Vec<Line> = popup
+            .lines
+            .iter()
+            .map(|line| Line::raw(line.as_str()))
+            .collect();
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines)
+            .scroll((popup.scroll as u16, 0))
+            .render(inner_area, buf);
+    }
+}
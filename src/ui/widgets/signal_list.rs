@@ -1,49 +1,158 @@
-use crate::{constants::WAVEFORM_HEIGHT, state::AppState};
+use crate::{signal_names::display_names, state::AppState};
 use ratatui::{
     prelude::{Buffer, Rect},
     style::{Color, Style},
-    widgets::{Block, Borders, Paragraph, StatefulWidget, Widget},
+    widgets::{
+        Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
+        Widget,
+    },
 };
+use std::path::Path;
 
 #[derive(Default, Copy, Clone)]
-pub struct SignalListWidget {}
+pub struct SignalListWidget {
+    /// Marks the list as belonging to the focused pane while the waveform is split
+    pub focused_pane: bool,
+}
+
+impl SignalListWidget {
+    /// The border around the signal rows, which are drawn inside it.
+    pub fn block() -> Block<'static> {
+        Block::default().borders(Borders::ALL)
+    }
 
-impl SignalListWidget {}
+    /// The column at the right of the scrolled signal rows where the scrollbar is drawn, if there
+    /// are more listed signals than fit in `area` below the pinned ones.
+    pub fn scrollbar_area(area: Rect, state: &AppState) -> Option<Rect> {
+        let inner = Self::block().inner(area);
+        // The rows inside the border down to the separator below the pinned signals
+        let pinned_height = state.pinned_height().saturating_sub(1).min(inner.height);
+        let rows: usize = state
+            .displayed_signals
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| state.is_listed(*index) && !state.is_pinned(*index))
+            .map(|(_, signal)| state.row_height(signal))
+            .sum();
+        (rows > area.height.saturating_sub(state.pinned_height()) as usize
+            && inner.width > 1
+            && inner.height > pinned_height)
+            .then(|| {
+                Rect::new(
+                    inner.right() - 1,
+                    inner.y + pinned_height,
+                    1,
+                    inner.height - pinned_height,
+                )
+            })
+    }
+
+    /// Scroll offset that centers the visible signals on a row of the scrollbar in `area`, so that
+    /// the thumb follows the mouse.
+    pub fn scroll_offset_at(area: Rect, row: u16, state: &AppState) -> Option<usize> {
+        let scrollbar = Self::scrollbar_area(area, state)?;
+        let visible_signals = state
+            .visible_signal_rows(area.height)
+            .iter()
+            .filter(|(index, _, _)| !state.is_pinned(*index))
+            .count();
+        // The thumb moves over the listed signals below the pinned ones, which are fewer than the
+        // displayed ones while the list is filtered
+        let listed: Vec<usize> = (0..state.displayed_signals.len())
+            .filter(|index| state.is_listed(*index) && !state.is_pinned(*index))
+            .collect();
+        let row = row.clamp(scrollbar.top(), scrollbar.bottom() - 1) - scrollbar.y;
+        let center = (row as usize * 2 + 1) * listed.len() / (scrollbar.height as usize * 2);
+        let first = center
+            .saturating_sub(visible_signals / 2)
+            .min(listed.len().saturating_sub(1));
+        Some(
+            listed
+                .get(first)
+                .copied()
+                .unwrap_or(0)
+                .min(state.max_signal_scroll_offset(area.height)),
+        )
+    }
+
+    // Title naming both files while one is loaded with `:diff`, A being the file drawn on top. The
+    // focused pane of a split waveform is marked with an arrow, and the filter narrowing the list
+    // follows the name.
+    fn title(&self, state: &AppState) -> String {
+        let focus = if self.focused_pane { "▶ " } else { "" };
+        let filter = state
+            .signal_filter
+            .as_ref()
+            .map_or(String::new(), |filter| format!(" /{}", filter.query));
+        let Some(diff) = &state.diff else {
+            return format!("{}Signals{}", focus, filter);
+        };
+        let file_a = state
+            .file_path
+            .as_deref()
+            .map_or("A".to_string(), file_name);
+        format!(
+            "{}Signals A: {} B: {}{}",
+            focus,
+            file_a,
+            file_name(&diff.path),
+            filter
+        )
+    }
+}
 
 impl StatefulWidget for SignalListWidget {
     type State = AppState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         // Draw the overall block
-        let block = Block::default().title("Signals").borders(Borders::ALL);
-        let inner_area = block.inner(area);
+        let block = Self::block().title(self.title(state));
+        let mut inner_area = block.inner(area);
         block.render(area, buf);
 
-        // Calculate how many signals we can display in the visible area
-        let visible_signals = area.height as usize / WAVEFORM_HEIGHT;
+        // A line separates the pinned signals from the scrolled ones, in the same screen row as
+        // the waveform's. The rows start below the border, so it's the last row of the last pinned
+        // signal here, which its name is drawn above.
+        let pinned_height = state.pinned_height();
+        let separator_y = area.y + pinned_height.saturating_sub(1);
+        if pinned_height > 0 && separator_y < inner_area.bottom() {
+            for x in inner_area.left()..inner_area.right() {
+                buf[(x, separator_y)].set_char('─').set_fg(Color::DarkGray);
+            }
+        }
 
         // Ensure scroll offset is within valid bounds
-        if state.signal_scroll_offset
-            > state
-                .displayed_signals
-                .len()
-                .saturating_sub(visible_signals)
-        {
-            state.signal_scroll_offset = state
-                .displayed_signals
-                .len()
-                .saturating_sub(visible_signals);
+        let max_offset = state.max_signal_scroll_offset(area.height);
+        state.signal_scroll_offset = state.signal_scroll_offset.min(max_offset);
+        let visible_rows = state.visible_signal_rows(area.height);
+
+        // Show where the visible signals are in the list when they don't all fit, keeping the
+        // scrollbar's column clear of names and values
+        if let Some(scrollbar_area) = Self::scrollbar_area(area, state) {
+            inner_area.width -= 1;
+            let mut scrollbar_state = ScrollbarState::new(state.listed_position(max_offset) + 1)
+                .position(state.listed_position(state.signal_scroll_offset))
+                .viewport_content_length(
+                    visible_rows
+                        .iter()
+                        .filter(|(index, _, _)| !state.is_pinned(*index))
+                        .count(),
+                );
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .track_style(Style::default().fg(Color::DarkGray))
+                .render(scrollbar_area, buf, &mut scrollbar_state);
         }
 
-        for (rel_idx, (idx, name)) in state
-            .displayed_signals
-            .iter()
-            .enumerate()
-            .skip(state.signal_scroll_offset)
-            .take(visible_signals)
-            .enumerate()
-        {
-            let y_position = inner_area.y + (rel_idx as u16 * WAVEFORM_HEIGHT as u16);
+        let display_names =
+            display_names(&state.displayed_signals, state.config.ui.signal_name_style);
+        let constant = state.constant_displayed_signals();
+
+        for (idx, top, _) in visible_rows {
+            let (name, display_name) = (&state.displayed_signals[idx], &display_names[idx]);
+            let y_position = inner_area.y + top;
+            let signal_height = state.signal_height(name) as u16;
 
             // Skip if we're outside the visible area
             if y_position >= inner_area.bottom() {
@@ -51,31 +160,57 @@ impl StatefulWidget for SignalListWidget {
             }
 
             let style = if idx == state.selected_signal {
-                Style::default().fg(Color::Yellow)
+                state.theme().selected()
+            } else if let Some(color) = state.signal_colors.get(name) {
+                Style::default().fg(state.theme().color(*color))
+            } else if constant[idx] {
+                // Signals that never change are of little interest
+                state.theme().dimmed()
             } else {
                 Style::default()
             };
 
             // Calculate vertical center of the waveform area
-            let vertical_center = y_position + ((WAVEFORM_HEIGHT as u16 / 2) - 1);
+            let vertical_center = y_position + (signal_height / 2).saturating_sub(1);
 
             // Signal name
+            let name_width = display_name.chars().count() as u16;
             let signal_area = Rect::new(
                 inner_area.x,
                 vertical_center,
-                inner_area.width.min(name.len() as u16),
+                inner_area.width.min(name_width),
                 1,
             );
 
-            Paragraph::new(name.as_str())
+            Paragraph::new(display_name.as_str())
                 .style(style)
                 .render(signal_area, buf);
 
-            // Only show signal changes for primary marker
-            if let Some(marker_time) = state.primary_marker {
+            // A dot at the end of the row marks the signal shown in the command bar's title
+            if state.tracked_signal.as_ref() == Some(name) && inner_area.width > 0 {
+                buf[(inner_area.right() - 1, vertical_center)]
+                    .set_char('•')
+                    .set_fg(Color::DarkGray);
+            }
+
+            // Mark signals the file loaded with `:diff` doesn't have in their empty row below
+            if let Some(diff) = &state.diff {
+                let diff_y = y_position + signal_height;
+                if !diff.data.values.contains_key(name)
+                    && !state.decoded.contains_key(name)
+                    && diff_y < inner_area.bottom()
+                {
+                    Paragraph::new(format!("not in {}", file_name(&diff.path)))
+                        .style(Style::default().fg(Color::DarkGray))
+                        .render(Rect::new(inner_area.x, diff_y, inner_area.width, 1), buf);
+                }
+            }
+
+            // Show values at the primary marker, or at the cursor without one
+            if let Some(marker_time) = state.primary_marker.or(state.cursor) {
                 // Calculate position for value display
-                let text_x = inner_area.x + name.len() as u16 + 1;
-                let max_width = inner_area.width.saturating_sub(name.len() as u16 + 1);
+                let text_x = inner_area.x + name_width + 1;
+                let max_width = inner_area.width.saturating_sub(name_width + 1);
 
                 if max_width == 0 {
                     continue;
@@ -97,9 +232,16 @@ impl StatefulWidget for SignalListWidget {
                 }
 
                 // Show current value if no transition
-                if let Some(value) = state.get_value_at_marker(name, marker_time) {
-                    let value_text = value.to_string();
-
+                // Decoded rows show the annotation under the marker instead
+                let value = match state.decoded.get(name) {
+                    Some(_) => state
+                        .annotation_at(name, marker_time)
+                        .map(|annotation| annotation.label.clone()),
+                    None => state
+                        .get_value_at_marker(name, marker_time)
+                        .map(|value| state.format_value(name, &value)),
+                };
+                if let Some(value_text) = value {
                     let value_area = Rect::new(
                         text_x,
                         vertical_center,
@@ -115,3 +257,11 @@ impl StatefulWidget for SignalListWidget {
         }
     }
 }
+
+// Helper function to get the name of a file without its directories
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .into_owned()
+}
@@ -0,0 +1,57 @@
+use crate::{state::AppState, stats::format_bytes};
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct StatsWidget {}
+
+impl StatefulWidget for StatsWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let stats = state.waveform_stats();
+        let header = Style::default().add_modifier(Modifier::BOLD);
+
+        let mut lines = vec![
+            Line::raw(format!("Signals:          {}", stats.signal_count)),
+            Line::raw(format!("Value changes:    {}", stats.total_changes)),
+            Line::raw(format!("Time span:        0 - {}", stats.max_time)),
+            Line::raw(format!(
+                "Estimated memory: {}",
+                format_bytes(stats.memory_bytes)
+            )),
+            Line::raw(""),
+            Line::styled("Busiest signals", header),
+        ];
+        for (signal, changes) in stats.busiest_signals() {
+            lines.push(Line::raw(format!("{:>10}  {}", changes, signal)));
+        }
+
+        // Calculate a centered rectangle just big enough for the summary
+        let popup_width = area.width.min(60);
+        let popup_height = area.height.min(lines.len() as u16 + 2);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title("Statistics [Esc to close]")
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Limit scrolling so the last line stays at the bottom
+        let max_scroll = lines.len().saturating_sub(inner_area.height as usize);
+        let scroll = state.stats_scroll.min(max_scroll);
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines)
+            .scroll((scroll as u16, 0))
+            .render(inner_area, buf);
+        state.stats_scroll = scroll;
+    }
+}
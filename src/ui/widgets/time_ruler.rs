@@ -1,5 +1,5 @@
 // digisurf/src/ui/widgets/time_ruler.rs
-use crate::state::AppState;
+use crate::{state::AppState, time_format::format_time, types::Viewport};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
@@ -10,73 +10,148 @@ use ratatui::{
 #[derive(Default, Copy, Clone)]
 pub struct TimeRulerWidget {}
 
+/// Character drawn at tick positions whose label was suppressed to avoid overlapping a neighbour.
+const TICK_CHAR: char = '|';
+
 impl StatefulWidget for TimeRulerWidget {
     type State = AppState;
 
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        if state.waveform_data.max_time == 0 {
+        if state.waveform_data.max_time == 0 || area.width == 0 || area.height == 0 {
             return;
         }
 
         // Draw the ruler background
-        for x in area.left()..area.right() {
-            for y in area.top()..area.bottom() {
-                buf[(x, y)].set_bg(Color::DarkGray);
-            }
+        buf.set_style(area, state.theme().ruler());
+
+        let tick_style = Style::default().fg(Color::Yellow);
+
+        // A zero-width time window can't be subdivided, so only its start time is labelled
+        if state.time_range == 0 {
+            let label = format_time(state.time_start, state.time_zero);
+            draw_label(buf, area, 0, &label, tick_style);
+            return;
         }
 
-        // Calculate appropriate time intervals based on current zoom level
-        let time_span = state.time_range;
-        let width = area.width as u64;
-        let tick_interval = calculate_tick_interval(time_span, width);
+        // Ticks line up with the reference time, so it is always labelled 0 when in view
+        let origin = state.time_zero.unwrap_or(0);
+        let ticks = visible_ticks(state.time_start, state.time_range, area.width, origin);
+        let labels: Vec<String> = ticks
+            .iter()
+            .map(|(_, time)| format_time(*time, state.time_zero))
+            .collect();
+        let label_starts = place_labels(&ticks, &labels, area.width);
 
-        // Draw ticks and labels
-        let mut time = (state.time_start / tick_interval) * tick_interval;
-        if time < state.time_start {
-            time += tick_interval;
+        // Ticks without a label get a small mark so the scale is still readable. Labels are drawn
+        // afterwards so they always win over a neighbouring tick mark.
+        for ((x_pos, _), label_start) in ticks.iter().zip(&label_starts) {
+            if label_start.is_none() {
+                buf[(area.x + x_pos, area.y)]
+                    .set_char(TICK_CHAR)
+                    .set_style(tick_style);
+            }
         }
 
-        while time <= state.time_start + time_span {
-            let x_pos = time_to_x(time, state.time_start, time_span, area.width);
-            if x_pos < area.width {
-                // Draw tick
-                let tick_style = Style::default().fg(Color::Yellow);
-                if area.height >= 1 {
-                    buf[(area.x + x_pos, area.y + area.height - 1)].set_style(tick_style);
-                }
-                if area.height >= 2 {
-                    buf[(area.x + x_pos, area.y + area.height - 2)].set_style(tick_style);
-                }
-
-                // Draw time label
-                let label = format!("{}", time);
-                let label_start = x_pos.saturating_sub(label.len() as u16 / 2);
-                for (i, c) in label.chars().enumerate() {
-                    let x = area.x + label_start + i as u16;
-                    if x < area.right() {
-                        buf[(x, area.y)].set_char(c).set_style(tick_style);
-                    }
-                }
+        for (label, label_start) in labels.iter().zip(&label_starts) {
+            if let Some(start) = label_start {
+                draw_label(buf, area, *start, label, tick_style);
             }
-            time += tick_interval;
         }
     }
 }
 
-// Helper function to convert time to x position
-fn time_to_x(time: u64, time_start: u64, time_span: u64, width: u16) -> u16 {
-    ((time.saturating_sub(time_start)) as f64 / time_span as f64 * width as f64) as u16
+// Helper function to draw a label starting at the given column, clipped to the ruler area
+fn draw_label(buf: &mut Buffer, area: Rect, start: u16, label: &str, style: Style) {
+    for (i, c) in label.chars().enumerate() {
+        let x = area.x + start + i as u16;
+        if x >= area.right() {
+            break;
+        }
+        buf[(x, area.y)].set_char(c).set_style(style);
+    }
+}
+
+// Helper function to collect the (x position, time) of every tick within the visible window, at
+// whole intervals from `origin`. Ticks that round to the same column as the previous tick are
+// dropped.
+fn visible_ticks(time_start: u64, time_span: u64, width: u16, origin: u64) -> Vec<(u16, u64)> {
+    let tick_interval = calculate_tick_interval(time_span, width as u64);
+    let viewport = Viewport {
+        time_start,
+        time_range: time_span,
+        width,
+    };
+
+    let mut time = time_start - time_start % tick_interval + origin % tick_interval;
+    if time < time_start {
+        time += tick_interval;
+    }
+
+    let mut ticks: Vec<(u16, u64)> = Vec::new();
+    while time <= time_start.saturating_add(time_span) {
+        let x_pos = viewport.column_of(time);
+        if x_pos >= width {
+            break;
+        }
+        if ticks.last().is_none_or(|(last_x, _)| *last_x != x_pos) {
+            ticks.push((x_pos, time));
+        }
+        time += tick_interval;
+    }
+    ticks
+}
+
+// Helper function to compute the column span [start, end) of a label centered on a tick, shifted
+// so that it stays inside the ruler whenever it is narrower than the ruler
+fn label_span(x_pos: u16, label_width: u16, width: u16) -> (u16, u16) {
+    let start = x_pos
+        .saturating_sub(label_width / 2)
+        .min(width.saturating_sub(label_width));
+    (start, start + label_width)
+}
+
+// Helper function to decide which tick labels are drawn and where they start. The leftmost and
+// rightmost labels are always kept, and the labels in between are only drawn if they leave at
+// least one blank column to both of their drawn neighbours.
+fn place_labels(ticks: &[(u16, u64)], labels: &[String], width: u16) -> Vec<Option<u16>> {
+    let mut placed = vec![None; ticks.len()];
+    if ticks.is_empty() {
+        return placed;
+    }
+
+    let spans: Vec<(u16, u16)> = ticks
+        .iter()
+        .zip(labels)
+        .map(|((x_pos, _), label)| label_span(*x_pos, label.len() as u16, width))
+        .collect();
+
+    let last = ticks.len() - 1;
+    placed[0] = Some(spans[0].0);
+    let mut previous_end = spans[0].1;
+
+    // The rightmost label only gives way if it would collide with the leftmost one
+    let mut right_limit = u16::MAX;
+    if last > 0 && spans[last].0 > previous_end {
+        placed[last] = Some(spans[last].0);
+        right_limit = spans[last].0;
+    }
+
+    for i in 1..last {
+        let (start, end) = spans[i];
+        if start > previous_end && end < right_limit {
+            placed[i] = Some(start);
+            previous_end = end;
+        }
+    }
+
+    placed
 }
 
-// Helper function to calculate appropriate tick intervals
-fn calculate_tick_interval(time_span: u64, width: u64) -> u64 {
+/// Time between ticks of a ruler `width` columns wide showing `time_span` time units.
+pub fn calculate_tick_interval(time_span: u64, width: u64) -> u64 {
     // Target roughly 5-10 ticks across the visible width
     let target_num_ticks = width / 10;
-    let approx_interval = if target_num_ticks > 0 {
-        time_span / target_num_ticks
-    } else {
-        time_span
-    };
+    let approx_interval = time_span.checked_div(target_num_ticks).unwrap_or(time_span);
 
     // Round to a nice number (1, 2, 5, 10, 20, 50, 100, etc.)
     let magnitude = if approx_interval > 0 {
@@ -105,22 +180,30 @@ fn calculate_tick_interval(time_span: u64, width: u64) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use insta::assert_snapshot;
+    use ratatui::{backend::TestBackend, Terminal};
 
-    #[test]
-    fn test_time_to_x() {
-        let time_start = 10;
-        let time_span = 100;
-        let width = 50;
+    fn render_ruler(
+        width: u16,
+        time_start: u64,
+        time_range: u64,
+        max_time: u64,
+        time_zero: Option<u64>,
+    ) -> Terminal<TestBackend> {
+        let mut state = AppState::default();
+        state.time_zero = time_zero;
+        state.waveform_data.max_time = max_time;
+        state.time_start = time_start;
+        state.time_range = time_range;
 
-        assert_eq!(time_to_x(time_start, time_start, time_span, width), 0);
-        assert_eq!(
-            time_to_x(time_start + time_span / 2, time_start, time_span, width),
-            width / 2
-        );
-        assert_eq!(
-            time_to_x(time_start + time_span, time_start, time_span, width),
-            width
-        );
+        let mut terminal = Terminal::new(TestBackend::new(width, 1)).unwrap();
+        terminal
+            .draw(|f| {
+                let size = f.area();
+                TimeRulerWidget::default().render(size, f.buffer_mut(), &mut state);
+            })
+            .unwrap();
+        terminal
     }
 
     #[test]
@@ -130,4 +213,70 @@ mod tests {
 
         assert_ne!(calculate_tick_interval(time_span, width), 0);
     }
+
+    #[test]
+    fn test_place_labels_skips_overlapping_labels() {
+        // Ticks every two columns with three character labels can't all be labelled
+        let ticks: Vec<(u16, u64)> = (0..10).map(|i| (i * 2, 100 + i as u64)).collect();
+        let labels: Vec<String> = ticks.iter().map(|(_, t)| t.to_string()).collect();
+        let placed = place_labels(&ticks, &labels, 20);
+
+        // Leftmost and rightmost labels are always drawn
+        assert_eq!(placed[0], Some(0));
+        assert_eq!(placed[9], Some(17));
+
+        // No two drawn labels touch each other
+        let spans: Vec<(u16, u16)> = placed
+            .iter()
+            .filter_map(|start| *start)
+            .map(|start| (start, start + 3))
+            .collect();
+        for pair in spans.windows(2) {
+            assert!(pair[0].1 < pair[1].0);
+        }
+        assert!(placed.iter().any(|start| start.is_none()));
+    }
+
+    #[test]
+    fn test_place_labels_window_smaller_than_label() {
+        let ticks = vec![(0, 123456789), (2, 123456790)];
+        let labels: Vec<String> = ticks.iter().map(|(_, t)| t.to_string()).collect();
+        let placed = place_labels(&ticks, &labels, 4);
+        assert_eq!(placed, vec![Some(0), None]);
+    }
+
+    #[test]
+    fn test_render_zero_time_range_does_not_panic() {
+        let terminal = render_ruler(40, 25, 0, 100, None);
+        assert_eq!(terminal.backend().buffer()[(0, 0)].symbol(), "2");
+    }
+
+    #[test]
+    fn test_render_time_ruler_extreme_zoom_in() {
+        assert_snapshot!(render_ruler(80, 123456, 3, 1000000, None).backend());
+    }
+
+    #[test]
+    fn test_render_time_ruler_extreme_zoom_out() {
+        assert_snapshot!(render_ruler(80, 0, 987654321000, 987654321000, None).backend());
+    }
+
+    #[test]
+    fn test_render_time_ruler_narrow_terminal() {
+        assert_snapshot!(render_ruler(40, 1000, 25, 5000, None).backend());
+    }
+
+    #[test]
+    fn test_render_time_ruler_relative_to_time_zero() {
+        // Ticks are labelled relative to time 1003, with earlier ones negative
+        let terminal = render_ruler(80, 990, 40, 5000, Some(1003));
+        let ruler: String = (0..80)
+            .map(|x| terminal.backend().buffer()[(x, 0)].symbol())
+            .collect();
+        let labels: Vec<&str> = ruler.split_whitespace().collect();
+        assert_eq!(labels.first(), Some(&"-10"));
+        assert!(labels.contains(&"0"));
+        assert!(labels.contains(&"10"));
+        assert_eq!(labels.last(), Some(&"25"));
+    }
 }
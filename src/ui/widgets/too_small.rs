@@ -0,0 +1,41 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Clear, Paragraph, Widget, Wrap},
+};
+
+/// Shown instead of the UI when the terminal is too small to draw it.
+#[derive(Default, Copy, Clone)]
+pub struct TooSmallWidget {
+    pub min_width: u16,
+    pub min_height: u16,
+}
+
+impl Widget for TooSmallWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+
+        let lines = vec![
+            Line::from("Terminal too small"),
+            Line::styled(
+                format!("need at least {}x{}", self.min_width, self.min_height),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        // Center the two lines vertically, unless they wrap and need all the rows there are
+        let fits = lines.iter().all(|line| line.width() <= area.width as usize);
+        let offset = if fits {
+            area.height.saturating_sub(2) / 2
+        } else {
+            0
+        };
+
+        let paragraph = Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
+        let message_area = Rect::new(area.x, area.y + offset, area.width, area.height - offset);
+        paragraph.render(message_area, buf);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::state::AppState;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct TraceWidget {}
+
+impl TraceWidget {
+    fn format_row(time: &str, value: &str, delta: &str, count: &str) -> String {
+        format!("{:>12}  {:>16}  {:>12}  {:>6}", time, value, delta, count)
+    }
+}
+
+impl StatefulWidget for TraceWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let Some(trace) = &state.trace else {
+            return;
+        };
+
+        // Calculate a centered rectangle for the popup
+        let popup_width = area.width.min(56);
+        let popup_height = area.height.min(trace.entries.len() as u16 + 3);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title(format!("Trace of {} [Esc to close]", trace.signal))
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Limit scrolling so the last entry stays at the bottom, below the header
+        let visible_rows = (inner_area.height as usize).saturating_sub(1);
+        let scroll = state
+            .trace_scroll
+            .min(trace.entries.len().saturating_sub(visible_rows));
+
+        let mut lines = vec![Line::styled(
+            Self::format_row("Time", "Value", "Delta", "Count"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        for (i, entry) in trace
+            .entries
+            .iter()
+            .enumerate()
+            .skip(scroll)
+            .take(visible_rows)
+        {
+            // The marker can move backwards, so the time since the previous entry has a sign
+            let delta = match i.checked_sub(1).map(|previous| &trace.entries[previous]) {
+                Some(previous) if entry.time >= previous.time => {
+                    format!("+{}", entry.time - previous.time)
+                }
+                Some(previous) => format!("-{}", previous.time - entry.time),
+                None => "-".to_string(),
+            };
+            let count = if entry.count > 1 {
+                format!("×{}", entry.count)
+            } else {
+                String::new()
+            };
+            lines.push(Line::raw(Self::format_row(
+                &entry.time.to_string(),
+                &entry.value,
+                &delta,
+                &count,
+            )));
+        }
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines).render(inner_area, buf);
+        state.trace_scroll = scroll;
+    }
+}
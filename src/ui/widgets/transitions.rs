@@ -0,0 +1,81 @@
+use crate::state::AppState;
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct TransitionsWidget {}
+
+impl TransitionsWidget {
+    fn format_row(time: &str, old_value: &str, new_value: &str, delta: &str) -> String {
+        format!(
+            "{:>12}  {:>16}  {:>16}  {:>12}",
+            time, old_value, new_value, delta
+        )
+    }
+}
+
+impl StatefulWidget for TransitionsWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let Some(popup) = &mut state.transitions_popup else {
+            return;
+        };
+
+        // Calculate a centered rectangle for the popup
+        let popup_width = area.width.min(70);
+        let popup_height = area.height.min(20);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title(format!("Transitions of {} [Enter to jump]", popup.signal))
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        // Keep the selected row in view below the header
+        let visible_rows = (inner_area.height as usize).saturating_sub(1);
+        if popup.selected < popup.scroll {
+            popup.scroll = popup.selected;
+        } else if visible_rows > 0 && popup.selected >= popup.scroll + visible_rows {
+            popup.scroll = popup.selected + 1 - visible_rows;
+        }
+
+        let mut lines = vec![Line::styled(
+            Self::format_row("Time", "Old", "New", "Delta"),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        for (i, transition) in popup
+            .transitions
+            .iter()
+            .enumerate()
+            .skip(popup.scroll)
+            .take(visible_rows)
+        {
+            let row = Self::format_row(
+                &transition.time.to_string(),
+                transition.old_value.as_deref().unwrap_or("-"),
+                &transition.new_value,
+                &transition
+                    .delta
+                    .map(|delta| delta.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            );
+            let style = if i == popup.selected {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            lines.push(Line::styled(row, style));
+        }
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines).render(inner_area, buf);
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{state::AppState, time_format::format_time};
+use ratatui::{
+    prelude::{Buffer, Rect},
+    style::{Modifier, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, Paragraph, StatefulWidget, Widget},
+};
+
+#[derive(Default, Clone, Copy, Eq, PartialEq)]
+pub struct ViewListWidget {}
+
+impl StatefulWidget for ViewListWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        // There are at most nine views, so they always fit without scrolling
+        let popup_width = area.width.min(60);
+        let popup_height = area.height.min(state.view_bookmarks.len() as u16 + 3);
+        let popup_x = area.x + (area.width.saturating_sub(popup_width)) / 2;
+        let popup_y = area.y + (area.height.saturating_sub(popup_height)) / 2;
+        let popup_area = Rect::new(popup_x, popup_y, popup_width, popup_height);
+
+        let block = Block::default()
+            .title("Saved views [1-9 to load, Esc to close]")
+            .borders(Borders::ALL);
+        let inner_area = block.inner(popup_area);
+
+        let mut lines = vec![Line::styled(
+            format!(
+                "{:<4} {:>12} {:>12}  {}",
+                "Slot", "Start", "End", "Selected"
+            ),
+            Style::default().add_modifier(Modifier::BOLD),
+        )];
+        for (slot, view) in &state.view_bookmarks {
+            let selected = view.selected_signal.as_deref().unwrap_or("-");
+            lines.push(Line::raw(format!(
+                "{:<4} {:>12} {:>12}  {}",
+                slot,
+                format_time(view.time_start, state.time_zero),
+                format_time(view.time_start + view.time_range, state.time_zero),
+                selected
+            )));
+        }
+
+        Clear.render(popup_area, buf);
+        block.render(popup_area, buf);
+        Paragraph::new(lines).render(inner_area, buf);
+    }
+}
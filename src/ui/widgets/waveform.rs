@@ -1,18 +1,26 @@
 use crate::{
-    constants::{DRAG_COLOR, PRIMARY_MARKER_COLOR, SECONDARY_MARKER_COLOR, WAVEFORM_HEIGHT},
+    bit_split::value_bits,
+    config::{LabelTruncation, RenderStyle},
+    decoders::{uart::FRAMING_ERROR, Annotation},
     parsers::types::{Value, WaveValue},
     state::AppState,
+    types::{DragKind, Highlight, Viewport},
 };
 use ratatui::{
-    layout::Rect,
+    layout::{Position, Rect},
     prelude::Buffer,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
+    symbols::Marker,
     widgets::{
         canvas::{Canvas, Line},
         Block, Paragraph, StatefulWidget, Widget,
     },
 };
 
+/// Columns from one dash of the dashed line Z is drawn as to the next. Each dash is half a column
+/// long, so it fills a single column.
+const Z_DASH_PERIOD: f64 = 2.0;
+
 #[derive(Default, Copy, Clone)]
 pub struct WaveformWidget {}
 
@@ -22,71 +30,46 @@ impl WaveformWidget {
         buf: &mut Buffer,
         area: Rect,
         values: &[(u64, WaveValue)],
-        time_offset: u64,
-        window_size: u64,
+        state: &AppState,
         style: Style,
     ) {
+        let levels = SignalLevels::new(state.config.ui.render_style, area.height);
+        let lines = binary_signal_lines(
+            values,
+            state.time_start,
+            state.time_range,
+            area.width,
+            style.fg.unwrap_or(Color::White),
+            UnknownColors::of(state),
+            &levels,
+        );
         let canvas = Canvas::default()
             .block(Block::default())
+            .marker(levels.marker)
             .x_bounds([0.0, area.width as f64])
-            .y_bounds([0.0, 2.0])
+            .y_bounds([0.0, levels.top])
             .paint(|ctx| {
-                let width = area.width as f64;
-                let mut last_value: Option<(f64, f64, Color)> = None;
-
-                let time_to_x = |t: u64| -> f64 {
-                    // Round to get precise pixel alignment
-                    ((t - time_offset) as f64 / window_size as f64 * width).round()
-                };
-
-                for (t, v) in values {
-                    let x = time_to_x(*t);
-                    let (y, color) = match v {
-                        WaveValue::Binary(Value::V1) => (1.5, style.fg.unwrap_or(Color::White)),
-                        WaveValue::Binary(Value::V0) => (0.5, style.fg.unwrap_or(Color::White)),
-                        WaveValue::Binary(Value::VZ) => (1.0, Color::Magenta),
-                        WaveValue::Binary(Value::VX) => (1.0, Color::Red),
-                        _ => (1.0, style.fg.unwrap_or(Color::White)),
-                    };
-
-                    if let Some((prev_y, prev_x, prev_color)) = last_value {
-                        // Draw horizontal line from last position
-                        ctx.draw(&Line {
-                            x1: prev_x,
-                            y1: prev_y,
-                            x2: x,
-                            y2: prev_y,
-                            color: prev_color,
-                        });
-
-                        // If value changed, draw vertical transition
-                        if prev_y != y {
-                            ctx.draw(&Line {
-                                x1: x,
-                                y1: prev_y,
-                                x2: x,
-                                y2: y,
-                                color,
-                            });
-                        }
-                    }
-
-                    last_value = Some((y, x, color));
-                }
-
-                // Draw remaining horizontal line to the end
-                if let Some((y, x, color)) = last_value {
-                    ctx.draw(&Line {
-                        x1: x,
-                        y1: y,
-                        x2: width,
-                        y2: y,
-                        color,
-                    });
+                for line in &lines {
+                    ctx.draw(line);
                 }
             });
 
         canvas.render(area, buf);
+
+        // Without colors X and Z can't be told from each other or from the signal, so spell them
+        // out along the signal's top row
+        if state.theme().is_mono() {
+            for segment in signal_segments(values, state.time_start, state.time_range, area.width) {
+                let glyph = match segment.value() {
+                    WaveValue::Binary(Value::VX) => 'x',
+                    WaveValue::Binary(Value::VZ) => 'z',
+                    _ => continue,
+                };
+                for x in segment.start_x..segment.end_x.min(area.width) {
+                    buf[(area.x + x, area.y)].set_char(glyph);
+                }
+            }
+        }
     }
 
     fn draw_bus_signal(
@@ -94,52 +77,59 @@ impl WaveformWidget {
         buf: &mut Buffer,
         area: Rect,
         values: &[(u64, WaveValue)],
-        time_start: u64,
-        time_range: u64,
+        signed_width: Option<usize>,
+        state: &AppState,
         style: Style,
     ) {
-        // Calculate transition points
         let width = area.width as f64;
-        let mut transition_points = Vec::new();
-
-        // Use the same time_to_x conversion as in draw_binary_signal
-        let time_to_x = |t: u64| -> u16 {
-            ((t - time_start) as f64 / time_range as f64 * width).round() as u16
-        };
-
-        for (_, (t, _)) in values.iter().enumerate() {
-            // Convert the time directly using the window
-            let x_pos = time_to_x(*t);
-            transition_points.push(x_pos);
-        }
-        transition_points.push(area.width);
+        let levels = SignalLevels::new(state.config.ui.render_style, area.height);
+        let segments = signal_segments(values, state.time_start, state.time_range, area.width);
+        let unknown = UnknownColors::of(state);
 
-        // Draw the bus signal with a straight line
+        // Draw the bus signal with a straight line, or a doubled line for segments with unknown
+        // bits so they stand out even when their label doesn't fit
         let canvas = Canvas::default()
             .block(Block::default())
+            .marker(levels.marker)
             .x_bounds([0.0, width])
-            .y_bounds([0.0, 2.0])
+            .y_bounds([0.0, levels.top])
             .paint(|ctx| {
-                // Draw a straight line in the middle
-                let y_middle = 1.0;
-                ctx.draw(&Line {
-                    x1: 0.0,
-                    y1: y_middle,
-                    x2: width,
-                    y2: y_middle,
-                    color: style.fg.unwrap_or(Color::White),
-                });
+                for segment in &segments {
+                    let x1 = segment.start_x as f64;
+                    let x2 = segment.end_x as f64;
+                    match unknown_bits_color(segment.value(), unknown) {
+                        Some(color) => {
+                            for y in [levels.low, levels.high] {
+                                ctx.draw(&Line {
+                                    x1,
+                                    y1: y,
+                                    x2,
+                                    y2: y,
+                                    color,
+                                });
+                            }
+                        }
+                        None => ctx.draw(&Line {
+                            x1,
+                            y1: levels.mid,
+                            x2,
+                            y2: levels.mid,
+                            color: style.fg.unwrap_or(Color::White),
+                        }),
+                    }
+                }
 
-                // Draw vertical transitions at change points
-                for i in 0..transition_points.len() - 1 {
-                    let x = transition_points[i] as f64;
+                // Draw vertical transitions at change points, but not where the value was carried
+                // in from before the window
+                for segment in segments.iter().filter(|segment| !segment.carried_in) {
+                    let x = segment.start_x as f64;
 
                     // Draw transition line
                     ctx.draw(&Line {
                         x1: x,
-                        y1: 0.5,
+                        y1: levels.low,
                         x2: x,
-                        y2: 1.5,
+                        y2: levels.high,
                         color: style.fg.unwrap_or(Color::White),
                     });
                 }
@@ -147,143 +137,569 @@ impl WaveformWidget {
 
         canvas.render(area, buf);
 
-        // Draw bus value labels in the middle of segments
-        for (i, (_, v)) in values.iter().enumerate() {
-            if let WaveValue::Bus(value) = v {
-                // Calculate midpoint between transitions
-                let start_x = transition_points[i];
-                let end_x = transition_points[i + 1];
-                let segment_width = end_x.saturating_sub(start_x);
-                let value_len = value.len() as u16;
-
-                // Only draw if there's enough space
-                if segment_width > value_len {
-                    let midpoint = start_x + (segment_width - value_len) / 2;
-                    let label_area = Rect::new(area.x + midpoint, area.y, value_len, 1);
-
-                    Paragraph::new(format!("{:X}", v))
-                        .style(style)
-                        .render(label_area, buf);
+        let label_row = Rect {
+            y: area.y + levels.label_row,
+            height: 1,
+            ..area
+        };
+        let labels = BusLabels {
+            unknown,
+            truncation: state.config.ui.bus_label_truncation,
+            signed_width,
+        };
+        draw_bus_labels(buf, label_row, &segments, style, labels);
+    }
+
+    // Draws a binary signal in a single row: the upper half of the cells while it is high, the
+    // lower half while it is low, and full cells where it changes. X is a double line and Z a dashed
+    // line between the two in their colors, or they are spelled out without colors.
+    fn draw_compact_binary_signal(
+        &self,
+        buf: &mut Buffer,
+        area: Rect,
+        values: &[(u64, WaveValue)],
+        state: &AppState,
+        style: Style,
+    ) {
+        let fg = style.fg.unwrap_or(Color::White);
+        let mono = state.theme().is_mono();
+        let unknown = UnknownColors::of(state);
+        let segments = signal_segments(values, state.time_start, state.time_range, area.width);
+        let mut last_value: Option<&WaveValue> = None;
+        for segment in &segments {
+            let (glyph, color) = match segment.value() {
+                WaveValue::Binary(Value::V1) => ('▀', fg),
+                WaveValue::Binary(Value::V0) => ('▄', fg),
+                WaveValue::Binary(Value::VX) => (if mono { 'x' } else { '═' }, unknown.x),
+                WaveValue::Binary(Value::VZ) => (if mono { 'z' } else { '╌' }, unknown.z),
+                _ => ('─', fg),
+            };
+
+            // Like the full height signal, there is no transition where the window is entered
+            let run = match last_value {
+                Some(_) => segment.run,
+                None => {
+                    let entered = segment.run.partition_point(|(t, _)| *t <= state.time_start);
+                    &segment.run[entered.saturating_sub(1)..]
                 }
+            };
+            let mut previous = last_value.unwrap_or(&run[0].1);
+            let mut changed = false;
+            for (_, value) in run {
+                changed |= value != previous;
+                previous = value;
+            }
+
+            for x in segment.start_x..segment.end_x.min(area.width) {
+                let glyph = if changed && x == segment.start_x {
+                    '█'
+                } else {
+                    glyph
+                };
+                buf[(area.x + x, area.y)].set_char(glyph).set_fg(color);
+            }
+            last_value = Some(segment.value());
+        }
+    }
+
+    // Draws a bus signal in a single row as only its values, with a bar at each change
+    fn draw_compact_bus_signal(
+        &self,
+        buf: &mut Buffer,
+        area: Rect,
+        values: &[(u64, WaveValue)],
+        signed_width: Option<usize>,
+        state: &AppState,
+        style: Style,
+    ) {
+        let segments = signal_segments(values, state.time_start, state.time_range, area.width);
+        let fg = style.fg.unwrap_or(Color::White);
+        for segment in segments.iter().filter(|segment| !segment.carried_in) {
+            if segment.start_x < area.width {
+                buf[(area.x + segment.start_x, area.y)]
+                    .set_char('│')
+                    .set_fg(fg);
             }
         }
+        let labels = BusLabels {
+            unknown: UnknownColors::of(state),
+            truncation: state.config.ui.bus_label_truncation,
+            signed_width,
+        };
+        draw_bus_labels(buf, area, &segments, style, labels);
     }
 
     pub fn draw_signals(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
-        let time_start = state.time_start;
-        let time_range = state.time_range;
+        let theme = state.theme();
 
-        // Calculate how many signals we can display in the visible area
-        let visible_signals = area.height as usize / WAVEFORM_HEIGHT;
+        // A line separates the pinned signals from the scrolled ones
+        let pinned_height = state.pinned_height();
+        if pinned_height > 0 && pinned_height <= area.height {
+            let separator_y = area.y + pinned_height - 1;
+            for x in area.left()..area.right() {
+                buf[(x, separator_y)].set_char('─').set_fg(Color::DarkGray);
+            }
+        }
 
         // Only render signals that are within the scroll viewport
-        for (rel_idx, (abs_idx, signal_name)) in state
-            .displayed_signals
-            .iter()
-            .enumerate()
-            .skip(state.signal_scroll_offset)
-            .take(visible_signals)
-            .enumerate()
-        {
+        for (abs_idx, top, row_height) in state.visible_signal_rows(area.height) {
+            let signal_name = &state.displayed_signals[abs_idx];
             let signal_area = Rect::new(
                 area.x,
-                area.y + (rel_idx as u16 * WAVEFORM_HEIGHT as u16),
+                area.y + top,
                 area.width,
-                WAVEFORM_HEIGHT as u16,
+                state.signal_height(signal_name) as u16,
             );
 
-            if signal_area.y >= area.bottom() {
-                break; // Don't render signals outside of visible area
-            }
-
             let is_selected = abs_idx == state.selected_signal;
             let style = if is_selected {
-                Style::default().fg(Color::Yellow)
+                theme.selected()
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(theme.color(
+                    state
+                        .signal_colors
+                        .get(signal_name)
+                        .copied()
+                        .unwrap_or(Color::White),
+                ))
             };
 
-            // Get visible values for this signal
-            let visible_values = state.get_visible_values(signal_name);
-
-            if visible_values.is_empty() {
-                continue;
-            }
-
-            // Determine if this is a bus or binary signal
-            let is_bus = visible_values
-                .iter()
-                .any(|(_, v)| matches!(v, WaveValue::Bus(_)));
+            let signed_width = state
+                .signed_signals
+                .contains(signal_name)
+                .then(|| state.signal_width(signal_name));
 
-            if is_bus {
-                self.draw_bus_signal(
+            // Decoded rows have no values, and nothing to compare in the `:diff` file
+            if let Some(annotations) = state.decoded.get(signal_name) {
+                self.draw_annotations(buf, signal_area, annotations, state, style);
+            } else {
+                let values = state.get_visible_values(signal_name);
+                self.draw_signal(
                     buf,
                     signal_area,
-                    &visible_values,
-                    time_start,
-                    time_range,
+                    values,
+                    state.first_value_time(signal_name),
+                    signed_width,
+                    state,
                     style,
                 );
-            } else {
-                self.draw_binary_signal(
+                self.draw_value_highlights(buf, signal_area, signal_name, values, state);
+                self.draw_glitches(buf, signal_area, signal_name, state);
+            }
+
+            // The same signal from the file loaded with `:diff` goes in the row below
+            let diff_area = Rect {
+                y: signal_area.bottom(),
+                ..signal_area
+            };
+            if state.diff.is_some()
+                && diff_area.y < area.bottom()
+                && !state.decoded.contains_key(signal_name)
+            {
+                let diff_values = state.get_visible_diff_values(signal_name);
+                let first_diff_time = state
+                    .diff
+                    .as_ref()
+                    .and_then(|diff| diff.data.values.get(signal_name))
+                    .and_then(|values| values.first())
+                    .map(|(time, _)| *time);
+                self.draw_signal(
                     buf,
-                    signal_area,
-                    &visible_values,
-                    time_start,
-                    time_range,
+                    diff_area,
+                    diff_values,
+                    first_diff_time,
+                    signed_width,
+                    state,
                     style,
                 );
             }
+
+            // The canvas only takes a color, so without colors the selected signal is drawn bold
+            if is_selected && theme.is_mono() {
+                let row = Rect {
+                    height: row_height,
+                    ..signal_area
+                };
+                buf.set_style(row.intersection(area), Modifier::BOLD);
+            }
         }
     }
 
-    pub fn draw_markers(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
-        let time_start = state.time_start;
-        let time_range = state.time_range;
-        let width = area.width as f64;
+    // Draws the visible values of one signal as a bus or a binary signal, after a dashed line up to
+    // the signal's first value at `first_time`. Bus values are labeled as signed decimal numbers
+    // `signed_width` bits wide if given, else in hex.
+    #[allow(clippy::too_many_arguments)]
+    fn draw_signal(
+        &self,
+        buf: &mut Buffer,
+        area: Rect,
+        values: &[(u64, WaveValue)],
+        first_time: Option<u64>,
+        signed_width: Option<usize>,
+        state: &AppState,
+        style: Style,
+    ) {
+        // Determine if this is a bus or binary signal
+        let is_bus = values.iter().any(|(_, v)| matches!(v, WaveValue::Bus(_)));
+
+        let compact = area.height == 1;
+        if is_bus && compact {
+            self.draw_compact_bus_signal(buf, area, values, signed_width, state, style);
+        } else if is_bus {
+            self.draw_bus_signal(buf, area, values, signed_width, state, style);
+        } else if !values.is_empty() && compact {
+            self.draw_compact_binary_signal(buf, area, values, state, style);
+        } else if !values.is_empty() {
+            self.draw_binary_signal(buf, area, values, state, style);
+        }
+
+        if let Some(first_time) = first_time {
+            if state.config.ui.show_no_data_yet {
+                self.draw_no_data_yet(buf, area, first_time, state);
+            }
+        }
+    }
+
+    /// Tints the columns in which a signal holds a value highlighted with `:highlight`. Columns
+    /// with many values are tinted by the value drawn for them, the last one.
+    fn draw_value_highlights(
+        &self,
+        buf: &mut Buffer,
+        area: Rect,
+        signal: &str,
+        values: &[(u64, WaveValue)],
+        state: &AppState,
+    ) {
+        let rules: Vec<&Highlight> = state
+            .highlights
+            .iter()
+            .filter(|rule| rule.signal == signal)
+            .collect();
+        if rules.is_empty() || state.time_range == 0 {
+            return;
+        }
+
+        let theme = state.theme();
+        for segment in signal_segments(values, state.time_start, state.time_range, area.width) {
+            let Some(value) = segment.value().normalized_hex() else {
+                continue;
+            };
+            let Some(rule) = rules.iter().find(|rule| rule.value == value) else {
+                continue;
+            };
+            for y in area.top()..area.bottom() {
+                for x in segment.start_x..segment.end_x.min(area.width) {
+                    buf[(area.x + x, y)].set_style(theme.value_highlight(rule.color));
+                }
+            }
+        }
+    }
+
+    // Marks each time in the window at which the signal changed more than once with a red `!` in
+    // its top row, since the values it had for no time at all can't be drawn
+    fn draw_glitches(&self, buf: &mut Buffer, area: Rect, signal: &str, state: &AppState) {
+        let Some(glitches) = state.waveform_data.glitches.get(signal) else {
+            return;
+        };
+        if state.time_range == 0 || area.width == 0 || area.height == 0 {
+            return;
+        }
+
+        let time_end = state.time_start + state.time_range;
+        let first = glitches.partition_point(|glitch| glitch.time < state.time_start);
+        let color = state.theme().color(Color::Red);
+        let viewport = state.viewport(area.width);
+        for glitch in glitches[first..]
+            .iter()
+            .take_while(|glitch| glitch.time <= time_end)
+        {
+            let x = viewport.column_of(glitch.time);
+            buf[(area.x + x.min(area.width - 1), area.y)]
+                .set_char('!')
+                .set_fg(color);
+        }
+    }
+
+    /// Draws the annotations of a decoded row like bus values, with each label centered in its
+    /// span where it fits, and like a compact bus in a single row. Annotations with framing errors
+    /// are labelled in red.
+    fn draw_annotations(
+        &self,
+        buf: &mut Buffer,
+        area: Rect,
+        annotations: &[Annotation],
+        state: &AppState,
+        style: Style,
+    ) {
+        if state.time_range == 0 {
+            return;
+        }
+        let time_end = state.time_start + state.time_range;
+        let visible: Vec<&Annotation> = annotations
+            .iter()
+            .filter(|annotation| annotation.end > state.time_start && annotation.start < time_end)
+            .collect();
+        let spans: Vec<(u64, u64)> = visible
+            .iter()
+            .map(|annotation| (annotation.start, annotation.end))
+            .collect();
+        let columns = mismatch_columns(&spans, area.width, state);
+        let color = style.fg.unwrap_or(Color::White);
+
+        // Ends cut off by the window aren't transitions
+        let ends = |annotation: &Annotation, (left, right): (u16, u16)| {
+            [
+                (annotation.start >= state.time_start).then_some(left),
+                (annotation.end <= time_end).then_some(right),
+            ]
+            .into_iter()
+            .flatten()
+        };
 
-        // Anonymous helper function to draw a single marker
-        let mut draw_marker = |marker_time: u64, color: Color| {
-            if self.is_marker_visible(marker_time, time_start, time_range) {
-                // Calculate x position
-                let x_ratio = (marker_time - time_start) as f64 / time_range as f64;
-                let x_pos = (x_ratio * width).round() as u16;
-
-                // Draw vertical line
-                let marker_line = Canvas::default()
-                    .block(Block::default())
-                    .x_bounds([0.0, width])
-                    .y_bounds([0.0, area.height as f64])
-                    .paint(|ctx| {
+        let levels = SignalLevels::new(state.config.ui.render_style, area.height);
+        let canvas = Canvas::default()
+            .block(Block::default())
+            .marker(levels.marker)
+            .x_bounds([0.0, area.width as f64])
+            .y_bounds([0.0, levels.top])
+            .paint(|ctx| {
+                for (annotation, (left, right)) in visible.iter().zip(&columns) {
+                    ctx.draw(&Line {
+                        x1: *left as f64,
+                        y1: levels.mid,
+                        x2: *right as f64,
+                        y2: levels.mid,
+                        color,
+                    });
+                    for x in ends(annotation, (*left, *right)) {
                         ctx.draw(&Line {
-                            x1: x_pos as f64,
-                            y1: 0.0,
-                            x2: x_pos as f64,
-                            y2: area.height as f64,
+                            x1: x as f64,
+                            y1: levels.low,
+                            x2: x as f64,
+                            y2: levels.high,
                             color,
                         });
-                    });
+                    }
+                }
+            });
+        if area.height == 1 {
+            for (annotation, (left, right)) in visible.iter().zip(&columns) {
+                for x in ends(annotation, (*left, *right)).filter(|x| *x < area.width) {
+                    buf[(area.x + x, area.y)].set_char('│').set_fg(color);
+                }
+            }
+        } else {
+            canvas.render(area, buf);
+        }
+
+        let label_y = area.y + levels.label_row;
+        for (annotation, (left, right)) in visible.iter().zip(&columns) {
+            let span_width = right - left;
+            let label_len = annotation.label.chars().count() as u16;
+            if span_width > label_len {
+                let midpoint = left + (span_width - label_len) / 2;
+                let label_style = if annotation.label == FRAMING_ERROR {
+                    style.fg(Color::Red)
+                } else {
+                    style
+                };
+                Paragraph::new(annotation.label.as_str())
+                    .style(label_style)
+                    .render(Rect::new(area.x + midpoint, label_y, label_len, 1), buf);
+            }
+        }
+    }
+
+    /// Draws a dim dashed line along the middle of a signal from the left edge to its first value,
+    /// so a signal that has no value yet isn't mistaken for one that isn't drawn. The line stops at
+    /// the first column the signal is drawn in, leaving the first transition in place.
+    fn draw_no_data_yet(&self, buf: &mut Buffer, area: Rect, first_time: u64, state: &AppState) {
+        if first_time <= state.time_start || state.time_range == 0 {
+            return;
+        }
+        let end_x = state
+            .viewport(area.width)
+            .column_of(first_time)
+            .min(area.width);
+        let y = area.y + area.height / 2;
+        let style = state.theme().dimmed();
+        for x in area.x..area.x + end_x {
+            if (area.top()..area.bottom()).any(|row| buf[(x, row)].symbol() != " ") {
+                break;
+            }
+            buf[(x, y)].set_char('╌').set_style(style);
+        }
+    }
 
-                marker_line.render(area, buf);
+    pub fn draw_regions(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
+        if state.time_range == 0 {
+            return;
+        }
+
+        // Tint the columns of each visible region across all signal rows, at least one column wide
+        let theme = state.theme();
+        for region in &state.regions {
+            let Some((left, right)) = interval_columns(region.start, region.end, area.width, state)
+            else {
+                continue;
+            };
+            for y in area.top()..area.bottom() {
+                for x in left..right {
+                    buf[(area.x + x, y)].set_style(theme.region(region.color));
+                }
             }
+        }
+    }
+
+    pub fn draw_comparison(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
+        let Some(comparison) = &state.comparison else {
+            return;
         };
+        if state.time_range == 0 {
+            return;
+        }
 
-        if let Some(marker_time) = state.primary_marker {
-            draw_marker(marker_time, PRIMARY_MARKER_COLOR);
+        let columns = mismatch_columns(&comparison.mismatches, area.width, state);
+        let mismatch_style = state.theme().mismatch();
+        for (index, top, _) in state.visible_signal_rows(area.height) {
+            let signal_name = &state.displayed_signals[index];
+            if *signal_name != comparison.signal_a && *signal_name != comparison.signal_b {
+                continue;
+            }
+
+            let top = area.y + top;
+            for y in top..top + state.signal_height(signal_name) as u16 {
+                for (left, right) in &columns {
+                    for x in *left..*right {
+                        buf[(area.x + x, y)].set_style(mismatch_style);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Highlights where each signal differs from the same signal in the file loaded with `:diff`,
+    /// across both of its rows.
+    pub fn draw_diff(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
+        let Some(diff) = &state.diff else {
+            return;
+        };
+        if state.time_range == 0 {
+            return;
         }
 
-        if let Some(marker_time) = state.secondary_marker {
-            draw_marker(marker_time, SECONDARY_MARKER_COLOR);
+        let mismatch_style = state.theme().mismatch();
+        for (index, top, row_height) in state.visible_signal_rows(area.height) {
+            let Some(mismatches) = diff.mismatches.get(&state.displayed_signals[index]) else {
+                continue;
+            };
+
+            let columns = mismatch_columns(mismatches, area.width, state);
+            let top = area.y + top;
+            for y in top..top + row_height {
+                for &(left, right) in &columns {
+                    for x in left..right {
+                        buf[(area.x + x, y)].set_style(mismatch_style);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Draws the keyboard cursor as a dim dashed line, only over empty cells so it doesn't hide the
+    /// signals it crosses.
+    pub fn draw_cursor(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
+        let Some(cursor) = state.cursor else {
+            return;
+        };
+        if !self.is_marker_visible(cursor, state.time_start, state.time_range) || area.width == 0 {
+            return;
+        }
+
+        let x = area.x
+            + state
+                .viewport(area.width)
+                .column_of(cursor)
+                .min(area.width - 1);
+        for y in area.top()..area.bottom() {
+            let cell = &mut buf[(x, y)];
+            if cell.symbol() == " " {
+                cell.set_char('┊').set_fg(Color::DarkGray);
+            }
+        }
+    }
+
+    pub fn draw_markers(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
+        let time_start = state.time_start;
+        let time_range = state.time_range;
+        let viewport = state.viewport(area.width);
+        let is_mono = state.theme().is_mono();
+        if area.width == 0 {
+            return;
+        }
+
+        // Anonymous helper function to get the column of a marker, if it is visible
+        let marker_x = |marker_time: u64| {
+            self.is_marker_visible(marker_time, time_start, time_range)
+                .then(|| viewport.column_of(marker_time))
+        };
+
+        // Anonymous helper function to draw a single marker. The line is drawn on the left edge of
+        // the marker's column, where a transition at the same time is drawn, or on the right edge
+        // of the last column at the end of the window.
+        let mut draw_marker = |marker_time: u64, color: Color, modifier: Modifier| {
+            if let Some(x_pos) = marker_x(marker_time) {
+                let x = area.x + x_pos.min(area.width - 1);
+
+                // Without colors the marker's column is reversed, keeping the waveform under it
+                if is_mono {
+                    buf.set_style(Rect::new(x, area.y, 1, area.height), modifier);
+                    return;
+                }
+
+                let line = if x_pos < area.width { '⡇' } else { '⢸' };
+                for y in area.top()..area.bottom() {
+                    buf[(x, y)].set_char(line).set_fg(color);
+                }
+            }
+        };
+
+        // Saved markers are drawn first so the primary and secondary markers stay on top. Those in
+        // the same column as either of them aren't drawn at all, so they don't flicker under them.
+        if state.config.ui.show_saved_marker_lines {
+            let marker_columns: Vec<u16> = [state.primary_marker, state.secondary_marker]
+                .into_iter()
+                .flatten()
+                .filter_map(marker_x)
+                .collect();
+            for marker in &state.saved_markers {
+                if marker_x(marker.time).is_some_and(|x| !marker_columns.contains(&x)) {
+                    let color = state.theme().color(marker.color);
+                    draw_marker(marker.time, color, Modifier::REVERSED);
+                }
+            }
+        }
+
+        if let Some(marker_time) = state.primary_marker {
+            draw_marker(
+                marker_time,
+                state.primary_marker_color(),
+                Modifier::REVERSED | Modifier::BOLD,
+            );
         }
 
-        for marker in &state.saved_markers {
-            draw_marker(marker.time, marker.color);
+        if let Some(marker_time) = state.secondary_marker {
+            draw_marker(
+                marker_time,
+                state.secondary_marker_color(),
+                Modifier::REVERSED,
+            );
         }
     }
 
     pub fn draw_drag_selection(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
         if state.is_dragging {
-            if let (Some((start_x, _)), Some((current_x, _))) =
+            if let (Some((start_x, start_time)), Some((current_x, current_time))) =
                 (state.drag_start, state.drag_current)
             {
                 // Determine selection box coordinates
@@ -296,32 +712,483 @@ impl WaveformWidget {
                     let selection_area = Rect::new(area.x + left, area.y, width, area.height);
 
                     // Draw a semi-transparent selection box
-                    for y in selection_area.top()..selection_area.bottom() {
-                        for x in selection_area.left()..selection_area.right() {
-                            buf[(x, y)].set_bg(DRAG_COLOR);
-                        }
-                    }
+                    buf.set_style(selection_area, state.theme().drag());
+                }
+
+                if state.drag_kind == DragKind::Measure {
+                    let delta = current_time.abs_diff(start_time);
+                    let style = state.theme().drag().add_modifier(Modifier::BOLD);
+                    self.draw_measure_readout(buf, area, current_x, delta, style);
                 }
             }
         }
     }
 
+    // Draws the live time delta of a measurement next to the cursor, flipping to the left of the
+    // cursor when there isn't enough room on the right
+    fn draw_measure_readout(
+        &self,
+        buf: &mut Buffer,
+        area: Rect,
+        cursor_x: u16,
+        delta: u64,
+        style: Style,
+    ) {
+        let readout = format!("Δ{}", delta);
+        let readout_width = readout.chars().count() as u16;
+        if readout_width > area.width {
+            return;
+        }
+
+        let x = if cursor_x + 1 + readout_width <= area.width {
+            cursor_x + 1
+        } else {
+            cursor_x.saturating_sub(readout_width)
+        };
+
+        Paragraph::new(readout)
+            .style(style.fg(Color::White))
+            .render(Rect::new(area.x + x, area.y, readout_width, 1), buf);
+    }
+
     fn is_marker_visible(&self, marker_time: u64, time_start: u64, time_range: u64) -> bool {
         marker_time >= time_start && marker_time <= time_start + time_range
     }
 }
 
-impl StatefulWidget for WaveformWidget {
-    type State = AppState;
-
-    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
-        if state.waveform_data.signals.is_empty() {
-            return;
-        }
+// Helper function to get the columns covered by each visible `[start, end)` mismatch interval.
+// Every visible mismatch covers at least one column so that short glitches don't disappear when
+// zoomed out.
+fn mismatch_columns(mismatches: &[(u64, u64)], width: u16, state: &AppState) -> Vec<(u16, u16)> {
+    mismatches
+        .iter()
+        .filter_map(|&(start, end)| interval_columns(start, end, width, state))
+        .collect()
+}
 
-        self.draw_signals(buf, area, &state);
-        self.draw_markers(buf, area, &state);
-        self.draw_drag_selection(buf, area, &state);
+// Helper function to get the columns covered by a `[start, end)` interval, at least one column
+// wide, or None if it is outside the view
+fn interval_columns(start: u64, end: u64, width: u16, state: &AppState) -> Option<(u16, u16)> {
+    let time_start = state.time_start;
+    let time_end = time_start + state.time_range;
+    if end <= time_start || start >= time_end {
+        return None;
+    }
+
+    let viewport = state.viewport(width);
+    let left = viewport
+        .column_of(start.max(time_start))
+        .min(width.saturating_sub(1));
+    let right = viewport.column_of(end.min(time_end)).clamp(left + 1, width);
+    Some((left, right))
+}
+
+// Groups time-sorted values by the terminal column they fall in. Each group is a contiguous run of
+// values, so there are at most `width + 1` groups however many values are visible. The first value
+// may start before the window and is clamped to the left edge.
+fn columns_of_values(
+    values: &[(u64, WaveValue)],
+    time_start: u64,
+    time_range: u64,
+    width: u16,
+) -> Vec<(u16, &[(u64, WaveValue)])> {
+    let viewport = Viewport {
+        time_start,
+        time_range,
+        width,
+    };
+    let time_to_x = |t: u64| viewport.column_of(t);
+
+    values
+        .chunk_by(|(a, _), (b, _)| time_to_x(*a) == time_to_x(*b))
+        .map(|run| (time_to_x(run[0].0), run))
+        .collect()
+}
+
+// Canvas marker and y coordinates of the signal levels for a render style. The y bounds have one
+// unit per sub-cell row of a signal's rows, so that every level lands exactly on a row.
+struct SignalLevels {
+    marker: Marker,
+    top: f64,
+    high: f64,
+    /// Top and bottom of the band X is drawn as, halfway between the middle and the high and low
+    /// levels
+    upper: f64,
+    mid: f64,
+    lower: f64,
+    low: f64,
+    /// Terminal row of the middle level, counted from the top, where bus values are labeled
+    label_row: u16,
+}
+
+impl SignalLevels {
+    fn new(render_style: RenderStyle, height: u16) -> Self {
+        // Sub-cell rows per terminal row, the row of the high level counted from the top, and the
+        // rows left below the low level as a gap to the next signal. The middle level is halfway
+        // between, so with full blocks at the default height X and Z are drawn high.
+        let (marker, rows_per_cell, high, gap) = match render_style {
+            RenderStyle::Block => (Marker::Block, 1, 0, 0),
+            RenderStyle::HalfBlock => (Marker::HalfBlock, 2, 0, 0),
+            RenderStyle::Braille => (Marker::Braille, 4, 1, 2),
+        };
+        let rows = rows_per_cell * height.max(1);
+        let low = (rows - 1 - gap).max(high);
+        let mid = (high + low) / 2;
+        let top = (rows - 1) as f64;
+        Self {
+            marker,
+            top,
+            high: top - high as f64,
+            upper: top - ((high + mid) / 2) as f64,
+            mid: top - mid as f64,
+            lower: top - ((mid + low) / 2) as f64,
+            low: top - low as f64,
+            label_row: mid / rows_per_cell,
+        }
+    }
+}
+
+// Colors of the values a binary signal can hold besides 0 and 1, also used for buses with such bits
+#[derive(Copy, Clone)]
+struct UnknownColors {
+    x: Color,
+    z: Color,
+}
+
+impl UnknownColors {
+    fn of(state: &AppState) -> Self {
+        UnknownColors {
+            x: state.x_color(),
+            z: state.z_color(),
+        }
+    }
+}
+
+// Bottom and top level of a binary value, which differ for the band X is drawn as, and its color
+fn binary_span(
+    value: &WaveValue,
+    fg: Color,
+    unknown: UnknownColors,
+    levels: &SignalLevels,
+) -> (f64, f64, Color) {
+    match value {
+        WaveValue::Binary(Value::V1) => (levels.high, levels.high, fg),
+        WaveValue::Binary(Value::V0) => (levels.low, levels.low, fg),
+        WaveValue::Binary(Value::VZ) => (levels.mid, levels.mid, unknown.z),
+        WaveValue::Binary(Value::VX) => (levels.lower, levels.upper, unknown.x),
+        _ => (levels.mid, levels.mid, fg),
+    }
+}
+
+// A stretch of columns `[start_x, end_x)` over which a signal holds one value. `run` holds every
+// value that falls in the first column, the last of which is the held value. The first segment may
+// only hold the value carried in from before the window, in which case it doesn't start with a
+// transition.
+struct Segment<'a> {
+    start_x: u16,
+    end_x: u16,
+    run: &'a [(u64, WaveValue)],
+    carried_in: bool,
+}
+
+impl Segment<'_> {
+    fn value(&self) -> &WaveValue {
+        &self.run[self.run.len() - 1].1
+    }
+}
+
+// Segments of a signal, one for each column that values fall in, so there are at most `width + 1`
+// of them however many values are visible.
+fn signal_segments(
+    values: &[(u64, WaveValue)],
+    time_start: u64,
+    time_range: u64,
+    width: u16,
+) -> Vec<Segment<'_>> {
+    let columns = columns_of_values(values, time_start, time_range, width);
+    columns
+        .iter()
+        .enumerate()
+        .map(|(i, (x, run))| Segment {
+            start_x: *x,
+            end_x: columns.get(i + 1).map_or(width, |(next_x, _)| *next_x),
+            run,
+            carried_in: i == 0 && run.len() == 1 && run[0].0 < time_start,
+        })
+        .collect()
+}
+
+// Lines making up a binary signal: horizontal lines for each segment, and a vertical line at the
+// start of each segment spanning every level the signal visits in that column, so a column with
+// many transitions costs the same as a column with one. X is a band between two lines and Z a
+// dashed middle line, so neither reads as a valid level.
+fn binary_signal_lines(
+    values: &[(u64, WaveValue)],
+    time_start: u64,
+    time_range: u64,
+    width: u16,
+    fg: Color,
+    unknown: UnknownColors,
+    levels: &SignalLevels,
+) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut last_span: Option<(f64, f64)> = None;
+    let span = |value: &WaveValue| {
+        let (bottom, top, _) = binary_span(value, fg, unknown, levels);
+        (bottom, top)
+    };
+
+    for segment in signal_segments(values, time_start, time_range, width) {
+        let x1 = segment.start_x as f64;
+        let x2 = segment.end_x as f64;
+        let (bottom, top, color) = binary_span(segment.value(), fg, unknown, levels);
+
+        // The window is entered with the latest value at or before its start, so there is never a
+        // transition at the left edge from a value the window doesn't show
+        let run = match last_span {
+            Some(_) => segment.run,
+            None => {
+                let entered = segment.run.partition_point(|(t, _)| *t <= time_start);
+                &segment.run[entered.saturating_sub(1)..]
+            }
+        };
+
+        // If the value changed, draw the transition from the previous level
+        let first = last_span.unwrap_or(span(&run[0].1));
+        let (low, high) = run
+            .iter()
+            .map(|(_, v)| span(v))
+            .fold(first, |(low, high), (bottom, top)| {
+                (low.min(bottom), high.max(top))
+            });
+        if (low, high) != (bottom, top) {
+            lines.push(Line {
+                x1,
+                y1: low,
+                x2: x1,
+                y2: high,
+                color,
+            });
+        }
+
+        if segment.value() == &WaveValue::Binary(Value::VZ) {
+            let mut x = x1;
+            while x < x2 {
+                lines.push(Line {
+                    x1: x,
+                    y1: bottom,
+                    x2: (x + 0.5).min(x2),
+                    y2: bottom,
+                    color,
+                });
+                x += Z_DASH_PERIOD;
+            }
+        } else {
+            let ys: &[f64] = if bottom == top {
+                &[bottom]
+            } else {
+                &[bottom, top]
+            };
+            for &y in ys {
+                lines.push(Line {
+                    x1,
+                    y1: y,
+                    x2,
+                    y2: y,
+                    color,
+                });
+            }
+        }
+        last_span = Some((bottom, top));
+    }
+
+    lines
+}
+
+// Color for a bus value with unknown bits, following the binary signal convention: the X color if
+// any bit is X, the Z color if the unknown bits are all Z. Returns None for fully known values.
+fn unknown_bits_color(value: &WaveValue, unknown: UnknownColors) -> Option<Color> {
+    let bits = value.values(16)?;
+    if bits.contains(&Value::VX) {
+        Some(unknown.x)
+    } else if bits.contains(&Value::VZ) {
+        Some(unknown.z)
+    } else {
+        None
+    }
+}
+
+// How the values of a bus are labeled: their colors when they have unknown bits, which end of
+// labels too wide for their segment is cut, and the width of signed decimal labels, if signed
+#[derive(Copy, Clone)]
+struct BusLabels {
+    unknown: UnknownColors,
+    truncation: LabelTruncation,
+    signed_width: Option<usize>,
+}
+
+// Draws the value of each bus segment centered in the part of it inside the window, along the row
+// `area`. Values too wide for their segment are shortened by `bus_label`, and left out of segments
+// too narrow for even that.
+fn draw_bus_labels(
+    buf: &mut Buffer,
+    area: Rect,
+    segments: &[Segment],
+    style: Style,
+    labels: BusLabels,
+) {
+    for segment in segments {
+        let v = segment.value();
+        if !matches!(v, WaveValue::Bus(_)) {
+            continue;
+        }
+
+        // A column is left free so labels don't run into the next transition
+        let end_x = segment.end_x.min(area.width);
+        let segment_width = end_x.saturating_sub(segment.start_x);
+        let room = segment_width.saturating_sub(1) as usize;
+        let Some(label) = bus_label(v, room, labels.signed_width, labels.truncation) else {
+            continue;
+        };
+        let label_len = label.chars().count() as u16;
+        let midpoint = segment.start_x + (segment_width - label_len) / 2;
+        let label_area = Rect::new(area.x + midpoint, area.y, label_len, 1);
+
+        let label_style = match unknown_bits_color(v, labels.unknown) {
+            Some(color) => style.fg(color),
+            None => style,
+        };
+        Paragraph::new(label)
+            .style(label_style)
+            .render(label_area, buf);
+    }
+}
+
+// The label of a bus value in at most `room` columns: its hex value, or its signed decimal value
+// `signed_width` bits wide if given, or for values with unknown bits, which are kept in binary,
+// their hex digits if the bits don't fit. Labels that still don't fit are cut off at the
+// `truncation` end, if at least 3 columns are left for the rest.
+fn bus_label(
+    value: &WaveValue,
+    room: usize,
+    signed_width: Option<usize>,
+    truncation: LabelTruncation,
+) -> Option<String> {
+    let label = match signed_width {
+        Some(width) if !has_unknown_bits(value) => value.format_signed_decimal(width),
+        _ => format!("{:X}", value),
+    };
+    if label.chars().count() <= room {
+        return Some(label);
+    }
+    let label = if has_unknown_bits(value) {
+        hex_digits(&value_bits(value))
+    } else {
+        label
+    };
+
+    let len = label.chars().count();
+    if len <= room {
+        Some(label)
+    } else if room >= 3 {
+        let kept = room - 1;
+        Some(match truncation {
+            LabelTruncation::Start => format!("…{}", &label[len - kept..]),
+            LabelTruncation::End => format!("{}…", &label[..kept]),
+        })
+    } else {
+        None
+    }
+}
+
+// Whether a bus value has x or z bits
+fn has_unknown_bits(value: &WaveValue) -> bool {
+    matches!(value, WaveValue::Bus(bits) if bits.contains(['x', 'X', 'z', 'Z']))
+}
+
+// Bits as uppercase hex digits, most significant first, with an X for each digit with any x bit
+// and a Z for each digit with z but no x bits, like 12XF
+fn hex_digits(bits: &[Value]) -> String {
+    let padding = (4 - bits.len() % 4) % 4;
+    let padded: Vec<&Value> = std::iter::repeat_n(&Value::V0, padding)
+        .chain(bits)
+        .collect();
+    padded
+        .chunks(4)
+        .map(|nibble| {
+            if nibble.contains(&&Value::VX) {
+                'X'
+            } else if nibble.contains(&&Value::VZ) {
+                'Z'
+            } else {
+                let digit = nibble
+                    .iter()
+                    .fold(0, |digit, bit| digit * 2 + u32::from(**bit == Value::V1));
+                std::char::from_digit(digit, 16)
+                    .unwrap_or('?')
+                    .to_ascii_uppercase()
+            }
+        })
+        .collect()
+}
+
+impl WaveformWidget {
+    /// Draws `signal = value` next to the mouse for the signal row and time under it, since bus
+    /// labels are cut short in narrow segments and left out of the narrowest.
+    fn draw_hover_readout(&self, buf: &mut Buffer, area: Rect, state: &AppState) {
+        let Some((column, row)) = state.hover_position else {
+            return;
+        };
+        if state.is_dragging || !area.contains(Position::new(column, row)) {
+            return;
+        }
+
+        let Some(signal) = state
+            .signal_at_row(row - area.y)
+            .map(|index| &state.displayed_signals[index])
+        else {
+            return;
+        };
+        let time = state.screen_pos_to_time(column - area.x, area.width);
+        let value = match state.decoded.get(signal) {
+            Some(_) => state
+                .annotation_at(signal, time)
+                .map(|annotation| annotation.label.clone()),
+            None => state
+                .get_value_at_marker(signal, time)
+                .map(|value| state.format_value(signal, &value)),
+        };
+        let Some(value) = value else {
+            return;
+        };
+
+        // Right of the cursor on the row above it, moved left or down to stay inside the area
+        let text = format!(" {} = {} ", signal, value);
+        let width = (text.chars().count() as u16).min(area.width);
+        let x = (column + 1).min(area.right() - width);
+        let y = if row > area.y { row - 1 } else { row + 1 }.min(area.bottom() - 1);
+        Paragraph::new(text)
+            .style(state.theme().readout())
+            .render(Rect::new(x, y, width, 1), buf);
+    }
+}
+
+impl StatefulWidget for WaveformWidget {
+    type State = AppState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        if state.waveform_data.signals.is_empty() {
+            return;
+        }
+
+        self.draw_signals(buf, area, state);
+        self.draw_regions(buf, area, state);
+        self.draw_comparison(buf, area, state);
+        self.draw_diff(buf, area, state);
+        self.draw_markers(buf, area, state);
+        self.draw_cursor(buf, area, state);
+        self.draw_drag_selection(buf, area, state);
+        self.draw_hover_readout(buf, area, state);
     }
 }
 
@@ -329,6 +1196,7 @@ impl StatefulWidget for WaveformWidget {
 mod tests {
     use super::*;
     use crate::{
+        constants::WAVEFORM_HEIGHT,
         parsers::types::{Value, WaveValue},
         state::AppState,
     };
@@ -336,6 +1204,11 @@ mod tests {
     use ratatui::{backend::TestBackend, Terminal};
     use std::collections::HashMap;
 
+    const UNKNOWN: UnknownColors = UnknownColors {
+        x: Color::Red,
+        z: Color::Magenta,
+    };
+
     fn create_test_state() -> AppState {
         let mut state = AppState::new();
 
@@ -356,8 +1229,8 @@ mod tests {
         values.insert(
             "sig2".to_string(),
             vec![
-                (0, WaveValue::Bus("00".to_string())),
-                (15, WaveValue::Bus("ff".to_string())),
+                (0, WaveValue::Bus("00".into())),
+                (15, WaveValue::Bus("ff".into())),
             ],
         );
 
@@ -383,13 +1256,13 @@ mod tests {
         terminal
             .draw(|f| {
                 let size = f.area();
-                widget.render(size, &mut f.buffer_mut(), &mut state);
+                widget.render(size, f.buffer_mut(), &mut state);
             })
             .unwrap();
 
         // Verify the widget rendered something to the buffer
         let buffer = terminal.backend().buffer();
-        assert!(buffer.content().len() > 0);
+        assert!(!buffer.content().is_empty());
         assert_snapshot!(terminal.backend());
     }
 
@@ -413,10 +1286,1060 @@ mod tests {
         terminal
             .draw(|f| {
                 let size = f.area();
-                widget.render(size, &mut f.buffer_mut(), &mut state);
+                widget.render(size, f.buffer_mut(), &mut state);
+            })
+            .unwrap();
+
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_glitch_indicator() {
+        use crate::parsers::types::Glitch;
+
+        let mut state = create_test_state();
+        state.time_start = 0;
+        state.time_range = 50;
+        state.waveform_data.glitches.insert(
+            "sig1".to_string(),
+            vec![Glitch {
+                time: 20,
+                via: vec![WaveValue::Binary(Value::VX)],
+            }],
+        );
+
+        let widget = WaveformWidget::default();
+        let mut terminal = Terminal::new(TestBackend::new(80, 10)).unwrap();
+        terminal
+            .draw(|f| widget.render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(32, 0)].symbol(), "!");
+        assert_eq!(buffer[(32, 0)].fg, Color::Red);
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_clicked_marker_renders_in_clicked_column() {
+        let mut state = create_test_state();
+        state.waveform_data.max_time = 20000;
+        let widget = WaveformWidget::default();
+        let ranges = (7..10007).step_by(499).chain([19, 20, 21, 99, 100, 101]);
+        for (width, time_range) in (20..200)
+            .step_by(7)
+            .flat_map(|w| ranges.clone().map(move |r| (w, r)))
+        {
+            state.set_window(3, time_range);
+            let area = Rect::new(0, 0, width, 2);
+            for column in [0, 1, width / 3, width / 2, width - 2, width - 1] {
+                state.set_primary_marker(column, width);
+                let time = state.primary_marker.unwrap();
+                let mut buf = Buffer::empty(area);
+                widget.draw_markers(&mut buf, area, &state);
+
+                let drawn: Vec<u16> = (0..width)
+                    .filter(|x| buf[(*x, 1)].symbol() != " ")
+                    .collect();
+                if time_range >= width as u64 {
+                    assert_eq!(drawn, vec![column], "width {} range {}", width, time_range);
+                } else {
+                    assert_eq!(drawn.len(), 1);
+                    assert_eq!(state.screen_pos_to_time(drawn[0], width), time);
+                }
+            }
+        }
+    }
+
+    fn render_saved_markers(show_lines: bool) -> Terminal<TestBackend> {
+        let mut state = create_test_state();
+        state.time_start = 0;
+        state.time_range = 50;
+        state.config.ui.show_saved_marker_lines = show_lines;
+
+        state.primary_marker = Some(10);
+        let mut marker = |time: u64, name: &str, color: Color| {
+            let mut marker = crate::types::Marker::new(time, name.to_string());
+            marker.color = color;
+            state.saved_markers.push(marker);
+        };
+        marker(30, "req", Color::Red);
+        marker(40, "ack", Color::Blue);
+        // In the primary marker's column, where it would only flicker under it
+        marker(10, "start", Color::Green);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 10)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        terminal
+    }
+
+    #[test]
+    fn test_render_saved_marker_lines() {
+        let terminal = render_saved_markers(true);
+
+        // Each saved marker's line is drawn full height in its own color, while the one in the
+        // primary marker's column isn't drawn
+        let buffer = terminal.backend().buffer();
+        let colored_cells = |color: Color| {
+            buffer
+                .content()
+                .iter()
+                .filter(|cell| cell.fg == color)
+                .count()
+        };
+        assert_eq!(colored_cells(Color::Red), 10);
+        assert_eq!(colored_cells(Color::Blue), 10);
+        assert_eq!(colored_cells(Color::Green), 0);
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_saved_marker_lines_can_be_turned_off() {
+        let terminal = render_saved_markers(false);
+        let buffer = terminal.backend().buffer();
+        assert!(buffer
+            .content()
+            .iter()
+            .all(|cell| cell.fg != Color::Red && cell.fg != Color::Blue));
+    }
+
+    #[test]
+    fn test_saved_marker_lines_follow_color_mode() {
+        let mut state = create_test_state();
+        state.time_start = 0;
+        state.time_range = 50;
+        state.config.ui.color_mode = crate::config::ColorMode::Ansi16;
+        let mut marker = crate::types::Marker::new(30, "req".to_string());
+        marker.color = Color::Rgb(250, 10, 10);
+        state.saved_markers.push(marker);
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 4)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        let expected = state.theme().color(Color::Rgb(250, 10, 10));
+        assert_ne!(expected, Color::Rgb(250, 10, 10));
+        assert_eq!(terminal.backend().buffer()[(48, 0)].fg, expected);
+    }
+
+    #[test]
+    fn test_render_measure_readout_while_dragging() {
+        let mut state = create_test_state();
+        state.time_start = 0;
+        state.time_range = 50;
+        state.is_dragging = true;
+        state.drag_kind = DragKind::Measure;
+        state.drag_start = Some((16, 10));
+        state.drag_current = Some((48, 30));
+
+        let widget = WaveformWidget::default();
+        let backend = TestBackend::new(80, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| {
+                let size = f.area();
+                widget.render(size, f.buffer_mut(), &mut state);
+            })
+            .unwrap();
+
+        let top_row: String = (0..80)
+            .map(|x| terminal.backend().buffer()[(x, 0)].symbol().to_string())
+            .collect();
+        assert!(top_row.contains("Δ20"));
+    }
+
+    #[test]
+    fn test_render_comparison_band() {
+        let mut state = create_test_state();
+        state.displayed_signals.push("other".to_string());
+        state.time_start = 0;
+        state.time_range = 50;
+        state.comparison = Some(crate::types::Comparison {
+            signal_a: "sig1".to_string(),
+            signal_b: "sig2".to_string(),
+            mismatches: vec![(10, 20), (40, 41)],
+        });
+        let mismatch = state.theme().mismatch().bg.unwrap();
+
+        let widget = WaveformWidget::default();
+        let backend = TestBackend::new(50, 6);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| {
+                let size = f.area();
+                widget.render(size, f.buffer_mut(), &mut state);
+            })
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        for y in 0..4 {
+            assert_eq!(buffer[(9, y)].bg, Color::Reset);
+            assert_eq!(buffer[(10, y)].bg, mismatch);
+            assert_eq!(buffer[(19, y)].bg, mismatch);
+            assert_eq!(buffer[(20, y)].bg, Color::Reset);
+            assert_eq!(buffer[(40, y)].bg, mismatch);
+        }
+
+        // Signals that aren't part of the comparison aren't highlighted
+        assert_eq!(buffer[(10, 4)].bg, Color::Reset);
+    }
+
+    #[test]
+    fn test_unknown_bits_color() {
+        assert_eq!(
+            unknown_bits_color(&WaveValue::Bus("FF".into()), UNKNOWN),
+            None
+        );
+        assert_eq!(
+            unknown_bits_color(&WaveValue::Bus("xZ".into()), UNKNOWN),
+            Some(Color::Red)
+        );
+        assert_eq!(
+            unknown_bits_color(&WaveValue::Bus("0Z".into()), UNKNOWN),
+            Some(Color::Magenta)
+        );
+        assert_eq!(
+            unknown_bits_color(&WaveValue::Bus("r1.5".into()), UNKNOWN),
+            None
+        );
+    }
+
+    #[test]
+    fn test_render_bus_with_unknown_bits() {
+        let mut state = create_test_state();
+        state.displayed_signals = vec!["sig2".to_string()];
+        state.waveform_data.values.insert(
+            "sig2".to_string(),
+            vec![
+                (0, WaveValue::Bus("00".into())),
+                (10, WaveValue::Bus("xZ".into())),
+                (20, WaveValue::Bus("zz".into())),
+                (30, WaveValue::Bus("FF".into())),
+            ],
+        );
+        state.time_start = 0;
+        state.time_range = 40;
+
+        let widget = WaveformWidget::default();
+        let backend = TestBackend::new(40, 2);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|f| {
+                let size = f.area();
+                widget.render(size, f.buffer_mut(), &mut state);
             })
             .unwrap();
 
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(2, 0)].fg, Color::Yellow);
+        assert_eq!(buffer[(12, 1)].fg, Color::Red);
+        assert_eq!(buffer[(22, 1)].fg, Color::Magenta);
+        assert_eq!(buffer[(32, 0)].fg, Color::Yellow);
+        assert_snapshot!(terminal.backend());
+    }
+
+    fn render_bus_window(values: Vec<(u64, WaveValue)>, time_start: u64) -> Terminal<TestBackend> {
+        let mut state = create_test_state();
+        state.displayed_signals = vec!["sig2".to_string()];
+        state
+            .waveform_data
+            .values
+            .insert("sig2".to_string(), values);
+        state.waveform_data.max_time = 100;
+        state.time_start = time_start;
+        state.time_range = 40;
+
+        let widget = WaveformWidget::default();
+        let mut terminal = Terminal::new(TestBackend::new(40, 2)).unwrap();
+        terminal
+            .draw(|f| widget.render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        terminal
+    }
+
+    fn render_late_signals(show_no_data_yet: bool) -> Terminal<TestBackend> {
+        let mut state = create_test_state();
+        state.config.ui.show_no_data_yet = show_no_data_yet;
+        state.waveform_data.values.insert(
+            "sig1".to_string(),
+            vec![
+                (20, WaveValue::Binary(Value::V1)),
+                (30, WaveValue::Binary(Value::V0)),
+            ],
+        );
+        state
+            .waveform_data
+            .values
+            .insert("sig2".to_string(), vec![(20, WaveValue::Bus("a5".into()))]);
+        state.waveform_data.max_time = 40;
+        state.time_start = 0;
+        state.time_range = 40;
+
+        let widget = WaveformWidget::default();
+        let mut terminal = Terminal::new(TestBackend::new(40, 4)).unwrap();
+        terminal
+            .draw(|f| widget.render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        terminal
+    }
+
+    #[test]
+    fn test_render_no_data_before_first_value() {
+        let terminal = render_late_signals(true);
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(0, 1)].symbol(), "╌");
+        assert_eq!(buffer[(18, 3)].fg, Color::DarkGray);
         assert_snapshot!(terminal.backend());
     }
+
+    #[test]
+    fn test_no_data_before_first_value_can_be_turned_off() {
+        let terminal = render_late_signals(false);
+        let buffer = terminal.backend().buffer();
+        assert!((0..19).all(|x| buffer[(x, 1)].symbol() == " "));
+    }
+
+    #[test]
+    fn test_no_data_line_spans_window_before_first_value() {
+        // Nothing to show yet in the whole window, and nothing at all for a signal without values
+        let mut state = create_test_state();
+        state.waveform_data.values.insert(
+            "sig1".to_string(),
+            vec![(100, WaveValue::Binary(Value::V1))],
+        );
+        state.waveform_data.values.remove("sig2");
+        state.time_start = 0;
+        state.time_range = 40;
+        assert!(state.get_visible_values("sig1").is_empty());
+
+        let widget = WaveformWidget::default();
+        let mut terminal = Terminal::new(TestBackend::new(40, 4)).unwrap();
+        terminal
+            .draw(|f| widget.render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        assert!((0..40).all(|x| buffer[(x, 1)].symbol() == "╌"));
+        assert!((0..40).all(|x| buffer[(x, 3)].symbol() == " "));
+    }
+
+    #[test]
+    fn test_render_bus_changed_before_window() {
+        let terminal = render_bus_window(
+            vec![
+                (0, WaveValue::Bus("00".into())),
+                (10, WaveValue::Bus("a5".into())),
+            ],
+            50,
+        );
+
+        // The value carried in from before the window has no transition at the left edge, and its
+        // label is centered over the whole window
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(0, 0)].symbol(), buffer[(1, 0)].symbol());
+        assert_eq!(buffer[(19, 0)].symbol(), "A");
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_bus_changed_at_window_start() {
+        let terminal = render_bus_window(
+            vec![
+                (0, WaveValue::Bus("00".into())),
+                (50, WaveValue::Bus("a5".into())),
+            ],
+            50,
+        );
+
+        // A change exactly at the start of the window is a real transition
+        let buffer = terminal.backend().buffer();
+        assert_ne!(buffer[(0, 0)].symbol(), buffer[(1, 0)].symbol());
+        assert_snapshot!(terminal.backend());
+    }
+
+    fn dense_binary_values(count: u64) -> Vec<(u64, WaveValue)> {
+        (0..count)
+            .map(|t| {
+                let value = if t % 2 == 0 { Value::V0 } else { Value::V1 };
+                (t, WaveValue::Binary(value))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_binary_decimation_bounded_by_width() {
+        let values = dense_binary_values(100_000);
+        let levels = SignalLevels::new(RenderStyle::Braille, WAVEFORM_HEIGHT as u16);
+        let lines = binary_signal_lines(&values, 0, 100_000, 100, Color::White, UNKNOWN, &levels);
+
+        // At most a horizontal and a vertical line for each of the 101 column positions
+        assert!(lines.len() <= 2 * (100 + 1), "{} lines", lines.len());
+
+        // Every column toggles, so each gets a full-height transition
+        let verticals: Vec<_> = lines.iter().filter(|line| line.y1 != line.y2).collect();
+        assert_eq!(verticals.len(), 101);
+        assert!(verticals
+            .iter()
+            .all(|line| line.y1 == levels.low && line.y2 == levels.high));
+    }
+
+    #[test]
+    fn test_binary_decimation_keeps_sparse_transitions() {
+        let values = vec![
+            (0, WaveValue::Binary(Value::V0)),
+            (10, WaveValue::Binary(Value::V1)),
+            (20, WaveValue::Binary(Value::V1)),
+        ];
+        let levels = SignalLevels::new(RenderStyle::Braille, WAVEFORM_HEIGHT as u16);
+        let lines = binary_signal_lines(&values, 0, 50, 50, Color::White, UNKNOWN, &levels);
+
+        // Low until 10, a rising edge at 10, then high to the end with no edge at 20
+        let endpoints: Vec<_> = lines
+            .iter()
+            .map(|line| (line.x1, line.y1, line.x2, line.y2))
+            .collect();
+        assert_eq!(
+            endpoints,
+            vec![
+                (0.0, levels.low, 10.0, levels.low),
+                (10.0, levels.low, 10.0, levels.high),
+                (10.0, levels.high, 20.0, levels.high),
+                (20.0, levels.high, 50.0, levels.high),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_binary_no_transition_at_window_entry() {
+        // Values at or before the start of the window, as if the one at 20 had been carried in
+        let values = vec![
+            (0, WaveValue::Binary(Value::V0)),
+            (20, WaveValue::Binary(Value::V1)),
+            (20, WaveValue::Binary(Value::V0)),
+            (30, WaveValue::Binary(Value::V1)),
+        ];
+        let levels = SignalLevels::new(RenderStyle::Braille, WAVEFORM_HEIGHT as u16);
+        let lines = binary_signal_lines(&values, 20, 20, 20, Color::White, UNKNOWN, &levels);
+
+        // Only the latest value at the start is drawn, so the first edge is the one at 30
+        let verticals: Vec<f64> = lines
+            .iter()
+            .filter(|line| line.x1 == line.x2)
+            .map(|line| line.x1)
+            .collect();
+        assert_eq!(verticals, vec![10.0]);
+        assert_eq!(lines[0].y1, levels.low);
+    }
+
+    fn render_binary_window(
+        values: Vec<(u64, WaveValue)>,
+        time_start: u64,
+    ) -> Terminal<TestBackend> {
+        let mut state = create_test_state();
+        state.displayed_signals = vec!["sig1".to_string()];
+        state
+            .waveform_data
+            .values
+            .insert("sig1".to_string(), values);
+        state.waveform_data.max_time = 100;
+        state.time_start = time_start;
+        state.time_range = 40;
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 2)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        terminal
+    }
+
+    fn z_and_x_cycle() -> Vec<(u64, WaveValue)> {
+        [Value::V0, Value::VZ, Value::V1, Value::VX, Value::V0]
+            .into_iter()
+            .enumerate()
+            .map(|(i, value)| (i as u64 * 8, WaveValue::Binary(value)))
+            .collect()
+    }
+
+    #[test]
+    fn test_binary_z_dashed_and_x_band_lines() {
+        let levels = SignalLevels::new(RenderStyle::Braille, WAVEFORM_HEIGHT as u16);
+        let lines =
+            binary_signal_lines(&z_and_x_cycle(), 0, 40, 40, Color::White, UNKNOWN, &levels);
+        let of_color = |color| {
+            lines
+                .iter()
+                .filter(|line| line.color == color)
+                .map(|line| (line.x1, line.y1, line.x2, line.y2))
+                .collect::<Vec<_>>()
+        };
+
+        // Z rises from 0 to the middle, then every other column is dashed until 1 takes over
+        let z = of_color(Color::Magenta);
+        assert_eq!(z[0], (8.0, levels.low, 8.0, levels.mid));
+        let dashes: Vec<_> = z[1..].iter().map(|(x1, _, x2, _)| (*x1, *x2)).collect();
+        assert_eq!(
+            dashes,
+            [(8.0, 8.5), (10.0, 10.5), (12.0, 12.5), (14.0, 14.5)]
+        );
+        assert!(z[1..]
+            .iter()
+            .all(|(_, y1, _, y2)| *y1 == levels.mid && *y2 == levels.mid));
+
+        // X leaves 1 down to the bottom of its band, which has a line at either edge
+        assert!(levels.low < levels.lower && levels.upper < levels.high);
+        assert_eq!(
+            of_color(Color::Red),
+            [
+                (24.0, levels.lower, 24.0, levels.high),
+                (24.0, levels.lower, 32.0, levels.lower),
+                (24.0, levels.upper, 32.0, levels.upper),
+            ]
+        );
+
+        // Out of the band, 0 falls from its top
+        let falling = lines.iter().find(|line| line.x1 == 32.0 && line.x2 == 32.0);
+        assert_eq!(
+            falling.map(|line| (line.y1, line.y2)),
+            Some((levels.low, levels.upper))
+        );
+    }
+
+    #[test]
+    fn test_render_binary_z_and_x() {
+        let terminal = render_binary_window(z_and_x_cycle(), 0);
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(10, 0)].fg, Color::Magenta);
+        assert_eq!(buffer[(28, 1)].fg, Color::Red);
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_binary_changed_at_window_start() {
+        let terminal = render_binary_window(
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (50, WaveValue::Binary(Value::V1)),
+            ],
+            50,
+        );
+
+        // The window is entered high, without an edge from the low value before it
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(0, 0)].symbol(), buffer[(1, 0)].symbol());
+        assert_eq!(buffer[(0, 1)].symbol(), " ");
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_binary_changed_at_window_end() {
+        let terminal = render_binary_window(
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (89, WaveValue::Binary(Value::V1)),
+            ],
+            50,
+        );
+
+        // Low across the window and a rising edge in the last column, with nothing at the left edge
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer[(0, 1)].symbol(), buffer[(1, 1)].symbol());
+        assert_ne!(buffer[(39, 0)].symbol(), " ");
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_bus_decimation_bounded_by_width() {
+        let values: Vec<(u64, WaveValue)> = (0..100_000u64)
+            .map(|t| (t, WaveValue::Bus(format!("{:x}", t).into())))
+            .collect();
+        let segments = signal_segments(&values, 0, 100_000, 100);
+
+        assert!(segments.len() <= 100 + 1, "{} segments", segments.len());
+        assert!(segments
+            .iter()
+            .all(|segment| segment.start_x < segment.end_x || segment.start_x == 100));
+    }
+
+    #[test]
+    fn test_render_dense_signals() {
+        let mut state = create_test_state();
+        state
+            .waveform_data
+            .values
+            .insert("sig1".to_string(), dense_binary_values(100_000));
+        state.waveform_data.values.insert(
+            "sig2".to_string(),
+            (0..100_000u64)
+                .map(|t| (t, WaveValue::Bus(format!("{:x}", t).into())))
+                .collect(),
+        );
+        state.waveform_data.max_time = 100_000;
+        state.time_start = 0;
+        state.time_range = 100_000;
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 4)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+
+        // The binary signal is busy in every column
+        let buffer = terminal.backend().buffer();
+        assert!((0..100).all(|x| buffer[(x, 0)].symbol() != " "));
+    }
+
+    #[test]
+    fn test_draw_regions() {
+        let mut state = create_test_state();
+        state.time_start = 10;
+        state.time_range = 50;
+        state.regions = vec![
+            crate::types::Region::new(0, 20, "reset".to_string()),
+            crate::types::Region::new(40, 45, "burst".to_string()),
+            crate::types::Region::new(70, 80, "hidden".to_string()),
+        ];
+
+        let mut terminal = Terminal::new(TestBackend::new(50, 4)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+
+        // The first region is clipped to the left edge, the second tints its own columns, and
+        // every signal row is tinted
+        let buffer = terminal.backend().buffer();
+        let tinted: Vec<u16> = (0..50)
+            .filter(|x| buffer[(*x, 0)].bg == crate::constants::DEFAULT_REGION_COLOR)
+            .collect();
+        assert_eq!(tinted, (0..10).chain(30..35).collect::<Vec<u16>>());
+        for y in 0..4 {
+            assert_eq!(buffer[(0, y)].bg, crate::constants::DEFAULT_REGION_COLOR);
+        }
+    }
+
+    // Markers, a drag selection and X and Z values without any colors to tell them apart
+    fn render_mono(state: &mut AppState) -> Terminal<TestBackend> {
+        let values = state.waveform_data.values.get_mut("sig1").unwrap();
+        values.push((30, WaveValue::Binary(Value::VX)));
+        values.push((40, WaveValue::Binary(Value::VZ)));
+        state.time_start = 0;
+        state.time_range = 50;
+        state.primary_marker = Some(10);
+        state.secondary_marker = Some(20);
+        state.is_dragging = true;
+        state.drag_start = Some((48, 30));
+        state.drag_current = Some((64, 40));
+
+        let mut terminal = Terminal::new(TestBackend::new(80, 4)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), state))
+            .unwrap();
+        terminal
+    }
+
+    #[test]
+    fn test_render_mono_markers_and_drag_selection() {
+        let mut state = create_test_state();
+        state.config.ui.color_mode = crate::config::ColorMode::Mono;
+        let terminal = render_mono(&mut state);
+        assert_snapshot!(terminal.backend());
+
+        // Markers are reversed columns, the primary one also bold, and so is the drag selection
+        let buffer = terminal.backend().buffer();
+        for y in 0..4 {
+            assert!(buffer[(16, y)]
+                .modifier
+                .contains(Modifier::REVERSED | Modifier::BOLD));
+            assert!(buffer[(32, y)].modifier.contains(Modifier::REVERSED));
+            assert!(buffer[(50, y)].modifier.contains(Modifier::REVERSED));
+            assert!(!buffer[(70, y)].modifier.contains(Modifier::REVERSED));
+        }
+
+        // The selected signal is bold, so only the primary marker is bold on the other one
+        assert!(buffer[(0, 0)].modifier.contains(Modifier::BOLD));
+        assert!(!buffer[(32, 2)].modifier.contains(Modifier::BOLD));
+
+        // X and Z are spelled out on the signal's top row
+        assert_eq!(buffer[(50, 0)].symbol(), "x");
+        assert_eq!(buffer[(70, 0)].symbol(), "z");
+    }
+
+    #[test]
+    fn test_no_color_forces_mono() {
+        let mut state = create_test_state();
+        state.no_color = true;
+        let terminal = render_mono(&mut state);
+        assert!(terminal.backend().buffer()[(16, 0)]
+            .modifier
+            .contains(Modifier::REVERSED));
+
+        // With colors the drag selection is tinted instead
+        let mut state = create_test_state();
+        let terminal = render_mono(&mut state);
+        let buffer = terminal.backend().buffer();
+        assert!(!buffer[(50, 0)].modifier.contains(Modifier::REVERSED));
+        assert_eq!(Some(buffer[(50, 0)].bg), state.theme().drag().bg);
+    }
+
+    fn render_fast_clock(render_style: RenderStyle) -> Terminal<TestBackend> {
+        let mut state = create_test_state();
+        state.config.ui.render_style = render_style;
+        state.displayed_signals = vec!["sig1".to_string()];
+
+        // A clock with a 3 time unit period, so several edges share a column, followed by a
+        // single pulse narrower than a column
+        let mut values: Vec<(u64, WaveValue)> = (0..20)
+            .map(|i| {
+                let value = if i % 2 == 0 { Value::V0 } else { Value::V1 };
+                (i * 3, WaveValue::Binary(value))
+            })
+            .collect();
+        values.push((100, WaveValue::Binary(Value::V1)));
+        values.push((101, WaveValue::Binary(Value::V0)));
+        state
+            .waveform_data
+            .values
+            .insert("sig1".to_string(), values);
+        state.waveform_data.max_time = 160;
+        state.time_start = 0;
+        state.time_range = 160;
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 2)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        terminal
+    }
+
+    #[test]
+    fn test_render_fast_clock_block() {
+        assert_snapshot!(render_fast_clock(RenderStyle::Block).backend());
+    }
+
+    #[test]
+    fn test_render_fast_clock_halfblock() {
+        assert_snapshot!(render_fast_clock(RenderStyle::HalfBlock).backend());
+    }
+
+    #[test]
+    fn test_render_fast_clock_braille() {
+        let terminal = render_fast_clock(RenderStyle::Braille);
+
+        // The short pulse at time 100 still shows up as a tick in both rows of its column, which the
+        // canvas rounds down to column 24
+        let buffer = terminal.backend().buffer();
+        assert_ne!(buffer[(24, 0)].symbol(), " ");
+        assert_ne!(buffer[(24, 1)].symbol(), " ");
+        assert_snapshot!(terminal.backend());
+    }
+
+    // A bus changing every 3 time units, far too often for any of its labels to fit
+    fn render_crowded_bus(hover_position: Option<(u16, u16)>) -> Terminal<TestBackend> {
+        let mut state = AppState::new();
+        state.waveform_data.signals = vec!["clk".to_string(), "count".to_string()];
+        state.displayed_signals = state.waveform_data.signals.clone();
+        state.waveform_data.values.insert(
+            "clk".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V0)),
+                (60, WaveValue::Binary(Value::V1)),
+            ],
+        );
+        state.waveform_data.values.insert(
+            "count".to_string(),
+            (0..40)
+                .map(|i| (i * 3, WaveValue::Bus(format!("{:02x}", i).into())))
+                .collect(),
+        );
+        state.waveform_data.max_time = 120;
+        state.set_window(0, 120);
+        state.hover_position = hover_position;
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 3 * WAVEFORM_HEIGHT as u16)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        terminal
+    }
+
+    #[test]
+    fn test_render_hover_readout_on_crowded_bus() {
+        // Time 60 is under column 20, where the bus has its 21st value
+        let row = WAVEFORM_HEIGHT as u16 + 1;
+        let terminal = render_crowded_bus(Some((20, row)));
+
+        let buffer = terminal.backend().buffer();
+        let readout: String = (21..32)
+            .map(|x| buffer[(x, row - 1)].symbol().to_string())
+            .collect();
+        assert_eq!(readout, " count = 20");
+        assert_eq!(buffer[(21, row - 1)].bg, Color::Gray);
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_hover_readout_stays_inside_area() {
+        // Near the right edge and on the top row, the readout moves left and below the cursor
+        let terminal = render_crowded_bus(Some((39, 0)));
+
+        let buffer = terminal.backend().buffer();
+        let readout: String = (31..40)
+            .map(|x| buffer[(x, 1)].symbol().to_string())
+            .collect();
+        assert_eq!(readout, " clk = 1 ");
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_no_hover_readout_below_last_signal() {
+        let without_hover = render_crowded_bus(None);
+        let below_signals = render_crowded_bus(Some((20, 2 * WAVEFORM_HEIGHT as u16)));
+        assert_eq!(
+            without_hover.backend().buffer(),
+            below_signals.backend().buffer()
+        );
+    }
+
+    fn render_decoded_row(hover_position: Option<(u16, u16)>) -> Terminal<TestBackend> {
+        let mut state = create_test_state();
+        state.decoded.insert(
+            "uart(sig1,9600)".to_string(),
+            vec![
+                Annotation::new(5, 25, "41"),
+                // Too short for its label, and cut off by the end of the window
+                Annotation::new(27, 29, "A5"),
+                Annotation::new(35, 60, FRAMING_ERROR),
+            ],
+        );
+        state.displayed_signals = vec!["sig1".to_string(), "uart(sig1,9600)".to_string()];
+        state.hover_position = hover_position;
+        state.time_start = 0;
+        state.time_range = 50;
+
+        let widget = WaveformWidget::default();
+        let mut terminal = Terminal::new(TestBackend::new(50, 6)).unwrap();
+        terminal
+            .draw(|f| widget.render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        terminal
+    }
+
+    #[test]
+    fn test_render_decoded_row() {
+        let terminal = render_decoded_row(None);
+        let buffer = terminal.backend().buffer();
+        let row = WAVEFORM_HEIGHT as u16;
+        let label: String = (14..16)
+            .map(|x| buffer[(x, row)].symbol().to_string())
+            .collect();
+        assert_eq!(label, "41");
+        assert_eq!(buffer[(41, row)].symbol(), "E");
+        assert_eq!(buffer[(41, row)].fg, Color::Red);
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_hover_readout_on_decoded_row() {
+        let row = WAVEFORM_HEIGHT as u16 + 1;
+        let terminal = render_decoded_row(Some((10, row)));
+        let buffer = terminal.backend().buffer();
+        let readout: String = (11..33)
+            .map(|x| buffer[(x, row - 1)].symbol().to_string())
+            .collect();
+        assert_eq!(readout, " uart(sig1,9600) = 41 ");
+    }
+
+    fn render_mixed_heights(hover_position: Option<(u16, u16)>) -> Terminal<TestBackend> {
+        let mut state = create_test_state();
+        state.waveform_data.values.insert(
+            "sig3".to_string(),
+            vec![
+                (0, WaveValue::Binary(Value::V1)),
+                (25, WaveValue::Binary(Value::VX)),
+                (35, WaveValue::Binary(Value::V0)),
+            ],
+        );
+        state.displayed_signals = vec!["sig1".to_string(), "sig2".to_string(), "sig3".to_string()];
+        state.signal_heights.insert("sig1".to_string(), 1);
+        state.signal_heights.insert("sig2".to_string(), 1);
+        state.signal_heights.insert("sig3".to_string(), 4);
+        state.hover_position = hover_position;
+        state.time_start = 0;
+        state.time_range = 50;
+
+        let widget = WaveformWidget::default();
+        let mut terminal = Terminal::new(TestBackend::new(50, 7)).unwrap();
+        terminal
+            .draw(|f| widget.render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        terminal
+    }
+
+    #[test]
+    fn test_render_mixed_heights() {
+        let terminal = render_mixed_heights(None);
+        let buffer = terminal.backend().buffer();
+
+        // Compact rows: low, then high after the change at 10, and the bus value with its bar
+        assert_eq!(buffer[(2, 0)].symbol(), "▄");
+        assert_eq!(buffer[(12, 0)].symbol(), "▀");
+        assert_eq!(buffer[(15, 1)].symbol(), "│");
+        assert_snapshot!(terminal.backend());
+    }
+
+    #[test]
+    fn test_render_hover_readout_on_tall_row() {
+        // The bottom row of the four row signal below the two compact rows
+        let terminal = render_mixed_heights(Some((5, 5)));
+        let buffer = terminal.backend().buffer();
+        let readout: String = (6..16)
+            .map(|x| buffer[(x, 4)].symbol().to_string())
+            .collect();
+        assert_eq!(readout, " sig3 = 1 ");
+    }
+
+    #[test]
+    fn test_render_value_highlights() {
+        use crate::{constants::HIGHLIGHT_COLORS, types::Highlight};
+        let mut state = create_test_state();
+        state.waveform_data.values.insert(
+            "sig3".to_string(),
+            vec![
+                (0, WaveValue::Bus("1".into())),
+                (30, WaveValue::Bus("6".into())),
+            ],
+        );
+        state.displayed_signals.push("sig3".to_string());
+        let highlight = |signal: &str, value: &str, color| Highlight {
+            signal: signal.to_string(),
+            value: value.to_string(),
+            color,
+        };
+        // The high run of sig1, the ff of sig2, and a value sig3 never holds
+        state.highlights = vec![
+            highlight("sig1", "1", HIGHLIGHT_COLORS[0]),
+            highlight("sig2", "ff", Color::Blue),
+            highlight("sig3", "7", HIGHLIGHT_COLORS[1]),
+        ];
+        state.time_start = 0;
+        state.time_range = 50;
+
+        let mut terminal = Terminal::new(TestBackend::new(50, 6)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+
+        // Each cell as the rule it is tinted by
+        let buffer = terminal.backend().buffer();
+        let tints: Vec<String> = (0..6)
+            .map(|y| {
+                (0..50)
+                    .map(|x| match buffer[(x, y)].bg {
+                        color if color == HIGHLIGHT_COLORS[0] => 'a',
+                        Color::Blue => 'b',
+                        color if color == HIGHLIGHT_COLORS[1] => 'c',
+                        _ => '.',
+                    })
+                    .collect()
+            })
+            .collect();
+        assert!(!tints.concat().contains('c'));
+        assert_snapshot!(tints.join("\n"));
+        assert_snapshot!("render_value_highlights_waveform", terminal.backend());
+    }
+
+    // A 128 bit bus holding a value with every hex digit, then one with unknown bits, then zero
+    fn render_wide_bus(time_start: u64, time_range: u64, truncation: LabelTruncation) -> String {
+        let mut state = create_test_state();
+        let unknown = format!("{}{}", "1010".repeat(30), "xxxxzz01");
+        state.waveform_data.values.insert(
+            "sig2".to_string(),
+            vec![
+                (0, WaveValue::Bus("0123456789ABCDEF".repeat(2).into())),
+                (40, WaveValue::Bus(unknown.into())),
+                (70, WaveValue::Bus("0".into())),
+            ],
+        );
+        state.waveform_data.max_time = 100;
+        state.displayed_signals = vec!["sig2".to_string()];
+        state.config.ui.bus_label_truncation = truncation;
+        state.set_window(time_start, time_range);
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 2)).unwrap();
+        terminal
+            .draw(|f| WaveformWidget::default().render(f.area(), f.buffer_mut(), &mut state))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..60)
+            .map(|x| buffer[(x, 0)].symbol().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_render_wide_bus_labels_at_zoom_levels() {
+        let labels = [
+            render_wide_bus(0, 100, LabelTruncation::Start),
+            render_wide_bus(0, 100, LabelTruncation::End),
+            render_wide_bus(30, 50, LabelTruncation::Start),
+            render_wide_bus(35, 10, LabelTruncation::Start),
+        ];
+        for label in &labels {
+            assert!(label.contains('…'), "{}", label);
+        }
+        assert_snapshot!(labels.join("\n"));
+    }
+
+    #[test]
+    fn test_render_bus_label_straddling_left_edge() {
+        // The first value started before the window, and is centered in its 42 columns in it
+        let label = render_wide_bus(5, 50, LabelTruncation::Start);
+        let start = label.find("0123456789ABCDEF0123456789ABCDEF").unwrap();
+        assert_eq!(label[..start].chars().count(), 5);
+        assert_snapshot!(label);
+    }
+
+    #[test]
+    fn test_bus_label() {
+        let bus = |value: &str| WaveValue::Bus(value.into());
+        assert_eq!(
+            bus_label(&bus("abcdef"), 6, None, LabelTruncation::Start).as_deref(),
+            Some("ABCDEF")
+        );
+        assert_eq!(
+            bus_label(&bus("abcdef"), 5, None, LabelTruncation::Start).as_deref(),
+            Some("…CDEF")
+        );
+        assert_eq!(
+            bus_label(&bus("abcdef"), 3, None, LabelTruncation::End).as_deref(),
+            Some("AB…")
+        );
+        assert_eq!(
+            bus_label(&bus("abcdef"), 2, None, LabelTruncation::End),
+            None
+        );
+
+        // Unknown bits fall back to hex digits before being cut off
+        let unknown = bus("0001xxxx0z101111");
+        assert_eq!(
+            bus_label(&unknown, 16, None, LabelTruncation::Start).as_deref(),
+            Some("0001XXXX0Z101111")
+        );
+        assert_eq!(
+            bus_label(&unknown, 15, None, LabelTruncation::Start).as_deref(),
+            Some("1XZF")
+        );
+        assert_eq!(
+            bus_label(&unknown, 3, None, LabelTruncation::Start).as_deref(),
+            Some("…ZF")
+        );
+
+        // Signed buses are labeled in decimal, unless they have unknown bits
+        assert_eq!(
+            bus_label(&bus("fe"), 4, Some(8), LabelTruncation::Start).as_deref(),
+            Some("-2")
+        );
+        assert_eq!(
+            bus_label(&bus("fx"), 4, Some(8), LabelTruncation::Start).as_deref(),
+            Some("FX")
+        );
+    }
 }
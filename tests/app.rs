@@ -0,0 +1,78 @@
+use digisurf::{app::App, config::AppConfig};
+use insta::assert_snapshot;
+use ratatui::{backend::TestBackend, Terminal};
+use std::path::Path;
+
+fn load_test_data() -> App {
+    let mut app = App::with_config(AppConfig::default());
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("resources/test_data.vcd");
+    app.load_vcd_file(path).unwrap();
+    app
+}
+
+#[test]
+fn test_commands_drive_a_loaded_file() {
+    let mut app = load_test_data();
+    assert_eq!(app.state.waveform_data.signals.len(), 6);
+
+    app.run_command("show top.clk").unwrap();
+    app.run_command("show top.data*").unwrap();
+    app.run_command("zoom in 4").unwrap();
+    app.run_command("marker add start 4").unwrap();
+    app.run_command("goto 6").unwrap();
+    assert!(app.run_command("no-such-command").is_err());
+
+    let names: Vec<_> = app
+        .state
+        .displayed_signals
+        .iter()
+        .map(String::as_str)
+        .collect();
+    assert_eq!(names, ["top.clk", "top.data_valid", "top.data[7:0]"]);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 16)).unwrap();
+    terminal
+        .draw(|frame| frame.render_widget(&mut app, frame.area()))
+        .unwrap();
+    assert_snapshot!(terminal.backend());
+}
+
+#[test]
+fn test_header_only_file_stays_usable() {
+    let mut app = App::with_config(AppConfig::default());
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("resources/test_data_header_only.vcd");
+    app.load_vcd_file(path).unwrap();
+    assert_eq!(app.state.waveform_data.signals.len(), 2);
+    assert_eq!(app.state.waveform_data.max_time, 0);
+    assert_eq!((app.state.time_start, app.state.time_range), (0, 1));
+
+    // The names are listed, with the reason there's nothing to draw next to them
+    app.run_command("show top.*").unwrap();
+    let mut terminal = Terminal::new(TestBackend::new(80, 16)).unwrap();
+    terminal
+        .draw(|frame| frame.render_widget(&mut app, frame.area()))
+        .unwrap();
+    assert_snapshot!(terminal.backend());
+
+    // Moving around an empty waveform never leaves an empty window
+    for command in [
+        "zoom 2",
+        "zoom in 4",
+        "zoom out",
+        "goto 0",
+        "zoomfull",
+        "pan 10",
+    ] {
+        let _ = app.run_command(command);
+        assert_eq!(
+            (app.state.time_start, app.state.time_range),
+            (0, 1),
+            "{}",
+            command
+        );
+    }
+    assert!(app.run_command("goto 5").is_err());
+    terminal
+        .draw(|frame| frame.render_widget(&mut app, frame.area()))
+        .unwrap();
+}
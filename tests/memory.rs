@@ -0,0 +1,119 @@
+use digisurf::parsers::{types::WaveValue, vcd::VcdParser};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Write,
+    sync::{
+        atomic::{AtomicIsize, Ordering},
+        Mutex, PoisonError,
+    },
+};
+
+// Counts the bytes and allocations alive, so what a waveform keeps allocated can be measured
+struct CountingAllocator;
+
+static LIVE_BYTES: AtomicIsize = AtomicIsize::new(0);
+static LIVE_ALLOCATIONS: AtomicIsize = AtomicIsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        LIVE_BYTES.fetch_add(layout.size() as isize, Ordering::SeqCst);
+        LIVE_ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        LIVE_BYTES.fetch_sub(layout.size() as isize, Ordering::SeqCst);
+        LIVE_ALLOCATIONS.fetch_sub(1, Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+// Held by each test so the counts don't include what another test allocates at the same time
+static COUNTING: Mutex<()> = Mutex::new(());
+
+const CHANGES: usize = 1_000_000;
+
+// Values of a 64 bit counter, spread out so most of their digits change
+fn counter(time: usize) -> u64 {
+    (time as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+/// Bytes and allocations kept alive by what `build` returns.
+fn retained<T>(build: impl FnOnce() -> T) -> (T, isize, isize) {
+    let (bytes, allocations) = (
+        LIVE_BYTES.load(Ordering::SeqCst),
+        LIVE_ALLOCATIONS.load(Ordering::SeqCst),
+    );
+    let built = build();
+    (
+        built,
+        LIVE_BYTES.load(Ordering::SeqCst) - bytes,
+        LIVE_ALLOCATIONS.load(Ordering::SeqCst) - allocations,
+    )
+}
+
+#[test]
+fn test_bus_changes_are_not_allocated_one_by_one() {
+    let _counting = COUNTING.lock().unwrap_or_else(PoisonError::into_inner);
+    let (values, bytes, allocations) = retained(|| {
+        (0..CHANGES)
+            .map(|time| {
+                let hex = format!("{:016X}", counter(time));
+                (time as u64, WaveValue::Bus(hex.into()))
+            })
+            .collect::<Vec<_>>()
+    });
+
+    // The same changes as the hex strings bus values used to be kept in
+    let (strings, baseline_bytes, baseline_allocations) = retained(|| {
+        (0..CHANGES)
+            .map(|time| (time as u64, format!("{:016X}", counter(time))))
+            .collect::<Vec<_>>()
+    });
+    assert_eq!(values[CHANGES - 1].1.to_string(), {
+        let hex = &strings[CHANGES - 1].1;
+        u64::from_str_radix(hex, 16).unwrap().to_string()
+    });
+
+    // Each string is an allocation of its own, while the values are kept inline
+    assert!(baseline_allocations >= CHANGES as isize);
+    assert!(allocations < 100);
+    // A quarter less, from the 16 bytes of hex digits no longer allocated for each change
+    assert!(
+        bytes * 4 <= baseline_bytes * 3,
+        "{} bytes kept, {} bytes as strings",
+        bytes,
+        baseline_bytes
+    );
+}
+
+#[test]
+fn test_parsed_bus_changes_are_not_allocated_one_by_one() {
+    let _counting = COUNTING.lock().unwrap_or_else(PoisonError::into_inner);
+    let changes = 10_000;
+    let mut vcd = String::from(
+        "$timescale 1ns $end\n$scope module top $end\n$var wire 64 ! count[63:0] $end\n\
+         $upscope $end\n$enddefinitions $end\n",
+    );
+    for time in 0..changes {
+        writeln!(vcd, "#{}\nb{:b} !", time, counter(time)).unwrap();
+    }
+
+    let (data, _, allocations) =
+        retained(|| VcdParser::new().parse_reader(vcd.as_bytes()).unwrap());
+    let values = &data.values[&data.signals[0]];
+    assert_eq!(values.len(), changes);
+    assert_eq!(
+        values[1].1,
+        WaveValue::Bus(format!("{:016X}", counter(1)).into())
+    );
+    assert!(
+        allocations < 100,
+        "{} allocations kept for {} changes",
+        allocations,
+        changes
+    );
+}